@@ -0,0 +1,195 @@
+//! Plain-text announcements of game events for screen readers.
+//!
+//! Each announcement is one sentence. It is shown on the bottom line of the
+//! screen, where a screen reader can follow it, and optionally written as a
+//! line to the stdin of a speech command such as `espeak`.
+
+use std::io::Write;
+use std::process::{Child, ChildStdin, Command, Stdio};
+
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::widgets::{Clear, Paragraph};
+use ratatui::Frame;
+use uuid::Uuid;
+
+use yaht_common::dice::DiceSet;
+use yaht_common::protocol::ServerMessage;
+use yaht_common::scoring;
+
+use crate::app::Screen;
+use crate::ui::game::{GameScreen, RoundStandings};
+
+pub struct Announcer {
+    last: Option<String>,
+    speech: Option<(Child, ChildStdin)>,
+}
+
+impl Announcer {
+    /// An announcer that only shows the announcement line, plus a speech
+    /// command run through the shell if given.
+    pub fn new(command: Option<&str>) -> anyhow::Result<Self> {
+        let speech = match command {
+            Some(command) => {
+                let mut child = Command::new("sh")
+                    .arg("-c")
+                    .arg(command)
+                    .stdin(Stdio::piped())
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null())
+                    .spawn()
+                    .map_err(|e| anyhow::anyhow!("cannot start '{}': {}", command, e))?;
+                let stdin = child.stdin.take().expect("stdin is piped");
+                Some((child, stdin))
+            }
+            None => None,
+        };
+        Ok(Self { last: None, speech })
+    }
+
+    pub fn say(&mut self, text: String) {
+        if let Some((_, stdin)) = &mut self.speech {
+            if writeln!(stdin, "{}", text).and_then(|_| stdin.flush()).is_err() {
+                tracing::warn!("Speech command exited; announcements are on screen only");
+                self.speech = None;
+            }
+        }
+        self.last = Some(text);
+    }
+
+    /// Draw the most recent announcement over the bottom line of the screen.
+    pub fn draw(&self, frame: &mut Frame) {
+        let Some(text) = &self.last else {
+            return;
+        };
+        let area = frame.area();
+        if area.height == 0 {
+            return;
+        }
+        let line = Rect::new(area.x, area.y + area.height - 1, area.width, 1);
+        frame.render_widget(Clear, line);
+        frame.render_widget(
+            Paragraph::new(text.as_str()).style(Style::default().fg(Color::White).bg(Color::Rgb(40, 40, 60))),
+            line,
+        );
+    }
+}
+
+impl Drop for Announcer {
+    fn drop(&mut self) {
+        if let Some((mut child, stdin)) = self.speech.take() {
+            drop(stdin);
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+/// What to announce for a server message, given the screen before the
+/// message is applied.
+pub fn describe(msg: &ServerMessage, screen: &Screen, me: Option<Uuid>) -> Option<String> {
+    let game = match screen {
+        Screen::Game(s) => Some(s),
+        _ => None,
+    };
+    let name_of = |id: Uuid| {
+        game.and_then(|g| g.game_state.players.iter().find(|p| p.id == id))
+            .map(|p| p.name.clone())
+            .unwrap_or_default()
+    };
+
+    match msg {
+        ServerMessage::DiceRolled { dice, rolls_remaining } => {
+            let roller = game?.current_turn_player_id?;
+            if Some(roller) == me {
+                Some(match rolls_remaining {
+                    0 => tr!(SayYouRolledLast, faces(dice)),
+                    n => tr!(SayYouRolled, faces(dice), n),
+                })
+            } else {
+                Some(tr!(SayPlayerRolled, name_of(roller), faces(dice)))
+            }
+        }
+        ServerMessage::TurnStarted {
+            player_id,
+            player_name,
+            turn_number,
+        } => Some(if Some(*player_id) == me {
+            tr!(SayYourTurn, turn_number)
+        } else {
+            tr!(TurnOfRound, player_name, turn_number)
+        }),
+        ServerMessage::CategoryScored {
+            player_id,
+            category,
+            score,
+        } => Some(tr!(ScoredFor, name_of(*player_id), score, category.display_name())),
+        ServerMessage::GameOver {
+            final_scores,
+            winner_ids,
+            ..
+        } => {
+            let winners: Vec<&str> = final_scores
+                .iter()
+                .filter(|(id, _, _)| winner_ids.contains(id))
+                .map(|(_, name, _)| name.as_str())
+                .collect();
+            let top = final_scores
+                .iter()
+                .find(|(id, _, _)| winner_ids.contains(id))
+                .map_or(0, |&(_, _, score)| score);
+            Some(match winners.len() {
+                1 => tr!(SayGameOverWinner, winners[0], top),
+                _ => tr!(SayGameOverDraw, winners.join(", "), top),
+            })
+        }
+        ServerMessage::ChatMessage {
+            sender_name, message, ..
+        } => Some(tr!(SayChat, sender_name, message)),
+        ServerMessage::SystemMessage { message, .. } => Some(message.clone()),
+        ServerMessage::Error { message, .. } => Some(tr!(Error, message)),
+        ServerMessage::PlayerForfeited { player_name, .. } => Some(tr!(Forfeited, player_name)),
+        ServerMessage::RoundStandings {
+            round,
+            totals,
+            leader_ids,
+        } => Some(RoundStandings::new(*round, totals, leader_ids).summary()),
+        ServerMessage::PlayerJoined { player_name, .. } => Some(tr!(JoinedRoom, player_name)),
+        ServerMessage::PlayerLeft { player_name, .. } => Some(tr!(LeftRoom, player_name)),
+        ServerMessage::RoomJoined { room_state, .. } => Some(tr!(SayJoinedRoom, room_state.room_name)),
+        _ => None,
+    }
+}
+
+/// The highlighted category and what the dice would score in it.
+pub fn describe_selection(game: &GameScreen) -> Option<String> {
+    let category = game.selected_category()?;
+    Some(match &game.dice {
+        Some(dice) => tr!(
+            SayCategoryPoints,
+            category.display_name(),
+            scoring::compute_score(category, &dice.values())
+        ),
+        None => category.display_name().to_string(),
+    })
+}
+
+/// The dice being held after a toggle.
+pub fn describe_holds(game: &GameScreen) -> Option<String> {
+    let dice = game.dice.as_ref()?;
+    let held: Vec<String> = dice
+        .dice
+        .iter()
+        .filter(|d| d.held)
+        .map(|d| d.value.to_string())
+        .collect();
+    Some(if held.is_empty() {
+        tr!(SayHoldingNone).to_string()
+    } else {
+        tr!(SayHolding, held.join(" "))
+    })
+}
+
+fn faces(dice: &DiceSet) -> String {
+    dice.values().map(|v| v.to_string()).join(" ")
+}
@@ -1,35 +1,79 @@
+use std::collections::HashSet;
 use std::io;
+use std::time::{Duration, Instant};
 
 use ratatui::backend::CrosstermBackend;
 use ratatui::Terminal;
 use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
 use uuid::Uuid;
 
-use yaht_common::dice::MAX_ROLLS;
-use yaht_common::game::TurnPhase;
-use yaht_common::protocol::{ClientMessage, ServerMessage};
+use yaht_common::game::{GameConfig, GamePhase, GameStateSnapshot, TurnPhase};
+use yaht_common::lobby::validate_room_name;
+use yaht_common::player::Scorecard;
+use yaht_common::protocol::{ClientMessage, ErrorCode, ServerMessage, PROTOCOL_VERSION};
+use yaht_common::scoring::ScoringRules;
 
-use crate::event::{self, AppEvent};
+use crate::announce::{self, Announcer};
+use crate::clipboard;
+use crate::commands::{self, ChatCommand};
+use crate::event::{self, AppEvent, Input, RenderGate};
+use crate::graphics;
 use crate::input::{self, Action};
-use crate::network;
+use crate::network::{self, ConnectOptions, PendingActions};
+use crate::presence;
+use crate::presets::{self, RoomPreset};
+use crate::replay::SpectatorReplay;
+use crate::sound::{self, SoundEvent};
+use crate::storage;
+use crate::streamer;
+use crate::title;
 use crate::ui::connect::ConnectScreen;
-use crate::ui::game::GameScreen;
+use crate::ui::debug_overlay::{self, DebugInfo};
+use crate::ui::game::{GameScreen, RoundStandings, MILESTONE_TAG};
 use crate::ui::help_popup;
-use crate::ui::lobby::LobbyScreen;
+use crate::ui::history::HistoryScreen;
+use crate::ui::leaderboard::LeaderboardScreen;
+use crate::ui::lobby::{room_name_error_text, CodePrompt, LobbyScreen, PresetPicker, RoomNamePrompt};
 use crate::ui::results::ResultsScreen;
+use crate::ui::stats::StatsScreen;
+
+/// How long the results stay up before an automatic rematch.
+const REMATCH_DELAY: Duration = Duration::from_secs(10);
 
 #[derive(Debug)]
 pub enum Screen {
     Connect(ConnectScreen),
     Lobby(LobbyScreen),
-    Game(GameScreen),
+    Game(Box<GameScreen>),
     Results(ResultsScreen),
+    Stats(StatsScreen),
+    Leaderboard(LeaderboardScreen),
+    History(HistoryScreen),
+}
+
+impl Screen {
+    /// The variant's name, for the debug overlay.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Screen::Connect(_) => "Connect",
+            Screen::Lobby(_) => "Lobby",
+            Screen::Game(_) => "Game",
+            Screen::Results(_) => "Results",
+            Screen::Stats(_) => "Stats",
+            Screen::Leaderboard(_) => "Leaderboard",
+            Screen::History(_) => "History",
+        }
+    }
 }
 
 pub async fn run(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     default_server: String,
     default_name: Option<String>,
+    connect_options: ConnectOptions,
+    mut announcer: Option<Announcer>,
+    auto_rematch: bool,
 ) -> anyhow::Result<()> {
     let mut connect_screen = ConnectScreen::new();
     connect_screen.host = default_server;
@@ -40,74 +84,295 @@ pub async fn run(
     let mut player_id: Option<Uuid> = None;
     let mut player_name = String::new();
     let mut network_tx: Option<mpsc::Sender<ClientMessage>> = None;
+    let mut server_host = String::new();
+    let mut resume_token: Option<Uuid> = None;
+    // Whether the server wants turns acknowledged (protocol 3 and up)
+    let mut ack_turns = false;
+    let mut connect_task: Option<JoinHandle<()>> = None;
+    let mut pending = PendingActions::default();
     let mut running = true;
     let mut show_help = false;
+    let mut replay: Option<SpectatorReplay> = None;
+    // Players whose chat is hidden, by name
+    let mut muted: HashSet<String> = HashSet::new();
+    let mut room_presets = presets::load();
+    // The preset a room we asked to create is set up from once it is ours
+    let mut creating_from: Option<RoomPreset> = None;
 
     let (local_event_tx, mut event_rx) = mpsc::channel::<AppEvent>(64);
 
     let local_tx = local_event_tx.clone();
-    let mut local_event_handle = Some(tokio::spawn(async move {
-        use crossterm::event::{Event, EventStream};
-        use futures::StreamExt;
-
-        let mut key_stream = EventStream::new();
-        loop {
-            if let Some(Ok(Event::Key(key))) = key_stream.next().await {
-                if local_tx.send(AppEvent::Key(key)).await.is_err() {
-                    break;
-                }
+    let mut keys = event::spawn_key_reader();
+    tokio::spawn(async move {
+        while let Some(input) = keys.recv().await {
+            let event = match input {
+                Input::Key(key) => AppEvent::Key(key),
+                Input::Resize => AppEvent::Resize,
+            };
+            if local_tx.send(event).await.is_err() {
+                break;
             }
         }
-    }));
+    });
+
+    let mut render = RenderGate::new();
+    let mut next_tick = tokio::time::Instant::now();
+    let mut tick_rate = event::tick_rate(false);
+    let mut show_debug = false;
+    let mut render_time = Duration::ZERO;
 
     while running {
-        terminal.draw(|frame| {
-            match &screen {
-                Screen::Connect(s) => s.draw(frame),
-                Screen::Lobby(s) => s.draw(frame),
-                Screen::Game(s) => s.draw(frame),
-                Screen::Results(s) => s.draw(frame),
-            }
-            // Overlay help popup if active
-            if show_help {
-                help_popup::draw_help_popup(frame);
-            }
-        })?;
-
-        let event = match event_rx.recv().await {
-            Some(e) => e,
-            None => break,
+        if render.should_draw(terminal)? {
+            if let Screen::Game(s) = &screen {
+                streamer::mirror(s);
+                presence::show_game(s);
+            } else {
+                presence::clear();
+            }
+            title::set(&window_title(&screen, player_id));
+            let started = Instant::now();
+            terminal.draw(|frame| {
+                match &screen {
+                    Screen::Connect(s) => s.draw(frame),
+                    Screen::Lobby(s) => s.draw(frame),
+                    Screen::Game(s) => s.draw(frame),
+                    Screen::Results(s) => s.draw(frame),
+                    Screen::Stats(s) => s.draw(frame),
+                    Screen::Leaderboard(s) => s.draw(frame),
+                    Screen::History(s) => s.draw(frame),
+                }
+                if let Some(a) = &announcer {
+                    a.draw(frame);
+                }
+                // Overlay help popup if active
+                if show_help {
+                    let scoring = match &screen {
+                        Screen::Game(s) => s.game_state.rules.scoring,
+                        _ => ScoringRules::default(),
+                    };
+                    help_popup::draw_help_popup(frame, &scoring);
+                }
+                if show_debug {
+                    let info = DebugInfo {
+                        screen: screen.name(),
+                        event_queue: event_rx.len(),
+                        outgoing_queue: network_tx.as_ref().map(|tx| tx.max_capacity() - tx.capacity()),
+                        pending_actions: pending.count(),
+                        tick_rate,
+                        render_time,
+                    };
+                    debug_overlay::draw_debug_overlay(frame, &info);
+                }
+            })?;
+            render_time = started.elapsed();
+            graphics::flush(terminal, &mut render, show_help || show_debug)?;
+        }
+
+        // Tick quickly only while something is animating
+        let animating = replay.is_some()
+            || match &screen {
+                Screen::Game(g) => g.is_animating(),
+                Screen::Results(r) => r.is_animating(),
+                _ => false,
+            };
+        tick_rate = event::tick_rate(animating);
+        next_tick = next_tick.min(tokio::time::Instant::now() + tick_rate);
+
+        let event = tokio::select! {
+            e = event_rx.recv() => match e {
+                Some(e) => e,
+                None => break,
+            },
+            _ = tokio::time::sleep_until(next_tick) => {
+                next_tick = tokio::time::Instant::now() + tick_rate;
+                AppEvent::Tick
+            }
         };
 
         // If help is shown, any key dismisses it
-        if show_help {
-            if matches!(&event, AppEvent::Key(_)) {
-                show_help = false;
+        if show_help && matches!(&event, AppEvent::Key(_)) {
+            show_help = false;
+            render.mark_dirty();
+            continue;
+        }
+        // Likewise for the dice stats popup
+        if let (Screen::Game(s), AppEvent::Key(_)) = (&mut screen, &event) {
+            if s.dice_stats.take().is_some() {
+                render.mark_dirty();
                 continue;
             }
         }
+        // Any key keeps an automatic rematch from taking us off the results
+        if let (Screen::Results(s), AppEvent::Key(_)) = (&mut screen, &event) {
+            s.decline_rematch();
+        }
 
-        let chat_focused = matches!(&screen, Screen::Game(g) if g.chat_focused);
+        let chat_focused = match &screen {
+            Screen::Game(g) => g.chat_focused,
+            Screen::Lobby(l) => l.command.is_some(),
+            _ => false,
+        };
         let action = match &event {
             AppEvent::Key(key) => input::map_key(*key, &screen, chat_focused),
+            AppEvent::Network(ServerMessage::ReplayStarted { event_count }) => {
+                replay = Some(SpectatorReplay::new(*event_count));
+                None
+            }
             AppEvent::Network(msg) => {
-                let outbound = handle_server_message(msg.clone(), &mut screen, &mut player_id, &mut player_name);
-                if let Some(ref tx) = network_tx {
-                    for out_msg in outbound {
-                        let _ = tx.send(out_msg).await;
+                render.mark_dirty();
+                if let ServerMessage::Welcome { resume_token: token, resumed, protocol, .. } = msg {
+                    // Older servers cannot resume sessions or track actions
+                    resume_token = token.filter(|_| *protocol >= 2);
+                    ack_turns = *protocol >= 3;
+                    // Whatever was not acknowledged before the connection
+                    // dropped goes again; the server skips what it applied
+                    if *resumed {
+                        send_all(&network_tx, pending.resend()).await;
+                    } else {
+                        pending.clear();
                     }
                 }
+                if let ServerMessage::ActionAck { request_id } = msg {
+                    pending.ack(*request_id);
+                }
+                if let Some(ref mut r) = replay {
+                    r.push(msg.clone());
+                } else {
+                    announce_with(&mut announcer, announce::describe(msg, &screen, player_id));
+                    let outbound = handle_server_message(msg.clone(), &mut screen, &mut player_id, &player_name, &muted);
+                    send_all(&network_tx, outbound).await;
+                }
+                match msg {
+                    ServerMessage::RoomJoined { .. } => {
+                        if let Some(preset) = creating_from.take() {
+                            send_all(&network_tx, vec![preset.settings_message()]).await;
+                        }
+                    }
+                    // Connected again from elsewhere: leave the seat to
+                    // that client instead of reconnecting to take it back
+                    ServerMessage::Error {
+                        code: code @ ErrorCode::SessionTaken,
+                        message,
+                    } => {
+                        creating_from = None;
+                        replay = None;
+                        screen = back_to_connect(&server_host, &player_name, error_text(code, message));
+                    }
+                    ServerMessage::Error { .. } => creating_from = None,
+                    // Nothing of a finished game is worth resending
+                    ServerMessage::GameOver { .. } => pending.clear(),
+                    // Tell the server how the game looks from here, so it
+                    // can set us straight if we missed something
+                    ServerMessage::TurnStarted { .. } if ack_turns && replay.is_none() => {
+                        if let Screen::Game(s) = &screen {
+                            let state = &s.game_state;
+                            if let Some(current) = state.players.get(state.current_player_index) {
+                                let ack = ClientMessage::AckTurn {
+                                    round: state.round,
+                                    player_id: current.id,
+                                    checksum: state.checksum(),
+                                };
+                                send_all(&network_tx, vec![ack]).await;
+                            }
+                        }
+                    }
+                    ServerMessage::RoomUpdate { .. } if auto_rematch => {
+                        if let Screen::Results(s) = &mut screen {
+                            s.offer_rematch(REMATCH_DELAY);
+                        }
+                    }
+                    _ => {}
+                }
                 None
             }
-            AppEvent::Tick => {
-                if let Screen::Game(s) = &mut screen {
-                    s.tick();
+            AppEvent::Disconnected => {
+                render.mark_dirty();
+                network_tx = None;
+                match &mut screen {
+                    // Players mid-game try to get their seat back
+                    Screen::Game(s) if !s.is_spectator() && resume_token.is_some() => {
+                        s.reconnecting = true;
+                        s.queued_actions = pending.count();
+                        connect_task = Some(spawn_connect(
+                            server_host.clone(),
+                            connect_options.clone(),
+                            hello(&player_name, resume_token),
+                            true,
+                            local_event_tx.clone(),
+                        ));
+                    }
+                    // e.g. after a handshake error, which is already shown
+                    Screen::Connect(s) => s.connecting = false,
+                    _ => {
+                        replay = None;
+                        screen = back_to_connect(&server_host, &player_name, tr!(ConnectionLost).to_string());
+                    }
                 }
                 None
             }
+            AppEvent::Connected(result) => {
+                render.mark_dirty();
+                connect_task = None;
+                // Finished just as it was cancelled
+                let cancelled = matches!(&screen, Screen::Connect(s) if !s.connecting);
+                match result {
+                    _ if cancelled => {}
+                    Ok(tx) => network_tx = Some(tx.clone()),
+                    Err(e) => match &mut screen {
+                        Screen::Connect(s) => {
+                            s.connecting = false;
+                            s.error_message = Some(tr!(ConnectionFailed, e));
+                        }
+                        _ => screen = back_to_connect(&server_host, &player_name, tr!(ConnectionFailed, e)),
+                    },
+                }
+                None
+            }
+            AppEvent::Resize => {
+                render.mark_dirty();
+                None
+            }
+            AppEvent::Tick => {
+                // Keep the debug overlay's figures current
+                if show_debug {
+                    render.mark_dirty();
+                }
+                // Play back the spectator catch-up at a readable pace
+                if let Some(ref mut r) = replay {
+                    for msg in r.due() {
+                        render.mark_dirty();
+                        let outbound = handle_server_message(msg, &mut screen, &mut player_id, &player_name, &muted);
+                        send_all(&network_tx, outbound).await;
+                    }
+                    let done = r.is_done();
+                    if let Screen::Game(s) = &mut screen {
+                        s.replay_progress = if done { None } else { Some(r.progress()) };
+                        if done {
+                            s.chat_messages.push(format!("[System] {}", tr!(CaughtUp)));
+                        }
+                    }
+                    if done {
+                        replay = None;
+                        render.mark_dirty();
+                    }
+                }
+                let changed = match &mut screen {
+                    Screen::Game(s) => s.tick(),
+                    Screen::Results(s) => s.tick(),
+                    _ => false,
+                };
+                if changed {
+                    render.mark_dirty();
+                }
+                match &screen {
+                    Screen::Results(s) if s.rematch_due() => Some(Action::PlayAgain),
+                    _ => None,
+                }
+            }
         };
 
         if let Some(action) = action {
+            render.mark_dirty();
             match action {
                 Action::Quit => {
                     if let Some(ref tx) = network_tx {
@@ -120,9 +385,23 @@ pub async fn run(
                     show_help = !show_help;
                 }
 
+                Action::ToggleDebug => {
+                    show_debug = !show_debug;
+                }
+
                 Action::TypeChar(c) => match &mut screen {
                     Screen::Connect(s) => s.type_char(c),
                     Screen::Game(s) if s.chat_focused => s.chat_input.push(c),
+                    Screen::Lobby(LobbyScreen { name_prompt: Some(prompt), .. }) => {
+                        prompt.input.push(c);
+                        prompt.rejected = None;
+                    }
+                    Screen::Lobby(LobbyScreen { code_prompt: Some(prompt), .. }) => {
+                        prompt.input.push(c);
+                        prompt.unknown = false;
+                    }
+                    Screen::Lobby(LobbyScreen { password_prompt: Some(prompt), .. }) => prompt.input.push(c),
+                    Screen::Lobby(LobbyScreen { command: Some(input), .. }) => input.push(c),
                     _ => {}
                 },
                 Action::Backspace => match &mut screen {
@@ -130,6 +409,20 @@ pub async fn run(
                     Screen::Game(s) if s.chat_focused => {
                         s.chat_input.pop();
                     }
+                    Screen::Lobby(LobbyScreen { name_prompt: Some(prompt), .. }) => {
+                        prompt.input.pop();
+                        prompt.rejected = None;
+                    }
+                    Screen::Lobby(LobbyScreen { code_prompt: Some(prompt), .. }) => {
+                        prompt.input.pop();
+                        prompt.unknown = false;
+                    }
+                    Screen::Lobby(LobbyScreen { password_prompt: Some(prompt), .. }) => {
+                        prompt.input.pop();
+                    }
+                    Screen::Lobby(LobbyScreen { command: Some(input), .. }) => {
+                        input.pop();
+                    }
                     _ => {}
                 },
                 Action::SwitchField => {
@@ -140,39 +433,30 @@ pub async fn run(
                 Action::Submit => {
                     if let Screen::Connect(s) = &mut screen {
                         if s.name.is_empty() {
-                            s.error_message = Some("Please enter a name".into());
+                            s.error_message = Some(tr!(EnterName).into());
                             continue;
                         }
                         s.connecting = true;
                         s.error_message = None;
                         player_name = s.name.clone();
-
-                        match network::connect(&s.host).await {
-                            Ok((tx, rx)) => {
-                                let _ = tx
-                                    .send(ClientMessage::Hello {
-                                        player_name: s.name.clone(),
-                                        version: env!("CARGO_PKG_VERSION").to_string(),
-                                    })
-                                    .await;
-
-                                network_tx = Some(tx);
-
-                                if let Some(handle) = local_event_handle.take() {
-                                    handle.abort();
-                                }
-
-                                let (full_event_tx, full_event_rx) =
-                                    mpsc::channel::<AppEvent>(64);
-                                event_rx = full_event_rx;
-
-                                tokio::spawn(event::event_loop(rx, full_event_tx));
-                            }
-                            Err(e) => {
-                                s.connecting = false;
-                                s.error_message = Some(format!("Connection failed: {}", e));
-                            }
-                        }
+                        server_host = s.host.clone();
+                        connect_task = Some(spawn_connect(
+                            server_host.clone(),
+                            connect_options.clone(),
+                            hello(&player_name, None),
+                            false,
+                            local_event_tx.clone(),
+                        ));
+                    }
+                }
+                Action::CancelConnect => {
+                    if let Some(task) = connect_task.take() {
+                        task.abort();
+                    }
+                    network_tx = None;
+                    if let Screen::Connect(s) = &mut screen {
+                        s.connecting = false;
+                        s.error_message = Some(tr!(ConnectCancelled).into());
                     }
                 }
 
@@ -181,50 +465,234 @@ pub async fn run(
                         let _ = tx.send(ClientMessage::ListRooms).await;
                     }
                 }
+                Action::CyclePace => {
+                    if let Screen::Lobby(s) = &mut screen {
+                        s.cycle_new_room_pace();
+                    }
+                }
                 Action::CreateRoom => {
-                    if let Some(ref tx) = network_tx {
-                        let _ = tx
-                            .send(ClientMessage::CreateRoom {
-                                room_name: format!("{}'s room", player_name),
-                                max_players: 6,
-                                password: None,
-                            })
-                            .await;
+                    if let Screen::Lobby(s) = &mut screen {
+                        s.name_prompt = Some(Box::new(RoomNamePrompt {
+                            input: tr!(DefaultRoomName, player_name),
+                            rejected: None,
+                            sent: false,
+                        }));
+                    }
+                }
+                Action::SubmitRoomName => {
+                    if let Screen::Lobby(s) = &mut screen {
+                        let Some(prompt) = s.name_prompt.as_mut() else {
+                            continue;
+                        };
+                        if prompt.sent || prompt.problem().is_some() {
+                            continue;
+                        }
+                        if let Some(ref tx) = network_tx {
+                            prompt.sent = true;
+                            let _ = tx
+                                .send(ClientMessage::CreateRoom {
+                                    room_name: prompt.input.trim().to_string(),
+                                    max_players: 6,
+                                    password: None,
+                                    rules: s.new_room_pace.apply(GameConfig::default()),
+                                })
+                                .await;
+                        }
+                    }
+                }
+                Action::CancelRoomName => {
+                    if let Screen::Lobby(s) = &mut screen {
+                        s.name_prompt = None;
                     }
                 }
                 Action::JoinSelected => {
-                    if let Screen::Lobby(s) = &screen {
-                        if let Some(room_id) = s.selected_room_id() {
-                            if let Some(ref tx) = network_tx {
-                                let _ = tx.send(ClientMessage::JoinRoom { room_id, password: None }).await;
+                    if let Screen::Lobby(s) = &mut screen {
+                        if let (Some(room_id), Some(tx)) = (s.selected_room_id(), &network_tx) {
+                            if let Some(msg) = s.join(room_id) {
+                                let _ = tx.send(msg).await;
                             }
                         }
                     }
                 }
-                Action::SpectateSelected => {
+                Action::OpenJoinByCode => {
+                    if let Screen::Lobby(s) = &mut screen {
+                        s.code_prompt = Some(Box::new(CodePrompt::default()));
+                    }
+                }
+                Action::JoinByCode => {
+                    if let Screen::Lobby(s) = &mut screen {
+                        let Some(code) = s.code_prompt.as_ref().map(|p| p.input.clone()) else {
+                            continue;
+                        };
+                        match s.room_for_code(&code) {
+                            Some(room_id) => {
+                                s.code_prompt = None;
+                                if let (Some(msg), Some(tx)) = (s.join(room_id), &network_tx) {
+                                    let _ = tx.send(msg).await;
+                                }
+                            }
+                            None => {
+                                if let Some(prompt) = s.code_prompt.as_mut() {
+                                    prompt.unknown = true;
+                                }
+                            }
+                        }
+                    }
+                }
+                Action::CancelJoinByCode => {
+                    if let Screen::Lobby(s) = &mut screen {
+                        s.code_prompt = None;
+                    }
+                }
+                Action::Paste => {
+                    if let Screen::Lobby(LobbyScreen { code_prompt: Some(prompt), .. }) = &mut screen {
+                        if let Some(text) = clipboard::paste() {
+                            prompt.input.push_str(text.trim());
+                            prompt.unknown = false;
+                        }
+                    }
+                }
+                Action::CopyRoomCode => {
+                    if let Screen::Lobby(LobbyScreen { joined_room: Some(room), status_message, .. }) = &mut screen {
+                        let code = room.room_id.to_string();
+                        *status_message = Some(match clipboard::copy(&code) {
+                            Ok(()) => tr!(CodeCopied, code),
+                            Err(e) => tr!(CopyFailed, e),
+                        });
+                    }
+                }
+                Action::AcceptWatch => {
+                    if let Screen::Lobby(s) = &mut screen {
+                        if let (Some(room_id), Some(tx)) = (s.watch_offer.take(), &network_tx) {
+                            // Joining again seats us if the room has since
+                            // opened up, and has the server seat us as a
+                            // spectator otherwise
+                            let _ = tx
+                                .send(ClientMessage::JoinRoom {
+                                    room_id,
+                                    password: None,
+                                    spectate_if_started: true,
+                                })
+                                .await;
+                        }
+                    }
+                }
+                Action::DeclineWatch => {
+                    if let Screen::Lobby(s) = &mut screen {
+                        s.watch_offer = None;
+                    }
+                }
+                Action::SubmitPassword => {
+                    if let Screen::Lobby(s) = &mut screen {
+                        if let (Some(prompt), Some(tx)) = (s.password_prompt.take(), &network_tx) {
+                            s.joining = Some(prompt.room_id);
+                            let _ = tx
+                                .send(ClientMessage::JoinRoom {
+                                    room_id: prompt.room_id,
+                                    password: Some(prompt.input),
+                                    spectate_if_started: false,
+                                })
+                                .await;
+                        }
+                    }
+                }
+                Action::OpenPresets => {
+                    if let Screen::Lobby(s) = &mut screen {
+                        if room_presets.is_empty() {
+                            s.status_message = Some(tr!(NoPresets).to_string());
+                        } else {
+                            s.preset_picker = Some(Box::new(PresetPicker {
+                                names: room_presets.iter().map(|p| p.name.clone()).collect(),
+                                selected: 0,
+                            }));
+                        }
+                    }
+                }
+                Action::CreateFromPreset => {
+                    if let Screen::Lobby(s) = &mut screen {
+                        let preset = s.preset_picker.take().and_then(|p| room_presets.get(p.selected));
+                        if let (Some(preset), Some(tx)) = (preset, &network_tx) {
+                            if let Err(e) = validate_room_name(&preset.room_name(&player_name)) {
+                                s.status_message = Some(room_name_error_text(e));
+                                continue;
+                            }
+                            let _ = tx.send(preset.create_message(&player_name)).await;
+                            creating_from = Some(preset.clone());
+                        }
+                    }
+                }
+                Action::ClosePresets => {
+                    if let Screen::Lobby(s) = &mut screen {
+                        s.preset_picker = None;
+                    }
+                }
+                Action::CancelPassword => {
+                    if let Screen::Lobby(s) = &mut screen {
+                        s.password_prompt = None;
+                    }
+                }
+                Action::DismissNotice => {
+                    if let Screen::Lobby(s) = &mut screen {
+                        s.kicked = None;
+                    }
+                }
+                Action::SpectateSelected | Action::SpectateFromStart => {
                     if let Screen::Lobby(s) = &screen {
                         if let Some(room_id) = s.selected_room_id() {
                             if let Some(ref tx) = network_tx {
-                                let _ =
-                                    tx.send(ClientMessage::SpectateRoom { room_id }).await;
+                                let catch_up = matches!(action, Action::SpectateFromStart);
+                                let _ = tx
+                                    .send(ClientMessage::SpectateRoom { room_id, catch_up })
+                                    .await;
                             }
                         }
                     }
                 }
+                Action::SkipReplay => {
+                    if let Some(ref mut r) = replay {
+                        r.skip();
+                    }
+                }
+                Action::CycleFocus => {
+                    if let Screen::Game(s) = &mut screen {
+                        if s.is_spectator() {
+                            s.cycle_focus();
+                            announce_with(&mut announcer, Some(s.focus_label()));
+                        }
+                    }
+                }
+                Action::ToggleLeaderGap => {
+                    if let Screen::Game(s) = &mut screen {
+                        s.show_leader_gap = !s.show_leader_gap;
+                    }
+                }
+                Action::ShowDiceStats => {
+                    if let Some(ref tx) = network_tx {
+                        let _ = tx.send(ClientMessage::GetDiceStats).await;
+                    }
+                }
                 Action::NavigateUp => match &mut screen {
                     Screen::Lobby(s) => s.select_prev(),
-                    Screen::Game(s) => s.select_prev_category(),
+                    Screen::History(s) => s.select_prev(),
+                    Screen::Game(s) => {
+                        s.select_prev_category();
+                        announce_with(&mut announcer, announce::describe_selection(s));
+                    }
                     _ => {}
                 },
                 Action::NavigateDown => match &mut screen {
                     Screen::Lobby(s) => s.select_next(),
-                    Screen::Game(s) => s.select_next_category(),
+                    Screen::History(s) => s.select_next(),
+                    Screen::Game(s) => {
+                        s.select_next_category();
+                        announce_with(&mut announcer, announce::describe_selection(s));
+                    }
                     _ => {}
                 },
 
                 Action::RollDice => {
-                    if let Some(ref tx) = network_tx {
-                        let _ = tx.send(ClientMessage::RollDice).await;
+                    if let Screen::Game(s) = &mut screen {
+                        send_game_action(&network_tx, &mut pending, resume_token.is_some(), s, ClientMessage::RollDice).await;
                     }
                 }
                 Action::ToggleHold(idx) => {
@@ -232,38 +700,71 @@ pub async fn run(
                         if let Some(ref pid) = player_id {
                             if s.is_my_turn(pid) {
                                 s.toggle_hold(idx);
-                                if let Some(ref tx) = network_tx {
-                                    let held = s.get_held_array();
-                                    let _ = tx.send(ClientMessage::HoldDice { held }).await;
-                                }
+                                announce_with(&mut announcer, announce::describe_holds(s));
+                                let held = s.get_held_array();
+                                send_game_action(&network_tx, &mut pending, resume_token.is_some(), s, ClientMessage::HoldDice { held }).await;
                             }
                         }
                     }
                 }
                 Action::ConfirmScore => {
-                    if let Screen::Game(s) = &screen {
-                        if let Some(cat) = s.selected_category() {
-                            if let Some(ref tx) = network_tx {
-                                let _ = tx
-                                    .send(ClientMessage::ScoreCategory { category: cat })
-                                    .await;
-                            }
+                    if let Screen::Game(s) = &mut screen {
+                        if let Some(cat) = s.selected_category().filter(|&cat| !s.confirm_zero(cat)) {
+                            send_game_action(&network_tx, &mut pending, resume_token.is_some(), s, ClientMessage::ScoreCategory { category: cat }).await;
                         }
                     }
                 }
-                Action::ToggleChatFocus => {
-                    if let Screen::Game(s) = &mut screen {
-                        s.chat_focused = !s.chat_focused;
+                Action::ToggleChatFocus => match &mut screen {
+                    Screen::Game(s) => s.chat_focused = !s.chat_focused,
+                    Screen::Lobby(s) => {
+                        s.command = match s.command {
+                            Some(_) => None,
+                            None => Some("/".into()),
+                        }
                     }
-                }
+                    _ => {}
+                },
                 Action::SendChat => {
-                    if let Screen::Game(s) = &mut screen {
-                        if !s.chat_input.is_empty() {
-                            let msg = s.chat_input.drain(..).collect::<String>();
+                    let input = match &mut screen {
+                        Screen::Game(s) => std::mem::take(&mut s.chat_input),
+                        Screen::Lobby(s) => s.command.take().unwrap_or_default(),
+                        _ => String::new(),
+                    };
+                    if input.trim().is_empty() {
+                        continue;
+                    }
+                    let msg = match commands::parse(&input) {
+                        None => Some(ClientMessage::Chat { message: input }),
+                        Some(Ok(command)) => run_command(command, &mut screen, &mut muted, &mut room_presets),
+                        Some(Err(text)) => {
+                            chat_notice(&mut screen, text);
+                            None
+                        }
+                    };
+                    match (msg, &mut screen) {
+                        (Some(msg), Screen::Game(s)) => {
+                            send_game_action(&network_tx, &mut pending, resume_token.is_some(), s, msg).await;
+                        }
+                        (Some(msg), _) => {
                             if let Some(ref tx) = network_tx {
-                                let _ = tx.send(ClientMessage::Chat { message: msg }).await;
+                                let _ = tx.send(msg).await;
                             }
                         }
+                        (None, _) => {}
+                    }
+                }
+                Action::CompleteChat => {
+                    let (players, spectators) = room_people(&screen);
+                    let names: Vec<String> = players.into_iter().map(|(_, name)| name).chain(spectators).collect();
+                    let input = match &mut screen {
+                        Screen::Game(s) => Some(&mut s.chat_input),
+                        Screen::Lobby(s) => s.command.as_mut(),
+                        _ => None,
+                    };
+                    if let Some(input) = input {
+                        if let Some(completed) = commands::complete(input, &names) {
+                            *input = completed;
+                        }
                     }
                 }
                 Action::StartGame => {
@@ -271,24 +772,116 @@ pub async fn run(
                         let _ = tx.send(ClientMessage::StartGame).await;
                     }
                 }
-                Action::LeaveRoom => {
-                    if let Some(ref tx) = network_tx {
-                        let _ = tx.send(ClientMessage::LeaveRoom).await;
-                        let _ = tx.send(ClientMessage::ListRooms).await;
+                Action::ChangeSetting(setting) => {
+                    if let (Screen::Lobby(s), Some(tx)) = (&screen, &network_tx) {
+                        if let Some(msg) = s.change_setting(setting) {
+                            let _ = tx.send(msg).await;
+                        }
+                    }
+                }
+                Action::MoveSeat(delta) => {
+                    if let (Screen::Lobby(s), Some(tx)) = (&screen, &network_tx) {
+                        if let Some(msg) = s.move_seat(delta) {
+                            let _ = tx.send(msg).await;
+                        }
                     }
+                }
+                Action::LeaveRoom => {
+                    replay = None;
                     if let Screen::Lobby(s) = &mut screen {
+                        s.leaving = leave_room(&network_tx).await;
                         s.joined_room = None;
+                        s.auto_start_in = None;
                         s.status_message = None;
+                        s.command = None;
                     }
                 }
 
-                Action::BackToLobby => {
+                Action::ShowLeaderboard => {
                     if let Some(ref tx) = network_tx {
-                        let _ = tx.send(ClientMessage::LeaveRoom).await;
-                        let _ = tx.send(ClientMessage::ListRooms).await;
+                        let _ = tx.send(ClientMessage::GetLeaderboard { season: None }).await;
+                    }
+                }
+                Action::PreviousSeason | Action::NextSeason => {
+                    if let Screen::Leaderboard(s) = &screen {
+                        let season = if matches!(action, Action::PreviousSeason) {
+                            s.previous_season()
+                        } else {
+                            s.next_season()
+                        };
+                        if let (Some(season), Some(ref tx)) = (season, &network_tx) {
+                            let _ = tx
+                                .send(ClientMessage::GetLeaderboard { season: Some(season) })
+                                .await;
+                        }
                     }
+                }
+
+                Action::ShowHistory => {
+                    if let Some(ref tx) = network_tx {
+                        let _ = tx
+                            .send(ClientMessage::GetRecentGames {
+                                player: Some(player_name.clone()),
+                            })
+                            .await;
+                    }
+                }
+                Action::OpenGame => {
+                    if let (Screen::History(s), Some(tx)) = (&screen, &network_tx) {
+                        if let Some(game_id) = s.selected_game_id() {
+                            let _ = tx.send(ClientMessage::GetGame { game_id }).await;
+                        }
+                    }
+                }
+                Action::CloseGame => {
+                    if let Screen::History(s) = &mut screen {
+                        s.open = None;
+                    }
+                }
+                Action::ReplayStep(forward) => {
+                    if let Screen::History(s) = &mut screen {
+                        s.step(forward);
+                    }
+                }
+
+                Action::ShowStats => {
+                    screen = Screen::Stats(StatsScreen::new(player_name.clone()));
+                    if let Some(ref tx) = network_tx {
+                        let _ = tx
+                            .send(ClientMessage::GetPlayerStats {
+                                player: player_name.clone(),
+                            })
+                            .await;
+                    }
+                }
+
+                Action::CopyScoreboard => {
+                    if let Screen::Results(results) = &mut screen {
+                        if let Some(scoreboard) = results.scoreboard() {
+                            let notice = match crate::share::scoreboard(&scoreboard) {
+                                Ok(path) => format!("Scoreboard copied, and saved to {}", path.display()),
+                                Err(e) => format!("Could not save the scoreboard: {}", e),
+                            };
+                            results.notify(notice);
+                        }
+                    }
+                }
+                Action::PlayAgain => {
+                    if let Screen::Results(results) = &mut screen {
+                        if let Some(room) = results.room.take() {
+                            replay = None;
+                            let mut lobby = LobbyScreen::new(player_name.clone());
+                            lobby.player_id = player_id;
+                            lobby.joined_room = Some(*room);
+                            screen = Screen::Lobby(lobby);
+                        }
+                    }
+                }
+                Action::BackToLobby => {
+                    replay = None;
                     let mut lobby = LobbyScreen::new(player_name.clone());
                     lobby.player_id = player_id;
+                    lobby.leaving = leave_room(&network_tx).await;
                     screen = Screen::Lobby(lobby);
                 }
 
@@ -299,30 +892,419 @@ pub async fn run(
     Ok(())
 }
 
+/// Leave the room we're in, if any. The server confirms with `RoomLeft`
+/// either way, which brings a fresh room list. Returns whether the request
+/// went out.
+async fn leave_room(network_tx: &Option<mpsc::Sender<ClientMessage>>) -> bool {
+    match network_tx {
+        Some(tx) => tx.send(ClientMessage::LeaveRoom).await.is_ok(),
+        None => false,
+    }
+}
+
+async fn send_all(network_tx: &Option<mpsc::Sender<ClientMessage>>, outbound: Vec<ClientMessage>) {
+    if let Some(ref tx) = network_tx {
+        for out_msg in outbound {
+            let _ = tx.send(out_msg).await;
+        }
+    }
+}
+
+/// Send a game action, tracked until the server acknowledges it, or hold
+/// it back to send once a dropped connection has been resumed.
+async fn send_game_action(
+    network_tx: &Option<mpsc::Sender<ClientMessage>>,
+    pending: &mut PendingActions,
+    tracked: bool,
+    screen: &mut GameScreen,
+    msg: ClientMessage,
+) {
+    // Servers without resume tokens predate request ids too
+    let msg = if tracked { pending.track(msg) } else { msg };
+    screen.queued_actions = pending.count();
+    if screen.reconnecting {
+        return;
+    }
+    if let Some(ref tx) = network_tx {
+        let _ = tx.send(msg).await;
+    }
+}
+
+fn hello(player_name: &str, resume_token: Option<Uuid>) -> ClientMessage {
+    ClientMessage::Hello {
+        player_name: player_name.to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        identity: Some(storage::identity()),
+        resume_token,
+        protocol: PROTOCOL_VERSION,
+    }
+}
+
+/// Connect and say hello in the background, so the screen stays live and
+/// the attempt can be cancelled. With `retry`, keeps trying with backoff
+/// after a dropped connection.
+fn spawn_connect(
+    host: String,
+    options: ConnectOptions,
+    hello: ClientMessage,
+    retry: bool,
+    event_tx: mpsc::Sender<AppEvent>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let result = if retry {
+            network::reconnect(&host, &options, hello).await
+        } else {
+            network::handshake(&host, &options, hello).await
+        };
+        match result {
+            Ok((tx, rx, reply)) => {
+                // The sender must be in place before the Welcome is handled,
+                // since that replays the queued actions
+                let _ = event_tx.send(AppEvent::Connected(Ok(tx))).await;
+                let _ = event_tx.send(AppEvent::Network(reply)).await;
+                event::forward_network(rx, event_tx).await;
+            }
+            Err(e) => {
+                let _ = event_tx.send(AppEvent::Connected(Err(format!("{:#}", e)))).await;
+            }
+        }
+    })
+}
+
+/// The window title for `screen`: the room, whose turn it is, and how many
+/// are playing and watching.
+fn window_title(screen: &Screen, player_id: Option<Uuid>) -> String {
+    let mut parts = vec!["YAHT".to_string()];
+    match screen {
+        Screen::Lobby(s) => match &s.joined_room {
+            Some(room) => {
+                parts.push(tr!(TitleRoom, room.room_name));
+                parts.push(tr!(TitlePlayers, room.players.len()));
+                if !room.spectators.is_empty() {
+                    parts.push(tr!(TitleWatching, room.spectators.len()));
+                }
+            }
+            None => parts.push(tr!(TitleLobby).to_string()),
+        },
+        Screen::Game(s) => {
+            if let Some(name) = &s.room_name {
+                parts.push(tr!(TitleRoom, name));
+            }
+            let current = s.game_state.players.get(s.game_state.current_player_index);
+            match current {
+                _ if s.game_state.phase == GamePhase::Finished => parts.push(tr!(TitleGameOver).to_string()),
+                Some(p) if Some(p.id) == player_id => parts.push(tr!(TitleYourTurn).to_string()),
+                Some(p) => parts.push(tr!(TitleTheirTurn, p.name)),
+                None => {}
+            }
+            parts.push(tr!(TitlePlayers, s.game_state.players.len()));
+            if !s.spectators.is_empty() {
+                parts.push(tr!(TitleWatching, s.spectators.len()));
+            }
+        }
+        Screen::Results(s) => {
+            if let Some(room) = &s.room {
+                parts.push(tr!(TitleRoom, room.room_name));
+            }
+            parts.push(tr!(TitleGameOver).to_string());
+        }
+        _ => {}
+    }
+    parts.join(" – ")
+}
+
+/// The game screen, knowing who was already watching from the waiting room.
+fn game_screen(screen: &Screen, player_id: Uuid, game_state: GameStateSnapshot) -> GameScreen {
+    let mut game = GameScreen::new(player_id, game_state);
+    if let Screen::Lobby(LobbyScreen { joined_room: Some(room), .. }) = screen {
+        game.spectators = room.spectators.clone();
+        game.room_name = Some(room.room_name.clone());
+    }
+    game
+}
+
+/// React to an error from the server according to its code.
+fn handle_error(
+    code: ErrorCode,
+    message: String,
+    screen: &mut Screen,
+    player_id: Option<Uuid>,
+    player_name: &str,
+) -> Vec<ClientMessage> {
+    let text = error_text(&code, &message);
+    match (code, &mut *screen) {
+        // Removed from the room: back to the room list, wherever we were,
+        // with a notice to dismiss
+        (ErrorCode::Kicked, Screen::Lobby(s)) => {
+            s.joined_room = None;
+            s.auto_start_in = None;
+            s.kicked = Some(text);
+            vec![ClientMessage::ListRooms]
+        }
+        (ErrorCode::Kicked, Screen::Game(_)) => {
+            let mut lobby = LobbyScreen::new(player_name.to_string());
+            lobby.player_id = player_id;
+            lobby.kicked = Some(text);
+            *screen = Screen::Lobby(lobby);
+            vec![ClientMessage::ListRooms]
+        }
+        (ErrorCode::GameAlreadyStarted, Screen::Lobby(s)) if !s.is_in_room() => {
+            s.watch_offer = s.joining.take();
+            if s.watch_offer.is_none() {
+                s.status_message = Some(text);
+            }
+            Vec::new()
+        }
+        // Keep the name dialog open to pick another
+        (ErrorCode::InvalidRoomName | ErrorCode::RoomNameTaken, Screen::Lobby(s)) if s.name_prompt.is_some() => {
+            if let Some(prompt) = s.name_prompt.as_mut() {
+                prompt.sent = false;
+                prompt.rejected = Some(text);
+            }
+            Vec::new()
+        }
+        (ErrorCode::WrongPassword, Screen::Lobby(s)) if s.joining.is_some() => {
+            s.retry_password();
+            Vec::new()
+        }
+        // Turned away at the door: the room list we chose from is stale
+        (
+            ErrorCode::RoomFull | ErrorCode::RoomNotFound | ErrorCode::RoomLocked | ErrorCode::SpectatorsFull,
+            Screen::Lobby(s),
+        ) => {
+            s.joining = None;
+            s.status_message = Some(text);
+            vec![ClientMessage::ListRooms]
+        }
+        (_, Screen::Lobby(s)) => {
+            s.joining = None;
+            if s.name_prompt.as_ref().is_some_and(|p| p.sent) {
+                s.name_prompt = None;
+            }
+            s.status_message = Some(text);
+            Vec::new()
+        }
+        (ErrorCode::NotYourTurn, Screen::Game(s)) => {
+            s.flash_action_bar();
+            s.status_message = Some(text);
+            Vec::new()
+        }
+        (_, Screen::Game(s)) => {
+            s.status_message = Some(text);
+            Vec::new()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// The error in the user's language. Codes without their own text fall
+/// back to the server's message.
+fn error_text(code: &ErrorCode, message: &str) -> String {
+    let text = match code {
+        ErrorCode::RoomFull => tr!(ErrRoomFull),
+        ErrorCode::RoomNotFound => tr!(ErrRoomNotFound),
+        ErrorCode::NotYourTurn => tr!(ErrNotYourTurn),
+        ErrorCode::CategoryAlreadyScored => tr!(ErrCategoryAlreadyScored),
+        ErrorCode::InvalidCategoryForJoker => tr!(ErrJokerPlacement),
+        ErrorCode::GameAlreadyStarted => tr!(ErrGameAlreadyStarted),
+        ErrorCode::NotEnoughPlayers => tr!(ErrNotEnoughPlayers),
+        ErrorCode::WrongPassword => tr!(ErrWrongPassword),
+        ErrorCode::RoomLocked => tr!(ErrRoomLocked),
+        ErrorCode::SpectatorsFull => tr!(ErrSpectatorsFull),
+        ErrorCode::NotHost => tr!(ErrNotHost),
+        ErrorCode::Kicked => tr!(ErrKicked),
+        ErrorCode::RoomNameTaken => tr!(ErrRoomNameTaken),
+        ErrorCode::SessionTaken => tr!(ErrSessionTaken),
+        ErrorCode::RateLimited => tr!(ErrRateLimited),
+        _ => return tr!(Error, message),
+    };
+    text.to_string()
+}
+
+/// Players in the current room with their ids, and the spectators' names.
+fn room_people(screen: &Screen) -> (Vec<(Uuid, String)>, Vec<String>) {
+    match screen {
+        Screen::Game(s) => (
+            s.game_state.players.iter().map(|p| (p.id, p.name.clone())).collect(),
+            s.spectators.clone(),
+        ),
+        Screen::Lobby(LobbyScreen { joined_room: Some(room), .. }) => (
+            room.players.iter().map(|p| (p.id, p.name.clone())).collect(),
+            room.spectators.clone(),
+        ),
+        _ => (Vec::new(), Vec::new()),
+    }
+}
+
+/// Feedback on a chat command: a line in the chat panel, or the waiting
+/// room's status.
+fn chat_notice(screen: &mut Screen, text: String) {
+    match screen {
+        Screen::Game(s) => {
+            s.chat_messages.push(format!("[System] {}", text));
+        }
+        Screen::Lobby(s) => s.status_message = Some(text),
+        _ => {}
+    }
+}
+
+/// Carry out a chat command, returning the message it sends, if any.
+fn run_command(
+    command: ChatCommand,
+    screen: &mut Screen,
+    muted: &mut HashSet<String>,
+    room_presets: &mut Vec<RoomPreset>,
+) -> Option<ClientMessage> {
+    let (players, spectators) = room_people(screen);
+    // Names are matched ignoring case, and sent as the server knows them
+    let named = |name: &str| {
+        players
+            .iter()
+            .map(|(_, n)| n)
+            .chain(&spectators)
+            .find(|n| n.eq_ignore_ascii_case(name))
+            .cloned()
+    };
+    match command {
+        ChatCommand::Help => {
+            if matches!(screen, Screen::Game(_)) {
+                for line in commands::help_lines() {
+                    chat_notice(screen, line.to_string());
+                }
+            } else {
+                chat_notice(screen, tr!(CmdList, commands::COMMANDS.join(" ")));
+            }
+            None
+        }
+        ChatCommand::Whisper { to, message } => match named(&to) {
+            Some(to) => Some(ClientMessage::Whisper { to, message }),
+            None => {
+                chat_notice(screen, tr!(NoSuchPlayer, to));
+                None
+            }
+        },
+        ChatCommand::Mute(name) => {
+            let name = named(&name).unwrap_or(name);
+            let text = if muted.insert(name.clone()) {
+                tr!(Muted, name)
+            } else {
+                muted.remove(&name);
+                tr!(Unmuted, name)
+            };
+            chat_notice(screen, text);
+            None
+        }
+        ChatCommand::Ready => {
+            let ready = !matches!(screen, Screen::Lobby(s) if s.is_ready());
+            Some(ClientMessage::SetReady { ready })
+        }
+        ChatCommand::Kick(name) => match players.iter().find(|(_, n)| n.eq_ignore_ascii_case(&name)) {
+            Some(&(player_id, _)) => Some(ClientMessage::Kick { player_id }),
+            None => {
+                chat_notice(screen, tr!(NoSuchPlayer, name));
+                None
+            }
+        },
+        ChatCommand::Seed(seed) => Some(ClientMessage::UpdateRoomSettings {
+            max_players: None,
+            password: None,
+            rules: None,
+            locked: None,
+            auto_start: None,
+            max_spectators: None,
+            seed: Some(seed),
+            announcements: None,
+            shuffle_order: None,
+            nudge_secs: None,
+        }),
+        ChatCommand::Preset(name) => {
+            let preset = match screen {
+                Screen::Lobby(LobbyScreen { joined_room: Some(room), player_name, .. }) => {
+                    RoomPreset::from_room(name.clone(), room, player_name)
+                }
+                _ => {
+                    chat_notice(screen, tr!(PresetNeedsRoom).to_string());
+                    return None;
+                }
+            };
+            let text = match presets::save(room_presets, preset) {
+                Ok(()) => tr!(PresetSaved, name),
+                Err(e) => tr!(PresetSaveFailed, e),
+            };
+            chat_notice(screen, text);
+            None
+        }
+    }
+}
+
+/// Back to the connect screen, filled in as before, showing why.
+fn back_to_connect(host: &str, name: &str, error: String) -> Screen {
+    let mut s = ConnectScreen::new();
+    s.host = host.to_string();
+    s.name = name.to_string();
+    s.error_message = Some(error);
+    Screen::Connect(s)
+}
+
+fn announce_with(announcer: &mut Option<Announcer>, text: Option<String>) {
+    if let (Some(a), Some(text)) = (announcer, text) {
+        a.say(text);
+    }
+}
+
 fn handle_server_message(
     msg: ServerMessage,
     screen: &mut Screen,
     player_id: &mut Option<Uuid>,
-    player_name: &mut String,
+    player_name: &str,
+    muted: &HashSet<String>,
 ) -> Vec<ClientMessage> {
     let mut outbound = Vec::new();
 
     match msg {
         ServerMessage::Welcome {
             player_id: pid,
-            server_version: _,
+            server_version,
+            resumed,
+            protocol,
+            ..
         } => {
             *player_id = Some(pid);
-            let mut lobby = LobbyScreen::new(player_name.clone());
-            lobby.player_id = Some(pid);
-            *screen = Screen::Lobby(lobby);
-            outbound.push(ClientMessage::ListRooms);
+            match screen {
+                // Back in the game; the server follows with its state, and
+                // anything queued meanwhile has been sent
+                Screen::Game(s) if resumed => {
+                    s.reconnecting = false;
+                    s.queued_actions = 0;
+                    s.status_message = Some(tr!(Reconnected).into());
+                }
+                _ => {
+                    let mut lobby = LobbyScreen::new(player_name.to_string());
+                    lobby.player_id = Some(pid);
+                    if matches!(screen, Screen::Game(_)) {
+                        lobby.status_message = Some(tr!(ResumeFailed).into());
+                    } else if protocol < PROTOCOL_VERSION {
+                        lobby.status_message = Some(tr!(CompatibilityMode, server_version));
+                    }
+                    *screen = Screen::Lobby(lobby);
+                    outbound.push(ClientMessage::ListRooms);
+                }
+            }
         }
 
-        ServerMessage::HandshakeError { reason } => {
+        ServerMessage::HandshakeError { reason, version_mismatch } => {
             if let Screen::Connect(s) = screen {
                 s.connecting = false;
-                s.error_message = Some(reason);
+                s.error_message = Some(match version_mismatch {
+                    Some(m) => tr!(
+                        ClientTooOld,
+                        m.server_version,
+                        m.min_protocol,
+                        env!("CARGO_PKG_VERSION"),
+                        PROTOCOL_VERSION
+                    ),
+                    None => reason,
+                });
             }
         }
 
@@ -340,29 +1322,50 @@ fn handle_server_message(
             room_state,
         } => {
             if let Screen::Lobby(s) = screen {
+                s.joining = None;
+                s.name_prompt = None;
                 s.status_message = None;
                 s.joined_room = Some(room_state);
             }
         }
 
-        ServerMessage::RoomUpdate { room_state } => {
+        ServerMessage::RoomUpdate { room_state } => match screen {
+            Screen::Lobby(s) if s.joined_room.is_some() => s.joined_room = Some(room_state),
+            // The room is open for another game
+            Screen::Results(s) if room_state.players.iter().any(|p| Some(p.id) == *player_id) => {
+                s.room = Some(Box::new(room_state));
+            }
+            _ => {}
+        },
+
+        ServerMessage::AutoStartCountdown { seconds_left } => {
             if let Screen::Lobby(s) = screen {
-                if s.joined_room.is_some() {
-                    s.joined_room = Some(room_state);
-                }
+                s.auto_start_in = Some(seconds_left);
+            }
+        }
+
+        ServerMessage::AutoStartCancelled => {
+            if let Screen::Lobby(s) = screen {
+                s.auto_start_in = None;
             }
         }
 
         ServerMessage::RoomLeft => {
             if let Screen::Lobby(s) = screen {
+                s.leaving = false;
                 s.joined_room = None;
+                s.auto_start_in = None;
                 outbound.push(ClientMessage::ListRooms);
             }
         }
 
+        // A game the room started before it saw us leave
+        ServerMessage::GameStarted { .. } | ServerMessage::GameState { .. }
+            if matches!(screen, Screen::Lobby(LobbyScreen { leaving: true, .. })) => {}
+
         ServerMessage::GameStarted { game_state } => {
             if let Some(pid) = player_id {
-                *screen = Screen::Game(GameScreen::new(*pid, game_state));
+                *screen = Screen::Game(Box::new(game_screen(screen, *pid, game_state)));
             }
         }
 
@@ -370,7 +1373,7 @@ fn handle_server_message(
             if let Screen::Game(s) = screen {
                 s.update_from_snapshot(game_state);
             } else if let Some(pid) = player_id {
-                *screen = Screen::Game(GameScreen::new(*pid, game_state));
+                *screen = Screen::Game(Box::new(game_screen(screen, *pid, game_state)));
             }
         }
 
@@ -378,22 +1381,32 @@ fn handle_server_message(
             dice,
             rolls_remaining,
         } => {
+            sound::play_roll(dice.values());
             if let Screen::Game(s) = screen {
                 // Start dice rolling animation
-                s.roll_animation = Some(crate::ui::game::RollAnimation::new(dice));
+                s.roll_animation = Some(crate::ui::game::RollAnimation::with_pace(
+                    dice,
+                    s.game_state.rules.pace,
+                ));
                 s.rolls_remaining = rolls_remaining;
                 // Update turn phase
                 s.game_state.turn_phase = if rolls_remaining == 0 {
                     Some(TurnPhase::MustScore)
                 } else {
-                    Some(TurnPhase::Rolling { rolls_used: MAX_ROLLS - rolls_remaining })
+                    Some(TurnPhase::Rolling {
+                        rolls_used: s.game_state.rules.rolls_per_turn.saturating_sub(rolls_remaining),
+                    })
                 };
             }
         }
 
         ServerMessage::DiceHeld { dice } => {
+            // Our own holds are already shown; applying the echo could undo
+            // a toggle made since it was sent
             if let Screen::Game(s) = screen {
-                s.dice = Some(dice);
+                if s.current_turn_player_id != Some(s.my_player_id) {
+                    s.dice = Some(dice);
+                }
             }
         }
 
@@ -414,10 +1427,7 @@ fn handle_server_message(
                     .find(|p| p.id == scored_pid)
                     .map(|p| p.name.clone())
                     .unwrap_or_default();
-                s.status_message = Some(format!(
-                    "{} scored {} for {}",
-                    scorer_name, score, category.display_name()
-                ));
+                s.status_message = Some(tr!(ScoredFor, scorer_name, score, category.display_name()));
                 // Trigger score flash animation
                 s.score_flash = Some((category, score, std::time::Instant::now()));
             }
@@ -429,11 +1439,8 @@ fn handle_server_message(
             turn_number,
         } => {
             if let Screen::Game(s) = screen {
-                // Ring bell if it's my turn
-                let is_my_turn = player_id.map(|pid| pid == turn_pid).unwrap_or(false);
-                if is_my_turn {
-                    // Terminal bell notification
-                    print!("\x07");
+                if *player_id == Some(turn_pid) {
+                    sound::play(SoundEvent::YourTurn);
                 }
 
                 s.current_turn_player_id = Some(turn_pid);
@@ -443,55 +1450,163 @@ fn handle_server_message(
                 }
                 s.round = turn_number;
                 s.game_state.round = turn_number;
-                s.rolls_remaining = MAX_ROLLS;
+                // Past the last round is a sudden-death playoff: one roll each
+                s.rolls_remaining = if turn_number > s.game_state.total_rounds {
+                    1
+                } else {
+                    s.game_state.rules.rolls_per_turn
+                };
                 s.dice = None;
                 s.selected_category_index = 0;
                 s.game_state.turn_phase = Some(TurnPhase::WaitingForRoll);
                 s.reset_turn_timer();
-                s.status_message =
-                    Some(format!("{}'s turn (round {})", turn_name, turn_number));
+                s.stop_time_bank();
+                s.pending_zero = None;
+                s.status_message = Some(tr!(TurnOfRound, turn_name, turn_number));
             }
         }
 
         ServerMessage::TurnEnded { player_id: _ } => {}
 
+        ServerMessage::RoundStandings {
+            round,
+            totals,
+            leader_ids,
+        } => {
+            if let Screen::Game(s) = screen {
+                let standings = RoundStandings::new(round, &totals, &leader_ids);
+                s.chat_messages.push(format!("[System] {}", standings.summary()));
+                s.round_standings = Some(standings);
+            }
+        }
+
+        ServerMessage::TurnTimer {
+            player_id: timed_pid,
+            seconds_left,
+        } => {
+            if let Screen::Game(s) = screen {
+                if s.current_turn_player_id == Some(timed_pid) {
+                    s.set_turn_seconds_left(seconds_left);
+                }
+            }
+        }
+
+        ServerMessage::TimeBank {
+            player_id: timed_pid,
+            seconds_left,
+        } => {
+            if let Screen::Game(s) = screen {
+                s.set_time_bank(timed_pid, seconds_left);
+            }
+        }
+
+        ServerMessage::PlayerForfeited {
+            player_id: forfeited_pid,
+            player_name: name,
+        } => {
+            if let Screen::Game(s) = screen {
+                if let Some(player) = s.game_state.players.iter_mut().find(|p| p.id == forfeited_pid) {
+                    player.forfeited = true;
+                }
+                s.chat_messages
+                    .push(format!("[System] {}", tr!(Forfeited, name)));
+            }
+        }
+
         ServerMessage::GameOver {
             final_scores,
-            winner_id,
+            winner_ids,
+            scorecards,
+            duration_secs,
+            round_totals,
+            average_turn_ms,
         } => {
-            // Bell on game over
-            print!("\x07");
-            *screen = Screen::Results(ResultsScreen::new(final_scores, winner_id));
+            let ids: Vec<Uuid> = final_scores.iter().map(|(id, _, _)| *id).collect();
+            let scorecards: Vec<(Uuid, Scorecard)> = ids.iter().copied().zip(scorecards).collect();
+
+            let mut milestones = Vec::new();
+            if let (Screen::Game(s), Some(pid)) = (&*screen, *player_id) {
+                let score = final_scores.iter().find(|(id, _, _)| *id == pid);
+                let sent = scorecards.iter().find(|(id, _)| *id == pid).map(|(_, card)| card.clone());
+                // Older servers send only totals; fall back to our local copy of
+                // the scorecard and recover the Yahtzee bonuses from the total
+                let local = || {
+                    let me = s.game_state.players.iter().find(|p| p.id == pid)?;
+                    let &(_, _, final_score) = score?;
+                    let mut scorecard = me.scorecard.clone();
+                    scorecard.yahtzee_bonus_count = final_score
+                        .saturating_sub(scorecard.grand_total())
+                        .checked_div(scorecard.rules.yahtzee_bonus)
+                        .unwrap_or(0) as u8;
+                    Some(scorecard)
+                };
+                // A shared first place is neither a win nor a loss
+                let won = match winner_ids.as_slice() {
+                    [winner] => Some(*winner == pid),
+                    winners if winners.contains(&pid) => None,
+                    _ => Some(false),
+                };
+                if let (Some(scorecard), Some(&(_, _, final_score))) = (sent.or_else(local), score) {
+                    milestones = crate::stats::record_game(player_name, &scorecard, final_score, won);
+                }
+            }
+
+            sound::play(SoundEvent::GameOver);
+            let mut results = ResultsScreen::new(final_scores, winner_ids).with_milestones(&milestones);
+            if !scorecards.is_empty() {
+                results = results.with_details(scorecards, duration_secs);
+            }
+            let turn_times: Vec<(Uuid, u64)> = ids
+                .iter()
+                .zip(average_turn_ms)
+                .filter_map(|(&id, ms)| Some((id, ms?)))
+                .collect();
+            if !turn_times.is_empty() {
+                results = results.with_turn_times(turn_times);
+            }
+            if !round_totals.is_empty() {
+                results = results.with_round_totals(ids.into_iter().zip(round_totals).collect());
+            }
+            *screen = Screen::Results(results);
         }
 
         ServerMessage::ChatMessage {
-            sender_id: _,
+            sender_id,
             sender_name,
             message,
             timestamp: _,
+            whisper_to,
         } => {
-            if let Screen::Game(s) = screen {
-                s.chat_messages
-                    .push(format!("{}: {}", sender_name, message));
+            if muted.contains(&sender_name) {
+                return outbound;
             }
-        }
-
-        ServerMessage::SystemMessage { message } => {
-            if let Screen::Game(s) = screen {
-                s.chat_messages.push(format!("[System] {}", message));
+            let line = match whisper_to {
+                Some(to) if *player_id == Some(sender_id) => tr!(WhisperTo, to, message),
+                Some(_) => tr!(WhisperFrom, sender_name, message),
+                None => format!("{}: {}", sender_name, message),
+            };
+            match screen {
+                Screen::Game(s) => {
+                    s.chat_messages.push(line);
+                }
+                Screen::Lobby(s) if s.is_in_room() => s.status_message = Some(line),
+                _ => {}
             }
         }
 
-        ServerMessage::Error { code: _, message } => match screen {
-            Screen::Lobby(s) => {
-                s.status_message = Some(format!("Error: {}", message));
-            }
+        ServerMessage::SystemMessage { message, milestone } => match screen {
             Screen::Game(s) => {
-                s.status_message = Some(format!("Error: {}", message));
+                let tag = if milestone.is_some() { MILESTONE_TAG } else { "[System]" };
+                s.chat_messages.push(format!("{} {}", tag, message));
             }
+            Screen::Lobby(s) if s.is_in_room() => s.status_message = Some(message),
             _ => {}
         },
 
+        ServerMessage::Error { code, message } => {
+            outbound.extend(handle_error(code, message, screen, *player_id, player_name));
+        }
+
         ServerMessage::PlayerJoined {
             player_id: joined_pid,
             player_name: name,
@@ -503,13 +1618,15 @@ fn handle_server_message(
                             id: joined_pid,
                             name: name.clone(),
                             connected: true,
+                            ready: false,
+                            head_to_head: None,
                         });
-                        s.status_message = Some(format!("{} joined", name));
+                        s.status_message = Some(tr!(JoinedRoom, name));
                     }
                 }
                 Screen::Game(s) => {
                     s.chat_messages
-                        .push(format!("[System] {} joined the game", name));
+                        .push(format!("[System] {}", tr!(JoinedGame, name)));
                 }
                 _ => {}
             }
@@ -523,12 +1640,12 @@ fn handle_server_message(
                 Screen::Lobby(s) => {
                     if let Some(ref mut room) = s.joined_room {
                         room.players.retain(|p| p.id != left_pid);
-                        s.status_message = Some(format!("{} left", name));
+                        s.status_message = Some(tr!(LeftRoom, name));
                     }
                 }
                 Screen::Game(s) => {
                     s.chat_messages
-                        .push(format!("[System] {} left the game", name));
+                        .push(format!("[System] {}", tr!(LeftGame, name)));
                 }
                 _ => {}
             }
@@ -543,7 +1660,8 @@ fn handle_server_message(
                 }
                 Screen::Game(s) => {
                     s.chat_messages
-                        .push(format!("[System] {} is spectating", name));
+                        .push(format!("[System] {}", tr!(Spectating, name)));
+                    s.spectators.push(name);
                 }
                 _ => {}
             }
@@ -558,13 +1676,70 @@ fn handle_server_message(
                 }
                 Screen::Game(s) => {
                     s.chat_messages
-                        .push(format!("[System] {} stopped spectating", name));
+                        .push(format!("[System] {}", tr!(StoppedSpectating, name)));
+                    s.spectators.retain(|n| n != &name);
                 }
                 _ => {}
             }
         }
 
+        ServerMessage::Leaderboard {
+            season,
+            current_season,
+            ended_at,
+            standings,
+        } => {
+            if matches!(screen, Screen::Lobby(_) | Screen::Leaderboard(_)) {
+                *screen = Screen::Leaderboard(LeaderboardScreen {
+                    season,
+                    current_season,
+                    ended_at,
+                    standings,
+                    my_name: player_name.to_string(),
+                });
+            }
+        }
+
+        ServerMessage::PlayerStats {
+            player,
+            categories,
+            turn_times,
+        } => {
+            if let Screen::Stats(s) = screen {
+                if s.player_name == player {
+                    s.server = Some(categories);
+                    s.turn_times = Some(turn_times);
+                }
+            }
+        }
+
+        ServerMessage::RecentGames { games } => {
+            if matches!(screen, Screen::Lobby(_) | Screen::History(_)) {
+                *screen = Screen::History(HistoryScreen::new(games, player_name.to_string()));
+            }
+        }
+
+        ServerMessage::GameDetails { game } => {
+            if let Screen::History(s) = screen {
+                s.open_game(game);
+            }
+        }
+
+        ServerMessage::DiceStats { game, room } => {
+            if let Screen::Game(s) = screen {
+                s.dice_stats = Some(Box::new((game, room)));
+            }
+        }
+
         ServerMessage::Pong => {}
+
+        // Tracked by the pending actions before reaching here
+        ServerMessage::ActionAck { .. } => {}
+
+        // Unwrapped by the replay buffer before reaching here
+        ServerMessage::ReplayStarted { .. }
+        | ServerMessage::ReplayEvent { .. }
+        | ServerMessage::ReplayFinished => {}
     }
 
     outbound
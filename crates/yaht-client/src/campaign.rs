@@ -0,0 +1,188 @@
+use std::io;
+
+use crossterm::event::KeyCode;
+use ratatui::backend::CrosstermBackend;
+use ratatui::Terminal;
+use serde::{Deserialize, Serialize};
+
+use yaht_common::ai::AiDifficulty;
+use yaht_common::game::GameConfig;
+
+use crate::event::{self, Input, RenderGate};
+use crate::solo::{self, AiOpponent, SoloOutcome, AI_NAMES};
+use crate::storage;
+use crate::ui::campaign::CampaignSummaryScreen;
+
+const PROGRESS_FILE: &str = "campaign.json";
+pub const STARTING_LIVES: u8 = 3;
+
+/// A campaign run in progress.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CampaignRun {
+    pub stage: u32,
+    pub lives: u8,
+    pub total_score: u32,
+    pub wins: u32,
+}
+
+impl CampaignRun {
+    pub fn new() -> Self {
+        Self {
+            stage: 1,
+            lives: STARTING_LIVES,
+            total_score: 0,
+            wins: 0,
+        }
+    }
+
+    /// Apply a finished game: a win advances to the next stage, a loss costs a life.
+    pub fn record_game(&mut self, score: u16, won: bool) {
+        self.total_score += score as u32;
+        if won {
+            self.wins += 1;
+            self.stage += 1;
+        } else {
+            self.lives = self.lives.saturating_sub(1);
+        }
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.lives == 0
+    }
+}
+
+impl Default for CampaignRun {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Campaign progress persisted between sessions.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CampaignProgress {
+    /// The unfinished run, resumed on the next `--campaign` launch.
+    pub current: Option<CampaignRun>,
+    pub best_stage: u32,
+    pub best_total_score: u32,
+    pub runs_completed: u32,
+}
+
+impl CampaignProgress {
+    fn finish_run(&mut self, run: &CampaignRun) {
+        self.best_stage = self.best_stage.max(run.stage);
+        self.best_total_score = self.best_total_score.max(run.total_score);
+        self.runs_completed += 1;
+        self.current = None;
+    }
+}
+
+/// AI lineup for a campaign stage; opponents get stronger and more numerous.
+pub fn lineup(stage: u32) -> Vec<AiOpponent> {
+    let (count, difficulty) = match stage {
+        0 | 1 => (1, AiDifficulty::Easy),
+        2 => (2, AiDifficulty::Easy),
+        3 => (1, AiDifficulty::Medium),
+        4 => (2, AiDifficulty::Medium),
+        5 => (1, AiDifficulty::Hard),
+        6 => (2, AiDifficulty::Hard),
+        n => ((n - 4).min(AI_NAMES.len() as u32) as usize, AiDifficulty::Hard),
+    };
+    (0..count)
+        .map(|i| AiOpponent {
+            name: AI_NAMES[i % AI_NAMES.len()].to_string(),
+            difficulty,
+        })
+        .collect()
+}
+
+pub async fn run_campaign(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    player_name: String,
+) -> anyhow::Result<()> {
+    let mut keys = event::spawn_key_reader();
+    let mut progress: CampaignProgress = storage::load(PROGRESS_FILE);
+    let mut run = progress.current.take().unwrap_or_default();
+
+    loop {
+        let opponents = lineup(run.stage);
+        let intro = format!(
+            "[System] Campaign stage {} - lives: {}, total: {}",
+            run.stage, run.lives, run.total_score
+        );
+        let outcome = solo::play_game(
+            terminal,
+            &mut keys,
+            player_name.clone(),
+            &opponents,
+            &intro,
+            GameConfig::default(),
+        )
+        .await?;
+
+        let (human_id, final_scores, winner_ids) = match outcome {
+            SoloOutcome::Finished {
+                human_id,
+                final_scores,
+                winner_ids,
+                ..
+            } => (human_id, final_scores, winner_ids),
+            SoloOutcome::Quit => {
+                // Keep the run so it can be resumed next time
+                progress.current = Some(run);
+                storage::save(PROGRESS_FILE, &progress)?;
+                return Ok(());
+            }
+        };
+
+        let stage_played = run.stage;
+        let human_score = final_scores
+            .iter()
+            .find(|(id, _, _)| *id == human_id)
+            .map(|(_, _, score)| *score)
+            .unwrap_or(0);
+        let won = winner_ids == [human_id];
+        run.record_game(human_score, won);
+
+        if run.is_over() {
+            progress.finish_run(&run);
+        } else {
+            progress.current = Some(run.clone());
+        }
+        storage::save(PROGRESS_FILE, &progress)?;
+
+        let summary = CampaignSummaryScreen {
+            stage_played,
+            won,
+            final_scores,
+            winner_ids,
+            run: run.clone(),
+            next_lineup: lineup(run.stage),
+            best_stage: progress.best_stage,
+            best_total_score: progress.best_total_score,
+        };
+
+        let mut render = RenderGate::new();
+        loop {
+            if render.should_draw(terminal)? {
+                terminal.draw(|frame| summary.draw(frame))?;
+            }
+            let key = tokio::select! {
+                k = keys.recv() => match k {
+                    Some(Input::Key(key)) => key,
+                    // Redrawn at the new size on the way round
+                    Some(Input::Resize) => continue,
+                    None => return Ok(()),
+                },
+                // Idle ticks catch resizes and resuming from Ctrl+Z
+                _ = tokio::time::sleep(event::tick_rate(false)) => continue,
+            };
+            render.mark_dirty();
+            match key.code {
+                KeyCode::Enter if !run.is_over() => break,
+                KeyCode::Enter | KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                _ => {}
+            }
+        }
+    }
+}
+
@@ -0,0 +1,49 @@
+//! Copy and paste. Built with the `clipboard` feature, both go through the
+//! system clipboard. Without it, copying asks the terminal to set the
+//! clipboard with the OSC 52 escape sequence, which terminals without
+//! support ignore, and pasting is left to the terminal's own shortcut.
+
+#[cfg(feature = "clipboard")]
+use std::sync::Mutex;
+
+/// Kept open for the life of the client: on X11 and Wayland, copied text
+/// is only served while the clipboard that set it is alive.
+#[cfg(feature = "clipboard")]
+static CLIPBOARD: Mutex<Option<arboard::Clipboard>> = Mutex::new(None);
+
+#[cfg(feature = "clipboard")]
+fn with_clipboard<T>(f: impl FnOnce(&mut arboard::Clipboard) -> Result<T, arboard::Error>) -> anyhow::Result<T> {
+    let mut clipboard = CLIPBOARD.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+    if clipboard.is_none() {
+        *clipboard = Some(arboard::Clipboard::new()?);
+    }
+    Ok(f(clipboard.as_mut().expect("opened above"))?)
+}
+
+#[cfg(feature = "clipboard")]
+pub fn copy(text: &str) -> anyhow::Result<()> {
+    with_clipboard(|c| c.set_text(text))
+}
+
+#[cfg(not(feature = "clipboard"))]
+pub fn copy(text: &str) -> anyhow::Result<()> {
+    use base64::Engine;
+    use std::io::Write;
+
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    let mut stdout = std::io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", encoded)?;
+    stdout.flush()?;
+    Ok(())
+}
+
+/// Text on the clipboard, if there is any we can read.
+#[cfg(feature = "clipboard")]
+pub fn paste() -> Option<String> {
+    with_clipboard(|c| c.get_text()).ok()
+}
+
+#[cfg(not(feature = "clipboard"))]
+pub fn paste() -> Option<String> {
+    None
+}
@@ -0,0 +1,112 @@
+//! Slash commands typed into the chat box.
+//!
+//! A line starting with `/` is parsed into a [`ChatCommand`] instead of
+//! being sent as chat. Tab completes the word under the cursor: a command
+//! name at the start of the line, a player's name anywhere else.
+
+/// A command typed into the chat box.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChatCommand {
+    Help,
+    Whisper { to: String, message: String },
+    /// Hide, or show again, a player's chat. Never leaves the client.
+    Mute(String),
+    /// Toggle ready in the waiting room.
+    Ready,
+    Kick(String),
+    Seed(u64),
+    /// Save the waiting room's settings as a preset with this name.
+    Preset(String),
+}
+
+/// Every command, as typed.
+pub const COMMANDS: [&str; 7] = ["/help", "/w", "/mute", "/ready", "/kick", "/seed", "/preset"];
+
+/// One line of help per command.
+pub fn help_lines() -> [&'static str; 7] {
+    [
+        tr!(CmdHelp),
+        tr!(CmdWhisper),
+        tr!(CmdMute),
+        tr!(CmdReady),
+        tr!(CmdKick),
+        tr!(CmdSeed),
+        tr!(CmdPreset),
+    ]
+}
+
+/// The command in `input`, or `None` if it is plain chat. An unknown
+/// command or a missing argument gives the text to show instead.
+pub fn parse(input: &str) -> Option<Result<ChatCommand, String>> {
+    let rest = input.trim_start().strip_prefix('/')?;
+    let (name, args) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    let args = args.trim();
+    let (first, rest) = args.split_once(char::is_whitespace).unwrap_or((args, ""));
+    let usage = |line: &str| Err(tr!(CmdUsage, line.split(" - ").next().unwrap_or(line)));
+
+    Some(match name.to_ascii_lowercase().as_str() {
+        "help" | "?" => Ok(ChatCommand::Help),
+        "w" | "whisper" | "msg" if !first.is_empty() && !rest.trim().is_empty() => Ok(ChatCommand::Whisper {
+            to: first.to_string(),
+            message: rest.trim().to_string(),
+        }),
+        "w" | "whisper" | "msg" => usage(tr!(CmdWhisper)),
+        "mute" if !first.is_empty() => Ok(ChatCommand::Mute(first.to_string())),
+        "mute" => usage(tr!(CmdMute)),
+        "ready" => Ok(ChatCommand::Ready),
+        "kick" if !first.is_empty() => Ok(ChatCommand::Kick(first.to_string())),
+        "kick" => usage(tr!(CmdKick)),
+        "seed" => match first.parse() {
+            Ok(seed) => Ok(ChatCommand::Seed(seed)),
+            Err(_) => usage(tr!(CmdSeed)),
+        },
+        "preset" if !args.is_empty() => Ok(ChatCommand::Preset(args.to_string())),
+        "preset" => usage(tr!(CmdPreset)),
+        _ => Err(tr!(CmdUnknown, format!("/{}", name))),
+    })
+}
+
+/// `input` with its last word completed from the command names, if it is
+/// the first word and starts with `/`, or else from `names`. Several
+/// matches complete as far as they agree. `None` if nothing matches.
+pub fn complete(input: &str, names: &[String]) -> Option<String> {
+    let start = input.rfind(char::is_whitespace).map_or(0, |i| i + 1);
+    let (head, word) = input.split_at(start);
+    if word.is_empty() {
+        return None;
+    }
+    let command = head.trim().is_empty() && word.starts_with('/');
+    let candidates: Vec<&str> = if command {
+        COMMANDS.iter().copied().filter(|c| starts_with_ignore_case(c, word)).collect()
+    } else {
+        names
+            .iter()
+            .map(String::as_str)
+            .filter(|n| starts_with_ignore_case(n, word))
+            .collect()
+    };
+    let completed = match candidates.as_slice() {
+        [] => return None,
+        [only] => format!("{} ", only),
+        [first, others @ ..] => {
+            let shared = others.iter().fold(first.len(), |len, other| common_prefix(&first[..len], other));
+            if shared <= word.len() {
+                return None;
+            }
+            first[..shared].to_string()
+        }
+    };
+    Some(format!("{}{}", head, completed))
+}
+
+fn starts_with_ignore_case(text: &str, prefix: &str) -> bool {
+    text.get(..prefix.len()).is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+}
+
+/// Length in bytes of the longest prefix `a` and `b` share, ignoring case.
+fn common_prefix(a: &str, b: &str) -> usize {
+    a.char_indices()
+        .zip(b.chars())
+        .find(|&((_, x), y)| !x.eq_ignore_ascii_case(&y))
+        .map_or_else(|| a.len().min(b.len()), |((i, _), _)| i)
+}
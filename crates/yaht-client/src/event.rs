@@ -1,40 +1,137 @@
+use std::io;
 use std::time::Duration;
 
-use crossterm::event::{Event, EventStream, KeyEvent};
-use futures::StreamExt;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::backend::Backend;
+use ratatui::layout::Size;
+use ratatui::Terminal;
 use tokio::sync::mpsc;
 
-use yaht_common::protocol::ServerMessage;
+use yaht_common::protocol::{ClientMessage, ServerMessage};
+
+use crate::graphics;
+use crate::keylog;
+use crate::suspend;
+
+/// Tick rate while something on screen is animating.
+const ANIMATION_TICK: Duration = Duration::from_millis(50);
+/// Tick rate otherwise; frequent enough for the turn timer and resizes.
+const IDLE_TICK: Duration = Duration::from_millis(250);
 
 #[derive(Debug)]
 pub enum AppEvent {
     Key(KeyEvent),
+    /// The terminal changed size.
+    Resize,
     Network(ServerMessage),
+    /// The connection to the server closed.
+    Disconnected,
+    /// A connect or reconnect finished its handshake, with the new
+    /// connection's sender, or failed. The server's answer follows as a
+    /// `Network` event.
+    Connected(Result<mpsc::Sender<ClientMessage>, String>),
     Tick,
 }
 
-pub async fn event_loop(
-    mut network_rx: mpsc::Receiver<ServerMessage>,
-    event_tx: mpsc::Sender<AppEvent>,
-) {
-    let mut key_stream = EventStream::new();
-    let mut tick_interval = tokio::time::interval(Duration::from_millis(50));
-
-    loop {
-        let event = tokio::select! {
-            Some(Ok(Event::Key(key))) = key_stream.next() => {
-                AppEvent::Key(key)
-            }
-            Some(msg) = network_rx.recv() => {
-                AppEvent::Network(msg)
+/// What the terminal reports that the screens act on.
+#[derive(Debug, Clone, Copy)]
+pub enum Input {
+    Key(KeyEvent),
+    /// The terminal changed size; the next frame is laid out anew.
+    Resize,
+}
+
+/// Spawn a task forwarding key presses and resizes: first any played back from
+/// `--play-input`, then the terminal's. Each is recorded if
+/// `--record-input` was given. Ctrl+Z suspends the client instead.
+pub fn spawn_key_reader() -> mpsc::Receiver<Input> {
+    let (key_tx, key_rx) = mpsc::channel::<Input>(64);
+    let script = keylog::take_script();
+    tokio::spawn(async move {
+        use crossterm::event::{Event, EventStream};
+        use futures::StreamExt;
+
+        for (delay, key) in script {
+            tokio::time::sleep(delay).await;
+            keylog::record(&key);
+            if key_tx.send(Input::Key(key)).await.is_err() {
+                return;
             }
-            _ = tick_interval.tick() => {
-                AppEvent::Tick
+        }
+        let mut key_stream = EventStream::new();
+        loop {
+            let input = match key_stream.next().await {
+                Some(Ok(Event::Key(key))) => {
+                    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('z') {
+                        suspend::suspend();
+                        continue;
+                    }
+                    keylog::record(&key);
+                    Input::Key(key)
+                }
+                Some(Ok(Event::Resize(..))) => Input::Resize,
+                _ => continue,
+            };
+            if key_tx.send(input).await.is_err() {
+                break;
             }
-        };
+        }
+    });
+    key_rx
+}
+
+/// How long to wait for the next tick.
+pub fn tick_rate(animating: bool) -> Duration {
+    if animating {
+        ANIMATION_TICK
+    } else {
+        IDLE_TICK
+    }
+}
+
+/// Tracks whether the screen needs redrawing, so frames are only rendered
+/// after something changed or the terminal was resized.
+#[derive(Debug, Default)]
+pub struct RenderGate {
+    clean: bool,
+    size: Option<Size>,
+}
+
+impl RenderGate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn mark_dirty(&mut self) {
+        self.clean = false;
+    }
+
+    /// Whether to draw a frame now. Clears the dirty flag. After the client
+    /// was suspended, the terminal is cleared so the frame is drawn in full.
+    pub fn should_draw<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> io::Result<bool> {
+        if suspend::take_resumed() {
+            terminal.clear()?;
+            graphics::forget_shown();
+            self.clean = false;
+        }
+        let size = terminal.size()?;
+        let resized = self.size.replace(size) != Some(size);
+        if resized {
+            // Drawing at a new size clears the screen, pictures and all
+            graphics::forget_shown();
+        }
+        let dirty = !self.clean || resized;
+        self.clean = true;
+        Ok(dirty)
+    }
+}
 
-        if event_tx.send(event).await.is_err() {
-            break;
+/// Pass server messages on as events, then report the connection closed.
+pub async fn forward_network(mut network_rx: mpsc::Receiver<ServerMessage>, event_tx: mpsc::Sender<AppEvent>) {
+    while let Some(msg) = network_rx.recv().await {
+        if event_tx.send(AppEvent::Network(msg)).await.is_err() {
+            return;
         }
     }
+    let _ = event_tx.send(AppEvent::Disconnected).await;
 }
@@ -0,0 +1,450 @@
+//! Dice drawn as pictures on terminals that speak the Kitty graphics or
+//! Sixel protocol.
+//!
+//! The text widget still lays out the dice area: with pictures on, the
+//! game screen leaves each die's box blank and notes where it is, and once
+//! the frame is on screen the pictures are written over the blanks. Built
+//! without the `graphics` feature, or on any other terminal, the text dice
+//! are all there is.
+
+use std::io;
+
+use ratatui::{backend::Backend, layout::Rect, Terminal};
+
+use crate::event::RenderGate;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum GraphicsMode {
+    /// Pictures if the terminal looks like it can show them.
+    #[default]
+    Auto,
+    /// The Kitty graphics protocol; needs the `graphics` feature.
+    Kitty,
+    /// Sixel images; needs the `graphics` feature.
+    Sixel,
+    Off,
+}
+
+/// How a die is drawn, matching the text widget's colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DieLook {
+    Plain,
+    Held,
+    Rolling,
+}
+
+/// Pick the picture protocol. Only the first call has any effect; until
+/// then, and if it is never called, there are no pictures.
+#[cfg(feature = "graphics")]
+pub fn init(mode: GraphicsMode) {
+    let protocol = match mode {
+        GraphicsMode::Auto => imp::detect(|key| std::env::var(key).ok()),
+        GraphicsMode::Kitty => Some(imp::Protocol::Kitty),
+        GraphicsMode::Sixel => Some(imp::Protocol::Sixel),
+        GraphicsMode::Off => None,
+    };
+    // Sixels are drawn in pixels, so without the cell size there is no
+    // telling how big to make them
+    let protocol = protocol.filter(|&p| {
+        let known = p != imp::Protocol::Sixel || imp::cell_size().is_some();
+        if !known {
+            tracing::warn!("The terminal did not report its cell size; drawing dice as text");
+        }
+        known
+    });
+    if let Some(protocol) = protocol {
+        let _ = imp::GRAPHICS.set(std::sync::Mutex::new(imp::Graphics::new(protocol)));
+    }
+}
+
+#[cfg(not(feature = "graphics"))]
+pub fn init(mode: GraphicsMode) {
+    if matches!(mode, GraphicsMode::Kitty | GraphicsMode::Sixel) {
+        tracing::warn!("This client was built without the graphics feature; drawing dice as text");
+    }
+}
+
+/// Whether dice are drawn as pictures.
+#[cfg(feature = "graphics")]
+pub fn active() -> bool {
+    imp::GRAPHICS.get().is_some()
+}
+
+#[cfg(not(feature = "graphics"))]
+pub fn active() -> bool {
+    false
+}
+
+/// Draw a die showing `value` over `area` once the frame being drawn is on
+/// screen.
+#[cfg(feature = "graphics")]
+pub fn place_die(area: Rect, value: u8, look: DieLook) {
+    if let Some(graphics) = imp::GRAPHICS.get() {
+        imp::lock(graphics).queued.push(imp::Placement { area, value, look });
+    }
+}
+
+#[cfg(not(feature = "graphics"))]
+pub fn place_die(_area: Rect, _value: u8, _look: DieLook) {}
+
+/// Bring the pictures on screen in line with the dice placed while drawing
+/// the last frame, or take them all down if `hidden`, e.g. under a popup.
+/// Call after each `terminal.draw` of the game screen and of whatever
+/// screen follows it.
+#[cfg(feature = "graphics")]
+pub fn flush<B: Backend>(terminal: &mut Terminal<B>, render: &mut RenderGate, hidden: bool) -> io::Result<()> {
+    let Some(graphics) = imp::GRAPHICS.get() else {
+        return Ok(());
+    };
+    let mut graphics = imp::lock(graphics);
+    let placed = std::mem::take(&mut graphics.queued);
+    let wanted = if hidden { Vec::new() } else { placed };
+    if wanted == graphics.shown {
+        return Ok(());
+    }
+    match graphics.show(wanted)? {
+        imp::Shown::Done => Ok(()),
+        imp::Shown::NeedsRedraw => {
+            // Repainting every cell is the only way to wipe sixels; the
+            // dice still wanted go back up after the next frame
+            terminal.clear()?;
+            render.mark_dirty();
+            Ok(())
+        }
+    }
+}
+
+/// Forget the pictures on screen after it was wiped, e.g. on resuming from
+/// Ctrl+Z, so the next flush puts them all back.
+#[cfg(feature = "graphics")]
+pub fn forget_shown() {
+    if let Some(graphics) = imp::GRAPHICS.get() {
+        imp::lock(graphics).shown.clear();
+    }
+}
+
+#[cfg(not(feature = "graphics"))]
+pub fn forget_shown() {}
+
+#[cfg(not(feature = "graphics"))]
+pub fn flush<B: Backend>(_terminal: &mut Terminal<B>, _render: &mut RenderGate, _hidden: bool) -> io::Result<()> {
+    Ok(())
+}
+
+#[cfg(feature = "graphics")]
+mod imp {
+    use std::collections::HashMap;
+    use std::fmt::Write as _;
+    use std::io::{self, Write as _};
+    use std::sync::{Mutex, MutexGuard, OnceLock, PoisonError};
+
+    use base64::Engine;
+    use ratatui::layout::Rect;
+
+    use super::DieLook;
+
+    /// Cell size to assume when the terminal doesn't say. Kitty scales
+    /// pictures to the cells they're placed on, so only the shape matters.
+    const DEFAULT_CELL: (u16, u16) = (10, 20);
+    /// Base64 bytes per Kitty escape, the most the protocol allows.
+    const KITTY_CHUNK: usize = 4096;
+    /// Removes every placement but keeps the images for reuse.
+    const KITTY_CLEAR: &str = "\x1b_Ga=d,d=a,q=2\x1b\\";
+
+    pub static GRAPHICS: OnceLock<Mutex<Graphics>> = OnceLock::new();
+
+    pub fn lock(graphics: &Mutex<Graphics>) -> MutexGuard<'_, Graphics> {
+        graphics.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Protocol {
+        Kitty,
+        Sixel,
+    }
+
+    /// Guess the protocol from the environment `var` reads.
+    pub fn detect(var: impl Fn(&str) -> Option<String>) -> Option<Protocol> {
+        // Multiplexers drop the escapes or draw them in the wrong place
+        if var("TMUX").is_some() || var("STY").is_some() {
+            return None;
+        }
+        let term = var("TERM").unwrap_or_default();
+        let program = var("TERM_PROGRAM").unwrap_or_default();
+        if var("KITTY_WINDOW_ID").is_some()
+            || matches!(term.as_str(), "xterm-kitty" | "xterm-ghostty")
+            || matches!(program.as_str(), "WezTerm" | "ghostty")
+        {
+            Some(Protocol::Kitty)
+        } else if ["foot", "mlterm", "contour", "yaft"].iter().any(|t| term.starts_with(t))
+            || term.contains("sixel")
+            || program == "iTerm.app"
+        {
+            Some(Protocol::Sixel)
+        } else {
+            None
+        }
+    }
+
+    /// The size of a cell in pixels, if the terminal reports it.
+    pub fn cell_size() -> Option<(u16, u16)> {
+        let size = crossterm::terminal::window_size().ok()?;
+        (size.columns > 0 && size.rows > 0 && size.width > 0 && size.height > 0)
+            .then(|| (size.width / size.columns, size.height / size.rows))
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Placement {
+        pub area: Rect,
+        pub value: u8,
+        pub look: DieLook,
+    }
+
+    /// One picture of a die, at a size in pixels.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    struct Picture {
+        value: u8,
+        look: DieLook,
+        width: u16,
+        height: u16,
+    }
+
+    impl Picture {
+        fn new(placement: &Placement, (cell_width, cell_height): (u16, u16)) -> Self {
+            Self {
+                value: placement.value,
+                look: placement.look,
+                width: placement.area.width * cell_width,
+                height: placement.area.height * cell_height,
+            }
+        }
+    }
+
+    pub enum Shown {
+        Done,
+        /// The text layer has to be repainted before the pictures can be.
+        NeedsRedraw,
+    }
+
+    pub struct Graphics {
+        protocol: Protocol,
+        /// Placed while drawing the current frame.
+        pub queued: Vec<Placement>,
+        /// On screen now.
+        pub shown: Vec<Placement>,
+        /// Images already sent to a Kitty terminal, by the id they were
+        /// sent under.
+        kitty_images: HashMap<Picture, u32>,
+        sixels: HashMap<Picture, String>,
+    }
+
+    impl Graphics {
+        pub fn new(protocol: Protocol) -> Self {
+            Self {
+                protocol,
+                queued: Vec::new(),
+                shown: Vec::new(),
+                kitty_images: HashMap::new(),
+                sixels: HashMap::new(),
+            }
+        }
+
+        pub fn show(&mut self, wanted: Vec<Placement>) -> io::Result<Shown> {
+            let cell = cell_size().unwrap_or(DEFAULT_CELL);
+            let mut out = String::new();
+            match self.protocol {
+                Protocol::Kitty => {
+                    out.push_str(KITTY_CLEAR);
+                    for placement in &wanted {
+                        let id = self.kitty_image(Picture::new(placement, cell), &mut out);
+                        let area = placement.area;
+                        let _ = write!(
+                            out,
+                            "\x1b[{};{}H\x1b_Ga=p,i={},c={},r={},C=1,q=2\x1b\\",
+                            area.y + 1,
+                            area.x + 1,
+                            id,
+                            area.width,
+                            area.height
+                        );
+                    }
+                }
+                Protocol::Sixel => {
+                    if self.shown.iter().any(|s| !wanted.iter().any(|w| w.area == s.area)) {
+                        self.shown.clear();
+                        return Ok(Shown::NeedsRedraw);
+                    }
+                    // A new sixel covers the old one in the same place
+                    for placement in wanted.iter().filter(|p| !self.shown.contains(p)) {
+                        let picture = Picture::new(placement, cell);
+                        let sixel = self.sixels.entry(picture).or_insert_with(|| encode_sixel(&picture));
+                        let _ = write!(out, "\x1b[{};{}H", placement.area.y + 1, placement.area.x + 1);
+                        out.push_str(sixel);
+                    }
+                }
+            }
+            self.shown = wanted;
+            let mut stdout = io::stdout().lock();
+            stdout.write_all(out.as_bytes())?;
+            stdout.flush()?;
+            Ok(Shown::Done)
+        }
+
+        /// The id of `picture` on the terminal, adding the escapes sending
+        /// it to `out` the first time.
+        fn kitty_image(&mut self, picture: Picture, out: &mut String) -> u32 {
+            if let Some(&id) = self.kitty_images.get(&picture) {
+                return id;
+            }
+            let id = self.kitty_images.len() as u32 + 1;
+            self.kitty_images.insert(picture, id);
+
+            let rgba: Vec<u8> = rasterize(&picture)
+                .into_iter()
+                .flat_map(|pixel| match pixel {
+                    Some([r, g, b]) => [r, g, b, 255],
+                    None => [0; 4],
+                })
+                .collect();
+            let data = base64::engine::general_purpose::STANDARD.encode(rgba);
+            let chunks: Vec<&str> = data
+                .as_bytes()
+                .chunks(KITTY_CHUNK)
+                .map(|c| std::str::from_utf8(c).expect("base64 is ASCII"))
+                .collect();
+            for (i, chunk) in chunks.iter().enumerate() {
+                let more = u8::from(i + 1 < chunks.len());
+                if i == 0 {
+                    let _ = write!(
+                        out,
+                        "\x1b_Ga=t,f=32,s={},v={},i={},q=2,m={};{}\x1b\\",
+                        picture.width, picture.height, id, more, chunk
+                    );
+                } else {
+                    let _ = write!(out, "\x1b_Gm={};{}\x1b\\", more, chunk);
+                }
+            }
+            id
+        }
+    }
+
+    /// (body, border) colors; the borders are the text widget's.
+    fn colors(look: DieLook) -> ([u8; 3], [u8; 3]) {
+        match look {
+            DieLook::Plain => ([240, 240, 245], [180, 180, 200]),
+            DieLook::Held => ([255, 225, 140], [255, 180, 50]),
+            DieLook::Rolling => ([190, 235, 255], [100, 200, 255]),
+        }
+    }
+
+    const PIP: [u8; 3] = [35, 35, 50];
+
+    /// Where the pips go on each face, as fractions of the die's side. The
+    /// same layout as the text faces.
+    fn pips(value: u8) -> &'static [(f32, f32)] {
+        const L: f32 = 0.27;
+        const M: f32 = 0.5;
+        const R: f32 = 0.73;
+        match value {
+            1 => &[(M, M)],
+            2 => &[(R, L), (L, R)],
+            3 => &[(R, L), (M, M), (L, R)],
+            4 => &[(L, L), (R, L), (L, R), (R, R)],
+            5 => &[(L, L), (R, L), (M, M), (L, R), (R, R)],
+            6 => &[(L, L), (R, L), (L, M), (R, M), (L, R), (R, R)],
+            _ => &[],
+        }
+    }
+
+    /// The die as rows of pixels, `None` outside it: a rounded square
+    /// centered in the picture, whatever the picture's shape.
+    fn rasterize(picture: &Picture) -> Vec<Option<[u8; 3]>> {
+        let (width, height) = (f32::from(picture.width), f32::from(picture.height));
+        let side = width.min(height) * 0.94;
+        let (left, top) = ((width - side) / 2.0, (height - side) / 2.0);
+        let (center_x, center_y) = (width / 2.0, height / 2.0);
+        let radius = side * 0.18;
+        let border = (side / 24.0).max(1.0);
+        let pip_radius = side * 0.09;
+        let (body, edge) = colors(picture.look);
+        let pips = pips(picture.value);
+
+        let mut pixels = Vec::with_capacity(usize::from(picture.width) * usize::from(picture.height));
+        for y in 0..picture.height {
+            for x in 0..picture.width {
+                let (px, py) = (f32::from(x) + 0.5, f32::from(y) + 0.5);
+                // Signed distance to the rounded square's edge
+                let dx = (px - center_x).abs() - (side / 2.0 - radius);
+                let dy = (py - center_y).abs() - (side / 2.0 - radius);
+                let distance = dx.max(0.0).hypot(dy.max(0.0)) + dx.max(dy).min(0.0) - radius;
+                let on_pip = pips.iter().any(|&(u, v)| {
+                    (px - (left + u * side)).hypot(py - (top + v * side)) <= pip_radius
+                });
+                pixels.push(if distance > 0.0 {
+                    None
+                } else if distance > -border {
+                    Some(edge)
+                } else if on_pip {
+                    Some(PIP)
+                } else {
+                    Some(body)
+                });
+            }
+        }
+        pixels
+    }
+
+    /// The die as a sixel image, leaving the pixels outside it as they are.
+    fn encode_sixel(picture: &Picture) -> String {
+        let pixels = rasterize(picture);
+        let (width, height) = (usize::from(picture.width), usize::from(picture.height));
+        let mut palette: Vec<[u8; 3]> = Vec::new();
+        for color in pixels.iter().flatten() {
+            if !palette.contains(color) {
+                palette.push(*color);
+            }
+        }
+
+        // Pixel aspect 1:1, unset pixels transparent
+        let mut out = format!("\x1bP0;1;0q\"1;1;{};{}", width, height);
+        for (i, [r, g, b]) in palette.iter().enumerate() {
+            let percent = |c: &u8| u32::from(*c) * 100 / 255;
+            let _ = write!(out, "#{};2;{};{};{}", i, percent(r), percent(g), percent(b));
+        }
+        for band in (0..height).step_by(6) {
+            for (i, color) in palette.iter().enumerate() {
+                let columns: Vec<u8> = (0..width)
+                    .map(|x| {
+                        (0..6)
+                            .filter(|row| band + row < height && pixels[(band + row) * width + x] == Some(*color))
+                            .fold(0, |bits, row| bits | 1 << row)
+                    })
+                    .collect();
+                if columns.iter().all(|&bits| bits == 0) {
+                    continue;
+                }
+                let _ = write!(out, "#{}", i);
+                push_run_length(&mut out, &columns);
+                out.push('$');
+            }
+            out.push('-');
+        }
+        out.push_str("\x1b\\");
+        out
+    }
+
+    /// Sixel characters for `columns`, with runs written as `!<count><char>`.
+    fn push_run_length(out: &mut String, columns: &[u8]) {
+        let mut rest = columns;
+        while let Some(&bits) = rest.first() {
+            let run = rest.iter().take_while(|&&b| b == bits).count();
+            let c = char::from(63 + bits);
+            if run > 3 {
+                let _ = write!(out, "!{}{}", run, c);
+            } else {
+                out.extend(std::iter::repeat_n(c, run));
+            }
+            rest = &rest[run..];
+        }
+    }
+}
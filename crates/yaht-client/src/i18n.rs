@@ -0,0 +1,866 @@
+//! User-facing strings, looked up by key in the selected language's catalog.
+//!
+//! Every catalog is an exhaustive match over [`Key`], so adding a key
+//! without translating it fails to compile. Templates mark arguments with
+//! `{}`, filled in order by [`tr!`].
+
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Lang {
+    #[default]
+    En,
+    De,
+}
+
+impl Lang {
+    /// The language named by a POSIX locale such as `de_DE.UTF-8`, if we
+    /// have a catalog for it.
+    pub fn from_locale(locale: &str) -> Option<Self> {
+        match locale.get(..2)?.to_ascii_lowercase().as_str() {
+            "en" => Some(Lang::En),
+            "de" => Some(Lang::De),
+            _ => None,
+        }
+    }
+
+    /// The language of the environment's locale (`LC_ALL`, `LC_MESSAGES`,
+    /// then `LANG`).
+    pub fn from_env() -> Option<Self> {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|v| !v.is_empty())
+            .and_then(|v| Self::from_locale(&v))
+    }
+}
+
+static LANG: OnceLock<Lang> = OnceLock::new();
+
+/// Choose the UI language. Only the first call has any effect.
+pub fn set_lang(lang: Lang) {
+    let _ = LANG.set(lang);
+}
+
+pub fn text(key: Key) -> &'static str {
+    match LANG.get().copied().unwrap_or_default() {
+        Lang::En => en(key),
+        Lang::De => de(key),
+    }
+}
+
+/// Replace each `{}` in `template` with the next argument.
+pub fn fill(template: &str, args: &[&dyn Display]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut args = args.iter();
+    let mut parts = template.split("{}");
+    if let Some(first) = parts.next() {
+        out.push_str(first);
+    }
+    for part in parts {
+        if let Some(arg) = args.next() {
+            out.push_str(&arg.to_string());
+        }
+        out.push_str(part);
+    }
+    out
+}
+
+/// `tr!(Key)` is the string for a key; `tr!(Key, args...)` fills its
+/// template.
+macro_rules! tr {
+    ($key:ident) => {
+        $crate::i18n::text($crate::i18n::Key::$key)
+    };
+    ($key:ident, $($arg:expr),+ $(,)?) => {
+        $crate::i18n::fill(
+            $crate::i18n::text($crate::i18n::Key::$key),
+            &[$(&$arg as &dyn std::fmt::Display),+],
+        )
+    };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    // Help popup
+    HelpTitle,
+    HelpRules,
+    HelpUpperSection,
+    HelpOnesToSixes,
+    HelpOnesToSixesDesc,
+    HelpUpperBonus,
+    HelpUpperBonusDesc,
+    HelpLowerSection,
+    HelpThreeOfAKind,
+    HelpThreeOfAKindDesc,
+    HelpFourOfAKind,
+    HelpFourOfAKindDesc,
+    HelpFullHouse,
+    HelpFullHouseDesc,
+    HelpSmallStraight,
+    HelpSmallStraightDesc,
+    HelpLargeStraight,
+    HelpLargeStraightDesc,
+    HelpYahtzee,
+    HelpYahtzeeDesc,
+    HelpChance,
+    HelpChanceDesc,
+    HelpYahtzeeBonus,
+    HelpYahtzeeBonusDesc,
+    HelpControls,
+    HelpRoll,
+    HelpHold,
+    HelpNavigate,
+    HelpScore,
+    HelpChat,
+    HelpCommands,
+    HelpFocus,
+    HelpDiceStats,
+    HelpLeaderGap,
+    HelpToggleHelp,
+    HelpQuit,
+    HelpClose,
+
+    // Dice stats popup
+    DiceStatsTitle,
+    DiceStatsGame,
+    DiceStatsRoom,
+    DiceStatsClose,
+    FairTooFew,
+    FairLooksFair,
+    FairUnusual,
+
+    // Action bars
+    ActSwitch,
+    ActConnect,
+    ActQuit,
+    ActCancel,
+    ActStartGame,
+    ActLeaveRoom,
+    ActCommand,
+    ActMoveSeat,
+    ActSeats,
+    ActTimer,
+    ActTimeBank,
+    ActOutOfTime,
+    ActSuddenDeath,
+    ActLock,
+    ActUnlock,
+    ActAutoStart,
+    ActShuffleOrder,
+    ActNudge,
+    ActSpectators,
+    ActAnnouncements,
+    ActCreate,
+    ActJoin,
+    ActSpectate,
+    ActWatchFromStart,
+    ActRefresh,
+    ActStats,
+    ActRollLeft,
+    ActHold,
+    ActScore,
+    ActChat,
+    ActSkipToLive,
+    ActFocus,
+
+    // Connect screen
+    ConnectTagline,
+    PlayerName,
+    ServerAddress,
+    Connecting,
+
+    // Lobby
+    WaitingRoom,
+    PlayerCount,
+    HostMarker,
+    ReadyMarker,
+    SettingRolls,
+    SettingTimer,
+    SettingNoTimer,
+    SettingTimeBank,
+    OutOfTimeAutoScore,
+    OutOfTimeForfeit,
+    SettingSuddenDeath,
+    SettingPassword,
+    SettingLocked,
+    SettingAutoStart,
+    SettingShuffleOrder,
+    SettingNudge,
+    SettingSpectators,
+    SettingNoSpectators,
+    SettingAnnounceOff,
+    SettingAnnounceAll,
+    SettingSeed,
+    AutoStartingIn,
+    SpectatorCount,
+    Players,
+    Standings,
+    StandingRecord,
+    WaitingForHost,
+    LobbyWelcome,
+    NoRoomsBefore,
+    NoRoomsAfter,
+    Rooms,
+    ColRoomName,
+    ColPlayers,
+    ColSpectators,
+    ColStatus,
+    ColPace,
+    ActPace,
+    PaceCasual,
+    PaceStandard,
+    PaceBlitz,
+    ConfirmZero,
+    RoomWaiting,
+    RoomInGame,
+    RoomFinished,
+    RoomWatchable,
+    RoomNotWatchable,
+    RoomLocked,
+    RoomPassword,
+    DefaultRoomName,
+
+    // Game screen
+    Round,
+    Turn,
+    Replay,
+    Dice,
+    DiceRolling,
+    WaitingForRoll,
+    RollingDice,
+    WaitingForOthers,
+    FollowingTurn,
+    FocusedOn,
+    Chat,
+
+    // Status and system messages
+    EnterName,
+    ConnectionFailed,
+    ConnectCancelled,
+    ClientTooOld,
+    CompatibilityMode,
+    ConnectionLost,
+    Reconnecting,
+    ActionsQueued,
+    Reconnected,
+    ResumeFailed,
+    Error,
+    ErrRoomFull,
+    ErrRoomNotFound,
+    ErrNotYourTurn,
+    ErrCategoryAlreadyScored,
+    ErrJokerPlacement,
+    ErrGameAlreadyStarted,
+    ErrNotEnoughPlayers,
+    ErrWrongPassword,
+    ErrRoomLocked,
+    ErrSpectatorsFull,
+    ErrNotHost,
+    ErrKicked,
+    ErrRateLimited,
+    PasswordFor,
+    RoomNameInput,
+    RoomCodeInput,
+    ErrUnknownCode,
+    RoomCode,
+    CodeCopied,
+    CopyFailed,
+    ActJoinByCode,
+    ActCopyCode,
+    ActPaste,
+    ErrRoomNameEmpty,
+    ErrRoomNameTooLong,
+    ErrRoomNameChar,
+    ErrRoomNameBlocked,
+    ErrRoomNameTaken,
+    ErrSessionTaken,
+    KickedTitle,
+    KickedDismiss,
+    OfferWatch,
+    ScoredFor,
+    TurnOfRound,
+    GameStarted,
+    CaughtUp,
+    Forfeited,
+    JoinedRoom,
+    LeftRoom,
+    JoinedGame,
+    LeftGame,
+    Spectating,
+    StoppedSpectating,
+    Watching,
+    UpNext,
+    AfterRound,
+    RoundOverLeader,
+    RoundOverTied,
+
+    // Chat commands
+    CmdHelp,
+    CmdWhisper,
+    CmdMute,
+    CmdReady,
+    CmdKick,
+    CmdSeed,
+    CmdPreset,
+    ActFromPreset,
+    PresetsTitle,
+    NoPresets,
+    PresetSaved,
+    PresetSaveFailed,
+    PresetNeedsRoom,
+    CmdList,
+    CmdUnknown,
+    CmdUsage,
+    NoSuchPlayer,
+    Muted,
+    Unmuted,
+    WhisperTo,
+    WhisperFrom,
+
+    // Screen reader announcements
+    SayYouRolled,
+    SayYouRolledLast,
+    SayPlayerRolled,
+    SayYourTurn,
+    SayGameOverWinner,
+    SayGameOverDraw,
+    SayChat,
+    SayJoinedRoom,
+    SayCategoryPoints,
+    SayHolding,
+    SayHoldingNone,
+
+    // Window title
+    TitleLobby,
+    TitleRoom,
+    TitleYourTurn,
+    TitleTheirTurn,
+    TitleGameOver,
+    TitlePlayers,
+    TitleWatching,
+
+    // Discord Rich Presence
+    PresenceLeading,
+    PresenceTied,
+    PresenceTrailing,
+    PresenceWatching,
+}
+
+fn en(key: Key) -> &'static str {
+    use Key::*;
+    match key {
+        HelpTitle => "Help - Yahtzee Rules & Controls",
+        HelpRules => "YAHTZEE SCORING RULES",
+        HelpUpperSection => "Upper Section",
+        HelpOnesToSixes => "Ones - Sixes",
+        HelpOnesToSixesDesc => "Sum of matching dice face values",
+        HelpUpperBonus => "Upper Bonus",
+        HelpUpperBonusDesc => "+{} if upper total >= {}",
+        HelpLowerSection => "Lower Section",
+        HelpThreeOfAKind => "3 of a Kind",
+        HelpThreeOfAKindDesc => "Sum of all dice if 3+ match",
+        HelpFourOfAKind => "4 of a Kind",
+        HelpFourOfAKindDesc => "Sum of all dice if 4+ match",
+        HelpFullHouse => "Full House",
+        HelpFullHouseDesc => "25 pts (3 of one + 2 of another)",
+        HelpSmallStraight => "Sm. Straight",
+        HelpSmallStraightDesc => "30 pts (4 consecutive dice)",
+        HelpLargeStraight => "Lg. Straight",
+        HelpLargeStraightDesc => "40 pts (5 consecutive dice)",
+        HelpYahtzee => "YAHTZEE",
+        HelpYahtzeeDesc => "50 pts (all 5 dice the same)",
+        HelpChance => "Chance",
+        HelpChanceDesc => "Sum of all dice (any combination)",
+        HelpYahtzeeBonus => "Yahtzee Bonus",
+        HelpYahtzeeBonusDesc => "+{} per extra Yahtzee",
+        HelpControls => "CONTROLS",
+        HelpRoll => "Roll dice (up to 3 times per turn)",
+        HelpHold => "Toggle hold on individual dice",
+        HelpNavigate => "Navigate categories up/down",
+        HelpScore => "Score selected category",
+        HelpChat => "Open/close chat",
+        HelpCommands => "In chat: list commands; Tab completes",
+        HelpFocus => "Spectating: focus a player",
+        HelpDiceStats => "Dice statistics",
+        HelpLeaderGap => "Show each player's gap to the leader",
+        HelpToggleHelp => "Toggle this help screen",
+        HelpQuit => "Quit game",
+        HelpClose => "Press [?] or any key to close",
+
+        DiceStatsTitle => "Dice Statistics",
+        DiceStatsGame => "This game: {} dice rolled",
+        DiceStatsRoom => "This room: {} dice rolled.",
+        DiceStatsClose => "Press any key to close",
+        FairTooFew => "Too few rolls to judge fairness yet.",
+        FairLooksFair => "Consistent with fair dice (chi-squared {}).",
+        FairUnusual => "Unusual spread for fair dice (chi-squared {}).",
+
+        ActSwitch => "Switch",
+        ActConnect => "Connect",
+        ActQuit => "Quit",
+        ActCancel => "Cancel",
+        ActStartGame => "Start Game",
+        ActLeaveRoom => "Leave Room",
+        ActCommand => "Command",
+        ActMoveSeat => "Seat",
+        ActSeats => "Seats",
+        ActTimer => "Timer",
+        ActTimeBank => "Time bank",
+        ActOutOfTime => "Out of time",
+        ActSuddenDeath => "Sudden death",
+        ActLock => "Lock",
+        ActUnlock => "Unlock",
+        ActAutoStart => "Auto-start",
+        ActShuffleOrder => "Shuffle order",
+        ActNudge => "Idle nudge",
+        ActSpectators => "Spectators",
+        ActAnnouncements => "Announcements",
+        ActCreate => "Create",
+        ActJoin => "Join",
+        ActSpectate => "Spectate",
+        ActWatchFromStart => "Watch from start",
+        ActRefresh => "Refresh",
+        ActStats => "Stats",
+        ActRollLeft => "Roll ({} left)",
+        ActHold => "Hold",
+        ActScore => "Score",
+        ActChat => "Chat",
+        ActSkipToLive => "skip to live",
+        ActFocus => "focus player",
+
+        ConnectTagline => "- Multiplayer Yahtzee",
+        PlayerName => "Player Name",
+        ServerAddress => "Server Address",
+        Connecting => "Connecting...",
+
+        WaitingRoom => "- Waiting Room",
+        PlayerCount => "({}/{} players)",
+        HostMarker => "(host)",
+        ReadyMarker => "ready",
+        SettingRolls => "{} rolls per turn",
+        SettingTimer => "{}s turn timer",
+        SettingNoTimer => "no turn timer",
+        SettingTimeBank => "{} min time bank, then {}",
+        OutOfTimeAutoScore => "auto-score",
+        OutOfTimeForfeit => "forfeit",
+        SettingSuddenDeath => "sudden death",
+        SettingPassword => "password",
+        SettingLocked => "locked",
+        SettingAutoStart => "starts when full",
+        SettingShuffleOrder => "random turn order",
+        SettingNudge => "nudge after {}s idle",
+        SettingSpectators => "up to {} spectators",
+        SettingNoSpectators => "no spectators",
+        SettingAnnounceOff => "no announcements",
+        SettingAnnounceAll => "all announcements",
+        SettingSeed => "seed {} (unrated)",
+        AutoStartingIn => "Room full - game starts in {}s",
+        SpectatorCount => "{} spectator(s)",
+        Players => "Players",
+        Standings => "Standings",
+        StandingRecord => "{} of {} won · {} points",
+        WaitingForHost => "Waiting for host to start...",
+        LobbyWelcome => "Lobby - Welcome, ",
+        NoRoomsBefore => "No rooms available. Press ",
+        NoRoomsAfter => " to create one.",
+        Rooms => "Rooms",
+        ColRoomName => "Room Name",
+        ColPlayers => "Players",
+        ColSpectators => "Spectators",
+        ColStatus => "Status",
+        ColPace => "Pace",
+        ActPace => "Pace: {}",
+        PaceCasual => "Casual",
+        PaceStandard => "Standard",
+        PaceBlitz => "Blitz",
+        ConfirmZero => "Score 0 for {}? Press again to confirm",
+        RoomWaiting => "Waiting",
+        RoomInGame => "In Game",
+        RoomFinished => "Finished",
+        RoomWatchable => "(watch)",
+        RoomNotWatchable => "(no watching)",
+        RoomLocked => "[locked]",
+        RoomPassword => "[password]",
+        DefaultRoomName => "{}'s room",
+
+        Round => "Round {}/{}",
+        Turn => "Turn: ",
+        Replay => "REPLAY {}/{}",
+        Dice => "Dice",
+        DiceRolling => "Dice - Rolling...",
+        WaitingForRoll => "Waiting for roll...",
+        RollingDice => "Rolling dice...",
+        WaitingForOthers => "Waiting for other player's turn...",
+        FollowingTurn => "Following the current turn",
+        FocusedOn => "Focused on {}",
+        Chat => "Chat",
+
+        EnterName => "Please enter a name",
+        ConnectionFailed => "Connection failed: {}",
+        ConnectCancelled => "Connection attempt cancelled.",
+        ClientTooOld => "Server {} needs protocol {} or newer; this client is {} (protocol {}). Please update.",
+        CompatibilityMode => "Server {} is older than this client; reconnecting after a dropped connection is unavailable.",
+        ConnectionLost => "Lost the connection to the server",
+        Reconnecting => "Connection lost - reconnecting...",
+        ActionsQueued => "{} action(s) will be sent once reconnected",
+        Reconnected => "Reconnected",
+        ResumeFailed => "Could not get back into the game; it ended or your seat was given up",
+        Error => "Error: {}",
+        ErrRoomFull => "That room is full",
+        ErrRoomNotFound => "That room no longer exists",
+        ErrNotYourTurn => "Wait for your turn",
+        ErrCategoryAlreadyScored => "You already scored that category",
+        ErrJokerPlacement => "A joker must go in its upper box first, then the lower section",
+        ErrGameAlreadyStarted => "That game has already started",
+        ErrNotEnoughPlayers => "Need at least 2 players to start",
+        ErrWrongPassword => "Wrong password, try again",
+        ErrRoomLocked => "The host has locked that room",
+        ErrSpectatorsFull => "That room can't take any more spectators",
+        ErrNotHost => "Only the host can do that",
+        ErrKicked => "The host removed you from the room",
+        ErrRateLimited => "Slow down - too many actions at once",
+        PasswordFor => "Password for {}: {}",
+        RoomNameInput => "Room name: {}",
+        RoomCodeInput => "Room code: {}",
+        ErrUnknownCode => "No room with that code",
+        RoomCode => "code {}",
+        CodeCopied => "Room code copied: {}",
+        CopyFailed => "Could not copy: {}",
+        ActJoinByCode => "Join by code",
+        ActCopyCode => "Copy code",
+        ActPaste => "Paste",
+        ErrRoomNameEmpty => "Give the room a name",
+        ErrRoomNameTooLong => "Room names can be at most {} characters",
+        ErrRoomNameChar => "Room names can't contain '{}'",
+        ErrRoomNameBlocked => "Please pick a friendlier room name",
+        ErrRoomNameTaken => "There is already a room with that name",
+        ErrSessionTaken => "You connected again from somewhere else, so this connection was closed",
+        KickedTitle => "Removed from room",
+        KickedDismiss => "Press Enter to continue",
+        OfferWatch => "That game has already started. Watch it instead?",
+        ScoredFor => "{} scored {} for {}",
+        TurnOfRound => "{}'s turn (round {})",
+        GameStarted => "Game started!",
+        CaughtUp => "Caught up - now watching live",
+        Forfeited => "{} forfeited",
+        JoinedRoom => "{} joined",
+        LeftRoom => "{} left",
+        JoinedGame => "{} joined the game",
+        LeftGame => "{} left the game",
+        Spectating => "{} is spectating",
+        StoppedSpectating => "{} stopped spectating",
+        Watching => "Watching: {}",
+        UpNext => "Next: {}",
+        AfterRound => "After round {}: {}",
+        RoundOverLeader => "Round {} is over. {} leads: {}",
+        RoundOverTied => "Round {} is over. {} share the lead: {}",
+        CmdHelp => "/help - list these commands",
+        CmdWhisper => "/w <player> <message> - message one player privately",
+        CmdMute => "/mute <player> - hide or show a player's chat",
+        CmdReady => "/ready - mark yourself ready, or not, in the waiting room",
+        CmdKick => "/kick <player> - remove a player from the room (host)",
+        CmdSeed => "/seed <number> - deal the dice from a seed; the game is unrated (host)",
+        CmdPreset => "/preset <name> - save this room's settings as a preset",
+        ActFromPreset => "From preset",
+        PresetsTitle => "Room presets",
+        NoPresets => "No presets yet; type /preset <name> in a waiting room to save one",
+        PresetSaved => "Saved preset {}",
+        PresetSaveFailed => "Could not save presets: {}",
+        PresetNeedsRoom => "Presets are saved from the waiting room",
+        CmdList => "Commands: {}",
+        CmdUnknown => "Unknown command {}; try /help",
+        CmdUsage => "Usage: {}",
+        NoSuchPlayer => "No player named {} here",
+        Muted => "Muted {}",
+        Unmuted => "Unmuted {}",
+        WhisperTo => "[to {}] {}",
+        WhisperFrom => "[from {}] {}",
+
+        SayYouRolled => "You rolled {}. {} rolls left.",
+        SayYouRolledLast => "You rolled {}. No rolls left, choose a category.",
+        SayPlayerRolled => "{} rolled {}.",
+        SayYourTurn => "Your turn, round {}.",
+        SayGameOverWinner => "Game over. {} wins with {}.",
+        SayGameOverDraw => "Game over. Draw between {} with {}.",
+        SayChat => "{} says: {}",
+        SayJoinedRoom => "Joined room {}.",
+        SayCategoryPoints => "{}: {} points",
+        SayHolding => "Holding {}",
+        SayHoldingNone => "Holding nothing",
+
+        TitleLobby => "Lobby",
+        TitleRoom => "Room '{}'",
+        TitleYourTurn => "your turn",
+        TitleTheirTurn => "{}'s turn",
+        TitleGameOver => "game over",
+        TitlePlayers => "{} players",
+        TitleWatching => "{} watching",
+
+        PresenceLeading => "Leading with {}",
+        PresenceTied => "Tied for the lead with {}",
+        PresenceTrailing => "{} behind the lead with {}",
+        PresenceWatching => "Watching a game",
+    }
+}
+
+fn de(key: Key) -> &'static str {
+    use Key::*;
+    match key {
+        HelpTitle => "Hilfe - Kniffel-Regeln & Steuerung",
+        HelpRules => "PUNKTEREGELN",
+        HelpUpperSection => "Oberer Teil",
+        HelpOnesToSixes => "Einer - Sechser",
+        HelpOnesToSixesDesc => "Summe der passenden Augen",
+        HelpUpperBonus => "Bonus oben",
+        HelpUpperBonusDesc => "+{} ab {} Punkten im oberen Teil",
+        HelpLowerSection => "Unterer Teil",
+        HelpThreeOfAKind => "Dreierpasch",
+        HelpThreeOfAKindDesc => "Summe aller Würfel bei 3 gleichen",
+        HelpFourOfAKind => "Viererpasch",
+        HelpFourOfAKindDesc => "Summe aller Würfel bei 4 gleichen",
+        HelpFullHouse => "Full House",
+        HelpFullHouseDesc => "25 Pkt. (3 gleiche + 2 gleiche)",
+        HelpSmallStraight => "Kl. Straße",
+        HelpSmallStraightDesc => "30 Pkt. (4 aufeinanderfolgende)",
+        HelpLargeStraight => "Gr. Straße",
+        HelpLargeStraightDesc => "40 Pkt. (5 aufeinanderfolgende)",
+        HelpYahtzee => "YAHTZEE",
+        HelpYahtzeeDesc => "50 Pkt. (alle 5 Würfel gleich)",
+        HelpChance => "Chance",
+        HelpChanceDesc => "Summe aller Würfel (beliebig)",
+        HelpYahtzeeBonus => "Yahtzee-Bonus",
+        HelpYahtzeeBonusDesc => "+{} für jedes weitere Yahtzee",
+        HelpControls => "STEUERUNG",
+        HelpRoll => "Würfeln (bis zu 3-mal pro Zug)",
+        HelpHold => "Einzelne Würfel halten/freigeben",
+        HelpNavigate => "Kategorie auf/ab wählen",
+        HelpScore => "Gewählte Kategorie werten",
+        HelpChat => "Chat öffnen/schließen",
+        HelpCommands => "Im Chat: Befehle anzeigen; Tab ergänzt",
+        HelpFocus => "Zuschauen: Spieler fokussieren",
+        HelpDiceStats => "Würfelstatistik",
+        HelpLeaderGap => "Rückstand auf den Führenden zeigen",
+        HelpToggleHelp => "Diese Hilfe ein-/ausblenden",
+        HelpQuit => "Spiel beenden",
+        HelpClose => "[?] oder eine beliebige Taste schließt",
+
+        DiceStatsTitle => "Würfelstatistik",
+        DiceStatsGame => "Dieses Spiel: {} Würfel geworfen",
+        DiceStatsRoom => "Dieser Raum: {} Würfel geworfen.",
+        DiceStatsClose => "Eine beliebige Taste schließt",
+        FairTooFew => "Noch zu wenige Würfe für ein Urteil.",
+        FairLooksFair => "Passt zu fairen Würfeln (Chi-Quadrat {}).",
+        FairUnusual => "Ungewöhnliche Verteilung für faire Würfel (Chi-Quadrat {}).",
+
+        ActSwitch => "Wechseln",
+        ActConnect => "Verbinden",
+        ActQuit => "Beenden",
+        ActCancel => "Abbrechen",
+        ActStartGame => "Spiel starten",
+        ActLeaveRoom => "Raum verlassen",
+        ActCommand => "Befehl",
+        ActMoveSeat => "Platz",
+        ActSeats => "Plätze",
+        ActTimer => "Zugzeit",
+        ActTimeBank => "Zeitkonto",
+        ActOutOfTime => "Zeit abgelaufen",
+        ActSuddenDeath => "Sudden Death",
+        ActLock => "Sperren",
+        ActUnlock => "Entsperren",
+        ActAutoStart => "Autostart",
+        ActShuffleOrder => "Reihenfolge mischen",
+        ActNudge => "Erinnerung",
+        ActSpectators => "Zuschauer",
+        ActAnnouncements => "Ansagen",
+        ActCreate => "Erstellen",
+        ActJoin => "Beitreten",
+        ActSpectate => "Zuschauen",
+        ActWatchFromStart => "Von Anfang an",
+        ActRefresh => "Aktualisieren",
+        ActStats => "Statistik",
+        ActRollLeft => "Würfeln (noch {})",
+        ActHold => "Halten",
+        ActScore => "Werten",
+        ActChat => "Chat",
+        ActSkipToLive => "zur Live-Ansicht",
+        ActFocus => "Spieler fokussieren",
+
+        ConnectTagline => "- Yahtzee für mehrere Spieler",
+        PlayerName => "Spielername",
+        ServerAddress => "Serveradresse",
+        Connecting => "Verbinde...",
+
+        WaitingRoom => "- Warteraum",
+        PlayerCount => "({}/{} Spieler)",
+        HostMarker => "(Gastgeber)",
+        ReadyMarker => "bereit",
+        SettingRolls => "{} Würfe pro Zug",
+        SettingTimer => "{}s Zugzeit",
+        SettingNoTimer => "ohne Zugzeit",
+        SettingTimeBank => "{} Min. Zeitkonto, danach {}",
+        OutOfTimeAutoScore => "automatisch werten",
+        OutOfTimeForfeit => "aufgeben",
+        SettingSuddenDeath => "Sudden Death",
+        SettingPassword => "Passwort",
+        SettingLocked => "gesperrt",
+        SettingAutoStart => "startet wenn voll",
+        SettingShuffleOrder => "zufällige Reihenfolge",
+        SettingNudge => "Erinnerung nach {} s Untätigkeit",
+        SettingSpectators => "bis zu {} Zuschauer",
+        SettingNoSpectators => "keine Zuschauer",
+        SettingAnnounceOff => "keine Ansagen",
+        SettingAnnounceAll => "alle Ansagen",
+        SettingSeed => "Seed {} (ungewertet)",
+        AutoStartingIn => "Raum voll - Spiel startet in {}s",
+        SpectatorCount => "{} Zuschauer",
+        Players => "Spieler",
+        Standings => "Tabelle",
+        StandingRecord => "{} von {} gewonnen · {} Punkte",
+        WaitingForHost => "Warte auf den Start durch den Gastgeber...",
+        LobbyWelcome => "Lobby - Willkommen, ",
+        NoRoomsBefore => "Keine Räume vorhanden. Drücke ",
+        NoRoomsAfter => ", um einen zu erstellen.",
+        Rooms => "Räume",
+        ColRoomName => "Raumname",
+        ColPlayers => "Spieler",
+        ColSpectators => "Zuschauer",
+        ColStatus => "Status",
+        ColPace => "Tempo",
+        ActPace => "Tempo: {}",
+        PaceCasual => "Gemütlich",
+        PaceStandard => "Standard",
+        PaceBlitz => "Blitz",
+        ConfirmZero => "0 Punkte für {}? Zum Bestätigen erneut drücken",
+        RoomWaiting => "Wartet",
+        RoomInGame => "Im Spiel",
+        RoomFinished => "Beendet",
+        RoomWatchable => "(zuschauen)",
+        RoomNotWatchable => "(kein Zuschauen)",
+        RoomLocked => "[gesperrt]",
+        RoomPassword => "[Passwort]",
+        DefaultRoomName => "Raum von {}",
+
+        Round => "Runde {}/{}",
+        Turn => "Am Zug: ",
+        Replay => "WIEDERHOLUNG {}/{}",
+        Dice => "Würfel",
+        DiceRolling => "Würfel - rollen...",
+        WaitingForRoll => "Warte auf den Wurf...",
+        RollingDice => "Würfel rollen...",
+        WaitingForOthers => "Ein anderer Spieler ist am Zug...",
+        FollowingTurn => "Folgt dem aktuellen Zug",
+        FocusedOn => "Fokus auf {}",
+        Chat => "Chat",
+
+        EnterName => "Bitte gib einen Namen ein",
+        ConnectionFailed => "Verbindung fehlgeschlagen: {}",
+        ConnectCancelled => "Verbindungsversuch abgebrochen.",
+        ClientTooOld => "Server {} braucht Protokoll {} oder neuer; dieser Client ist {} (Protokoll {}). Bitte aktualisieren.",
+        CompatibilityMode => "Server {} ist älter als dieser Client; nach einem Verbindungsabbruch ist kein Wiedereinstieg möglich.",
+        ConnectionLost => "Verbindung zum Server verloren",
+        Reconnecting => "Verbindung verloren - verbinde neu...",
+        ActionsQueued => "{} Aktion(en) werden nach dem Neuverbinden gesendet",
+        Reconnected => "Wieder verbunden",
+        ResumeFailed => "Rückkehr ins Spiel nicht möglich; es ist vorbei oder dein Platz wurde freigegeben",
+        Error => "Fehler: {}",
+        ErrRoomFull => "Der Raum ist voll",
+        ErrRoomNotFound => "Den Raum gibt es nicht mehr",
+        ErrNotYourTurn => "Warte, bis du am Zug bist",
+        ErrCategoryAlreadyScored => "Diese Kategorie hast du schon belegt",
+        ErrJokerPlacement => "Ein Joker gehört zuerst in sein oberes Feld, dann in den unteren Block",
+        ErrGameAlreadyStarted => "Das Spiel hat schon begonnen",
+        ErrNotEnoughPlayers => "Zum Starten braucht es mindestens 2 Spieler",
+        ErrWrongPassword => "Falsches Passwort, versuch es noch einmal",
+        ErrRoomLocked => "Der Gastgeber hat den Raum gesperrt",
+        ErrSpectatorsFull => "Der Raum nimmt keine weiteren Zuschauer auf",
+        ErrNotHost => "Das kann nur der Gastgeber",
+        ErrKicked => "Der Gastgeber hat dich aus dem Raum entfernt",
+        ErrRateLimited => "Langsam - zu viele Aktionen auf einmal",
+        PasswordFor => "Passwort für {}: {}",
+        RoomNameInput => "Raumname: {}",
+        RoomCodeInput => "Raumcode: {}",
+        ErrUnknownCode => "Kein Raum mit diesem Code",
+        RoomCode => "Code {}",
+        CodeCopied => "Raumcode kopiert: {}",
+        CopyFailed => "Kopieren fehlgeschlagen: {}",
+        ActJoinByCode => "Per Code beitreten",
+        ActCopyCode => "Code kopieren",
+        ActPaste => "Einfügen",
+        ErrRoomNameEmpty => "Gib dem Raum einen Namen",
+        ErrRoomNameTooLong => "Raumnamen dürfen höchstens {} Zeichen lang sein",
+        ErrRoomNameChar => "Raumnamen dürfen kein '{}' enthalten",
+        ErrRoomNameBlocked => "Bitte wähle einen freundlicheren Raumnamen",
+        ErrRoomNameTaken => "Es gibt schon einen Raum mit diesem Namen",
+        ErrSessionTaken => "Du hast dich woanders neu verbunden, daher wurde diese Verbindung getrennt",
+        KickedTitle => "Aus dem Raum entfernt",
+        KickedDismiss => "Weiter mit Enter",
+        OfferWatch => "Das Spiel läuft schon. Stattdessen zuschauen?",
+        ScoredFor => "{} erzielt {} in {}",
+        TurnOfRound => "{} ist am Zug (Runde {})",
+        GameStarted => "Spiel gestartet!",
+        CaughtUp => "Aufgeholt - jetzt live",
+        Forfeited => "{} hat aufgegeben",
+        JoinedRoom => "{} ist beigetreten",
+        LeftRoom => "{} ist gegangen",
+        JoinedGame => "{} ist dem Spiel beigetreten",
+        LeftGame => "{} hat das Spiel verlassen",
+        Spectating => "{} schaut zu",
+        StoppedSpectating => "{} schaut nicht mehr zu",
+        Watching => "Zuschauer: {}",
+        UpNext => "Danach: {}",
+        AfterRound => "Nach Runde {}: {}",
+        RoundOverLeader => "Runde {} ist vorbei. {} führt: {}",
+        RoundOverTied => "Runde {} ist vorbei. {} teilen sich die Führung: {}",
+        CmdHelp => "/help - diese Befehle anzeigen",
+        CmdWhisper => "/w <Spieler> <Nachricht> - einem Spieler privat schreiben",
+        CmdMute => "/mute <Spieler> - Chat eines Spielers aus- oder einblenden",
+        CmdReady => "/ready - im Warteraum bereit melden oder zurückziehen",
+        CmdKick => "/kick <Spieler> - Spieler aus dem Raum entfernen (Gastgeber)",
+        CmdSeed => "/seed <Zahl> - Würfel aus einem Seed; das Spiel wird nicht gewertet (Gastgeber)",
+        CmdPreset => "/preset <Name> - Einstellungen dieses Raums als Vorlage speichern",
+        ActFromPreset => "Aus Vorlage",
+        PresetsTitle => "Raumvorlagen",
+        NoPresets => "Noch keine Vorlagen; /preset <Name> im Warteraum speichert eine",
+        PresetSaved => "Vorlage {} gespeichert",
+        PresetSaveFailed => "Vorlagen konnten nicht gespeichert werden: {}",
+        PresetNeedsRoom => "Vorlagen werden im Warteraum gespeichert",
+        CmdList => "Befehle: {}",
+        CmdUnknown => "Unbekannter Befehl {}; siehe /help",
+        CmdUsage => "Aufruf: {}",
+        NoSuchPlayer => "Kein Spieler namens {} hier",
+        Muted => "{} stummgeschaltet",
+        Unmuted => "{} nicht mehr stummgeschaltet",
+        WhisperTo => "[an {}] {}",
+        WhisperFrom => "[von {}] {}",
+
+        SayYouRolled => "Du hast {} gewürfelt. Noch {} Würfe.",
+        SayYouRolledLast => "Du hast {} gewürfelt. Keine Würfe mehr, wähle eine Kategorie.",
+        SayPlayerRolled => "{} hat {} gewürfelt.",
+        SayYourTurn => "Du bist am Zug, Runde {}.",
+        SayGameOverWinner => "Spiel vorbei. {} gewinnt mit {}.",
+        SayGameOverDraw => "Spiel vorbei. Unentschieden zwischen {} mit {}.",
+        SayChat => "{} sagt: {}",
+        SayJoinedRoom => "Raum {} beigetreten.",
+        SayCategoryPoints => "{}: {} Punkte",
+        SayHolding => "Gehalten: {}",
+        SayHoldingNone => "Nichts gehalten",
+
+        TitleLobby => "Lobby",
+        TitleRoom => "Raum '{}'",
+        TitleYourTurn => "du bist am Zug",
+        TitleTheirTurn => "{} ist am Zug",
+        TitleGameOver => "Spiel vorbei",
+        TitlePlayers => "{} Spieler",
+        TitleWatching => "{} schauen zu",
+
+        PresenceLeading => "Führt mit {}",
+        PresenceTied => "Gleichauf an der Spitze mit {}",
+        PresenceTrailing => "{} hinter der Spitze mit {}",
+        PresenceWatching => "Schaut ein Spiel",
+    }
+}
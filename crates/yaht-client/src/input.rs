@@ -1,12 +1,14 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 use crate::app::Screen;
+use crate::ui::lobby::RoomSetting;
 
 #[derive(Debug, Clone)]
 pub enum Action {
     // Global
     Quit,
     ShowHelp,
+    ToggleDebug,
 
     // Text input
     TypeChar(char),
@@ -19,14 +21,37 @@ pub enum Action {
 
     // Connect screen
     SwitchField,
+    CancelConnect,
 
     // Lobby
     RefreshRooms,
     CreateRoom,
+    CyclePace,
     JoinSelected,
     SpectateSelected,
+    SpectateFromStart,
     StartGame,
     LeaveRoom,
+    ChangeSetting(RoomSetting),
+    /// Move our seat this many places later in the turn order.
+    MoveSeat(i8),
+    AcceptWatch,
+    DeclineWatch,
+    SubmitRoomName,
+    CancelRoomName,
+    OpenJoinByCode,
+    JoinByCode,
+    CancelJoinByCode,
+    /// Paste from the clipboard into the text being entered.
+    Paste,
+    /// Copy the waiting room's code to the clipboard.
+    CopyRoomCode,
+    SubmitPassword,
+    CancelPassword,
+    OpenPresets,
+    CreateFromPreset,
+    ClosePresets,
+    DismissNotice,
 
     // Game
     RollDice,
@@ -34,9 +59,29 @@ pub enum Action {
     ConfirmScore,
     ToggleChatFocus,
     SendChat,
+    CompleteChat,
+    SkipReplay,
+    CycleFocus,
+    ShowDiceStats,
+    ToggleLeaderGap,
 
     // Results
     BackToLobby,
+    /// Back to the waiting room of the room just played in.
+    PlayAgain,
+    CopyScoreboard,
+    ShowStats,
+
+    // Game history
+    ShowHistory,
+    OpenGame,
+    CloseGame,
+    ReplayStep(bool),
+
+    // Leaderboard
+    ShowLeaderboard,
+    PreviousSeason,
+    NextSeason,
 }
 
 pub fn map_key(key: KeyEvent, screen: &Screen, chat_focused: bool) -> Option<Action> {
@@ -44,12 +89,16 @@ pub fn map_key(key: KeyEvent, screen: &Screen, chat_focused: bool) -> Option<Act
     if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
         return Some(Action::Quit);
     }
+    if key.code == KeyCode::F(12) {
+        return Some(Action::ToggleDebug);
+    }
 
     // Chat input mode
     if chat_focused {
         return match key.code {
             KeyCode::Enter => Some(Action::SendChat),
             KeyCode::Esc => Some(Action::ToggleChatFocus),
+            KeyCode::Tab => Some(Action::CompleteChat),
             KeyCode::Char(c) => Some(Action::TypeChar(c)),
             KeyCode::Backspace => Some(Action::Backspace),
             _ => None,
@@ -57,6 +106,11 @@ pub fn map_key(key: KeyEvent, screen: &Screen, chat_focused: bool) -> Option<Act
     }
 
     match screen {
+        Screen::Connect(s) if s.connecting => match key.code {
+            KeyCode::Esc => Some(Action::CancelConnect),
+            _ => None,
+        },
+
         Screen::Connect(_) => match key.code {
             KeyCode::Enter => Some(Action::Submit),
             KeyCode::Tab => Some(Action::SwitchField),
@@ -66,19 +120,89 @@ pub fn map_key(key: KeyEvent, screen: &Screen, chat_focused: bool) -> Option<Act
             _ => None,
         },
 
+        Screen::Lobby(s) if s.kicked.is_some() => match key.code {
+            KeyCode::Enter | KeyCode::Esc => Some(Action::DismissNotice),
+            _ => None,
+        },
+
+        Screen::Lobby(s) if s.name_prompt.is_some() => match key.code {
+            KeyCode::Enter => Some(Action::SubmitRoomName),
+            KeyCode::Esc => Some(Action::CancelRoomName),
+            KeyCode::Char(c) => Some(Action::TypeChar(c)),
+            KeyCode::Backspace => Some(Action::Backspace),
+            _ => None,
+        },
+
+        Screen::Lobby(s) if s.code_prompt.is_some() => match key.code {
+            KeyCode::Enter => Some(Action::JoinByCode),
+            KeyCode::Esc => Some(Action::CancelJoinByCode),
+            KeyCode::Char('v') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Action::Paste),
+            KeyCode::Char(c) => Some(Action::TypeChar(c)),
+            KeyCode::Backspace => Some(Action::Backspace),
+            _ => None,
+        },
+
+        Screen::Lobby(s) if s.password_prompt.is_some() => match key.code {
+            KeyCode::Enter => Some(Action::SubmitPassword),
+            KeyCode::Esc => Some(Action::CancelPassword),
+            KeyCode::Char(c) => Some(Action::TypeChar(c)),
+            KeyCode::Backspace => Some(Action::Backspace),
+            _ => None,
+        },
+
+        Screen::Lobby(s) if s.preset_picker.is_some() => match key.code {
+            KeyCode::Enter => Some(Action::CreateFromPreset),
+            KeyCode::Esc => Some(Action::ClosePresets),
+            KeyCode::Up | KeyCode::Char('k') => Some(Action::NavigateUp),
+            KeyCode::Down | KeyCode::Char('j') => Some(Action::NavigateDown),
+            _ => None,
+        },
+
         Screen::Lobby(s) if s.is_in_room() => match key.code {
             KeyCode::Enter => Some(Action::StartGame),
             KeyCode::Esc => Some(Action::LeaveRoom),
+            KeyCode::Char('+') | KeyCode::Char('=') if s.is_host() => {
+                Some(Action::ChangeSetting(RoomSetting::MaxPlayers(1)))
+            }
+            KeyCode::Char('-') if s.is_host() => Some(Action::ChangeSetting(RoomSetting::MaxPlayers(-1))),
+            KeyCode::Char('p') if s.is_host() => Some(Action::ChangeSetting(RoomSetting::Pace)),
+            KeyCode::Char('t') if s.is_host() => Some(Action::ChangeSetting(RoomSetting::TurnTimer)),
+            KeyCode::Char('b') if s.is_host() => Some(Action::ChangeSetting(RoomSetting::TimeBank)),
+            KeyCode::Char('o') if s.is_host() => Some(Action::ChangeSetting(RoomSetting::OutOfTime)),
+            KeyCode::Char('d') if s.is_host() => Some(Action::ChangeSetting(RoomSetting::SuddenDeath)),
+            KeyCode::Char('l') if s.is_host() => Some(Action::ChangeSetting(RoomSetting::Locked)),
+            KeyCode::Char('a') if s.is_host() => Some(Action::ChangeSetting(RoomSetting::AutoStart)),
+            KeyCode::Char('s') if s.is_host() => Some(Action::ChangeSetting(RoomSetting::ShuffleOrder)),
+            KeyCode::Char('i') if s.is_host() => Some(Action::ChangeSetting(RoomSetting::Nudge)),
+            KeyCode::Char('v') if s.is_host() => Some(Action::ChangeSetting(RoomSetting::Spectators)),
+            KeyCode::Char('n') if s.is_host() => Some(Action::ChangeSetting(RoomSetting::Announcements)),
+            KeyCode::Char('y') => Some(Action::CopyRoomCode),
+            KeyCode::Char('[') => Some(Action::MoveSeat(-1)),
+            KeyCode::Char(']') => Some(Action::MoveSeat(1)),
+            KeyCode::Char('/') => Some(Action::ToggleChatFocus),
             KeyCode::Char('q') => Some(Action::Quit),
             KeyCode::Char('?') => Some(Action::ShowHelp),
             _ => None,
         },
 
+        Screen::Lobby(s) if s.watch_offer.is_some() => match key.code {
+            KeyCode::Char('y') | KeyCode::Enter => Some(Action::AcceptWatch),
+            KeyCode::Char('n') | KeyCode::Esc => Some(Action::DeclineWatch),
+            _ => None,
+        },
+
         Screen::Lobby(_) => match key.code {
             KeyCode::Char('q') => Some(Action::Quit),
             KeyCode::Char('r') => Some(Action::RefreshRooms),
             KeyCode::Char('c') => Some(Action::CreateRoom),
+            KeyCode::Char('f') => Some(Action::OpenPresets),
+            KeyCode::Char('i') => Some(Action::OpenJoinByCode),
+            KeyCode::Char('p') => Some(Action::CyclePace),
             KeyCode::Char('s') => Some(Action::SpectateSelected),
+            KeyCode::Char('w') => Some(Action::SpectateFromStart),
+            KeyCode::Char('t') => Some(Action::ShowStats),
+            KeyCode::Char('l') => Some(Action::ShowLeaderboard),
+            KeyCode::Char('h') => Some(Action::ShowHistory),
             KeyCode::Enter => Some(Action::JoinSelected),
             KeyCode::Up | KeyCode::Char('k') => Some(Action::NavigateUp),
             KeyCode::Down | KeyCode::Char('j') => Some(Action::NavigateDown),
@@ -100,15 +224,53 @@ pub fn map_key(key: KeyEvent, screen: &Screen, chat_focused: bool) -> Option<Act
             KeyCode::Up | KeyCode::Char('k') => Some(Action::NavigateUp),
             KeyCode::Down | KeyCode::Char('j') => Some(Action::NavigateDown),
             KeyCode::Enter => Some(Action::ConfirmScore),
+            KeyCode::Tab => Some(Action::SkipReplay),
+            KeyCode::Char('f') | KeyCode::Char('F') => Some(Action::CycleFocus),
+            KeyCode::Char('d') | KeyCode::Char('D') => Some(Action::ShowDiceStats),
+            KeyCode::Char('g') | KeyCode::Char('G') => Some(Action::ToggleLeaderGap),
             KeyCode::Char('?') => Some(Action::ShowHelp),
             _ => None,
         },
 
-        Screen::Results(_) => match key.code {
+        Screen::Results(s) => match key.code {
             KeyCode::Char('q') => Some(Action::Quit),
             KeyCode::Enter => Some(Action::BackToLobby),
+            KeyCode::Char('r') if s.room.is_some() => Some(Action::PlayAgain),
+            KeyCode::Char('c') => Some(Action::CopyScoreboard),
+            KeyCode::Char('t') => Some(Action::ShowStats),
             KeyCode::Esc => Some(Action::Quit),
             _ => None,
         },
+
+        Screen::History(s) if s.open.is_some() => match key.code {
+            KeyCode::Char('q') => Some(Action::Quit),
+            KeyCode::Left | KeyCode::Char('h') => Some(Action::ReplayStep(false)),
+            KeyCode::Right | KeyCode::Char('l') => Some(Action::ReplayStep(true)),
+            KeyCode::Enter | KeyCode::Esc => Some(Action::CloseGame),
+            _ => None,
+        },
+
+        Screen::History(_) => match key.code {
+            KeyCode::Char('q') => Some(Action::Quit),
+            KeyCode::Up | KeyCode::Char('k') => Some(Action::NavigateUp),
+            KeyCode::Down | KeyCode::Char('j') => Some(Action::NavigateDown),
+            KeyCode::Enter => Some(Action::OpenGame),
+            KeyCode::Esc => Some(Action::BackToLobby),
+            _ => None,
+        },
+
+        Screen::Leaderboard(_) => match key.code {
+            KeyCode::Char('q') => Some(Action::Quit),
+            KeyCode::Left | KeyCode::Char('h') => Some(Action::PreviousSeason),
+            KeyCode::Right | KeyCode::Char('l') => Some(Action::NextSeason),
+            KeyCode::Enter | KeyCode::Esc => Some(Action::BackToLobby),
+            _ => None,
+        },
+
+        Screen::Stats(_) => match key.code {
+            KeyCode::Char('q') => Some(Action::Quit),
+            KeyCode::Enter | KeyCode::Esc | KeyCode::Char('t') => Some(Action::BackToLobby),
+            _ => None,
+        },
     }
 }
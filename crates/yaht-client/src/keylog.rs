@@ -0,0 +1,154 @@
+//! Recording key presses to a file and playing them back, for reproducing
+//! UI bugs and scripting demos.
+//!
+//! A recording is plain text with one key per line: the milliseconds since
+//! the previous key, then the key, e.g. `350 r`, `120 Ctrl+c` or
+//! `800 Enter`. Lines starting with `#` are comments.
+
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use anyhow::Context;
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+
+/// Keys with names, besides characters and function keys.
+const NAMED_KEYS: [(&str, KeyCode); 15] = [
+    ("Enter", KeyCode::Enter),
+    ("Esc", KeyCode::Esc),
+    ("Tab", KeyCode::Tab),
+    ("BackTab", KeyCode::BackTab),
+    ("Backspace", KeyCode::Backspace),
+    ("Delete", KeyCode::Delete),
+    ("Insert", KeyCode::Insert),
+    ("Up", KeyCode::Up),
+    ("Down", KeyCode::Down),
+    ("Left", KeyCode::Left),
+    ("Right", KeyCode::Right),
+    ("Home", KeyCode::Home),
+    ("End", KeyCode::End),
+    ("PageUp", KeyCode::PageUp),
+    ("PageDown", KeyCode::PageDown),
+];
+
+const MODIFIERS: [(&str, KeyModifiers); 3] = [
+    ("Ctrl+", KeyModifiers::CONTROL),
+    ("Alt+", KeyModifiers::ALT),
+    ("Shift+", KeyModifiers::SHIFT),
+];
+
+#[derive(Debug, Default)]
+pub struct KeyLogOptions {
+    /// Record every key press to this file, replacing what it held.
+    pub record: Option<PathBuf>,
+    /// Press the keys in this file before reading the terminal's.
+    pub play: Option<PathBuf>,
+}
+
+struct Recorder {
+    file: File,
+    last: Instant,
+}
+
+static RECORDER: Mutex<Option<Recorder>> = Mutex::new(None);
+/// The keys still to play back, taken by the first key reader.
+static SCRIPT: Mutex<Vec<(Duration, KeyEvent)>> = Mutex::new(Vec::new());
+
+/// Read the keys to play back and create the recording, failing on a file
+/// that can't be read or a line that doesn't parse.
+pub fn init(options: KeyLogOptions) -> anyhow::Result<()> {
+    if let Some(path) = &options.play {
+        *SCRIPT.lock().unwrap_or_else(PoisonError::into_inner) = load(path)?;
+    }
+    if let Some(path) = &options.record {
+        let mut file = File::create(path).with_context(|| format!("cannot create {}", path.display()))?;
+        writeln!(file, "# yaht-client input recording: milliseconds since the last key, then the key")?;
+        *RECORDER.lock().unwrap_or_else(PoisonError::into_inner) = Some(Recorder {
+            file,
+            last: Instant::now(),
+        });
+    }
+    Ok(())
+}
+
+fn load(path: &Path) -> anyhow::Result<Vec<(Duration, KeyEvent)>> {
+    let text = std::fs::read_to_string(path).with_context(|| format!("cannot read {}", path.display()))?;
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(number, line)| {
+            parse_line(line).with_context(|| format!("{}:{}: expected `<ms> <key>`, got {:?}", path.display(), number, line))
+        })
+        .collect()
+}
+
+fn parse_line(line: &str) -> Option<(Duration, KeyEvent)> {
+    let (ms, key) = line.split_once(char::is_whitespace)?;
+    Some((Duration::from_millis(ms.parse().ok()?), parse_key(key.trim())?))
+}
+
+/// The keys to play back, leaving none for later readers.
+pub fn take_script() -> Vec<(Duration, KeyEvent)> {
+    std::mem::take(&mut *SCRIPT.lock().unwrap_or_else(PoisonError::into_inner))
+}
+
+/// Add `key` to the recording, if one is being made. Only presses are
+/// kept, and a recording that can no longer be written is stopped.
+pub fn record(key: &KeyEvent) {
+    if key.kind != KeyEventKind::Press {
+        return;
+    }
+    let mut recorder = RECORDER.lock().unwrap_or_else(PoisonError::into_inner);
+    let Some(rec) = recorder.as_mut() else {
+        return;
+    };
+    let Some(name) = format_key(key) else {
+        return;
+    };
+    let elapsed = rec.last.elapsed();
+    rec.last = Instant::now();
+    if let Err(e) = writeln!(rec.file, "{} {}", elapsed.as_millis(), name) {
+        tracing::warn!("Stopped recording input: {}", e);
+        *recorder = None;
+    }
+}
+
+/// `key` as written in a recording, if it can be played back.
+fn format_key(key: &KeyEvent) -> Option<String> {
+    let name = match key.code {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::F(n) => format!("F{}", n),
+        code => NAMED_KEYS.iter().find(|(_, named)| *named == code)?.0.to_string(),
+    };
+    let mut text: String = MODIFIERS
+        .iter()
+        .filter(|(_, m)| key.modifiers.contains(*m))
+        .map(|(prefix, _)| *prefix)
+        .collect();
+    text.push_str(&name);
+    Some(text)
+}
+
+fn parse_key(text: &str) -> Option<KeyEvent> {
+    let mut rest = text;
+    let mut modifiers = KeyModifiers::NONE;
+    // A lone "+" is the plus key, not a modifier
+    while let Some((prefix, modifier)) = MODIFIERS.iter().find(|(p, _)| rest.len() > p.len() && rest.starts_with(p)) {
+        modifiers |= *modifier;
+        rest = &rest[prefix.len()..];
+    }
+    let mut chars = rest.chars();
+    let code = match (chars.next(), chars.next()) {
+        (Some(c), None) => KeyCode::Char(c),
+        _ if rest == "Space" => KeyCode::Char(' '),
+        _ => match rest.strip_prefix('F').and_then(|n| n.parse().ok()) {
+            Some(n) => KeyCode::F(n),
+            None => NAMED_KEYS.iter().find(|(name, _)| *name == rest)?.1,
+        },
+    };
+    Some(KeyEvent::new(code, modifiers))
+}
@@ -1,11 +1,36 @@
+#[macro_use]
+mod i18n;
+
+mod announce;
 mod app;
+mod campaign;
+mod clipboard;
+mod commands;
 mod event;
+mod graphics;
 mod input;
+mod keylog;
 mod network;
+mod practice;
+mod presence;
+mod presets;
+mod proxy;
+mod records;
+mod replay;
+mod share;
 mod solo;
+mod sound;
+mod stats;
+mod storage;
+mod streamer;
+mod suspend;
+mod title;
+mod tutorial;
 mod ui;
 
 use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
 
 use clap::Parser;
 use crossterm::{
@@ -15,6 +40,8 @@ use crossterm::{
 };
 use ratatui::prelude::*;
 
+use yaht_common::protocol::{self, DEFAULT_MAX_FRAME_LENGTH};
+
 /// YAHT Client - Multiplayer Yahtzee terminal game
 #[derive(Parser, Debug)]
 #[command(name = "yaht-client", version, about)]
@@ -31,9 +58,114 @@ struct Args {
     #[arg(long)]
     solo: bool,
 
-    /// Number of AI opponents in solo mode (1-5)
+    /// Number of AI opponents in solo mode (0-5, 0 for a solitaire run)
     #[arg(long, default_value_t = 1)]
     ai_count: u8,
+
+    /// Campaign mode: consecutive solo games against harder AI lineups
+    #[arg(long)]
+    campaign: bool,
+
+    /// Practice mode: graded dice scenarios
+    #[arg(long)]
+    practice: bool,
+
+    /// Guided tutorial for first-time players, followed by a solo game
+    #[arg(long)]
+    tutorial: bool,
+
+    /// Largest message sent to or accepted from the server, in bytes
+    #[arg(long, default_value_t = DEFAULT_MAX_FRAME_LENGTH)]
+    max_frame_length: usize,
+
+    /// Reach the server through a proxy: socks5://[USER:PASS@]HOST:PORT or
+    /// http://[USER:PASS@]HOST:PORT. Overrides `proxy` in config.json
+    #[arg(long)]
+    proxy: Option<String>,
+
+    /// Seconds to wait for the server to answer before giving up.
+    /// Overrides `connect_timeout_secs` in config.json
+    #[arg(long)]
+    connect_timeout: Option<u64>,
+
+    /// Seconds the server has to answer the handshake once connected.
+    /// Overrides `handshake_timeout_secs` in config.json
+    #[arg(long)]
+    handshake_timeout: Option<u64>,
+
+    /// Interface language. Overrides `lang` in config.json and the
+    /// system locale
+    #[arg(long, value_enum)]
+    lang: Option<i18n::Lang>,
+
+    /// Describe game events in plain sentences on the bottom line, for
+    /// screen readers
+    #[arg(long)]
+    screen_reader: bool,
+
+    /// Also pipe each description to this command's stdin, one per line,
+    /// e.g. "espeak". Implies --screen-reader
+    #[arg(long)]
+    speech_command: Option<String>,
+
+    /// How to play sound cues. Overrides `sounds.backend` in config.json
+    #[arg(long, value_enum)]
+    sound: Option<sound::SoundBackend>,
+
+    /// Presentation mode for streaming: hide the server address, enlarge
+    /// the dice and scoreboard
+    #[arg(long)]
+    streamer: bool,
+
+    /// Keep this file updated with the game as plain text, e.g. for an OBS
+    /// text source
+    #[arg(long)]
+    overlay_file: Option<PathBuf>,
+
+    /// Show dice, scores and results without animating them. Overrides
+    /// `animations` in config.json
+    #[arg(long)]
+    no_animations: bool,
+
+    /// Show rolls at once and keep highlights steady instead of blinking.
+    /// Overrides `motion.reduce_motion` in config.json
+    #[arg(long)]
+    reduce_motion: bool,
+
+    /// Discord application id to show games as Rich Presence under.
+    /// Needs a client built with the `discord` feature. Overrides
+    /// `discord_app_id` in config.json
+    #[arg(long)]
+    discord_app_id: Option<String>,
+
+    /// After an online game, go back to the room's waiting room on our own
+    /// unless a key is pressed within a few seconds. Overrides
+    /// `auto_rematch` in config.json
+    #[arg(long)]
+    auto_rematch: bool,
+
+    /// Draw the dice as pictures on terminals with Kitty graphics or
+    /// Sixel support. Needs a client built with the `graphics` feature
+    #[arg(long, value_enum, default_value_t = graphics::GraphicsMode::Auto)]
+    graphics: graphics::GraphicsMode,
+
+    /// Write every key pressed to this file, with timings, to reproduce a
+    /// session with --play-input. An existing file is overwritten
+    #[arg(long)]
+    record_input: Option<PathBuf>,
+
+    /// Press the keys recorded in this file, at their original pace,
+    /// before taking keys from the terminal
+    #[arg(long)]
+    play_input: Option<PathBuf>,
+
+    /// Print the servers listed in the public directory and exit
+    #[arg(long)]
+    list_servers: bool,
+
+    /// Public server directory URL. Overrides `directory` in config.json
+    #[arg(long)]
+    directory: Option<String>,
 }
 
 #[tokio::main]
@@ -47,27 +179,94 @@ async fn main() -> anyhow::Result<()> {
         .init();
 
     let args = Args::parse();
+    let config = storage::config();
+    if args.list_servers {
+        let url = args
+            .directory
+            .or(config.directory)
+            .ok_or_else(|| anyhow::anyhow!("no directory: pass --directory or set `directory` in config.json"))?;
+        return list_servers(&url).await;
+    }
+    i18n::set_lang(
+        args.lang
+            .or(config.lang)
+            .or_else(i18n::Lang::from_env)
+            .unwrap_or_default(),
+    );
+    let mut sounds = config.sounds;
+    if let Some(backend) = args.sound {
+        sounds.backend = backend;
+    }
+    sound::init(sounds);
+    ui::set_motion(
+        config.animations && !args.no_animations,
+        ui::MotionConfig {
+            reduce_motion: config.motion.reduce_motion || args.reduce_motion,
+            ..config.motion
+        },
+    );
+    graphics::init(args.graphics);
+    presence::init(args.discord_app_id.or(config.discord_app_id));
+    streamer::init(streamer::StreamerOptions {
+        enabled: args.streamer,
+        overlay_file: args.overlay_file,
+    });
+    let connect_options = network::ConnectOptions {
+        max_frame_length: args.max_frame_length,
+        proxy: args
+            .proxy
+            .or(config.proxy)
+            .map(|url| url.parse::<proxy::Proxy>())
+            .transpose()
+            .map_err(|e| anyhow::anyhow!("invalid proxy: {}", e))?,
+        timeout: Duration::from_secs(args.connect_timeout.unwrap_or(config.connect_timeout_secs)),
+        handshake_timeout: Duration::from_secs(
+            args.handshake_timeout.unwrap_or(config.handshake_timeout_secs),
+        ),
+    };
+    keylog::init(keylog::KeyLogOptions {
+        record: args.record_input,
+        play: args.play_input,
+    })?;
+    let announcer = if args.screen_reader || args.speech_command.is_some() {
+        Some(announce::Announcer::new(args.speech_command.as_deref())?)
+    } else {
+        None
+    };
 
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen)?;
+    title::save();
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
+    suspend::watch_signals();
 
     // Run app
-    let result = if args.solo {
+    let result = if args.tutorial {
+        let player_name = args.name.unwrap_or_else(|| "Player".to_string());
+        tutorial::run_tutorial(&mut terminal, player_name).await
+    } else if args.practice {
+        let player_name = args.name.unwrap_or_else(|| "Player".to_string());
+        practice::run_practice(&mut terminal, player_name).await
+    } else if args.campaign {
         let player_name = args.name.unwrap_or_else(|| "Player".to_string());
-        let ai_count = args.ai_count.clamp(1, 5);
-        solo::run_solo(&mut terminal, player_name, ai_count).await
+        campaign::run_campaign(&mut terminal, player_name).await
+    } else if args.solo {
+        let player_name = args.name.unwrap_or_else(|| "Player".to_string());
+        let ai_count = args.ai_count.min(5);
+        solo::run_solo(&mut terminal, player_name, ai_count, config.solo_rules).await
     } else {
-        app::run(&mut terminal, args.server, args.name).await
+        let auto_rematch = config.auto_rematch || args.auto_rematch;
+        app::run(&mut terminal, args.server, args.name, connect_options, announcer, auto_rematch).await
     };
 
     // Restore terminal
     disable_raw_mode()?;
     execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture)?;
     terminal.show_cursor()?;
+    title::restore();
 
     if let Err(e) = result {
         eprintln!("Error: {}", e);
@@ -75,3 +274,26 @@ async fn main() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Print the directory's servers, busiest first.
+async fn list_servers(url: &str) -> anyhow::Result<()> {
+    let mut servers = yaht_common::directory::fetch_servers(url).await?;
+    if servers.is_empty() {
+        println!("No public servers listed at {}", url);
+        return Ok(());
+    }
+    servers.sort_by(|a, b| b.players.cmp(&a.players).then_with(|| a.name.cmp(&b.name)));
+    println!("{:<24} {:<28} {:<10} {:>9}", "NAME", "ADDRESS", "REGION", "PLAYERS");
+    for s in servers {
+        let compatible = protocol::negotiate_protocol(s.protocol).is_some();
+        println!(
+            "{:<24} {:<28} {:<10} {:>9}{}",
+            s.name,
+            s.address,
+            s.region.as_deref().unwrap_or("-"),
+            format!("{}/{}", s.players, s.max_players),
+            if compatible { "" } else { "  (incompatible version)" }
+        );
+    }
+    Ok(())
+}
@@ -1,31 +1,146 @@
+use std::io;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Mutex, OnceLock, PoisonError};
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use futures::stream::FuturesUnordered;
 use futures::{SinkExt, StreamExt};
 use tokio::net::TcpStream;
 use tokio::sync::mpsc;
 
 use yaht_common::protocol::{
-    ClientMessage, ServerMessage, framed_transport, serialize_message, deserialize_message,
+    self, ClientMessage, EncodeError, ErrorCode, FrameEncoder, ServerMessage,
+    framed_transport_with_limit, deserialize_message,
 };
 
+use yaht_common::history::History;
+
+use crate::proxy::Proxy;
+
+/// Port used when the server address does not name one.
+pub const DEFAULT_PORT: u16 = 9876;
+
+/// Messages to and from the server kept for the debug overlay.
+const TRAFFIC_LOG: usize = 10;
+/// Longest message description kept in the traffic log.
+const TRAFFIC_LINE_CHARS: usize = 160;
+
+/// How long to wait on one address before also trying the next (RFC 8305).
+const CONNECTION_ATTEMPT_DELAY: Duration = Duration::from_millis(250);
+
+/// Delays before each attempt to reach the server again after the
+/// connection dropped, the last one repeating. Together they cover the
+/// server's default minute of holding a seat.
+const RECONNECT_BACKOFF: [Duration; 4] = [
+    Duration::from_secs(1),
+    Duration::from_secs(2),
+    Duration::from_secs(4),
+    Duration::from_secs(8),
+];
+const RECONNECT_ATTEMPTS: usize = 10;
+
+/// How to reach the server.
+#[derive(Debug, Clone)]
+pub struct ConnectOptions {
+    pub max_frame_length: usize,
+    pub proxy: Option<Proxy>,
+    /// Limit on resolving, any proxy handshake and the TCP connect together.
+    pub timeout: Duration,
+    /// Limit on the server answering our `Hello` once connected.
+    pub handshake_timeout: Duration,
+}
+
+/// An open connection and the server's answer to our `Hello`.
+pub type Handshake = (mpsc::Sender<ClientMessage>, mpsc::Receiver<ServerMessage>, ServerMessage);
+
+/// Game actions sent with a request id and not yet acknowledged. After a
+/// dropped connection is resumed they are all sent again, and the server
+/// skips any it had already applied.
+#[derive(Debug, Default)]
+pub struct PendingActions {
+    next_id: u64,
+    unacked: Vec<(u64, ClientMessage)>,
+}
+
+impl PendingActions {
+    /// Tag an action with the next request id, keeping it until acknowledged.
+    pub fn track(&mut self, action: ClientMessage) -> ClientMessage {
+        self.next_id += 1;
+        let tracked = ClientMessage::Tracked {
+            request_id: self.next_id,
+            action: Box::new(action),
+        };
+        self.unacked.push((self.next_id, tracked.clone()));
+        tracked
+    }
+
+    /// The server has handled this action, and so every one before it.
+    pub fn ack(&mut self, request_id: u64) {
+        self.unacked.retain(|&(id, _)| id > request_id);
+    }
+
+    /// Every unacknowledged action, with the id it was first sent with.
+    pub fn resend(&self) -> Vec<ClientMessage> {
+        self.unacked.iter().map(|(_, msg)| msg.clone()).collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.unacked.clear();
+    }
+
+    pub fn count(&self) -> usize {
+        self.unacked.len()
+    }
+}
+
 /// Connect to the server and return channels for bidirectional communication.
+///
+/// `addr` is `host:port`, `[ipv6]:port`, or a host or IP alone for the
+/// default port. Frames over `max_frame_length` bytes are refused in both
+/// directions and reported as a local `Error` message rather than breaking
+/// the connection.
 pub async fn connect(
     addr: &str,
+    options: &ConnectOptions,
 ) -> anyhow::Result<(mpsc::Sender<ClientMessage>, mpsc::Receiver<ServerMessage>)> {
-    let stream = TcpStream::connect(addr).await?;
-    let transport = framed_transport(stream);
+    let max_frame_length = options.max_frame_length;
+    let (host, port) = parse_server_addr(addr)?;
+    let stream = tokio::time::timeout(options.timeout, async {
+        match &options.proxy {
+            Some(proxy) => proxy.connect(&host, port).await,
+            None => connect_direct(&host, port).await,
+        }
+    })
+    .await
+    .map_err(|_| anyhow!("timed out after {}s reaching {}", options.timeout.as_secs(), addr))??;
+    let transport = framed_transport_with_limit(stream, max_frame_length);
     let (mut sink, mut stream) = transport.split();
 
     let (client_tx, mut client_rx) = mpsc::channel::<ClientMessage>(64);
     let (server_tx, server_rx) = mpsc::channel::<ServerMessage>(64);
 
     // Writer task: client_rx -> TCP sink
+    let local_tx = server_tx.clone();
     tokio::spawn(async move {
+        let mut encoder = FrameEncoder::with_max_frame_length(max_frame_length);
         while let Some(msg) = client_rx.recv().await {
-            match serialize_message(&msg) {
+            // Only the name: outgoing messages can carry room passwords
+            log_traffic(format!("-> {}", msg.name()));
+            match encoder.encode(&msg) {
                 Ok(bytes) => {
-                    if sink.send(bytes.into()).await.is_err() {
+                    if sink.send(bytes).await.is_err() {
                         break;
                     }
                 }
+                Err(e @ EncodeError::TooLarge { .. }) => {
+                    let _ = local_tx
+                        .send(ServerMessage::Error {
+                            code: ErrorCode::FrameTooLarge,
+                            message: format!("Not sent: {}", e),
+                        })
+                        .await;
+                }
                 Err(e) => {
                     tracing::error!("Failed to serialize client message: {}", e);
                 }
@@ -35,9 +150,29 @@ pub async fn connect(
 
     // Reader task: TCP stream -> server_tx
     tokio::spawn(async move {
-        while let Some(Ok(frame)) = stream.next().await {
+        while let Some(frame) = stream.next().await {
+            let frame = match frame {
+                Ok(frame) => frame,
+                Err(e) if protocol::is_frame_too_large(&e) => {
+                    let _ = server_tx
+                        .send(ServerMessage::Error {
+                            code: ErrorCode::FrameTooLarge,
+                            message: format!(
+                                "Server sent a message over the {} byte limit",
+                                max_frame_length
+                            ),
+                        })
+                        .await;
+                    break;
+                }
+                Err(e) => {
+                    tracing::warn!("Connection error: {}", e);
+                    break;
+                }
+            };
             match deserialize_message::<ServerMessage>(&frame) {
                 Ok(msg) => {
+                    log_traffic(format!("<- {:?}", msg));
                     if server_tx.send(msg).await.is_err() {
                         break;
                     }
@@ -51,3 +186,136 @@ pub async fn connect(
 
     Ok((client_tx, server_rx))
 }
+
+fn traffic() -> &'static Mutex<History<String>> {
+    static TRAFFIC: OnceLock<Mutex<History<String>>> = OnceLock::new();
+    TRAFFIC.get_or_init(|| Mutex::new(History::new(TRAFFIC_LOG)))
+}
+
+fn log_traffic(mut line: String) {
+    if let Some((cut, _)) = line.char_indices().nth(TRAFFIC_LINE_CHARS) {
+        line.truncate(cut);
+        line.push('…');
+    }
+    traffic().lock().unwrap_or_else(PoisonError::into_inner).push(line);
+}
+
+/// The last messages sent to and received from the server, oldest first,
+/// `->` marking those sent.
+pub fn recent_traffic() -> Vec<String> {
+    traffic().lock().unwrap_or_else(PoisonError::into_inner).iter().cloned().collect()
+}
+
+/// Connect, send `hello` and wait for the server's answer, a `Welcome`
+/// or `HandshakeError`. Something that accepts the connection but never
+/// answers fails after `options.handshake_timeout`.
+pub async fn handshake(addr: &str, options: &ConnectOptions, hello: ClientMessage) -> anyhow::Result<Handshake> {
+    let (tx, mut rx) = connect(addr, options).await?;
+    tx.send(hello)
+        .await
+        .map_err(|_| anyhow!("connection to {} closed", addr))?;
+    match tokio::time::timeout(options.handshake_timeout, rx.recv()).await {
+        Ok(Some(reply)) => Ok((tx, rx, reply)),
+        Ok(None) => Err(anyhow!("{} closed the connection without answering", addr)),
+        Err(_) => Err(anyhow!(
+            "no answer from {} after {}s; is it a yaht server?",
+            addr,
+            options.handshake_timeout.as_secs()
+        )),
+    }
+}
+
+/// Try to reach the server again after the connection dropped, backing
+/// off between attempts. Returns the first handshake completed, or the
+/// last error once every attempt has failed.
+pub async fn reconnect(addr: &str, options: &ConnectOptions, hello: ClientMessage) -> anyhow::Result<Handshake> {
+    let mut attempt = 0;
+    loop {
+        tokio::time::sleep(RECONNECT_BACKOFF[attempt.min(RECONNECT_BACKOFF.len() - 1)]).await;
+        attempt += 1;
+        match handshake(addr, options, hello.clone()).await {
+            Ok(handshake) => return Ok(handshake),
+            Err(e) if attempt == RECONNECT_ATTEMPTS => return Err(e),
+            Err(e) => tracing::debug!("Reconnect attempt {} failed: {:#}", attempt, e),
+        }
+    }
+}
+
+/// Split a server address into host and port. IPv6 literals may be
+/// bracketed, and must be when a port is given.
+pub fn parse_server_addr(addr: &str) -> anyhow::Result<(String, u16)> {
+    let addr = addr.trim();
+    if let Ok(socket) = addr.parse::<SocketAddr>() {
+        return Ok((socket.ip().to_string(), socket.port()));
+    }
+    let bare = addr.strip_prefix('[').and_then(|a| a.strip_suffix(']')).unwrap_or(addr);
+    if let Ok(ip) = bare.parse::<IpAddr>() {
+        return Ok((ip.to_string(), DEFAULT_PORT));
+    }
+    match addr.rsplit_once(':') {
+        Some((host, port)) if !host.contains(':') => {
+            let port = port
+                .parse()
+                .map_err(|_| anyhow!("'{}' is not a valid port", port))?;
+            if host.is_empty() {
+                return Err(anyhow!("missing host in '{}'", addr));
+            }
+            Ok((host.to_string(), port))
+        }
+        Some(_) => Err(anyhow!("put IPv6 addresses in brackets, e.g. [{}]:{}", bare, DEFAULT_PORT)),
+        None if addr.is_empty() => Err(anyhow!("enter a server address")),
+        None => Ok((addr.to_string(), DEFAULT_PORT)),
+    }
+}
+
+/// Resolve `host` and connect to whichever of its addresses answers
+/// first, alternating IPv6 and IPv4 and starting a new attempt every
+/// `CONNECTION_ATTEMPT_DELAY` while earlier ones are pending.
+async fn connect_direct(host: &str, port: u16) -> anyhow::Result<TcpStream> {
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, port))
+        .await
+        .with_context(|| format!("cannot resolve '{}'", host))?
+        .collect();
+    let tried = addrs.len();
+    let mut pending = interleave_families(addrs).into_iter();
+    let mut attempts = FuturesUnordered::new();
+    let mut last_error: Option<io::Error> = None;
+
+    loop {
+        if let Some(addr) = pending.next() {
+            attempts.push(async move { (addr, TcpStream::connect(addr).await) });
+        } else if attempts.is_empty() {
+            let e = last_error.map_or_else(|| "no addresses found".to_string(), |e| e.to_string());
+            return Err(match tried {
+                0 | 1 => anyhow!("cannot connect to {}:{}: {}", host, port, e),
+                n => anyhow!("cannot connect to {}:{} ({} addresses tried): {}", host, port, n, e),
+            });
+        }
+
+        tokio::select! {
+            Some((addr, result)) = attempts.next() => match result {
+                Ok(stream) => return Ok(stream),
+                Err(e) => {
+                    tracing::debug!("Connecting to {} failed: {}", addr, e);
+                    last_error = Some(e);
+                }
+            },
+            _ = tokio::time::sleep(CONNECTION_ATTEMPT_DELAY), if pending.len() > 0 => {}
+        }
+    }
+}
+
+/// Alternate address families, starting with the resolver's first choice.
+fn interleave_families(addrs: Vec<SocketAddr>) -> Vec<SocketAddr> {
+    let prefer_v6 = addrs.first().is_some_and(SocketAddr::is_ipv6);
+    let (first, second): (Vec<_>, Vec<_>) = addrs.into_iter().partition(|a| a.is_ipv6() == prefer_v6);
+    let mut first = first.into_iter();
+    let mut second = second.into_iter();
+    let mut ordered = Vec::new();
+    loop {
+        match (first.next(), second.next()) {
+            (None, None) => return ordered,
+            (a, b) => ordered.extend(a.into_iter().chain(b)),
+        }
+    }
+}
@@ -0,0 +1,343 @@
+use std::io;
+
+use ratatui::backend::CrosstermBackend;
+use ratatui::Terminal;
+use uuid::Uuid;
+
+use yaht_common::ai::{self, HoldEvaluation};
+use yaht_common::dice::{DiceSet, MAX_ROLLS};
+use yaht_common::game::{GameConfig, GameState, TurnPhase};
+use yaht_common::history::History;
+use yaht_common::player::{Player, Scorecard};
+use yaht_common::scoring::{self, Category};
+
+use crate::event::{self, Input, RenderGate};
+use crate::graphics;
+use crate::input::{self, Action};
+use crate::ui::game::{GameScreen, CHAT_HISTORY};
+use crate::ui::help_popup;
+
+/// A curated practice position.
+pub struct Scenario {
+    pub description: &'static str,
+    pub dice: [u8; 5],
+    pub rolls_left: u8,
+    /// Categories already filled, with their recorded scores.
+    pub filled: &'static [(Category, u16)],
+}
+
+pub const SCENARIOS: &[Scenario] = &[
+    Scenario {
+        description: "Two rolls left and only the small straight still open among the straights.",
+        dice: [2, 3, 4, 6, 6],
+        rolls_left: 2,
+        filled: &[
+            (Category::LargeStraight, 40),
+            (Category::FullHouse, 25),
+            (Category::Ones, 3),
+            (Category::Twos, 6),
+        ],
+    },
+    Scenario {
+        description: "Opening roll of the game: three fives on the table.",
+        dice: [5, 5, 5, 2, 3],
+        rolls_left: 2,
+        filled: &[],
+    },
+    Scenario {
+        description: "Four ones with one roll left. Yahtzee is open, Ones is taken.",
+        dice: [1, 1, 1, 1, 4],
+        rolls_left: 1,
+        filled: &[(Category::Ones, 3), (Category::Chance, 22)],
+    },
+    Scenario {
+        description: "Two pairs and one roll left. Full House is open.",
+        dice: [3, 3, 4, 4, 6],
+        rolls_left: 1,
+        filled: &[(Category::Threes, 9), (Category::Fours, 12), (Category::Chance, 24)],
+    },
+    Scenario {
+        description: "Out of rolls with a near-straight. Pick the best category.",
+        dice: [1, 2, 3, 4, 6],
+        rolls_left: 0,
+        filled: &[(Category::Chance, 23), (Category::Ones, 2)],
+    },
+    Scenario {
+        description: "Late game: only Ones, Twos and Yahtzee remain. No rolls left.",
+        dice: [2, 2, 5, 5, 6],
+        rolls_left: 0,
+        filled: &[
+            (Category::Threes, 9),
+            (Category::Fours, 12),
+            (Category::Fives, 15),
+            (Category::Sixes, 18),
+            (Category::ThreeOfAKind, 22),
+            (Category::FourOfAKind, 0),
+            (Category::FullHouse, 25),
+            (Category::SmallStraight, 30),
+            (Category::LargeStraight, 0),
+            (Category::Chance, 21),
+        ],
+    },
+    Scenario {
+        description: "Chasing the upper bonus: Fours is the last open upper category.",
+        dice: [4, 4, 4, 5, 6],
+        rolls_left: 2,
+        filled: &[
+            (Category::Ones, 3),
+            (Category::Twos, 6),
+            (Category::Threes, 9),
+            (Category::Fives, 15),
+            (Category::Sixes, 18),
+            (Category::Chance, 25),
+        ],
+    },
+    Scenario {
+        description: "A broken straight with two rolls to go. Both straights are open.",
+        dice: [1, 3, 4, 5, 5],
+        rolls_left: 2,
+        filled: &[(Category::Fives, 15), (Category::Chance, 19)],
+    },
+];
+
+/// The optimal decision for a scenario.
+enum BestPlay {
+    Hold(HoldEvaluation),
+    Score(Category, u16),
+}
+
+impl BestPlay {
+    fn value(&self) -> f64 {
+        match self {
+            BestPlay::Hold(eval) => eval.expected_score,
+            BestPlay::Score(_, score) => *score as f64,
+        }
+    }
+
+    fn describe(&self, dice: &[u8; 5]) -> String {
+        match self {
+            BestPlay::Hold(eval) => format!(
+                "{} and roll (expected {:.1} pts)",
+                describe_hold(dice, &eval.held),
+                eval.expected_score
+            ),
+            BestPlay::Score(cat, score) => format!("score {} now for {} pts", cat.display_name(), score),
+        }
+    }
+}
+
+pub async fn run_practice(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    player_name: String,
+) -> anyhow::Result<()> {
+    let mut keys = event::spawn_key_reader();
+    let player_id = Uuid::new_v4();
+
+    let mut index = 0;
+    let mut attempted = 0u32;
+    let mut optimal = 0u32;
+    let mut show_help = false;
+
+    let mut game = scenario_game(&SCENARIOS[index], player_id, &player_name);
+    let mut gs = scenario_screen(&game, player_id, index, History::new(CHAT_HISTORY));
+    let mut graded = false;
+
+    let mut render = RenderGate::new();
+
+    loop {
+        if render.should_draw(terminal)? {
+            terminal.draw(|frame| {
+                gs.draw(frame);
+                if show_help {
+                    help_popup::draw_help_popup(frame, &gs.game_state.rules.scoring);
+                }
+            })?;
+            graphics::flush(terminal, &mut render, show_help)?;
+        }
+
+        let key = tokio::select! {
+            k = keys.recv() => match k {
+                Some(Input::Key(key)) => key,
+                // Redrawn at the new size on the way round
+                Some(Input::Resize) => continue,
+                None => return Ok(()),
+            },
+            _ = tokio::time::sleep(event::tick_rate(gs.is_animating())) => {
+                // Tick for animations
+                if gs.tick() {
+                    render.mark_dirty();
+                }
+                continue;
+            }
+        };
+        render.mark_dirty();
+
+        if show_help {
+            show_help = false;
+            continue;
+        }
+
+        let app_screen = crate::app::Screen::Game(Box::new(gs.clone()));
+        let action = match input::map_key(key, &app_screen, false) {
+            Some(action) => action,
+            None => continue,
+        };
+
+        match action {
+            Action::Quit => return Ok(()),
+            Action::ShowHelp => show_help = true,
+            Action::RollDice | Action::ConfirmScore if graded => {
+                index = (index + 1) % SCENARIOS.len();
+                game = scenario_game(&SCENARIOS[index], player_id, &player_name);
+                gs = scenario_screen(&game, player_id, index, gs.chat_messages);
+                graded = false;
+            }
+            Action::ToggleHold(idx) => {
+                gs.toggle_hold(idx);
+            }
+            Action::NavigateUp => gs.select_prev_category(),
+            Action::NavigateDown => gs.select_next_category(),
+            Action::RollDice | Action::ConfirmScore => {
+                let scenario = &SCENARIOS[index];
+                let turn = game.turn.as_ref().unwrap();
+                let scorecard = &game.current_player().scorecard;
+                let best = best_play(&turn.dice, scorecard, scenario.rolls_left);
+
+                let (chosen, value) = if matches!(action, Action::RollDice) {
+                    if scenario.rolls_left == 0 {
+                        gs.status_message = Some("No rolls left - pick a category to score.".into());
+                        continue;
+                    }
+                    let held = gs.get_held_array();
+                    let eval = ai::evaluate_holds(&turn.dice, scorecard, scenario.rolls_left)
+                        .into_iter()
+                        .find(|e| e.held == held)
+                        .unwrap();
+                    (
+                        format!(
+                            "{} and roll (expected {:.1} pts)",
+                            describe_hold(&scenario.dice, &held),
+                            eval.expected_score
+                        ),
+                        eval.expected_score,
+                    )
+                } else {
+                    let cat = match gs.selected_category() {
+                        Some(cat) => cat,
+                        None => continue,
+                    };
+                    let score = scoring::compute_score(cat, &scenario.dice);
+                    (
+                        format!("score {} for {} pts", cat.display_name(), score),
+                        score as f64,
+                    )
+                };
+
+                let verdict = grade(value, best.value());
+                attempted += 1;
+                if verdict == "Optimal" {
+                    optimal += 1;
+                }
+                gs.chat_messages.push(format!("[System] You: {}", chosen));
+                gs.chat_messages
+                    .push(format!("[System] Best: {}", best.describe(&scenario.dice)));
+                gs.chat_messages.push(format!("[System] Verdict: {}", verdict));
+                gs.status_message = Some(format!(
+                    "{} - {}/{} optimal. Press [Enter] for the next scenario.",
+                    verdict, optimal, attempted
+                ));
+                graded = true;
+            }
+            _ => {}
+        }
+    }
+}
+
+fn scenario_game(scenario: &Scenario, player_id: Uuid, player_name: &str) -> GameState {
+    let mut player = Player::new(player_id, player_name.to_string());
+    for &(cat, score) in scenario.filled {
+        let _ = player.scorecard.record(cat, score);
+    }
+
+    let mut game = GameState::new(vec![player], GameConfig::default());
+    game.start_solo().expect("practice game has one player");
+    game.round = scenario.filled.len() as u8 + 1;
+
+    let rolls_used = MAX_ROLLS - scenario.rolls_left;
+    let turn = game.turn.as_mut().unwrap();
+    for (die, &value) in turn.dice.dice.iter_mut().zip(scenario.dice.iter()) {
+        die.value = value;
+        die.held = false;
+    }
+    turn.rolls_used = rolls_used;
+    turn.phase = if scenario.rolls_left == 0 {
+        TurnPhase::MustScore
+    } else {
+        TurnPhase::Rolling { rolls_used }
+    };
+    game
+}
+
+fn scenario_screen(
+    game: &GameState,
+    player_id: Uuid,
+    index: usize,
+    mut log: History<String>,
+) -> GameScreen {
+    let scenario = &SCENARIOS[index];
+    let mut gs = GameScreen::new(player_id, game.snapshot());
+    log.push(format!(
+        "[System] Scenario {}/{}: {}",
+        index + 1,
+        SCENARIOS.len(),
+        scenario.description
+    ));
+    gs.chat_messages = log;
+    gs.status_message = Some(if scenario.rolls_left > 0 {
+        "Toggle holds [1-5] then [R] to commit, or pick a category and [S] to score now.".into()
+    } else {
+        "Pick a category with [j]/[k] and press [S] to score.".into()
+    });
+    gs
+}
+
+fn best_play(dice: &DiceSet, scorecard: &Scorecard, rolls_left: u8) -> BestPlay {
+    let values = dice.values();
+    let (best_cat, best_score) = scorecard
+        .available_categories()
+        .into_iter()
+        .map(|cat| (cat, scoring::compute_score(cat, &values)))
+        .max_by_key(|&(_, score)| score)
+        .unwrap_or((Category::Chance, 0));
+
+    match ai::best_hold(dice, scorecard, rolls_left) {
+        Some(eval) if eval.expected_score > best_score as f64 => BestPlay::Hold(eval),
+        _ => BestPlay::Score(best_cat, best_score),
+    }
+}
+
+fn grade(value: f64, best: f64) -> &'static str {
+    if value >= best - 0.05 {
+        "Optimal"
+    } else if value >= best * 0.9 {
+        "Good"
+    } else if value >= best * 0.75 {
+        "Fair"
+    } else {
+        "Miss"
+    }
+}
+
+fn describe_hold(dice: &[u8; 5], held: &[bool; 5]) -> String {
+    let kept: Vec<String> = dice
+        .iter()
+        .zip(held.iter())
+        .filter(|(_, &h)| h)
+        .map(|(v, _)| v.to_string())
+        .collect();
+    match kept.len() {
+        0 => "reroll everything".to_string(),
+        5 => "keep everything".to_string(),
+        _ => format!("keep {}", kept.join(" ")),
+    }
+}
@@ -0,0 +1,167 @@
+//! Discord Rich Presence: the round and how we stand, shown on the
+//! player's Discord profile while a game is on.
+//!
+//! Built with the `discord` feature and given a Discord application id,
+//! the client talks to the desktop app over its local IPC socket from a
+//! thread of its own, so a missing or slow Discord never holds up the UI.
+//! Presence is only sent when its text changes.
+
+use crate::ui::game::GameScreen;
+
+/// Start publishing presence as the application `app_id`, if given.
+#[cfg(feature = "discord")]
+pub fn init(app_id: Option<String>) {
+    if let Some(app_id) = app_id {
+        imp::spawn(app_id);
+    }
+}
+
+#[cfg(not(feature = "discord"))]
+pub fn init(app_id: Option<String>) {
+    if app_id.is_some() {
+        tracing::warn!("This client was built without the discord feature; not showing Rich Presence");
+    }
+}
+
+/// Show `game` as the current activity.
+pub fn show_game(game: &GameScreen) {
+    if enabled() {
+        set(Some(activity(game)));
+    }
+}
+
+/// Show no activity, e.g. in the lobby.
+pub fn clear() {
+    set(None);
+}
+
+#[cfg(feature = "discord")]
+fn enabled() -> bool {
+    imp::enabled()
+}
+
+#[cfg(not(feature = "discord"))]
+fn enabled() -> bool {
+    false
+}
+
+#[cfg(feature = "discord")]
+fn set(lines: Option<(String, String)>) {
+    imp::set(lines);
+}
+
+#[cfg(not(feature = "discord"))]
+fn set(_lines: Option<(String, String)>) {}
+
+/// The activity's two lines: the round and whose turn, then our standing.
+fn activity(game: &GameScreen) -> (String, String) {
+    let state = &game.game_state;
+    let mut details = tr!(Round, state.round.min(state.total_rounds), state.total_rounds);
+    if game.is_my_turn(&game.my_player_id) {
+        details = format!("{} – {}", details, tr!(TitleYourTurn));
+    }
+
+    let standing = match state.players.iter().find(|p| p.id == game.my_player_id) {
+        None => tr!(PresenceWatching).to_string(),
+        Some(me) => {
+            let mine = me.scorecard.grand_total();
+            let best_other = state
+                .players
+                .iter()
+                .filter(|p| p.id != me.id && !p.forfeited)
+                .map(|p| p.scorecard.grand_total())
+                .max();
+            match best_other {
+                Some(best) if best > mine => tr!(PresenceTrailing, best - mine, mine),
+                Some(best) if best == mine => tr!(PresenceTied, mine),
+                _ => tr!(PresenceLeading, mine),
+            }
+        }
+    };
+    (details, standing)
+}
+
+#[cfg(feature = "discord")]
+mod imp {
+    use std::sync::mpsc;
+    use std::sync::{Mutex, OnceLock, PoisonError};
+
+    use discord_rich_presence::activity::{Activity, Timestamps};
+    use discord_rich_presence::{DiscordIpc, DiscordIpcClient};
+
+    type Lines = Option<(String, String)>;
+
+    struct Presence {
+        tx: mpsc::Sender<Lines>,
+        /// What was last sent, to skip unchanged frames.
+        last: Mutex<Lines>,
+    }
+
+    static PRESENCE: OnceLock<Presence> = OnceLock::new();
+
+    pub fn spawn(app_id: String) {
+        let (tx, rx) = mpsc::channel::<Lines>();
+        std::thread::spawn(move || run(app_id, rx));
+        let _ = PRESENCE.set(Presence {
+            tx,
+            last: Mutex::new(None),
+        });
+    }
+
+    pub fn enabled() -> bool {
+        PRESENCE.get().is_some()
+    }
+
+    pub fn set(lines: Lines) {
+        let Some(presence) = PRESENCE.get() else {
+            return;
+        };
+        let mut last = presence.last.lock().unwrap_or_else(PoisonError::into_inner);
+        if *last != lines {
+            let _ = presence.tx.send(lines.clone());
+            *last = lines;
+        }
+    }
+
+    /// Send each update to Discord, connecting when needed. An update that
+    /// fails drops the connection, and the next one tries again.
+    fn run(app_id: String, rx: mpsc::Receiver<Lines>) {
+        let mut client: Option<DiscordIpcClient> = None;
+        let mut since: Option<i64> = None;
+        for lines in rx {
+            if lines.is_some() && since.is_none() {
+                since = Some(chrono::Utc::now().timestamp_millis());
+            } else if lines.is_none() {
+                since = None;
+            }
+            if client.is_none() {
+                let mut new = DiscordIpcClient::new(&app_id);
+                match new.connect() {
+                    Ok(()) => client = Some(new),
+                    Err(e) => {
+                        tracing::debug!("Discord is not reachable: {}", e);
+                        continue;
+                    }
+                }
+            }
+            let Some(ipc) = client.as_mut() else {
+                continue;
+            };
+            let sent = match &lines {
+                Some((details, state)) => {
+                    let mut activity = Activity::new().details(details.as_str()).state(state.as_str());
+                    if let Some(start) = since {
+                        activity = activity.timestamps(Timestamps::new().start(start));
+                    }
+                    ipc.set_activity(activity)
+                }
+                None => ipc.clear_activity(),
+            };
+            if let Err(e) = sent {
+                tracing::debug!("Lost the connection to Discord: {}", e);
+                let _ = ipc.close();
+                client = None;
+            }
+        }
+    }
+}
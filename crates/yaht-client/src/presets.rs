@@ -0,0 +1,124 @@
+//! Room presets: the settings of a regular game night, kept in
+//! `presets.json` in the data directory and offered when creating a room.
+
+use serde::{Deserialize, Serialize};
+use yaht_common::game::GameConfig;
+use yaht_common::protocol::{Announcements, ClientMessage, RoomSnapshot};
+
+const PRESETS_FILE: &str = "presets.json";
+/// Stands for our player name in a preset's room name.
+const PLAYER_PLACEHOLDER: &str = "{player}";
+
+/// Settings to create a room with. Fields left out of `presets.json` take
+/// their defaults.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RoomPreset {
+    /// What the preset is called in the lobby.
+    pub name: String,
+    /// Name for the room, with `{player}` standing for ours. Empty for the
+    /// usual name.
+    pub room_name: String,
+    pub max_players: u8,
+    /// Only ever set by editing `presets.json`; rooms don't tell their
+    /// password back.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    pub rules: GameConfig,
+    pub locked: bool,
+    pub auto_start: bool,
+    pub max_spectators: Option<u8>,
+    pub announcements: Announcements,
+    pub shuffle_order: bool,
+    pub nudge_secs: u32,
+}
+
+impl Default for RoomPreset {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            room_name: String::new(),
+            max_players: 6,
+            password: None,
+            rules: GameConfig::default(),
+            locked: false,
+            auto_start: false,
+            max_spectators: None,
+            announcements: Announcements::default(),
+            shuffle_order: false,
+            nudge_secs: 0,
+        }
+    }
+}
+
+impl RoomPreset {
+    /// A preset named `name` with `room`'s settings.
+    pub fn from_room(name: String, room: &RoomSnapshot, player_name: &str) -> Self {
+        Self {
+            name,
+            room_name: room.room_name.replace(player_name, PLAYER_PLACEHOLDER),
+            max_players: room.max_players,
+            password: None,
+            rules: room.rules,
+            locked: room.locked,
+            auto_start: room.auto_start,
+            max_spectators: room.max_spectators,
+            announcements: room.announcements,
+            shuffle_order: room.shuffle_order,
+            nudge_secs: room.nudge_secs,
+        }
+    }
+
+    /// The name for a room created from this preset.
+    pub fn room_name(&self, player_name: &str) -> String {
+        if self.room_name.trim().is_empty() {
+            tr!(DefaultRoomName, player_name)
+        } else {
+            self.room_name.replace(PLAYER_PLACEHOLDER, player_name).trim().to_string()
+        }
+    }
+
+    /// The message creating a room from this preset. The settings
+    /// `CreateRoom` has no room for follow in `settings_message` once the
+    /// room is ours.
+    pub fn create_message(&self, player_name: &str) -> ClientMessage {
+        ClientMessage::CreateRoom {
+            room_name: self.room_name(player_name),
+            max_players: self.max_players,
+            password: self.password.clone().filter(|p| !p.is_empty()),
+            rules: self.rules,
+        }
+    }
+
+    pub fn settings_message(&self) -> ClientMessage {
+        ClientMessage::UpdateRoomSettings {
+            max_players: None,
+            password: None,
+            rules: None,
+            locked: Some(self.locked),
+            auto_start: Some(self.auto_start),
+            max_spectators: self.max_spectators,
+            seed: None,
+            announcements: Some(self.announcements),
+            shuffle_order: Some(self.shuffle_order),
+            nudge_secs: Some(self.nudge_secs),
+        }
+    }
+}
+
+pub fn load() -> Vec<RoomPreset> {
+    crate::storage::load(PRESETS_FILE)
+}
+
+/// Add `preset` to `presets` and save them, replacing any preset of the
+/// same name but keeping its password.
+pub fn save(presets: &mut Vec<RoomPreset>, mut preset: RoomPreset) -> anyhow::Result<()> {
+    match presets.iter_mut().find(|p| p.name.eq_ignore_ascii_case(&preset.name)) {
+        Some(existing) => {
+            preset.password = existing.password.take();
+            *existing = preset;
+        }
+        None => presets.push(preset),
+    }
+    crate::storage::save(PRESETS_FILE, presets)
+}
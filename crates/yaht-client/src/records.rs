@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+
+const RECORDS_FILE: &str = "solitaire.json";
+/// How many recent games the rolling average covers.
+pub const RECENT_GAMES: usize = 10;
+/// How many top scores are kept.
+const TOP_SCORES: usize = 5;
+
+/// Personal records for solitaire (`--solo --ai-count 0`) games.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SoloRecords {
+    /// Best scores, highest first.
+    pub top_scores: Vec<u16>,
+    /// Most recent scores, oldest first.
+    pub recent: Vec<u16>,
+    pub games_played: u32,
+}
+
+impl SoloRecords {
+    pub fn load() -> Self {
+        crate::storage::load(RECORDS_FILE)
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        crate::storage::save(RECORDS_FILE, self)
+    }
+
+    pub fn best(&self) -> Option<u16> {
+        self.top_scores.first().copied()
+    }
+
+    /// Average over the last `RECENT_GAMES` games.
+    pub fn recent_average(&self) -> Option<f64> {
+        if self.recent.is_empty() {
+            return None;
+        }
+        let sum: u32 = self.recent.iter().map(|&s| s as u32).sum();
+        Some(sum as f64 / self.recent.len() as f64)
+    }
+
+    /// Record a finished game. Returns true if it set a new personal best.
+    pub fn record(&mut self, score: u16) -> bool {
+        let new_best = self.best().is_none_or(|best| score > best);
+
+        self.games_played += 1;
+        self.recent.push(score);
+        if self.recent.len() > RECENT_GAMES {
+            self.recent.remove(0);
+        }
+
+        self.top_scores.push(score);
+        self.top_scores.sort_by_key(|&s| std::cmp::Reverse(s));
+        self.top_scores.truncate(TOP_SCORES);
+
+        new_best
+    }
+
+    /// One-line summary, e.g. "Best: 254 | Avg (last 10): 212.3 | Games: 14".
+    pub fn summary(&self) -> String {
+        match (self.best(), self.recent_average()) {
+            (Some(best), Some(avg)) => format!(
+                "Best: {} | Avg (last {}): {:.1} | Games: {}",
+                best,
+                self.recent.len(),
+                avg,
+                self.games_played
+            ),
+            _ => "No games recorded yet".to_string(),
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use yaht_common::protocol::ServerMessage;
+
+const REPLAY_STEP: Duration = Duration::from_millis(500);
+
+/// Paces a spectator's catch-up replay, holding back live messages until it is done.
+#[derive(Debug)]
+pub struct SpectatorReplay {
+    queue: VecDeque<ServerMessage>,
+    received_all: bool,
+    skipping: bool,
+    total: u32,
+    played: u32,
+    last_step: Instant,
+}
+
+impl SpectatorReplay {
+    pub fn new(event_count: u32) -> Self {
+        Self {
+            queue: VecDeque::new(),
+            received_all: false,
+            skipping: false,
+            total: event_count,
+            played: 0,
+            last_step: Instant::now(),
+        }
+    }
+
+    /// Queue a message that arrived while the replay is running.
+    pub fn push(&mut self, msg: ServerMessage) {
+        match msg {
+            ServerMessage::ReplayEvent { event } => self.queue.push_back(*event),
+            ServerMessage::ReplayFinished => self.received_all = true,
+            other => self.queue.push_back(other),
+        }
+    }
+
+    /// Fast-forward: play everything queued on the next tick.
+    pub fn skip(&mut self) {
+        self.skipping = true;
+    }
+
+    /// Messages due for playback on this tick.
+    pub fn due(&mut self) -> Vec<ServerMessage> {
+        let count = if self.skipping {
+            self.queue.len()
+        } else if self.last_step.elapsed() >= REPLAY_STEP {
+            self.queue.len().min(1)
+        } else {
+            0
+        };
+        if count > 0 {
+            self.last_step = Instant::now();
+        }
+        self.played = (self.played + count as u32).min(self.total);
+        self.queue.drain(..count).collect()
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.received_all && self.queue.is_empty()
+    }
+
+    /// (events played, total events) for the progress indicator.
+    pub fn progress(&self) -> (u32, u32) {
+        (self.played, self.total)
+    }
+}
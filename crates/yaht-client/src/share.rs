@@ -0,0 +1,19 @@
+//! Sharing a finished game's scoreboard as text.
+
+use std::path::PathBuf;
+
+const SCOREBOARD_FILE: &str = "scoreboard.txt";
+
+/// Put `scoreboard` on the clipboard as a code block, ready to paste into
+/// a chat, and save it to `scoreboard.txt` in the data directory for
+/// terminals that don't pass the clipboard through. Returns where it was
+/// saved.
+pub fn scoreboard(scoreboard: &str) -> anyhow::Result<PathBuf> {
+    let block = format!("```\n{}```\n", scoreboard);
+    crate::clipboard::copy(&block)?;
+    let dir = crate::storage::data_dir();
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(SCOREBOARD_FILE);
+    std::fs::write(&path, block)?;
+    Ok(path)
+}
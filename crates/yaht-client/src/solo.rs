@@ -9,112 +9,194 @@ use uuid::Uuid;
 
 use yaht_common::ai::{self, AiDifficulty};
 use yaht_common::dice::MAX_ROLLS;
-use yaht_common::game::{GamePhase, GameState, TurnPhase};
+use yaht_common::game::{GameConfig, GamePhase, GameState, TurnPhase};
+use yaht_common::observer::EngineEvent;
 use yaht_common::player::Player;
+use yaht_common::records::Milestone;
 
+use crate::event::{self, Input, RenderGate};
+use crate::graphics;
 use crate::input::{self, Action};
-use crate::ui::game::{GameScreen, RollAnimation};
+use crate::presence;
+use crate::records::SoloRecords;
+use crate::sound::{self, SoundEvent};
+use crate::stats;
+use crate::streamer;
+use crate::ui::game::{chat_history, GameScreen, RollAnimation};
 use crate::ui::help_popup;
 use crate::ui::results::ResultsScreen;
+use crate::ui::stats::StatsScreen;
 
-#[derive(Debug)]
-enum SoloScreen {
-    Game(GameScreen),
-    Results(ResultsScreen),
+pub const AI_NAMES: [&str; 5] = ["Bot Alpha", "Bot Beta", "Bot Gamma", "Bot Delta", "Bot Epsilon"];
+
+/// An AI seat in a solo game.
+#[derive(Debug, Clone)]
+pub struct AiOpponent {
+    pub name: String,
+    pub difficulty: AiDifficulty,
+}
+
+/// How a single solo game ended.
+#[derive(Debug, Clone)]
+pub enum SoloOutcome {
+    Finished {
+        human_id: Uuid,
+        final_scores: Vec<(Uuid, String, u16)>,
+        winner_ids: Vec<Uuid>,
+        /// Each player's total after every round.
+        round_totals: Vec<(Uuid, Vec<u16>)>,
+        /// Personal records the human player broke.
+        milestones: Vec<Milestone>,
+    },
+    Quit,
 }
 
 pub async fn run_solo(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     player_name: String,
     ai_count: u8,
+    rules: GameConfig,
 ) -> anyhow::Result<()> {
+    let mut keys = event::spawn_key_reader();
+
+    let opponents: Vec<AiOpponent> = (0..ai_count as usize)
+        .map(|i| AiOpponent {
+            name: AI_NAMES[i % AI_NAMES.len()].to_string(),
+            difficulty: AiDifficulty::Hard,
+        })
+        .collect();
+
+    // Solitaire runs track personal records instead of wins, as long as
+    // they are played by the standard rules
+    let records = (ai_count == 0 && rules == GameConfig::default()).then(SoloRecords::load);
+    let intro = match records {
+        Some(ref records) => format!("[System] Solitaire game started! {}", records.summary()),
+        None => "[System] Solo game started! You vs AI.".to_string(),
+    };
+
+    let outcome = play_game(terminal, &mut keys, player_name, &opponents, &intro, rules).await?;
+
+    if let SoloOutcome::Finished {
+        human_id,
+        final_scores,
+        winner_ids,
+        round_totals,
+        milestones,
+    } = outcome
+    {
+        let mut results = ResultsScreen::new(final_scores, winner_ids)
+            .with_round_totals(round_totals)
+            .with_milestones(&milestones);
+        if let Some(mut records) = records {
+            let score = results
+                .final_scores
+                .iter()
+                .find(|(id, _, _)| *id == human_id)
+                .map(|(_, _, score)| *score)
+                .unwrap_or(0);
+            let new_best = records.record(score);
+            if let Err(e) = records.save() {
+                tracing::warn!("Failed to save solitaire records: {}", e);
+            }
+            results = results.with_records(records, new_best);
+        }
+        show_results(terminal, &mut keys, results, human_id).await?;
+    }
+
+    Ok(())
+}
+
+/// Play one game under `rules` against the given AI lineup until it
+/// finishes or the player quits.
+pub async fn play_game(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    keys: &mut mpsc::Receiver<Input>,
+    player_name: String,
+    opponents: &[AiOpponent],
+    intro: &str,
+    rules: GameConfig,
+) -> anyhow::Result<SoloOutcome> {
     let mut rng = rand::rngs::StdRng::from_entropy();
 
     // Create players: human + AI
     let human_id = Uuid::new_v4();
     let mut players = vec![Player::new(human_id, player_name)];
 
-    let ai_names = ["Bot Alpha", "Bot Beta", "Bot Gamma", "Bot Delta", "Bot Epsilon"];
-    let mut ai_ids = Vec::new();
-    for i in 0..ai_count as usize {
+    let mut ai_seats: Vec<(Uuid, AiDifficulty)> = Vec::new();
+    for opponent in opponents {
         let id = Uuid::new_v4();
-        ai_ids.push(id);
-        players.push(Player::new(id, ai_names[i % ai_names.len()].to_string()));
+        ai_seats.push((id, opponent.difficulty));
+        players.push(Player::new(id, opponent.name.clone()));
     }
 
-    let mut game = GameState::new(players);
+    let mut game = GameState::new(players, rules);
+    // Our own rolls make a sound, as the engine reports them
+    game.observe(move |event| match event {
+        EngineEvent::DiceRolled { player_id, dice, .. } if *player_id == human_id => sound::play_roll(*dice),
+        _ => {}
+    });
     game.start_solo()?;
 
     let snapshot = game.snapshot();
-    let mut game_screen = GameScreen::new(human_id, snapshot);
-    game_screen.chat_messages = vec!["[System] Solo game started! You vs AI.".into()];
+    let mut gs = GameScreen::new(human_id, snapshot);
+    gs.chat_messages = chat_history(intro);
 
-    let mut screen = SoloScreen::Game(game_screen);
-    let mut running = true;
     let mut show_help = false;
 
-    // Set up key event channel
-    let (event_tx, mut event_rx) = mpsc::channel::<crossterm::event::KeyEvent>(64);
-    tokio::spawn(async move {
-        use crossterm::event::{Event, EventStream};
-        use futures::StreamExt;
-        let mut key_stream = EventStream::new();
-        loop {
-            if let Some(Ok(Event::Key(key))) = key_stream.next().await {
-                if event_tx.send(key).await.is_err() {
-                    break;
-                }
-            }
-        }
-    });
-
     // Initial turn notification
-    let first_player = &game.players[game.current_player_index];
-    if first_player.id == human_id {
-        if let SoloScreen::Game(ref mut gs) = screen {
-            gs.status_message = Some("Your turn! Press [R] to roll.".into());
-        }
+    if game.is_current_player(human_id) {
+        gs.status_message = Some("Your turn! Press [R] to roll.".into());
     }
 
-    while running {
+    let mut render = RenderGate::new();
+
+    loop {
         // Draw
-        terminal.draw(|frame| {
-            match &screen {
-                SoloScreen::Game(s) => s.draw(frame),
-                SoloScreen::Results(s) => s.draw(frame),
-            }
-            if show_help {
-                help_popup::draw_help_popup(frame);
-            }
-        })?;
+        if render.should_draw(terminal)? {
+            streamer::mirror(&gs);
+            presence::show_game(&gs);
+            terminal.draw(|frame| {
+                gs.draw(frame);
+                if show_help {
+                    help_popup::draw_help_popup(frame, &gs.game_state.rules.scoring);
+                }
+            })?;
+            graphics::flush(terminal, &mut render, show_help)?;
+        }
+
+        if game.phase == GamePhase::Finished {
+            return Ok(finished_outcome(&game, human_id));
+        }
 
         // Check if it's an AI's turn
-        if game.phase == GamePhase::Playing {
-            let current_id = game.current_player().id;
-            if ai_ids.contains(&current_id) {
-                // AI turn - process it with a small delay for visual effect
-                tokio::time::sleep(Duration::from_millis(300)).await;
-                process_ai_turn(&mut game, current_id, &mut rng, &mut screen, human_id, &ai_ids);
-                continue;
-            }
+        let current_id = game.current_player().id;
+        if let Some(&(_, difficulty)) = ai_seats.iter().find(|(id, _)| *id == current_id) {
+            // AI turn - process it with a small delay for visual effect
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            process_ai_turn(&mut game, current_id, difficulty, &mut rng, &mut gs, human_id);
+            render.mark_dirty();
+            continue;
         }
 
         // Wait for human input with tick
         let key = tokio::select! {
-            k = event_rx.recv() => {
+            k = keys.recv() => {
                 match k {
-                    Some(key) => key,
-                    None => break,
+                    Some(Input::Key(key)) => key,
+                    // Redrawn at the new size on the way round
+                    Some(Input::Resize) => continue,
+                    None => return Ok(SoloOutcome::Quit),
                 }
             }
-            _ = tokio::time::sleep(Duration::from_millis(50)) => {
+            _ = tokio::time::sleep(event::tick_rate(gs.is_animating())) => {
                 // Tick for animations
-                if let SoloScreen::Game(ref mut gs) = screen {
-                    gs.tick();
+                if gs.tick() {
+                    render.mark_dirty();
                 }
                 continue;
             }
         };
+        render.mark_dirty();
 
         // Help dismiss
         if show_help {
@@ -122,119 +204,152 @@ pub async fn run_solo(
             continue;
         }
 
-        let chat_focused = matches!(&screen, SoloScreen::Game(g) if g.chat_focused);
-        let app_screen = match &screen {
-            SoloScreen::Game(g) => crate::app::Screen::Game(g.clone()),
-            SoloScreen::Results(r) => crate::app::Screen::Results(r.clone()),
+        let app_screen = crate::app::Screen::Game(Box::new(gs.clone()));
+        let action = match input::map_key(key, &app_screen, gs.chat_focused) {
+            Some(action) => action,
+            None => continue,
         };
-        let action = input::map_key(key, &app_screen, chat_focused);
 
-        if let Some(action) = action {
-            match action {
-                Action::Quit => {
-                    running = false;
-                }
-                Action::ShowHelp => {
-                    show_help = !show_help;
-                }
-                Action::RollDice => {
-                    if game.phase == GamePhase::Playing && game.is_current_player(human_id) {
-                        if let Ok(()) = game.roll_dice(human_id, &mut rng) {
-                            let turn = game.turn.as_ref().unwrap();
-                            let dice = turn.dice;
-                            let rolls_remaining = MAX_ROLLS - turn.rolls_used;
-
-                            if let SoloScreen::Game(ref mut gs) = screen {
-                                gs.roll_animation = Some(RollAnimation::new(dice));
-                                gs.rolls_remaining = rolls_remaining;
-                                gs.game_state = game.snapshot();
-                            }
-                        }
-                    }
+        match action {
+            Action::Quit => return Ok(SoloOutcome::Quit),
+            Action::ShowHelp => {
+                show_help = !show_help;
+            }
+            Action::RollDice if game.roll_dice(human_id, &mut rng).is_ok() => {
+                let turn = game.turn.as_ref().unwrap();
+                let dice = turn.dice;
+                let rolls_remaining = MAX_ROLLS - turn.rolls_used;
+
+                gs.roll_animation = Some(RollAnimation::new(dice));
+                gs.rolls_remaining = rolls_remaining;
+                gs.game_state = game.snapshot();
+            }
+            Action::ToggleHold(idx) if game.is_current_player(human_id) => {
+                gs.toggle_hold(idx);
+                let held = gs.get_held_array();
+                let _ = game.hold_dice(human_id, held);
+                if let Some(ref turn) = game.turn {
+                    gs.dice = Some(turn.dice);
                 }
-                Action::ToggleHold(idx) => {
-                    if game.phase == GamePhase::Playing && game.is_current_player(human_id) {
-                        if let SoloScreen::Game(ref mut gs) = screen {
-                            gs.toggle_hold(idx);
-                            let held = gs.get_held_array();
-                            let _ = game.hold_dice(human_id, held);
-                            if let Some(ref turn) = game.turn {
-                                gs.dice = Some(turn.dice);
+            }
+            Action::ConfirmScore if game.is_current_player(human_id) => {
+                if let Some(cat) = gs.selected_category() {
+                    let prev_player = game.current_player().name.clone();
+                    match game.score_category(human_id, cat) {
+                        Ok(score) => {
+                            gs.score_flash = Some((cat, score, std::time::Instant::now()));
+                            gs.status_message = Some(format!(
+                                "{} scored {} for {}",
+                                prev_player,
+                                score,
+                                cat.display_name()
+                            ));
+                            gs.game_state = game.snapshot();
+                            if game.phase != GamePhase::Finished {
+                                update_game_screen_turn(&game, &mut gs, human_id);
                             }
                         }
-                    }
-                }
-                Action::ConfirmScore => {
-                    if game.phase == GamePhase::Playing && game.is_current_player(human_id) {
-                        if let SoloScreen::Game(ref gs) = screen {
-                            if let Some(cat) = gs.selected_category() {
-                                let prev_player = game.current_player().name.clone();
-                                match game.score_category(human_id, cat) {
-                                    Ok(score) => {
-                                        if let SoloScreen::Game(ref mut gs) = screen {
-                                            gs.score_flash = Some((cat, score, std::time::Instant::now()));
-                                            gs.status_message = Some(format!(
-                                                "{} scored {} for {}",
-                                                prev_player, score, cat.display_name()
-                                            ));
-                                            gs.game_state = game.snapshot();
-
-                                            if game.phase == GamePhase::Finished {
-                                                let final_scores: Vec<(Uuid, String, u16)> = game
-                                                    .players
-                                                    .iter()
-                                                    .map(|p| (p.id, p.name.clone(), p.scorecard.grand_total()))
-                                                    .collect();
-                                                let winner_id = game.winner().map(|w| w.id).unwrap_or(human_id);
-                                                print!("\x07"); // Bell
-                                                screen = SoloScreen::Results(ResultsScreen::new(final_scores, winner_id));
-                                            } else {
-                                                // Update for next turn
-                                                update_game_screen_turn(&game, gs, human_id);
-                                            }
-                                        }
-                                    }
-                                    Err(_) => {
-                                        if let SoloScreen::Game(ref mut gs) = screen {
-                                            gs.status_message = Some("Cannot score that category".into());
-                                        }
-                                    }
-                                }
-                            }
+                        Err(_) => {
+                            gs.status_message = Some("Cannot score that category".into());
                         }
                     }
                 }
-                Action::NavigateUp => {
-                    if let SoloScreen::Game(ref mut gs) = screen {
-                        gs.select_prev_category();
-                    }
-                }
-                Action::NavigateDown => {
-                    if let SoloScreen::Game(ref mut gs) = screen {
-                        gs.select_next_category();
-                    }
+            }
+            Action::NavigateUp => gs.select_prev_category(),
+            Action::NavigateDown => gs.select_next_category(),
+            Action::ToggleLeaderGap => gs.show_leader_gap = !gs.show_leader_gap,
+            _ => {}
+        }
+    }
+}
+
+/// Show a results screen until the player continues (`true`) or quits (`false`).
+pub async fn show_results(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    keys: &mut mpsc::Receiver<Input>,
+    mut results: ResultsScreen,
+    human_id: Uuid,
+) -> anyhow::Result<bool> {
+    let app_screen = crate::app::Screen::Results(results.clone());
+    let mut stats: Option<StatsScreen> = None;
+    let mut render = RenderGate::new();
+    loop {
+        if render.should_draw(terminal)? {
+            terminal.draw(|frame| match stats {
+                Some(ref stats) => stats.draw(frame),
+                None => results.draw(frame),
+            })?;
+            graphics::flush(terminal, &mut render, false)?;
+        }
+        let key = tokio::select! {
+            k = keys.recv() => match k {
+                Some(Input::Key(key)) => key,
+                // Redrawn at the new size on the way round
+                Some(Input::Resize) => continue,
+                None => return Ok(false),
+            },
+            _ = tokio::time::sleep(event::tick_rate(results.is_animating())) => {
+                if results.tick() {
+                    render.mark_dirty();
                 }
-                Action::BackToLobby => {
-                    running = false;
+                continue;
+            }
+        };
+        render.mark_dirty();
+        if stats.is_some() {
+            stats = None;
+            continue;
+        }
+        match input::map_key(key, &app_screen, false) {
+            Some(Action::Quit) => return Ok(false),
+            Some(Action::BackToLobby) => return Ok(true),
+            Some(Action::ShowStats) => {
+                let me = results.final_scores.iter().find(|(id, _, _)| *id == human_id);
+                if let Some((_, name, _)) = me {
+                    stats = Some(StatsScreen::new(name.clone()));
                 }
-                _ => {}
             }
+            _ => {}
         }
     }
+}
 
-    Ok(())
+fn finished_outcome(game: &GameState, human_id: Uuid) -> SoloOutcome {
+    let final_scores: Vec<(Uuid, String, u16)> = game
+        .players
+        .iter()
+        .map(|p| (p.id, p.name.clone(), p.scorecard.grand_total()))
+        .collect();
+    let winner_ids: Vec<Uuid> = game.winners().iter().map(|w| w.id).collect();
+
+    let mut milestones = Vec::new();
+    if let Some(human) = game.players.iter().find(|p| p.id == human_id) {
+        // Against the AI, a shared first place is neither a win nor a loss
+        let won = (game.players.len() > 1 && !(winner_ids.len() > 1 && winner_ids.contains(&human_id)))
+            .then_some(winner_ids == [human_id]);
+        milestones = stats::record_game(&human.name, &human.scorecard, human.scorecard.grand_total(), won);
+    }
+
+    sound::play(SoundEvent::GameOver);
+    presence::clear();
+    SoloOutcome::Finished {
+        human_id,
+        final_scores,
+        winner_ids,
+        round_totals: game.players.iter().map(|p| p.id).zip(game.round_totals()).collect(),
+        milestones,
+    }
 }
 
 fn process_ai_turn(
     game: &mut GameState,
     ai_id: Uuid,
+    difficulty: AiDifficulty,
     rng: &mut impl Rng,
-    screen: &mut SoloScreen,
+    gs: &mut GameScreen,
     human_id: Uuid,
-    _ai_ids: &[Uuid],
 ) {
     let ai_name = game.current_player().name.clone();
-    let difficulty = AiDifficulty::Hard;
 
     // Roll up to 3 times
     for roll_num in 0..3 {
@@ -245,11 +360,9 @@ fn process_ai_turn(
         let turn = game.turn.as_ref().unwrap();
         let dice = turn.dice;
 
-        if let SoloScreen::Game(ref mut gs) = screen {
-            gs.dice = Some(dice);
-            gs.rolls_remaining = MAX_ROLLS - turn.rolls_used;
-            gs.game_state = game.snapshot();
-        }
+        gs.dice = Some(dice);
+        gs.rolls_remaining = MAX_ROLLS - turn.rolls_used;
+        gs.game_state = game.snapshot();
 
         // Decide whether to reroll
         if roll_num < 2 {
@@ -273,39 +386,27 @@ fn process_ai_turn(
 
     match game.score_category(ai_id, category) {
         Ok(score) => {
-            if let SoloScreen::Game(ref mut gs) = screen {
-                gs.score_flash = Some((category, score, std::time::Instant::now()));
-                gs.status_message = Some(format!(
-                    "{} scored {} for {}",
-                    ai_name, score, category.display_name()
-                ));
-                gs.game_state = game.snapshot();
-
-                if game.phase == GamePhase::Finished {
-                    let final_scores: Vec<(Uuid, String, u16)> = game
-                        .players
-                        .iter()
-                        .map(|p| (p.id, p.name.clone(), p.scorecard.grand_total()))
-                        .collect();
-                    let winner_id = game.winner().map(|w| w.id).unwrap_or(ai_id);
-                    print!("\x07"); // Bell
-                    *screen = SoloScreen::Results(ResultsScreen::new(final_scores, winner_id));
-                } else {
-                    update_game_screen_turn(game, gs, human_id);
-                }
-            }
+            gs.score_flash = Some((category, score, std::time::Instant::now()));
+            gs.status_message = Some(format!(
+                "{} scored {} for {}",
+                ai_name,
+                score,
+                category.display_name()
+            ));
+            gs.game_state = game.snapshot();
         }
         Err(_) => {
             // AI error - shouldn't happen, but try Chance as fallback
-            if let Some(fallback) = game.current_player().scorecard.available_categories().first() {
+            if let Some(fallback) = game.current_player().scorecard.scorable_categories(&dice.values()).first() {
                 let _ = game.score_category(ai_id, *fallback);
             }
-            if let SoloScreen::Game(ref mut gs) = screen {
-                gs.game_state = game.snapshot();
-                update_game_screen_turn(game, gs, human_id);
-            }
+            gs.game_state = game.snapshot();
         }
     }
+
+    if game.phase != GamePhase::Finished {
+        update_game_screen_turn(game, gs, human_id);
+    }
 }
 
 fn update_game_screen_turn(game: &GameState, gs: &mut GameScreen, human_id: Uuid) {
@@ -320,7 +421,7 @@ fn update_game_screen_turn(game: &GameState, gs: &mut GameScreen, human_id: Uuid
     gs.game_state.turn_phase = Some(TurnPhase::WaitingForRoll);
 
     if current.id == human_id {
-        print!("\x07"); // Bell for human's turn
+        sound::play(SoundEvent::YourTurn);
         gs.status_message = Some(format!("Your turn! (round {})", game.round));
     }
 }
@@ -0,0 +1,189 @@
+//! Sound cues for game events.
+//!
+//! By default each cue rings the terminal bell. Built with the `audio`
+//! feature, the client can instead play short synthesized tones through the
+//! system's audio output. Which events make a sound is set per event in
+//! `config.json`.
+
+use std::io::Write;
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoundEvent {
+    YourTurn,
+    Roll,
+    /// A roll that came up five of a kind.
+    Yahtzee,
+    GameOver,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum SoundBackend {
+    /// The terminal bell.
+    #[default]
+    Bell,
+    /// Synthesized tones; needs a client built with the `audio` feature.
+    Audio,
+    Off,
+}
+
+/// The `sounds` section of `config.json`.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default)]
+pub struct SoundConfig {
+    pub backend: SoundBackend,
+    pub your_turn: bool,
+    pub roll: bool,
+    pub yahtzee: bool,
+    pub game_over: bool,
+}
+
+impl Default for SoundConfig {
+    fn default() -> Self {
+        Self {
+            backend: SoundBackend::Bell,
+            your_turn: true,
+            roll: false,
+            yahtzee: true,
+            game_over: true,
+        }
+    }
+}
+
+impl SoundConfig {
+    fn enabled(&self, event: SoundEvent) -> bool {
+        match event {
+            SoundEvent::YourTurn => self.your_turn,
+            SoundEvent::Roll => self.roll,
+            SoundEvent::Yahtzee => self.yahtzee,
+            SoundEvent::GameOver => self.game_over,
+        }
+    }
+}
+
+enum Output {
+    Bell,
+    #[cfg(feature = "audio")]
+    Audio(std::sync::mpsc::Sender<SoundEvent>),
+}
+
+struct Sound {
+    config: SoundConfig,
+    output: Option<Output>,
+}
+
+static SOUND: OnceLock<Sound> = OnceLock::new();
+
+/// Set up sound output. Only the first call has any effect; until then,
+/// and if it is never called, every event rings the bell by the defaults.
+pub fn init(config: SoundConfig) {
+    let output = match config.backend {
+        SoundBackend::Off => None,
+        SoundBackend::Bell => Some(Output::Bell),
+        #[cfg(feature = "audio")]
+        SoundBackend::Audio => match audio::spawn() {
+            Ok(sender) => Some(Output::Audio(sender)),
+            Err(e) => {
+                tracing::warn!("No audio output ({}); using the terminal bell", e);
+                Some(Output::Bell)
+            }
+        },
+        #[cfg(not(feature = "audio"))]
+        SoundBackend::Audio => {
+            tracing::warn!("This client was built without the audio feature; using the terminal bell");
+            Some(Output::Bell)
+        }
+    };
+    let _ = SOUND.set(Sound { config, output });
+}
+
+pub fn play(event: SoundEvent) {
+    let sound = SOUND.get_or_init(|| Sound {
+        config: SoundConfig::default(),
+        output: Some(Output::Bell),
+    });
+    if !sound.config.enabled(event) {
+        return;
+    }
+    match &sound.output {
+        None => {}
+        Some(Output::Bell) => {
+            let mut stdout = std::io::stdout();
+            let _ = stdout.write_all(b"\x07").and_then(|_| stdout.flush());
+        }
+        #[cfg(feature = "audio")]
+        Some(Output::Audio(sender)) => {
+            let _ = sender.send(event);
+        }
+    }
+}
+
+/// Play `Roll`, or `Yahtzee` if the dice show five of a kind.
+pub fn play_roll(values: [u8; 5]) {
+    if values.iter().all(|&v| v == values[0]) {
+        play(SoundEvent::Yahtzee);
+    } else {
+        play(SoundEvent::Roll);
+    }
+}
+
+#[cfg(feature = "audio")]
+mod audio {
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    use rodio::source::{SineWave, Source};
+    use rodio::{OutputStream, Sink};
+
+    use super::SoundEvent;
+
+    const VOLUME: f32 = 0.2;
+
+    /// The notes of each cue as (frequency in Hz, length in ms).
+    fn notes(event: SoundEvent) -> &'static [(f32, u64)] {
+        match event {
+            SoundEvent::YourTurn => &[(660.0, 90), (880.0, 140)],
+            SoundEvent::Roll => &[(180.0, 25), (240.0, 25)],
+            SoundEvent::Yahtzee => &[(523.0, 90), (659.0, 90), (784.0, 90), (1047.0, 260)],
+            SoundEvent::GameOver => &[(784.0, 150), (659.0, 150), (523.0, 300)],
+        }
+    }
+
+    /// Start a thread that owns the audio output and plays each event sent
+    /// to it. Fails if there is no output device.
+    pub fn spawn() -> anyhow::Result<mpsc::Sender<SoundEvent>> {
+        let (tx, rx) = mpsc::channel::<SoundEvent>();
+        let (ready_tx, ready_rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            // The stream is not Send, so it lives and dies on this thread.
+            let (_stream, handle) = match OutputStream::try_default() {
+                Ok(output) => {
+                    let _ = ready_tx.send(Ok(()));
+                    output
+                }
+                Err(e) => {
+                    let _ = ready_tx.send(Err(e.to_string()));
+                    return;
+                }
+            };
+            for event in rx {
+                let Ok(sink) = Sink::try_new(&handle) else {
+                    continue;
+                };
+                for &(freq, ms) in notes(event) {
+                    let length = Duration::from_millis(ms);
+                    sink.append(SineWave::new(freq).take_duration(length).amplify(VOLUME));
+                }
+                sink.detach();
+            }
+        });
+        ready_rx
+            .recv()
+            .map_err(|_| anyhow::anyhow!("audio thread exited"))?
+            .map_err(anyhow::Error::msg)?;
+        Ok(tx)
+    }
+}
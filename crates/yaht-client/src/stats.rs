@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use yaht_common::player::Scorecard;
+use yaht_common::records::{Milestone, PersonalBests};
+use yaht_common::scoring::Category;
+
+use crate::storage;
+
+const STATS_FILE: &str = "stats.json";
+
+/// Lifetime statistics for one player name.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PlayerStats {
+    pub games_played: u32,
+    /// Games with at least one opponent; the denominator for win rate.
+    pub contested_games: u32,
+    pub wins: u32,
+    pub total_score: u64,
+    pub best_score: u16,
+    pub category_totals: HashMap<Category, u32>,
+    pub yahtzees: u32,
+    pub upper_bonuses: u32,
+    #[serde(default)]
+    pub bests: PersonalBests,
+}
+
+impl PlayerStats {
+    /// Fold a finished game into the totals. `won` is `None` for solitaire
+    /// games and draws. Returns the personal records the game broke.
+    pub fn record_game(&mut self, scorecard: &Scorecard, final_score: u16, won: Option<bool>) -> Vec<Milestone> {
+        self.games_played += 1;
+        if let Some(won) = won {
+            self.contested_games += 1;
+            if won {
+                self.wins += 1;
+            }
+        }
+        self.total_score += final_score as u64;
+        self.best_score = self.best_score.max(final_score);
+
+        for (cat, score) in scorecard.scores() {
+            *self.category_totals.entry(cat).or_default() += score as u32;
+        }
+        if scorecard.score(Category::Yahtzee) == Some(50) {
+            self.yahtzees += 1;
+        }
+        self.yahtzees += scorecard.yahtzee_bonus_count as u32;
+        if scorecard.upper_bonus() > 0 {
+            self.upper_bonuses += 1;
+        }
+        self.bests.record(scorecard, final_score)
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        ratio(self.wins, self.contested_games)
+    }
+
+    pub fn average_score(&self) -> f64 {
+        if self.games_played == 0 {
+            return 0.0;
+        }
+        self.total_score as f64 / self.games_played as f64
+    }
+
+    pub fn category_average(&self, category: Category) -> f64 {
+        let total = self.category_totals.get(&category).copied().unwrap_or(0);
+        if self.games_played == 0 {
+            return 0.0;
+        }
+        total as f64 / self.games_played as f64
+    }
+
+    pub fn yahtzees_per_game(&self) -> f64 {
+        ratio(self.yahtzees, self.games_played)
+    }
+
+    pub fn bonus_rate(&self) -> f64 {
+        ratio(self.upper_bonuses, self.games_played)
+    }
+}
+
+fn ratio(n: u32, d: u32) -> f64 {
+    if d == 0 {
+        0.0
+    } else {
+        n as f64 / d as f64
+    }
+}
+
+/// All locally recorded stats, keyed by player name.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StatsStore {
+    pub players: HashMap<String, PlayerStats>,
+}
+
+/// Stats for a player name (empty if none recorded yet).
+pub fn load_player(player_name: &str) -> PlayerStats {
+    let store: StatsStore = storage::load(STATS_FILE);
+    store.players.get(player_name).cloned().unwrap_or_default()
+}
+
+/// Record a finished game for `player_name` in the local stats file.
+/// Returns the personal records the game broke.
+pub fn record_game(player_name: &str, scorecard: &Scorecard, final_score: u16, won: Option<bool>) -> Vec<Milestone> {
+    if let Err(e) = scorecard.validate() {
+        tracing::warn!("Not recording stats for an invalid scorecard: {}", e);
+        return Vec::new();
+    }
+    let mut store: StatsStore = storage::load(STATS_FILE);
+    let milestones = store
+        .players
+        .entry(player_name.to_string())
+        .or_default()
+        .record_game(scorecard, final_score, won);
+    if let Err(e) = storage::save(STATS_FILE, &store) {
+        tracing::warn!("Failed to save stats: {}", e);
+    }
+    milestones
+}
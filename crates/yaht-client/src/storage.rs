@@ -0,0 +1,116 @@
+use std::path::PathBuf;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use yaht_common::game::GameConfig;
+
+use crate::i18n::Lang;
+use crate::sound::SoundConfig;
+use crate::ui::MotionConfig;
+
+const IDENTITY_FILE: &str = "identity.json";
+const CONFIG_FILE: &str = "config.json";
+
+/// Settings read from `config.json` in the data directory. Command line
+/// flags take precedence.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Proxy URL for server connections, e.g. `socks5://127.0.0.1:1080`.
+    pub proxy: Option<String>,
+    pub connect_timeout_secs: u64,
+    /// How long the server has to answer once connected.
+    pub handshake_timeout_secs: u64,
+    /// Interface language, e.g. `"de"`.
+    pub lang: Option<Lang>,
+    /// Sound backend and which events play a sound.
+    pub sounds: SoundConfig,
+    /// Dice rolls, score flashes and the winner celebration.
+    pub animations: bool,
+    /// Reduced motion and how long animations last.
+    pub motion: MotionConfig,
+    /// Go back to the room's waiting room on our own after an online game.
+    pub auto_rematch: bool,
+    /// Discord application id for Rich Presence, with the `discord` feature.
+    pub discord_app_id: Option<String>,
+    /// Public server directory for `--list-servers`, e.g. `http://directory.example.org`.
+    pub directory: Option<String>,
+    /// Rules for `--solo` games, e.g. fewer rounds or another Joker rule.
+    pub solo_rules: GameConfig,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            proxy: None,
+            connect_timeout_secs: 10,
+            handshake_timeout_secs: 10,
+            lang: None,
+            sounds: SoundConfig::default(),
+            animations: true,
+            motion: MotionConfig::default(),
+            auto_rematch: false,
+            discord_app_id: None,
+            directory: None,
+            solo_rules: GameConfig::default(),
+        }
+    }
+}
+
+pub fn config() -> Config {
+    load(CONFIG_FILE)
+}
+
+/// Directory holding the client's local data files.
+///
+/// `YAHT_DATA_DIR` overrides the default of `$XDG_DATA_HOME/yaht`
+/// (falling back to `~/.local/share/yaht`).
+pub fn data_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os("YAHT_DATA_DIR") {
+        return PathBuf::from(dir);
+    }
+    if let Some(dir) = std::env::var_os("XDG_DATA_HOME") {
+        return PathBuf::from(dir).join("yaht");
+    }
+    if let Some(home) = std::env::var_os("HOME") {
+        return PathBuf::from(home).join(".local").join("share").join("yaht");
+    }
+    PathBuf::from(".yaht")
+}
+
+/// Load a JSON file from the data directory, falling back to the default
+/// value if it is missing or unreadable.
+pub fn load<T: DeserializeOwned + Default>(file: &str) -> T {
+    let path = data_dir().join(file);
+    match std::fs::read(&path) {
+        Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+            tracing::warn!("Ignoring corrupt data file {}: {}", path.display(), e);
+            T::default()
+        }),
+        Err(_) => T::default(),
+    }
+}
+
+/// Write a JSON file to the data directory (atomically via a temp file).
+pub fn save<T: Serialize>(file: &str, value: &T) -> anyhow::Result<()> {
+    let dir = data_dir();
+    std::fs::create_dir_all(&dir)?;
+    let tmp = dir.join(format!("{}.tmp", file));
+    std::fs::write(&tmp, serde_json::to_vec_pretty(value)?)?;
+    std::fs::rename(tmp, dir.join(file))?;
+    Ok(())
+}
+
+/// This install's persistent identity, generated on first use. Sent in
+/// `Hello` so the server can keep records across sessions.
+pub fn identity() -> Uuid {
+    if let Some(id) = load::<Option<Uuid>>(IDENTITY_FILE) {
+        return id;
+    }
+    let id = Uuid::new_v4();
+    if let Err(e) = save(IDENTITY_FILE, &Some(id)) {
+        tracing::warn!("Failed to save identity: {}", e);
+    }
+    id
+}
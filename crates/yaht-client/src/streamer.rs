@@ -0,0 +1,127 @@
+//! Presentation mode for streaming a game.
+//!
+//! Streamer mode hides the server address, draws the dice and scoreboard
+//! larger, and shrinks the chat. Separately, an overlay file can mirror the
+//! game as plain text for an OBS text source; it is rewritten whenever what
+//! it shows changes.
+
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+use crate::ui::game::GameScreen;
+
+/// Shown in place of hidden text.
+const MASK: &str = "••••••";
+
+#[derive(Debug, Default)]
+pub struct StreamerOptions {
+    pub enabled: bool,
+    pub overlay_file: Option<PathBuf>,
+}
+
+struct Streamer {
+    enabled: bool,
+    overlay: Option<Mutex<Overlay>>,
+}
+
+struct Overlay {
+    path: PathBuf,
+    /// What the file holds, so unchanged frames are not rewritten.
+    last: String,
+}
+
+static STREAMER: OnceLock<Streamer> = OnceLock::new();
+
+/// Set the presentation options. Only the first call has any effect.
+pub fn init(options: StreamerOptions) {
+    let overlay = options.overlay_file.map(|path| {
+        Mutex::new(Overlay {
+            path,
+            last: String::new(),
+        })
+    });
+    let _ = STREAMER.set(Streamer {
+        enabled: options.enabled,
+        overlay,
+    });
+}
+
+pub fn enabled() -> bool {
+    STREAMER.get().is_some_and(|s| s.enabled)
+}
+
+/// `text` as it should appear on screen: one bullet per character in
+/// streamer mode.
+pub fn conceal(text: &str) -> String {
+    if enabled() {
+        "•".repeat(text.chars().count())
+    } else {
+        text.to_string()
+    }
+}
+
+/// Replace each occurrence of `secret` in `text` in streamer mode.
+pub fn redact(text: &str, secret: &str) -> String {
+    if enabled() && !secret.is_empty() {
+        text.replace(secret, MASK)
+    } else {
+        text.to_string()
+    }
+}
+
+/// Mirror the game to the overlay file, if there is one.
+pub fn mirror(game: &GameScreen) {
+    let Some(overlay) = STREAMER.get().and_then(|s| s.overlay.as_ref()) else {
+        return;
+    };
+    let Ok(mut overlay) = overlay.lock() else {
+        return;
+    };
+    let text = overlay_text(game);
+    if text == overlay.last {
+        return;
+    }
+    // Write through a temp file so OBS never reads a half-written file.
+    let tmp = overlay.path.with_extension("tmp");
+    let written = std::fs::write(&tmp, &text).and_then(|_| std::fs::rename(&tmp, &overlay.path));
+    match written {
+        Ok(()) => overlay.last = text,
+        Err(e) => {
+            tracing::warn!("Cannot write overlay file {}: {}", overlay.path.display(), e);
+            // Don't retry every frame.
+            overlay.last = text;
+        }
+    }
+}
+
+fn overlay_text(game: &GameScreen) -> String {
+    let state = &game.game_state;
+    let current = state
+        .players
+        .iter()
+        .find(|p| Some(p.id) == game.current_turn_player_id)
+        .map_or("?", |p| p.name.as_str());
+
+    let mut lines = vec![
+        tr!(Round, game.round, state.total_rounds),
+        format!("{}{}", tr!(Turn), current),
+    ];
+    // Only settled dice; the roll animation would rewrite the file each frame.
+    if game.roll_animation.is_none() {
+        if let Some(dice) = &game.dice {
+            let faces: Vec<String> = dice.values().iter().map(|v| v.to_string()).collect();
+            lines.push(format!("{}: {}", tr!(Dice), faces.join(" ")));
+        }
+    }
+    lines.push(String::new());
+    let width = state.players.iter().map(|p| p.name.chars().count()).max().unwrap_or(0);
+    for player in &state.players {
+        lines.push(format!(
+            "{:<width$}  {:>4}",
+            player.name,
+            player.scorecard.grand_total(),
+            width = width
+        ));
+    }
+    lines.join("\n") + "\n"
+}
@@ -0,0 +1,78 @@
+//! Suspending with Ctrl+Z.
+//!
+//! Raw mode turns Ctrl+Z into an ordinary key press, so the client stops
+//! itself: it hands the terminal back the way the shell expects it, stops,
+//! and once continued takes the terminal again and redraws everything. The
+//! connection stays open while stopped, so an online game picks up where it
+//! was as soon as the client catches up on the messages it missed.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set while the terminal is handed back to the shell.
+#[cfg(unix)]
+static SUSPENDED: AtomicBool = AtomicBool::new(false);
+/// Set on being continued, until the next frame is drawn in full.
+static RESUMED: AtomicBool = AtomicBool::new(false);
+
+/// Restore the terminal and stop the process. Returns once it is continued,
+/// with the terminal taken back.
+#[cfg(unix)]
+pub fn suspend() {
+    use crossterm::{cursor::Show, execute, terminal::LeaveAlternateScreen};
+
+    SUSPENDED.store(true, Ordering::SeqCst);
+    let _ = crossterm::terminal::disable_raw_mode();
+    let _ = execute!(std::io::stdout(), LeaveAlternateScreen, Show);
+    // SIGSTOP, as SIGTSTP is caught in `watch_signals`
+    if let Err(e) = signal_hook::low_level::raise(signal_hook::consts::SIGSTOP) {
+        tracing::warn!("Could not suspend: {}", e);
+    }
+    resume();
+}
+
+#[cfg(not(unix))]
+pub fn suspend() {}
+
+/// Take the terminal back if we handed it over, and have the next frame
+/// drawn in full either way: whatever stopped us, the screen may have
+/// changed in the meantime.
+#[cfg(unix)]
+fn resume() {
+    use crossterm::{cursor::Hide, execute, terminal::EnterAlternateScreen};
+
+    if SUSPENDED.swap(false, Ordering::SeqCst) {
+        let _ = crossterm::terminal::enable_raw_mode();
+        let _ = execute!(std::io::stdout(), EnterAlternateScreen, Hide);
+    }
+    RESUMED.store(true, Ordering::SeqCst);
+}
+
+/// Suspend properly on a SIGTSTP sent from outside, e.g. by `kill`, and
+/// redraw on every SIGCONT.
+#[cfg(unix)]
+pub fn watch_signals() {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let watch = |kind: i32, on_signal: fn()| {
+        match signal(SignalKind::from_raw(kind)) {
+            Ok(mut signals) => {
+                tokio::spawn(async move {
+                    while signals.recv().await.is_some() {
+                        on_signal();
+                    }
+                });
+            }
+            Err(e) => tracing::warn!("Cannot watch signal {}: {}", kind, e),
+        }
+    };
+    watch(signal_hook::consts::SIGTSTP, suspend);
+    watch(signal_hook::consts::SIGCONT, resume);
+}
+
+#[cfg(not(unix))]
+pub fn watch_signals() {}
+
+/// Whether the client was continued since the last call.
+pub fn take_resumed() -> bool {
+    RESUMED.swap(false, Ordering::SeqCst)
+}
@@ -0,0 +1,36 @@
+//! The terminal window's title, naming the room and whose turn it is so a
+//! player with many tabs open can see at a glance when they're up.
+//!
+//! The title the terminal had before is pushed onto its title stack on
+//! start and popped on exit; terminals without one simply keep ours.
+
+use std::io;
+use std::sync::{Mutex, PoisonError};
+
+use crossterm::{execute, terminal::SetTitle};
+
+/// The title last set, to only write changes.
+static CURRENT: Mutex<String> = Mutex::new(String::new());
+
+/// Save the terminal's title (xterm's "push title").
+pub fn save() {
+    let _ = execute!(io::stdout(), crossterm::style::Print("\x1b[22;0t"));
+}
+
+/// Give back the title saved by [`save`].
+pub fn restore() {
+    let _ = execute!(io::stdout(), crossterm::style::Print("\x1b[23;0t"));
+}
+
+/// Set the window title, if it changed. Control characters, which could
+/// end the escape sequence early, are dropped.
+pub fn set(title: &str) {
+    let title: String = title.chars().filter(|c| !c.is_control()).collect();
+    let mut current = CURRENT.lock().unwrap_or_else(PoisonError::into_inner);
+    if *current == title {
+        return;
+    }
+    if execute!(io::stdout(), SetTitle(&title)).is_ok() {
+        *current = title;
+    }
+}
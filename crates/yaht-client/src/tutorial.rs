@@ -0,0 +1,224 @@
+use std::io;
+
+use ratatui::backend::CrosstermBackend;
+use ratatui::Terminal;
+use uuid::Uuid;
+
+use yaht_common::ai::AiDifficulty;
+use yaht_common::dice::MAX_ROLLS;
+use yaht_common::game::{GameConfig, GameState};
+use yaht_common::player::Player;
+use yaht_common::scoring::Category;
+use yaht_common::script::{ScriptAction, ScriptExpect, ScriptStep, ScriptedGame};
+
+use crate::event::{self, Input, RenderGate};
+use crate::graphics;
+use crate::input::{self, Action};
+use crate::solo::{self, AiOpponent, SoloOutcome};
+use crate::ui::callout::{self, CalloutTarget};
+use crate::ui::game::{chat_history, GameScreen, RollAnimation};
+use crate::ui::help_popup;
+use crate::ui::results::ResultsScreen;
+
+const NONE: [bool; 5] = [false; 5];
+
+/// The scripted opening: a Yahtzee, an upper-section score, then a joker.
+fn tutorial_steps() -> Vec<ScriptStep> {
+    vec![
+        ScriptStep::roll(
+            "Each turn you get up to three rolls. Press [R] to roll the dice.",
+            NONE,
+            [3, 3, 5, 3, 2],
+        ),
+        ScriptStep::roll(
+            "Three 3s! Hold them with [1], [2] and [4] so they stay put, then press [R] to roll the other two.",
+            [true, true, false, true, false],
+            [3, 3, 3, 3, 6],
+        ),
+        ScriptStep::roll(
+            "Four of a kind. Also hold die [3] and roll the last one.",
+            [true, true, true, true, false],
+            [3, 3, 3, 3, 3],
+        ),
+        ScriptStep::score(
+            "YAHTZEE! Five of a kind scores 50. Select YAHTZEE with [j]/[k] and press [S].",
+            Category::Yahtzee,
+        ),
+        ScriptStep::roll(
+            "Ones to Sixes score the sum of matching dice. Reach 63 in that upper section for a +35 bonus - about three of each face. Press [R].",
+            NONE,
+            [6, 6, 6, 2, 1],
+        ),
+        ScriptStep::roll(
+            "Hold the three 6s with [1], [2] and [3], then roll again.",
+            [true, true, true, false, false],
+            [6, 6, 6, 6, 4],
+        ),
+        ScriptStep::score(
+            "Four 6s is 24 in Sixes - 6 above the 18 (three 6s) that keeps you on pace for the bonus. Score Sixes.",
+            Category::Sixes,
+        ),
+        ScriptStep::roll("Now for the joker rule. Press [R].", NONE, [6, 6, 6, 6, 6]),
+        ScriptStep::score(
+            "Another Yahtzee! With 50 already in YAHTZEE you earn +100. A joker must go in its own upper box, but Sixes is taken, so it scores full value in the lower section - even a Large Straight. Score Lg. Straight for 40.",
+            Category::LargeStraight,
+        ),
+    ]
+}
+
+pub async fn run_tutorial(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    player_name: String,
+) -> anyhow::Result<()> {
+    let mut keys = event::spawn_key_reader();
+
+    let player_id = Uuid::new_v4();
+    let mut game = GameState::new(vec![Player::new(player_id, player_name.clone())], GameConfig::default());
+    game.start_solo()?;
+    let mut script = ScriptedGame::new(game, player_id, tutorial_steps());
+
+    let mut gs = GameScreen::new(player_id, script.game.snapshot());
+    gs.chat_messages = chat_history("[System] Tutorial started!");
+    let mut show_help = false;
+
+    let mut render = RenderGate::new();
+
+    loop {
+        if render.should_draw(terminal)? {
+            terminal.draw(|frame| {
+                gs.draw(frame);
+                if show_help {
+                    help_popup::draw_help_popup(frame, &gs.game_state.rules.scoring);
+                } else if let Some(step) = script.current_step() {
+                    let target = match step.expect {
+                        ScriptExpect::Roll { .. } => CalloutTarget::Dice,
+                        ScriptExpect::Score(_) => CalloutTarget::Scoreboard,
+                    };
+                    let title = format!(
+                        "Tutorial {}/{}",
+                        script.step_index() + 1,
+                        script.step_count()
+                    );
+                    callout::draw_callout(frame, &title, &step.prompt, target);
+                } else {
+                    callout::draw_callout(
+                        frame,
+                        "Tutorial complete",
+                        "That's the basics! Press [Enter] to play a real game against an AI opponent.",
+                        CalloutTarget::Scoreboard,
+                    );
+                }
+            })?;
+            graphics::flush(terminal, &mut render, show_help)?;
+        }
+
+        let key = tokio::select! {
+            k = keys.recv() => match k {
+                Some(Input::Key(key)) => key,
+                // Redrawn at the new size on the way round
+                Some(Input::Resize) => continue,
+                None => return Ok(()),
+            },
+            _ = tokio::time::sleep(event::tick_rate(gs.is_animating())) => {
+                // Tick for animations
+                if gs.tick() {
+                    render.mark_dirty();
+                }
+                continue;
+            }
+        };
+        render.mark_dirty();
+
+        if show_help {
+            show_help = false;
+            continue;
+        }
+
+        let app_screen = crate::app::Screen::Game(Box::new(gs.clone()));
+        let action = match input::map_key(key, &app_screen, false) {
+            Some(action) => action,
+            None => continue,
+        };
+
+        let result = match action {
+            Action::Quit => return Ok(()),
+            Action::ShowHelp => {
+                show_help = true;
+                continue;
+            }
+            Action::ConfirmScore if script.is_finished() => break,
+            Action::NavigateUp => {
+                gs.select_prev_category();
+                continue;
+            }
+            Action::NavigateDown => {
+                gs.select_next_category();
+                continue;
+            }
+            Action::RollDice => script.perform(ScriptAction::Roll),
+            Action::ToggleHold(idx) => {
+                let mut held = gs.get_held_array();
+                held[idx] = !held[idx];
+                script.perform(ScriptAction::Hold(held))
+            }
+            Action::ConfirmScore => match gs.selected_category() {
+                Some(cat) => script.perform(ScriptAction::Score(cat)),
+                None => continue,
+            },
+            _ => continue,
+        };
+
+        match (action, result) {
+            (Action::RollDice, Ok(_)) => {
+                let turn = script.game.turn.as_ref().unwrap();
+                gs.roll_animation = Some(RollAnimation::new(turn.dice));
+                gs.rolls_remaining = MAX_ROLLS - turn.rolls_used;
+                gs.game_state = script.game.snapshot();
+                gs.status_message = None;
+            }
+            (Action::ToggleHold(_), Ok(_)) => {
+                if let Some(ref turn) = script.game.turn {
+                    gs.dice = Some(turn.dice);
+                }
+            }
+            (Action::ConfirmScore, Ok(score)) => {
+                gs.score_flash = gs
+                    .selected_category()
+                    .map(|cat| (cat, score.unwrap_or(0), std::time::Instant::now()));
+                gs.update_from_snapshot(script.game.snapshot());
+                gs.selected_category_index = 0;
+                gs.status_message = None;
+            }
+            (_, Err(e)) => gs.status_message = Some(format!("Not yet - {}", e)),
+            _ => {}
+        }
+    }
+
+    // Tutorial done: a normal solo game against an easy opponent
+    let opponents = [AiOpponent {
+        name: solo::AI_NAMES[0].to_string(),
+        difficulty: AiDifficulty::Easy,
+    }];
+    let outcome = solo::play_game(
+        terminal,
+        &mut keys,
+        player_name,
+        &opponents,
+        "[System] Your first game! Press [?] any time for the rules.",
+        GameConfig::default(),
+    )
+    .await?;
+
+    if let SoloOutcome::Finished {
+        human_id,
+        final_scores,
+        winner_ids,
+        ..
+    } = outcome
+    {
+        let results = ResultsScreen::new(final_scores, winner_ids);
+        solo::show_results(terminal, &mut keys, results, human_id).await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// Which part of the game screen a callout points at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalloutTarget {
+    Dice,
+    Scoreboard,
+}
+
+/// Draw a tutorial callout over the chat panel of the game screen,
+/// with an arrow towards the highlighted area.
+pub fn draw_callout(frame: &mut Frame, title: &str, text: &str, target: CalloutTarget) {
+    let area = callout_area(frame.area());
+    frame.render_widget(Clear, area);
+
+    let arrow = match target {
+        CalloutTarget::Dice => "  ▲ Dice",
+        CalloutTarget::Scoreboard => "  Scorecard ▶",
+    };
+
+    let lines = vec![
+        Line::from(Span::styled(
+            arrow,
+            Style::default()
+                .fg(Color::Rgb(255, 220, 50))
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("  {}", text),
+            Style::default().fg(Color::Rgb(200, 200, 220)),
+        )),
+    ];
+
+    let paragraph = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Rgb(255, 220, 50)))
+            .title(format!(" {} ", title))
+            .title_style(
+                Style::default()
+                    .fg(Color::Rgb(255, 220, 50))
+                    .add_modifier(Modifier::BOLD),
+            ),
+    );
+    frame.render_widget(paragraph, area);
+}
+
+/// The chat panel area of `GameScreen::draw`'s layout.
+fn callout_area(area: Rect) -> Rect {
+    let left = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+        .split(area)[0];
+
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Title
+            Constraint::Length(9), // Dice
+            Constraint::Length(4), // Actions
+            Constraint::Min(5),   // Callout
+        ])
+        .split(left)[3]
+}
@@ -0,0 +1,180 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+use uuid::Uuid;
+
+use yaht_common::ai::AiDifficulty;
+
+use crate::campaign::{CampaignRun, STARTING_LIVES};
+use crate::solo::AiOpponent;
+
+/// Between-game summary shown after each campaign stage.
+#[derive(Debug, Clone)]
+pub struct CampaignSummaryScreen {
+    pub stage_played: u32,
+    pub won: bool,
+    pub final_scores: Vec<(Uuid, String, u16)>,
+    pub winner_ids: Vec<Uuid>,
+    pub run: CampaignRun,
+    pub next_lineup: Vec<AiOpponent>,
+    pub best_stage: u32,
+    pub best_total_score: u32,
+}
+
+impl CampaignSummaryScreen {
+    pub fn draw(&self, frame: &mut Frame) {
+        let area = frame.area();
+
+        let vertical = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Percentage(15),
+                Constraint::Length(3),  // Title
+                Constraint::Length(2),  // Result
+                Constraint::Min(8),    // Details
+                Constraint::Length(2), // Help
+                Constraint::Percentage(15),
+            ])
+            .split(area);
+
+        let horizontal = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(20),
+                Constraint::Percentage(60),
+                Constraint::Percentage(20),
+            ])
+            .split(vertical[3]);
+
+        // Title
+        let title_text = if self.run.is_over() {
+            "  CAMPAIGN OVER".to_string()
+        } else {
+            format!("  CAMPAIGN - STAGE {}", self.stage_played)
+        };
+        let title = Paragraph::new(Line::from(Span::styled(
+            title_text,
+            Style::default()
+                .fg(Color::Rgb(255, 220, 50))
+                .add_modifier(Modifier::BOLD),
+        )))
+        .alignment(Alignment::Center);
+        frame.render_widget(title, vertical[1]);
+
+        // Stage result
+        let (result_text, result_color) = if self.won {
+            ("Stage cleared!", Color::Rgb(100, 255, 150))
+        } else {
+            ("Stage lost - one life spent", Color::Rgb(255, 100, 100))
+        };
+        let result = Paragraph::new(Line::from(Span::styled(
+            result_text,
+            Style::default().fg(result_color).add_modifier(Modifier::BOLD),
+        )))
+        .alignment(Alignment::Center);
+        frame.render_widget(result, vertical[2]);
+
+        // Details
+        let label = Style::default().fg(Color::Rgb(150, 150, 170));
+        let value = Style::default().fg(Color::Rgb(200, 200, 220));
+
+        let mut lines = vec![Line::from("")];
+        for (id, name, score) in &self.final_scores {
+            let style = if self.winner_ids.contains(id) {
+                Style::default()
+                    .fg(Color::Rgb(255, 220, 50))
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                value
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {:<16}", name), style),
+                Span::styled(score.to_string(), style),
+            ]));
+        }
+        lines.push(Line::from(""));
+
+        let hearts = format!(
+            "{}{}",
+            "♥ ".repeat(self.run.lives as usize),
+            "· ".repeat(STARTING_LIVES.saturating_sub(self.run.lives) as usize)
+        );
+        lines.push(Line::from(vec![
+            Span::styled("  Lives:        ", label),
+            Span::styled(hearts, Style::default().fg(Color::Rgb(255, 100, 120))),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled("  Total score:  ", label),
+            Span::styled(self.run.total_score.to_string(), value),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled("  Stages won:   ", label),
+            Span::styled(self.run.wins.to_string(), value),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled("  Best run:     ", label),
+            Span::styled(
+                format!(
+                    "stage {} / {} pts",
+                    self.best_stage.max(self.run.stage),
+                    self.best_total_score.max(self.run.total_score)
+                ),
+                value,
+            ),
+        ]));
+
+        if !self.run.is_over() {
+            let opponents: Vec<String> = self
+                .next_lineup
+                .iter()
+                .map(|o| format!("{} ({})", o.name, difficulty_label(o.difficulty)))
+                .collect();
+            lines.push(Line::from(""));
+            lines.push(Line::from(vec![
+                Span::styled(format!("  Stage {}: ", self.run.stage), label),
+                Span::styled(opponents.join(", "), Style::default().fg(Color::Rgb(100, 200, 255))),
+            ]));
+        }
+
+        let details = Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Rgb(80, 80, 100)))
+                .title(" Campaign ")
+                .title_style(
+                    Style::default()
+                        .fg(Color::Rgb(255, 220, 50))
+                        .add_modifier(Modifier::BOLD),
+                ),
+        );
+        frame.render_widget(details, horizontal[1]);
+
+        // Help
+        let help = if self.run.is_over() {
+            Line::from(vec![
+                Span::styled("[Enter]", Style::default().fg(Color::Rgb(100, 255, 150))),
+                Span::styled(" Finish", Style::default().fg(Color::Rgb(120, 120, 140))),
+            ])
+        } else {
+            Line::from(vec![
+                Span::styled("[Enter]", Style::default().fg(Color::Rgb(100, 255, 150))),
+                Span::styled(" Next stage  ", Style::default().fg(Color::Rgb(120, 120, 140))),
+                Span::styled("[Q]", Style::default().fg(Color::Rgb(255, 150, 100))),
+                Span::styled(" Save & quit", Style::default().fg(Color::Rgb(120, 120, 140))),
+            ])
+        };
+        frame.render_widget(Paragraph::new(help).alignment(Alignment::Center), vertical[4]);
+    }
+}
+
+fn difficulty_label(difficulty: AiDifficulty) -> &'static str {
+    match difficulty {
+        AiDifficulty::Easy => "easy",
+        AiDifficulty::Medium => "medium",
+        AiDifficulty::Hard => "hard",
+    }
+}
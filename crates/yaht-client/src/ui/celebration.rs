@@ -0,0 +1,119 @@
+//! The winner celebration on the results screen: confetti falling behind
+//! the text, a pulsing winner name and the standings revealed from last
+//! place up.
+
+use std::time::{Duration, Instant};
+
+use rand::{Rng, SeedableRng};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+
+/// How long the confetti falls.
+const CONFETTI_DURATION: Duration = Duration::from_millis(4000);
+/// Time between podium places being revealed.
+const REVEAL_STEP: Duration = Duration::from_millis(450);
+/// Length of one pulse of the winner's name.
+const PULSE_PERIOD: f32 = 0.8;
+const PARTICLES: usize = 60;
+const CONFETTI_CHARS: [&str; 5] = ["*", "+", "•", "◆", "✦"];
+const CONFETTI_COLORS: [Color; 5] = [
+    Color::Rgb(255, 220, 50),
+    Color::Rgb(100, 255, 150),
+    Color::Rgb(100, 200, 255),
+    Color::Rgb(255, 120, 160),
+    Color::Rgb(200, 150, 255),
+];
+
+#[derive(Debug, Clone)]
+struct Particle {
+    /// Column, as a fraction of the screen width.
+    x: f32,
+    /// Row at the start, as a fraction of the screen height.
+    y: f32,
+    /// Rows fallen per second.
+    speed: f32,
+    symbol: &'static str,
+    color: Color,
+}
+
+#[derive(Debug, Clone)]
+pub struct Celebration {
+    started_at: Instant,
+    /// Rows in the standings, revealed one at a time.
+    places: usize,
+    particles: Vec<Particle>,
+}
+
+impl Celebration {
+    pub fn new(places: usize) -> Self {
+        let mut rng = rand::rngs::StdRng::from_entropy();
+        let particles = (0..PARTICLES)
+            .map(|_| Particle {
+                x: rng.gen(),
+                // Start above the screen so the confetti rains in
+                y: -0.6 * rng.gen::<f32>(),
+                speed: rng.gen_range(6.0..14.0),
+                symbol: CONFETTI_CHARS[rng.gen_range(0..CONFETTI_CHARS.len())],
+                color: CONFETTI_COLORS[rng.gen_range(0..CONFETTI_COLORS.len())],
+            })
+            .collect();
+        Self {
+            started_at: Instant::now(),
+            places,
+            particles,
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        let reveal = REVEAL_STEP * self.places as u32;
+        self.started_at.elapsed() >= CONFETTI_DURATION.max(reveal)
+    }
+
+    /// Whether the row for `place` (0 for the winner) has been revealed.
+    /// The last place is shown first and the winner last.
+    pub fn revealed(&self, place: usize) -> bool {
+        let order = self.places.saturating_sub(place + 1) as u32;
+        self.started_at.elapsed() >= REVEAL_STEP * order
+    }
+
+    /// The winner's name style, brightening and dimming.
+    pub fn winner_style(&self) -> Style {
+        let t = self.started_at.elapsed().as_secs_f32() / PULSE_PERIOD;
+        let level = 0.5 + 0.5 * (t * std::f32::consts::TAU).sin();
+        let channel = |low: u8, high: u8| (low as f32 + (high as f32 - low as f32) * level) as u8;
+        let style = Style::default().fg(Color::Rgb(
+            channel(100, 255),
+            channel(255, 255),
+            channel(150, 120),
+        ));
+        if level > 0.5 {
+            style.add_modifier(Modifier::BOLD)
+        } else {
+            style
+        }
+    }
+
+    /// Draw the confetti into the blank cells of `area`, so it falls behind
+    /// whatever is already drawn.
+    pub fn draw_confetti(&self, buf: &mut Buffer, area: Rect) {
+        let elapsed = self.started_at.elapsed();
+        if elapsed >= CONFETTI_DURATION || area.width == 0 || area.height == 0 {
+            return;
+        }
+        let secs = elapsed.as_secs_f32();
+        for particle in &self.particles {
+            let row = particle.y * area.height as f32 + particle.speed * secs;
+            if row < 0.0 || row >= area.height as f32 {
+                continue;
+            }
+            let col = (particle.x * area.width as f32) as u16;
+            let Some(cell) = buf.cell_mut((area.x + col.min(area.width - 1), area.y + row as u16)) else {
+                continue;
+            };
+            if cell.symbol() == " " {
+                cell.set_symbol(particle.symbol).set_fg(particle.color);
+            }
+        }
+    }
+}
@@ -1,11 +1,13 @@
 use ratatui::{
-    layout::{Constraint, Direction, Layout},
+    layout::{Constraint, Direction, Layout, Margin},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
 
+use crate::streamer;
+
 #[derive(Debug, Clone)]
 pub struct ConnectScreen {
     pub host: String,
@@ -101,7 +103,7 @@ impl ConnectScreen {
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(
-                "- Multiplayer Yahtzee",
+                tr!(ConnectTagline),
                 Style::default().fg(Color::Rgb(180, 180, 200)),
             ),
         ]));
@@ -127,7 +129,7 @@ impl ConnectScreen {
                 Block::default()
                     .borders(Borders::ALL)
                     .border_style(name_border)
-                    .title(" Player Name ")
+                    .title(format!(" {} ", tr!(PlayerName)))
                     .title_style(name_title_style),
             );
         frame.render_widget(name_input, chunks[1]);
@@ -146,20 +148,20 @@ impl ConnectScreen {
                 Style::default().fg(Color::Rgb(120, 120, 140)),
             )
         };
-        let host_input = Paragraph::new(self.host.as_str())
+        let host_input = Paragraph::new(streamer::conceal(&self.host))
             .style(Style::default().fg(Color::Rgb(180, 180, 200)))
             .block(
                 Block::default()
                     .borders(Borders::ALL)
                     .border_style(host_border)
-                    .title(" Server Address ")
+                    .title(format!(" {} ", tr!(ServerAddress)))
                     .title_style(host_title_style),
             );
         frame.render_widget(host_input, chunks[2]);
 
         // Status/Error
         if self.connecting {
-            let status = Paragraph::new("  Connecting...")
+            let status = Paragraph::new(format!("  {}", tr!(Connecting)))
                 .style(
                     Style::default()
                         .fg(Color::Rgb(100, 200, 255))
@@ -167,30 +169,41 @@ impl ConnectScreen {
                 );
             frame.render_widget(status, chunks[3]);
         } else if let Some(ref err) = self.error_message {
+            let host = self.host.rsplit_once(':').map_or(self.host.as_str(), |(host, _)| host);
+            let err = streamer::redact(err, host.trim_start_matches('[').trim_end_matches(']'));
             let error = Paragraph::new(format!("  {}", err))
                 .style(Style::default().fg(Color::Rgb(255, 100, 100)));
             frame.render_widget(error, chunks[3]);
         }
 
         // Help
-        let help = Paragraph::new(Line::from(vec![
-            Span::raw("  "),
-            Span::styled("[Tab]", Style::default().fg(Color::Rgb(100, 200, 255))),
-            Span::styled(" Switch  ", Style::default().fg(Color::Rgb(120, 120, 140))),
-            Span::styled("[Enter]", Style::default().fg(Color::Rgb(100, 255, 150))),
-            Span::styled(" Connect  ", Style::default().fg(Color::Rgb(120, 120, 140))),
-            Span::styled("[Esc]", Style::default().fg(Color::Rgb(255, 150, 100))),
-            Span::styled(" Quit", Style::default().fg(Color::Rgb(120, 120, 140))),
-        ]));
+        let help = if self.connecting {
+            Paragraph::new(Line::from(vec![
+                Span::raw("  "),
+                Span::styled("[Esc]", Style::default().fg(Color::Rgb(255, 150, 100))),
+                Span::styled(format!(" {}", tr!(ActCancel)), Style::default().fg(Color::Rgb(120, 120, 140))),
+            ]))
+        } else {
+            Paragraph::new(Line::from(vec![
+                Span::raw("  "),
+                Span::styled("[Tab]", Style::default().fg(Color::Rgb(100, 200, 255))),
+                Span::styled(format!(" {}  ", tr!(ActSwitch)), Style::default().fg(Color::Rgb(120, 120, 140))),
+                Span::styled("[Enter]", Style::default().fg(Color::Rgb(100, 255, 150))),
+                Span::styled(format!(" {}  ", tr!(ActConnect)), Style::default().fg(Color::Rgb(120, 120, 140))),
+                Span::styled("[Esc]", Style::default().fg(Color::Rgb(255, 150, 100))),
+                Span::styled(format!(" {}", tr!(ActQuit)), Style::default().fg(Color::Rgb(120, 120, 140))),
+            ]))
+        };
         frame.render_widget(help, chunks[4]);
 
         // Set cursor position
         if !self.connecting {
-            let (cursor_x, cursor_y) = match self.active_field {
-                ConnectField::Name => (chunks[1].x + self.name.len() as u16 + 1, chunks[1].y + 1),
-                ConnectField::Host => (chunks[2].x + self.host.len() as u16 + 1, chunks[2].y + 1),
+            let (field, text) = match self.active_field {
+                ConnectField::Name => (chunks[1], &self.name),
+                ConnectField::Host => (chunks[2], &self.host),
             };
-            frame.set_cursor_position((cursor_x, cursor_y));
+            let cursor = (field.x + text.chars().count() as u16 + 1, field.y + 1);
+            frame.set_cursor_position(super::clamp_cursor(field.inner(Margin::new(1, 1)), cursor));
         }
     }
 }
@@ -0,0 +1,99 @@
+//! The debug overlay toggled with F12: what the client is up to, for
+//! diagnosing desyncs and slow rendering from a user's screenshot.
+
+use std::time::Duration;
+
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::network;
+
+const OVERLAY_WIDTH: u16 = 100;
+
+/// The client's state as of the frame being drawn.
+#[derive(Debug)]
+pub struct DebugInfo<'a> {
+    pub screen: &'a str,
+    /// Key presses, server messages and ticks waiting to be handled.
+    pub event_queue: usize,
+    /// Messages waiting to be written to the server, if connected.
+    pub outgoing_queue: Option<usize>,
+    /// Game actions sent but not yet acknowledged.
+    pub pending_actions: usize,
+    pub tick_rate: Duration,
+    /// How long the previous frame took to draw.
+    pub render_time: Duration,
+}
+
+/// Draw the overlay along the bottom of the screen.
+pub fn draw_debug_overlay(frame: &mut Frame, info: &DebugInfo) {
+    let traffic = network::recent_traffic();
+    let label = Style::default().fg(Color::Rgb(120, 120, 140));
+    let value = Style::default().fg(Color::Rgb(100, 255, 150));
+    let field = |name: &str, text: String| {
+        [
+            Span::styled(format!("{}: ", name), label),
+            Span::styled(text, value),
+            Span::raw("  "),
+        ]
+    };
+
+    let mut lines = vec![
+        Line::from(
+            [
+                field("screen", info.screen.to_string()),
+                field("tick", format!("{}ms", info.tick_rate.as_millis())),
+                field("render", format!("{:.1}ms", info.render_time.as_secs_f64() * 1000.0)),
+            ]
+            .concat(),
+        ),
+        Line::from(
+            [
+                field("events queued", info.event_queue.to_string()),
+                field(
+                    "outgoing",
+                    info.outgoing_queue.map_or("-".to_string(), |n| n.to_string()),
+                ),
+                field("unacknowledged", info.pending_actions.to_string()),
+            ]
+            .concat(),
+        ),
+        Line::from(""),
+    ];
+    if traffic.is_empty() {
+        lines.push(Line::from(Span::styled("No messages yet", label)));
+    }
+    lines.extend(traffic.into_iter().map(|line| {
+        let color = if line.starts_with("->") {
+            Color::Rgb(100, 200, 255)
+        } else {
+            Color::Rgb(200, 200, 210)
+        };
+        Line::from(Span::styled(line, Style::default().fg(color)))
+    }));
+
+    let area = frame.area();
+    let width = area.width.min(OVERLAY_WIDTH);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let overlay = Rect::new(area.x, area.y + area.height - height, width, height);
+    frame.render_widget(Clear, overlay);
+    frame.render_widget(
+        Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Rgb(255, 150, 100)))
+                .title(" Debug [F12] ")
+                .title_style(
+                    Style::default()
+                        .fg(Color::Rgb(255, 150, 100))
+                        .add_modifier(Modifier::BOLD),
+                ),
+        ),
+        overlay,
+    );
+}
@@ -0,0 +1,87 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use yaht_common::dice::FaceCounts;
+
+use super::help_popup::centered_rect;
+
+/// Width of the longest bar, in cells.
+const BAR_WIDTH: usize = 30;
+/// Dice needed before the fairness verdict means anything.
+const MIN_DICE_FOR_VERDICT: u32 = 60;
+/// Chi-squared with five degrees of freedom exceeds this 5% of the time.
+const CHI_SQUARED_5_PERCENT: f64 = 11.07;
+
+pub fn draw_dice_stats_popup(frame: &mut Frame, game: &FaceCounts, room: &FaceCounts) {
+    let popup_area = centered_rect(60, 60, frame.area());
+    frame.render_widget(Clear, popup_area);
+
+    let dim = Style::default().fg(Color::Rgb(150, 150, 170));
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("  {}", tr!(DiceStatsGame, game.total())),
+            Style::default()
+                .fg(Color::Rgb(100, 200, 255))
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+
+    let most = game.0.iter().copied().max().unwrap_or(0).max(1);
+    for (face, &count) in (1..).zip(game.0.iter()) {
+        let filled = (count as usize * BAR_WIDTH).div_ceil(most as usize);
+        let share = if game.total() > 0 {
+            100.0 * count as f64 / game.total() as f64
+        } else {
+            0.0
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("    {}  ", face), Style::default().fg(Color::White)),
+            Span::styled("█".repeat(filled), Style::default().fg(Color::Rgb(255, 220, 100))),
+            Span::styled("░".repeat(BAR_WIDTH - filled), Style::default().fg(Color::Rgb(60, 60, 80))),
+            Span::styled(format!("  {:>4}  {:>5.1}%", count, share), dim),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(format!("  {}", verdict(game)), dim)));
+    lines.push(Line::from(Span::styled(
+        format!("  {}  {}", tr!(DiceStatsRoom, room.total()), verdict(room)),
+        dim,
+    )));
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!("  {}", tr!(DiceStatsClose)),
+        Style::default().fg(Color::Rgb(100, 100, 120)),
+    )));
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Rgb(100, 200, 255)))
+            .title(format!(" {} ", tr!(DiceStatsTitle)))
+            .title_style(
+                Style::default()
+                    .fg(Color::Rgb(255, 220, 50))
+                    .add_modifier(Modifier::BOLD),
+            ),
+    );
+    frame.render_widget(paragraph, popup_area);
+}
+
+/// Whether the counts look like a fair die.
+fn verdict(counts: &FaceCounts) -> String {
+    let chi_squared = counts.chi_squared();
+    if counts.total() < MIN_DICE_FOR_VERDICT {
+        tr!(FairTooFew).to_string()
+    } else if chi_squared <= CHI_SQUARED_5_PERCENT {
+        tr!(FairLooksFair, format!("{:.1}", chi_squared))
+    } else {
+        tr!(FairUnusual, format!("{:.1}", chi_squared))
+    }
+}
@@ -1,12 +1,14 @@
 use ratatui::{
+    layout::Rect,
     style::{Color, Modifier, Style},
     text::{Line, Span},
 };
 
 use yaht_common::dice::Die;
 
-fn render_die_styled(die: &Die, index: usize, animating: bool) -> Vec<Line<'static>> {
-    let (border_style, dot_style) = if animating && !die.held {
+/// The (border, pips) styles of a die: blue while rolling, gold while held.
+fn die_styles(die: &Die, animating: bool) -> (Style, Style) {
+    if animating && !die.held {
         (
             Style::default().fg(Color::Rgb(100, 200, 255)),
             Style::default()
@@ -25,44 +27,49 @@ fn render_die_styled(die: &Die, index: usize, animating: bool) -> Vec<Line<'stat
             Style::default().fg(Color::Rgb(180, 180, 200)),
             Style::default().fg(Color::White),
         )
-    };
-
-    let (top, mid, bot) = die_face(die.value);
-
-    let label = if die.held {
-        format!(" [{}]* ", index + 1)
-    } else {
-        format!("  {}   ", index + 1)
-    };
+    }
+}
 
-    let label_style = if die.held {
+fn label_style(die: &Die) -> Style {
+    if die.held {
         Style::default()
             .fg(Color::Rgb(255, 180, 50))
             .add_modifier(Modifier::BOLD)
     } else {
         Style::default().fg(Color::Rgb(120, 120, 140))
+    }
+}
+
+fn render_die_styled(die: &Die, index: usize, animating: bool, large: bool) -> Vec<Line<'static>> {
+    let (border_style, dot_style) = die_styles(die, animating);
+
+    let face: Vec<&'static str> = if large {
+        large_die_face(die.value).to_vec()
+    } else {
+        let (top, mid, bot) = die_face(die.value);
+        vec![top, mid, bot]
     };
+    let width = face[0].chars().count();
 
-    vec![
-        Line::from(Span::styled("┌─────┐", border_style)),
-        Line::from(vec![
-            Span::styled("│", border_style),
-            Span::styled(top, dot_style),
-            Span::styled("│", border_style),
-        ]),
-        Line::from(vec![
-            Span::styled("│", border_style),
-            Span::styled(mid, dot_style),
-            Span::styled("│", border_style),
-        ]),
+    let label = match (die.held, large) {
+        (true, false) => format!(" [{}]* ", index + 1),
+        (false, false) => format!("  {}   ", index + 1),
+        (true, true) => format!("{:^w$}", format!("[{}]*", index + 1), w = width + 2),
+        (false, true) => format!("{:^w$}", index + 1, w = width + 2),
+    };
+
+    let edge = "─".repeat(width);
+    let mut lines = vec![Line::from(Span::styled(format!("┌{}┐", edge), border_style))];
+    lines.extend(face.into_iter().map(|row| {
         Line::from(vec![
             Span::styled("│", border_style),
-            Span::styled(bot, dot_style),
+            Span::styled(row, dot_style),
             Span::styled("│", border_style),
-        ]),
-        Line::from(Span::styled("└─────┘", border_style)),
-        Line::from(Span::styled(label, label_style)),
-    ]
+        ])
+    }));
+    lines.push(Line::from(Span::styled(format!("└{}┘", edge), border_style)));
+    lines.push(Line::from(Span::styled(label, label_style(die))));
+    lines
 }
 
 fn die_face(value: u8) -> (&'static str, &'static str, &'static str) {
@@ -77,17 +84,76 @@ fn die_face(value: u8) -> (&'static str, &'static str, &'static str) {
     }
 }
 
+/// The Unicode die face showing `value`.
+fn face_glyph(value: u8) -> char {
+    match value {
+        1..=6 => char::from_u32(0x267F + u32::from(value)).unwrap_or('?'),
+        _ => '?',
+    }
+}
+
+/// The face of a large die, five rows of nine cells.
+fn large_die_face(value: u8) -> [&'static str; 5] {
+    const NONE: &str = "         ";
+    const LEFT: &str = " ●       ";
+    const RIGHT: &str = "       ● ";
+    const BOTH: &str = " ●     ● ";
+    const MID: &str = "    ●    ";
+    match value {
+        1 => [NONE, NONE, MID, NONE, NONE],
+        2 => [RIGHT, NONE, NONE, NONE, LEFT],
+        3 => [RIGHT, NONE, MID, NONE, LEFT],
+        4 => [BOTH, NONE, NONE, NONE, BOTH],
+        5 => [BOTH, NONE, MID, NONE, BOTH],
+        6 => [BOTH, NONE, BOTH, NONE, BOTH],
+        _ => [NONE, NONE, "    ?    ", NONE, NONE],
+    }
+}
+
 /// Render all 5 dice side by side as a block of lines.
-pub fn render_dice_row(dice: &[Die; 5]) -> Vec<Line<'static>> {
-    render_dice_row_animated(dice, false)
+pub fn render_dice_row(dice: &[Die; 5], large: bool) -> Vec<Line<'static>> {
+    render_dice_row_animated(dice, false, large)
+}
+
+/// The dice row with only the labels under each die drawn, leaving the
+/// boxes blank for pictures of the dice to go over.
+pub fn render_dice_labels(dice: &[Die; 5], large: bool) -> Vec<Line<'static>> {
+    let mut lines = render_dice_row(dice, large);
+    let labels = lines.pop();
+    lines.fill(Line::default());
+    lines.extend(labels);
+    lines
+}
+
+/// The size of a die's box in cells: its face plus the border.
+fn box_size(large: bool) -> (u16, u16) {
+    if large {
+        (11, 7)
+    } else {
+        (7, 5)
+    }
+}
+
+/// Lines taken by a row of dice drawn as boxes, labels included.
+pub fn row_height(large: bool) -> u16 {
+    box_size(large).1 + 1
+}
+
+/// Where die `index`'s box lands when the row is drawn from the top left of
+/// `area`, if it fits.
+pub fn die_box(area: Rect, index: usize, large: bool) -> Option<Rect> {
+    // Two cells between dice
+    let (width, height) = box_size(large);
+    let die = Rect::new(area.x + index as u16 * (width + 2), area.y, width, height);
+    (area.intersection(die) == die).then_some(die)
 }
 
 /// Render all 5 dice side by side, with optional animation styling.
-pub fn render_dice_row_animated(dice: &[Die; 5], animating: bool) -> Vec<Line<'static>> {
+pub fn render_dice_row_animated(dice: &[Die; 5], animating: bool, large: bool) -> Vec<Line<'static>> {
     let rendered: Vec<Vec<Line>> = dice
         .iter()
         .enumerate()
-        .map(|(i, d)| render_die_styled(d, i, animating))
+        .map(|(i, d)| render_die_styled(d, i, animating, large))
         .collect();
 
     let num_lines = rendered[0].len();
@@ -108,3 +174,22 @@ pub fn render_dice_row_animated(dice: &[Die; 5], animating: bool) -> Vec<Line<'s
 
     result
 }
+
+/// All 5 dice on a single line as Unicode die faces, each after its number
+/// and held dice in brackets, for when there is no room for the boxes.
+pub fn render_dice_micro(dice: &[Die; 5], animating: bool) -> Line<'static> {
+    let mut spans = vec![Span::raw(" ")];
+    for (i, die) in dice.iter().enumerate() {
+        let (border_style, dot_style) = die_styles(die, animating);
+        let (open, close) = if die.held { ("[", "]") } else { (" ", " ") };
+        spans.push(Span::styled(format!("{}", i + 1), label_style(die)));
+        spans.push(Span::styled(open, border_style));
+        spans.push(Span::styled(
+            format!("{} {}", face_glyph(die.value), die.value),
+            dot_style,
+        ));
+        spans.push(Span::styled(close, border_style));
+        spans.push(Span::raw("  "));
+    }
+    Line::from(spans)
+}
@@ -2,7 +2,7 @@ use std::time::{Duration, Instant};
 
 use rand::{Rng, SeedableRng};
 use ratatui::{
-    layout::{Constraint, Direction, Layout},
+    layout::{Constraint, Direction, Layout, Margin},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
@@ -10,17 +10,40 @@ use ratatui::{
 };
 use uuid::Uuid;
 
-use yaht_common::dice::{Die, DiceSet, MAX_ROLLS};
-use yaht_common::game::{GameStateSnapshot, TurnPhase};
-use yaht_common::scoring::Category;
+use yaht_common::dice::{Die, DiceSet, FaceCounts};
+use yaht_common::game::{GameStateSnapshot, Pace, TurnPhase};
+use yaht_common::history::History;
+use yaht_common::scoring::{self, Category};
 
+use crate::graphics::{self, DieLook};
+use crate::streamer;
+
+use super::dice_stats;
 use super::dice_widget;
-use super::scoreboard_widget;
+use super::scoreboard_widget::{self, ScoreboardLayout};
 
 const ROLL_ANIM_DURATION: Duration = Duration::from_millis(600);
 const ROLL_ANIM_FRAME_INTERVAL: Duration = Duration::from_millis(60);
-const SCORE_FLASH_DURATION: Duration = Duration::from_millis(1500);
+/// How long the action bar flashes after an action out of turn.
+const ACTION_FLASH_DURATION: Duration = Duration::from_millis(600);
+/// Turn timer shown when the game has none of its own.
 const TURN_TIMER_SECONDS: u64 = 60;
+/// How far the turn timer may drift from the server's before it is reset.
+const TIMER_DRIFT: Duration = Duration::from_secs(1);
+/// Width of the turn timer gauge, in cells.
+const TIMER_GAUGE_WIDTH: u64 = 10;
+/// Chat and system lines kept for the chat panel.
+pub const CHAT_HISTORY: usize = 200;
+
+/// Marks a chat line announcing a game milestone.
+pub const MILESTONE_TAG: &str = "★";
+
+/// A chat history holding a single opening line.
+pub fn chat_history(first: impl Into<String>) -> History<String> {
+    let mut history = History::new(CHAT_HISTORY);
+    history.push(first.into());
+    history
+}
 
 /// Dice rolling animation state
 #[derive(Debug, Clone)]
@@ -29,21 +52,36 @@ pub struct RollAnimation {
     pub started_at: Instant,
     pub last_frame: Instant,
     pub current_display: [u8; 5],
+    duration: Duration,
 }
 
 impl RollAnimation {
     pub fn new(final_dice: DiceSet) -> Self {
+        Self::with_pace(final_dice, None)
+    }
+
+    /// An animation as long as the game's pace suggests.
+    pub fn with_pace(final_dice: DiceSet, pace: Option<Pace>) -> Self {
         let now = Instant::now();
+        // Keeping still, show the result until the first tick ends it
+        let current_display = if !super::reduce_motion() {
+            [1, 1, 1, 1, 1]
+        } else {
+            final_dice.values()
+        };
         Self {
             final_dice,
             started_at: now,
             last_frame: now,
-            current_display: [1, 1, 1, 1, 1],
+            current_display,
+            duration: super::roll_duration()
+                .or(pace.map(Pace::roll_animation))
+                .unwrap_or(ROLL_ANIM_DURATION),
         }
     }
 
     pub fn is_done(&self) -> bool {
-        self.started_at.elapsed() >= ROLL_ANIM_DURATION
+        super::reduce_motion() || self.started_at.elapsed() >= self.duration
     }
 
     /// Advance animation frame, returns true if display changed
@@ -56,11 +94,11 @@ impl RollAnimation {
         }
         self.last_frame = Instant::now();
         let mut rng = rand::rngs::StdRng::from_entropy();
-        for i in 0..5 {
-            if !self.final_dice.dice[i].held {
-                self.current_display[i] = rng.gen_range(1..=6);
+        for (shown, die) in self.current_display.iter_mut().zip(self.final_dice.dice.iter()) {
+            if !die.held {
+                *shown = rng.gen_range(1..=6);
             } else {
-                self.current_display[i] = self.final_dice.dice[i].value;
+                *shown = die.value;
             }
         }
         true
@@ -68,14 +106,52 @@ impl RollAnimation {
 
     /// Get dice to display during animation
     pub fn display_dice(&self) -> [Die; 5] {
-        let mut dice = [Die { value: 1, held: false }; 5];
-        for i in 0..5 {
-            dice[i] = Die {
-                value: self.current_display[i],
-                held: self.final_dice.dice[i].held,
-            };
+        std::array::from_fn(|i| Die {
+            value: self.current_display[i],
+            held: self.final_dice.dice[i].held,
+        })
+    }
+}
+
+/// Everyone's total as the last round ended, as the server summed it up.
+#[derive(Debug, Clone)]
+pub struct RoundStandings {
+    pub round: u8,
+    /// Names and totals, highest first.
+    pub totals: Vec<(String, u16)>,
+    pub leaders: Vec<String>,
+}
+
+impl RoundStandings {
+    pub fn new(round: u8, totals: &[(Uuid, String, u16)], leader_ids: &[Uuid]) -> Self {
+        let mut sorted: Vec<(String, u16)> = totals.iter().map(|(_, name, total)| (name.clone(), *total)).collect();
+        sorted.sort_by_key(|&(_, total)| std::cmp::Reverse(total));
+        Self {
+            round,
+            totals: sorted,
+            leaders: totals
+                .iter()
+                .filter(|(id, _, _)| leader_ids.contains(id))
+                .map(|(_, name, _)| name.clone())
+                .collect(),
+        }
+    }
+
+    /// Totals on one line, e.g. "Bob 87 · Alice 64".
+    pub fn compact(&self) -> String {
+        self.totals
+            .iter()
+            .map(|(name, total)| format!("{} {}", name, total))
+            .collect::<Vec<_>>()
+            .join(" · ")
+    }
+
+    /// The chat line announcing the end of the round.
+    pub fn summary(&self) -> String {
+        match self.leaders.as_slice() {
+            [leader] => tr!(RoundOverLeader, self.round, leader, self.compact()),
+            leaders => tr!(RoundOverTied, self.round, leaders.join(" & "), self.compact()),
         }
-        dice
     }
 }
 
@@ -87,7 +163,7 @@ pub struct GameScreen {
     pub rolls_remaining: u8,
     pub round: u8,
     pub current_turn_player_id: Option<Uuid>,
-    pub chat_messages: Vec<String>,
+    pub chat_messages: History<String>,
     pub chat_input: String,
     pub chat_focused: bool,
     pub selected_category_index: usize,
@@ -95,8 +171,34 @@ pub struct GameScreen {
     // Animation state
     pub roll_animation: Option<RollAnimation>,
     pub score_flash: Option<(Category, u16, Instant)>,
+    /// When the action bar started flashing to say it isn't our turn
+    action_flash: Option<Instant>,
     // Turn timer
     pub turn_started_at: Instant,
+    /// Seconds shown by the turn timer at the last tick
+    timer_shown: u64,
+    /// The running time bank as last reported: whose, seconds left and when
+    time_bank_synced: Option<(Uuid, u32, Instant)>,
+    /// (played, total) while a spectator catch-up replay is running
+    pub replay_progress: Option<(u32, u32)>,
+    /// Player a spectator has locked the view to; `None` follows the turn
+    pub focus: Option<Uuid>,
+    /// (this game, this room) face counts while the dice stats popup is open
+    pub dice_stats: Option<Box<(FaceCounts, FaceCounts)>>,
+    /// Whether the scoreboard shows each player's gap to the leader
+    pub show_leader_gap: bool,
+    /// Whether the connection dropped and is being resumed
+    pub reconnecting: bool,
+    /// Game actions waiting to be sent once the session resumes
+    pub queued_actions: usize,
+    /// Names of everyone watching the game
+    pub spectators: Vec<String>,
+    /// The room the game is played in, when we came from its waiting room
+    pub room_name: Option<String>,
+    /// A category picked for zero points, waiting for a second press
+    pub pending_zero: Option<Category>,
+    /// Totals as of the last round to end, shown under the title
+    pub round_standings: Option<RoundStandings>,
 }
 
 impl GameScreen {
@@ -107,71 +209,250 @@ impl GameScreen {
             .map(|p| p.id);
         let round = game_state.round;
         let dice = game_state.dice;
-        let rolls_remaining = MAX_ROLLS - game_state.rolls_used;
+        let rolls_remaining = game_state.rolls_remaining();
 
-        Self {
+        let mut screen = Self {
             game_state,
             my_player_id,
             dice,
             rolls_remaining,
             round,
             current_turn_player_id: current_pid,
-            chat_messages: vec!["[System] Game started!".into()],
+            chat_messages: chat_history(format!("[System] {}", tr!(GameStarted))),
             chat_input: String::new(),
             chat_focused: false,
             selected_category_index: 0,
             status_message: None,
             roll_animation: None,
             score_flash: None,
+            action_flash: None,
             turn_started_at: Instant::now(),
-        }
+            timer_shown: TURN_TIMER_SECONDS,
+            time_bank_synced: None,
+            replay_progress: None,
+            focus: None,
+            dice_stats: None,
+            show_leader_gap: false,
+            reconnecting: false,
+            queued_actions: 0,
+            spectators: Vec::new(),
+            room_name: None,
+            pending_zero: None,
+            round_standings: None,
+        };
+        screen.sync_turn_timer();
+        screen
     }
 
     pub fn update_from_snapshot(&mut self, snapshot: GameStateSnapshot) {
         self.dice = snapshot.dice;
-        self.rolls_remaining = MAX_ROLLS - snapshot.rolls_used;
+        self.rolls_remaining = snapshot.rolls_remaining();
         self.round = snapshot.round;
         self.current_turn_player_id = snapshot
             .players
             .get(snapshot.current_player_index)
             .map(|p| p.id);
         self.game_state = snapshot;
+        self.sync_turn_timer();
     }
 
-    /// Called on each tick to advance animations
-    pub fn tick(&mut self) {
+    /// Called on each tick to advance animations. Returns true if the
+    /// screen needs redrawing.
+    pub fn tick(&mut self) -> bool {
+        let mut changed = false;
+
         // Advance dice rolling animation
         if let Some(ref mut anim) = self.roll_animation {
             if anim.is_done() {
                 // Animation finished, set final dice
                 self.dice = Some(anim.final_dice);
                 self.roll_animation = None;
+                changed = true;
             } else {
-                anim.tick();
+                changed |= anim.tick();
             }
         }
 
-        // Clear expired score flash
+        // The status line blinks while a score flash is active
         if let Some((_, _, started)) = self.score_flash {
-            if started.elapsed() >= SCORE_FLASH_DURATION {
+            if started.elapsed() >= super::score_flash_duration() {
                 self.score_flash = None;
             }
+            changed = true;
+        }
+
+        if let Some(started) = self.action_flash {
+            if started.elapsed() >= ACTION_FLASH_DURATION {
+                self.action_flash = None;
+            }
+            changed = true;
+        }
+
+        let remaining = self.turn_remaining_seconds();
+        if remaining != self.timer_shown {
+            self.timer_shown = remaining;
+            changed = true;
         }
+        changed |= self.tick_time_bank();
+
+        changed
+    }
+
+    /// Flash the action bar, for an action tried out of turn.
+    pub fn flash_action_bar(&mut self) {
+        self.action_flash = Some(Instant::now());
+    }
+
+    /// Follow the server's count of the current player's time bank.
+    pub fn set_time_bank(&mut self, player_id: Uuid, seconds_left: u32) {
+        self.time_bank_synced = Some((player_id, seconds_left, Instant::now()));
+        self.tick_time_bank();
+    }
+
+    /// Stop counting down a time bank, leaving it at what was last shown.
+    pub fn stop_time_bank(&mut self) {
+        self.time_bank_synced = None;
+    }
+
+    /// Count the running time bank down in the scoreboard. Returns true if
+    /// the seconds shown changed.
+    fn tick_time_bank(&mut self) -> bool {
+        let Some((player_id, seconds_left, synced_at)) = self.time_bank_synced else {
+            return false;
+        };
+        let left = seconds_left.saturating_sub(synced_at.elapsed().as_secs() as u32);
+        let Some(player) = self.game_state.players.iter_mut().find(|p| p.id == player_id) else {
+            return false;
+        };
+        if player.time_bank == Some(left) {
+            return false;
+        }
+        player.time_bank = Some(left);
+        true
+    }
+
+    /// Whether an animation is running and ticks should come quickly.
+    pub fn is_animating(&self) -> bool {
+        self.roll_animation.is_some() || self.score_flash.is_some()
+    }
+
+    /// Whether we are watching rather than playing.
+    pub fn is_spectator(&self) -> bool {
+        !self.game_state.players.iter().any(|p| p.id == self.my_player_id)
+    }
+
+    /// Step the spectator focus through the players in seat order, then
+    /// back to following the current turn.
+    pub fn cycle_focus(&mut self) {
+        let players = &self.game_state.players;
+        let next = match self.focus.and_then(|id| players.iter().position(|p| p.id == id)) {
+            Some(idx) => players.get(idx + 1),
+            None => players.first(),
+        };
+        self.focus = next.map(|p| p.id);
+    }
+
+    /// Who a spectator is watching, e.g. "Focused on Alice".
+    pub fn focus_label(&self) -> String {
+        let focused = self
+            .focus
+            .and_then(|id| self.game_state.players.iter().find(|p| p.id == id));
+        match focused {
+            Some(player) => tr!(FocusedOn, player.name),
+            None => tr!(FollowingTurn).to_string(),
+        }
+    }
+
+    /// Index of the player the scoreboard centres on: for spectators the
+    /// locked player, or else whoever's turn it is.
+    fn focused_player_index(&self) -> Option<usize> {
+        if !self.is_spectator() {
+            return None;
+        }
+        let players = &self.game_state.players;
+        self.focus
+            .and_then(|id| players.iter().position(|p| p.id == id))
+            .or_else(|| {
+                let current = self.current_turn_player_id?;
+                players.iter().position(|p| p.id == current)
+            })
+    }
+
+    /// Names of the players still in the game who play after the current
+    /// one, in turn order.
+    fn up_next(&self) -> Vec<&str> {
+        let players = &self.game_state.players;
+        let current = self.game_state.current_player_index;
+        (1..players.len())
+            .map(|offset| &players[(current + offset) % players.len()])
+            .filter(|p| !p.forfeited)
+            .map(|p| p.name.as_str())
+            .collect()
     }
 
     pub fn is_my_turn(&self, my_id: &Uuid) -> bool {
         self.current_turn_player_id.as_ref() == Some(my_id)
     }
 
+    fn turn_seconds(&self) -> u64 {
+        self.game_state
+            .rules
+            .turn_seconds
+            .map_or(TURN_TIMER_SECONDS, u64::from)
+    }
+
     pub fn turn_remaining_seconds(&self) -> u64 {
         let elapsed = self.turn_started_at.elapsed().as_secs();
-        TURN_TIMER_SECONDS.saturating_sub(elapsed)
+        self.turn_seconds().saturating_sub(elapsed)
+    }
+
+    /// Line the turn timer up with the snapshot's deadline, so a countdown
+    /// picked up mid-turn shows the time actually left.
+    fn sync_turn_timer(&mut self) {
+        let Some(deadline) = self.game_state.turn_deadline else {
+            return;
+        };
+        let total = self.turn_seconds();
+        let left = (deadline - chrono::Utc::now().timestamp()).clamp(0, total as i64) as u64;
+        let now = Instant::now();
+        self.turn_started_at = now
+            .checked_sub(Duration::from_secs(total - left))
+            .unwrap_or(now);
+    }
+
+    /// Follow the server's clock for the current turn. Drift under a
+    /// second is ignored, so the countdown runs on smoothly between updates.
+    pub fn set_turn_seconds_left(&mut self, seconds_left: u32) {
+        let total = Duration::from_secs(self.turn_seconds());
+        let left = Duration::from_secs(seconds_left.into()).min(total);
+        let shown = total.saturating_sub(self.turn_started_at.elapsed());
+        if shown.max(left) - shown.min(left) < TIMER_DRIFT {
+            return;
+        }
+        let now = Instant::now();
+        self.turn_started_at = now.checked_sub(total - left).unwrap_or(now);
     }
 
     pub fn reset_turn_timer(&mut self) {
         self.turn_started_at = Instant::now();
     }
 
+    /// Whether scoring `category` should wait for a second press: the
+    /// pace asks before zeros, and it would score nothing.
+    pub fn confirm_zero(&mut self, category: Category) -> bool {
+        let confirms = Pace::of(&self.game_state.rules).is_some_and(Pace::confirms_zero_scores);
+        let zero = self
+            .dice
+            .is_some_and(|dice| scoring::compute_score(category, &dice.values()) == 0);
+        if !confirms || !zero || self.pending_zero == Some(category) {
+            self.pending_zero = None;
+            return false;
+        }
+        self.pending_zero = Some(category);
+        self.status_message = Some(tr!(ConfirmZero, category.display_name()));
+        true
+    }
+
     pub fn selected_category(&self) -> Option<Category> {
         let me = self
             .game_state
@@ -237,20 +518,39 @@ impl GameScreen {
     }
 
     pub fn draw(&self, frame: &mut Frame) {
-        let area = frame.area();
+        let mut area = frame.area();
+        if self.reconnecting {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(1), Constraint::Min(0)])
+                .split(area);
+            self.draw_reconnect_banner(frame, chunks[0]);
+            area = chunks[1];
+        }
+        // Streamer mode trades chat space for bigger dice and scoreboard
+        let (left_percent, mut dice_height, chat_min) = if streamer::enabled() {
+            (50, 11, 3)
+        } else {
+            (55, 9, 5)
+        };
+        // Too short for dice boxes and the panels under them, as in a split
+        // pane: the dice go on one line
+        if area.height < 2 + dice_height + 4 + chat_min {
+            dice_height = 3;
+        }
 
         let main_chunks = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+            .constraints([Constraint::Percentage(left_percent), Constraint::Percentage(100 - left_percent)])
             .split(area);
 
         let left_chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(2), // Title
-                Constraint::Length(9), // Dice
-                Constraint::Length(4), // Actions
-                Constraint::Min(5),   // Chat
+                Constraint::Length(2),           // Title
+                Constraint::Length(dice_height), // Dice
+                Constraint::Length(4),           // Actions
+                Constraint::Min(chat_min),       // Chat
             ])
             .split(main_chunks[0]);
 
@@ -259,6 +559,24 @@ impl GameScreen {
         self.draw_action_bar(frame, left_chunks[2]);
         self.draw_chat_panel(frame, left_chunks[3]);
         self.draw_scoreboard(frame, main_chunks[1]);
+
+        if let Some((game, room)) = self.dice_stats.as_deref() {
+            dice_stats::draw_dice_stats_popup(frame, game, room);
+        }
+    }
+
+    fn draw_reconnect_banner(&self, frame: &mut Frame, area: ratatui::layout::Rect) {
+        let mut text = format!(" {}", tr!(Reconnecting));
+        if self.queued_actions > 0 {
+            text.push_str(&format!("  {}", tr!(ActionsQueued, self.queued_actions)));
+        }
+        let banner = Paragraph::new(text).style(
+            Style::default()
+                .fg(Color::Rgb(30, 30, 30))
+                .bg(Color::Rgb(255, 180, 60))
+                .add_modifier(Modifier::BOLD),
+        );
+        frame.render_widget(banner, area);
     }
 
     fn draw_title_bar(&self, frame: &mut Frame, area: ratatui::layout::Rect) {
@@ -276,17 +594,18 @@ impl GameScreen {
             Color::Rgb(180, 180, 200)
         };
 
-        // Turn timer
+        // Turn timer, yellow from half time and red near the end
         let remaining = self.turn_remaining_seconds();
-        let timer_color = if remaining <= 10 {
-            Color::Rgb(255, 80, 80) // Red when low
-        } else if remaining <= 20 {
-            Color::Rgb(255, 200, 100) // Orange when medium
+        let total = self.turn_seconds().max(1);
+        let timer_color = if remaining <= 10 || remaining * 4 <= total {
+            Color::Rgb(255, 80, 80)
+        } else if remaining * 2 <= total {
+            Color::Rgb(255, 220, 50)
         } else {
-            Color::Rgb(100, 100, 120) // Dim when plenty of time
+            Color::Rgb(100, 100, 120)
         };
 
-        let title = Line::from(vec![
+        let mut title = Line::from(vec![
             Span::styled(
                 " YAHT ",
                 Style::default()
@@ -294,11 +613,11 @@ impl GameScreen {
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(
-                format!(" Round {}/{}", self.round, self.game_state.total_rounds),
+                format!(" {}", tr!(Round, self.round, self.game_state.total_rounds)),
                 Style::default().fg(Color::Rgb(150, 150, 170)),
             ),
             Span::styled("  |  ", Style::default().fg(Color::Rgb(80, 80, 100))),
-            Span::styled("Turn: ", Style::default().fg(Color::Rgb(150, 150, 170))),
+            Span::styled(tr!(Turn), Style::default().fg(Color::Rgb(150, 150, 170))),
             Span::styled(
                 current_name,
                 Style::default()
@@ -307,23 +626,57 @@ impl GameScreen {
             ),
             Span::styled("  |  ", Style::default().fg(Color::Rgb(80, 80, 100))),
             Span::styled(
-                format!("{}s", remaining),
+                timer_gauge(remaining, total),
+                Style::default().fg(timer_color).bg(Color::Rgb(40, 40, 50)),
+            ),
+            Span::styled(
+                format!(" {}s", remaining),
                 Style::default().fg(timer_color).add_modifier(if remaining <= 10 { Modifier::BOLD } else { Modifier::empty() }),
             ),
         ]);
-        frame.render_widget(Paragraph::new(title), area);
+        if let Some((played, total)) = self.replay_progress {
+            title.spans.extend([
+                Span::styled("  |  ", Style::default().fg(Color::Rgb(80, 80, 100))),
+                Span::styled(
+                    format!("{} ", tr!(Replay, played, total)),
+                    Style::default()
+                        .fg(Color::Rgb(200, 150, 255))
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled("[Tab]", Style::default().fg(Color::Rgb(100, 255, 150))),
+                Span::styled(format!(" {}", tr!(ActSkipToLive)), Style::default().fg(Color::Rgb(120, 120, 140))),
+            ]);
+        }
+        let mut details = Vec::new();
+        let up_next = self.up_next();
+        if !up_next.is_empty() {
+            details.push(tr!(UpNext, up_next.join(" → ")));
+        }
+        if let Some(standings) = &self.round_standings {
+            details.push(tr!(AfterRound, standings.round, standings.compact()));
+        }
+        if !self.spectators.is_empty() {
+            details.push(tr!(Watching, self.spectators.join(", ")));
+        }
+        let mut lines = vec![title];
+        if !details.is_empty() {
+            lines.push(Line::from(Span::styled(
+                format!(" {}", details.join("  |  ")),
+                Style::default().fg(Color::Rgb(120, 120, 140)),
+            )));
+        }
+        frame.render_widget(Paragraph::new(lines), area);
     }
 
     fn draw_dice_area(&self, frame: &mut Frame, area: ratatui::layout::Rect) {
         // Check if we're in a rolling animation
         if let Some(ref anim) = self.roll_animation {
-            let anim_dice = anim.display_dice();
-            let lines = dice_widget::render_dice_row_animated(&anim_dice, true);
+            let lines = self.dice_lines(&anim.display_dice(), true, area);
             let paragraph = Paragraph::new(lines).block(
                 Block::default()
                     .borders(Borders::ALL)
                     .border_style(Style::default().fg(Color::Rgb(100, 200, 255)))
-                    .title(" Dice - Rolling... ")
+                    .title(format!(" {} ", tr!(DiceRolling)))
                     .title_style(
                         Style::default()
                             .fg(Color::Rgb(100, 200, 255))
@@ -332,29 +685,58 @@ impl GameScreen {
             );
             frame.render_widget(paragraph, area);
         } else if let Some(ref dice) = self.dice {
-            let lines = dice_widget::render_dice_row(&dice.dice);
+            let lines = self.dice_lines(&dice.dice, false, area);
             let paragraph = Paragraph::new(lines).block(
                 Block::default()
                     .borders(Borders::ALL)
                     .border_style(Style::default().fg(Color::Rgb(80, 80, 100)))
-                    .title(" Dice ")
+                    .title(format!(" {} ", tr!(Dice)))
                     .title_style(Style::default().fg(Color::Rgb(180, 180, 200))),
             );
             frame.render_widget(paragraph, area);
         } else {
-            let paragraph = Paragraph::new("  Waiting for roll...")
+            let paragraph = Paragraph::new(format!("  {}", tr!(WaitingForRoll)))
                 .style(Style::default().fg(Color::Rgb(100, 100, 120)))
                 .block(
                     Block::default()
                         .borders(Borders::ALL)
                         .border_style(Style::default().fg(Color::Rgb(60, 60, 80)))
-                        .title(" Dice ")
+                        .title(format!(" {} ", tr!(Dice)))
                         .title_style(Style::default().fg(Color::Rgb(120, 120, 140))),
                 );
             frame.render_widget(paragraph, area);
         }
     }
 
+    /// The dice row for the dice area `area`: a single line of die faces if
+    /// it's too short for the boxes. Where the terminal can show pictures,
+    /// the boxes are left blank and a picture placed over each.
+    fn dice_lines(&self, dice: &[Die; 5], rolling: bool, area: ratatui::layout::Rect) -> Vec<Line<'static>> {
+        let large = streamer::enabled();
+        let inner = area.inner(Margin::new(1, 1));
+        if inner.height < dice_widget::row_height(large) {
+            return vec![dice_widget::render_dice_micro(dice, rolling)];
+        }
+        let boxes: Option<Vec<_>> = (0..dice.len())
+            .map(|i| dice_widget::die_box(inner, i, large))
+            .collect();
+        // Pictures would cover the dice stats popup
+        let Some(boxes) = boxes.filter(|_| graphics::active() && self.dice_stats.is_none()) else {
+            return dice_widget::render_dice_row_animated(dice, rolling, large);
+        };
+        for (die, cell) in dice.iter().zip(boxes) {
+            let look = if die.held {
+                DieLook::Held
+            } else if rolling {
+                DieLook::Rolling
+            } else {
+                DieLook::Plain
+            };
+            graphics::place_die(cell, die.value, look);
+        }
+        dice_widget::render_dice_labels(dice, large)
+    }
+
     fn draw_action_bar(&self, frame: &mut Frame, area: ratatui::layout::Rect) {
         let is_my_turn = self.is_my_turn(&self.my_player_id);
         let is_rolling = self.roll_animation.is_some();
@@ -376,7 +758,7 @@ impl GameScreen {
 
         if is_rolling {
             lines.push(Line::from(Span::styled(
-                "  Rolling dice...",
+                format!("  {}", tr!(RollingDice)),
                 Style::default()
                     .fg(Color::Rgb(100, 200, 255))
                     .add_modifier(Modifier::BOLD),
@@ -391,7 +773,7 @@ impl GameScreen {
                         .add_modifier(Modifier::BOLD),
                 ));
                 spans.push(Span::styled(
-                    format!(" Roll ({} left)  ", self.rolls_remaining),
+                    format!(" {}  ", tr!(ActRollLeft, self.rolls_remaining)),
                     Style::default().fg(Color::Rgb(150, 150, 170)),
                 ));
             }
@@ -400,7 +782,7 @@ impl GameScreen {
                 Style::default().fg(Color::Rgb(100, 200, 255)),
             ));
             spans.push(Span::styled(
-                " Hold  ",
+                format!(" {}  ", tr!(ActHold)),
                 Style::default().fg(Color::Rgb(150, 150, 170)),
             ));
             if can_score {
@@ -411,7 +793,7 @@ impl GameScreen {
                         .add_modifier(Modifier::BOLD),
                 ));
                 spans.push(Span::styled(
-                    " Score  ",
+                    format!(" {}  ", tr!(ActScore)),
                     Style::default().fg(Color::Rgb(150, 150, 170)),
                 ));
             }
@@ -420,13 +802,19 @@ impl GameScreen {
                 Style::default().fg(Color::Rgb(100, 180, 255)),
             ));
             spans.push(Span::styled(
-                " Chat",
+                format!(" {}", tr!(ActChat)),
                 Style::default().fg(Color::Rgb(150, 150, 170)),
             ));
             lines.push(Line::from(spans));
+        } else if self.is_spectator() {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {}  ", self.focus_label()), Style::default().fg(Color::Rgb(150, 150, 170))),
+                Span::styled("[F]", Style::default().fg(Color::Rgb(100, 200, 255))),
+                Span::styled(format!(" {}", tr!(ActFocus)), Style::default().fg(Color::Rgb(150, 150, 170))),
+            ]));
         } else {
             lines.push(Line::from(Span::styled(
-                "  Waiting for other player's turn...",
+                format!("  {}", tr!(WaitingForOthers)),
                 Style::default().fg(Color::Rgb(100, 100, 120)),
             )));
         }
@@ -434,7 +822,7 @@ impl GameScreen {
         if let Some(ref msg) = self.status_message {
             let style = if let Some((_, _, started)) = self.score_flash {
                 let elapsed = started.elapsed().as_millis();
-                let blink = (elapsed / 200) % 2 == 0;
+                let blink = !super::reduce_motion() && (elapsed / 200) % 2 == 0;
                 if blink {
                     Style::default()
                         .fg(Color::Rgb(255, 220, 50))
@@ -448,38 +836,56 @@ impl GameScreen {
             lines.push(Line::from(Span::styled(format!("  {}", msg), style)));
         }
 
-        frame.render_widget(Paragraph::new(lines), area);
+        let mut bar = Paragraph::new(lines);
+        if self.action_flash.is_some() && super::animations() {
+            bar = bar.style(Style::default().bg(Color::Rgb(90, 30, 30)));
+        }
+        frame.render_widget(bar, area);
     }
 
     fn draw_chat_panel(&self, frame: &mut Frame, area: ratatui::layout::Rect) {
         let inner_height = area.height.saturating_sub(2) as usize;
-        let skip = if self.chat_messages.len() > inner_height.saturating_sub(1) {
-            self.chat_messages.len() - (inner_height.saturating_sub(1))
-        } else {
-            0
-        };
-
-        let mut lines: Vec<Line> = self.chat_messages[skip..]
+        let my_name = self
+            .game_state
+            .players
             .iter()
+            .find(|p| p.id == self.my_player_id)
+            .map(|p| p.name.as_str());
+        let mut lines: Vec<Line> = self
+            .chat_messages
+            .recent(inner_height.saturating_sub(1))
             .map(|msg| {
-                if msg.starts_with("[System]") {
+                if msg.starts_with(MILESTONE_TAG) {
+                    Line::from(Span::styled(
+                        format!("  {}", msg),
+                        Style::default()
+                            .fg(Color::Rgb(255, 200, 80))
+                            .add_modifier(Modifier::BOLD),
+                    ))
+                } else if msg.starts_with("[System]") {
                     Line::from(Span::styled(
                         format!("  {}", msg),
                         Style::default().fg(Color::Rgb(100, 100, 120)),
                     ))
                 } else if let Some(colon_pos) = msg.find(':') {
                     let (name, rest) = msg.split_at(colon_pos);
+                    // Someone else saying our name stands out from the rest
+                    let mentioned = my_name.is_some_and(|me| name != me && mentions(rest, me));
+                    let rest_style = if mentioned {
+                        Style::default()
+                            .fg(Color::Rgb(255, 220, 100))
+                            .add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default().fg(Color::Rgb(200, 200, 220))
+                    };
                     Line::from(vec![
                         Span::styled(
-                            format!("  {}", name),
+                            format!("{}{}", if mentioned { "» " } else { "  " }, name),
                             Style::default()
                                 .fg(Color::Rgb(100, 200, 255))
                                 .add_modifier(Modifier::BOLD),
                         ),
-                        Span::styled(
-                            rest.to_string(),
-                            Style::default().fg(Color::Rgb(200, 200, 220)),
-                        ),
+                        Span::styled(rest.to_string(), rest_style),
                     ])
                 } else {
                     Line::from(Span::styled(
@@ -490,17 +896,21 @@ impl GameScreen {
             })
             .collect();
 
+        let inner = area.inner(Margin::new(1, 1));
         let prefix = if self.chat_focused { "  > " } else { "  " };
+        let input = super::input_tail(&self.chat_input, inner.width.saturating_sub(prefix.len() as u16));
         let style = if self.chat_focused {
             Style::default().fg(Color::White)
         } else {
             Style::default().fg(Color::Rgb(80, 80, 100))
         };
         lines.push(Line::from(Span::styled(
-            format!("{}{}", prefix, self.chat_input),
+            format!("{}{}", prefix, input),
             style,
         )));
 
+        let input_row = lines.len() as u16 - 1;
+
         let (border_style, title_style) = if self.chat_focused {
             (
                 Style::default().fg(Color::Rgb(100, 180, 255)),
@@ -519,15 +929,15 @@ impl GameScreen {
             Block::default()
                 .borders(Borders::ALL)
                 .border_style(border_style)
-                .title(" Chat ")
+                .title(format!(" {} ", tr!(Chat)))
                 .title_style(title_style),
         );
         frame.render_widget(paragraph, area);
 
         if self.chat_focused {
-            let cursor_x = area.x + 4 + self.chat_input.len() as u16;
-            let cursor_y = area.y + area.height - 2;
-            frame.set_cursor_position((cursor_x, cursor_y));
+            let cursor_x = inner.x + prefix.len() as u16 + input.chars().count() as u16;
+            let cursor_y = inner.y + input_row;
+            frame.set_cursor_position(super::clamp_cursor(inner, (cursor_x, cursor_y)));
         }
     }
 
@@ -561,7 +971,7 @@ impl GameScreen {
 
         // Get the flash category for highlighting
         let flash_cat = self.score_flash.as_ref().and_then(|(cat, score, started)| {
-            if started.elapsed() < SCORE_FLASH_DURATION {
+            if started.elapsed() < super::score_flash_duration() {
                 Some((*cat, *score))
             } else {
                 None
@@ -575,7 +985,40 @@ impl GameScreen {
             self.my_player_id,
             selected_all_idx,
             flash_cat,
+            ScoreboardLayout {
+                large: streamer::enabled(),
+                focus: self.focused_player_index(),
+                leader_gap: self.show_leader_gap,
+            },
         );
         frame.render_widget(table, area);
     }
 }
+
+/// Whether `text` contains `name` as a whole word, ignoring case.
+fn mentions(text: &str, name: &str) -> bool {
+    if name.is_empty() {
+        return false;
+    }
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    let text = text.to_lowercase();
+    let name = name.to_lowercase();
+    text.match_indices(&name).any(|(i, _)| {
+        let before = text[..i].chars().next_back();
+        let after = text[i + name.len()..].chars().next();
+        !before.is_some_and(is_word) && !after.is_some_and(is_word)
+    })
+}
+
+/// A bar showing `remaining` of `total` seconds, in eighths of a cell.
+fn timer_gauge(remaining: u64, total: u64) -> String {
+    const EIGHTHS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+    let filled = (remaining.min(total) * TIMER_GAUGE_WIDTH * 8).div_ceil(total.max(1));
+    let full = (filled / 8) as usize;
+    let mut gauge = "█".repeat(full);
+    if full < TIMER_GAUGE_WIDTH as usize {
+        gauge.push(EIGHTHS[(filled % 8) as usize]);
+        gauge.push_str(&" ".repeat(TIMER_GAUGE_WIDTH as usize - full - 1));
+    }
+    gauge
+}
@@ -6,7 +6,10 @@ use ratatui::{
     Frame,
 };
 
-pub fn draw_help_popup(frame: &mut Frame) {
+use yaht_common::scoring::ScoringRules;
+
+/// Show the rules and controls, with the bonuses of the game's `scoring`.
+pub fn draw_help_popup(frame: &mut Frame, scoring: &ScoringRules) {
     let area = frame.area();
 
     // Center popup
@@ -15,45 +18,52 @@ pub fn draw_help_popup(frame: &mut Frame) {
     // Clear background
     frame.render_widget(Clear, popup_area);
 
+    let upper_bonus = tr!(HelpUpperBonusDesc, scoring.upper_bonus, scoring.upper_bonus_threshold);
+    let yahtzee_bonus = tr!(HelpYahtzeeBonusDesc, scoring.yahtzee_bonus);
+
     let sections = vec![
         (
-            "YAHTZEE SCORING RULES",
+            tr!(HelpRules),
             Color::Rgb(255, 220, 50),
             vec![],
         ),
         (
-            "Upper Section",
+            tr!(HelpUpperSection),
             Color::Rgb(100, 200, 255),
             vec![
-                ("Ones - Sixes", "Sum of matching dice face values"),
-                ("Upper Bonus", "+35 if upper total >= 63"),
+                (tr!(HelpOnesToSixes), tr!(HelpOnesToSixesDesc)),
+                (tr!(HelpUpperBonus), upper_bonus.as_str()),
             ],
         ),
         (
-            "Lower Section",
+            tr!(HelpLowerSection),
             Color::Rgb(200, 150, 255),
             vec![
-                ("3 of a Kind", "Sum of all dice if 3+ match"),
-                ("4 of a Kind", "Sum of all dice if 4+ match"),
-                ("Full House", "25 pts (3 of one + 2 of another)"),
-                ("Sm. Straight", "30 pts (4 consecutive dice)"),
-                ("Lg. Straight", "40 pts (5 consecutive dice)"),
-                ("YAHTZEE", "50 pts (all 5 dice the same)"),
-                ("Chance", "Sum of all dice (any combination)"),
-                ("Yahtzee Bonus", "+100 per extra Yahtzee"),
+                (tr!(HelpThreeOfAKind), tr!(HelpThreeOfAKindDesc)),
+                (tr!(HelpFourOfAKind), tr!(HelpFourOfAKindDesc)),
+                (tr!(HelpFullHouse), tr!(HelpFullHouseDesc)),
+                (tr!(HelpSmallStraight), tr!(HelpSmallStraightDesc)),
+                (tr!(HelpLargeStraight), tr!(HelpLargeStraightDesc)),
+                (tr!(HelpYahtzee), tr!(HelpYahtzeeDesc)),
+                (tr!(HelpChance), tr!(HelpChanceDesc)),
+                (tr!(HelpYahtzeeBonus), yahtzee_bonus.as_str()),
             ],
         ),
         (
-            "CONTROLS",
+            tr!(HelpControls),
             Color::Rgb(100, 255, 150),
             vec![
-                ("[R]", "Roll dice (up to 3 times per turn)"),
-                ("[1]-[5]", "Toggle hold on individual dice"),
-                ("[j]/[k]", "Navigate categories up/down"),
-                ("[S]/[Enter]", "Score selected category"),
-                ("[C]", "Open/close chat"),
-                ("[?]", "Toggle this help screen"),
-                ("[Q]", "Quit game"),
+                ("[R]", tr!(HelpRoll)),
+                ("[1]-[5]", tr!(HelpHold)),
+                ("[j]/[k]", tr!(HelpNavigate)),
+                ("[S]/[Enter]", tr!(HelpScore)),
+                ("[C]", tr!(HelpChat)),
+                ("/help", tr!(HelpCommands)),
+                ("[F]", tr!(HelpFocus)),
+                ("[D]", tr!(HelpDiceStats)),
+                ("[G]", tr!(HelpLeaderGap)),
+                ("[?]", tr!(HelpToggleHelp)),
+                ("[Q]", tr!(HelpQuit)),
             ],
         ),
     ];
@@ -87,7 +97,7 @@ pub fn draw_help_popup(frame: &mut Frame) {
     }
 
     lines.push(Line::from(Span::styled(
-        "  Press [?] or any key to close",
+        format!("  {}", tr!(HelpClose)),
         Style::default().fg(Color::Rgb(100, 100, 120)),
     )));
 
@@ -97,7 +107,7 @@ pub fn draw_help_popup(frame: &mut Frame) {
             Block::default()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Rgb(100, 200, 255)))
-                .title(" Help - Yahtzee Rules & Controls ")
+                .title(format!(" {} ", tr!(HelpTitle)))
                 .title_style(
                     Style::default()
                         .fg(Color::Rgb(255, 220, 50))
@@ -108,7 +118,7 @@ pub fn draw_help_popup(frame: &mut Frame) {
     frame.render_widget(paragraph, popup_area);
 }
 
-fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -0,0 +1,341 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+use yaht_common::player::Scorecard;
+use yaht_common::protocol::{ArchivedGame, GameSummary};
+use yaht_common::scoring::Category;
+
+/// Finished games from the server's archive, and one of them opened to
+/// step through turn by turn.
+#[derive(Debug, Clone)]
+pub struct HistoryScreen {
+    pub games: Vec<GameSummary>,
+    pub table_state: TableState,
+    /// The game being looked at, once the server has sent it.
+    pub open: Option<Box<ArchivedGame>>,
+    /// Turns of the open game played so far in the replay.
+    pub turns_shown: usize,
+    pub my_name: String,
+}
+
+impl HistoryScreen {
+    pub fn new(games: Vec<GameSummary>, my_name: String) -> Self {
+        let mut table_state = TableState::default();
+        if !games.is_empty() {
+            table_state.select(Some(0));
+        }
+        Self {
+            games,
+            table_state,
+            open: None,
+            turns_shown: 0,
+            my_name,
+        }
+    }
+
+    pub fn selected_game_id(&self) -> Option<uuid::Uuid> {
+        self.table_state
+            .selected()
+            .and_then(|i| self.games.get(i))
+            .map(|g| g.game_id)
+    }
+
+    pub fn select_next(&mut self) {
+        if self.games.is_empty() {
+            return;
+        }
+        let i = self.table_state.selected().map_or(0, |i| (i + 1) % self.games.len());
+        self.table_state.select(Some(i));
+    }
+
+    pub fn select_prev(&mut self) {
+        if self.games.is_empty() {
+            return;
+        }
+        let i = self
+            .table_state
+            .selected()
+            .map_or(0, |i| if i == 0 { self.games.len() - 1 } else { i - 1 });
+        self.table_state.select(Some(i));
+    }
+
+    /// Show a game, starting from its final scores.
+    pub fn open_game(&mut self, game: Box<ArchivedGame>) {
+        self.turns_shown = game.turns.len();
+        self.open = Some(game);
+    }
+
+    /// Step the replay by one turn.
+    pub fn step(&mut self, forward: bool) {
+        let Some(game) = &self.open else {
+            return;
+        };
+        self.turns_shown = if forward {
+            (self.turns_shown + 1).min(game.turns.len())
+        } else {
+            self.turns_shown.saturating_sub(1)
+        };
+    }
+
+    pub fn draw(&self, frame: &mut Frame) {
+        match &self.open {
+            Some(game) => self.draw_game(frame, game),
+            None => self.draw_list(frame),
+        }
+    }
+
+    fn draw_list(&self, frame: &mut Frame) {
+        let vertical = layout(frame);
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "  GAME HISTORY",
+                Style::default()
+                    .fg(Color::Rgb(255, 220, 50))
+                    .add_modifier(Modifier::BOLD),
+            )))
+            .alignment(Alignment::Center),
+            vertical[1],
+        );
+
+        let header = Row::new(vec!["Finished", "Room", "Players", "Winner"])
+            .style(
+                Style::default()
+                    .fg(Color::Rgb(180, 180, 200))
+                    .add_modifier(Modifier::BOLD),
+            )
+            .bottom_margin(1);
+        let rows: Vec<Row> = self
+            .games
+            .iter()
+            .map(|g| {
+                let players: Vec<String> = g
+                    .players
+                    .iter()
+                    .map(|(name, score)| format!("{} {}", name, score))
+                    .collect();
+                let winner = if g.winners.len() > 1 {
+                    "Draw".to_string()
+                } else {
+                    g.winners.join("")
+                };
+                let style = if g.winners.contains(&self.my_name) {
+                    Style::default().fg(Color::Rgb(100, 255, 150))
+                } else {
+                    Style::default().fg(Color::Rgb(200, 200, 220))
+                };
+                Row::new(vec![
+                    Cell::from(format_date(g.finished_at)),
+                    Cell::from(g.room_name.clone()),
+                    Cell::from(players.join(", ")),
+                    Cell::from(winner),
+                ])
+                .style(style)
+            })
+            .collect();
+
+        let widths = [
+            Constraint::Length(17),
+            Constraint::Length(16),
+            Constraint::Min(20),
+            Constraint::Length(12),
+        ];
+        let empty = self.games.is_empty();
+        let table = Table::new(rows, widths)
+            .header(header)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Rgb(80, 80, 100)))
+                    .title(if empty { " No finished games yet " } else { " Recent games " })
+                    .title_style(
+                        Style::default()
+                            .fg(Color::Rgb(255, 220, 50))
+                            .add_modifier(Modifier::BOLD),
+                    ),
+            )
+            .row_highlight_style(
+                Style::default()
+                    .bg(Color::Rgb(40, 40, 60))
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol(" > ");
+        let mut table_state = self.table_state.clone();
+        frame.render_stateful_widget(table, vertical[2], &mut table_state);
+
+        draw_help(frame, vertical[3], &[("[Enter]", "Open"), ("[Esc]", "Back")]);
+    }
+
+    fn draw_game(&self, frame: &mut Frame, game: &ArchivedGame) {
+        let vertical = layout(frame);
+        let summary = &game.summary;
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::styled(
+                    format!("  {}", summary.room_name),
+                    Style::default()
+                        .fg(Color::Rgb(255, 220, 50))
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!(
+                        "  {}  {} min  turn {}/{}",
+                        format_date(summary.finished_at),
+                        summary.duration_secs / 60,
+                        self.turns_shown,
+                        game.turns.len()
+                    ),
+                    Style::default().fg(Color::Rgb(150, 150, 170)),
+                ),
+            ]))
+            .alignment(Alignment::Center),
+            vertical[1],
+        );
+
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(vertical[2]);
+
+        // Scorecards as they stood after the turns shown
+        let mut cards = vec![Scorecard::new(); game.player_ids.len()];
+        for turn in &game.turns[..self.turns_shown] {
+            if let Some(card) = game.seat(turn).and_then(|seat| cards.get_mut(seat)) {
+                let _ = card.record(turn.category, turn.score);
+                if turn.yahtzee_bonus {
+                    card.add_yahtzee_bonus();
+                }
+            }
+        }
+        let last = self.turns_shown.checked_sub(1).map(|i| &game.turns[i]);
+        let header = Row::new(
+            std::iter::once(Cell::from("Category"))
+                .chain(summary.players.iter().map(|(name, _)| Cell::from(name.clone())))
+                .collect::<Vec<_>>(),
+        )
+        .style(
+            Style::default()
+                .fg(Color::Rgb(180, 180, 200))
+                .add_modifier(Modifier::BOLD),
+        )
+        .bottom_margin(1);
+        let mut rows: Vec<Row> = Category::ALL
+            .iter()
+            .map(|&cat| {
+                let cells = std::iter::once(Cell::from(cat.display_name())).chain(cards.iter().enumerate().map(
+                    |(seat, card)| {
+                        let just_scored =
+                            last.is_some_and(|t| t.category == cat && game.seat(t) == Some(seat));
+                        let style = if just_scored {
+                            Style::default()
+                                .fg(Color::Rgb(255, 220, 50))
+                                .add_modifier(Modifier::BOLD)
+                        } else {
+                            Style::default().fg(Color::Rgb(200, 200, 220))
+                        };
+                        Cell::from(card.score(cat).map_or("·".to_string(), |s| s.to_string())).style(style)
+                    },
+                ));
+                Row::new(cells.collect::<Vec<_>>())
+            })
+            .collect();
+        rows.push(
+            Row::new(
+                std::iter::once(Cell::from("TOTAL"))
+                    .chain(cards.iter().map(|card| Cell::from(card.grand_total().to_string())))
+                    .collect::<Vec<_>>(),
+            )
+            .style(
+                Style::default()
+                    .fg(Color::Rgb(255, 220, 50))
+                    .add_modifier(Modifier::BOLD),
+            ),
+        );
+        let widths: Vec<Constraint> = std::iter::once(Constraint::Length(13))
+            .chain(cards.iter().map(|_| Constraint::Length(10)))
+            .collect();
+        frame.render_widget(
+            Table::new(rows, widths).header(header).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Rgb(80, 80, 100)))
+                    .title(" Scoreboard "),
+            ),
+            columns[0],
+        );
+
+        // The turns so far, latest first
+        let name = |turn| {
+            game.seat(turn)
+                .and_then(|seat| summary.players.get(seat))
+                .map_or("?", |(name, _)| name.as_str())
+        };
+        let lines: Vec<Line> = game.turns[..self.turns_shown]
+            .iter()
+            .rev()
+            .map(|turn| {
+                let dice = turn
+                    .final_dice()
+                    .map(|d| d.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(" "))
+                    .unwrap_or_default();
+                Line::from(vec![
+                    Span::styled(
+                        format!(" R{:<2} {:<10} ", turn.round, name(turn)),
+                        Style::default().fg(Color::Rgb(100, 200, 255)),
+                    ),
+                    Span::styled(format!("[{}] ", dice), Style::default().fg(Color::Rgb(150, 150, 170))),
+                    Span::styled(
+                        format!("{} {}", turn.category.display_name(), turn.score),
+                        Style::default().fg(Color::Rgb(200, 200, 220)),
+                    ),
+                ])
+            })
+            .collect();
+        frame.render_widget(
+            Paragraph::new(lines).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Rgb(80, 80, 100)))
+                    .title(" Turns "),
+            ),
+            columns[1],
+        );
+
+        draw_help(frame, vertical[3], &[("[←/→]", "Step turns"), ("[Esc]", "Back to list")]);
+    }
+}
+
+fn layout(frame: &Frame) -> std::rc::Rc<[ratatui::layout::Rect]> {
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(2), // Title
+            Constraint::Min(5),    // Content
+            Constraint::Length(2), // Help
+        ])
+        .split(frame.area())
+}
+
+fn draw_help(frame: &mut Frame, area: ratatui::layout::Rect, keys: &[(&str, &str)]) {
+    let spans: Vec<Span> = keys
+        .iter()
+        .flat_map(|&(key, label)| {
+            [
+                Span::styled(key.to_string(), Style::default().fg(Color::Rgb(100, 200, 255))),
+                Span::styled(format!(" {}  ", label), Style::default().fg(Color::Rgb(120, 120, 140))),
+            ]
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(Line::from(spans)).alignment(Alignment::Center), area);
+}
+
+fn format_date(ts: i64) -> String {
+    chrono::DateTime::from_timestamp(ts, 0)
+        .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
@@ -0,0 +1,140 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    Frame,
+};
+
+use yaht_common::protocol::LeaderboardEntry;
+
+/// Server rating standings for one season.
+#[derive(Debug, Clone)]
+pub struct LeaderboardScreen {
+    pub season: u32,
+    pub current_season: u32,
+    pub ended_at: Option<i64>,
+    pub standings: Vec<LeaderboardEntry>,
+    pub my_name: String,
+}
+
+impl LeaderboardScreen {
+    pub fn previous_season(&self) -> Option<u32> {
+        (self.season > 1).then(|| self.season - 1)
+    }
+
+    pub fn next_season(&self) -> Option<u32> {
+        (self.season < self.current_season).then(|| self.season + 1)
+    }
+
+    pub fn draw(&self, frame: &mut Frame) {
+        let area = frame.area();
+
+        let vertical = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Length(2), // Title
+                Constraint::Min(5),   // Table
+                Constraint::Length(2), // Help
+            ])
+            .split(area);
+
+        let horizontal = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(15),
+                Constraint::Percentage(70),
+                Constraint::Percentage(15),
+            ])
+            .split(vertical[2]);
+
+        // Title
+        let subtitle = match self.ended_at {
+            Some(ts) => chrono::DateTime::from_timestamp(ts, 0)
+                .map(|t| format!("  (ended {})", t.format("%Y-%m-%d")))
+                .unwrap_or_default(),
+            None => "  (current)".to_string(),
+        };
+        let title = Paragraph::new(Line::from(vec![
+            Span::styled(
+                format!("  LEADERBOARD - SEASON {}", self.season),
+                Style::default()
+                    .fg(Color::Rgb(255, 220, 50))
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(subtitle, Style::default().fg(Color::Rgb(150, 150, 170))),
+        ]))
+        .alignment(Alignment::Center);
+        frame.render_widget(title, vertical[1]);
+
+        // Standings
+        let header = Row::new(vec!["Rank", "Player", "Rating", "Games", "Wins"])
+            .style(
+                Style::default()
+                    .fg(Color::Rgb(180, 180, 200))
+                    .add_modifier(Modifier::BOLD),
+            )
+            .bottom_margin(1);
+
+        let rows: Vec<Row> = self
+            .standings
+            .iter()
+            .map(|e| {
+                let style = if e.player_name == self.my_name {
+                    Style::default()
+                        .fg(Color::Rgb(100, 255, 150))
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::Rgb(200, 200, 220))
+                };
+                Row::new(vec![
+                    Cell::from(format!("  #{}", e.rank)),
+                    Cell::from(e.player_name.clone()),
+                    Cell::from(e.rating.to_string()),
+                    Cell::from(e.games_played.to_string()),
+                    Cell::from(e.wins.to_string()),
+                ])
+                .style(style)
+            })
+            .collect();
+
+        let widths = [
+            Constraint::Length(7),
+            Constraint::Min(16),
+            Constraint::Length(8),
+            Constraint::Length(7),
+            Constraint::Length(6),
+        ];
+        let empty = self.standings.is_empty();
+        let table = Table::new(rows, widths).header(header).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Rgb(80, 80, 100)))
+                .title(if empty { " No rated games yet " } else { " Standings " })
+                .title_style(
+                    Style::default()
+                        .fg(Color::Rgb(255, 220, 50))
+                        .add_modifier(Modifier::BOLD),
+                ),
+        );
+        frame.render_widget(table, horizontal[1]);
+
+        // Help
+        let mut spans = Vec::new();
+        if self.previous_season().is_some() {
+            spans.push(Span::styled("[←]", Style::default().fg(Color::Rgb(100, 200, 255))));
+            spans.push(Span::styled(" Previous season  ", Style::default().fg(Color::Rgb(120, 120, 140))));
+        }
+        if self.next_season().is_some() {
+            spans.push(Span::styled("[→]", Style::default().fg(Color::Rgb(100, 200, 255))));
+            spans.push(Span::styled(" Next season  ", Style::default().fg(Color::Rgb(120, 120, 140))));
+        }
+        spans.push(Span::styled("[Enter]", Style::default().fg(Color::Rgb(100, 255, 150))));
+        spans.push(Span::styled(" Back", Style::default().fg(Color::Rgb(120, 120, 140))));
+        frame.render_widget(
+            Paragraph::new(Line::from(spans)).alignment(Alignment::Center),
+            vertical[3],
+        );
+    }
+}
@@ -2,13 +2,100 @@ use ratatui::{
     layout::{Constraint, Direction, Layout},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState},
     Frame,
 };
 use uuid::Uuid;
 
-use yaht_common::lobby::{RoomInfo, RoomInfoState};
-use yaht_common::protocol::RoomSnapshot;
+use yaht_common::game::{OutOfTime, Pace};
+use yaht_common::lobby::{validate_room_name, RoomInfo, RoomInfoState, RoomNameError, MAX_ROOM_NAME_CHARS};
+use yaht_common::protocol::{Announcements, ClientMessage, RoomSnapshot};
+
+use super::help_popup::centered_rect;
+
+/// Turn timers the host cycles through, in seconds.
+const TURN_TIMER_STEPS: [Option<u32>; 5] = [None, Some(30), Some(60), Some(90), Some(120)];
+/// Time banks the host cycles through, in seconds.
+const TIME_BANK_STEPS: [Option<u32>; 4] = [None, Some(300), Some(600), Some(1200)];
+/// Spectator limits the host cycles through; 0 closes the room to them.
+const SPECTATOR_STEPS: [u8; 4] = [0, 4, 8, 16];
+/// Idle nudges the host cycles through, in seconds; 0 is off.
+const NUDGE_STEPS: [u32; 4] = [0, 20, 45, 90];
+
+/// Shortest start of a room id accepted as its code.
+const MIN_CODE_PREFIX: usize = 4;
+
+/// A waiting room setting the host can change.
+#[derive(Debug, Clone, Copy)]
+pub enum RoomSetting {
+    /// Seats to add or remove.
+    MaxPlayers(i8),
+    Pace,
+    TurnTimer,
+    TimeBank,
+    OutOfTime,
+    SuddenDeath,
+    Locked,
+    AutoStart,
+    Spectators,
+    Announcements,
+    ShuffleOrder,
+    Nudge,
+}
+
+/// A password being typed to join a room that has one.
+#[derive(Debug, Clone)]
+pub struct PasswordPrompt {
+    pub room_id: Uuid,
+    pub room_name: String,
+    pub input: String,
+    /// Whether the last password tried was wrong.
+    pub retry: bool,
+}
+
+/// The name of a room we're creating, being typed.
+#[derive(Debug, Clone)]
+pub struct RoomNamePrompt {
+    pub input: String,
+    /// Why the server turned down the name last sent.
+    pub rejected: Option<String>,
+    /// Sent, until the server opens the room or turns it down.
+    pub sent: bool,
+}
+
+impl RoomNamePrompt {
+    /// What's wrong with the name as typed, checked as the server will.
+    pub fn problem(&self) -> Option<String> {
+        match validate_room_name(&self.input) {
+            Err(e) => Some(room_name_error_text(e)),
+            Ok(_) => self.rejected.clone(),
+        }
+    }
+}
+
+pub fn room_name_error_text(e: RoomNameError) -> String {
+    match e {
+        RoomNameError::Empty => tr!(ErrRoomNameEmpty).to_string(),
+        RoomNameError::TooLong => tr!(ErrRoomNameTooLong, MAX_ROOM_NAME_CHARS),
+        RoomNameError::InvalidCharacter(c) => tr!(ErrRoomNameChar, c),
+        RoomNameError::Blocked => tr!(ErrRoomNameBlocked).to_string(),
+    }
+}
+
+/// A room code being typed or pasted, to join a room by its id.
+#[derive(Debug, Clone, Default)]
+pub struct CodePrompt {
+    pub input: String,
+    /// Whether the last code tried matched no room.
+    pub unknown: bool,
+}
+
+/// The list of saved room presets, open to pick one to create a room from.
+#[derive(Debug, Clone)]
+pub struct PresetPicker {
+    pub names: Vec<String>,
+    pub selected: usize,
+}
 
 #[derive(Debug, Clone)]
 pub struct LobbyScreen {
@@ -18,6 +105,28 @@ pub struct LobbyScreen {
     pub player_id: Option<Uuid>,
     pub status_message: Option<String>,
     pub joined_room: Option<RoomSnapshot>,
+    /// Seconds until a full room's game starts on its own.
+    pub auto_start_in: Option<u32>,
+    /// The room last asked to join, until the server answers.
+    pub joining: Option<Uuid>,
+    /// A room whose game started before we could join, offered to watch.
+    pub watch_offer: Option<Uuid>,
+    /// Pace for rooms we create.
+    pub new_room_pace: Pace,
+    /// Open while naming a room to create.
+    pub name_prompt: Option<Box<RoomNamePrompt>>,
+    /// Open while entering a room code.
+    pub code_prompt: Option<Box<CodePrompt>>,
+    /// Open while typing a room's password.
+    pub password_prompt: Option<Box<PasswordPrompt>>,
+    /// Why the host removed us from a room, shown until dismissed.
+    pub kicked: Option<String>,
+    /// Open while typing a chat line or command in the waiting room.
+    pub command: Option<String>,
+    pub preset_picker: Option<Box<PresetPicker>>,
+    /// Set from asking to leave a room until the server confirms; anything
+    /// the room sends in between is stale.
+    pub leaving: bool,
 }
 
 impl LobbyScreen {
@@ -29,6 +138,17 @@ impl LobbyScreen {
             player_id: None,
             status_message: None,
             joined_room: None,
+            auto_start_in: None,
+            joining: None,
+            watch_offer: None,
+            new_room_pace: Pace::Standard,
+            name_prompt: None,
+            code_prompt: None,
+            password_prompt: None,
+            kicked: None,
+            command: None,
+            preset_picker: None,
+            leaving: false,
         }
     }
 
@@ -36,6 +156,22 @@ impl LobbyScreen {
         self.joined_room.is_some()
     }
 
+    /// Whether we have said we are ready in the waiting room.
+    pub fn is_ready(&self) -> bool {
+        match (&self.joined_room, self.player_id) {
+            (Some(room), Some(pid)) => room.players.iter().any(|p| p.id == pid && p.ready),
+            _ => false,
+        }
+    }
+
+    /// The message moving our seat `delta` places, if there is a seat there.
+    pub fn move_seat(&self, delta: i8) -> Option<ClientMessage> {
+        let room = self.joined_room.as_ref()?;
+        let seat = room.players.iter().position(|p| Some(p.id) == self.player_id)?;
+        let to = seat.checked_add_signed(delta as isize).filter(|&to| to < room.players.len())?;
+        Some(ClientMessage::MoveSeat { seat: to as u8 })
+    }
+
     pub fn is_host(&self) -> bool {
         match (&self.joined_room, self.player_id) {
             (Some(room), Some(pid)) => room.host_id == pid,
@@ -43,7 +179,92 @@ impl LobbyScreen {
         }
     }
 
+    /// The message changing one of the room's settings, if hosting.
+    pub fn change_setting(&self, setting: RoomSetting) -> Option<ClientMessage> {
+        let room = self.joined_room.as_ref().filter(|_| self.is_host())?;
+        let mut max_players = room.max_players;
+        let mut rules = room.rules;
+        let mut locked = room.locked;
+        let mut auto_start = room.auto_start;
+        let mut max_spectators = room.max_spectators;
+        let mut announcements = room.announcements;
+        let mut shuffle_order = room.shuffle_order;
+        let mut nudge_secs = room.nudge_secs;
+        match setting {
+            RoomSetting::Pace => rules = next_pace(Pace::of(&rules)).apply(rules),
+            RoomSetting::MaxPlayers(delta) => {
+                max_players = max_players.saturating_add_signed(delta).clamp(2, 6);
+            }
+            RoomSetting::TurnTimer => {
+                let next = TURN_TIMER_STEPS
+                    .iter()
+                    .position(|&step| step == rules.turn_seconds)
+                    .map_or(0, |i| (i + 1) % TURN_TIMER_STEPS.len());
+                rules.turn_seconds = TURN_TIMER_STEPS[next];
+            }
+            RoomSetting::TimeBank => {
+                let next = TIME_BANK_STEPS
+                    .iter()
+                    .position(|&step| step == rules.time_bank_secs)
+                    .map_or(0, |i| (i + 1) % TIME_BANK_STEPS.len());
+                rules.time_bank_secs = TIME_BANK_STEPS[next];
+            }
+            RoomSetting::OutOfTime => {
+                rules.out_of_time = match rules.out_of_time {
+                    OutOfTime::AutoScore => OutOfTime::Forfeit,
+                    OutOfTime::Forfeit => OutOfTime::AutoScore,
+                };
+            }
+            RoomSetting::SuddenDeath => rules.sudden_death = !rules.sudden_death,
+            RoomSetting::Locked => locked = !locked,
+            RoomSetting::AutoStart => auto_start = !auto_start,
+            RoomSetting::ShuffleOrder => shuffle_order = !shuffle_order,
+            RoomSetting::Spectators => {
+                let next = SPECTATOR_STEPS
+                    .iter()
+                    .position(|&step| Some(step) == max_spectators)
+                    .map_or(0, |i| (i + 1) % SPECTATOR_STEPS.len());
+                max_spectators = Some(SPECTATOR_STEPS[next]);
+            }
+            RoomSetting::Nudge => {
+                let next = NUDGE_STEPS
+                    .iter()
+                    .position(|&step| step == nudge_secs)
+                    .map_or(0, |i| (i + 1) % NUDGE_STEPS.len());
+                nudge_secs = NUDGE_STEPS[next];
+            }
+            RoomSetting::Announcements => {
+                announcements = match announcements {
+                    Announcements::Off => Announcements::Highlights,
+                    Announcements::Highlights => Announcements::All,
+                    Announcements::All => Announcements::Off,
+                };
+            }
+        }
+        Some(ClientMessage::UpdateRoomSettings {
+            max_players: Some(max_players),
+            password: None,
+            rules: Some(rules),
+            locked: Some(locked),
+            auto_start: Some(auto_start),
+            max_spectators,
+            seed: None,
+            announcements: Some(announcements),
+            shuffle_order: Some(shuffle_order),
+            nudge_secs: Some(nudge_secs),
+        })
+    }
+
+    /// Step the pace of rooms we create.
+    pub fn cycle_new_room_pace(&mut self) {
+        self.new_room_pace = next_pace(Some(self.new_room_pace));
+    }
+
     pub fn select_next(&mut self) {
+        if let Some(picker) = &mut self.preset_picker {
+            picker.selected = (picker.selected + 1) % picker.names.len().max(1);
+            return;
+        }
         if self.rooms.is_empty() {
             return;
         }
@@ -55,6 +276,10 @@ impl LobbyScreen {
     }
 
     pub fn select_prev(&mut self) {
+        if let Some(picker) = &mut self.preset_picker {
+            picker.selected = picker.selected.checked_sub(1).unwrap_or(picker.names.len().saturating_sub(1));
+            return;
+        }
         if self.rooms.is_empty() {
             return;
         }
@@ -66,11 +291,71 @@ impl LobbyScreen {
         self.table_state.select(Some(i));
     }
 
+    pub fn selected_room(&self) -> Option<&RoomInfo> {
+        self.table_state.selected().and_then(|i| self.rooms.get(i))
+    }
+
     pub fn selected_room_id(&self) -> Option<uuid::Uuid> {
-        self.table_state
-            .selected()
-            .and_then(|i| self.rooms.get(i))
-            .map(|r| r.room_id)
+        self.selected_room().map(|r| r.room_id)
+    }
+
+    /// The message joining `room_id`, or `None` after opening the password
+    /// prompt if the room list says it has one.
+    pub fn join(&mut self, room_id: Uuid) -> Option<ClientMessage> {
+        if let Some(room) = self.rooms.iter().find(|r| r.room_id == room_id && r.has_password) {
+            self.password_prompt = Some(Box::new(PasswordPrompt {
+                room_id,
+                room_name: room.room_name.clone(),
+                input: String::new(),
+                retry: false,
+            }));
+            return None;
+        }
+        self.joining = Some(room_id);
+        Some(ClientMessage::JoinRoom {
+            room_id,
+            password: None,
+            spectate_if_started: false,
+        })
+    }
+
+    /// The room a code stands for: a full room id, or the start of the id
+    /// of exactly one listed room.
+    pub fn room_for_code(&self, code: &str) -> Option<Uuid> {
+        let code = code.trim().to_ascii_lowercase();
+        if let Ok(id) = Uuid::parse_str(&code) {
+            return Some(id);
+        }
+        if code.len() < MIN_CODE_PREFIX {
+            return None;
+        }
+        let mut matching = self
+            .rooms
+            .iter()
+            .filter(|r| r.room_id.to_string().starts_with(&code));
+        match (matching.next(), matching.next()) {
+            (Some(room), None) => Some(room.room_id),
+            _ => None,
+        }
+    }
+
+    /// Ask for the password of the room we last tried to join, again.
+    pub fn retry_password(&mut self) {
+        let Some(room_id) = self.joining.take() else {
+            return;
+        };
+        let room_name = self
+            .rooms
+            .iter()
+            .find(|r| r.room_id == room_id)
+            .map(|r| r.room_name.clone())
+            .unwrap_or_default();
+        self.password_prompt = Some(Box::new(PasswordPrompt {
+            room_id,
+            room_name,
+            input: String::new(),
+            retry: true,
+        }));
     }
 
     pub fn draw(&self, frame: &mut Frame) {
@@ -79,16 +364,27 @@ impl LobbyScreen {
         } else {
             self.draw_room_list(frame);
         }
+        if let Some(ref picker) = self.preset_picker {
+            draw_preset_popup(frame, picker);
+        }
+        if let Some(ref reason) = self.kicked {
+            draw_kicked_popup(frame, reason);
+        }
     }
 
     fn draw_waiting_room(&self, frame: &mut Frame, room: &RoomSnapshot) {
         let area = frame.area();
+        // Records over the games played here, once there are any
+        let standings_height = match room.standings.len() {
+            0 => 0,
+            n => n as u16 + 2,
+        };
 
         let vertical = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Percentage(20),
-                Constraint::Length(14),
+                Constraint::Length(15 + standings_height),
                 Constraint::Percentage(20),
             ])
             .split(area);
@@ -108,8 +404,9 @@ impl LobbyScreen {
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Length(2),  // Title
-                Constraint::Length(2),  // Room name
+                Constraint::Length(3),  // Room name and settings
                 Constraint::Min(4),    // Player list
+                Constraint::Length(standings_height), // Standings
                 Constraint::Length(2),  // Status
                 Constraint::Length(2),  // Help
             ])
@@ -124,26 +421,82 @@ impl LobbyScreen {
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(
-                "- Waiting Room",
+                tr!(WaitingRoom),
                 Style::default().fg(Color::Rgb(180, 180, 200)),
             ),
-        ]));
-        frame.render_widget(title, chunks[0]);
-
-        // Room name + player count
-        let room_info = Paragraph::new(Line::from(vec![
-            Span::raw("  "),
-            Span::styled(
-                &room.room_name,
-                Style::default()
-                    .fg(Color::Rgb(100, 200, 255))
-                    .add_modifier(Modifier::BOLD),
-            ),
             Span::styled(
-                format!("  ({}/{} players)", room.players.len(), room.max_players),
+                format!("  {}", tr!(RoomCode, room.room_id)),
                 Style::default().fg(Color::Rgb(120, 120, 140)),
             ),
         ]));
+        frame.render_widget(title, chunks[0]);
+
+        // Room name + player count, then the rules
+        let mut settings: Vec<String> = Pace::of(&room.rules).map(|p| pace_label(p).to_string()).into_iter().collect();
+        settings.extend([
+            tr!(SettingRolls, room.rules.rolls_per_turn),
+            match room.rules.turn_seconds {
+                Some(secs) => tr!(SettingTimer, secs),
+                None => tr!(SettingNoTimer).to_string(),
+            },
+        ]);
+        if let Some(secs) = room.rules.time_bank_secs {
+            let penalty = match room.rules.out_of_time {
+                OutOfTime::AutoScore => tr!(OutOfTimeAutoScore),
+                OutOfTime::Forfeit => tr!(OutOfTimeForfeit),
+            };
+            settings.push(tr!(SettingTimeBank, secs / 60, penalty));
+        }
+        if room.rules.sudden_death {
+            settings.push(tr!(SettingSuddenDeath).to_string());
+        }
+        if room.has_password {
+            settings.push(tr!(SettingPassword).to_string());
+        }
+        if room.locked {
+            settings.push(tr!(SettingLocked).to_string());
+        }
+        if room.auto_start {
+            settings.push(tr!(SettingAutoStart).to_string());
+        }
+        if room.shuffle_order {
+            settings.push(tr!(SettingShuffleOrder).to_string());
+        }
+        if room.nudge_secs > 0 {
+            settings.push(tr!(SettingNudge, room.nudge_secs));
+        }
+        match room.max_spectators {
+            Some(0) => settings.push(tr!(SettingNoSpectators).to_string()),
+            Some(max) => settings.push(tr!(SettingSpectators, max)),
+            None => {}
+        }
+        match room.announcements {
+            Announcements::Off => settings.push(tr!(SettingAnnounceOff).to_string()),
+            Announcements::All => settings.push(tr!(SettingAnnounceAll).to_string()),
+            Announcements::Highlights => {}
+        }
+        if let Some(seed) = room.seed {
+            settings.push(tr!(SettingSeed, seed));
+        }
+        let room_info = Paragraph::new(vec![
+            Line::from(vec![
+                Span::raw("  "),
+                Span::styled(
+                    &room.room_name,
+                    Style::default()
+                        .fg(Color::Rgb(100, 200, 255))
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!("  {}", tr!(PlayerCount, room.players.len(), room.max_players)),
+                    Style::default().fg(Color::Rgb(120, 120, 140)),
+                ),
+            ]),
+            Line::from(Span::styled(
+                format!("  {}", settings.join(" · ")),
+                Style::default().fg(Color::Rgb(150, 150, 170)),
+            )),
+        ]);
         frame.render_widget(room_info, chunks[1]);
 
         // Player list
@@ -168,10 +521,11 @@ impl LobbyScreen {
                 };
                 Line::from(vec![
                     Span::styled(marker, Style::default().fg(Color::Rgb(120, 120, 140))),
+                    Span::styled(format!("{}. ", idx + 1), Style::default().fg(Color::Rgb(120, 120, 140))),
                     Span::styled(&p.name, Style::default().fg(color)),
                     if p.id == room.host_id {
                         Span::styled(
-                            " (host)",
+                            format!(" {}", tr!(HostMarker)),
                             Style::default()
                                 .fg(Color::Rgb(255, 220, 50))
                                 .add_modifier(Modifier::BOLD),
@@ -179,13 +533,28 @@ impl LobbyScreen {
                     } else {
                         Span::raw("")
                     },
+                    if p.ready {
+                        Span::styled(
+                            format!("  ✓ {}", tr!(ReadyMarker)),
+                            Style::default().fg(Color::Rgb(100, 255, 150)),
+                        )
+                    } else {
+                        Span::raw("")
+                    },
+                    match p.head_to_head {
+                        Some(record) => Span::styled(
+                            format!("  vs. {}: {}–{}", p.name, record.wins, record.losses),
+                            Style::default().fg(Color::Rgb(150, 150, 170)),
+                        ),
+                        None => Span::raw(""),
+                    },
                 ])
             })
             .collect();
 
         if !room.spectators.is_empty() {
             player_lines.push(Line::from(Span::styled(
-                format!("   {} spectator(s)", room.spectators.len()),
+                format!("   {}", tr!(SpectatorCount, room.spectators.len())),
                 Style::default().fg(Color::Rgb(120, 120, 140)),
             )));
         }
@@ -194,16 +563,61 @@ impl LobbyScreen {
             Block::default()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Rgb(80, 80, 100)))
-                .title(" Players ")
+                .title(format!(" {} ", tr!(Players)))
                 .title_style(Style::default().fg(Color::Rgb(180, 180, 200))),
         );
         frame.render_widget(players_widget, chunks[2]);
 
-        // Status
-        if let Some(ref msg) = self.status_message {
+        if !room.standings.is_empty() {
+            let leader = &room.standings[0];
+            let lines: Vec<Line> = room
+                .standings
+                .iter()
+                .enumerate()
+                .map(|(idx, standing)| {
+                    let mut name_style = Style::default().fg(Color::Rgb(200, 200, 220));
+                    if (standing.wins, standing.total) == (leader.wins, leader.total) {
+                        name_style = name_style.fg(Color::Rgb(255, 220, 50)).add_modifier(Modifier::BOLD);
+                    }
+                    Line::from(vec![
+                        Span::styled(format!("   {}. ", idx + 1), Style::default().fg(Color::Rgb(120, 120, 140))),
+                        Span::styled(&standing.name, name_style),
+                        Span::styled(
+                            format!("  {}", tr!(StandingRecord, standing.wins, standing.games, standing.total)),
+                            Style::default().fg(Color::Rgb(150, 150, 170)),
+                        ),
+                    ])
+                })
+                .collect();
+            let standings = Paragraph::new(lines).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Rgb(80, 80, 100)))
+                    .title(format!(" {} ", tr!(Standings)))
+                    .title_style(Style::default().fg(Color::Rgb(180, 180, 200))),
+            );
+            frame.render_widget(standings, chunks[3]);
+        }
+
+        // Status, or the chat line being typed
+        if let Some(ref input) = self.command {
+            let input = super::input_tail(input, chunks[4].width.saturating_sub(4));
+            let prompt = Paragraph::new(format!("  > {}", input))
+                .style(Style::default().fg(Color::Rgb(200, 200, 220)));
+            frame.render_widget(prompt, chunks[4]);
+            let cursor = (chunks[4].x + 4 + input.chars().count() as u16, chunks[4].y);
+            frame.set_cursor_position(super::clamp_cursor(chunks[4], cursor));
+        } else if let Some(secs) = self.auto_start_in {
+            let status = Paragraph::new(format!("  {}", tr!(AutoStartingIn, secs))).style(
+                Style::default()
+                    .fg(Color::Rgb(255, 220, 50))
+                    .add_modifier(Modifier::BOLD),
+            );
+            frame.render_widget(status, chunks[4]);
+        } else if let Some(ref msg) = self.status_message {
             let status = Paragraph::new(format!("  {}", msg))
                 .style(Style::default().fg(Color::Rgb(100, 255, 150)));
-            frame.render_widget(status, chunks[3]);
+            frame.render_widget(status, chunks[4]);
         }
 
         // Help
@@ -211,21 +625,60 @@ impl LobbyScreen {
             let help = Paragraph::new(Line::from(vec![
                 Span::raw("  "),
                 Span::styled("[Enter]", Style::default().fg(Color::Rgb(100, 255, 150))),
-                Span::styled(" Start Game  ", Style::default().fg(Color::Rgb(120, 120, 140))),
+                Span::styled(format!(" {}  ", tr!(ActStartGame)), Style::default().fg(Color::Rgb(120, 120, 140))),
+                Span::styled("[p]", Style::default().fg(Color::Rgb(100, 200, 255))),
+                Span::styled(format!(" {}  ", tr!(ColPace)), Style::default().fg(Color::Rgb(120, 120, 140))),
+                Span::styled("[+/-]", Style::default().fg(Color::Rgb(100, 200, 255))),
+                Span::styled(format!(" {}  ", tr!(ActSeats)), Style::default().fg(Color::Rgb(120, 120, 140))),
+                Span::styled("[t]", Style::default().fg(Color::Rgb(100, 200, 255))),
+                Span::styled(format!(" {}  ", tr!(ActTimer)), Style::default().fg(Color::Rgb(120, 120, 140))),
+                Span::styled("[b]", Style::default().fg(Color::Rgb(100, 200, 255))),
+                Span::styled(format!(" {}  ", tr!(ActTimeBank)), Style::default().fg(Color::Rgb(120, 120, 140))),
+                Span::styled("[o]", Style::default().fg(Color::Rgb(100, 200, 255))),
+                Span::styled(format!(" {}  ", tr!(ActOutOfTime)), Style::default().fg(Color::Rgb(120, 120, 140))),
+                Span::styled("[d]", Style::default().fg(Color::Rgb(100, 200, 255))),
+                Span::styled(format!(" {}  ", tr!(ActSuddenDeath)), Style::default().fg(Color::Rgb(120, 120, 140))),
+                Span::styled("[a]", Style::default().fg(Color::Rgb(100, 200, 255))),
+                Span::styled(format!(" {}  ", tr!(ActAutoStart)), Style::default().fg(Color::Rgb(120, 120, 140))),
+                Span::styled("[s]", Style::default().fg(Color::Rgb(100, 200, 255))),
+                Span::styled(format!(" {}  ", tr!(ActShuffleOrder)), Style::default().fg(Color::Rgb(120, 120, 140))),
+                Span::styled("[i]", Style::default().fg(Color::Rgb(100, 200, 255))),
+                Span::styled(format!(" {}  ", tr!(ActNudge)), Style::default().fg(Color::Rgb(120, 120, 140))),
+                Span::styled("[v]", Style::default().fg(Color::Rgb(100, 200, 255))),
+                Span::styled(format!(" {}  ", tr!(ActSpectators)), Style::default().fg(Color::Rgb(120, 120, 140))),
+                Span::styled("[n]", Style::default().fg(Color::Rgb(100, 200, 255))),
+                Span::styled(format!(" {}  ", tr!(ActAnnouncements)), Style::default().fg(Color::Rgb(120, 120, 140))),
+                Span::styled("[l]", Style::default().fg(Color::Rgb(100, 200, 255))),
+                Span::styled(
+                    format!(" {}  ", if room.locked { tr!(ActUnlock) } else { tr!(ActLock) }),
+                    Style::default().fg(Color::Rgb(120, 120, 140)),
+                ),
+                Span::styled("[[/]]", Style::default().fg(Color::Rgb(100, 200, 255))),
+                Span::styled(format!(" {}  ", tr!(ActMoveSeat)), Style::default().fg(Color::Rgb(120, 120, 140))),
+                Span::styled("[y]", Style::default().fg(Color::Rgb(100, 200, 255))),
+                Span::styled(format!(" {}  ", tr!(ActCopyCode)), Style::default().fg(Color::Rgb(120, 120, 140))),
+                Span::styled("[/]", Style::default().fg(Color::Rgb(100, 200, 255))),
+                Span::styled(format!(" {}  ", tr!(ActCommand)), Style::default().fg(Color::Rgb(120, 120, 140))),
                 Span::styled("[Esc]", Style::default().fg(Color::Rgb(255, 150, 100))),
-                Span::styled(" Leave Room", Style::default().fg(Color::Rgb(120, 120, 140))),
+                Span::styled(format!(" {}", tr!(ActLeaveRoom)), Style::default().fg(Color::Rgb(120, 120, 140))),
             ]));
-            frame.render_widget(help, chunks[4]);
+            frame.render_widget(help, chunks[5]);
         } else {
             let help = Paragraph::new(Line::from(vec![
                 Span::styled(
-                    "  Waiting for host to start...  ",
+                    format!("  {}  ", tr!(WaitingForHost)),
                     Style::default().fg(Color::Rgb(150, 150, 170)),
                 ),
+                Span::styled("[[/]]", Style::default().fg(Color::Rgb(100, 200, 255))),
+                Span::styled(format!(" {}  ", tr!(ActMoveSeat)), Style::default().fg(Color::Rgb(120, 120, 140))),
+                Span::styled("[y]", Style::default().fg(Color::Rgb(100, 200, 255))),
+                Span::styled(format!(" {}  ", tr!(ActCopyCode)), Style::default().fg(Color::Rgb(120, 120, 140))),
+                Span::styled("[/]", Style::default().fg(Color::Rgb(100, 200, 255))),
+                Span::styled(format!(" {}  ", tr!(ActCommand)), Style::default().fg(Color::Rgb(120, 120, 140))),
                 Span::styled("[Esc]", Style::default().fg(Color::Rgb(255, 150, 100))),
-                Span::styled(" Leave Room", Style::default().fg(Color::Rgb(120, 120, 140))),
+                Span::styled(format!(" {}", tr!(ActLeaveRoom)), Style::default().fg(Color::Rgb(120, 120, 140))),
             ]));
-            frame.render_widget(help, chunks[4]);
+            frame.render_widget(help, chunks[5]);
         }
     }
 
@@ -249,7 +702,7 @@ impl LobbyScreen {
                     .fg(Color::Rgb(255, 220, 50))
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled("Lobby - Welcome, ", Style::default().fg(Color::Rgb(180, 180, 200))),
+            Span::styled(tr!(LobbyWelcome), Style::default().fg(Color::Rgb(180, 180, 200))),
             Span::styled(
                 &self.player_name,
                 Style::default()
@@ -268,24 +721,25 @@ impl LobbyScreen {
         // Room list
         if self.rooms.is_empty() {
             let empty = Paragraph::new(Line::from(vec![
-                Span::styled("  No rooms available. Press ", Style::default().fg(Color::Rgb(120, 120, 140))),
+                Span::styled(format!("  {}", tr!(NoRoomsBefore)), Style::default().fg(Color::Rgb(120, 120, 140))),
                 Span::styled("[C]", Style::default().fg(Color::Rgb(100, 200, 255))),
-                Span::styled(" to create one.", Style::default().fg(Color::Rgb(120, 120, 140))),
+                Span::styled(tr!(NoRoomsAfter), Style::default().fg(Color::Rgb(120, 120, 140))),
             ]))
             .block(
                 Block::default()
                     .borders(Borders::ALL)
                     .border_style(Style::default().fg(Color::Rgb(80, 80, 100)))
-                    .title(" Rooms ")
+                    .title(format!(" {} ", tr!(Rooms)))
                     .title_style(Style::default().fg(Color::Rgb(180, 180, 200))),
             );
             frame.render_widget(empty, chunks[1]);
         } else {
             let header = Row::new(vec![
-                Cell::from("Room Name").style(Style::default().fg(Color::Rgb(180, 180, 200))),
-                Cell::from("Players").style(Style::default().fg(Color::Rgb(180, 180, 200))),
-                Cell::from("Spectators").style(Style::default().fg(Color::Rgb(180, 180, 200))),
-                Cell::from("Status").style(Style::default().fg(Color::Rgb(180, 180, 200))),
+                Cell::from(tr!(ColRoomName)).style(Style::default().fg(Color::Rgb(180, 180, 200))),
+                Cell::from(tr!(ColPlayers)).style(Style::default().fg(Color::Rgb(180, 180, 200))),
+                Cell::from(tr!(ColSpectators)).style(Style::default().fg(Color::Rgb(180, 180, 200))),
+                Cell::from(tr!(ColPace)).style(Style::default().fg(Color::Rgb(180, 180, 200))),
+                Cell::from(tr!(ColStatus)).style(Style::default().fg(Color::Rgb(180, 180, 200))),
             ])
             .style(Style::default().add_modifier(Modifier::BOLD));
 
@@ -293,33 +747,55 @@ impl LobbyScreen {
                 .rooms
                 .iter()
                 .map(|room| {
-                    let status = match room.state {
-                        RoomInfoState::Waiting => "Waiting",
-                        RoomInfoState::InProgress => "In Game",
-                        RoomInfoState::Finished => "Finished",
-                    };
+                    let mut status = match room.state {
+                        RoomInfoState::Waiting => tr!(RoomWaiting),
+                        RoomInfoState::InProgress => tr!(RoomInGame),
+                        RoomInfoState::Finished => tr!(RoomFinished),
+                    }
+                    .to_string();
+                    // While the game is on, watching is the only way in
+                    if room.state == RoomInfoState::InProgress {
+                        let watch = if room.can_spectate() {
+                            tr!(RoomWatchable)
+                        } else {
+                            tr!(RoomNotWatchable)
+                        };
+                        status = format!("{} {}", status, watch);
+                    }
                     let status_color = match room.state {
                         RoomInfoState::Waiting => Color::Rgb(100, 255, 150),
                         RoomInfoState::InProgress => Color::Rgb(100, 200, 255),
                         RoomInfoState::Finished => Color::Rgb(100, 100, 120),
                     };
-                    let lock_icon = if room.has_password { "[locked] " } else { "" };
+                    let lock_icon = if room.locked {
+                        format!("{} ", tr!(RoomLocked))
+                    } else if room.has_password {
+                        format!("{} ", tr!(RoomPassword))
+                    } else {
+                        String::new()
+                    };
                     Row::new(vec![
                         Cell::from(format!("{}{}", lock_icon, room.room_name))
                             .style(Style::default().fg(Color::Rgb(200, 200, 220))),
                         Cell::from(format!("{}/{}", room.player_count, room.max_players))
                             .style(Style::default().fg(Color::Rgb(150, 150, 170))),
-                        Cell::from(format!("{}", room.spectator_count))
+                        Cell::from(match room.max_spectators {
+                            Some(max) => format!("{}/{}", room.spectator_count, max),
+                            None => format!("{}", room.spectator_count),
+                        })
                             .style(Style::default().fg(Color::Rgb(150, 150, 170))),
+                        Cell::from(room.pace.map_or("–", pace_label))
+                            .style(Style::default().fg(pace_color(room.pace))),
                         Cell::from(status).style(Style::default().fg(status_color)),
                     ])
                 })
                 .collect();
 
             let widths = [
-                Constraint::Percentage(40),
-                Constraint::Percentage(20),
-                Constraint::Percentage(20),
+                Constraint::Percentage(35),
+                Constraint::Percentage(15),
+                Constraint::Percentage(15),
+                Constraint::Percentage(15),
                 Constraint::Percentage(20),
             ];
 
@@ -329,7 +805,7 @@ impl LobbyScreen {
                     Block::default()
                         .borders(Borders::ALL)
                         .border_style(Style::default().fg(Color::Rgb(80, 80, 100)))
-                        .title(" Rooms ")
+                        .title(format!(" {} ", tr!(Rooms)))
                         .title_style(Style::default().fg(Color::Rgb(180, 180, 200))),
                 )
                 .row_highlight_style(
@@ -345,24 +821,117 @@ impl LobbyScreen {
 
         // Help bar
         let mut help_spans = vec![Span::raw("  ")];
-        if let Some(ref msg) = self.status_message {
-            help_spans.push(Span::styled(
-                format!("{} | ", msg),
-                Style::default().fg(Color::Rgb(100, 255, 150)),
-            ));
-        }
-        help_spans.extend_from_slice(&[
-            Span::styled("[C]", Style::default().fg(Color::Rgb(100, 200, 255))),
-            Span::styled(" Create  ", Style::default().fg(Color::Rgb(120, 120, 140))),
-            Span::styled("[Enter]", Style::default().fg(Color::Rgb(100, 255, 150))),
-            Span::styled(" Join  ", Style::default().fg(Color::Rgb(120, 120, 140))),
-            Span::styled("[S]", Style::default().fg(Color::Rgb(200, 150, 255))),
-            Span::styled(" Spectate  ", Style::default().fg(Color::Rgb(120, 120, 140))),
-            Span::styled("[R]", Style::default().fg(Color::Rgb(255, 200, 100))),
-            Span::styled(" Refresh  ", Style::default().fg(Color::Rgb(120, 120, 140))),
-            Span::styled("[Q]", Style::default().fg(Color::Rgb(255, 150, 100))),
-            Span::styled(" Quit", Style::default().fg(Color::Rgb(120, 120, 140))),
-        ]);
+        if let Some(ref prompt) = self.name_prompt {
+            if let Some(problem) = prompt.problem() {
+                help_spans.push(Span::styled(
+                    format!("{} | ", problem),
+                    Style::default().fg(Color::Rgb(255, 100, 100)),
+                ));
+            }
+            help_spans.extend_from_slice(&[
+                Span::styled(
+                    format!("{}_  ", tr!(RoomNameInput, prompt.input)),
+                    Style::default()
+                        .fg(Color::Rgb(255, 220, 50))
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled("[Enter]", Style::default().fg(Color::Rgb(100, 255, 150))),
+                Span::styled(format!(" {}  ", tr!(ActCreate)), Style::default().fg(Color::Rgb(120, 120, 140))),
+                Span::styled("[Esc]", Style::default().fg(Color::Rgb(255, 150, 100))),
+                Span::styled(format!(" {}", tr!(ActCancel)), Style::default().fg(Color::Rgb(120, 120, 140))),
+            ]);
+        } else if let Some(ref prompt) = self.code_prompt {
+            if prompt.unknown {
+                help_spans.push(Span::styled(
+                    format!("{} | ", tr!(ErrUnknownCode)),
+                    Style::default().fg(Color::Rgb(255, 100, 100)),
+                ));
+            }
+            help_spans.extend_from_slice(&[
+                Span::styled(
+                    format!("{}_  ", tr!(RoomCodeInput, prompt.input)),
+                    Style::default()
+                        .fg(Color::Rgb(255, 220, 50))
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled("[Enter]", Style::default().fg(Color::Rgb(100, 255, 150))),
+                Span::styled(format!(" {}  ", tr!(ActJoin)), Style::default().fg(Color::Rgb(120, 120, 140))),
+            ]);
+            if cfg!(feature = "clipboard") {
+                help_spans.extend_from_slice(&[
+                    Span::styled("[Ctrl+V]", Style::default().fg(Color::Rgb(100, 200, 255))),
+                    Span::styled(format!(" {}  ", tr!(ActPaste)), Style::default().fg(Color::Rgb(120, 120, 140))),
+                ]);
+            }
+            help_spans.extend_from_slice(&[
+                Span::styled("[Esc]", Style::default().fg(Color::Rgb(255, 150, 100))),
+                Span::styled(format!(" {}", tr!(ActCancel)), Style::default().fg(Color::Rgb(120, 120, 140))),
+            ]);
+        } else if let Some(ref prompt) = self.password_prompt {
+            if prompt.retry {
+                help_spans.push(Span::styled(
+                    format!("{} | ", tr!(ErrWrongPassword)),
+                    Style::default().fg(Color::Rgb(255, 100, 100)),
+                ));
+            }
+            help_spans.extend_from_slice(&[
+                Span::styled(
+                    format!("{}  ", tr!(PasswordFor, prompt.room_name, "*".repeat(prompt.input.chars().count()))),
+                    Style::default()
+                        .fg(Color::Rgb(255, 220, 50))
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled("[Enter]", Style::default().fg(Color::Rgb(100, 255, 150))),
+                Span::styled(format!(" {}  ", tr!(ActJoin)), Style::default().fg(Color::Rgb(120, 120, 140))),
+                Span::styled("[Esc]", Style::default().fg(Color::Rgb(255, 150, 100))),
+                Span::styled(format!(" {}", tr!(ActCancel)), Style::default().fg(Color::Rgb(120, 120, 140))),
+            ]);
+        } else if self.watch_offer.is_some() {
+            help_spans.extend_from_slice(&[
+                Span::styled(
+                    format!("{}  ", tr!(OfferWatch)),
+                    Style::default()
+                        .fg(Color::Rgb(255, 220, 50))
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled("[Y]", Style::default().fg(Color::Rgb(100, 255, 150))),
+                Span::styled(format!(" {}  ", tr!(ActSpectate)), Style::default().fg(Color::Rgb(120, 120, 140))),
+                Span::styled("[N]", Style::default().fg(Color::Rgb(255, 150, 100))),
+                Span::styled(format!(" {}", tr!(ActCancel)), Style::default().fg(Color::Rgb(120, 120, 140))),
+            ]);
+        } else {
+            if let Some(ref msg) = self.status_message {
+                help_spans.push(Span::styled(
+                    format!("{} | ", msg),
+                    Style::default().fg(Color::Rgb(100, 255, 150)),
+                ));
+            }
+            help_spans.extend_from_slice(&[
+                Span::styled("[C]", Style::default().fg(Color::Rgb(100, 200, 255))),
+                Span::styled(format!(" {}  ", tr!(ActCreate)), Style::default().fg(Color::Rgb(120, 120, 140))),
+                Span::styled("[F]", Style::default().fg(Color::Rgb(100, 200, 255))),
+                Span::styled(format!(" {}  ", tr!(ActFromPreset)), Style::default().fg(Color::Rgb(120, 120, 140))),
+                Span::styled("[P]", Style::default().fg(Color::Rgb(100, 200, 255))),
+                Span::styled(
+                    format!(" {}  ", tr!(ActPace, pace_label(self.new_room_pace))),
+                    Style::default().fg(Color::Rgb(120, 120, 140)),
+                ),
+                Span::styled("[Enter]", Style::default().fg(Color::Rgb(100, 255, 150))),
+                Span::styled(format!(" {}  ", tr!(ActJoin)), Style::default().fg(Color::Rgb(120, 120, 140))),
+                Span::styled("[I]", Style::default().fg(Color::Rgb(100, 255, 150))),
+                Span::styled(format!(" {}  ", tr!(ActJoinByCode)), Style::default().fg(Color::Rgb(120, 120, 140))),
+                Span::styled("[S]", Style::default().fg(Color::Rgb(200, 150, 255))),
+                Span::styled(format!(" {}  ", tr!(ActSpectate)), Style::default().fg(Color::Rgb(120, 120, 140))),
+                Span::styled("[W]", Style::default().fg(Color::Rgb(200, 150, 255))),
+                Span::styled(format!(" {}  ", tr!(ActWatchFromStart)), Style::default().fg(Color::Rgb(120, 120, 140))),
+                Span::styled("[R]", Style::default().fg(Color::Rgb(255, 200, 100))),
+                Span::styled(format!(" {}  ", tr!(ActRefresh)), Style::default().fg(Color::Rgb(120, 120, 140))),
+                Span::styled("[T]", Style::default().fg(Color::Rgb(100, 200, 255))),
+                Span::styled(format!(" {}  ", tr!(ActStats)), Style::default().fg(Color::Rgb(120, 120, 140))),
+                Span::styled("[Q]", Style::default().fg(Color::Rgb(255, 150, 100))),
+                Span::styled(format!(" {}", tr!(ActQuit)), Style::default().fg(Color::Rgb(120, 120, 140))),
+            ]);
+        }
         let help = Paragraph::new(Line::from(help_spans)).block(
             Block::default()
                 .borders(Borders::TOP)
@@ -371,3 +940,98 @@ impl LobbyScreen {
         frame.render_widget(help, chunks[2]);
     }
 }
+
+fn draw_preset_popup(frame: &mut Frame, picker: &PresetPicker) {
+    let popup_area = centered_rect(50, 50, frame.area());
+    frame.render_widget(Clear, popup_area);
+    let mut lines = vec![Line::from("")];
+    lines.extend(picker.names.iter().enumerate().map(|(i, name)| {
+        if i == picker.selected {
+            Line::from(Span::styled(
+                format!(" > {}", name),
+                Style::default()
+                    .fg(Color::Rgb(255, 220, 50))
+                    .add_modifier(Modifier::BOLD),
+            ))
+        } else {
+            Line::from(Span::styled(
+                format!("   {}", name),
+                Style::default().fg(Color::Rgb(200, 200, 220)),
+            ))
+        }
+    }));
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::raw("  "),
+        Span::styled("[Enter]", Style::default().fg(Color::Rgb(100, 255, 150))),
+        Span::styled(format!(" {}  ", tr!(ActCreate)), Style::default().fg(Color::Rgb(120, 120, 140))),
+        Span::styled("[Esc]", Style::default().fg(Color::Rgb(255, 150, 100))),
+        Span::styled(format!(" {}", tr!(ActCancel)), Style::default().fg(Color::Rgb(120, 120, 140))),
+    ]));
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Rgb(100, 180, 255)))
+            .title(format!(" {} ", tr!(PresetsTitle)))
+            .title_style(
+                Style::default()
+                    .fg(Color::Rgb(100, 180, 255))
+                    .add_modifier(Modifier::BOLD),
+            ),
+    );
+    frame.render_widget(paragraph, popup_area);
+}
+
+fn draw_kicked_popup(frame: &mut Frame, reason: &str) {
+    let popup_area = centered_rect(50, 25, frame.area());
+    frame.render_widget(Clear, popup_area);
+    let lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("  {}", reason),
+            Style::default().fg(Color::Rgb(200, 200, 220)),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("  {}", tr!(KickedDismiss)),
+            Style::default().fg(Color::Rgb(100, 100, 120)),
+        )),
+    ];
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Rgb(255, 100, 100)))
+            .title(format!(" {} ", tr!(KickedTitle)))
+            .title_style(
+                Style::default()
+                    .fg(Color::Rgb(255, 100, 100))
+                    .add_modifier(Modifier::BOLD),
+            ),
+    );
+    frame.render_widget(paragraph, popup_area);
+}
+
+/// The preset after `pace`, wrapping around; custom rules go to the first.
+fn next_pace(pace: Option<Pace>) -> Pace {
+    let next = pace
+        .and_then(|p| Pace::ALL.iter().position(|&q| q == p))
+        .map_or(0, |i| (i + 1) % Pace::ALL.len());
+    Pace::ALL[next]
+}
+
+pub fn pace_label(pace: Pace) -> &'static str {
+    match pace {
+        Pace::Casual => tr!(PaceCasual),
+        Pace::Standard => tr!(PaceStandard),
+        Pace::Blitz => tr!(PaceBlitz),
+    }
+}
+
+fn pace_color(pace: Option<Pace>) -> Color {
+    match pace {
+        Some(Pace::Casual) => Color::Rgb(150, 255, 150),
+        Some(Pace::Standard) => Color::Rgb(100, 200, 255),
+        Some(Pace::Blitz) => Color::Rgb(255, 150, 100),
+        None => Color::Rgb(120, 120, 140),
+    }
+}
@@ -1,7 +1,105 @@
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use ratatui::layout::Rect;
+use serde::Deserialize;
+
+pub mod callout;
+pub mod campaign;
+pub mod celebration;
 pub mod connect;
+pub mod debug_overlay;
+pub mod dice_stats;
 pub mod dice_widget;
 pub mod game;
 pub mod help_popup;
+pub mod history;
+pub mod leaderboard;
 pub mod lobby;
 pub mod results;
 pub mod scoreboard_widget;
+pub mod stats;
+pub mod toast;
+
+/// The `motion` section of `config.json`.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default)]
+pub struct MotionConfig {
+    /// Show rolls at once and keep highlights steady instead of blinking
+    /// or pulsing.
+    pub reduce_motion: bool,
+    /// How long the dice tumble on each roll, in milliseconds. By default,
+    /// as long as the game's pace suggests.
+    pub roll_ms: Option<u64>,
+    /// How long a newly scored box stays highlighted, in milliseconds.
+    pub score_flash_ms: u64,
+}
+
+impl Default for MotionConfig {
+    fn default() -> Self {
+        Self {
+            reduce_motion: false,
+            roll_ms: None,
+            score_flash_ms: 1500,
+        }
+    }
+}
+
+struct Motion {
+    animations: bool,
+    config: MotionConfig,
+}
+
+static MOTION: OnceLock<Motion> = OnceLock::new();
+
+/// Turn animations on or off and set how they move. Only the first call
+/// has any effect.
+pub fn set_motion(animations: bool, config: MotionConfig) {
+    let _ = MOTION.set(Motion { animations, config });
+}
+
+fn motion() -> &'static Motion {
+    MOTION.get_or_init(|| Motion {
+        animations: true,
+        config: MotionConfig::default(),
+    })
+}
+
+/// Whether to animate; on unless turned off.
+pub fn animations() -> bool {
+    motion().animations
+}
+
+/// Whether to keep still: dice land at once and nothing blinks or pulses.
+/// Always so with animations off.
+pub fn reduce_motion() -> bool {
+    let motion = motion();
+    !motion.animations || motion.config.reduce_motion
+}
+
+/// How long the dice tumble, if set in `config.json`.
+pub fn roll_duration() -> Option<Duration> {
+    motion().config.roll_ms.map(Duration::from_millis)
+}
+
+/// How long a newly scored box stays highlighted.
+pub fn score_flash_duration() -> Duration {
+    Duration::from_millis(motion().config.score_flash_ms)
+}
+
+/// The end of `text` that fits in `width` cells with room for the cursor
+/// after it, so what is being typed stays in view in a narrow terminal.
+pub fn input_tail(text: &str, width: u16) -> &str {
+    let room = usize::from(width.saturating_sub(1));
+    let skip = text.chars().count().saturating_sub(room);
+    text.char_indices().nth(skip).map_or("", |(i, _)| &text[i..])
+}
+
+/// `(x, y)` moved inside `area`, so the cursor stays on the widget it
+/// belongs to after the terminal shrinks.
+pub fn clamp_cursor(area: Rect, (x, y): (u16, u16)) -> (u16, u16) {
+    (
+        x.clamp(area.x, area.right().saturating_sub(1).max(area.x)),
+        y.clamp(area.y, area.bottom().saturating_sub(1).max(area.y)),
+    )
+}
@@ -1,26 +1,180 @@
+use std::time::{Duration, Instant};
+
 use ratatui::{
-    layout::{Constraint, Direction, Layout},
+    layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
+    symbols::Marker,
     text::{Line, Span},
-    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    widgets::{
+        Axis, Block, Borders, Cell, Chart, Dataset, GraphType, LegendPosition, Paragraph, Row, Table,
+    },
     Frame,
 };
 use uuid::Uuid;
 
+use yaht_common::player::Scorecard;
+use yaht_common::protocol::RoomSnapshot;
+use yaht_common::records::Milestone;
+
+use crate::records::SoloRecords;
+
+use super::celebration::Celebration;
+use super::toast::Toasts;
+
 #[derive(Debug, Clone)]
 pub struct ResultsScreen {
     pub final_scores: Vec<(Uuid, String, u16)>,
-    pub winner_id: Uuid,
+    /// More than one on a draw.
+    pub winner_ids: Vec<Uuid>,
+    /// Personal records, shown after solitaire games.
+    pub records: Option<SoloRecords>,
+    pub new_best: bool,
+    /// Final scorecards, when the server sent them.
+    pub scorecards: Vec<(Uuid, Scorecard)>,
+    /// Game length in seconds, when the server sent it.
+    pub duration_secs: Option<u64>,
+    /// Each player's total after every round, for the score chart.
+    pub round_totals: Vec<(Uuid, Vec<u16>)>,
+    /// Each player's average turn in milliseconds, when the server timed them.
+    pub average_turn_ms: Vec<(Uuid, u64)>,
+    /// Running until the winner celebration ends; never with animations off.
+    celebration: Option<Celebration>,
+    /// Personal records the local player just broke.
+    toasts: Toasts,
+    /// The room we played in, once the server has it waiting for another
+    /// game with us still seated.
+    pub room: Option<Box<RoomSnapshot>>,
+    /// When to go back to the room's waiting room on our own, with
+    /// automatic rematches on.
+    rematch_at: Option<Instant>,
+    /// Set once a key was pressed, which keeps us here.
+    rematch_declined: bool,
 }
 
 impl ResultsScreen {
-    pub fn new(final_scores: Vec<(Uuid, String, u16)>, winner_id: Uuid) -> Self {
+    pub fn new(final_scores: Vec<(Uuid, String, u16)>, winner_ids: Vec<Uuid>) -> Self {
         let mut scores = final_scores;
-        scores.sort_by(|a, b| b.2.cmp(&a.2)); // sort descending by score
+        scores.sort_by_key(|s| std::cmp::Reverse(s.2)); // sort descending by score
+        let celebration = (!super::reduce_motion()).then(|| Celebration::new(scores.len()));
         Self {
             final_scores: scores,
-            winner_id,
+            winner_ids,
+            records: None,
+            new_best: false,
+            scorecards: Vec::new(),
+            duration_secs: None,
+            round_totals: Vec::new(),
+            average_turn_ms: Vec::new(),
+            celebration,
+            toasts: Toasts::default(),
+            room: None,
+            rematch_at: None,
+            rematch_declined: false,
+        }
+    }
+
+    /// Advance the celebration and expire toasts. Returns true if the
+    /// screen needs redrawing.
+    pub fn tick(&mut self) -> bool {
+        let toasts = self.toasts.tick();
+        toasts
+            || self.rematch_at.is_some()
+            || match &self.celebration {
+                Some(c) if c.is_done() => {
+                    self.celebration = None;
+                    true
+                }
+                Some(_) => true,
+                None => false,
+            }
+    }
+
+    /// The final scoreboard as a text table, when the server sent the
+    /// scorecards.
+    pub fn scoreboard(&self) -> Option<String> {
+        let players: Vec<(&str, &Scorecard)> = self
+            .final_scores
+            .iter()
+            .map(|(id, name, _)| {
+                let (_, card) = self.scorecards.iter().find(|(card_id, _)| card_id == id)?;
+                Some((name.as_str(), card))
+            })
+            .collect::<Option<_>>()?;
+        (!players.is_empty()).then(|| yaht_common::scoreboard::render(&players))
+    }
+
+    /// Count down to the rematch, once the room is open for one and unless
+    /// a key was pressed.
+    pub fn offer_rematch(&mut self, delay: Duration) {
+        if self.room.is_some() && self.rematch_at.is_none() && !self.rematch_declined {
+            self.rematch_at = Some(Instant::now() + delay);
+        }
+    }
+
+    /// Stop the countdown for good.
+    pub fn decline_rematch(&mut self) {
+        self.rematch_at = None;
+        self.rematch_declined = true;
+    }
+
+    /// Whether the countdown ran out.
+    pub fn rematch_due(&self) -> bool {
+        self.rematch_at.is_some_and(|at| Instant::now() >= at)
+    }
+
+    /// Pop up a short notice.
+    pub fn notify(&mut self, text: String) {
+        self.toasts.push(text);
+    }
+
+    pub fn is_animating(&self) -> bool {
+        self.celebration.is_some() || !self.toasts.is_empty()
+    }
+
+    /// Toast each personal record the game broke.
+    pub fn with_milestones(mut self, milestones: &[Milestone]) -> Self {
+        for milestone in milestones {
+            let text = milestone.to_string();
+            let mut chars = text.chars();
+            let text = chars
+                .next()
+                .map(|c| c.to_uppercase().chain(chars).collect())
+                .unwrap_or_default();
+            self.toasts.push(text);
         }
+        self
+    }
+
+    /// Show each player's section totals and how long the game took.
+    pub fn with_details(mut self, scorecards: Vec<(Uuid, Scorecard)>, duration_secs: u64) -> Self {
+        self.scorecards = scorecards;
+        self.duration_secs = Some(duration_secs);
+        self
+    }
+
+    /// Chart how each player's total grew round by round.
+    pub fn with_round_totals(mut self, round_totals: Vec<(Uuid, Vec<u16>)>) -> Self {
+        self.round_totals = round_totals;
+        self
+    }
+
+    /// Show how long each player took over a turn on average.
+    pub fn with_turn_times(mut self, average_turn_ms: Vec<(Uuid, u64)>) -> Self {
+        self.average_turn_ms = average_turn_ms;
+        self
+    }
+
+    fn scorecard(&self, player_id: Uuid) -> Option<&Scorecard> {
+        self.scorecards
+            .iter()
+            .find(|(id, _)| *id == player_id)
+            .map(|(_, card)| card)
+    }
+
+    pub fn with_records(mut self, records: SoloRecords, new_best: bool) -> Self {
+        self.records = Some(records);
+        self.new_best = new_best;
+        self
     }
 
     pub fn draw(&self, frame: &mut Frame) {
@@ -32,7 +186,9 @@ impl ResultsScreen {
                 Constraint::Percentage(15),
                 Constraint::Length(3),  // Title
                 Constraint::Length(3),  // Winner
+                Constraint::Length(if self.records.is_some() { 3 } else { 0 }), // Records
                 Constraint::Min(5),    // Score table
+                Constraint::Length(if self.round_totals.is_empty() { 0 } else { 12 }), // Score chart
                 Constraint::Length(2), // Help
                 Constraint::Percentage(15),
             ])
@@ -45,7 +201,7 @@ impl ResultsScreen {
                 Constraint::Percentage(60),
                 Constraint::Percentage(20),
             ])
-            .split(vertical[3]);
+            .split(vertical[4]);
 
         // Title
         let title = Paragraph::new(Line::from(vec![Span::styled(
@@ -58,32 +214,74 @@ impl ResultsScreen {
         frame.render_widget(title, vertical[1]);
 
         // Winner announcement
-        let winner_name = self
+        let winner_names: Vec<&str> = self
             .final_scores
             .iter()
-            .find(|(id, _, _)| *id == self.winner_id)
+            .filter(|(id, _, _)| self.winner_ids.contains(id))
             .map(|(_, name, _)| name.as_str())
-            .unwrap_or("Unknown");
+            .collect();
+        let (label, names) = match winner_names.len() {
+            0 => ("  Winner: ", "Unknown".to_string()),
+            1 => ("  Winner: ", winner_names[0].to_string()),
+            _ => ("  Draw: ", winner_names.join(" & ")),
+        };
 
-        let winner = Paragraph::new(Line::from(vec![
-            Span::styled("  Winner: ", Style::default().fg(Color::Rgb(180, 180, 200))),
+        let mut winner_lines = vec![Line::from(vec![
+            Span::styled(label, Style::default().fg(Color::Rgb(180, 180, 200))),
             Span::styled(
-                winner_name,
-                Style::default()
-                    .fg(Color::Rgb(100, 255, 150))
-                    .add_modifier(Modifier::BOLD),
+                names,
+                self.celebration.as_ref().map_or(
+                    Style::default()
+                        .fg(Color::Rgb(100, 255, 150))
+                        .add_modifier(Modifier::BOLD),
+                    Celebration::winner_style,
+                ),
             ),
             Span::styled(" !", Style::default().fg(Color::Rgb(255, 220, 50))),
-        ]))
-        .alignment(ratatui::layout::Alignment::Center);
+        ])];
+        if let Some(secs) = self.duration_secs {
+            winner_lines.push(Line::from(Span::styled(
+                format!("Played in {}:{:02}", secs / 60, secs % 60),
+                Style::default().fg(Color::Rgb(120, 120, 140)),
+            )));
+        }
+        let winner = Paragraph::new(winner_lines).alignment(ratatui::layout::Alignment::Center);
         frame.render_widget(winner, vertical[2]);
 
-        // Score table
-        let header = Row::new(vec![
-            Cell::from("Rank").style(Style::default().fg(Color::Rgb(180, 180, 200))),
-            Cell::from("Player").style(Style::default().fg(Color::Rgb(180, 180, 200))),
-            Cell::from("Score").style(Style::default().fg(Color::Rgb(180, 180, 200))),
-        ])
+        // Personal records
+        if let Some(ref records) = self.records {
+            let mut lines = vec![Line::from(Span::styled(
+                records.summary(),
+                Style::default().fg(Color::Rgb(150, 150, 170)),
+            ))];
+            if self.new_best {
+                lines.push(Line::from(Span::styled(
+                    "New personal best!",
+                    Style::default()
+                        .fg(Color::Rgb(255, 220, 50))
+                        .add_modifier(Modifier::BOLD),
+                )));
+            }
+            let records = Paragraph::new(lines).alignment(ratatui::layout::Alignment::Center);
+            frame.render_widget(records, vertical[3]);
+        }
+
+        // Score table, with section totals when the scorecards are known
+        let detailed = !self.scorecards.is_empty();
+        let mut headings = vec!["Rank", "Player"];
+        if detailed {
+            headings.extend(["Upper", "Lower", "Bonus"]);
+        }
+        let timed = !self.average_turn_ms.is_empty();
+        if timed {
+            headings.push("Avg turn");
+        }
+        headings.push("Score");
+        let header = Row::new(
+            headings
+                .into_iter()
+                .map(|h| Cell::from(h).style(Style::default().fg(Color::Rgb(180, 180, 200)))),
+        )
         .style(Style::default().add_modifier(Modifier::BOLD))
         .bottom_margin(1);
 
@@ -97,7 +295,7 @@ impl ResultsScreen {
             .final_scores
             .iter()
             .enumerate()
-            .map(|(i, (_id, name, score))| {
+            .map(|(i, (id, name, score))| {
                 let color = if i < 3 {
                     podium_colors[i]
                 } else {
@@ -116,19 +314,45 @@ impl ResultsScreen {
                     2 => "  #3",
                     _ => "   -",
                 };
-                Row::new(vec![
-                    Cell::from(medal.to_string()).style(style),
-                    Cell::from(name.clone()).style(style),
-                    Cell::from(score.to_string()).style(style),
-                ])
+                let mut cells = vec![Cell::from(medal.to_string()).style(style)];
+                // Podium places are revealed one at a time, winner last
+                if self.celebration.as_ref().is_some_and(|c| !c.revealed(i)) {
+                    return Row::new(cells);
+                }
+                cells.push(Cell::from(name.clone()).style(style));
+                if detailed {
+                    let sections = match self.scorecard(*id) {
+                        Some(card) => [
+                            card.upper_subtotal() + card.upper_bonus(),
+                            card.lower_total(),
+                            card.yahtzee_bonus_total(),
+                        ]
+                        .map(|points| points.to_string()),
+                        None => Default::default(),
+                    };
+                    cells.extend(sections.map(|s| Cell::from(s).style(style)));
+                }
+                if timed {
+                    let average = self
+                        .average_turn_ms
+                        .iter()
+                        .find(|(player, _)| player == id)
+                        .map_or(String::new(), |(_, ms)| format!("{:.1}s", *ms as f64 / 1000.0));
+                    cells.push(Cell::from(average).style(style));
+                }
+                cells.push(Cell::from(score.to_string()).style(style));
+                Row::new(cells)
             })
             .collect();
 
-        let widths = [
-            Constraint::Length(8),
-            Constraint::Percentage(50),
-            Constraint::Length(10),
-        ];
+        let mut widths = vec![Constraint::Length(8), Constraint::Percentage(50)];
+        if detailed {
+            widths.extend([Constraint::Length(7); 3]);
+        }
+        if timed {
+            widths.push(Constraint::Length(9));
+        }
+        widths.push(Constraint::Length(10));
 
         let table = Table::new(rows, widths).header(header).block(
             Block::default()
@@ -143,15 +367,160 @@ impl ResultsScreen {
         );
         frame.render_widget(table, horizontal[1]);
 
+        if !self.round_totals.is_empty() {
+            let chart_area = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([
+                    Constraint::Percentage(20),
+                    Constraint::Percentage(60),
+                    Constraint::Percentage(20),
+                ])
+                .split(vertical[5])[1];
+            self.draw_score_chart(frame, chart_area);
+        }
+
         // Help
-        let help = Paragraph::new(Line::from(vec![
+        let mut help_spans = vec![
             Span::raw("  "),
             Span::styled("[Enter]", Style::default().fg(Color::Rgb(100, 255, 150))),
             Span::styled(" Back to lobby  ", Style::default().fg(Color::Rgb(120, 120, 140))),
+        ];
+        if self.room.is_some() {
+            let label = match self.rematch_at {
+                Some(at) => {
+                    let secs = at.saturating_duration_since(Instant::now()).as_secs_f64().ceil();
+                    format!(" Play again (in {}s, any key to stay)  ", secs)
+                }
+                None => " Play again  ".to_string(),
+            };
+            help_spans.extend([
+                Span::styled("[R]", Style::default().fg(Color::Rgb(100, 255, 150))),
+                Span::styled(label, Style::default().fg(Color::Rgb(120, 120, 140))),
+            ]);
+        }
+        if !self.scorecards.is_empty() {
+            help_spans.extend([
+                Span::styled("[C]", Style::default().fg(Color::Rgb(100, 200, 255))),
+                Span::styled(" Copy scoreboard  ", Style::default().fg(Color::Rgb(120, 120, 140))),
+            ]);
+        }
+        help_spans.extend([
+            Span::styled("[T]", Style::default().fg(Color::Rgb(100, 200, 255))),
+            Span::styled(" Stats  ", Style::default().fg(Color::Rgb(120, 120, 140))),
             Span::styled("[Q]", Style::default().fg(Color::Rgb(255, 150, 100))),
             Span::styled(" Quit", Style::default().fg(Color::Rgb(120, 120, 140))),
-        ]))
+        ]);
+        let help = Paragraph::new(Line::from(help_spans))
         .alignment(ratatui::layout::Alignment::Center);
-        frame.render_widget(help, vertical[4]);
+        frame.render_widget(help, vertical[6]);
+
+        if let Some(ref celebration) = self.celebration {
+            celebration.draw_confetti(frame.buffer_mut(), area);
+        }
+        self.toasts.draw(frame);
+    }
+
+    fn draw_score_chart(&self, frame: &mut Frame, area: Rect) {
+        const LINE_COLORS: [Color; 6] = [
+            Color::Rgb(255, 220, 50),
+            Color::Rgb(100, 200, 255),
+            Color::Rgb(255, 120, 160),
+            Color::Rgb(100, 255, 150),
+            Color::Rgb(200, 150, 255),
+            Color::Rgb(210, 150, 100),
+        ];
+
+        // Every line starts from zero before the first round
+        let points: Vec<(&str, Vec<(f64, f64)>)> = self
+            .final_scores
+            .iter()
+            .filter_map(|(id, name, _)| {
+                let (_, totals) = self.round_totals.iter().find(|(p, _)| p == id)?;
+                let line = std::iter::once(0)
+                    .chain(totals.iter().copied())
+                    .enumerate()
+                    .map(|(round, total)| (round as f64, total as f64))
+                    .collect();
+                Some((name.as_str(), line))
+            })
+            .collect();
+        let rounds = self.round_totals.iter().map(|(_, t)| t.len()).max().unwrap_or(0).max(1);
+        let top = self
+            .round_totals
+            .iter()
+            .flat_map(|(_, t)| t.iter().copied())
+            .max()
+            .unwrap_or(0)
+            .div_ceil(50)
+            .max(1)
+            * 50;
+
+        let datasets: Vec<Dataset> = points
+            .iter()
+            .zip(LINE_COLORS.iter().cycle())
+            .map(|((name, line), &color)| {
+                Dataset::default()
+                    .name(name.to_string())
+                    .marker(Marker::Braille)
+                    .graph_type(GraphType::Line)
+                    .style(Style::default().fg(color))
+                    .data(line)
+            })
+            .collect();
+
+        let dim = Style::default().fg(Color::Rgb(120, 120, 140));
+        let title = match lead_changes(&self.round_totals) {
+            0 => " Score by Round ".to_string(),
+            1 => " Score by Round - the lead changed once ".to_string(),
+            n => format!(" Score by Round - the lead changed {} times ", n),
+        };
+        let chart = Chart::new(datasets)
+            // Lines climb to the right, so the top left stays clear
+            .legend_position(Some(LegendPosition::TopLeft))
+            .hidden_legend_constraints((Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Rgb(80, 80, 100)))
+                    .title(title)
+                    .title_style(
+                        Style::default()
+                            .fg(Color::Rgb(255, 220, 50))
+                            .add_modifier(Modifier::BOLD),
+                    ),
+            )
+            .x_axis(
+                Axis::default()
+                    .style(dim)
+                    .bounds([0.0, rounds as f64])
+                    .labels(["0".to_string(), rounds.to_string()]),
+            )
+            .y_axis(
+                Axis::default()
+                    .style(dim)
+                    .bounds([0.0, top as f64])
+                    .labels(["0".to_string(), (top / 2).to_string(), top.to_string()]),
+            );
+        frame.render_widget(chart, area);
+    }
+}
+
+/// How many times the outright leader changed hands. Rounds where first
+/// place is shared keep the previous leader.
+fn lead_changes(round_totals: &[(Uuid, Vec<u16>)]) -> usize {
+    let rounds = round_totals.iter().map(|(_, t)| t.len()).max().unwrap_or(0);
+    let mut leader: Option<Uuid> = None;
+    let mut changes = 0;
+    for round in 0..rounds {
+        let score = |totals: &Vec<u16>| totals.get(round).copied().unwrap_or(0);
+        let best = round_totals.iter().map(|(_, t)| score(t)).max().unwrap_or(0);
+        let mut leaders = round_totals.iter().filter(|(_, t)| score(t) == best);
+        if let (Some((id, _)), None) = (leaders.next(), leaders.next()) {
+            if leader.is_some_and(|l| l != *id) {
+                changes += 1;
+            }
+            leader = Some(*id);
+        }
     }
+    changes
 }
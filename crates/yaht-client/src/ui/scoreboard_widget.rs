@@ -1,6 +1,7 @@
 use ratatui::{
     layout::Constraint,
     style::{Color, Modifier, Style},
+    text::{Line, Text},
     widgets::{Block, Borders, Cell, Row, Table},
 };
 
@@ -17,10 +18,24 @@ const PLAYER_COLORS: [Color; 6] = [
     Color::Rgb(255, 150, 200), // Pink
 ];
 
+/// Time banks under this many seconds are drawn in red.
+const LOW_TIME_BANK_SECS: u32 = 60;
+
 fn player_color(idx: usize) -> Color {
     PLAYER_COLORS[idx % PLAYER_COLORS.len()]
 }
 
+/// How the scoreboard's columns are laid out.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ScoreboardLayout {
+    /// Wider columns, for streamer mode.
+    pub large: bool,
+    /// Index of a player whose column is pinned first and drawn wider.
+    pub focus: Option<usize>,
+    /// Add a row with each player's gap to the leader.
+    pub leader_gap: bool,
+}
+
 pub fn build_scoreboard_table<'a>(
     players: &[PlayerSnapshot],
     current_player_index: usize,
@@ -28,27 +43,57 @@ pub fn build_scoreboard_table<'a>(
     my_player_id: uuid::Uuid,
     selected_category: Option<usize>,
     flash_cat: Option<(Category, u16)>,
+    layout: ScoreboardLayout,
 ) -> Table<'a> {
+    // Large tables have wider columns and room for longer names
+    let (name_width, score_width) = if layout.large { (16, 12) } else { (12, 8) };
+    let focus = layout.focus.filter(|&idx| idx < players.len());
+    let column_width = |idx: usize| if Some(idx) == focus { score_width + 8 } else { score_width };
+
+    // Column order: the focused player first, the rest as seated
+    let order: Vec<usize> = focus
+        .into_iter()
+        .chain((0..players.len()).filter(|&idx| Some(idx) != focus))
+        .collect();
+    let ordered = || order.iter().map(|&idx| (idx, &players[idx]));
+    // Time banks go under the names, when the game has them
+    let clocks = players.iter().any(|p| p.time_bank.is_some());
+
     let header_cells: Vec<Cell> = std::iter::once(
         Cell::from("Category").style(Style::default().fg(Color::Rgb(180, 180, 200))),
     )
-    .chain(players.iter().enumerate().map(|(idx, p)| {
+    .chain(ordered().map(|(idx, p)| {
+        let name_len = column_width(idx) as usize;
         let mut style = Style::default()
             .fg(player_color(idx))
             .add_modifier(Modifier::BOLD);
         if p.id == my_player_id {
             style = style.add_modifier(Modifier::UNDERLINED);
         }
-        if idx == current_player_index {
-            Cell::from(format!(">{}", truncate_name(&p.name, 7))).style(style)
+        if p.forfeited {
+            style = style.add_modifier(Modifier::CROSSED_OUT | Modifier::DIM);
+        }
+        let name = if idx == current_player_index {
+            format!(">{}", truncate_name(&p.name, name_len - 1))
         } else {
-            Cell::from(truncate_name(&p.name, 8)).style(style)
+            truncate_name(&p.name, name_len)
+        };
+        let mut lines = vec![Line::from(name)];
+        if let Some(secs) = p.time_bank {
+            let color = if secs < LOW_TIME_BANK_SECS {
+                Color::Rgb(255, 100, 100)
+            } else {
+                Color::Rgb(150, 150, 170)
+            };
+            lines.push(Line::styled(format_clock(secs), Style::default().fg(color)));
         }
+        Cell::from(Text::from(lines)).style(style)
     }))
     .collect();
 
     let header = Row::new(header_cells)
         .style(Style::default().add_modifier(Modifier::BOLD))
+        .height(if clocks { 2 } else { 1 })
         .bottom_margin(1);
 
     let mut rows: Vec<Row> = Vec::new();
@@ -85,8 +130,8 @@ pub fn build_scoreboard_table<'a>(
 
         let mut cells: Vec<Cell> = vec![Cell::from(cat.display_name().to_string()).style(name_style)];
 
-        for (player_idx, player) in players.iter().enumerate() {
-            if let Some(&score) = player.scorecard.scores.get(cat) {
+        for (player_idx, player) in ordered() {
+            if let Some(score) = player.scorecard.score(*cat) {
                 let cell_style = if is_flashing {
                     Style::default()
                         .fg(Color::Rgb(100, 255, 100))
@@ -97,8 +142,8 @@ pub fn build_scoreboard_table<'a>(
                     Style::default().fg(player_color(player_idx))
                 };
                 cells.push(Cell::from(score.to_string()).style(cell_style));
-            } else if player_idx == current_player_index && dice_values.is_some() {
-                let potential = scoring::compute_score(*cat, dice_values.unwrap());
+            } else if let Some(values) = dice_values.filter(|_| player_idx == current_player_index) {
+                let potential = scoring::compute_score(*cat, values);
                 let pot_style = if potential == 0 {
                     Style::default().fg(Color::Rgb(80, 80, 80))
                 } else if is_selected {
@@ -123,7 +168,7 @@ pub fn build_scoreboard_table<'a>(
             // Bonus row
             let mut bonus_cells: Vec<Cell> = vec![Cell::from("  Bonus")
                 .style(Style::default().fg(Color::Rgb(120, 120, 140)))];
-            for (_player_idx, player) in players.iter().enumerate() {
+            for (_, player) in ordered() {
                 let bonus = player.scorecard.upper_bonus();
                 if bonus > 0 {
                     bonus_cells.push(
@@ -143,7 +188,7 @@ pub fn build_scoreboard_table<'a>(
                         Color::Rgb(100, 100, 120)
                     };
                     bonus_cells.push(
-                        Cell::from(format!("{}/63", subtotal))
+                        Cell::from(format!("{}/{}", subtotal, player.scorecard.rules.upper_bonus_threshold))
                             .style(Style::default().fg(progress_color)),
                     );
                 }
@@ -163,7 +208,7 @@ pub fn build_scoreboard_table<'a>(
     if has_any_bonus {
         let mut yb_cells: Vec<Cell> = vec![Cell::from("  YZ Bonus")
             .style(Style::default().fg(Color::Rgb(120, 120, 140)))];
-        for player in players.iter() {
+        for (_, player) in ordered() {
             if player.scorecard.yahtzee_bonus_count > 0 {
                 yb_cells.push(
                     Cell::from(format!(
@@ -196,7 +241,7 @@ pub fn build_scoreboard_table<'a>(
             .fg(Color::Rgb(255, 220, 50))
             .add_modifier(Modifier::BOLD),
     )];
-    for (player_idx, player) in players.iter().enumerate() {
+    for (player_idx, player) in ordered() {
         total_cells.push(
             Cell::from(player.scorecard.grand_total().to_string()).style(
                 Style::default()
@@ -207,10 +252,14 @@ pub fn build_scoreboard_table<'a>(
     }
     rows.push(Row::new(total_cells));
 
+    if layout.leader_gap {
+        rows.push(leader_gap_row(players, &order));
+    }
+
     // Column widths
-    let mut widths = vec![Constraint::Length(12)]; // category name
-    for _ in players {
-        widths.push(Constraint::Length(8));
+    let mut widths = vec![Constraint::Length(name_width)]; // category name
+    for &idx in &order {
+        widths.push(Constraint::Length(column_width(idx)));
     }
 
     Table::new(rows, widths).header(header).block(
@@ -226,6 +275,41 @@ pub fn build_scoreboard_table<'a>(
     )
 }
 
+/// A crown over the leader, or everyone tied for the lead, and how far
+/// behind everyone else is. Forfeited players are left out.
+fn leader_gap_row<'a>(players: &[PlayerSnapshot], order: &[usize]) -> Row<'a> {
+    let total = |p: &PlayerSnapshot| p.scorecard.grand_total();
+    let best = players
+        .iter()
+        .filter(|p| !p.forfeited)
+        .map(total)
+        .max()
+        .unwrap_or(0);
+
+    let cells = std::iter::once(Cell::from("  Behind").style(Style::default().fg(Color::Rgb(120, 120, 140))))
+        .chain(order.iter().map(|&idx| {
+            let player = &players[idx];
+            if player.forfeited || best == 0 {
+                Cell::from("·").style(Style::default().fg(Color::Rgb(60, 60, 70)))
+            } else if total(player) == best {
+                Cell::from("♛").style(
+                    Style::default()
+                        .fg(Color::Rgb(255, 220, 50))
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                Cell::from(format!("-{}", best - total(player)))
+                    .style(Style::default().fg(Color::Rgb(200, 120, 120)))
+            }
+        }));
+    Row::new(cells.collect::<Vec<_>>())
+}
+
+/// Seconds as a clock, e.g. "9:05".
+fn format_clock(secs: u32) -> String {
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
 fn truncate_name(name: &str, max_len: usize) -> String {
     if name.len() <= max_len {
         name.to_string()
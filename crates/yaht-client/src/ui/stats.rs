@@ -0,0 +1,214 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    Frame,
+};
+
+use yaht_common::protocol::{CategoryStats, TurnTimes};
+use yaht_common::scoring::Category;
+
+use crate::stats::PlayerStats;
+
+/// Expected score per category under optimal solitaire play, in
+/// `Category::ALL` order. The heat view colours averages against these.
+const PAR: [f64; 13] = [
+    2.11, 5.28, 8.57, 12.16, 15.69, 19.19, 21.66, 13.10, 22.59, 29.46, 32.71, 16.87, 22.01,
+];
+
+/// Lifetime stats for the local player.
+#[derive(Debug, Clone)]
+pub struct StatsScreen {
+    pub player_name: String,
+    pub stats: PlayerStats,
+    /// Per-category tallies from the server, once it has answered.
+    pub server: Option<Vec<CategoryStats>>,
+    /// Turn times from the server, once it has answered.
+    pub turn_times: Option<TurnTimes>,
+}
+
+impl StatsScreen {
+    pub fn new(player_name: String) -> Self {
+        let stats = crate::stats::load_player(&player_name);
+        Self {
+            player_name,
+            stats,
+            server: None,
+            turn_times: None,
+        }
+    }
+
+    pub fn draw(&self, frame: &mut Frame) {
+        let area = frame.area();
+
+        let vertical = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Length(2),  // Title
+                Constraint::Min(10),   // Panels
+                Constraint::Length(2), // Help
+            ])
+            .split(area);
+
+        let horizontal = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(10),
+                Constraint::Percentage(38),
+                Constraint::Percentage(42),
+                Constraint::Percentage(10),
+            ])
+            .split(vertical[2]);
+
+        // Title
+        let title = Paragraph::new(Line::from(vec![
+            Span::styled(
+                "  STATS  ",
+                Style::default()
+                    .fg(Color::Rgb(255, 220, 50))
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                self.player_name.as_str(),
+                Style::default().fg(Color::Rgb(100, 200, 255)),
+            ),
+        ]))
+        .alignment(Alignment::Center);
+        frame.render_widget(title, vertical[1]);
+
+        // Overview
+        let label = Style::default().fg(Color::Rgb(150, 150, 170));
+        let value = Style::default().fg(Color::Rgb(200, 200, 220));
+        let s = &self.stats;
+
+        let mut overview_rows = vec![
+            ("Games played", s.games_played.to_string()),
+            (
+                "Wins",
+                format!("{} / {} ({:.0}%)", s.wins, s.contested_games, s.win_rate() * 100.0),
+            ),
+            ("Average score", format!("{:.1}", s.average_score())),
+            ("Best score", s.best_score.to_string()),
+            ("Yahtzees", format!("{} ({:.2} per game)", s.yahtzees, s.yahtzees_per_game())),
+            ("Upper bonus", format!("{:.0}% of games", s.bonus_rate() * 100.0)),
+        ];
+        if let Some(times) = self.turn_times.filter(|t| t.turns > 0) {
+            let secs = |ms: u64| format!("{:.1}s", ms as f64 / 1000.0);
+            overview_rows.push(("Avg turn", secs(times.average_ms().unwrap_or(0))));
+            overview_rows.push(("Slowest turn", secs(times.slowest_ms)));
+        }
+        let mut lines = vec![Line::from("")];
+        for (name, text) in overview_rows {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {:<15}", name), label),
+                Span::styled(text, value),
+            ]));
+        }
+        if s.games_played == 0 {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                "  No games recorded yet",
+                Style::default().fg(Color::Rgb(100, 100, 120)),
+            )));
+        }
+
+        let overview = Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Rgb(80, 80, 100)))
+                .title(" Lifetime ")
+                .title_style(
+                    Style::default()
+                        .fg(Color::Rgb(255, 220, 50))
+                        .add_modifier(Modifier::BOLD),
+                ),
+        );
+        frame.render_widget(overview, horizontal[1]);
+
+        // Category heat: the server's tallies if it keeps them, else the local averages
+        let server = self.server.as_ref().filter(|c| c.iter().any(|t| t.games() > 0));
+        let header = Row::new(vec![" Category", "   Avg", " Zero%", ""]).style(
+            Style::default()
+                .fg(Color::Rgb(180, 180, 200))
+                .add_modifier(Modifier::BOLD),
+        );
+        let rows: Vec<Row> = Category::ALL
+            .iter()
+            .zip(PAR)
+            .map(|(&cat, par)| {
+                let color = if cat.is_upper() {
+                    Color::Rgb(100, 200, 255)
+                } else {
+                    Color::Rgb(200, 150, 255)
+                };
+                let tally = server.and_then(|c| c.iter().find(|t| t.category == cat));
+                let (average, zero_rate) = match tally {
+                    Some(t) => (t.average(), Some(t.zero_rate())),
+                    None => (s.category_average(cat), None),
+                };
+                let played = tally.map_or(s.games_played, |t| t.games()) > 0;
+                let heat = if played {
+                    Style::default().fg(heat_color(average / par))
+                } else {
+                    value
+                };
+                Row::new(vec![
+                    Cell::from(format!(" {}", cat.display_name())).style(Style::default().fg(color)),
+                    Cell::from(format!("{:>6.1}", average)).style(heat),
+                    Cell::from(zero_rate.map_or("     -".to_string(), |z| format!("{:>5.0}%", z * 100.0)))
+                        .style(value),
+                    Cell::from(if played { heat_bar(average / par) } else { String::new() }).style(heat),
+                ])
+            })
+            .collect();
+
+        let title = if server.is_some() {
+            " Category Heat (server) "
+        } else {
+            " Category Heat "
+        };
+        let widths = [
+            Constraint::Min(14),
+            Constraint::Length(7),
+            Constraint::Length(7),
+            Constraint::Length(10),
+        ];
+        let table = Table::new(rows, widths).header(header).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Rgb(80, 80, 100)))
+                .title(title)
+                .title_style(
+                    Style::default()
+                        .fg(Color::Rgb(255, 220, 50))
+                        .add_modifier(Modifier::BOLD),
+                ),
+        );
+        frame.render_widget(table, horizontal[2]);
+
+        // Help
+        let help = Paragraph::new(Line::from(vec![
+            Span::styled("[Enter]", Style::default().fg(Color::Rgb(100, 255, 150))),
+            Span::styled(" Back", Style::default().fg(Color::Rgb(120, 120, 140))),
+        ]))
+        .alignment(Alignment::Center);
+        frame.render_widget(help, vertical[3]);
+    }
+}
+
+/// Red well below par, through yellow, to green at or above it.
+fn heat_color(ratio: f64) -> Color {
+    let t = ((ratio - 0.5) * 2.0).clamp(0.0, 1.0);
+    if t < 0.5 {
+        Color::Rgb(255, (80.0 + 350.0 * t) as u8, 80)
+    } else {
+        Color::Rgb((255.0 - 310.0 * (t - 0.5)) as u8, 255, (80.0 + 140.0 * (t - 0.5)) as u8)
+    }
+}
+
+/// A bar of up to ten cells for the average as a share of par.
+fn heat_bar(ratio: f64) -> String {
+    "█".repeat((ratio.clamp(0.0, 1.0) * 10.0).round() as usize)
+}
@@ -0,0 +1,70 @@
+use std::time::{Duration, Instant};
+
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// How long each toast stays up.
+const TOAST_DURATION: Duration = Duration::from_secs(5);
+const TOAST_WIDTH: u16 = 44;
+
+/// Personal record notices stacked in the top-right corner, each shown
+/// for a while.
+#[derive(Debug, Clone, Default)]
+pub struct Toasts {
+    items: Vec<(String, Instant)>,
+}
+
+impl Toasts {
+    pub fn push(&mut self, text: String) {
+        self.items.push((text, Instant::now() + TOAST_DURATION));
+    }
+
+    /// Drop expired toasts. Returns true if the screen needs redrawing.
+    pub fn tick(&mut self) -> bool {
+        let now = Instant::now();
+        let before = self.items.len();
+        self.items.retain(|(_, until)| *until > now);
+        self.items.len() != before
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn draw(&self, frame: &mut Frame) {
+        let area = frame.area();
+        let width = TOAST_WIDTH.min(area.width);
+        let mut y = area.y + 1;
+        for (text, _) in &self.items {
+            if y + 3 > area.bottom() {
+                break;
+            }
+            let rect = Rect::new(area.right().saturating_sub(width + 1), y, width, 3);
+            frame.render_widget(Clear, rect);
+            frame.render_widget(
+                Paragraph::new(Line::from(Span::styled(
+                    format!(" {}", text),
+                    Style::default().fg(Color::Rgb(200, 200, 220)),
+                )))
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(Color::Rgb(255, 220, 50)))
+                        .title(" ★ Personal record ")
+                        .title_style(
+                            Style::default()
+                                .fg(Color::Rgb(255, 220, 50))
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                ),
+                rect,
+            );
+            y += 3;
+        }
+    }
+}
@@ -1,6 +1,8 @@
-use rand::Rng;
+use std::collections::HashMap;
 
-use crate::dice::DiceSet;
+use rand::{Rng, RngCore};
+
+use crate::dice::{DiceSet, NUM_DICE};
 use crate::player::Scorecard;
 use crate::scoring::{self, Category};
 
@@ -12,6 +14,27 @@ pub enum AiDifficulty {
     Hard,   // Greedy with smart holds and upper bonus awareness
 }
 
+/// How a computer player decides its holds and its category. Implement
+/// this to plug a custom player into anything that drives `GameState`.
+pub trait Strategy {
+    /// Dice to keep before the next roll.
+    fn choose_holds(&self, dice: &DiceSet, scorecard: &Scorecard, rng: &mut dyn RngCore) -> [bool; 5];
+
+    /// An open category to score the dice in.
+    fn choose_category(&self, dice: &DiceSet, scorecard: &Scorecard, rng: &mut dyn RngCore) -> Category;
+}
+
+/// The built-in players, one per difficulty.
+impl Strategy for AiDifficulty {
+    fn choose_holds(&self, dice: &DiceSet, scorecard: &Scorecard, mut rng: &mut dyn RngCore) -> [bool; 5] {
+        choose_holds(dice, scorecard, *self, &mut rng)
+    }
+
+    fn choose_category(&self, dice: &DiceSet, scorecard: &Scorecard, mut rng: &mut dyn RngCore) -> Category {
+        choose_category(dice, scorecard, *self, &mut rng)
+    }
+}
+
 /// Choose which dice to hold based on AI strategy.
 /// Returns the held array [bool; 5].
 pub fn choose_holds(
@@ -42,7 +65,7 @@ pub fn choose_category(
     difficulty: AiDifficulty,
     rng: &mut impl Rng,
 ) -> Category {
-    let available = scorecard.available_categories();
+    let available = scorecard.scorable_categories(&dice.values());
     if available.is_empty() {
         return Category::Chance; // shouldn't happen
     }
@@ -98,7 +121,7 @@ fn greedy_holds(dice: &DiceSet, scorecard: &Scorecard, difficulty: AiDifficulty)
                 .filter(|&v| counts[v as usize] >= 2)
                 .map(|v| (v, counts[v as usize]))
                 .collect();
-            groups.sort_by(|a, b| b.1.cmp(&a.1));
+            groups.sort_by_key(|g| std::cmp::Reverse(g.1));
 
             if groups.len() >= 2 {
                 // Hold the triple and the pair
@@ -146,7 +169,7 @@ fn greedy_holds(dice: &DiceSet, scorecard: &Scorecard, difficulty: AiDifficulty)
 /// For Hard difficulty, also considers upper bonus potential.
 fn greedy_category(dice: &DiceSet, scorecard: &Scorecard, difficulty: AiDifficulty) -> Category {
     let values = dice.values();
-    let available = scorecard.available_categories();
+    let available = scorecard.scorable_categories(&values);
 
     if available.is_empty() {
         return Category::Chance;
@@ -156,7 +179,7 @@ fn greedy_category(dice: &DiceSet, scorecard: &Scorecard, difficulty: AiDifficul
     let mut scored: Vec<(Category, u16, i32)> = available
         .iter()
         .map(|&cat| {
-            let score = scoring::compute_score(cat, &values);
+            let score = scorecard.score_for(cat, &values);
             let priority = if difficulty == AiDifficulty::Hard {
                 category_priority(cat, score, scorecard)
             } else {
@@ -192,15 +215,15 @@ fn category_priority(cat: Category, score: u16, scorecard: &Scorecard) -> i32 {
         // Full house is decent
         Category::FullHouse if score > 0 => base + 10,
 
-        // Upper section: bonus for being on track for 63
+        // Upper section: bonus for being on track for the upper bonus
         cat if cat.is_upper() => {
             let upper_so_far = scorecard.upper_subtotal();
             let cats_used = Category::UPPER.iter().filter(|c| scorecard.is_category_used(**c)).count();
             let cats_remaining = 6 - cats_used;
 
             if cats_remaining > 0 {
-                // Target per remaining category to hit 63
-                let remaining_needed = 63u16.saturating_sub(upper_so_far);
+                // Target per remaining category to hit the threshold
+                let remaining_needed = scorecard.rules.upper_bonus_threshold.saturating_sub(upper_so_far);
                 let target_per_cat = if cats_remaining > 0 {
                     remaining_needed / cats_remaining as u16
                 } else {
@@ -262,6 +285,149 @@ fn least_valuable_zero(available: &[Category], scorecard: &Scorecard) -> Categor
         .unwrap_or(Category::Chance)
 }
 
+/// Expected outcome of keeping a set of dice and rerolling the rest.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HoldEvaluation {
+    pub held: [bool; 5],
+    /// Expected best immediate score at the end of the turn, assuming
+    /// optimal holds on any later rolls.
+    pub expected_score: f64,
+}
+
+/// Best score available right now from the open categories.
+pub fn best_immediate_score(dice: &DiceSet, scorecard: &Scorecard) -> u16 {
+    stop_value(&dice.values(), &scorecard.available_categories()) as u16
+}
+
+/// Evaluate every hold choice by exhaustive search over the remaining rolls.
+/// `rolls_left` counts the rolls still available in the turn (including the
+/// one the hold is made for). Results are sorted best first.
+pub fn evaluate_holds(dice: &DiceSet, scorecard: &Scorecard, rolls_left: u8) -> Vec<HoldEvaluation> {
+    if rolls_left == 0 {
+        return Vec::new();
+    }
+    let values = dice.values();
+    let mut evaluator = Evaluator::new(scorecard);
+    let mut evaluations: Vec<HoldEvaluation> = (0..1u8 << NUM_DICE)
+        .map(|mask| {
+            let held: [bool; 5] = std::array::from_fn(|i| mask & (1 << i) != 0);
+            let mut kept: Vec<u8> = (0..NUM_DICE).filter(|&i| held[i]).map(|i| values[i]).collect();
+            kept.sort();
+            HoldEvaluation {
+                held,
+                expected_score: evaluator.keep_value(kept, rolls_left),
+            }
+        })
+        .collect();
+    evaluations.sort_by(|a, b| b.expected_score.total_cmp(&a.expected_score));
+    evaluations
+}
+
+/// The hold with the highest expected score.
+pub fn best_hold(dice: &DiceSet, scorecard: &Scorecard, rolls_left: u8) -> Option<HoldEvaluation> {
+    evaluate_holds(dice, scorecard, rolls_left).into_iter().next()
+}
+
+fn stop_value(values: &[u8; 5], available: &[Category]) -> f64 {
+    available
+        .iter()
+        .map(|&cat| scoring::compute_score(cat, values))
+        .max()
+        .unwrap_or(0) as f64
+}
+
+/// Memoized expectimax over sorted dice multisets.
+struct Evaluator {
+    available: Vec<Category>,
+    /// Distinct sorted outcomes (with probability) of rolling `n` dice, indexed by `n`.
+    outcomes: Vec<Vec<(Vec<u8>, f64)>>,
+    value_memo: HashMap<([u8; 5], u8), f64>,
+    keep_memo: HashMap<(Vec<u8>, u8), f64>,
+}
+
+impl Evaluator {
+    fn new(scorecard: &Scorecard) -> Self {
+        Self {
+            available: scorecard.available_categories(),
+            outcomes: (0..=NUM_DICE).map(roll_outcomes).collect(),
+            value_memo: HashMap::new(),
+            keep_memo: HashMap::new(),
+        }
+    }
+
+    /// Value of holding a sorted dice set with `rolls_left` rolls still usable.
+    fn value(&mut self, dice: [u8; 5], rolls_left: u8) -> f64 {
+        let stop = stop_value(&dice, &self.available);
+        if rolls_left == 0 {
+            return stop;
+        }
+        if let Some(&v) = self.value_memo.get(&(dice, rolls_left)) {
+            return v;
+        }
+        let mut best = stop;
+        for mask in 0..(1u8 << NUM_DICE) - 1 {
+            let kept: Vec<u8> = (0..NUM_DICE).filter(|&i| mask & (1 << i) != 0).map(|i| dice[i]).collect();
+            best = best.max(self.keep_value(kept, rolls_left));
+        }
+        self.value_memo.insert((dice, rolls_left), best);
+        best
+    }
+
+    /// Expected value of keeping `kept` (sorted) and rolling the other dice.
+    fn keep_value(&mut self, kept: Vec<u8>, rolls_left: u8) -> f64 {
+        if let Some(&v) = self.keep_memo.get(&(kept.clone(), rolls_left)) {
+            return v;
+        }
+        let outcomes = self.outcomes[NUM_DICE - kept.len()].clone();
+        let mut expected = 0.0;
+        for (rolled, probability) in &outcomes {
+            let mut dice = [0u8; 5];
+            for (slot, &v) in dice.iter_mut().zip(kept.iter().chain(rolled.iter())) {
+                *slot = v;
+            }
+            dice.sort();
+            expected += probability * self.value(dice, rolls_left - 1);
+        }
+        self.keep_memo.insert((kept, rolls_left), expected);
+        expected
+    }
+}
+
+/// All sorted outcomes of rolling `n` dice with their probabilities.
+fn roll_outcomes(n: usize) -> Vec<(Vec<u8>, f64)> {
+    fn build(n: usize, min: u8, current: &mut Vec<u8>, multisets: &mut Vec<Vec<u8>>) {
+        if current.len() == n {
+            multisets.push(current.clone());
+            return;
+        }
+        for v in min..=6 {
+            current.push(v);
+            build(n, v, current, multisets);
+            current.pop();
+        }
+    }
+
+    fn factorial(n: usize) -> f64 {
+        (1..=n).map(|i| i as f64).product()
+    }
+
+    let mut multisets = Vec::new();
+    build(n, 1, &mut Vec::with_capacity(n), &mut multisets);
+
+    let total = 6f64.powi(n as i32);
+    multisets
+        .into_iter()
+        .map(|multiset| {
+            let mut counts = [0usize; 7];
+            for &v in &multiset {
+                counts[v as usize] += 1;
+            }
+            let permutations = factorial(n) / counts.iter().map(|&c| factorial(c)).product::<f64>();
+            (multiset, permutations / total)
+        })
+        .collect()
+}
+
 fn upper_face_value(cat: Category) -> u8 {
     match cat {
         Category::Ones => 1,
@@ -400,4 +566,47 @@ mod tests {
         // Should pick Twos (score 2) over Ones (score 0), or Twos which actually scores
         assert!(cat == Category::Ones || cat == Category::Twos);
     }
+
+    #[test]
+    fn test_roll_outcome_probabilities_sum_to_one() {
+        for n in 0..=5 {
+            let total: f64 = roll_outcomes(n).iter().map(|(_, p)| p).sum();
+            assert!((total - 1.0).abs() < 1e-9);
+        }
+        assert_eq!(roll_outcomes(5).len(), 252);
+    }
+
+    #[test]
+    fn test_best_hold_keeps_yahtzee() {
+        let dice = make_dice([4, 4, 4, 4, 4]);
+        let sc = Scorecard::new();
+        let best = best_hold(&dice, &sc, 2).unwrap();
+        assert_eq!(best.held, [true; 5]);
+        assert!((best.expected_score - 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_best_hold_chases_open_category() {
+        // Only Sixes is open: keeping the sixes beats rerolling everything.
+        let dice = make_dice([6, 6, 1, 2, 3]);
+        let mut sc = Scorecard::new();
+        for cat in Category::ALL {
+            if cat != Category::Sixes {
+                sc.record(cat, 0).unwrap();
+            }
+        }
+        let evaluations = evaluate_holds(&dice, &sc, 1);
+        assert_eq!(evaluations.len(), 32);
+        assert_eq!(evaluations[0].held, [true, true, false, false, false]);
+        // 12 kept + 3 dice * (1/6 * 6) expected
+        assert!((evaluations[0].expected_score - 15.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_no_holds_without_rolls_left() {
+        let dice = make_dice([1, 2, 3, 4, 5]);
+        let sc = Scorecard::new();
+        assert!(evaluate_holds(&dice, &sc, 0).is_empty());
+        assert_eq!(best_immediate_score(&dice, &sc), 40);
+    }
 }
@@ -5,6 +5,7 @@ pub const NUM_DICE: usize = 5;
 pub const MAX_ROLLS: u8 = 3;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Die {
     pub value: u8,
     pub held: bool,
@@ -32,6 +33,7 @@ impl Default for Die {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct DiceSet {
     pub dice: [Die; NUM_DICE],
 }
@@ -49,6 +51,15 @@ impl DiceSet {
         }
     }
 
+    /// Set the unheld dice to the given faces, as if they had been rolled.
+    pub fn set_unheld(&mut self, faces: [u8; 5]) {
+        for (die, &face) in self.dice.iter_mut().zip(faces.iter()) {
+            if !die.held {
+                die.value = face;
+            }
+        }
+    }
+
     pub fn set_held(&mut self, held: [bool; 5]) {
         for (die, &h) in self.dice.iter_mut().zip(held.iter()) {
             die.held = h;
@@ -71,6 +82,10 @@ impl DiceSet {
         ]
     }
 
+    pub fn held(&self) -> [bool; 5] {
+        self.dice.map(|d| d.held)
+    }
+
     pub fn sorted_values(&self) -> [u8; 5] {
         let mut v = self.values();
         v.sort();
@@ -84,6 +99,45 @@ impl Default for DiceSet {
     }
 }
 
+/// How often each face has come up, `0` holding the count of ones.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct FaceCounts(pub [u32; 6]);
+
+impl FaceCounts {
+    /// Count one die showing `face`; values outside 1-6 are ignored.
+    pub fn record(&mut self, face: u8) {
+        if let Some(count) = self.0.get_mut((face as usize).wrapping_sub(1)) {
+            *count += 1;
+        }
+    }
+
+    /// Count the dice that were just rolled, i.e. the unheld ones.
+    pub fn record_roll(&mut self, dice: &DiceSet) {
+        for die in dice.dice.iter().filter(|d| !d.held) {
+            self.record(die.value);
+        }
+    }
+
+    pub fn total(&self) -> u32 {
+        self.0.iter().sum()
+    }
+
+    /// Pearson's chi-squared statistic against a fair die. With five
+    /// degrees of freedom, a fair die exceeds 11.07 only 5% of the time.
+    pub fn chi_squared(&self) -> f64 {
+        let total = self.total();
+        if total == 0 {
+            return 0.0;
+        }
+        let expected = total as f64 / 6.0;
+        self.0
+            .iter()
+            .map(|&count| (count as f64 - expected).powi(2) / expected)
+            .sum()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -156,4 +210,36 @@ mod tests {
         dice.dice[4].value = 2;
         assert_eq!(dice.sorted_values(), [1, 2, 3, 4, 5]);
     }
+
+    #[test]
+    fn test_face_counts() {
+        let mut counts = FaceCounts::default();
+        assert_eq!(counts.chi_squared(), 0.0);
+
+        let mut dice = DiceSet::new();
+        dice.set_unheld([1, 2, 3, 4, 5]);
+        dice.dice[0].held = true;
+        counts.record_roll(&dice);
+        counts.record(6);
+        counts.record(7);
+        assert_eq!(counts, FaceCounts([0, 1, 1, 1, 1, 1]));
+        assert_eq!(counts.total(), 5);
+
+        // Perfectly even counts are as fair as it gets; all sixes is not
+        assert_eq!(FaceCounts([10; 6]).chi_squared(), 0.0);
+        assert!(FaceCounts([0, 0, 0, 0, 0, 60]).chi_squared() > 11.07);
+    }
+
+    #[test]
+    fn test_seeded_rolls_look_fair() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let mut counts = FaceCounts::default();
+        let mut dice = DiceSet::new();
+        for _ in 0..1200 {
+            dice.roll_unheld(&mut rng);
+            counts.record_roll(&dice);
+        }
+        assert_eq!(counts.total(), 6000);
+        assert!(counts.chi_squared() < 20.5, "{:?}", counts);
+    }
 }
@@ -0,0 +1,168 @@
+//! The public server directory. Servers that opt in announce themselves
+//! with a periodic `POST {url}/servers`, and clients list them with
+//! `GET {url}/servers`. Both carry [`ServerListing`]s as JSON over plain
+//! HTTP.
+
+use serde::{Deserialize, Serialize};
+
+/// One server as announced to, and listed by, the directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServerListing {
+    pub name: String,
+    /// Where clients should connect, as HOST:PORT.
+    pub address: String,
+    #[serde(default)]
+    pub region: Option<String>,
+    /// Connected players.
+    pub players: u32,
+    pub max_players: u32,
+    /// The newest protocol revision the server speaks.
+    pub protocol: u32,
+}
+
+/// A directory URL split into what a request needs.
+#[cfg(any(feature = "net", test))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Endpoint {
+    /// HOST:PORT to connect to.
+    addr: String,
+    host: String,
+    /// The request path for the server list.
+    path: String,
+}
+
+#[cfg(any(feature = "net", test))]
+fn endpoint(url: &str) -> Result<Endpoint, String> {
+    let rest = url
+        .strip_prefix("http://")
+        .ok_or_else(|| format!("expected an http:// directory URL, got '{}'", url))?;
+    let (host, base) = match rest.find('/') {
+        Some(i) => (&rest[..i], rest[i..].trim_end_matches('/')),
+        None => (rest, ""),
+    };
+    if host.is_empty() {
+        return Err(format!("no host in directory URL '{}'", url));
+    }
+    let addr = if host.rsplit_once(':').is_some_and(|(_, port)| port.parse::<u16>().is_ok()) {
+        host.to_string()
+    } else {
+        format!("{}:80", host)
+    };
+    Ok(Endpoint {
+        addr,
+        host: host.to_string(),
+        path: format!("{}/servers", base),
+    })
+}
+
+/// The body of a successful response, or why there is none.
+#[cfg(any(feature = "net", test))]
+fn response_body(response: &[u8]) -> Result<&[u8], String> {
+    let split = response
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .ok_or("truncated response from the directory")?;
+    let head = String::from_utf8_lossy(&response[..split]);
+    let status = head.lines().next().unwrap_or_default();
+    match status.split_whitespace().nth(1).and_then(|code| code.parse::<u16>().ok()) {
+        Some(200..=299) => Ok(&response[split + 4..]),
+        _ => Err(format!("directory answered '{}'", status)),
+    }
+}
+
+#[cfg(feature = "net")]
+pub use net::{announce, fetch_servers};
+
+#[cfg(feature = "net")]
+mod net {
+    use std::time::Duration;
+
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpStream;
+
+    use super::{endpoint, response_body, ServerListing};
+
+    const TIMEOUT: Duration = Duration::from_secs(10);
+    /// Longest response read from the directory.
+    const MAX_RESPONSE: u64 = 1 << 20;
+
+    /// Send one heartbeat for this server.
+    pub async fn announce(url: &str, listing: &ServerListing) -> anyhow::Result<()> {
+        request(url, "POST", &serde_json::to_vec(listing)?).await?;
+        Ok(())
+    }
+
+    /// Every server the directory lists.
+    pub async fn fetch_servers(url: &str) -> anyhow::Result<Vec<ServerListing>> {
+        let body = request(url, "GET", &[]).await?;
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    /// One HTTP/1.0 request, so the answer is never chunked and ends when
+    /// the directory closes the connection.
+    async fn request(url: &str, method: &str, body: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let endpoint = endpoint(url).map_err(|e| anyhow::anyhow!(e))?;
+        let exchange = async {
+            let mut stream = TcpStream::connect(&endpoint.addr).await?;
+            let head = format!(
+                "{} {} HTTP/1.0\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n",
+                method,
+                endpoint.path,
+                endpoint.host,
+                body.len()
+            );
+            stream.write_all(head.as_bytes()).await?;
+            stream.write_all(body).await?;
+            let mut response = Vec::new();
+            stream.take(MAX_RESPONSE).read_to_end(&mut response).await?;
+            anyhow::Ok(response)
+        };
+        let response = tokio::time::timeout(TIMEOUT, exchange)
+            .await
+            .map_err(|_| anyhow::anyhow!("directory at {} timed out", endpoint.addr))??;
+        Ok(response_body(&response).map_err(|e| anyhow::anyhow!(e))?.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint_from_url() {
+        assert_eq!(
+            endpoint("http://dir.example.org").unwrap(),
+            Endpoint {
+                addr: "dir.example.org:80".into(),
+                host: "dir.example.org".into(),
+                path: "/servers".into(),
+            }
+        );
+        let e = endpoint("http://10.0.0.5:8080/yaht/").unwrap();
+        assert_eq!(e.addr, "10.0.0.5:8080");
+        assert_eq!(e.path, "/yaht/servers");
+
+        assert!(endpoint("https://dir.example.org").is_err());
+        assert!(endpoint("http:///servers").is_err());
+    }
+
+    #[test]
+    fn test_response_body_needs_success() {
+        let ok = b"HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\r\n[]";
+        assert_eq!(response_body(ok).unwrap(), b"[]");
+        let created = b"HTTP/1.0 204 No Content\r\n\r\n";
+        assert_eq!(response_body(created).unwrap(), b"");
+
+        let err = response_body(b"HTTP/1.1 503 Service Unavailable\r\n\r\nbusy").unwrap_err();
+        assert!(err.contains("503"), "{}", err);
+        assert!(response_body(b"HTTP/1.1 200 OK\r\n").is_err());
+    }
+
+    #[test]
+    fn test_listing_region_is_optional() {
+        let json = r#"{"name":"Kitchen table","address":"yaht.example.org:9876","players":3,"max_players":100,"protocol":2}"#;
+        let listing: ServerListing = serde_json::from_str(json).unwrap();
+        assert_eq!(listing.region, None);
+        assert_eq!(listing.players, 3);
+    }
+}
@@ -1,14 +1,18 @@
+use std::time::Duration;
+
 use rand::Rng;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 use uuid::Uuid;
 
 use crate::dice::{DiceSet, MAX_ROLLS};
+use crate::observer::{Bonus, EngineEvent, Observers};
 use crate::player::{Player, Scorecard};
-use crate::scoring::{self, Category};
+use crate::scoring::{self, Category, ScoringRules};
 
 // -- Turn State Machine --
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum TurnPhase {
     WaitingForRoll,
     Rolling { rolls_used: u8 },
@@ -22,20 +26,29 @@ pub struct TurnState {
     pub phase: TurnPhase,
     pub dice: DiceSet,
     pub rolls_used: u8,
+    pub max_rolls: u8,
+    /// Every roll so far this turn.
+    pub rolls: Vec<RollRecord>,
 }
 
 impl TurnState {
-    pub fn new(player_id: Uuid) -> Self {
+    pub fn new(player_id: Uuid, max_rolls: u8) -> Self {
         Self {
             player_id,
             phase: TurnPhase::WaitingForRoll,
             dice: DiceSet::new(),
             rolls_used: 0,
+            max_rolls,
+            rolls: Vec::new(),
         }
     }
 
+    pub fn rolls_remaining(&self) -> u8 {
+        self.max_rolls.saturating_sub(self.rolls_used)
+    }
+
     pub fn can_roll(&self) -> bool {
-        self.rolls_used < MAX_ROLLS
+        self.rolls_used < self.max_rolls
             && matches!(
                 self.phase,
                 TurnPhase::WaitingForRoll | TurnPhase::Rolling { .. }
@@ -51,15 +64,29 @@ impl TurnState {
     }
 
     pub fn roll(&mut self, rng: &mut impl Rng) -> Result<(), GameError> {
+        self.roll_with(|dice| dice.roll_unheld(rng))
+    }
+
+    /// Roll with predetermined faces for the unheld dice (scripted play).
+    pub fn roll_fixed(&mut self, faces: [u8; 5]) -> Result<(), GameError> {
+        self.roll_with(|dice| dice.set_unheld(faces))
+    }
+
+    fn roll_with(&mut self, roll: impl FnOnce(&mut DiceSet)) -> Result<(), GameError> {
         if !self.can_roll() {
             return Err(GameError::CannotRoll);
         }
         if self.rolls_used == 0 {
             self.dice.release_all();
         }
-        self.dice.roll_unheld(rng);
+        let held = self.dice.held();
+        roll(&mut self.dice);
+        self.rolls.push(RollRecord {
+            held,
+            dice: self.dice.values(),
+        });
         self.rolls_used += 1;
-        self.phase = if self.rolls_used >= MAX_ROLLS {
+        self.phase = if self.rolls_used >= self.max_rolls {
             TurnPhase::MustScore
         } else {
             TurnPhase::Rolling {
@@ -78,9 +105,209 @@ impl TurnState {
     }
 }
 
+// -- Turn History --
+
+/// One roll within a turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RollRecord {
+    /// Dice kept from the previous roll. All false on the first roll.
+    pub held: [bool; 5],
+    /// Faces after the roll.
+    pub dice: [u8; 5],
+}
+
+/// A finished turn: who played it, how the dice went and what was scored.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TurnRecord {
+    pub player_id: Uuid,
+    pub round: u8,
+    pub rolls: Vec<RollRecord>,
+    pub category: Category,
+    pub score: u16,
+    /// Whether the turn also earned a Yahtzee bonus.
+    pub yahtzee_bonus: bool,
+    /// How long the turn took in milliseconds, when a server timed it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+}
+
+impl TurnRecord {
+    /// The dice the turn was scored with.
+    pub fn final_dice(&self) -> Option<[u8; 5]> {
+        self.rolls.last().map(|r| r.dice)
+    }
+}
+
+// -- Rules --
+
+/// Rounds in a standard game, one per category.
+pub const STANDARD_ROUNDS: u8 = 13;
+
+/// Settings a game is played under, fixed when it starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct GameConfig {
+    /// Rounds played, at most one per category. A shorter game ends with
+    /// categories left open.
+    pub rounds: u8,
+    pub rolls_per_turn: u8,
+    /// Bonus thresholds and values, and the Joker rule.
+    pub scoring: ScoringRules,
+    /// Seconds each turn may take before the server plays it out, or
+    /// `None` for no turn timer.
+    pub turn_seconds: Option<u32>,
+    /// Break a tie for first with a sudden-death playoff instead of
+    /// declaring a draw.
+    pub sudden_death: bool,
+    /// Seconds each player has for all of their turns together, like a
+    /// chess clock, or `None` for no time bank.
+    pub time_bank_secs: Option<u32>,
+    /// What happens to a player whose time bank runs out.
+    pub out_of_time: OutOfTime,
+    /// The preset these rules were picked from, or `None` for rules set
+    /// by hand.
+    pub pace: Option<Pace>,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            rounds: STANDARD_ROUNDS,
+            rolls_per_turn: MAX_ROLLS,
+            scoring: ScoringRules::default(),
+            turn_seconds: None,
+            sudden_death: false,
+            time_bank_secs: None,
+            out_of_time: OutOfTime::default(),
+            pace: None,
+        }
+    }
+}
+
+/// How fast a game plays: a turn timer, plus hints for how clients pace
+/// themselves around it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum Pace {
+    Casual,
+    Standard,
+    Blitz,
+}
+
+impl Pace {
+    pub const ALL: [Pace; 3] = [Pace::Casual, Pace::Standard, Pace::Blitz];
+
+    /// Seconds each turn may take.
+    pub fn turn_seconds(self) -> Option<u32> {
+        match self {
+            Pace::Casual => None,
+            Pace::Standard => Some(90),
+            Pace::Blitz => Some(20),
+        }
+    }
+
+    /// Whether clients should ask before scoring a zero.
+    pub fn confirms_zero_scores(self) -> bool {
+        self != Pace::Blitz
+    }
+
+    /// How long clients should let the dice tumble on each roll.
+    pub fn roll_animation(self) -> Duration {
+        match self {
+            Pace::Casual => Duration::from_millis(900),
+            Pace::Standard => Duration::from_millis(600),
+            Pace::Blitz => Duration::from_millis(200),
+        }
+    }
+
+    /// `rules` played at this pace.
+    pub fn apply(self, rules: GameConfig) -> GameConfig {
+        GameConfig {
+            turn_seconds: self.turn_seconds(),
+            pace: Some(self),
+            ..rules
+        }
+    }
+
+    /// The preset `rules` still match, if they came from one.
+    pub fn of(rules: &GameConfig) -> Option<Pace> {
+        rules.pace.filter(|pace| pace.turn_seconds() == rules.turn_seconds)
+    }
+}
+
+/// The consequence of running out of time bank.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum OutOfTime {
+    /// The player's turns are played for them: one roll, scored in the
+    /// best open category.
+    #[default]
+    AutoScore,
+    /// The player forfeits the game.
+    Forfeit,
+}
+
+// -- Sudden Death --
+
+/// Playoff rounds played before a tie that will not break is called a draw.
+pub const MAX_PLAYOFF_ROUNDS: u8 = 3;
+
+/// A playoff between players tied for first. Each contender rolls once per
+/// playoff round; the highest total wins, and those tied on it go again.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SuddenDeath {
+    /// Current playoff round, starting at 1.
+    pub round: u8,
+    /// Players still in the playoff, in turn order.
+    pub contenders: Vec<Uuid>,
+    /// Every playoff roll so far.
+    pub rolls: Vec<PlayoffRoll>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PlayoffRoll {
+    pub round: u8,
+    pub player_id: Uuid,
+    pub dice: [u8; 5],
+}
+
+impl PlayoffRoll {
+    pub fn total(&self) -> u16 {
+        self.dice.iter().map(|&d| d as u16).sum()
+    }
+}
+
+impl SuddenDeath {
+    fn has_rolled(&self, player_id: Uuid) -> bool {
+        self.rolls
+            .iter()
+            .any(|r| r.round == self.round && r.player_id == player_id)
+    }
+
+    /// Contenders with the best total in the current round, in turn order.
+    fn round_leaders(&self) -> Vec<Uuid> {
+        if self.contenders.len() == 1 {
+            return self.contenders.clone();
+        }
+        let round: Vec<&PlayoffRoll> = self.rolls.iter().filter(|r| r.round == self.round).collect();
+        let best = round.iter().map(|r| r.total()).max().unwrap_or(0);
+        self.contenders
+            .iter()
+            .copied()
+            .filter(|id| round.iter().any(|r| r.player_id == *id && r.total() == best))
+            .collect()
+    }
+}
+
 // -- Game State Machine --
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum GamePhase {
     Lobby,
     Playing,
@@ -95,20 +322,52 @@ pub struct GameState {
     pub turn: Option<TurnState>,
     pub round: u8,
     pub total_rounds: u8,
+    /// Every completed turn, oldest first.
+    pub turn_history: Vec<TurnRecord>,
+    pub rules: GameConfig,
+    /// When the current turn runs out (Unix seconds). The engine has no
+    /// clock, so whoever runs the game sets this with `start_turn_timer`.
+    pub turn_deadline: Option<i64>,
+    /// The tiebreaker, once the regular rounds end in a tie for first with
+    /// `rules.sudden_death` on.
+    pub sudden_death: Option<SuddenDeath>,
+    /// Whose time bank is running and since when (Unix seconds). Set with
+    /// `start_turn_timer`, like the turn deadline.
+    #[serde(default)]
+    pub time_bank_running: Option<(Uuid, i64)>,
+    /// Called with each engine event; see `observe`.
+    #[serde(skip)]
+    pub observers: Observers,
 }
 
 impl GameState {
-    pub fn new(players: Vec<Player>) -> Self {
+    /// A game between `players` under `config`, their scorecards scored
+    /// under its scoring rules.
+    pub fn new(mut players: Vec<Player>, config: GameConfig) -> Self {
+        for player in &mut players {
+            player.scorecard.rules = config.scoring;
+        }
         Self {
             phase: GamePhase::Lobby,
             players,
             current_player_index: 0,
             turn: None,
             round: 0,
-            total_rounds: 13,
+            total_rounds: config.rounds.clamp(1, STANDARD_ROUNDS),
+            turn_history: Vec::new(),
+            rules: config,
+            turn_deadline: None,
+            sudden_death: None,
+            time_bank_running: None,
+            observers: Observers::default(),
         }
     }
 
+    /// Call `callback` with each [`EngineEvent`] from now on.
+    pub fn observe(&mut self, callback: impl Fn(&EngineEvent) + Send + Sync + 'static) {
+        self.observers.add(callback);
+    }
+
     pub fn start(&mut self) -> Result<(), GameError> {
         if self.players.len() < 2 {
             return Err(GameError::NotEnoughPlayers);
@@ -119,7 +378,10 @@ impl GameState {
         self.phase = GamePhase::Playing;
         self.round = 1;
         self.current_player_index = 0;
-        self.turn = Some(TurnState::new(self.current_player().id));
+        self.turn = Some(self.new_turn());
+        for player in &mut self.players {
+            player.time_bank = self.rules.time_bank_secs;
+        }
         Ok(())
     }
 
@@ -134,7 +396,7 @@ impl GameState {
         self.phase = GamePhase::Playing;
         self.round = 1;
         self.current_player_index = 0;
-        self.turn = Some(TurnState::new(self.current_player().id));
+        self.turn = Some(self.new_turn());
         Ok(())
     }
 
@@ -151,14 +413,97 @@ impl GameState {
     }
 
     pub fn roll_dice(&mut self, player_id: Uuid, rng: &mut impl Rng) -> Result<(), GameError> {
+        self.active_turn_mut(player_id)?.roll(rng)?;
+        self.report_roll();
+        self.after_roll();
+        Ok(())
+    }
+
+    /// Roll the current player's unheld dice to predetermined faces.
+    pub fn roll_dice_fixed(&mut self, player_id: Uuid, faces: [u8; 5]) -> Result<(), GameError> {
+        self.active_turn_mut(player_id)?.roll_fixed(faces)?;
+        self.report_roll();
+        self.after_roll();
+        Ok(())
+    }
+
+    fn report_roll(&self) {
+        let Some(turn) = &self.turn else {
+            return;
+        };
+        let dice = turn.dice.values();
+        self.observers.emit(EngineEvent::DiceRolled {
+            player_id: turn.player_id,
+            dice,
+            rolls_left: turn.rolls_remaining(),
+        });
+        if dice.iter().all(|&v| v == dice[0]) {
+            self.observers.emit(EngineEvent::YahtzeeRolled {
+                player_id: turn.player_id,
+                face: dice[0],
+            });
+        }
+    }
+
+    /// Withdraw a player from a game in progress. Their scorecard stays as
+    /// it is, their turns are skipped and they cannot win. If it was their
+    /// turn, play passes on; with fewer than two players left, the game ends.
+    pub fn forfeit(&mut self, player_id: Uuid) -> Result<(), GameError> {
+        if self.phase != GamePhase::Playing {
+            return Err(GameError::GameNotInProgress);
+        }
+        let player = self
+            .players
+            .iter_mut()
+            .find(|p| p.id == player_id && !p.forfeited)
+            .ok_or(GameError::NotInGame)?;
+        player.forfeited = true;
+        let was_current = self.is_current_player(player_id);
+
+        let mut contenders = None;
+        if let Some(sd) = &mut self.sudden_death {
+            sd.contenders.retain(|id| *id != player_id);
+            let round = sd.round;
+            sd.rolls.retain(|r| r.round != round || r.player_id != player_id);
+            contenders = Some(sd.contenders.len());
+        }
+
+        if self.active_players().count() < 2 || contenders.is_some_and(|n| n < 2) {
+            self.phase = GamePhase::Finished;
+            self.turn = None;
+            self.turn_deadline = None;
+        } else if was_current {
+            self.advance_turn();
+        }
+        Ok(())
+    }
+
+    /// Players who have not forfeited, in seat order.
+    pub fn active_players(&self) -> impl Iterator<Item = &Player> {
+        self.players.iter().filter(|p| !p.forfeited)
+    }
+
+    /// A playoff turn is a single roll, which ends it.
+    fn after_roll(&mut self) {
+        let (Some(sd), Some(turn)) = (self.sudden_death.as_mut(), self.turn.as_ref()) else {
+            return;
+        };
+        sd.rolls.push(PlayoffRoll {
+            round: sd.round,
+            player_id: turn.player_id,
+            dice: turn.dice.values(),
+        });
+        self.advance_turn();
+    }
+
+    fn active_turn_mut(&mut self, player_id: Uuid) -> Result<&mut TurnState, GameError> {
         if self.phase != GamePhase::Playing {
             return Err(GameError::GameNotInProgress);
         }
         if !self.is_current_player(player_id) {
             return Err(GameError::NotYourTurn);
         }
-        let turn = self.turn.as_mut().ok_or(GameError::NoActiveTurn)?;
-        turn.roll(rng)
+        self.turn.as_mut().ok_or(GameError::NoActiveTurn)
     }
 
     pub fn hold_dice(
@@ -193,49 +538,240 @@ impl GameState {
         }
 
         let dice_values = turn.dice.values();
-        let is_yahtzee = scoring::compute_score(Category::Yahtzee, &dice_values) == 50;
+        let scorecard = &self.current_player().scorecard;
+        if scorecard.is_category_used(category) {
+            return Err(GameError::CategoryAlreadyScored);
+        }
 
         // Yahtzee bonus: if dice are a Yahtzee AND the player already scored
-        // Yahtzee with 50, they get a 100-point bonus.
-        let joker_active = is_yahtzee
-            && self.current_player().scorecard.scores.get(&Category::Yahtzee) == Some(&50);
-
-        if joker_active {
-            self.current_player_mut().scorecard.add_yahtzee_bonus();
+        // Yahtzee with 50, they get the bonus and must place the Joker
+        // where the rules say.
+        let joker_active = scorecard.joker_active(&dice_values);
+        if joker_active && !scorecard.joker_categories(dice_values[0]).contains(&category) {
+            return Err(GameError::InvalidCategoryForJoker);
         }
 
         // Use Joker scoring when applicable (Full House/Straights score full value with Yahtzee)
-        let score = scoring::compute_score_joker(category, &dice_values, joker_active);
+        let score = scorecard.score_for(category, &dice_values);
+        if !scoring::is_possible_score(category, score) {
+            return Err(GameError::ImpossibleScore);
+        }
+        let had_upper_bonus = scorecard.upper_bonus() > 0;
         self.current_player_mut()
             .scorecard
             .record(category, score)
             .map_err(|_| GameError::CategoryAlreadyScored)?;
+        if joker_active {
+            self.current_player_mut().scorecard.add_yahtzee_bonus();
+        }
+
+        let turn = self.turn.take().ok_or(GameError::NoActiveTurn)?;
+        self.turn_history.push(TurnRecord {
+            player_id,
+            round: self.round,
+            rolls: turn.rolls,
+            category,
+            score,
+            yahtzee_bonus: joker_active,
+            duration_ms: None,
+        });
+
+        if !had_upper_bonus && self.current_player().scorecard.upper_bonus() > 0 {
+            self.observers.emit(EngineEvent::BonusAchieved {
+                player_id,
+                bonus: Bonus::UpperSection,
+            });
+        }
+        if joker_active {
+            self.observers.emit(EngineEvent::BonusAchieved {
+                player_id,
+                bonus: Bonus::Yahtzee,
+            });
+        }
 
         self.advance_turn();
         Ok(score)
     }
 
     fn advance_turn(&mut self) {
-        self.current_player_index += 1;
-        if self.current_player_index >= self.players.len() {
-            self.current_player_index = 0;
-            self.round += 1;
+        self.turn_deadline = None;
+        if let Some(sd) = &self.sudden_death {
+            let next = sd.contenders.iter().copied().find(|&id| !sd.has_rolled(id));
+            match next {
+                Some(id) => self.start_turn_for(id),
+                None => self.finish_or_playoff(sd.round_leaders()),
+            }
+            return;
+        }
+
+        // Skip anyone who has forfeited. Every pass through the seats
+        // starts a new round, so this ends even if everyone has.
+        loop {
+            self.current_player_index += 1;
+            if self.current_player_index >= self.players.len() {
+                self.current_player_index = 0;
+                self.round += 1;
+            }
+            if !self.current_player().forfeited || self.round > self.total_rounds {
+                break;
+            }
         }
         if self.round > self.total_rounds {
+            self.finish_or_playoff(self.score_leaders());
+        } else {
+            self.turn = Some(self.new_turn());
+        }
+    }
+
+    /// End the game, unless `leaders` are tied and the rules call for
+    /// (another) playoff round between them.
+    fn finish_or_playoff(&mut self, leaders: Vec<Uuid>) {
+        let played = self.sudden_death.as_ref().map_or(0, |sd| sd.round);
+        if leaders.len() < 2 || !self.rules.sudden_death || played >= MAX_PLAYOFF_ROUNDS {
             self.phase = GamePhase::Finished;
             self.turn = None;
+            return;
+        }
+        let first = leaders[0];
+        let sd = self.sudden_death.get_or_insert_with(|| SuddenDeath {
+            round: 0,
+            contenders: Vec::new(),
+            rolls: Vec::new(),
+        });
+        sd.round += 1;
+        sd.contenders = leaders;
+        self.start_turn_for(first);
+    }
+
+    fn start_turn_for(&mut self, player_id: Uuid) {
+        if let Some(index) = self.players.iter().position(|p| p.id == player_id) {
+            self.current_player_index = index;
+        }
+        self.turn = Some(self.new_turn());
+    }
+
+    fn new_turn(&self) -> TurnState {
+        let max_rolls = if self.sudden_death.is_some() {
+            1
         } else {
-            self.turn = Some(TurnState::new(self.current_player().id));
+            self.rules.rolls_per_turn
+        };
+        TurnState::new(self.current_player().id, max_rolls)
+    }
+
+    /// Active players with the highest grand total, in seat order.
+    fn score_leaders(&self) -> Vec<Uuid> {
+        let best = self
+            .active_players()
+            .map(|p| p.scorecard.grand_total())
+            .max()
+            .unwrap_or(0);
+        self.active_players()
+            .filter(|p| p.scorecard.grand_total() == best)
+            .map(|p| p.id)
+            .collect()
+    }
+
+    /// Set the current turn's deadline from the rules' turn timer, counting
+    /// from `now` (Unix seconds). Clears it when there is no timer or turn.
+    pub fn start_turn_timer(&mut self, now: i64) {
+        self.turn_deadline = match (self.rules.turn_seconds, &self.turn) {
+            (Some(secs), Some(_)) => Some(now + secs as i64),
+            _ => None,
+        };
+
+        // Charge the last turn to its player's time bank, then start the
+        // clock for whoever plays next
+        if let Some((player_id, since)) = self.time_bank_running.take() {
+            if let Some(player) = self.players.iter_mut().find(|p| p.id == player_id) {
+                let used = now.saturating_sub(since).clamp(0, u32::MAX as i64) as u32;
+                player.time_bank = player.time_bank.map(|left| left.saturating_sub(used));
+            }
+        }
+        if self.phase == GamePhase::Playing && self.turn.is_some() {
+            let current = self.current_player();
+            if current.time_bank.is_some() {
+                self.time_bank_running = Some((current.id, now));
+            }
         }
     }
 
-    pub fn winner(&self) -> Option<&Player> {
+    /// Seconds left on the current player's time bank at `now`, while it
+    /// is running.
+    pub fn time_bank_left(&self, now: i64) -> Option<u32> {
+        let (player_id, since) = self.time_bank_running?;
+        let player = self.players.iter().find(|p| p.id == player_id)?;
+        let used = now.saturating_sub(since).clamp(0, u32::MAX as i64) as u32;
+        Some(player.time_bank?.saturating_sub(used))
+    }
+
+    /// Everyone who finished first: one player, or several on a draw.
+    /// Empty until the game is over.
+    pub fn winners(&self) -> Vec<&Player> {
         if self.phase != GamePhase::Finished {
-            return None;
+            return Vec::new();
         }
+        let ids = match &self.sudden_death {
+            Some(sd) => sd.round_leaders(),
+            None => self.score_leaders(),
+        };
+        self.players.iter().filter(|p| ids.contains(&p.id)).collect()
+    }
+
+    /// The outright winner, or `None` on a draw or before the game is over.
+    pub fn winner(&self) -> Option<&Player> {
+        match self.winners().as_slice() {
+            [winner] => Some(winner),
+            _ => None,
+        }
+    }
+
+    pub fn is_draw(&self) -> bool {
+        self.winners().len() > 1
+    }
+
+    /// Each player's average timed turn in milliseconds, in seat order;
+    /// `None` for a player with no timed turns.
+    pub fn average_turn_ms(&self) -> Vec<Option<u64>> {
         self.players
             .iter()
-            .max_by_key(|p| p.scorecard.grand_total())
+            .map(|p| {
+                let times: Vec<u64> = self
+                    .turn_history
+                    .iter()
+                    .filter(|t| t.player_id == p.id)
+                    .filter_map(|t| t.duration_ms)
+                    .collect();
+                (!times.is_empty()).then(|| times.iter().sum::<u64>() / times.len() as u64)
+            })
+            .collect()
+    }
+
+    /// Each player's total after every round played so far, in seat order.
+    /// Rebuilt from the turn history, so bonuses count from the round they
+    /// were earned in.
+    pub fn round_totals(&self) -> Vec<Vec<u16>> {
+        let mut blank = Scorecard::new();
+        blank.rules = self.rules.scoring;
+        let mut cards = vec![blank; self.players.len()];
+        let mut totals = vec![Vec::new(); self.players.len()];
+        let rounds = self.turn_history.last().map_or(0, |t| t.round);
+        let mut turns = self.turn_history.iter().peekable();
+        for round in 1..=rounds {
+            while let Some(turn) = turns.next_if(|t| t.round == round) {
+                let Some(seat) = self.players.iter().position(|p| p.id == turn.player_id) else {
+                    continue;
+                };
+                let _ = cards[seat].record(turn.category, turn.score);
+                if turn.yahtzee_bonus {
+                    cards[seat].add_yahtzee_bonus();
+                }
+            }
+            for (seat_totals, card) in totals.iter_mut().zip(&cards) {
+                seat_totals.push(card.grand_total());
+            }
+        }
+        totals
     }
 
     pub fn snapshot(&self) -> GameStateSnapshot {
@@ -249,6 +785,8 @@ impl GameState {
                     name: p.name.clone(),
                     scorecard: p.scorecard.clone(),
                     connected: p.connected,
+                    forfeited: p.forfeited,
+                    time_bank: p.time_bank,
                 })
                 .collect(),
             current_player_index: self.current_player_index,
@@ -257,13 +795,90 @@ impl GameState {
             rolls_used: self.turn.as_ref().map(|t| t.rolls_used).unwrap_or(0),
             round: self.round,
             total_rounds: self.total_rounds,
+            turn_history: None,
+            rules: self.rules,
+            turn_deadline: self.turn_deadline,
+            sudden_death: self.sudden_death.clone(),
+        }
+    }
+
+    /// Like `snapshot()`, with the turn history included.
+    pub fn snapshot_with_history(&self) -> GameStateSnapshot {
+        GameStateSnapshot {
+            turn_history: Some(self.turn_history.clone()),
+            ..self.snapshot()
+        }
+    }
+
+    /// Borrowed view that serializes exactly like `snapshot()`, without
+    /// cloning player names or scorecards.
+    pub fn view(&self) -> GameStateView<'_> {
+        GameStateView {
+            phase: &self.phase,
+            players: &self.players,
+            current_player_index: self.current_player_index,
+            dice: self.turn.as_ref().map(|t| t.dice),
+            turn_phase: self.turn.as_ref().map(|t| &t.phase),
+            rolls_used: self.turn.as_ref().map(|t| t.rolls_used).unwrap_or(0),
+            round: self.round,
+            total_rounds: self.total_rounds,
+            turn_history: None,
+            rules: self.rules,
+            turn_deadline: self.turn_deadline,
+            sudden_death: self.sudden_death.as_ref(),
         }
     }
+
+    /// Like `view()`, with the turn history included.
+    pub fn view_with_history(&self) -> GameStateView<'_> {
+        GameStateView {
+            turn_history: Some(&self.turn_history),
+            ..self.view()
+        }
+    }
+
+    /// Fingerprint of the scorecards, round and current player, matching
+    /// `GameStateSnapshot::checksum` for a snapshot of this state.
+    pub fn checksum(&self) -> u64 {
+        let current = self.players.get(self.current_player_index).map(|p| p.id);
+        state_checksum(self.round, current, self.players.iter().map(|p| (p.id, &p.scorecard)))
+    }
+}
+
+/// FNV-1a over the parts of a game every client follows move by move, so
+/// a client and the server can tell whether they still agree.
+fn state_checksum<'a>(
+    round: u8,
+    current: Option<Uuid>,
+    players: impl Iterator<Item = (Uuid, &'a Scorecard)>,
+) -> u64 {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let mut hash = OFFSET;
+    let mut feed = |bytes: &[u8]| {
+        for &b in bytes {
+            hash ^= u64::from(b);
+            hash = hash.wrapping_mul(PRIME);
+        }
+    };
+    feed(&[round]);
+    feed(current.unwrap_or_default().as_bytes());
+    for (id, card) in players {
+        feed(id.as_bytes());
+        for &category in Category::ALL.iter() {
+            // An unused category hashes apart from any score
+            feed(&card.score(category).unwrap_or(u16::MAX).to_le_bytes());
+        }
+        feed(&[card.yahtzee_bonus_count]);
+    }
+    hash
 }
 
 // -- Snapshot (sent over the network) --
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct GameStateSnapshot {
     pub phase: GamePhase,
     pub players: Vec<PlayerSnapshot>,
@@ -273,14 +888,86 @@ pub struct GameStateSnapshot {
     pub rolls_used: u8,
     pub round: u8,
     pub total_rounds: u8,
+    /// Completed turns, when the sender chose to include them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub turn_history: Option<Vec<TurnRecord>>,
+    #[serde(default)]
+    pub rules: GameConfig,
+    /// When the current turn runs out (Unix seconds), if there is a turn timer.
+    #[serde(default)]
+    pub turn_deadline: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sudden_death: Option<SuddenDeath>,
+}
+
+impl GameStateSnapshot {
+    pub fn rolls_remaining(&self) -> u8 {
+        self.rules.rolls_per_turn.saturating_sub(self.rolls_used)
+    }
+
+    /// Fingerprint of the scorecards, round and current player, as sent
+    /// with `ClientMessage::AckTurn`.
+    pub fn checksum(&self) -> u64 {
+        let current = self.players.get(self.current_player_index).map(|p| p.id);
+        state_checksum(self.round, current, self.players.iter().map(|p| (p.id, &p.scorecard)))
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PlayerSnapshot {
     pub id: Uuid,
     pub name: String,
     pub scorecard: Scorecard,
     pub connected: bool,
+    #[serde(default)]
+    pub forfeited: bool,
+    /// Seconds left on the player's time bank as of the start of the
+    /// current turn, if the game has one.
+    #[serde(default)]
+    pub time_bank: Option<u32>,
+}
+
+/// Serialize-only counterpart of `GameStateSnapshot` borrowing from a `GameState`.
+#[derive(Debug, Serialize)]
+pub struct GameStateView<'a> {
+    pub phase: &'a GamePhase,
+    #[serde(serialize_with = "serialize_player_views")]
+    pub players: &'a [Player],
+    pub current_player_index: usize,
+    pub dice: Option<DiceSet>,
+    pub turn_phase: Option<&'a TurnPhase>,
+    pub rolls_used: u8,
+    pub round: u8,
+    pub total_rounds: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub turn_history: Option<&'a [TurnRecord]>,
+    pub rules: GameConfig,
+    pub turn_deadline: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sudden_death: Option<&'a SuddenDeath>,
+}
+
+/// Borrowed counterpart of `PlayerSnapshot`.
+#[derive(Serialize)]
+struct PlayerView<'a> {
+    id: Uuid,
+    name: &'a str,
+    scorecard: &'a Scorecard,
+    connected: bool,
+    forfeited: bool,
+    time_bank: Option<u32>,
+}
+
+fn serialize_player_views<S: Serializer>(players: &&[Player], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(players.iter().map(|p| PlayerView {
+        id: p.id,
+        name: &p.name,
+        scorecard: &p.scorecard,
+        connected: p.connected,
+        forfeited: p.forfeited,
+        time_bank: p.time_bank,
+    }))
 }
 
 // -- Errors --
@@ -297,6 +984,9 @@ pub enum GameError {
     NoActiveTurn,
     #[error("category already scored")]
     CategoryAlreadyScored,
+    /// A Joker went somewhere other than the boxes the rules force.
+    #[error("a Joker must go in the matching upper box, then the lower section")]
+    InvalidCategoryForJoker,
     #[error("not enough players (need 2-6)")]
     NotEnoughPlayers,
     #[error("too many players (max 6)")]
@@ -305,11 +995,18 @@ pub enum GameError {
     NotYourTurn,
     #[error("game not in progress")]
     GameNotInProgress,
+    #[error("not an active player in this game")]
+    NotInGame,
+    /// The dice produced a score the category cannot hold, e.g. after
+    /// they were edited by hand.
+    #[error("impossible score")]
+    ImpossibleScore,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::scoring::JokerRule;
     use rand::SeedableRng;
 
     fn make_players(n: usize) -> Vec<Player> {
@@ -320,19 +1017,19 @@ mod tests {
 
     #[test]
     fn test_game_start_requires_min_players() {
-        let mut game = GameState::new(vec![Player::new(Uuid::new_v4(), "Solo".into())]);
+        let mut game = GameState::new(vec![Player::new(Uuid::new_v4(), "Solo".into())], GameConfig::default());
         assert!(matches!(game.start(), Err(GameError::NotEnoughPlayers)));
     }
 
     #[test]
     fn test_game_start_max_players() {
-        let mut game = GameState::new(make_players(7));
+        let mut game = GameState::new(make_players(7), GameConfig::default());
         assert!(matches!(game.start(), Err(GameError::TooManyPlayers)));
     }
 
     #[test]
     fn test_game_start_success() {
-        let mut game = GameState::new(make_players(2));
+        let mut game = GameState::new(make_players(2), GameConfig::default());
         assert!(game.start().is_ok());
         assert_eq!(game.phase, GamePhase::Playing);
         assert_eq!(game.round, 1);
@@ -344,7 +1041,7 @@ mod tests {
         let mut rng = rand::rngs::StdRng::seed_from_u64(42);
         let players = make_players(2);
         let p1_id = players[0].id;
-        let mut game = GameState::new(players);
+        let mut game = GameState::new(players, GameConfig::default());
         game.start().unwrap();
 
         // Player 1 rolls
@@ -364,7 +1061,7 @@ mod tests {
         let mut rng = rand::rngs::StdRng::seed_from_u64(42);
         let players = make_players(2);
         let p2_id = players[1].id;
-        let mut game = GameState::new(players);
+        let mut game = GameState::new(players, GameConfig::default());
         game.start().unwrap();
 
         assert!(matches!(
@@ -377,7 +1074,7 @@ mod tests {
     fn test_cannot_score_before_rolling() {
         let players = make_players(2);
         let p1_id = players[0].id;
-        let mut game = GameState::new(players);
+        let mut game = GameState::new(players, GameConfig::default());
         game.start().unwrap();
 
         assert!(matches!(
@@ -391,7 +1088,7 @@ mod tests {
         let mut rng = rand::rngs::StdRng::seed_from_u64(42);
         let players = make_players(2);
         let p1_id = players[0].id;
-        let mut game = GameState::new(players);
+        let mut game = GameState::new(players, GameConfig::default());
         game.start().unwrap();
 
         game.roll_dice(p1_id, &mut rng).unwrap();
@@ -415,7 +1112,7 @@ mod tests {
     fn test_hold_before_roll_fails() {
         let players = make_players(2);
         let p1_id = players[0].id;
-        let mut game = GameState::new(players);
+        let mut game = GameState::new(players, GameConfig::default());
         game.start().unwrap();
 
         assert!(matches!(
@@ -425,11 +1122,12 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::needless_range_loop)]
     fn test_full_game_two_players() {
         let mut rng = rand::rngs::StdRng::seed_from_u64(123);
         let players = make_players(2);
         let ids: Vec<Uuid> = players.iter().map(|p| p.id).collect();
-        let mut game = GameState::new(players);
+        let mut game = GameState::new(players, GameConfig::default());
         game.start().unwrap();
 
         let categories = Category::ALL;
@@ -451,10 +1149,411 @@ mod tests {
         assert!(game.winner().is_some());
     }
 
+    #[test]
+    fn test_view_serializes_like_snapshot() {
+        let players = make_players(2);
+        let pid = players[0].id;
+        let mut game = GameState::new(players, GameConfig::default());
+        game.start().unwrap();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        game.roll_dice(pid, &mut rng).unwrap();
+        game.score_category(pid, Category::Chance).unwrap();
+
+        let from_view = serde_json::to_value(game.view()).unwrap();
+        let from_snapshot = serde_json::to_value(game.snapshot()).unwrap();
+        assert_eq!(from_view, from_snapshot);
+
+        let bytes = serde_json::to_vec(&game.view()).unwrap();
+        let parsed: GameStateSnapshot = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(parsed.players[0].scorecard.grand_total(), game.players[0].scorecard.grand_total());
+        assert!(parsed.players[0].scorecard.is_category_used(Category::Chance));
+    }
+
+    #[test]
+    fn test_turn_history_records_rolls_and_score() {
+        let players = make_players(2);
+        let p1_id = players[0].id;
+        let p2_id = players[1].id;
+        let mut game = GameState::new(players, GameConfig::default());
+        game.start().unwrap();
+
+        game.roll_dice_fixed(p1_id, [3, 3, 5, 3, 2]).unwrap();
+        game.hold_dice(p1_id, [true, true, false, true, false]).unwrap();
+        game.roll_dice_fixed(p1_id, [6, 6, 3, 6, 3]).unwrap();
+        game.score_category(p1_id, Category::Yahtzee).unwrap();
+
+        game.roll_dice_fixed(p2_id, [1, 2, 3, 4, 6]).unwrap();
+        game.score_category(p2_id, Category::Chance).unwrap();
+
+        assert_eq!(game.turn_history.len(), 2);
+        let first = &game.turn_history[0];
+        assert_eq!(first.player_id, p1_id);
+        assert_eq!(first.round, 1);
+        assert_eq!(
+            first.rolls,
+            vec![
+                RollRecord { held: [false; 5], dice: [3, 3, 5, 3, 2] },
+                RollRecord { held: [true, true, false, true, false], dice: [3, 3, 3, 3, 3] },
+            ]
+        );
+        assert_eq!(first.category, Category::Yahtzee);
+        assert_eq!(first.score, 50);
+        assert_eq!(first.final_dice(), Some([3, 3, 3, 3, 3]));
+
+        let second = &game.turn_history[1];
+        assert_eq!(second.player_id, p2_id);
+        assert_eq!(second.rolls.len(), 1);
+        assert_eq!(second.score, 16);
+
+        // The next turn starts with no rolls of its own
+        assert!(game.turn.as_ref().unwrap().rolls.is_empty());
+    }
+
+    #[test]
+    fn test_round_totals() {
+        let players = make_players(2);
+        let p1_id = players[0].id;
+        let p2_id = players[1].id;
+        let mut game = GameState::new(players, GameConfig::default());
+        game.start().unwrap();
+        assert!(game.round_totals().iter().all(Vec::is_empty));
+
+        game.roll_dice_fixed(p1_id, [6, 6, 6, 6, 6]).unwrap();
+        game.score_category(p1_id, Category::Sixes).unwrap();
+        game.roll_dice_fixed(p2_id, [1, 2, 3, 4, 6]).unwrap();
+        game.score_category(p2_id, Category::Chance).unwrap();
+        game.roll_dice_fixed(p1_id, [5, 5, 5, 5, 5]).unwrap();
+        game.score_category(p1_id, Category::Fives).unwrap();
+        // Round 2 is still in progress for the second player
+        assert_eq!(game.round_totals(), vec![vec![30, 55], vec![16, 16]]);
+
+        game.roll_dice_fixed(p2_id, [4, 4, 4, 4, 4]).unwrap();
+        game.score_category(p2_id, Category::Fours).unwrap();
+        game.roll_dice_fixed(p1_id, [4, 4, 4, 4, 4]).unwrap();
+        game.score_category(p1_id, Category::Fours).unwrap();
+        // 30 + 25 + 20 passes the upper bonus threshold
+        assert_eq!(game.round_totals(), vec![vec![30, 55, 110], vec![16, 36, 36]]);
+        assert_eq!(game.round_totals()[0][2], game.players[0].scorecard.grand_total());
+    }
+
+    #[test]
+    fn test_round_totals_follow_the_scoring_rules() {
+        let players = make_players(2);
+        let (p1_id, p2_id) = (players[0].id, players[1].id);
+        let rules = GameConfig {
+            scoring: ScoringRules {
+                upper_bonus_threshold: 20,
+                upper_bonus: 7,
+                ..ScoringRules::default()
+            },
+            ..GameConfig::default()
+        };
+        let mut game = GameState::new(players, rules);
+        game.start().unwrap();
+        game.roll_dice_fixed(p1_id, [6, 6, 6, 6, 6]).unwrap();
+        game.score_category(p1_id, Category::Sixes).unwrap();
+        game.roll_dice_fixed(p2_id, [1, 1, 1, 2, 2]).unwrap();
+        game.score_category(p2_id, Category::Ones).unwrap();
+        assert_eq!(game.round_totals(), vec![vec![37], vec![3]]);
+        assert_eq!(game.round_totals()[0][0], game.players[0].scorecard.grand_total());
+    }
+
+    #[test]
+    fn test_average_turn_ms() {
+        let players = make_players(3);
+        let (p1_id, p2_id) = (players[0].id, players[1].id);
+        let mut game = GameState::new(players, GameConfig::default());
+        game.start().unwrap();
+        assert_eq!(game.average_turn_ms(), [None, None, None]);
+
+        game.roll_dice_fixed(p1_id, [6, 6, 6, 6, 6]).unwrap();
+        game.score_category(p1_id, Category::Sixes).unwrap();
+        game.turn_history.last_mut().unwrap().duration_ms = Some(4_000);
+        // An untimed turn does not count towards the average
+        game.roll_dice_fixed(p2_id, [1, 2, 3, 4, 6]).unwrap();
+        game.score_category(p2_id, Category::Chance).unwrap();
+        assert_eq!(game.average_turn_ms(), [Some(4_000), None, None]);
+
+        let p3_id = game.current_player().id;
+        game.roll_dice_fixed(p3_id, [1, 1, 2, 2, 2]).unwrap();
+        game.score_category(p3_id, Category::FullHouse).unwrap();
+        game.roll_dice_fixed(p1_id, [5, 5, 5, 5, 5]).unwrap();
+        game.score_category(p1_id, Category::Fives).unwrap();
+        game.turn_history.last_mut().unwrap().duration_ms = Some(9_000);
+        assert_eq!(game.average_turn_ms()[0], Some(6_500));
+    }
+
+    #[test]
+    fn test_snapshot_history_is_optional() {
+        let players = make_players(2);
+        let p1_id = players[0].id;
+        let mut game = GameState::new(players, GameConfig::default());
+        game.start().unwrap();
+        game.roll_dice_fixed(p1_id, [1, 1, 1, 2, 2]).unwrap();
+        game.score_category(p1_id, Category::FullHouse).unwrap();
+
+        let plain = serde_json::to_value(game.snapshot()).unwrap();
+        assert!(plain.get("turn_history").is_none());
+        assert_eq!(plain, serde_json::to_value(game.view()).unwrap());
+
+        let full = serde_json::to_value(game.snapshot_with_history()).unwrap();
+        assert_eq!(full, serde_json::to_value(game.view_with_history()).unwrap());
+        let parsed: GameStateSnapshot = serde_json::from_value(full).unwrap();
+        assert_eq!(parsed.turn_history, Some(game.turn_history.clone()));
+
+        // Snapshots from senders that never include history still parse
+        let parsed: GameStateSnapshot = serde_json::from_value(plain).unwrap();
+        assert!(parsed.turn_history.is_none());
+    }
+
+    #[test]
+    fn test_rolls_per_turn_rule() {
+        let players = make_players(2);
+        let p1_id = players[0].id;
+        let rules = GameConfig {
+            rolls_per_turn: 1,
+            ..GameConfig::default()
+        };
+        let mut game = GameState::new(players, rules);
+        game.start().unwrap();
+
+        game.roll_dice_fixed(p1_id, [1, 2, 3, 4, 5]).unwrap();
+        assert_eq!(game.turn.as_ref().unwrap().phase, TurnPhase::MustScore);
+        assert!(matches!(
+            game.roll_dice_fixed(p1_id, [1, 2, 3, 4, 5]),
+            Err(GameError::CannotRoll)
+        ));
+        assert_eq!(game.snapshot().rolls_remaining(), 0);
+        assert_eq!(game.snapshot().rules.rolls_per_turn, 1);
+    }
+
+    #[test]
+    fn test_turn_deadline_follows_timer() {
+        let players = make_players(2);
+        let p1_id = players[0].id;
+        let mut untimed = GameState::new(players.clone(), GameConfig::default());
+        untimed.start().unwrap();
+        untimed.start_turn_timer(1_000);
+        assert_eq!(untimed.snapshot().turn_deadline, None);
+
+        let rules = GameConfig {
+            turn_seconds: Some(45),
+            ..GameConfig::default()
+        };
+        let mut game = GameState::new(players, rules);
+        game.start().unwrap();
+        game.start_turn_timer(1_000);
+        assert_eq!(game.snapshot().turn_deadline, Some(1_045));
+
+        // Scoring ends the turn, and its deadline with it
+        game.roll_dice_fixed(p1_id, [1, 2, 3, 4, 5]).unwrap();
+        game.score_category(p1_id, Category::Chance).unwrap();
+        assert_eq!(game.turn_deadline, None);
+        game.start_turn_timer(1_030);
+        assert_eq!(game.turn_deadline, Some(1_075));
+
+        let json = serde_json::to_string(&game.view()).unwrap();
+        let parsed: GameStateSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.rules, rules);
+        assert_eq!(parsed.turn_deadline, Some(1_075));
+    }
+
+    #[test]
+    fn test_pace_presets() {
+        let blitz = Pace::Blitz.apply(GameConfig {
+            sudden_death: true,
+            ..GameConfig::default()
+        });
+        assert_eq!(blitz.turn_seconds, Some(20));
+        assert!(blitz.sudden_death);
+        assert_eq!(Pace::of(&blitz), Some(Pace::Blitz));
+
+        // Changing the timer by hand leaves the preset behind
+        let custom = GameConfig {
+            turn_seconds: Some(60),
+            ..blitz
+        };
+        assert_eq!(Pace::of(&custom), None);
+        assert_eq!(Pace::of(&Pace::Casual.apply(custom)), Some(Pace::Casual));
+        assert_eq!(Pace::of(&GameConfig::default()), None);
+    }
+
+    #[test]
+    fn test_time_bank_charges_each_turn() {
+        let players = make_players(2);
+        let (p1_id, p2_id) = (players[0].id, players[1].id);
+        let rules = GameConfig {
+            time_bank_secs: Some(600),
+            ..GameConfig::default()
+        };
+        let mut game = GameState::new(players, rules);
+        game.start().unwrap();
+        game.start_turn_timer(1_000);
+        assert_eq!(game.time_bank_left(1_040), Some(560));
+
+        // The turn's 45 seconds come off the first player's bank only
+        game.roll_dice_fixed(p1_id, [1, 2, 3, 4, 5]).unwrap();
+        game.score_category(p1_id, Category::Chance).unwrap();
+        game.start_turn_timer(1_045);
+        assert_eq!(game.players[0].time_bank, Some(555));
+        assert_eq!(game.players[1].time_bank, Some(600));
+        assert_eq!(game.time_bank_running, Some((p2_id, 1_045)));
+        assert_eq!(game.time_bank_left(2_000), Some(0));
+
+        let json = serde_json::to_string(&game.view()).unwrap();
+        let parsed: GameStateSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.players[0].time_bank, Some(555));
+        assert_eq!(parsed.rules.out_of_time, OutOfTime::AutoScore);
+
+        // Without a time bank, nothing runs
+        let mut untimed = GameState::new(make_players(2), GameConfig::default());
+        untimed.start().unwrap();
+        untimed.start_turn_timer(1_000);
+        assert_eq!(untimed.time_bank_left(1_000), None);
+    }
+
+    /// Two players who roll and score identically through all 13 rounds.
+    fn tied_game(rules: GameConfig) -> (GameState, Uuid, Uuid) {
+        let players = make_players(2);
+        let (p1_id, p2_id) = (players[0].id, players[1].id);
+        let mut game = GameState::new(players, rules);
+        game.start().unwrap();
+        for cat in Category::ALL {
+            for pid in [p1_id, p2_id] {
+                game.roll_dice_fixed(pid, [2, 2, 3, 3, 3]).unwrap();
+                game.score_category(pid, cat).unwrap();
+            }
+        }
+        (game, p1_id, p2_id)
+    }
+
+    #[test]
+    fn test_tie_is_a_draw() {
+        let (game, p1_id, p2_id) = tied_game(GameConfig::default());
+        assert_eq!(game.phase, GamePhase::Finished);
+        let winners: Vec<Uuid> = game.winners().iter().map(|p| p.id).collect();
+        assert_eq!(winners, vec![p1_id, p2_id]);
+        assert!(game.winner().is_none());
+        assert!(game.is_draw());
+    }
+
+    #[test]
+    fn test_sudden_death_breaks_tie() {
+        let rules = GameConfig {
+            sudden_death: true,
+            ..GameConfig::default()
+        };
+        let (mut game, p1_id, p2_id) = tied_game(rules);
+        assert_eq!(game.phase, GamePhase::Playing);
+        assert!(game.winners().is_empty());
+        let sd = game.sudden_death.clone().unwrap();
+        assert_eq!(sd.round, 1);
+        assert_eq!(sd.contenders, vec![p1_id, p2_id]);
+        assert!(game.is_current_player(p1_id));
+
+        // Playoff turns are a single roll; there is nothing to score
+        assert!(matches!(
+            game.score_category(p1_id, Category::Chance),
+            Err(GameError::CannotScore)
+        ));
+
+        // A tied playoff round goes again
+        game.roll_dice_fixed(p1_id, [4, 4, 4, 4, 4]).unwrap();
+        assert!(game.is_current_player(p2_id));
+        game.roll_dice_fixed(p2_id, [2, 6, 4, 4, 4]).unwrap();
+        assert_eq!(game.phase, GamePhase::Playing);
+        assert_eq!(game.sudden_death.as_ref().unwrap().round, 2);
+        assert!(game.is_current_player(p1_id));
+
+        game.roll_dice_fixed(p1_id, [1, 1, 2, 2, 3]).unwrap();
+        game.roll_dice_fixed(p2_id, [6, 6, 5, 5, 4]).unwrap();
+        assert_eq!(game.phase, GamePhase::Finished);
+        assert_eq!(game.winner().map(|p| p.id), Some(p2_id));
+        assert_eq!(game.sudden_death.as_ref().unwrap().rolls.len(), 4);
+        // The playoff does not touch the scorecards
+        assert_eq!(
+            game.players[0].scorecard.grand_total(),
+            game.players[1].scorecard.grand_total()
+        );
+    }
+
+    #[test]
+    fn test_sudden_death_gives_up_after_max_rounds() {
+        let rules = GameConfig {
+            sudden_death: true,
+            ..GameConfig::default()
+        };
+        let (mut game, p1_id, p2_id) = tied_game(rules);
+        for _ in 0..MAX_PLAYOFF_ROUNDS {
+            game.roll_dice_fixed(p1_id, [5, 5, 5, 5, 5]).unwrap();
+            game.roll_dice_fixed(p2_id, [5, 5, 5, 5, 5]).unwrap();
+        }
+        assert_eq!(game.phase, GamePhase::Finished);
+        assert!(game.is_draw());
+    }
+
+    #[test]
+    fn test_forfeit_skips_player() {
+        let players = make_players(3);
+        let ids: Vec<Uuid> = players.iter().map(|p| p.id).collect();
+        let mut game = GameState::new(players, GameConfig::default());
+        game.start().unwrap();
+
+        // Forfeiting on your own turn passes it on
+        game.forfeit(ids[0]).unwrap();
+        assert_eq!(game.phase, GamePhase::Playing);
+        assert!(game.is_current_player(ids[1]));
+        assert_eq!(game.active_players().count(), 2);
+
+        game.roll_dice_fixed(ids[1], [1, 2, 3, 4, 5]).unwrap();
+        game.score_category(ids[1], Category::Chance).unwrap();
+        game.roll_dice_fixed(ids[2], [1, 2, 3, 4, 5]).unwrap();
+        game.score_category(ids[2], Category::Chance).unwrap();
+
+        // The next round skips the forfeited seat
+        assert_eq!(game.round, 2);
+        assert!(game.is_current_player(ids[1]));
+        assert!(game.snapshot().players[0].forfeited);
+
+        assert!(matches!(game.forfeit(ids[0]), Err(GameError::NotInGame)));
+        assert!(matches!(game.forfeit(Uuid::new_v4()), Err(GameError::NotInGame)));
+    }
+
+    #[test]
+    fn test_forfeit_down_to_one_player_ends_game() {
+        let players = make_players(2);
+        let (p1_id, p2_id) = (players[0].id, players[1].id);
+        let mut game = GameState::new(players, GameConfig::default());
+        game.start().unwrap();
+        game.roll_dice_fixed(p1_id, [6, 6, 6, 6, 6]).unwrap();
+        game.score_category(p1_id, Category::Yahtzee).unwrap();
+
+        // The leader walks out while it is not their turn
+        game.forfeit(p1_id).unwrap();
+        assert_eq!(game.phase, GamePhase::Finished);
+        assert!(game.turn.is_none());
+        assert_eq!(game.winner().map(|p| p.id), Some(p2_id));
+
+        assert!(matches!(game.forfeit(p2_id), Err(GameError::GameNotInProgress)));
+    }
+
+    #[test]
+    fn test_forfeit_during_sudden_death() {
+        let rules = GameConfig {
+            sudden_death: true,
+            ..GameConfig::default()
+        };
+        let (mut game, p1_id, p2_id) = tied_game(rules);
+        game.roll_dice_fixed(p1_id, [6, 6, 6, 6, 6]).unwrap();
+        game.forfeit(p1_id).unwrap();
+        assert_eq!(game.phase, GamePhase::Finished);
+        assert_eq!(game.winner().map(|p| p.id), Some(p2_id));
+    }
+
     #[test]
     fn test_snapshot_round_trip() {
         let players = make_players(3);
-        let game = GameState::new(players);
+        let game = GameState::new(players, GameConfig::default());
         let snap = game.snapshot();
 
         let json = serde_json::to_string(&snap).unwrap();
@@ -465,14 +1564,14 @@ mod tests {
 
     #[test]
     fn test_solo_start_with_one_player() {
-        let mut game = GameState::new(make_players(1));
+        let mut game = GameState::new(make_players(1), GameConfig::default());
         assert!(game.start_solo().is_ok());
         assert_eq!(game.phase, GamePhase::Playing);
     }
 
     #[test]
     fn test_solo_start_with_multiple_players() {
-        let mut game = GameState::new(make_players(3));
+        let mut game = GameState::new(make_players(3), GameConfig::default());
         assert!(game.start_solo().is_ok());
         assert_eq!(game.phase, GamePhase::Playing);
         assert_eq!(game.players.len(), 3);
@@ -483,7 +1582,7 @@ mod tests {
         let mut rng = rand::rngs::StdRng::seed_from_u64(42);
         let players = make_players(2);
         let p1_id = players[0].id;
-        let mut game = GameState::new(players);
+        let mut game = GameState::new(players, GameConfig::default());
         game.start().unwrap();
 
         // Roll first
@@ -505,13 +1604,36 @@ mod tests {
         assert_eq!(turn.dice.dice[2].value, held_val_2);
     }
 
+    #[test]
+    fn test_roll_fixed_keeps_held_dice() {
+        let players = make_players(2);
+        let p1_id = players[0].id;
+        let mut game = GameState::new(players, GameConfig::default());
+        game.start().unwrap();
+
+        game.roll_dice_fixed(p1_id, [3, 3, 5, 3, 2]).unwrap();
+        game.hold_dice(p1_id, [true, true, false, true, false]).unwrap();
+        game.roll_dice_fixed(p1_id, [6, 6, 3, 6, 3]).unwrap();
+
+        let turn = game.turn.as_ref().unwrap();
+        assert_eq!(turn.dice.values(), [3, 3, 3, 3, 3]);
+        assert_eq!(turn.rolls_used, 2);
+
+        // Other players cannot roll out of turn
+        let p2_id = game.players[1].id;
+        assert!(matches!(
+            game.roll_dice_fixed(p2_id, [1, 1, 1, 1, 1]),
+            Err(GameError::NotYourTurn)
+        ));
+    }
+
     #[test]
     fn test_category_already_scored() {
         let mut rng = rand::rngs::StdRng::seed_from_u64(42);
         let players = make_players(2);
         let p1_id = players[0].id;
         let p2_id = players[1].id;
-        let mut game = GameState::new(players);
+        let mut game = GameState::new(players, GameConfig::default());
         game.start().unwrap();
 
         // P1 rolls and scores Chance
@@ -536,7 +1658,7 @@ mod tests {
         let players = make_players(2);
         let p1_id = players[0].id;
         let p2_id = players[1].id;
-        let mut game = GameState::new(players);
+        let mut game = GameState::new(players, GameConfig::default());
         game.start().unwrap();
 
         // Manually set dice to all 5s for yahtzee
@@ -567,11 +1689,32 @@ mod tests {
     }
 
     #[test]
+    fn test_impossible_score_is_not_recorded() {
+        let players = make_players(2);
+        let p1_id = players[0].id;
+        let mut game = GameState::new(players, GameConfig::default());
+        game.start().unwrap();
+        game.roll_dice_fixed(p1_id, [1, 2, 3, 4, 5]).unwrap();
+        for die in &mut game.turn.as_mut().unwrap().dice.dice {
+            die.value = 0;
+        }
+
+        assert!(matches!(
+            game.score_category(p1_id, Category::Chance),
+            Err(GameError::ImpossibleScore)
+        ));
+        assert!(!game.players[0].scorecard.is_category_used(Category::Chance));
+        assert!(game.is_current_player(p1_id));
+        assert!(game.turn_history.is_empty());
+    }
+
+    #[test]
+    #[allow(clippy::needless_range_loop)]
     fn test_full_game_six_players() {
         let mut rng = rand::rngs::StdRng::seed_from_u64(777);
         let players = make_players(6);
         let ids: Vec<Uuid> = players.iter().map(|p| p.id).collect();
-        let mut game = GameState::new(players);
+        let mut game = GameState::new(players, GameConfig::default());
         game.start().unwrap();
 
         let categories = Category::ALL;
@@ -598,7 +1741,7 @@ mod tests {
         let mut rng = rand::rngs::StdRng::seed_from_u64(42);
         let players = make_players(2);
         let p1_id = players[0].id;
-        let game = GameState::new(players);
+        let game = GameState::new(players, GameConfig::default());
 
         // Game is in Lobby phase, should fail
         let mut game_clone = game.clone();
@@ -607,4 +1750,296 @@ mod tests {
             Err(GameError::GameNotInProgress)
         ));
     }
+
+    fn record_events(game: &mut GameState) -> std::sync::Arc<std::sync::Mutex<Vec<EngineEvent>>> {
+        let events = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = events.clone();
+        game.observe(move |event| sink.lock().unwrap().push(event.clone()));
+        events
+    }
+
+    #[test]
+    fn test_observers_see_rolls_and_yahtzees() {
+        let players = make_players(2);
+        let p1_id = players[0].id;
+        let mut game = GameState::new(players, GameConfig::default());
+        game.start().unwrap();
+        let events = record_events(&mut game);
+
+        game.roll_dice_fixed(p1_id, [1, 2, 3, 4, 5]).unwrap();
+        game.hold_dice(p1_id, [false; 5]).unwrap();
+        game.roll_dice_fixed(p1_id, [6, 6, 6, 6, 6]).unwrap();
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            [
+                EngineEvent::DiceRolled { player_id: p1_id, dice: [1, 2, 3, 4, 5], rolls_left: 2 },
+                EngineEvent::DiceRolled { player_id: p1_id, dice: [6, 6, 6, 6, 6], rolls_left: 1 },
+                EngineEvent::YahtzeeRolled { player_id: p1_id, face: 6 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_observers_see_bonuses_once() {
+        let players = make_players(2);
+        let p1_id = players[0].id;
+        let p2_id = players[1].id;
+        let mut game = GameState::new(players, GameConfig::default());
+        game.start().unwrap();
+        let card = &mut game.players[0].scorecard;
+        card.record(Category::Yahtzee, 50).unwrap();
+        card.record(Category::Fours, 16).unwrap();
+        card.record(Category::Fives, 20).unwrap();
+        card.record(Category::Sixes, 24).unwrap();
+        let events = record_events(&mut game);
+
+        // Three threes bring the upper section to 69
+        game.roll_dice_fixed(p1_id, [3, 3, 3, 1, 2]).unwrap();
+        game.score_category(p1_id, Category::Threes).unwrap();
+        game.roll_dice_fixed(p2_id, [1, 2, 3, 4, 6]).unwrap();
+        game.score_category(p2_id, Category::Chance).unwrap();
+        // A second Yahtzee, already past the threshold
+        game.roll_dice_fixed(p1_id, [2, 2, 2, 2, 2]).unwrap();
+        game.score_category(p1_id, Category::Twos).unwrap();
+
+        let bonuses: Vec<_> = events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|e| match e {
+                EngineEvent::BonusAchieved { player_id, bonus } => Some((*player_id, *bonus)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(bonuses, [(p1_id, Bonus::UpperSection), (p1_id, Bonus::Yahtzee)]);
+    }
+
+    #[test]
+    fn test_clones_do_not_report() {
+        let players = make_players(2);
+        let p1_id = players[0].id;
+        let mut game = GameState::new(players, GameConfig::default());
+        game.start().unwrap();
+        let events = record_events(&mut game);
+
+        let mut copy = game.clone();
+        assert!(copy.observers.is_empty());
+        copy.roll_dice_fixed(p1_id, [1, 1, 1, 1, 1]).unwrap();
+        assert!(events.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_used_category_reported_before_score_checks() {
+        let players = make_players(2);
+        let p1_id = players[0].id;
+        let mut game = GameState::new(players, GameConfig::default());
+        game.start().unwrap();
+        game.players[0].scorecard.record(Category::Chance, 20).unwrap();
+        game.roll_dice_fixed(p1_id, [1, 2, 3, 4, 5]).unwrap();
+        for die in &mut game.turn.as_mut().unwrap().dice.dice {
+            die.value = 0;
+        }
+
+        assert!(matches!(
+            game.score_category(p1_id, Category::Chance),
+            Err(GameError::CategoryAlreadyScored)
+        ));
+    }
+
+    #[test]
+    fn test_joker_goes_in_matching_upper_box_first() {
+        let players = make_players(2);
+        let p1_id = players[0].id;
+        let mut game = GameState::new(players, GameConfig::default());
+        game.start().unwrap();
+        game.players[0].scorecard.record(Category::Yahtzee, 50).unwrap();
+        game.roll_dice_fixed(p1_id, [4, 4, 4, 4, 4]).unwrap();
+
+        for cat in [Category::Ones, Category::FullHouse, Category::Chance] {
+            assert!(matches!(
+                game.score_category(p1_id, cat),
+                Err(GameError::InvalidCategoryForJoker)
+            ));
+        }
+        assert_eq!(game.score_category(p1_id, Category::Fours).unwrap(), 20);
+        assert_eq!(game.players[0].scorecard.yahtzee_bonus_count, 1);
+    }
+
+    #[test]
+    fn test_joker_falls_back_to_lower_then_upper() {
+        let mut sc = Scorecard::new();
+        sc.record(Category::Yahtzee, 50).unwrap();
+        sc.record(Category::Sixes, 30).unwrap();
+        let dice = [6; 5];
+        assert!(sc.joker_active(&dice));
+        let lower = sc.scorable_categories(&dice);
+        assert!(lower.contains(&Category::LargeStraight));
+        assert!(lower.iter().all(|c| !c.is_upper()));
+
+        for cat in [
+            Category::ThreeOfAKind, Category::FourOfAKind, Category::FullHouse,
+            Category::SmallStraight, Category::LargeStraight, Category::Chance,
+        ] {
+            sc.record(cat, 0).unwrap();
+        }
+        assert_eq!(sc.scorable_categories(&dice), &Category::UPPER[..5]);
+        // Without a Yahtzee there is no Joker
+        assert_eq!(sc.scorable_categories(&[6, 6, 6, 6, 5]), sc.available_categories());
+    }
+
+    #[test]
+    fn test_joker_rules_from_config() {
+        for (joker, full_house) in [(JokerRule::Free, 25), (JokerRule::Off, 0)] {
+            let players = make_players(2);
+            let p1_id = players[0].id;
+            let config = GameConfig {
+                scoring: ScoringRules {
+                    joker,
+                    ..ScoringRules::default()
+                },
+                ..GameConfig::default()
+            };
+            let mut game = GameState::new(players, config);
+            game.start().unwrap();
+            game.players[0].scorecard.record(Category::Yahtzee, 50).unwrap();
+            game.roll_dice_fixed(p1_id, [4, 4, 4, 4, 4]).unwrap();
+
+            // Fours is open, but neither rule forces the Joker there
+            assert_eq!(game.score_category(p1_id, Category::FullHouse).unwrap(), full_house);
+            assert_eq!(game.players[0].scorecard.yahtzee_bonus_count, 1);
+        }
+    }
+
+    #[test]
+    fn test_config_sets_rounds_and_bonuses() {
+        let players = make_players(2);
+        let ids: Vec<Uuid> = players.iter().map(|p| p.id).collect();
+        let config = GameConfig {
+            rounds: 2,
+            scoring: ScoringRules {
+                upper_bonus_threshold: 10,
+                upper_bonus: 50,
+                ..ScoringRules::default()
+            },
+            ..GameConfig::default()
+        };
+        let mut game = GameState::new(players, config);
+        game.start().unwrap();
+        assert_eq!(game.total_rounds, 2);
+
+        for cat in [Category::Sixes, Category::Chance] {
+            for &id in &ids {
+                game.roll_dice_fixed(id, [6, 6, 1, 2, 3]).unwrap();
+                game.score_category(id, cat).unwrap();
+            }
+        }
+        assert_eq!(game.phase, GamePhase::Finished);
+        // 12 in Sixes clears the lowered threshold
+        assert_eq!(game.players[0].scorecard.grand_total(), 12 + 50 + 18);
+
+        let json = serde_json::to_string(&game.view()).unwrap();
+        let parsed: GameStateSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.rules, config);
+        assert_eq!(parsed.players[0].scorecard.grand_total(), 12 + 50 + 18);
+
+        // More rounds than categories can't be played
+        let long = GameState::new(make_players(2), GameConfig { rounds: 20, ..config });
+        assert_eq!(long.total_rounds, STANDARD_ROUNDS);
+    }
+
+    /// Drive games with random, often illegal, actions from every player
+    /// and check that nothing rejected changes the game, and that what is
+    /// accepted keeps the scorecards valid.
+    #[test]
+    fn test_turn_state_machine_survives_malicious_sequences() {
+        use rand::Rng;
+
+        for seed in 0..20 {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+            let players = make_players(rng.gen_range(2..=4));
+            let mut ids: Vec<Uuid> = players.iter().map(|p| p.id).collect();
+            ids.push(Uuid::new_v4()); // a stranger
+            let mut game = GameState::new(players, GameConfig::default());
+            game.start().unwrap();
+
+            for _ in 0..2000 {
+                if game.phase == GamePhase::Finished {
+                    break;
+                }
+                let actor = ids[rng.gen_range(0..ids.len())];
+                let before = serde_json::to_string(&game.snapshot()).unwrap();
+                let was_current = game.is_current_player(actor);
+                let scorecard = game.current_player().scorecard.clone();
+                let dice = game.turn.as_ref().map(|t| t.dice.values());
+
+                let result = match rng.gen_range(0..5) {
+                    0 => game.roll_dice(actor, &mut rng).map(|_| None),
+                    // Five of a kind, to bring out the Joker rules
+                    1 => game.roll_dice_fixed(actor, [rng.gen_range(1..=6); 5]).map(|_| None),
+                    2 => game.hold_dice(actor, rng.gen()).map(|_| None),
+                    3 => {
+                        let cat = Category::ALL[rng.gen_range(0..Category::ALL.len())];
+                        game.score_category(actor, cat).map(|score| Some((cat, score)))
+                    }
+                    // Mostly legal picks, so the game moves on
+                    _ => {
+                        let open = match dice {
+                            Some(dice) => scorecard.scorable_categories(&dice),
+                            None => scorecard.available_categories(),
+                        };
+                        let cat = open[rng.gen_range(0..open.len())];
+                        game.score_category(actor, cat).map(|score| Some((cat, score)))
+                    }
+                };
+
+                match result {
+                    Err(e) => {
+                        assert_eq!(serde_json::to_string(&game.snapshot()).unwrap(), before, "{e} changed the game");
+                        if !was_current {
+                            assert!(matches!(e, GameError::NotYourTurn));
+                        }
+                    }
+                    Ok(scored) => {
+                        assert!(was_current);
+                        if let Some((cat, score)) = scored {
+                            let dice = dice.expect("scored without dice");
+                            assert!(scorecard.scorable_categories(&dice).contains(&cat));
+                            let joker = scorecard.joker_active(&dice);
+                            assert_eq!(score, scoring::compute_score_joker(cat, &dice, joker));
+                        }
+                    }
+                }
+                for player in &game.players {
+                    player.scorecard.validate().unwrap();
+                }
+            }
+            assert_eq!(game.phase, GamePhase::Finished, "seed {seed} did not finish");
+        }
+    }
+
+    #[test]
+    fn test_checksum_follows_scores_and_turn() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(3);
+        let players = make_players(2);
+        let p1_id = players[0].id;
+        let mut game = GameState::new(players, GameConfig::default());
+        game.start().unwrap();
+
+        let before = game.checksum();
+        assert_eq!(game.snapshot().checksum(), before);
+        // Rolling changes nothing the checksum covers
+        game.roll_dice(p1_id, &mut rng).unwrap();
+        assert_eq!(game.checksum(), before);
+
+        game.score_category(p1_id, Category::Chance).unwrap();
+        let after = game.checksum();
+        assert_ne!(after, before);
+        assert_eq!(game.snapshot().checksum(), after);
+
+        // A client that missed the score disagrees
+        let mut stale = game.snapshot();
+        stale.players[0].scorecard = Scorecard::new();
+        assert_ne!(stale.checksum(), after);
+    }
 }
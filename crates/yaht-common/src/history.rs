@@ -0,0 +1,142 @@
+use std::collections::VecDeque;
+
+/// An append-only log that keeps only the most recent `capacity` entries.
+///
+/// Used for chat panels and room event replay, so long sessions use bounded
+/// memory. Once full, each push evicts the oldest entry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct History<T> {
+    items: VecDeque<T>,
+    capacity: usize,
+    evicted: u64,
+}
+
+impl<T> History<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            items: VecDeque::with_capacity(capacity.min(64)),
+            capacity,
+            evicted: 0,
+        }
+    }
+
+    /// Append an entry, returning the oldest one if it had to be evicted.
+    pub fn push(&mut self, item: T) -> Option<T> {
+        if self.capacity == 0 {
+            self.evicted += 1;
+            return Some(item);
+        }
+        let evicted = if self.items.len() == self.capacity {
+            self.evicted += 1;
+            self.items.pop_front()
+        } else {
+            None
+        };
+        self.items.push_back(item);
+        evicted
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// How many entries have been dropped since the last `clear`.
+    pub fn evicted(&self) -> u64 {
+        self.evicted
+    }
+
+    pub fn clear(&mut self) {
+        self.items.clear();
+        self.evicted = 0;
+    }
+
+    pub fn last(&self) -> Option<&T> {
+        self.items.back()
+    }
+
+    /// Entries from oldest to newest.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        self.items.iter()
+    }
+
+    /// The newest `n` entries, oldest first.
+    pub fn recent(&self, n: usize) -> impl Iterator<Item = &T> {
+        self.items.iter().skip(self.items.len().saturating_sub(n))
+    }
+}
+
+impl<T> Extend<T> for History<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a History<T> {
+    type Item = &'a T;
+    type IntoIter = std::collections::vec_deque::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_within_capacity() {
+        let mut h = History::new(3);
+        assert!(h.push(1).is_none());
+        assert!(h.push(2).is_none());
+        assert_eq!(h.iter().copied().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(h.evicted(), 0);
+    }
+
+    #[test]
+    fn test_evicts_oldest_when_full() {
+        let mut h = History::new(3);
+        h.extend([1, 2, 3]);
+        assert_eq!(h.push(4), Some(1));
+        assert_eq!(h.push(5), Some(2));
+        assert_eq!(h.iter().copied().collect::<Vec<_>>(), vec![3, 4, 5]);
+        assert_eq!(h.len(), 3);
+        assert_eq!(h.evicted(), 2);
+        assert_eq!(h.last(), Some(&5));
+    }
+
+    #[test]
+    fn test_recent() {
+        let mut h = History::new(10);
+        h.extend(1..=5);
+        assert_eq!(h.recent(2).copied().collect::<Vec<_>>(), vec![4, 5]);
+        assert_eq!(h.recent(20).count(), 5);
+    }
+
+    #[test]
+    fn test_zero_capacity_keeps_nothing() {
+        let mut h = History::new(0);
+        assert_eq!(h.push("a"), Some("a"));
+        assert!(h.is_empty());
+        assert_eq!(h.evicted(), 1);
+    }
+
+    #[test]
+    fn test_clear_resets_eviction_count() {
+        let mut h = History::new(1);
+        h.extend([1, 2]);
+        h.clear();
+        assert!(h.is_empty());
+        assert_eq!(h.evicted(), 0);
+    }
+}
@@ -1,7 +1,82 @@
+//! Yahtzee rules, scoring, AI players and the yaht network protocol.
+//!
+//! The engine is usable on its own: drive a [`Game`] with player ids and
+//! dice, and let an [`ai::Strategy`] play any seat. Most programs only need
+//! the [`prelude`]. The `net` feature (on by default) adds message framing
+//! and the async TCP transport; turn it off to embed the engine without
+//! tokio.
+//!
+//! Without `net` the crate builds for `wasm32-unknown-unknown`, so a browser
+//! front end can share the engine and the serde message types.
+
 pub mod ai;
 pub mod dice;
+pub mod directory;
 pub mod game;
+pub mod history;
 pub mod lobby;
+pub mod observer;
 pub mod player;
 pub mod protocol;
+pub mod rating;
+pub mod records;
+pub mod scoreboard;
 pub mod scoring;
+pub mod script;
+
+pub use dice::DiceSet;
+pub use game::{GameConfig, GameError, GameState as Game, GameStateSnapshot};
+pub use player::{Player, Scorecard};
+pub use scoring::Category;
+
+/// The types needed to run a game.
+pub mod prelude {
+    pub use crate::ai::{AiDifficulty, Strategy};
+    pub use crate::dice::DiceSet;
+    pub use crate::game::{GameConfig, GameError, GamePhase, GameState as Game, TurnPhase};
+    pub use crate::player::{Player, Scorecard};
+    pub use crate::scoring::Category;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use rand::SeedableRng;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_prelude_plays_a_game() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(5);
+        let players = vec![
+            Player::new(Uuid::new_v4(), "Ada".into()),
+            Player::new(Uuid::new_v4(), "Bea".into()),
+        ];
+        let strategies: [&dyn Strategy; 2] = [&AiDifficulty::Hard, &AiDifficulty::Easy];
+        let mut game = Game::new(players, GameConfig::default());
+        game.start().unwrap();
+
+        while game.phase == GamePhase::Playing {
+            let seat = game.current_player_index;
+            let id = game.current_player().id;
+            game.roll_dice(id, &mut rng).unwrap();
+            while game.turn.as_ref().unwrap().can_roll() {
+                let held = strategies[seat].choose_holds(
+                    &game.turn.as_ref().unwrap().dice,
+                    &game.current_player().scorecard,
+                    &mut rng,
+                );
+                game.hold_dice(id, held).unwrap_or(());
+                game.roll_dice(id, &mut rng).unwrap();
+            }
+            let category = strategies[seat].choose_category(
+                &game.turn.as_ref().unwrap().dice,
+                &game.current_player().scorecard,
+                &mut rng,
+            );
+            game.score_category(id, category).unwrap();
+        }
+
+        assert!(game.players.iter().all(|p| p.scorecard.is_complete()));
+        assert!(!game.winners().is_empty());
+    }
+}
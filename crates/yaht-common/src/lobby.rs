@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::game::Pace;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct RoomInfo {
     pub room_id: Uuid,
     pub room_name: String,
@@ -10,11 +13,99 @@ pub struct RoomInfo {
     pub spectator_count: u8,
     pub state: RoomInfoState,
     pub has_password: bool,
+    /// Closed to new players by the host, password or not.
+    #[serde(default)]
+    pub locked: bool,
+    /// Spectators allowed at once; 0 if the room can't be watched, and
+    /// `None` from servers without a limit.
+    #[serde(default)]
+    pub max_spectators: Option<u8>,
+    /// The room's speed preset, if its rules come from one.
+    #[serde(default)]
+    pub pace: Option<Pace>,
+}
+
+impl RoomInfo {
+    /// Whether another spectator would be let in.
+    pub fn can_spectate(&self) -> bool {
+        self.max_spectators.is_none_or(|max| self.spectator_count < max)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum RoomInfoState {
     Waiting,
     InProgress,
     Finished,
 }
+
+/// Longest room name allowed, in characters.
+pub const MAX_ROOM_NAME_CHARS: usize = 40;
+
+/// Punctuation allowed in room names besides letters, digits and spaces.
+const ROOM_NAME_PUNCTUATION: &str = "-_'.,:!?&#+()";
+
+/// Word beginnings that keep a name out of the room list. Matching on the
+/// start of each word spares names like "Scunthorpe".
+const BLOCKED_WORDS: &[&str] = &["fuck", "shit", "cunt", "bitch", "asshole", "wank", "twat"];
+
+/// Why a room name was refused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum RoomNameError {
+    #[error("room name is empty")]
+    Empty,
+    #[error("room name is longer than {MAX_ROOM_NAME_CHARS} characters")]
+    TooLong,
+    #[error("room names cannot contain '{0}'")]
+    InvalidCharacter(char),
+    #[error("room name contains a blocked word")]
+    Blocked,
+}
+
+/// Check a room name as the server will, returning it trimmed.
+pub fn validate_room_name(name: &str) -> Result<&str, RoomNameError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(RoomNameError::Empty);
+    }
+    if name.chars().count() > MAX_ROOM_NAME_CHARS {
+        return Err(RoomNameError::TooLong);
+    }
+    if let Some(c) = name
+        .chars()
+        .find(|&c| !(c.is_alphanumeric() || c == ' ' || ROOM_NAME_PUNCTUATION.contains(c)))
+    {
+        return Err(RoomNameError::InvalidCharacter(c));
+    }
+    let lowercase = name.to_lowercase();
+    let blocked = lowercase
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|word| BLOCKED_WORDS.iter().any(|b| word.starts_with(b)));
+    if blocked {
+        return Err(RoomNameError::Blocked);
+    }
+    Ok(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_room_names_are_trimmed() {
+        assert_eq!(validate_room_name("  Alice's room "), Ok("Alice's room"));
+        assert_eq!(validate_room_name("Spieleabend #3 (Jürgen)"), Ok("Spieleabend #3 (Jürgen)"));
+        assert_eq!(validate_room_name("Scunthorpe"), Ok("Scunthorpe"));
+    }
+
+    #[test]
+    fn test_invalid_room_names() {
+        assert_eq!(validate_room_name("   "), Err(RoomNameError::Empty));
+        assert_eq!(validate_room_name(&"x".repeat(MAX_ROOM_NAME_CHARS + 1)), Err(RoomNameError::TooLong));
+        assert!(validate_room_name(&"ü".repeat(MAX_ROOM_NAME_CHARS)).is_ok());
+        assert_eq!(validate_room_name("a\tb"), Err(RoomNameError::InvalidCharacter('\t')));
+        assert_eq!(validate_room_name("<script>"), Err(RoomNameError::InvalidCharacter('<')));
+        assert_eq!(validate_room_name("Shitheads only"), Err(RoomNameError::Blocked));
+    }
+}
@@ -0,0 +1,67 @@
+//! Callbacks for following a game as it is played.
+//!
+//! Sound, notifications and achievements can react to what happened at the
+//! table instead of working it out from snapshots: register a callback with
+//! [`Game::observe`](crate::Game::observe) and it is called with each
+//! [`EngineEvent`] as the game produces it.
+
+use std::fmt;
+
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EngineEvent {
+    /// A player rolled; `dice` are all five faces after the roll.
+    DiceRolled {
+        player_id: Uuid,
+        dice: [u8; 5],
+        rolls_left: u8,
+    },
+    /// The roll just reported came up five of a kind.
+    YahtzeeRolled { player_id: Uuid, face: u8 },
+    /// The category just scored earned a bonus.
+    BonusAchieved { player_id: Uuid, bonus: Bonus },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bonus {
+    /// The upper section reached the bonus threshold.
+    UpperSection,
+    /// Another Yahtzee after 50 in the Yahtzee box.
+    Yahtzee,
+}
+
+type Callback = Box<dyn Fn(&EngineEvent) + Send + Sync>;
+
+/// The callbacks a game reports to. A clone of a game starts without any,
+/// so simulating moves on a copy stays quiet.
+#[derive(Default)]
+pub struct Observers(Vec<Callback>);
+
+impl Observers {
+    pub fn add(&mut self, callback: impl Fn(&EngineEvent) + Send + Sync + 'static) {
+        self.0.push(Box::new(callback));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub(crate) fn emit(&self, event: EngineEvent) {
+        for callback in &self.0 {
+            callback(&event);
+        }
+    }
+}
+
+impl Clone for Observers {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl fmt::Debug for Observers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Observers({})", self.0.len())
+    }
+}
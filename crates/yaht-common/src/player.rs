@@ -1,32 +1,53 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::HashMap;
 use uuid::Uuid;
 
-use crate::scoring::{Category, UPPER_BONUS_THRESHOLD, UPPER_BONUS_VALUE, YAHTZEE_BONUS_VALUE};
+use crate::scoring::{self, Category, JokerRule, ScoringRules};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Scorecard {
-    pub scores: HashMap<Category, u16>,
+    /// Indexed by `Category::index`; `None` until the category is used.
+    /// Serialized as a map of used categories in `Category::ALL` order.
+    #[serde(serialize_with = "serialize_scores", deserialize_with = "deserialize_scores")]
+    #[cfg_attr(feature = "schema", schemars(with = "HashMap<Category, u16>"))]
+    scores: [Option<u16>; 13],
     pub yahtzee_bonus_count: u8,
+    /// The bonuses and Joker rule this card is scored under.
+    #[serde(default, skip_serializing_if = "ScoringRules::is_standard")]
+    pub rules: ScoringRules,
 }
 
 impl Scorecard {
     pub fn new() -> Self {
         Self {
-            scores: HashMap::new(),
+            scores: [None; 13],
             yahtzee_bonus_count: 0,
+            rules: ScoringRules::default(),
         }
     }
 
+    pub fn score(&self, category: Category) -> Option<u16> {
+        self.scores[category.index()]
+    }
+
+    /// Used categories and their scores, in `Category::ALL` order.
+    pub fn scores(&self) -> impl Iterator<Item = (Category, u16)> + '_ {
+        Category::ALL
+            .iter()
+            .zip(self.scores.iter())
+            .filter_map(|(&cat, score)| score.map(|s| (cat, s)))
+    }
+
     pub fn is_category_used(&self, category: Category) -> bool {
-        self.scores.contains_key(&category)
+        self.scores[category.index()].is_some()
     }
 
     pub fn record(&mut self, category: Category, score: u16) -> Result<(), ScorecardError> {
         if self.is_category_used(category) {
             return Err(ScorecardError::CategoryAlreadyUsed);
         }
-        self.scores.insert(category, score);
+        self.scores[category.index()] = Some(score);
         Ok(())
     }
 
@@ -35,38 +56,53 @@ impl Scorecard {
     }
 
     pub fn upper_subtotal(&self) -> u16 {
-        Category::UPPER
-            .iter()
-            .filter_map(|c| self.scores.get(c))
-            .sum()
+        self.scores[..Category::UPPER.len()].iter().flatten().sum()
     }
 
     pub fn upper_bonus(&self) -> u16 {
-        if self.upper_subtotal() >= UPPER_BONUS_THRESHOLD {
-            UPPER_BONUS_VALUE
+        if self.upper_subtotal() >= self.rules.upper_bonus_threshold {
+            self.rules.upper_bonus
         } else {
             0
         }
     }
 
     pub fn lower_total(&self) -> u16 {
-        Category::ALL
-            .iter()
-            .filter(|c| !c.is_upper())
-            .filter_map(|c| self.scores.get(c))
-            .sum()
+        self.scores[Category::UPPER.len()..].iter().flatten().sum()
     }
 
     pub fn yahtzee_bonus_total(&self) -> u16 {
-        self.yahtzee_bonus_count as u16 * YAHTZEE_BONUS_VALUE
+        (self.yahtzee_bonus_count as u16).saturating_mul(self.rules.yahtzee_bonus)
     }
 
+    /// Saturates rather than overflow under outlandish bonus rules.
     pub fn grand_total(&self) -> u16 {
-        self.upper_subtotal() + self.upper_bonus() + self.lower_total() + self.yahtzee_bonus_total()
+        self.upper_subtotal()
+            .saturating_add(self.upper_bonus())
+            .saturating_add(self.lower_total())
+            .saturating_add(self.yahtzee_bonus_total())
     }
 
     pub fn is_complete(&self) -> bool {
-        self.scores.len() == 13
+        self.scores.iter().all(Option::is_some)
+    }
+
+    /// Check every score is one some roll could produce, and that any
+    /// Yahtzee bonuses were earned. Catches corrupt or hand-edited data.
+    pub fn validate(&self) -> Result<(), ScorecardError> {
+        for (category, score) in self.scores() {
+            if !scoring::is_possible_score(category, score) {
+                return Err(ScorecardError::ImpossibleScore { category, score });
+            }
+        }
+        // Each bonus comes with another category scored after the Yahtzee
+        let bonus_turns = self.scores().count().saturating_sub(1);
+        if self.yahtzee_bonus_count > 0
+            && (self.score(Category::Yahtzee) != Some(50) || self.yahtzee_bonus_count as usize > bonus_turns)
+        {
+            return Err(ScorecardError::UnearnedYahtzeeBonus(self.yahtzee_bonus_count));
+        }
+        Ok(())
     }
 
     pub fn available_categories(&self) -> Vec<Category> {
@@ -76,6 +112,47 @@ impl Scorecard {
             .copied()
             .collect()
     }
+
+    /// Whether `dice` are a Yahtzee rolled after the Yahtzee box was
+    /// filled with 50, so the Joker rules and the bonus apply.
+    pub fn joker_active(&self, dice: &[u8; 5]) -> bool {
+        scoring::compute_score(Category::Yahtzee, dice) == 50 && self.score(Category::Yahtzee) == Some(50)
+    }
+
+    /// What `dice` score in `category` on this card, Joker included.
+    pub fn score_for(&self, category: Category, dice: &[u8; 5]) -> u16 {
+        let joker = self.rules.joker != JokerRule::Off && self.joker_active(dice);
+        scoring::compute_score_joker(category, dice, joker)
+    }
+
+    /// Where a Joker of `face` may go. Under the forced rule that is the
+    /// matching upper box while it is open, otherwise any open lower box,
+    /// otherwise any open upper box; otherwise any open box.
+    pub fn joker_categories(&self, face: u8) -> Vec<Category> {
+        if self.rules.joker != JokerRule::Forced {
+            return self.available_categories();
+        }
+        let upper = Category::UPPER[face.clamp(1, 6) as usize - 1];
+        if !self.is_category_used(upper) {
+            return vec![upper];
+        }
+        let open = self.available_categories();
+        let lower: Vec<Category> = open.iter().copied().filter(|c| !c.is_upper()).collect();
+        if lower.is_empty() {
+            open
+        } else {
+            lower
+        }
+    }
+
+    /// The categories `dice` may be scored in, with forced Joker placement.
+    pub fn scorable_categories(&self, dice: &[u8; 5]) -> Vec<Category> {
+        if self.joker_active(dice) {
+            self.joker_categories(dice[0])
+        } else {
+            self.available_categories()
+        }
+    }
 }
 
 impl Default for Scorecard {
@@ -84,10 +161,32 @@ impl Default for Scorecard {
     }
 }
 
+fn serialize_scores<S: Serializer>(scores: &[Option<u16>; 13], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(
+        Category::ALL
+            .iter()
+            .zip(scores.iter())
+            .filter_map(|(cat, score)| score.map(|s| (cat, s))),
+    )
+}
+
+fn deserialize_scores<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[Option<u16>; 13], D::Error> {
+    let map = HashMap::<Category, u16>::deserialize(deserializer)?;
+    let mut scores = [None; 13];
+    for (cat, score) in map {
+        scores[cat.index()] = Some(score);
+    }
+    Ok(scores)
+}
+
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum ScorecardError {
     #[error("category already used")]
     CategoryAlreadyUsed,
+    #[error("{score} is not a possible score for {}", category.display_name())]
+    ImpossibleScore { category: Category, score: u16 },
+    #[error("{0} Yahtzee bonuses without the Yahtzees to earn them")]
+    UnearnedYahtzeeBonus(u8),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -96,6 +195,13 @@ pub struct Player {
     pub name: String,
     pub scorecard: Scorecard,
     pub connected: bool,
+    /// Out of the game; their turns are skipped and they cannot win.
+    #[serde(default)]
+    pub forfeited: bool,
+    /// Seconds left on the player's time bank, charged as each of their
+    /// turns ends. `None` unless the rules give one.
+    #[serde(default)]
+    pub time_bank: Option<u32>,
 }
 
 impl Player {
@@ -105,6 +211,8 @@ impl Player {
             name,
             scorecard: Scorecard::new(),
             connected: true,
+            forfeited: false,
+            time_bank: None,
         }
     }
 }
@@ -186,6 +294,32 @@ mod tests {
         assert_eq!(sc.available_categories().len(), 0);
     }
 
+    #[test]
+    fn test_validate() {
+        let mut sc = Scorecard::new();
+        sc.record(Category::Threes, 9).unwrap();
+        sc.record(Category::FullHouse, 25).unwrap();
+        assert!(sc.validate().is_ok());
+
+        let mut bad = sc.clone();
+        bad.record(Category::Ones, 6).unwrap();
+        assert!(matches!(
+            bad.validate(),
+            Err(ScorecardError::ImpossibleScore { category: Category::Ones, score: 6 })
+        ));
+
+        // A bonus needs a scored Yahtzee, and a later turn to claim it on
+        let mut bonus = sc.clone();
+        bonus.add_yahtzee_bonus();
+        assert!(matches!(bonus.validate(), Err(ScorecardError::UnearnedYahtzeeBonus(1))));
+        bonus.record(Category::Yahtzee, 50).unwrap();
+        assert!(bonus.validate().is_ok());
+        for _ in 0..2 {
+            bonus.add_yahtzee_bonus();
+        }
+        assert!(bonus.validate().is_err());
+    }
+
     #[test]
     fn test_available_categories() {
         let mut sc = Scorecard::new();
@@ -196,4 +330,36 @@ mod tests {
         assert!(!available.contains(&Category::Ones));
         assert!(!available.contains(&Category::Yahtzee));
     }
+
+    #[test]
+    fn test_serialized_in_category_order() {
+        let mut sc = Scorecard::new();
+        sc.record(Category::Chance, 22).unwrap();
+        sc.record(Category::Twos, 6).unwrap();
+        sc.record(Category::FullHouse, 25).unwrap();
+        let json = serde_json::to_string(&sc).unwrap();
+        assert_eq!(
+            json,
+            r#"{"scores":{"Twos":6,"FullHouse":25,"Chance":22},"yahtzee_bonus_count":0}"#
+        );
+
+        let parsed: Scorecard = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.score(Category::FullHouse), Some(25));
+        assert_eq!(parsed.score(Category::Ones), None);
+        assert_eq!(parsed.grand_total(), sc.grand_total());
+        let used: Vec<Category> = parsed.scores().map(|(cat, _)| cat).collect();
+        assert_eq!(used, vec![Category::Twos, Category::FullHouse, Category::Chance]);
+    }
+
+    #[test]
+    fn test_totals_saturate() {
+        let mut sc = Scorecard::new();
+        sc.rules.yahtzee_bonus = u16::MAX;
+        sc.rules.upper_bonus = u16::MAX;
+        sc.rules.upper_bonus_threshold = 0;
+        sc.yahtzee_bonus_count = 3;
+        sc.record(Category::Chance, 30).unwrap();
+        assert_eq!(sc.yahtzee_bonus_total(), u16::MAX);
+        assert_eq!(sc.grand_total(), u16::MAX);
+    }
 }
@@ -1,33 +1,91 @@
-use bytes::Bytes;
+#[cfg(feature = "net")]
+use bytes::{BufMut, Bytes, BytesMut};
+#[cfg(feature = "net")]
 use futures::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "net")]
 use tokio::net::TcpStream;
-use tokio_util::codec::{Framed, LengthDelimitedCodec};
+#[cfg(feature = "net")]
+use tokio_util::codec::{Framed, LengthDelimitedCodec, LengthDelimitedCodecError};
 use uuid::Uuid;
 
-use crate::dice::DiceSet;
-use crate::game::GameStateSnapshot;
+use crate::dice::{DiceSet, FaceCounts};
+use crate::game::{GameConfig, GameStateSnapshot, TurnRecord};
+#[cfg(feature = "net")]
+use crate::game::{GameState, GameStateView};
 use crate::lobby::RoomInfo;
+use crate::player::Scorecard;
 use crate::scoring::Category;
 
 // -- Framing --
 
+#[cfg(feature = "net")]
 pub type Transport = Framed<TcpStream, LengthDelimitedCodec>;
 
+/// Largest frame either side accepts unless configured otherwise.
+pub const DEFAULT_MAX_FRAME_LENGTH: usize = 64 * 1024;
+
+#[cfg(feature = "net")]
 pub fn framed_transport(stream: TcpStream) -> Transport {
+    framed_transport_with_limit(stream, DEFAULT_MAX_FRAME_LENGTH)
+}
+
+/// A transport that rejects frames longer than `max_frame_length` bytes,
+/// in either direction.
+#[cfg(feature = "net")]
+pub fn framed_transport_with_limit(stream: TcpStream, max_frame_length: usize) -> Transport {
     LengthDelimitedCodec::builder()
-        .max_frame_length(64 * 1024)
+        .max_frame_length(max_frame_length)
         .new_framed(stream)
 }
 
+/// Whether a transport error is an inbound frame over the length limit.
+/// The stream cannot be resynchronised after one.
+#[cfg(feature = "net")]
+pub fn is_frame_too_large(e: &std::io::Error) -> bool {
+    e.get_ref()
+        .is_some_and(|inner| inner.is::<LengthDelimitedCodecError>())
+}
+
+// -- Versioning --
+
+/// Protocol revision this build speaks. Revision 2 added session resume
+/// and tracked actions, revision 3 turn acknowledgements; clients and
+/// servers from before the handshake negotiated a revision speak 1.
+pub const PROTOCOL_VERSION: u32 = 3;
+
+/// Oldest revision a server still accepts a client speaking.
+pub const MIN_PROTOCOL_VERSION: u32 = 1;
+
+fn legacy_protocol() -> u32 {
+    1
+}
+
+/// The revision a server speaking `PROTOCOL_VERSION` and a peer speaking
+/// `peer` agree on, or `None` if the peer is too old.
+pub fn negotiate_protocol(peer: u32) -> Option<u32> {
+    (peer >= MIN_PROTOCOL_VERSION).then(|| peer.min(PROTOCOL_VERSION))
+}
+
 // -- Client -> Server Messages --
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum ClientMessage {
     // Handshake
     Hello {
         player_name: String,
         version: String,
+        /// Persistent client identity, used to keep records across sessions.
+        #[serde(default)]
+        identity: Option<Uuid>,
+        /// Token from an earlier `Welcome`, to take back a seat held after
+        /// the connection dropped mid-game.
+        #[serde(default)]
+        resume_token: Option<Uuid>,
+        /// Protocol revision the client speaks.
+        #[serde(default = "legacy_protocol")]
+        protocol: u32,
     },
 
     // Lobby
@@ -35,18 +93,81 @@ pub enum ClientMessage {
         room_name: String,
         max_players: u8,
         password: Option<String>,
+        #[serde(default)]
+        rules: GameConfig,
     },
     JoinRoom {
         room_id: Uuid,
         password: Option<String>,
+        /// Watch the game instead if it has already started, rather than
+        /// being turned away.
+        #[serde(default)]
+        spectate_if_started: bool,
     },
     LeaveRoom,
     ListRooms,
     StartGame,
+    /// Change the room's settings while it waits for a game. Host only;
+    /// the room may not shrink below the players already in it. Settings
+    /// left out stay as they are.
+    UpdateRoomSettings {
+        #[serde(default)]
+        max_players: Option<u8>,
+        /// A new password, or an empty one to remove it.
+        #[serde(default)]
+        password: Option<String>,
+        #[serde(default)]
+        rules: Option<GameConfig>,
+        /// Close the room to new players, or open it again.
+        #[serde(default)]
+        locked: Option<bool>,
+        /// Start the game on its own once every seat is taken. Turning it
+        /// off cancels a countdown in progress.
+        #[serde(default)]
+        auto_start: Option<bool>,
+        /// Spectators allowed at once, 0 for none. Lowering it turns
+        /// away newcomers only.
+        #[serde(default)]
+        max_spectators: Option<u8>,
+        /// Deal the room's dice from this seed, so a game can be played
+        /// again roll for roll. Games on a chosen seed are not rated.
+        #[serde(default)]
+        seed: Option<u64>,
+        /// Which game milestones the room announces.
+        #[serde(default)]
+        announcements: Option<Announcements>,
+        /// Seat players in a random order when the game starts, instead
+        /// of the order they joined in.
+        #[serde(default)]
+        shuffle_order: Option<bool>,
+        /// Nudge a player who has neither rolled nor scored for this many
+        /// seconds, and tell the room who it is waiting on; 0 for never.
+        /// Nudges that would come after the turn timer runs out are not
+        /// sent.
+        #[serde(default)]
+        nudge_secs: Option<u32>,
+    },
+    /// Mark yourself ready, or not, in a waiting room. The game starts
+    /// once every seated player is ready.
+    SetReady {
+        ready: bool,
+    },
+    /// Move to another seat in a waiting room, shifting the players in
+    /// between. Seats are numbered from 0 and set the turn order.
+    MoveSeat {
+        seat: u8,
+    },
+    /// Remove a player or spectator from the room. Host only.
+    Kick {
+        player_id: Uuid,
+    },
 
     // Spectator
     SpectateRoom {
         room_id: Uuid,
+        /// Replay the game so far before switching to live updates.
+        #[serde(default)]
+        catch_up: bool,
     },
 
     // Gameplay
@@ -57,28 +178,123 @@ pub enum ClientMessage {
     ScoreCategory {
         category: Category,
     },
+    // Face frequencies of the dice rolled in the current room.
+    GetDiceStats,
+    /// Sent as each turn starts with `GameStateSnapshot::checksum` of the
+    /// state as the client sees it. A server that sees it differ sends
+    /// the client the full `GameState`.
+    AckTurn {
+        round: u8,
+        player_id: Uuid,
+        checksum: u64,
+    },
 
     // Chat
     Chat {
         message: String,
     },
+    /// A chat message only the named room member sees.
+    Whisper {
+        to: String,
+        message: String,
+    },
+    /// A game or chat message tagged with a client-chosen id, answered with
+    /// `ActionAck`. Each player's ids must increase; one at or below the
+    /// last seen is a resend and is acknowledged without being applied
+    /// again.
+    Tracked {
+        request_id: u64,
+        action: Box<ClientMessage>,
+    },
+
+    // Ratings
+    /// Standings for a season; `None` for the current one.
+    GetLeaderboard {
+        season: Option<u32>,
+    },
+
+    // Player stats
+    /// How a player has done in each category over their rated games.
+    GetPlayerStats {
+        player: String,
+    },
+
+    // Game archive
+    /// The most recently finished games, newest first.
+    GetRecentGames {
+        /// Only games this player took part in.
+        #[serde(default)]
+        player: Option<String>,
+    },
+    /// One archived game with its turns, for a replay.
+    GetGame {
+        game_id: Uuid,
+    },
 
     // Connection
     Ping,
     Disconnect,
 }
 
+impl ClientMessage {
+    /// The variant name, as used for its serialized tag. For logging.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ClientMessage::Hello { .. } => "Hello",
+            ClientMessage::CreateRoom { .. } => "CreateRoom",
+            ClientMessage::JoinRoom { .. } => "JoinRoom",
+            ClientMessage::LeaveRoom => "LeaveRoom",
+            ClientMessage::ListRooms => "ListRooms",
+            ClientMessage::StartGame => "StartGame",
+            ClientMessage::UpdateRoomSettings { .. } => "UpdateRoomSettings",
+            ClientMessage::SetReady { .. } => "SetReady",
+            ClientMessage::MoveSeat { .. } => "MoveSeat",
+            ClientMessage::Kick { .. } => "Kick",
+            ClientMessage::SpectateRoom { .. } => "SpectateRoom",
+            ClientMessage::RollDice => "RollDice",
+            ClientMessage::HoldDice { .. } => "HoldDice",
+            ClientMessage::ScoreCategory { .. } => "ScoreCategory",
+            ClientMessage::GetDiceStats => "GetDiceStats",
+            ClientMessage::AckTurn { .. } => "AckTurn",
+            ClientMessage::Chat { .. } => "Chat",
+            ClientMessage::Whisper { .. } => "Whisper",
+            ClientMessage::Tracked { .. } => "Tracked",
+            ClientMessage::GetLeaderboard { .. } => "GetLeaderboard",
+            ClientMessage::GetPlayerStats { .. } => "GetPlayerStats",
+            ClientMessage::GetRecentGames { .. } => "GetRecentGames",
+            ClientMessage::GetGame { .. } => "GetGame",
+            ClientMessage::Ping => "Ping",
+            ClientMessage::Disconnect => "Disconnect",
+        }
+    }
+}
+
 // -- Server -> Client Messages --
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum ServerMessage {
     // Handshake
     Welcome {
         player_id: Uuid,
         server_version: String,
+        /// Send this back in `Hello` to resume the session after a dropped
+        /// connection.
+        #[serde(default)]
+        resume_token: Option<Uuid>,
+        /// Whether the `Hello` resumed an earlier session, keeping its
+        /// player ID and seat.
+        #[serde(default)]
+        resumed: bool,
+        /// Protocol revision the session uses, the older of the two sides'.
+        #[serde(default = "legacy_protocol")]
+        protocol: u32,
     },
     HandshakeError {
         reason: String,
+        /// Set when the client's protocol is too old for this server.
+        #[serde(default)]
+        version_mismatch: Option<VersionMismatch>,
     },
 
     // Lobby
@@ -93,6 +309,14 @@ pub enum ServerMessage {
         room_state: RoomSnapshot,
     },
     RoomLeft,
+    /// The room is full and its game starts on its own this many seconds
+    /// from now. Sent each second until it starts or is cancelled.
+    AutoStartCountdown {
+        seconds_left: u32,
+    },
+    // The countdown stopped: the host turned auto-start off or a seat
+    // came free.
+    AutoStartCancelled,
 
     // Game state
     GameStarted {
@@ -106,6 +330,18 @@ pub enum ServerMessage {
         player_name: String,
         turn_number: u8,
     },
+    /// Time left in a timed turn. Sent as the turn starts and every few
+    /// seconds after.
+    TurnTimer {
+        player_id: Uuid,
+        seconds_left: u32,
+    },
+    /// Time left on the current player's time bank. Sent as each turn
+    /// starts and every few seconds after.
+    TimeBank {
+        player_id: Uuid,
+        seconds_left: u32,
+    },
     DiceRolled {
         dice: DiceSet,
         rolls_remaining: u8,
@@ -121,9 +357,43 @@ pub enum ServerMessage {
     TurnEnded {
         player_id: Uuid,
     },
+    /// Everyone's total as a round ends, in seat order. Sent just before
+    /// the next round's first turn; the last round ends with `GameOver`.
+    RoundStandings {
+        round: u8,
+        totals: Vec<(Uuid, String, u16)>,
+        /// The player in front, or everyone sharing the lead. Forfeited
+        /// players never lead.
+        leader_ids: Vec<Uuid>,
+    },
+    /// A player left mid-game; their turns are skipped from now on.
+    PlayerForfeited {
+        player_id: Uuid,
+        player_name: String,
+    },
     GameOver {
         final_scores: Vec<(Uuid, String, u16)>,
-        winner_id: Uuid,
+        /// One player, or everyone sharing first place on a draw.
+        winner_ids: Vec<Uuid>,
+        /// Each player's final scorecard, in `final_scores` order.
+        #[serde(default)]
+        scorecards: Vec<Scorecard>,
+        /// Seconds from the start of the game to the last score.
+        #[serde(default)]
+        duration_secs: u64,
+        /// Each player's total after every round, in `final_scores` order.
+        #[serde(default)]
+        round_totals: Vec<Vec<u16>>,
+        /// Each player's average turn in milliseconds, in `final_scores`
+        /// order; `None` where no turn was timed.
+        #[serde(default)]
+        average_turn_ms: Vec<Option<u64>>,
+    },
+    DiceStats {
+        /// Dice rolled in the current or most recent game.
+        game: FaceCounts,
+        /// Dice rolled since the room was created.
+        room: FaceCounts,
     },
 
     // Chat
@@ -132,9 +402,49 @@ pub enum ServerMessage {
         sender_name: String,
         message: String,
         timestamp: i64,
+        /// For a whisper, who it was sent to. Only they and the sender
+        /// see it.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        whisper_to: Option<String>,
     },
     SystemMessage {
         message: String,
+        /// The game moment this announces, for clients to set apart from
+        /// plain notices.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        milestone: Option<Milestone>,
+    },
+    /// The `Tracked` message with this id has been handled, now or
+    /// earlier. Any error it caused was sent first.
+    ActionAck {
+        request_id: u64,
+    },
+
+    // Ratings
+    Leaderboard {
+        season: u32,
+        current_season: u32,
+        /// Unix timestamp the season ended, for archived seasons.
+        ended_at: Option<i64>,
+        standings: Vec<LeaderboardEntry>,
+    },
+
+    // Player stats
+    PlayerStats {
+        player: String,
+        /// One entry per category, in `Category::ALL` order.
+        categories: Vec<CategoryStats>,
+        /// How long the player takes over a turn.
+        #[serde(default)]
+        turn_times: TurnTimes,
+    },
+
+    // Game archive
+    RecentGames {
+        games: Vec<GameSummary>,
+    },
+    GameDetails {
+        game: Box<ArchivedGame>,
     },
 
     // Errors
@@ -159,23 +469,74 @@ pub enum ServerMessage {
     SpectatorLeft {
         player_name: String,
     },
+
+    // Spectator catch-up: recorded game events, followed by a live GameState
+    ReplayStarted {
+        event_count: u32,
+    },
+    ReplayEvent {
+        event: Box<ServerMessage>,
+    },
+    ReplayFinished,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum ErrorCode {
     RoomFull,
     RoomNotFound,
     NotYourTurn,
     InvalidAction,
     CategoryAlreadyScored,
+    /// A Joker scored outside the boxes the rules allow for it.
+    InvalidCategoryForJoker,
     GameAlreadyStarted,
     NotEnoughPlayers,
     NameTaken,
     WrongPassword,
+    SeasonNotFound,
+    /// No archived game has that id.
+    GameNotFound,
+    /// A frame that could not be parsed as a client message.
+    MalformedMessage,
+    /// Dice cannot be held now, or the hold changes nothing.
+    InvalidHold,
+    /// A message over the frame length limit.
+    FrameTooLarge,
+    /// Too many messages of one kind in a short time.
+    RateLimited,
+    /// The host has closed the room to new players.
+    RoomLocked,
+    /// The room allows no more spectators, or none at all.
+    SpectatorsFull,
+    /// Only the room's host may do that.
+    NotHost,
+    /// The host removed you from the room.
+    Kicked,
+    /// A room name that is empty, too long, or has characters or words
+    /// the server refuses.
+    InvalidRoomName,
+    /// Another room already has that name, on servers that keep them unique.
+    RoomNameTaken,
+    /// The same identity connected again, and the new connection took
+    /// over; this one is closed.
+    SessionTaken,
     InternalError,
 }
 
+/// Why a server turned a client away at the handshake.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct VersionMismatch {
+    pub server_version: String,
+    /// Protocol revision the server speaks.
+    pub server_protocol: u32,
+    /// Oldest revision it accepts.
+    pub min_protocol: u32,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct RoomSnapshot {
     pub room_id: Uuid,
     pub room_name: String,
@@ -184,9 +545,69 @@ pub struct RoomSnapshot {
     pub spectators: Vec<String>,
     pub state: RoomState,
     pub max_players: u8,
+    /// Rules the room's games are played under.
+    #[serde(default)]
+    pub rules: GameConfig,
+    #[serde(default)]
+    pub has_password: bool,
+    #[serde(default)]
+    pub locked: bool,
+    /// Whether the game starts on its own once the room is full.
+    #[serde(default)]
+    pub auto_start: bool,
+    /// Whether turn order is drawn at random when the game starts.
+    #[serde(default)]
+    pub shuffle_order: bool,
+    /// Seconds an idle player waits before being nudged; 0 for never.
+    #[serde(default)]
+    pub nudge_secs: u32,
+    #[serde(default)]
+    pub max_spectators: Option<u8>,
+    /// The dice seed the host chose; games on it are not rated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    #[serde(default)]
+    pub announcements: Announcements,
+    /// Each player's record over the games finished in this room, most
+    /// wins first. Empty until the first game ends.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub standings: Vec<SeriesStanding>,
+}
+
+/// A notable moment in a game, announced to the room.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum Milestone {
+    Yahtzee,
+    UpperBonus,
+    TookLead,
+    FinalRound,
+}
+
+/// How many milestones a room announces.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum Announcements {
+    Off,
+    /// Yahtzees and the final round.
+    #[default]
+    Highlights,
+    /// Also upper bonuses and changes of lead.
+    All,
+}
+
+impl Announcements {
+    pub fn includes(self, milestone: Milestone) -> bool {
+        match self {
+            Announcements::Off => false,
+            Announcements::Highlights => matches!(milestone, Milestone::Yahtzee | Milestone::FinalRound),
+            Announcements::All => true,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum RoomState {
     WaitingForPlayers,
     InGame,
@@ -194,38 +615,308 @@ pub enum RoomState {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct LeaderboardEntry {
+    pub rank: u32,
+    pub player_name: String,
+    pub rating: i32,
+    pub games_played: u32,
+    pub wins: u32,
+}
+
+/// A finished game in the server's archive, without its turns.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct GameSummary {
+    pub game_id: Uuid,
+    pub room_name: String,
+    /// Unix timestamp of the last score.
+    pub finished_at: i64,
+    pub duration_secs: u64,
+    /// Each player's name and final total, in seat order.
+    pub players: Vec<(String, u16)>,
+    /// The winner, or everyone sharing first place on a draw.
+    pub winners: Vec<String>,
+}
+
+/// A finished game with everything needed to replay it turn by turn.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ArchivedGame {
+    pub summary: GameSummary,
+    pub rules: GameConfig,
+    /// Player ids in seat order, to match turns to seats.
+    pub player_ids: Vec<Uuid>,
+    /// Final scorecards, in seat order.
+    pub scorecards: Vec<Scorecard>,
+    /// Every turn, in the order played.
+    pub turns: Vec<TurnRecord>,
+}
+
+impl ArchivedGame {
+    /// The seat that played `turn`.
+    pub fn seat(&self, turn: &TurnRecord) -> Option<usize> {
+        self.player_ids.iter().position(|&id| id == turn.player_id)
+    }
+}
+
+/// How often a player has scored or zeroed one category, and for how much.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CategoryStats {
+    pub category: Category,
+    /// Games the category was filled with points.
+    pub scored: u32,
+    /// Games the category was filled with a zero.
+    pub zeroed: u32,
+    /// Points scored in the category over all games.
+    pub points: u32,
+}
+
+impl CategoryStats {
+    pub fn new(category: Category) -> Self {
+        Self {
+            category,
+            scored: 0,
+            zeroed: 0,
+            points: 0,
+        }
+    }
+
+    pub fn record(&mut self, score: u16) {
+        if score == 0 {
+            self.zeroed += 1;
+        } else {
+            self.scored += 1;
+        }
+        self.points += score as u32;
+    }
+
+    pub fn games(&self) -> u32 {
+        self.scored + self.zeroed
+    }
+
+    pub fn average(&self) -> f64 {
+        match self.games() {
+            0 => 0.0,
+            n => self.points as f64 / n as f64,
+        }
+    }
+
+    pub fn zero_rate(&self) -> f64 {
+        match self.games() {
+            0 => 0.0,
+            n => self.zeroed as f64 / n as f64,
+        }
+    }
+}
+
+/// Lifetime tallies of how long a player's timed turns took.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TurnTimes {
+    pub turns: u32,
+    pub total_ms: u64,
+    pub slowest_ms: u64,
+}
+
+impl TurnTimes {
+    pub fn record(&mut self, duration_ms: u64) {
+        self.turns += 1;
+        self.total_ms += duration_ms;
+        self.slowest_ms = self.slowest_ms.max(duration_ms);
+    }
+
+    pub fn average_ms(&self) -> Option<u64> {
+        (self.turns > 0).then(|| self.total_ms / self.turns as u64)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct HeadToHead {
+    pub wins: u32,
+    pub losses: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PlayerInfo {
     pub id: Uuid,
     pub name: String,
     pub connected: bool,
+    /// Whether they are ready to start, in a waiting room.
+    #[serde(default)]
+    pub ready: bool,
+    /// The recipient's record against this player, if both have identities.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub head_to_head: Option<HeadToHead>,
+}
+
+/// A player's record over the games played back-to-back in one room.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SeriesStanding {
+    pub player_id: Uuid,
+    pub name: String,
+    pub games: u16,
+    pub wins: u16,
+    /// Points over all those games.
+    pub total: u32,
+}
+
+impl SeriesStanding {
+    /// Add a finished game's scores to `standings`, keeping them ordered by
+    /// wins, then total points.
+    pub fn record(standings: &mut Vec<SeriesStanding>, final_scores: &[(Uuid, String, u16)], winner_ids: &[Uuid]) {
+        for (id, name, score) in final_scores {
+            let standing = match standings.iter_mut().position(|s| s.player_id == *id) {
+                Some(i) => &mut standings[i],
+                None => {
+                    standings.push(SeriesStanding {
+                        player_id: *id,
+                        name: name.clone(),
+                        games: 0,
+                        wins: 0,
+                        total: 0,
+                    });
+                    standings.last_mut().unwrap()
+                }
+            };
+            standing.games += 1;
+            standing.total += u32::from(*score);
+            if winner_ids.contains(id) {
+                standing.wins += 1;
+            }
+        }
+        standings.sort_by(|a, b| b.wins.cmp(&a.wins).then(b.total.cmp(&a.total)));
+    }
 }
 
 // -- Serialization helpers --
 
+#[cfg(feature = "net")]
 pub fn serialize_message<T: Serialize>(msg: &T) -> Result<Bytes, serde_json::Error> {
     let json = serde_json::to_vec(msg)?;
     Ok(Bytes::from(json))
 }
 
+/// Spare capacity reserved before encoding each message.
+#[cfg(feature = "net")]
+const ENCODER_RESERVE: usize = 1024;
+
+#[derive(Debug, thiserror::Error)]
+pub enum EncodeError {
+    #[error(transparent)]
+    Serialize(#[from] serde_json::Error),
+    #[error("message is {len} bytes, over the {max} byte limit")]
+    TooLarge { len: usize, max: usize },
+}
+
+/// Check an encoded frame against a length limit before sending it, so an
+/// oversized message is reported instead of failing the transport.
+pub fn check_frame_length(frame: &[u8], max: usize) -> Result<(), EncodeError> {
+    if frame.len() > max {
+        return Err(EncodeError::TooLarge {
+            len: frame.len(),
+            max,
+        });
+    }
+    Ok(())
+}
+
+/// Serializes messages into a reused buffer, splitting each frame off the
+/// front. Once earlier frames have been sent and dropped their memory is
+/// reclaimed, so steady-state encoding does not allocate per message.
+#[cfg(feature = "net")]
+#[derive(Debug)]
+pub struct FrameEncoder {
+    buf: BytesMut,
+    max_frame_length: usize,
+}
+
+#[cfg(feature = "net")]
+impl Default for FrameEncoder {
+    fn default() -> Self {
+        Self::with_max_frame_length(DEFAULT_MAX_FRAME_LENGTH)
+    }
+}
+
+#[cfg(feature = "net")]
+impl FrameEncoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_max_frame_length(max_frame_length: usize) -> Self {
+        Self {
+            buf: BytesMut::new(),
+            max_frame_length,
+        }
+    }
+
+    pub fn encode<T: Serialize>(&mut self, msg: &T) -> Result<Bytes, EncodeError> {
+        self.buf.reserve(ENCODER_RESERVE);
+        if let Err(e) = serde_json::to_writer((&mut self.buf).writer(), msg) {
+            self.buf.clear();
+            return Err(e.into());
+        }
+        if let Err(e) = check_frame_length(&self.buf, self.max_frame_length) {
+            self.buf.clear();
+            return Err(e);
+        }
+        Ok(self.buf.split().freeze())
+    }
+}
+
+/// Serialize a `ServerMessage::GameState` straight from a `GameState`,
+/// borrowing instead of building a snapshot first. Includes the turn history,
+/// since this is what players joining mid-game catch up from.
+#[cfg(feature = "net")]
+pub fn serialize_game_state(game: &GameState) -> Result<Bytes, serde_json::Error> {
+    #[derive(Serialize)]
+    enum Borrowed<'a> {
+        GameState { game_state: GameStateView<'a> },
+    }
+    serialize_message(&Borrowed::GameState {
+        game_state: game.view_with_history(),
+    })
+}
+
+/// Deserialize directly from a received frame, without copying it first.
 pub fn deserialize_message<T: for<'de> Deserialize<'de>>(
     data: &[u8],
 ) -> Result<T, serde_json::Error> {
     serde_json::from_slice(data)
 }
 
+// -- Schema --
+
+/// JSON Schemas for `ClientMessage` and `ServerMessage`, keyed by name, for
+/// checking third-party clients against the real message shapes.
+#[cfg(feature = "schema")]
+pub fn json_schema() -> serde_json::Value {
+    serde_json::json!({
+        "ClientMessage": schemars::schema_for!(ClientMessage),
+        "ServerMessage": schemars::schema_for!(ServerMessage),
+    })
+}
+
 // -- Transport helpers --
 
+#[cfg(feature = "net")]
 pub async fn send_message<T: Serialize>(
     transport: &mut Transport,
     msg: &T,
 ) -> anyhow::Result<()> {
     let bytes = serialize_message(msg).map_err(|e| anyhow::anyhow!("serialize error: {}", e))?;
     transport
-        .send(bytes.into())
+        .send(bytes)
         .await
         .map_err(|e| anyhow::anyhow!("send error: {}", e))
 }
 
+#[cfg(feature = "net")]
 pub async fn recv_message<T: for<'de> Deserialize<'de>>(
     transport: &mut Transport,
 ) -> anyhow::Result<Option<T>> {
@@ -240,7 +931,7 @@ pub async fn recv_message<T: for<'de> Deserialize<'de>>(
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "net"))]
 mod tests {
     use super::*;
 
@@ -249,6 +940,9 @@ mod tests {
         let msg = ClientMessage::Hello {
             player_name: "Alice".into(),
             version: "0.1.0".into(),
+            identity: Some(Uuid::new_v4()),
+            resume_token: None,
+            protocol: PROTOCOL_VERSION,
         };
         let bytes = serialize_message(&msg).unwrap();
         let deserialized: ClientMessage = deserialize_message(&bytes).unwrap();
@@ -256,6 +950,7 @@ mod tests {
             ClientMessage::Hello {
                 player_name,
                 version,
+                ..
             } => {
                 assert_eq!(player_name, "Alice");
                 assert_eq!(version, "0.1.0");
@@ -264,12 +959,67 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_client_message_name_matches_tag() {
+        let messages = [
+            ClientMessage::RollDice,
+            ClientMessage::HoldDice { held: [false; 5] },
+            ClientMessage::GetLeaderboard { season: None },
+        ];
+        for msg in messages {
+            let json = serde_json::to_string(&msg).unwrap();
+            let tag = json.trim_start_matches(['{', '"']);
+            assert!(tag.starts_with(msg.name()), "{} vs {}", json, msg.name());
+        }
+    }
+
+    /// Variant names listed in a message enum's schema.
+    #[cfg(feature = "schema")]
+    fn schema_variants(schema: &serde_json::Value) -> Vec<String> {
+        let mut names = Vec::new();
+        for variant in schema["oneOf"].as_array().unwrap() {
+            if let Some(unit) = variant["enum"].as_array() {
+                names.extend(unit.iter().map(|v| v.as_str().unwrap().to_string()));
+            } else {
+                names.push(variant["required"][0].as_str().unwrap().to_string());
+            }
+        }
+        names
+    }
+
+    #[cfg(feature = "schema")]
+    #[test]
+    fn test_json_schema_covers_messages() {
+        let schema = json_schema();
+        let client = schema_variants(&schema["ClientMessage"]);
+        for msg in [
+            ClientMessage::RollDice,
+            ClientMessage::HoldDice { held: [false; 5] },
+            ClientMessage::GetLeaderboard { season: None },
+        ] {
+            assert!(client.contains(&msg.name().to_string()), "{} missing", msg.name());
+        }
+        assert_eq!(client.len(), 25);
+
+        let server = schema_variants(&schema["ServerMessage"]);
+        for name in ["Welcome", "GameStarted", "GameOver", "ReplayEvent", "Pong"] {
+            assert!(server.contains(&name.to_string()), "{} missing", name);
+        }
+
+        // Scorecards go over the wire as a map of used categories
+        let scorecard = &schema["ServerMessage"]["$defs"]["Scorecard"]["properties"]["scores"];
+        assert_eq!(scorecard["type"], "object");
+    }
+
     #[test]
     fn test_server_message_serialization() {
         let id = Uuid::new_v4();
         let msg = ServerMessage::Welcome {
             player_id: id,
             server_version: "0.1.0".into(),
+            resume_token: Some(Uuid::new_v4()),
+            resumed: false,
+            protocol: PROTOCOL_VERSION,
         };
         let bytes = serialize_message(&msg).unwrap();
         let deserialized: ServerMessage = deserialize_message(&bytes).unwrap();
@@ -277,9 +1027,15 @@ mod tests {
             ServerMessage::Welcome {
                 player_id,
                 server_version,
+                resume_token,
+                resumed,
+                protocol,
             } => {
                 assert_eq!(player_id, id);
                 assert_eq!(server_version, "0.1.0");
+                assert!(resume_token.is_some());
+                assert!(!resumed);
+                assert_eq!(protocol, PROTOCOL_VERSION);
             }
             _ => panic!("wrong variant"),
         }
@@ -288,27 +1044,54 @@ mod tests {
     #[test]
     fn test_game_over_serialization() {
         let winner = Uuid::new_v4();
+        let mut scorecard = Scorecard::new();
+        scorecard.record(Category::Yahtzee, 50).unwrap();
+        scorecard.add_yahtzee_bonus();
         let msg = ServerMessage::GameOver {
             final_scores: vec![
                 (winner, "Alice".into(), 250),
                 (Uuid::new_v4(), "Bob".into(), 200),
             ],
-            winner_id: winner,
+            winner_ids: vec![winner],
+            scorecards: vec![scorecard, Scorecard::new()],
+            duration_secs: 600,
+            round_totals: vec![vec![50, 150], vec![0, 0]],
+            average_turn_ms: vec![Some(12_500), None],
         };
         let bytes = serialize_message(&msg).unwrap();
         let deserialized: ServerMessage = deserialize_message(&bytes).unwrap();
         match deserialized {
             ServerMessage::GameOver {
                 final_scores,
-                winner_id,
+                winner_ids,
+                scorecards,
+                duration_secs,
+                round_totals,
+                average_turn_ms,
             } => {
                 assert_eq!(final_scores.len(), 2);
-                assert_eq!(winner_id, winner);
+                assert_eq!(average_turn_ms, [Some(12_500), None]);
+                assert_eq!(round_totals[0], [50, 150]);
+                assert_eq!(winner_ids, vec![winner]);
+                assert_eq!(scorecards[0].score(Category::Yahtzee), Some(50));
+                assert_eq!(scorecards[0].yahtzee_bonus_count, 1);
+                assert_eq!(duration_secs, 600);
             }
             _ => panic!("wrong variant"),
         }
     }
 
+    #[test]
+    fn test_game_over_from_older_server() {
+        let json = r#"{"GameOver":{"final_scores":[],"winner_ids":[]}}"#;
+        let msg: ServerMessage = deserialize_message(json.as_bytes()).unwrap();
+        assert!(matches!(
+            msg,
+            ServerMessage::GameOver { scorecards, duration_secs: 0, round_totals, average_turn_ms, .. }
+                if scorecards.is_empty() && round_totals.is_empty() && average_turn_ms.is_empty()
+        ));
+    }
+
     #[test]
     fn test_all_client_messages_serialize() {
         let room_id = Uuid::new_v4();
@@ -316,17 +1099,49 @@ mod tests {
             ClientMessage::Hello {
                 player_name: "Test".into(),
                 version: "0.1.0".into(),
+                identity: None,
+                resume_token: Some(Uuid::new_v4()),
+                protocol: PROTOCOL_VERSION,
             },
             ClientMessage::CreateRoom {
                 room_name: "Room1".into(),
                 max_players: 4,
                 password: None,
+                rules: GameConfig {
+                    sudden_death: true,
+                    ..GameConfig::default()
+                },
+            },
+            ClientMessage::JoinRoom {
+                room_id,
+                password: None,
+                spectate_if_started: true,
             },
-            ClientMessage::JoinRoom { room_id, password: None },
             ClientMessage::LeaveRoom,
             ClientMessage::ListRooms,
             ClientMessage::StartGame,
-            ClientMessage::SpectateRoom { room_id },
+            ClientMessage::UpdateRoomSettings {
+                max_players: Some(3),
+                password: Some("secret".into()),
+                rules: Some(GameConfig {
+                    turn_seconds: Some(60),
+                    ..GameConfig::default()
+                }),
+                locked: Some(true),
+                auto_start: Some(true),
+                max_spectators: Some(4),
+                seed: Some(42),
+                announcements: Some(Announcements::All),
+                shuffle_order: Some(true),
+                nudge_secs: None,
+            },
+            ClientMessage::SetReady { ready: true },
+            ClientMessage::MoveSeat { seat: 2 },
+            ClientMessage::Kick { player_id: room_id },
+            ClientMessage::SpectateRoom {
+                room_id,
+                catch_up: true,
+            },
             ClientMessage::RollDice,
             ClientMessage::HoldDice {
                 held: [true, false, true, false, true],
@@ -337,6 +1152,19 @@ mod tests {
             ClientMessage::Chat {
                 message: "hello".into(),
             },
+            ClientMessage::Whisper {
+                to: "Bob".into(),
+                message: "psst".into(),
+            },
+            ClientMessage::Tracked {
+                request_id: 1,
+                action: Box::new(ClientMessage::RollDice),
+            },
+            ClientMessage::GetLeaderboard { season: None },
+            ClientMessage::GetLeaderboard { season: Some(2) },
+            ClientMessage::GetPlayerStats { player: "Test".into() },
+            ClientMessage::GetRecentGames { player: Some("Test".into()) },
+            ClientMessage::GetGame { game_id: room_id },
             ClientMessage::Ping,
             ClientMessage::Disconnect,
         ];
@@ -346,4 +1174,253 @@ mod tests {
             let _: ClientMessage = deserialize_message(&bytes).unwrap();
         }
     }
+
+    #[test]
+    fn test_archived_game_round_trip() {
+        let alice = Uuid::new_v4();
+        let bob = Uuid::new_v4();
+        let turn = |player_id, category, score| TurnRecord {
+            player_id,
+            round: 1,
+            rolls: vec![crate::game::RollRecord {
+                held: [false; 5],
+                dice: [2, 2, 3, 3, 3],
+            }],
+            category,
+            score,
+            yahtzee_bonus: false,
+            duration_ms: Some(8_000),
+        };
+        let msg = ServerMessage::GameDetails {
+            game: Box::new(ArchivedGame {
+                summary: GameSummary {
+                    game_id: Uuid::new_v4(),
+                    room_name: "table".into(),
+                    finished_at: 1_760_000_000,
+                    duration_secs: 300,
+                    players: vec![("Alice".into(), 25), ("Bob".into(), 13)],
+                    winners: vec!["Alice".into()],
+                },
+                rules: GameConfig::default(),
+                player_ids: vec![alice, bob],
+                scorecards: vec![Scorecard::new(), Scorecard::new()],
+                turns: vec![turn(alice, Category::FullHouse, 25), turn(bob, Category::Chance, 13)],
+            }),
+        };
+        let bytes = serialize_message(&msg).unwrap();
+        match deserialize_message(&bytes).unwrap() {
+            ServerMessage::GameDetails { game } => {
+                assert_eq!(game.summary.winners, ["Alice"]);
+                assert_eq!(game.turns.len(), 2);
+                assert_eq!(game.seat(&game.turns[1]), Some(1));
+            }
+            other => panic!("wrong variant: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_category_stats_tally() {
+        let mut stats = CategoryStats::new(Category::LargeStraight);
+        assert_eq!(stats.average(), 0.0);
+        assert_eq!(stats.zero_rate(), 0.0);
+
+        for score in [40, 0, 40, 0] {
+            stats.record(score);
+        }
+        assert_eq!((stats.scored, stats.zeroed, stats.points), (2, 2, 80));
+        assert_eq!(stats.games(), 4);
+        assert_eq!(stats.average(), 20.0);
+        assert_eq!(stats.zero_rate(), 0.5);
+    }
+
+    #[test]
+    fn test_turn_times_tally() {
+        let mut times = TurnTimes::default();
+        assert_eq!(times.average_ms(), None);
+        for ms in [4_000, 20_000, 9_000] {
+            times.record(ms);
+        }
+        assert_eq!(times.average_ms(), Some(11_000));
+        assert_eq!(times.slowest_ms, 20_000);
+    }
+
+    #[test]
+    fn test_spectate_room_defaults_to_live() {
+        let room_id = Uuid::new_v4();
+        let json = format!(r#"{{"SpectateRoom":{{"room_id":"{}"}}}}"#, room_id);
+        let msg: ClientMessage = deserialize_message(json.as_bytes()).unwrap();
+        assert!(matches!(msg, ClientMessage::SpectateRoom { catch_up: false, .. }));
+    }
+
+    #[test]
+    fn test_replay_event_serialization() {
+        let msg = ServerMessage::ReplayEvent {
+            event: Box::new(ServerMessage::TurnEnded {
+                player_id: Uuid::new_v4(),
+            }),
+        };
+        let bytes = serialize_message(&msg).unwrap();
+        let deserialized: ServerMessage = deserialize_message(&bytes).unwrap();
+        match deserialized {
+            ServerMessage::ReplayEvent { event } => {
+                assert!(matches!(*event, ServerMessage::TurnEnded { .. }));
+            }
+            _ => panic!("wrong variant"),
+        }
+    }
+
+    #[test]
+    fn test_hello_without_identity() {
+        let json = r#"{"Hello":{"player_name":"Old","version":"0.1.0"}}"#;
+        let msg: ClientMessage = deserialize_message(json.as_bytes()).unwrap();
+        assert!(matches!(
+            msg,
+            ClientMessage::Hello { identity: None, resume_token: None, protocol: 1, .. }
+        ));
+    }
+
+    #[test]
+    fn test_welcome_from_older_server() {
+        let json = r#"{"Welcome":{"player_id":"00000000-0000-0000-0000-000000000001","server_version":"0.1.0"}}"#;
+        let msg: ServerMessage = deserialize_message(json.as_bytes()).unwrap();
+        assert!(matches!(
+            msg,
+            ServerMessage::Welcome { resume_token: None, resumed: false, protocol: 1, .. }
+        ));
+    }
+
+    #[test]
+    fn test_system_message_milestone_optional() {
+        let json = r#"{"SystemMessage":{"message":"hi"}}"#;
+        let msg: ServerMessage = deserialize_message(json.as_bytes()).unwrap();
+        assert!(matches!(msg, ServerMessage::SystemMessage { milestone: None, .. }));
+
+        let msg = ServerMessage::SystemMessage {
+            message: "Final round!".into(),
+            milestone: Some(Milestone::FinalRound),
+        };
+        let bytes = serialize_message(&msg).unwrap();
+        let back: ServerMessage = deserialize_message(&bytes).unwrap();
+        assert!(matches!(back, ServerMessage::SystemMessage { milestone: Some(Milestone::FinalRound), .. }));
+    }
+
+    #[test]
+    fn test_announcement_levels() {
+        assert!(!Announcements::Off.includes(Milestone::Yahtzee));
+        assert!(Announcements::Highlights.includes(Milestone::FinalRound));
+        assert!(!Announcements::Highlights.includes(Milestone::TookLead));
+        assert!(Announcements::All.includes(Milestone::UpperBonus));
+    }
+
+    #[test]
+    fn test_series_standings_accumulate() {
+        let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut standings = Vec::new();
+        SeriesStanding::record(&mut standings, &[(alice, "alice".into(), 250), (bob, "bob".into(), 200)], &[alice]);
+        SeriesStanding::record(&mut standings, &[(alice, "alice".into(), 150), (bob, "bob".into(), 300)], &[bob]);
+        assert_eq!(standings[0].player_id, bob, "level on wins, bob has more points");
+        assert_eq!((standings[0].games, standings[0].wins, standings[0].total), (2, 1, 500));
+        assert_eq!((standings[1].games, standings[1].wins, standings[1].total), (2, 1, 400));
+
+        // A draw is a win for everyone on top
+        SeriesStanding::record(&mut standings, &[(alice, "alice".into(), 200), (bob, "bob".into(), 200)], &[alice, bob]);
+        assert!(standings.iter().all(|s| s.wins == 2));
+    }
+
+    #[test]
+    fn test_handshake_error_without_mismatch() {
+        let json = r#"{"HandshakeError":{"reason":"Expected Hello message"}}"#;
+        let msg: ServerMessage = deserialize_message(json.as_bytes()).unwrap();
+        assert!(matches!(msg, ServerMessage::HandshakeError { version_mismatch: None, .. }));
+    }
+
+    #[test]
+    fn test_negotiate_protocol() {
+        assert_eq!(negotiate_protocol(MIN_PROTOCOL_VERSION), Some(MIN_PROTOCOL_VERSION));
+        assert_eq!(negotiate_protocol(PROTOCOL_VERSION), Some(PROTOCOL_VERSION));
+        // Newer clients fall back to what the server speaks
+        assert_eq!(negotiate_protocol(PROTOCOL_VERSION + 1), Some(PROTOCOL_VERSION));
+        assert_eq!(negotiate_protocol(MIN_PROTOCOL_VERSION - 1), None);
+    }
+
+    #[test]
+    fn test_player_info_head_to_head_optional() {
+        let info = PlayerInfo {
+            id: Uuid::new_v4(),
+            name: "Bob".into(),
+            connected: true,
+            ready: false,
+            head_to_head: None,
+        };
+        let json = serde_json::to_string(&info).unwrap();
+        assert!(!json.contains("head_to_head"));
+
+        let with_record = PlayerInfo {
+            head_to_head: Some(HeadToHead { wins: 7, losses: 3 }),
+            ..info
+        };
+        let bytes = serde_json::to_vec(&with_record).unwrap();
+        let parsed: PlayerInfo = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(parsed.head_to_head, Some(HeadToHead { wins: 7, losses: 3 }));
+    }
+
+    #[test]
+    fn test_serialize_game_state_matches_owned_message() {
+        let mut game = GameState::new(
+            vec![
+                crate::player::Player::new(Uuid::new_v4(), "Alice".into()),
+                crate::player::Player::new(Uuid::new_v4(), "Bob".into()),
+            ],
+            GameConfig::default(),
+        );
+        game.start().unwrap();
+        let alice = game.players[0].id;
+        game.roll_dice_fixed(alice, [2, 3, 4, 5, 6]).unwrap();
+        game.score_category(alice, Category::LargeStraight).unwrap();
+
+        let bytes = serialize_game_state(&game).unwrap();
+        let msg: ServerMessage = deserialize_message(&bytes).unwrap();
+        match msg {
+            ServerMessage::GameState { game_state } => {
+                assert_eq!(game_state.players.len(), 2);
+                assert_eq!(game_state.players[1].name, "Bob");
+                assert_eq!(game_state.turn_history, Some(game.turn_history.clone()));
+            }
+            _ => panic!("Expected GameState"),
+        }
+    }
+
+    #[test]
+    fn test_frame_encoder_matches_serialize_message() {
+        let mut encoder = FrameEncoder::new();
+        let messages = [
+            ServerMessage::Pong,
+            ServerMessage::SystemMessage {
+                message: "x".repeat(3000),
+                milestone: None,
+            },
+            ServerMessage::RoomLeft,
+        ];
+        let frames: Vec<Bytes> = messages.iter().map(|m| encoder.encode(m).unwrap()).collect();
+        for (frame, msg) in frames.iter().zip(&messages) {
+            assert_eq!(frame, &serialize_message(msg).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_frame_encoder_rejects_oversized_messages() {
+        let mut encoder = FrameEncoder::with_max_frame_length(100);
+        let big = ServerMessage::SystemMessage {
+            message: "x".repeat(200),
+            milestone: None,
+        };
+        assert!(matches!(
+            encoder.encode(&big),
+            Err(EncodeError::TooLarge { max: 100, .. })
+        ));
+
+        // The rejected message leaves nothing behind in the buffer
+        let frame = encoder.encode(&ServerMessage::Pong).unwrap();
+        assert_eq!(frame, serialize_message(&ServerMessage::Pong).unwrap());
+    }
 }
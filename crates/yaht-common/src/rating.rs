@@ -0,0 +1,87 @@
+// -- Elo ratings --
+
+pub const INITIAL_RATING: f64 = 1500.0;
+const K_FACTOR: f64 = 32.0;
+
+/// Expected score of a player rated `rating` against `opponent` (0.0-1.0).
+pub fn expected_score(rating: f64, opponent: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((opponent - rating) / 400.0))
+}
+
+/// Rating changes for a finished game, given each player's rating and final score.
+///
+/// Multiplayer games are treated as a round of pairwise matches decided by
+/// final score, with K split across opponents so a game is worth the same
+/// total regardless of table size.
+pub fn rating_changes(players: &[(f64, u16)]) -> Vec<f64> {
+    if players.len() < 2 {
+        return vec![0.0; players.len()];
+    }
+    let k = K_FACTOR / (players.len() - 1) as f64;
+
+    players
+        .iter()
+        .enumerate()
+        .map(|(i, &(rating, score))| {
+            players
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .map(|(_, &(opp_rating, opp_score))| {
+                    let actual = match score.cmp(&opp_score) {
+                        std::cmp::Ordering::Greater => 1.0,
+                        std::cmp::Ordering::Equal => 0.5,
+                        std::cmp::Ordering::Less => 0.0,
+                    };
+                    k * (actual - expected_score(rating, opp_rating))
+                })
+                .sum()
+        })
+        .collect()
+}
+
+/// Pull a rating back towards the initial rating, keeping `keep` (0.0-1.0)
+/// of its distance. Used at season boundaries.
+pub fn soft_reset(rating: f64, keep: f64) -> f64 {
+    INITIAL_RATING + (rating - INITIAL_RATING) * keep
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_even_two_player_game() {
+        let changes = rating_changes(&[(1500.0, 250), (1500.0, 200)]);
+        assert!((changes[0] - 16.0).abs() < 1e-9);
+        assert!((changes[1] + 16.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_changes_sum_to_zero() {
+        let changes = rating_changes(&[(1600.0, 180), (1450.0, 240), (1500.0, 240), (1700.0, 120)]);
+        assert_eq!(changes.len(), 4);
+        assert!(changes.iter().sum::<f64>().abs() < 1e-9);
+        // The favourite finishing last loses the most
+        assert!(changes[3] < changes[0]);
+    }
+
+    #[test]
+    fn test_upset_gains_more() {
+        let underdog = rating_changes(&[(1300.0, 250), (1700.0, 200)]);
+        let favourite = rating_changes(&[(1700.0, 250), (1300.0, 200)]);
+        assert!(underdog[0] > favourite[0]);
+    }
+
+    #[test]
+    fn test_single_player_unrated() {
+        assert_eq!(rating_changes(&[(1500.0, 300)]), vec![0.0]);
+    }
+
+    #[test]
+    fn test_soft_reset() {
+        assert_eq!(soft_reset(1700.0, 0.5), 1600.0);
+        assert_eq!(soft_reset(1300.0, 0.5), 1400.0);
+        assert_eq!(soft_reset(1800.0, 0.0), INITIAL_RATING);
+    }
+}
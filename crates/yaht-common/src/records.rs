@@ -0,0 +1,134 @@
+// -- Personal bests --
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::player::Scorecard;
+use crate::scoring::Category;
+
+/// A player's best results over all their finished games.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PersonalBests {
+    pub games: u32,
+    /// Highest final total.
+    pub best_game: u16,
+    /// Highest score in a single box, and the box it was in.
+    pub best_category: Option<(Category, u16)>,
+    /// Most Yahtzees in one game, bonus Yahtzees included.
+    pub most_yahtzees: u8,
+}
+
+/// A personal record broken by a finished game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Milestone {
+    BestGame(u16),
+    BestCategory(Category, u16),
+    MostYahtzees(u8),
+}
+
+impl fmt::Display for Milestone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Milestone::BestGame(total) => write!(f, "best game ({})", total),
+            Milestone::BestCategory(category, score) => {
+                write!(f, "best single box ({} in {})", score, category.display_name())
+            }
+            Milestone::MostYahtzees(1) => write!(f, "first Yahtzee"),
+            Milestone::MostYahtzees(count) => write!(f, "most Yahtzees in a game ({})", count),
+        }
+    }
+}
+
+impl PersonalBests {
+    /// Fold a finished game into the bests. Returns the records it broke;
+    /// a player's first game sets the bar without breaking anything, except
+    /// for a first Yahtzee.
+    pub fn record(&mut self, scorecard: &Scorecard, final_score: u16) -> Vec<Milestone> {
+        let mut broken = Vec::new();
+        let counts = self.games > 0;
+        self.games += 1;
+
+        if final_score > self.best_game {
+            if counts {
+                broken.push(Milestone::BestGame(final_score));
+            }
+            self.best_game = final_score;
+        }
+
+        // The first box in `Category::ALL` order wins a tie within the game
+        let best_box = scorecard
+            .scores()
+            .fold(None, |best: Option<(Category, u16)>, (cat, score)| match best {
+                Some((_, top)) if top >= score => best,
+                _ => Some((cat, score)),
+            });
+        if let Some((category, score)) = best_box {
+            if self.best_category.is_none_or(|(_, top)| score > top) {
+                if counts {
+                    broken.push(Milestone::BestCategory(category, score));
+                }
+                self.best_category = Some((category, score));
+            }
+        }
+
+        let yahtzees = (scorecard.score(Category::Yahtzee) == Some(50)) as u8 + scorecard.yahtzee_bonus_count;
+        if yahtzees > self.most_yahtzees {
+            broken.push(Milestone::MostYahtzees(yahtzees));
+            self.most_yahtzees = yahtzees;
+        }
+
+        broken
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(scores: &[(Category, u16)], bonuses: u8) -> Scorecard {
+        let mut card = Scorecard::new();
+        for &(cat, score) in scores {
+            card.record(cat, score).unwrap();
+        }
+        card.yahtzee_bonus_count = bonuses;
+        card
+    }
+
+    #[test]
+    fn test_first_game_sets_the_bar_quietly() {
+        let mut bests = PersonalBests::default();
+        let broken = bests.record(&card(&[(Category::FullHouse, 25), (Category::Chance, 22)], 0), 47);
+        assert!(broken.is_empty());
+        assert_eq!(bests.best_game, 47);
+        assert_eq!(bests.best_category, Some((Category::FullHouse, 25)));
+        assert_eq!(bests.games, 1);
+    }
+
+    #[test]
+    fn test_later_games_break_records() {
+        let mut bests = PersonalBests::default();
+        bests.record(&card(&[(Category::FullHouse, 25)], 0), 200);
+
+        let broken = bests.record(&card(&[(Category::SmallStraight, 30)], 0), 180);
+        assert_eq!(broken, [Milestone::BestCategory(Category::SmallStraight, 30)]);
+
+        let broken = bests.record(&card(&[(Category::Chance, 30)], 0), 230);
+        assert_eq!(broken, [Milestone::BestGame(230)], "a tied box is not a record");
+        assert_eq!(bests.best_category, Some((Category::SmallStraight, 30)));
+    }
+
+    #[test]
+    fn test_yahtzee_counts_include_bonuses() {
+        let mut bests = PersonalBests::default();
+        let broken = bests.record(&card(&[(Category::Yahtzee, 50)], 0), 150);
+        assert_eq!(broken, [Milestone::MostYahtzees(1)]);
+        assert_eq!(Milestone::MostYahtzees(1).to_string(), "first Yahtzee");
+
+        let broken = bests.record(&card(&[(Category::Yahtzee, 50)], 2), 120);
+        assert_eq!(broken, [Milestone::MostYahtzees(3)]);
+        assert_eq!(broken[0].to_string(), "most Yahtzees in a game (3)");
+        assert!(bests.record(&card(&[(Category::Yahtzee, 0)], 0), 100).is_empty());
+    }
+}
@@ -0,0 +1,106 @@
+//! Plain-text scoreboards, for sharing a finished game where only text
+//! goes, such as a chat message.
+
+use crate::player::Scorecard;
+use crate::scoring::Category;
+
+/// Longest player name shown; longer ones are cut short.
+const MAX_NAME_CHARS: usize = 12;
+/// Room for the numbers in a player's column.
+const MIN_COLUMN: usize = 5;
+const YAHTZEE_BONUS_LABEL: &str = "Yahtzee bonus";
+
+/// A table of `players`' scorecards side by side, one column each, with
+/// the upper bonus, Yahtzee bonuses and totals. Boxes not yet scored show
+/// as `-`. Only ASCII is used for the rules, so it lines up in any
+/// monospaced font.
+pub fn render(players: &[(&str, &Scorecard)]) -> String {
+    let names: Vec<String> = players
+        .iter()
+        .map(|(name, _)| name.chars().take(MAX_NAME_CHARS).collect())
+        .collect();
+    let mut table = Table {
+        out: String::new(),
+        label_width: Category::ALL
+            .iter()
+            .map(|c| c.display_name().len())
+            .chain([YAHTZEE_BONUS_LABEL.len()])
+            .max()
+            .unwrap_or(0),
+        widths: names.iter().map(|n| n.chars().count().max(MIN_COLUMN)).collect(),
+    };
+    let boxes = |category: Category| -> Vec<String> {
+        players
+            .iter()
+            .map(|(_, card)| card.score(category).map_or("-".to_string(), |s| s.to_string()))
+            .collect()
+    };
+    let totals = |total: fn(&Scorecard) -> u16| -> Vec<String> {
+        players.iter().map(|(_, card)| total(card).to_string()).collect()
+    };
+
+    table.row("", &names);
+    table.rule('-');
+    for category in Category::UPPER {
+        table.row(category.display_name(), &boxes(category));
+    }
+    table.row("Upper bonus", &totals(Scorecard::upper_bonus));
+    table.rule('-');
+    for &category in &Category::ALL[Category::UPPER.len()..] {
+        table.row(category.display_name(), &boxes(category));
+    }
+    table.row(YAHTZEE_BONUS_LABEL, &totals(Scorecard::yahtzee_bonus_total));
+    table.rule('=');
+    table.row("Total", &totals(Scorecard::grand_total));
+    table.out
+}
+
+struct Table {
+    out: String,
+    label_width: usize,
+    widths: Vec<usize>,
+}
+
+impl Table {
+    fn row(&mut self, label: &str, cells: &[String]) {
+        self.out.push_str(&format!("{:<1$}", label, self.label_width));
+        for (cell, width) in cells.iter().zip(&self.widths) {
+            self.out.push_str(&format!(" | {:>1$}", cell, width));
+        }
+        self.out.push('\n');
+    }
+
+    fn rule(&mut self, fill: char) {
+        let line = |width: usize| fill.to_string().repeat(width);
+        self.out.push_str(&line(self.label_width));
+        for &width in &self.widths {
+            self.out.push_str(&format!("{}+{}", fill, line(width + 1)));
+        }
+        self.out.push('\n');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_lines_up_columns() {
+        let mut alice = Scorecard::new();
+        for category in Category::UPPER {
+            alice.record(category, 4 * (category.index() as u16 + 1)).unwrap();
+        }
+        alice.record(Category::Yahtzee, 50).unwrap();
+        alice.add_yahtzee_bonus();
+        let bob = Scorecard::new();
+
+        let text = render(&[("alice", &alice), ("a very long player name", &bob)]);
+        let lines: Vec<&str> = text.lines().collect();
+        assert!(lines.iter().all(|l| l.len() == lines[0].len()), "ragged table:\n{}", text);
+        assert!(lines[0].ends_with("| alice | a very long "));
+        assert!(text.contains("\nUpper bonus   |    35 |            0\n"));
+        assert!(text.contains("\nYAHTZEE       |    50 |            -\n"));
+        assert!(lines.last().unwrap().starts_with("Total         |   269 |"));
+        assert!(text.is_ascii());
+    }
+}
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum Category {
     // Upper section
     Ones,
@@ -45,6 +46,11 @@ impl Category {
         Category::Sixes,
     ];
 
+    /// Position in `Category::ALL`.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
     pub fn is_upper(&self) -> bool {
         matches!(
             self,
@@ -80,6 +86,67 @@ pub const UPPER_BONUS_THRESHOLD: u16 = 63;
 pub const UPPER_BONUS_VALUE: u16 = 35;
 pub const YAHTZEE_BONUS_VALUE: u16 = 100;
 
+/// Highest bonus the rules may award, upper or Yahtzee.
+pub const MAX_BONUS_VALUE: u16 = 1000;
+/// The upper section's highest possible total, five of each face.
+pub const MAX_UPPER_TOTAL: u16 = 105;
+
+/// How a scorecard's bonuses are totalled and where a Joker may go. The
+/// defaults are the standard rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct ScoringRules {
+    /// Upper section points needed for the upper bonus.
+    pub upper_bonus_threshold: u16,
+    pub upper_bonus: u16,
+    /// Points for each Yahtzee after 50 in the Yahtzee box.
+    pub yahtzee_bonus: u16,
+    pub joker: JokerRule,
+}
+
+impl ScoringRules {
+    pub fn is_standard(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// These rules with bonuses and threshold held to what a game can use.
+    pub fn clamped(self) -> Self {
+        Self {
+            upper_bonus_threshold: self.upper_bonus_threshold.min(MAX_UPPER_TOTAL),
+            upper_bonus: self.upper_bonus.min(MAX_BONUS_VALUE),
+            yahtzee_bonus: self.yahtzee_bonus.min(MAX_BONUS_VALUE),
+            ..self
+        }
+    }
+}
+
+impl Default for ScoringRules {
+    fn default() -> Self {
+        Self {
+            upper_bonus_threshold: UPPER_BONUS_THRESHOLD,
+            upper_bonus: UPPER_BONUS_VALUE,
+            yahtzee_bonus: YAHTZEE_BONUS_VALUE,
+            joker: JokerRule::default(),
+        }
+    }
+}
+
+/// What a Yahtzee rolled after 50 in the Yahtzee box may be scored as.
+/// The bonus is paid either way.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum JokerRule {
+    /// Full value in Full House and the straights, but only in the matching
+    /// upper box while it is open, else in an open lower box.
+    #[default]
+    Forced,
+    /// Full value in Full House and the straights, in any open box.
+    Free,
+    /// No Joker: the dice score only what they make, in any open box.
+    Off,
+}
+
 /// Compute the score for a given category and dice values.
 pub fn compute_score(category: Category, dice: &[u8; 5]) -> u16 {
     match category {
@@ -165,6 +232,27 @@ pub fn compute_score_joker(category: Category, dice: &[u8; 5], joker_active: boo
     }
 }
 
+/// Whether some roll could score `score` in `category`, Joker rules included.
+pub fn is_possible_score(category: Category, score: u16) -> bool {
+    match category {
+        Category::Ones
+        | Category::Twos
+        | Category::Threes
+        | Category::Fours
+        | Category::Fives
+        | Category::Sixes => {
+            let face = category.index() as u16 + 1;
+            score.is_multiple_of(face) && score <= 5 * face
+        }
+        Category::ThreeOfAKind | Category::FourOfAKind => score == 0 || (5..=30).contains(&score),
+        Category::FullHouse => matches!(score, 0 | 25),
+        Category::SmallStraight => matches!(score, 0 | 30),
+        Category::LargeStraight => matches!(score, 0 | 40),
+        Category::Yahtzee => matches!(score, 0 | 50),
+        Category::Chance => (5..=30).contains(&score),
+    }
+}
+
 fn count_value(dice: &[u8; 5], val: u8) -> u16 {
     dice.iter().filter(|&&d| d == val).count() as u16 * val as u16
 }
@@ -187,8 +275,8 @@ fn has_n_of_a_kind(dice: &[u8; 5], n: u8) -> bool {
 
 fn is_full_house(dice: &[u8; 5]) -> bool {
     let counts = value_counts(dice);
-    let has_three = counts.iter().any(|&c| c == 3);
-    let has_two = counts.iter().any(|&c| c == 2);
+    let has_three = counts.contains(&3);
+    let has_two = counts.contains(&2);
     has_three && has_two
 }
 
@@ -210,6 +298,30 @@ fn is_yahtzee(dice: &[u8; 5]) -> bool {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_possible_scores() {
+        assert!(is_possible_score(Category::Ones, 5));
+        assert!(!is_possible_score(Category::Ones, 6));
+        assert!(is_possible_score(Category::Sixes, 24));
+        assert!(!is_possible_score(Category::Sixes, 25));
+        assert!(is_possible_score(Category::FullHouse, 25));
+        assert!(!is_possible_score(Category::FullHouse, 20));
+        assert!(!is_possible_score(Category::Chance, 0));
+        assert!(!is_possible_score(Category::ThreeOfAKind, 31));
+
+        // Every roll scores something possible in every category
+        let mut dice = [1u8; 5];
+        for n in 0..6u32.pow(5) {
+            for (i, die) in dice.iter_mut().enumerate() {
+                *die = (n / 6u32.pow(i as u32) % 6) as u8 + 1;
+            }
+            for cat in Category::ALL {
+                assert!(is_possible_score(cat, compute_score(cat, &dice)), "{:?} {:?}", cat, dice);
+                assert!(is_possible_score(cat, compute_score_joker(cat, &dice, true)));
+            }
+        }
+    }
+
     // Upper section tests
     #[test]
     fn test_ones() {
@@ -0,0 +1,193 @@
+use uuid::Uuid;
+
+use crate::game::{GameError, GameState};
+use crate::scoring::Category;
+
+// -- Scripted play over a GameState (tutorials) --
+
+/// A player action fed into a script.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScriptAction {
+    Roll,
+    Hold([bool; 5]),
+    Score(Category),
+}
+
+/// What a script step waits for before moving on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScriptExpect {
+    /// Roll with exactly `holds` held; the unheld dice land on `faces`.
+    Roll { holds: [bool; 5], faces: [u8; 5] },
+    /// Score this category.
+    Score(Category),
+}
+
+#[derive(Debug, Clone)]
+pub struct ScriptStep {
+    pub prompt: String,
+    pub expect: ScriptExpect,
+}
+
+impl ScriptStep {
+    pub fn roll(prompt: impl Into<String>, holds: [bool; 5], faces: [u8; 5]) -> Self {
+        Self {
+            prompt: prompt.into(),
+            expect: ScriptExpect::Roll { holds, faces },
+        }
+    }
+
+    pub fn score(prompt: impl Into<String>, category: Category) -> Self {
+        Self {
+            prompt: prompt.into(),
+            expect: ScriptExpect::Score(category),
+        }
+    }
+}
+
+/// A game driven by a fixed sequence of steps with predetermined dice.
+#[derive(Debug, Clone)]
+pub struct ScriptedGame {
+    pub game: GameState,
+    player_id: Uuid,
+    steps: Vec<ScriptStep>,
+    index: usize,
+}
+
+impl ScriptedGame {
+    pub fn new(game: GameState, player_id: Uuid, steps: Vec<ScriptStep>) -> Self {
+        Self {
+            game,
+            player_id,
+            steps,
+            index: 0,
+        }
+    }
+
+    pub fn current_step(&self) -> Option<&ScriptStep> {
+        self.steps.get(self.index)
+    }
+
+    /// Zero-based index of the current step.
+    pub fn step_index(&self) -> usize {
+        self.index
+    }
+
+    pub fn step_count(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.index >= self.steps.len()
+    }
+
+    /// Apply a player action. Holds are free-form; rolls and scores must match
+    /// the current step, which then advances. Returns the score for `Score`.
+    pub fn perform(&mut self, action: ScriptAction) -> Result<Option<u16>, ScriptError> {
+        let expect = self
+            .current_step()
+            .map(|s| s.expect.clone())
+            .ok_or(ScriptError::Finished)?;
+
+        let result = match (action, expect) {
+            (ScriptAction::Hold(held), _) => {
+                self.game.hold_dice(self.player_id, held)?;
+                return Ok(None);
+            }
+            (ScriptAction::Roll, ScriptExpect::Roll { holds, faces }) => {
+                if let Some(turn) = self.game.turn.as_ref() {
+                    let held = turn.dice.dice.map(|d| d.held);
+                    if turn.rolls_used > 0 && held != holds {
+                        return Err(ScriptError::WrongHolds);
+                    }
+                }
+                self.game.roll_dice_fixed(self.player_id, faces)?;
+                None
+            }
+            (ScriptAction::Score(category), ScriptExpect::Score(expected)) => {
+                if category != expected {
+                    return Err(ScriptError::WrongCategory(expected));
+                }
+                Some(self.game.score_category(self.player_id, category)?)
+            }
+            _ => return Err(ScriptError::UnexpectedAction),
+        };
+
+        self.index += 1;
+        Ok(result)
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum ScriptError {
+    #[error("script finished")]
+    Finished,
+    #[error("hold the dice shown in the prompt first")]
+    WrongHolds,
+    #[error("score {} here", .0.display_name())]
+    WrongCategory(Category),
+    #[error("not the next step")]
+    UnexpectedAction,
+    #[error(transparent)]
+    Game(#[from] GameError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::GameConfig;
+    use crate::player::Player;
+
+    const NONE: [bool; 5] = [false; 5];
+
+    fn scripted(steps: Vec<ScriptStep>) -> ScriptedGame {
+        let id = Uuid::new_v4();
+        let mut game = GameState::new(vec![Player::new(id, "Learner".into())], GameConfig::default());
+        game.start_solo().unwrap();
+        ScriptedGame::new(game, id, steps)
+    }
+
+    #[test]
+    fn test_script_forces_dice_and_advances() {
+        let mut s = scripted(vec![
+            ScriptStep::roll("roll", NONE, [3, 3, 5, 3, 2]),
+            ScriptStep::roll("reroll", [true, true, false, true, false], [1, 1, 3, 1, 3]),
+            ScriptStep::score("score", Category::Yahtzee),
+        ]);
+
+        s.perform(ScriptAction::Roll).unwrap();
+        assert_eq!(s.step_index(), 1);
+        s.perform(ScriptAction::Hold([true, true, false, true, false])).unwrap();
+        assert_eq!(s.step_index(), 1);
+        s.perform(ScriptAction::Roll).unwrap();
+        assert_eq!(
+            s.game.turn.as_ref().unwrap().dice.values(),
+            [3, 3, 3, 3, 3]
+        );
+        assert_eq!(s.perform(ScriptAction::Score(Category::Yahtzee)).unwrap(), Some(50));
+        assert!(s.is_finished());
+        assert!(matches!(s.perform(ScriptAction::Roll), Err(ScriptError::Finished)));
+    }
+
+    #[test]
+    fn test_script_rejects_off_script_actions() {
+        let mut s = scripted(vec![
+            ScriptStep::roll("roll", NONE, [6, 6, 6, 2, 1]),
+            ScriptStep::roll("reroll", [true, true, true, false, false], [1, 1, 1, 6, 4]),
+            ScriptStep::score("score", Category::Sixes),
+        ]);
+
+        assert!(matches!(
+            s.perform(ScriptAction::Score(Category::Chance)),
+            Err(ScriptError::UnexpectedAction)
+        ));
+        s.perform(ScriptAction::Roll).unwrap();
+        assert!(matches!(s.perform(ScriptAction::Roll), Err(ScriptError::WrongHolds)));
+        s.perform(ScriptAction::Hold([true, true, true, false, false])).unwrap();
+        s.perform(ScriptAction::Roll).unwrap();
+        assert!(matches!(
+            s.perform(ScriptAction::Score(Category::Chance)),
+            Err(ScriptError::WrongCategory(Category::Sixes))
+        ));
+        assert_eq!(s.perform(ScriptAction::Score(Category::Sixes)).unwrap(), Some(24));
+    }
+}
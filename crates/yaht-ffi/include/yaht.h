@@ -0,0 +1,40 @@
+/*
+ * C API for yaht's scoring and AI. Link against libyaht from
+ * `cargo build --release -p yaht-ffi`.
+ *
+ * Categories are indices 0-12: Ones, Twos, Threes, Fours, Fives, Sixes,
+ * ThreeOfAKind, FourOfAKind, FullHouse, SmallStraight, LargeStraight,
+ * Yahtzee, Chance. Dice are 5 values in 1-6. A scorecard is 13 int32_t in
+ * category order, -1 for an open category.
+ *
+ * Every function returns -1 on bad input.
+ */
+#ifndef YAHT_H
+#define YAHT_H
+
+#include <stdbool.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Score dice in a category. */
+int32_t yaht_score(uint8_t category, const uint8_t dice[5]);
+
+/* Score dice in a category, with Joker rules for a Yahtzee when
+ * joker_active is set. */
+int32_t yaht_score_joker(uint8_t category, const uint8_t dice[5], bool joker_active);
+
+/* The open category the hard AI would score the dice in. */
+int32_t yaht_suggest_category(const uint8_t dice[5], const int32_t scores[13]);
+
+/* Writes the best dice to keep with rolls_left rolls to go into held_out
+ * and returns the expected score, rounded. */
+int32_t yaht_suggest_holds(const uint8_t dice[5], const int32_t scores[13], uint8_t rolls_left, bool held_out[5]);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* YAHT_H */
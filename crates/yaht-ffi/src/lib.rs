@@ -0,0 +1,191 @@
+//! C bindings for yaht's scoring and AI, so tools written in other
+//! languages score dice exactly as the game does. See `include/yaht.h`.
+//!
+//! Categories are passed as their index in `Category::ALL` (0 = Ones,
+//! 12 = Chance). A scorecard is 13 `int32_t` in the same order, -1 for an
+//! open category. Functions return -1 on bad input.
+
+use std::slice;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use yaht_common::ai::{self, AiDifficulty};
+use yaht_common::dice::{DiceSet, NUM_DICE};
+use yaht_common::player::Scorecard;
+use yaht_common::scoring::{self, Category};
+
+const INVALID: i32 = -1;
+
+fn category(index: u8) -> Option<Category> {
+    Category::ALL.get(index as usize).copied()
+}
+
+/// # Safety
+///
+/// `dice` must be null or point to `NUM_DICE` readable bytes.
+unsafe fn read_dice(dice: *const u8) -> Option<[u8; 5]> {
+    if dice.is_null() {
+        return None;
+    }
+    let values: [u8; 5] = slice::from_raw_parts(dice, NUM_DICE).try_into().ok()?;
+    values.iter().all(|v| (1..=6).contains(v)).then_some(values)
+}
+
+/// # Safety
+///
+/// `scores` must be null or point to 13 readable `int32_t`.
+unsafe fn read_scorecard(scores: *const i32) -> Option<Scorecard> {
+    if scores.is_null() {
+        return None;
+    }
+    let mut scorecard = Scorecard::new();
+    for (&cat, &score) in Category::ALL.iter().zip(slice::from_raw_parts(scores, Category::ALL.len())) {
+        match score {
+            -1 => {}
+            0..=0xFFFF => scorecard.record(cat, score as u16).ok()?,
+            _ => return None,
+        }
+    }
+    Some(scorecard)
+}
+
+fn dice_set(values: [u8; 5]) -> DiceSet {
+    let mut dice = DiceSet::new();
+    dice.set_unheld(values);
+    dice
+}
+
+/// Score `dice` in `category`.
+///
+/// # Safety
+///
+/// `dice` must be null or point to 5 readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn yaht_score(category_index: u8, dice: *const u8) -> i32 {
+    yaht_score_joker(category_index, dice, false)
+}
+
+/// Score `dice` in `category`, applying Joker rules when `joker_active`
+/// and the dice are a Yahtzee.
+///
+/// # Safety
+///
+/// `dice` must be null or point to 5 readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn yaht_score_joker(category_index: u8, dice: *const u8, joker_active: bool) -> i32 {
+    match (category(category_index), read_dice(dice)) {
+        (Some(cat), Some(values)) => scoring::compute_score_joker(cat, &values, joker_active) as i32,
+        _ => INVALID,
+    }
+}
+
+/// The open category the hard AI would score `dice` in.
+///
+/// # Safety
+///
+/// `dice` must be null or point to 5 readable bytes, and `scores` null or
+/// point to 13 readable `int32_t`.
+#[no_mangle]
+pub unsafe extern "C" fn yaht_suggest_category(dice: *const u8, scores: *const i32) -> i32 {
+    let (Some(values), Some(scorecard)) = (read_dice(dice), read_scorecard(scores)) else {
+        return INVALID;
+    };
+    if scorecard.is_complete() {
+        return INVALID;
+    }
+    // The hard AI never draws on the generator.
+    let mut rng = StdRng::seed_from_u64(0);
+    ai::choose_category(&dice_set(values), &scorecard, AiDifficulty::Hard, &mut rng).index() as i32
+}
+
+/// The dice to keep for the best expected score with `rolls_left` rolls to
+/// go. Writes the holds to `held_out` and returns the expected score
+/// rounded to the nearest point.
+///
+/// # Safety
+///
+/// `dice` must be null or point to 5 readable bytes, `scores` null or point
+/// to 13 readable `int32_t`, and `held_out` null or point to 5 writable
+/// `bool`.
+#[no_mangle]
+pub unsafe extern "C" fn yaht_suggest_holds(
+    dice: *const u8,
+    scores: *const i32,
+    rolls_left: u8,
+    held_out: *mut bool,
+) -> i32 {
+    let (Some(values), Some(scorecard)) = (read_dice(dice), read_scorecard(scores)) else {
+        return INVALID;
+    };
+    if held_out.is_null() || scorecard.is_complete() {
+        return INVALID;
+    }
+    let Some(best) = ai::best_hold(&dice_set(values), &scorecard, rolls_left) else {
+        return INVALID;
+    };
+    slice::from_raw_parts_mut(held_out, NUM_DICE).copy_from_slice(&best.held);
+    best.expected_score.round() as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ptr;
+
+    const OPEN: [i32; 13] = [-1; 13];
+
+    #[test]
+    fn test_score() {
+        let dice = [2, 3, 4, 5, 6];
+        unsafe {
+            assert_eq!(yaht_score(Category::LargeStraight.index() as u8, dice.as_ptr()), 40);
+            assert_eq!(yaht_score(Category::Twos.index() as u8, dice.as_ptr()), 2);
+            assert_eq!(yaht_score(13, dice.as_ptr()), INVALID);
+            assert_eq!(yaht_score(0, [0, 1, 1, 1, 1].as_ptr()), INVALID);
+            assert_eq!(yaht_score(0, ptr::null()), INVALID);
+        }
+    }
+
+    #[test]
+    fn test_score_joker() {
+        let dice = [4; 5];
+        let full_house = Category::FullHouse.index() as u8;
+        unsafe {
+            assert_eq!(yaht_score_joker(full_house, dice.as_ptr(), false), 0);
+            assert_eq!(yaht_score_joker(full_house, dice.as_ptr(), true), 25);
+        }
+    }
+
+    #[test]
+    fn test_suggest_category() {
+        let dice = [6; 5];
+        let mut scores = OPEN;
+        unsafe {
+            assert_eq!(
+                yaht_suggest_category(dice.as_ptr(), scores.as_ptr()),
+                Category::Yahtzee.index() as i32
+            );
+            scores[Category::Yahtzee.index()] = 50;
+            assert_ne!(
+                yaht_suggest_category(dice.as_ptr(), scores.as_ptr()),
+                Category::Yahtzee.index() as i32
+            );
+            assert_eq!(yaht_suggest_category(dice.as_ptr(), [0; 13].as_ptr()), INVALID);
+            scores[0] = -2;
+            assert_eq!(yaht_suggest_category(dice.as_ptr(), scores.as_ptr()), INVALID);
+        }
+    }
+
+    #[test]
+    fn test_suggest_holds() {
+        let dice = [5, 5, 5, 5, 1];
+        let mut held = [false; 5];
+        unsafe {
+            let expected = yaht_suggest_holds(dice.as_ptr(), OPEN.as_ptr(), 1, held.as_mut_ptr());
+            assert!(expected > 0);
+            assert_eq!(held, [true, true, true, true, false]);
+            assert_eq!(yaht_suggest_holds(dice.as_ptr(), OPEN.as_ptr(), 1, ptr::null_mut()), INVALID);
+            assert_eq!(yaht_suggest_holds(dice.as_ptr(), OPEN.as_ptr(), 0, held.as_mut_ptr()), INVALID);
+        }
+    }
+}
@@ -0,0 +1,326 @@
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context};
+use futures::{SinkExt, StreamExt};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use tokio::net::TcpStream;
+use tokio::sync::watch;
+use uuid::Uuid;
+
+use yaht_common::ai::{self, AiDifficulty};
+use yaht_common::dice::DiceSet;
+use yaht_common::game::GameConfig;
+use yaht_common::player::Scorecard;
+use yaht_common::protocol::{
+    self, deserialize_message, framed_transport, ClientMessage, ErrorCode, FrameEncoder, ServerMessage,
+    Transport,
+};
+
+/// Pause before retrying a rate-limited request.
+const RATE_LIMIT_BACKOFF: Duration = Duration::from_millis(200);
+
+/// A request whose round trip is timed, from sending it to seeing the
+/// server's broadcast in response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Request {
+    Roll,
+    Hold,
+    Score,
+}
+
+impl Request {
+    pub const ALL: [Request; 3] = [Request::Roll, Request::Hold, Request::Score];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Request::Roll => "roll",
+            Request::Hold => "hold",
+            Request::Score => "score",
+        }
+    }
+}
+
+/// How a bot gets into its room.
+pub enum Role {
+    /// Create the room, publish its id, and start the game once `room_size`
+    /// players are in.
+    Host {
+        room_size: u8,
+        room_id: watch::Sender<Option<Uuid>>,
+    },
+    Guest {
+        room_id: watch::Receiver<Option<Uuid>>,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct BotConfig {
+    pub server: String,
+    pub think: Duration,
+    pub jitter: Duration,
+}
+
+/// What one simulated client saw.
+#[derive(Debug, Default)]
+pub struct BotReport {
+    pub latencies: Vec<(Request, Duration)>,
+    pub sent: u64,
+    pub received: u64,
+    pub errors: u64,
+    pub rate_limited: u64,
+    pub finished: bool,
+}
+
+struct Bot {
+    name: String,
+    config: BotConfig,
+    transport: Transport,
+    encoder: FrameEncoder,
+    rng: StdRng,
+    player_id: Uuid,
+    my_turn: bool,
+    pending: Option<(Request, Instant)>,
+    /// The last request sent, to retry after being rate limited.
+    last_request: Option<ClientMessage>,
+    scorecard: Scorecard,
+    report: BotReport,
+}
+
+/// Connect, join a room and play one full game.
+///
+/// The report is returned even if the game was cut short; check `finished`.
+pub async fn run(name: String, role: Role, config: BotConfig) -> anyhow::Result<BotReport> {
+    let stream = TcpStream::connect(&config.server)
+        .await
+        .with_context(|| format!("{}: failed to connect to {}", name, config.server))?;
+
+    let mut bot = Bot {
+        name,
+        config,
+        transport: framed_transport(stream),
+        encoder: FrameEncoder::new(),
+        rng: StdRng::from_entropy(),
+        player_id: Uuid::nil(),
+        my_turn: false,
+        pending: None,
+        last_request: None,
+        scorecard: Scorecard::new(),
+        report: BotReport::default(),
+    };
+
+    bot.handshake().await?;
+    bot.enter_room(role).await?;
+    if let Err(e) = bot.play().await {
+        tracing::warn!("{}: {}", bot.name, e);
+    }
+    bot.send(&ClientMessage::Disconnect).await.ok();
+    Ok(bot.report)
+}
+
+impl Bot {
+    async fn send(&mut self, msg: &ClientMessage) -> anyhow::Result<()> {
+        let bytes = self.encoder.encode(msg)?;
+        self.transport.send(bytes).await?;
+        self.report.sent += 1;
+        Ok(())
+    }
+
+    async fn recv(&mut self) -> anyhow::Result<ServerMessage> {
+        let Some(frame) = self.transport.next().await else {
+            bail!("server closed the connection");
+        };
+        let msg = deserialize_message(&frame?)?;
+        self.report.received += 1;
+        Ok(msg)
+    }
+
+    /// Wait the configured think time, then send a timed request.
+    async fn request(&mut self, kind: Request, msg: ClientMessage) -> anyhow::Result<()> {
+        let jitter = self.config.jitter.as_millis() as u64;
+        let extra = if jitter > 0 {
+            self.rng.gen_range(0..=jitter)
+        } else {
+            0
+        };
+        tokio::time::sleep(self.config.think + Duration::from_millis(extra)).await;
+
+        self.pending = Some((kind, Instant::now()));
+        self.send(&msg).await?;
+        self.last_request = Some(msg);
+        Ok(())
+    }
+
+    fn complete(&mut self, kind: Request) -> bool {
+        match self.pending {
+            Some((pending, sent_at)) if pending == kind => {
+                self.report.latencies.push((kind, sent_at.elapsed()));
+                self.pending = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    async fn handshake(&mut self) -> anyhow::Result<()> {
+        self.send(&ClientMessage::Hello {
+            player_name: self.name.clone(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            identity: None,
+            resume_token: None,
+            protocol: protocol::PROTOCOL_VERSION,
+        })
+        .await?;
+
+        match self.recv().await? {
+            ServerMessage::Welcome { player_id, .. } => {
+                self.player_id = player_id;
+                Ok(())
+            }
+            ServerMessage::HandshakeError { reason, .. } => {
+                bail!("{}: handshake failed: {}", self.name, reason)
+            }
+            other => bail!("{}: expected Welcome, got {:?}", self.name, other),
+        }
+    }
+
+    async fn enter_room(&mut self, role: Role) -> anyhow::Result<()> {
+        match role {
+            Role::Host { room_size, room_id } => {
+                self.send(&ClientMessage::CreateRoom {
+                    room_name: format!("load-{}", self.name),
+                    max_players: room_size,
+                    password: None,
+                    rules: GameConfig::default(),
+                })
+                .await?;
+                let id = self.wait_for_room().await?;
+                room_id.send_replace(Some(id));
+
+                let mut joined = 1;
+                while joined < room_size {
+                    if let ServerMessage::PlayerJoined { .. } = self.recv().await? {
+                        joined += 1;
+                    }
+                }
+                self.send(&ClientMessage::StartGame).await
+            }
+            Role::Guest { mut room_id } => {
+                let id = room_id
+                    .wait_for(Option::is_some)
+                    .await
+                    .with_context(|| format!("{}: host never created a room", self.name))?
+                    .expect("waited for a room id");
+                self.send(&ClientMessage::JoinRoom {
+                    room_id: id,
+                    password: None,
+                    spectate_if_started: false,
+                })
+                .await?;
+                self.wait_for_room().await.map(|_| ())
+            }
+        }
+    }
+
+    async fn wait_for_room(&mut self) -> anyhow::Result<Uuid> {
+        loop {
+            match self.recv().await? {
+                ServerMessage::RoomJoined { room_id, .. } => return Ok(room_id),
+                ServerMessage::Error { message, .. } => bail!("{}: {}", self.name, message),
+                _ => {}
+            }
+        }
+    }
+
+    async fn play(&mut self) -> anyhow::Result<()> {
+        loop {
+            match self.recv().await? {
+                ServerMessage::GameStarted { game_state } => {
+                    let first = game_state.players.get(game_state.current_player_index);
+                    if first.map(|p| p.id) == Some(self.player_id) {
+                        self.start_turn().await?;
+                    }
+                }
+                ServerMessage::TurnStarted { player_id, .. } => {
+                    if player_id == self.player_id {
+                        self.start_turn().await?;
+                    } else {
+                        self.my_turn = false;
+                    }
+                }
+                ServerMessage::DiceRolled {
+                    dice,
+                    rolls_remaining,
+                } if self.my_turn => {
+                    self.complete(Request::Roll);
+                    self.decide(&dice, rolls_remaining).await?;
+                }
+                ServerMessage::DiceHeld { .. } if self.my_turn && self.complete(Request::Hold) => {
+                    self.request(Request::Roll, ClientMessage::RollDice).await?;
+                }
+                ServerMessage::CategoryScored {
+                    player_id,
+                    category,
+                    score,
+                } if player_id == self.player_id => {
+                    self.complete(Request::Score);
+                    self.scorecard.record(category, score).ok();
+                    self.my_turn = false;
+                }
+                ServerMessage::Error {
+                    code: ErrorCode::RateLimited,
+                    ..
+                } => {
+                    self.report.rate_limited += 1;
+                    tokio::time::sleep(RATE_LIMIT_BACKOFF).await;
+                    if let (Some((kind, _)), Some(msg)) = (self.pending, self.last_request.clone()) {
+                        self.request(kind, msg).await?;
+                    }
+                }
+                ServerMessage::Error { message, .. } => {
+                    self.report.errors += 1;
+                    tracing::debug!("{}: server error: {}", self.name, message);
+                    // Keep the game moving: roll anyway after a rejected hold,
+                    // or fall back to any open category after a rejected score
+                    if let Some((Request::Hold, _)) = self.pending {
+                        self.request(Request::Roll, ClientMessage::RollDice).await?;
+                    } else if let Some((Request::Score, _)) = self.pending {
+                        let available = self.scorecard.available_categories();
+                        if !available.is_empty() {
+                            let category = available[self.rng.gen_range(0..available.len())];
+                            self.request(Request::Score, ClientMessage::ScoreCategory { category })
+                                .await?;
+                        }
+                    }
+                }
+                ServerMessage::GameOver { .. } => {
+                    self.report.finished = true;
+                    return Ok(());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    async fn start_turn(&mut self) -> anyhow::Result<()> {
+        self.my_turn = true;
+        self.request(Request::Roll, ClientMessage::RollDice).await
+    }
+
+    /// Hold and reroll, or score, the way a medium AI would.
+    async fn decide(&mut self, dice: &DiceSet, rolls_remaining: u8) -> anyhow::Result<()> {
+        if rolls_remaining > 0 {
+            let held = ai::choose_holds(dice, &self.scorecard, AiDifficulty::Medium, &mut self.rng);
+            if held == dice.dice.map(|d| d.held) {
+                return self.request(Request::Roll, ClientMessage::RollDice).await;
+            }
+            if !held.iter().all(|&h| h) {
+                return self.request(Request::Hold, ClientMessage::HoldDice { held }).await;
+            }
+        }
+        let category =
+            ai::choose_category(dice, &self.scorecard, AiDifficulty::Medium, &mut self.rng);
+        self.request(Request::Score, ClientMessage::ScoreCategory { category })
+            .await
+    }
+}
@@ -0,0 +1,114 @@
+mod bot;
+mod stats;
+
+use std::time::{Duration, Instant};
+
+use clap::Parser;
+use tokio::sync::watch;
+use tokio::task::JoinSet;
+
+use bot::{BotConfig, Role};
+use stats::Summary;
+
+/// YAHT load test - simulated clients playing full games against a server
+#[derive(Parser, Debug)]
+#[command(name = "yaht-loadtest", version, about)]
+struct Args {
+    /// Server address to connect to
+    #[arg(short = 's', long, default_value = "127.0.0.1:9876")]
+    server: String,
+
+    /// Number of simulated clients
+    #[arg(short = 'n', long, default_value_t = 20)]
+    clients: usize,
+
+    /// Players per room (2-6)
+    #[arg(short, long, default_value_t = 2)]
+    room_size: u8,
+
+    /// Pause before each action, in milliseconds
+    #[arg(long, default_value_t = 100)]
+    think_ms: u64,
+
+    /// Random extra pause added to each action, up to this many milliseconds
+    #[arg(long, default_value_t = 50)]
+    jitter_ms: u64,
+
+    /// Give up on clients still playing after this many seconds
+    #[arg(long, default_value_t = 600)]
+    timeout_secs: u64,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| "yaht_loadtest=info".into()),
+        )
+        .init();
+
+    let args = Args::parse();
+    let room_size = args.room_size.clamp(2, 6);
+    let config = BotConfig {
+        server: args.server.clone(),
+        think: Duration::from_millis(args.think_ms),
+        jitter: Duration::from_millis(args.jitter_ms),
+    };
+
+    // A room needs two players, so a lone leftover client would never start
+    let mut clients = args.clients;
+    if clients % room_size as usize == 1 {
+        clients -= 1;
+        tracing::warn!("Dropping one client that would be alone in its room");
+    }
+
+    tracing::info!(
+        "Starting {} clients against {} in rooms of {}",
+        clients,
+        args.server,
+        room_size
+    );
+
+    let started = Instant::now();
+    let mut bots = JoinSet::new();
+    let mut room_id = None;
+    for i in 0..clients {
+        let role = if i % room_size as usize == 0 {
+            let (tx, rx) = watch::channel(None);
+            room_id = Some(rx);
+            Role::Host {
+                room_size: (clients - i).min(room_size as usize) as u8,
+                room_id: tx,
+            }
+        } else {
+            Role::Guest {
+                room_id: room_id.clone().expect("a host comes first"),
+            }
+        };
+        let bot = bot::run(format!("bot-{}", i), role, config.clone());
+        bots.spawn(tokio::time::timeout(Duration::from_secs(args.timeout_secs), bot));
+    }
+
+    let mut summary = Summary::default();
+    while let Some(result) = bots.join_next().await {
+        match result {
+            Ok(Ok(Ok(report))) => summary.add(report),
+            Ok(Ok(Err(e))) => {
+                tracing::warn!("{}", e);
+                summary.add_failure();
+            }
+            Ok(Err(_)) => {
+                tracing::warn!("Client timed out");
+                summary.add_failure();
+            }
+            Err(e) => {
+                tracing::warn!("Client task failed: {}", e);
+                summary.add_failure();
+            }
+        }
+    }
+
+    summary.print(started.elapsed());
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use std::time::Duration;
+
+use crate::bot::{BotReport, Request};
+
+/// Totals across every simulated client.
+#[derive(Debug, Default)]
+pub struct Summary {
+    pub clients: usize,
+    pub failed: usize,
+    pub games_finished: usize,
+    pub sent: u64,
+    pub received: u64,
+    pub errors: u64,
+    pub rate_limited: u64,
+    latencies: Vec<(Request, Duration)>,
+}
+
+impl Summary {
+    pub fn add(&mut self, report: BotReport) {
+        self.clients += 1;
+        if report.finished {
+            self.games_finished += 1;
+        }
+        self.sent += report.sent;
+        self.received += report.received;
+        self.errors += report.errors;
+        self.rate_limited += report.rate_limited;
+        self.latencies.extend(report.latencies);
+    }
+
+    pub fn add_failure(&mut self) {
+        self.clients += 1;
+        self.failed += 1;
+    }
+
+    pub fn print(&self, elapsed: Duration) {
+        let secs = elapsed.as_secs_f64().max(f64::EPSILON);
+        println!();
+        println!("Clients:         {} ({} failed)", self.clients, self.failed);
+        println!("Games finished:  {} of {} clients", self.games_finished, self.clients);
+        println!("Elapsed:         {:.1}s", secs);
+        println!(
+            "Messages:        {} sent, {} received, {} server errors, {} rate limited",
+            self.sent, self.received, self.errors, self.rate_limited
+        );
+        println!(
+            "Throughput:      {:.0} msg/s in, {:.0} msg/s out",
+            self.sent as f64 / secs,
+            self.received as f64 / secs
+        );
+        println!();
+        println!(
+            "{:<8}{:>8}{:>10}{:>10}{:>10}{:>10}",
+            "request", "count", "p50", "p90", "p99", "max"
+        );
+        for kind in Request::ALL {
+            let mut samples: Vec<Duration> = self
+                .latencies
+                .iter()
+                .filter(|(k, _)| *k == kind)
+                .map(|(_, d)| *d)
+                .collect();
+            if samples.is_empty() {
+                continue;
+            }
+            samples.sort_unstable();
+            println!(
+                "{:<8}{:>8}{:>10}{:>10}{:>10}{:>10}",
+                kind.label(),
+                samples.len(),
+                format_ms(percentile(&samples, 50.0)),
+                format_ms(percentile(&samples, 90.0)),
+                format_ms(percentile(&samples, 99.0)),
+                format_ms(samples[samples.len() - 1]),
+            );
+        }
+    }
+}
+
+/// Nearest-rank percentile of sorted samples.
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+fn format_ms(d: Duration) -> String {
+    format!("{:.2}ms", d.as_secs_f64() * 1000.0)
+}
@@ -1,25 +1,108 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use bytes::Bytes;
 use futures::{SinkExt, StreamExt};
 use tokio::net::TcpStream;
-use tokio::sync::mpsc;
+use tokio::sync::{Notify, mpsc, oneshot};
 use uuid::Uuid;
 
 use yaht_common::protocol::{
-    self, ClientMessage, ServerMessage, framed_transport, serialize_message,
+    self, ClientMessage, ErrorCode, ServerMessage, VersionMismatch, framed_transport_with_limit,
+    serialize_message,
 };
 
 use crate::handler;
+use crate::ratelimit::RateLimiter;
+use crate::room::{RoomCommand, RoomHandle};
 use crate::server::SharedState;
 
+/// Malformed frames a connection may send before it is disconnected.
+const MAX_MALFORMED_FRAMES: u32 = 5;
+
+/// How long to keep writing queued messages after the connection closes.
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// How long a new connection waits for an older one with the same identity
+/// to close and give up its seat.
+const TAKEOVER_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A connected player's identity and outbound channel, shared with the
+/// room they are in.
+#[derive(Clone)]
 pub struct ConnectionHandle {
     pub player_id: Uuid,
     pub player_name: String,
-    pub tx: mpsc::Sender<ServerMessage>,
-    pub room_id: Option<Uuid>,
-    pub is_spectator: bool,
+    /// Persistent identity sent by the client, if any.
+    pub identity: Option<Uuid>,
+    /// Serialized frames for the writer task.
+    pub tx: mpsc::Sender<Bytes>,
+    /// Asks the connection to close for a newer one with the same identity.
+    /// It answers with its resume token once its seat is parked.
+    takeover: mpsc::Sender<oneshot::Sender<Uuid>>,
+    /// Woken when a room finds the outbound queue full, so the connection
+    /// closes rather than hold the room up or silently miss messages.
+    overflowed: Arc<Notify>,
+}
+
+impl ConnectionHandle {
+    pub async fn send(&self, msg: ServerMessage) {
+        match serialize_message(&msg) {
+            Ok(bytes) => self.send_bytes(bytes).await,
+            Err(e) => tracing::error!("Failed to serialize message: {}", e),
+        }
+    }
+
+    /// Send an already-serialized message, e.g. one frame shared by a broadcast.
+    pub async fn send_bytes(&self, bytes: Bytes) {
+        let _ = self.tx.send(bytes).await;
+    }
+
+    /// Queue an already-serialized message without waiting, for senders
+    /// that must not stall on a slow client. A full queue closes the
+    /// connection.
+    pub fn try_send_bytes(&self, bytes: Bytes) {
+        if let Err(mpsc::error::TrySendError::Full(_)) = self.tx.try_send(bytes) {
+            tracing::warn!("Outbound queue full for {}, disconnecting", self.player_name);
+            self.overflowed.notify_one();
+        }
+    }
+
+    /// Queue a message, waiting up to `timeout` for room. A queue that
+    /// stays full closes the connection.
+    pub async fn send_bytes_within(&self, bytes: Bytes, timeout: Duration) {
+        if tokio::time::timeout(timeout, self.tx.send(bytes)).await.is_err() {
+            tracing::warn!("Outbound queue stayed full for {}, disconnecting", self.player_name);
+            self.overflowed.notify_one();
+        }
+    }
+
+    /// Close this connection for a newer one with the same identity.
+    /// Returns the resume token a seat it held mid-game is parked under.
+    pub async fn take_over(&self) -> Option<Uuid> {
+        let (reply, closed) = oneshot::channel();
+        let taken = async {
+            self.takeover.send(reply).await.ok()?;
+            closed.await.ok()
+        };
+        tokio::time::timeout(TAKEOVER_TIMEOUT, taken).await.ok().flatten()
+    }
+}
+
+/// Per-connection state owned by the reader loop.
+pub struct Session {
+    pub conn: ConnectionHandle,
+    pub room: Option<RoomHandle>,
+    /// Whether `room` is being watched rather than played in.
+    pub spectating: bool,
+    pub limiter: RateLimiter,
+    /// Sent in `Welcome`; parks this session under it if the connection
+    /// drops mid-game.
+    pub resume_token: Uuid,
 }
 
 pub async fn handle_connection(stream: TcpStream, state: SharedState) -> anyhow::Result<()> {
-    let mut transport = framed_transport(stream);
+    let mut transport = framed_transport_with_limit(stream, state.max_frame_length);
 
     // Step 1: Handshake -- expect Hello
     let hello: ClientMessage = match protocol::recv_message(&mut transport).await? {
@@ -27,88 +110,238 @@ pub async fn handle_connection(stream: TcpStream, state: SharedState) -> anyhow:
         None => return Ok(()),
     };
 
-    let (player_id, player_name) = match hello {
+    let (player_name, identity, resume_token, client_protocol) = match hello {
         ClientMessage::Hello {
             player_name,
             version,
+            identity,
+            resume_token,
+            protocol,
         } => {
             tracing::info!(
-                "Player '{}' connected (client version: {})",
+                "Player '{}' connected (client version: {}, protocol {})",
                 player_name,
-                version
+                version,
+                protocol
             );
-            let id = Uuid::new_v4();
-            protocol::send_message(
-                &mut transport,
-                &ServerMessage::Welcome {
-                    player_id: id,
-                    server_version: env!("CARGO_PKG_VERSION").to_string(),
-                },
-            )
-            .await?;
-            (id, player_name)
+            (player_name, identity, resume_token, protocol)
         }
         _ => {
             protocol::send_message(
                 &mut transport,
                 &ServerMessage::HandshakeError {
                     reason: "Expected Hello message".into(),
+                    version_mismatch: None,
                 },
             )
             .await?;
             return Ok(());
         }
     };
+    let Some(session_protocol) = protocol::negotiate_protocol(client_protocol) else {
+        protocol::send_message(
+            &mut transport,
+            &ServerMessage::HandshakeError {
+                reason: format!(
+                    "Client protocol {} is too old; this server needs {} or newer",
+                    client_protocol,
+                    protocol::MIN_PROTOCOL_VERSION
+                ),
+                version_mismatch: Some(VersionMismatch {
+                    server_version: env!("CARGO_PKG_VERSION").to_string(),
+                    server_protocol: protocol::PROTOCOL_VERSION,
+                    min_protocol: protocol::MIN_PROTOCOL_VERSION,
+                }),
+            },
+        )
+        .await?;
+        return Ok(());
+    };
 
-    // Step 2: Create mpsc channel for outbound messages
-    let (tx, mut rx) = mpsc::channel::<ServerMessage>(64);
+    // Step 2: Create mpsc channel for outbound messages. A resumed room
+    // queues its catch-up here before the writer starts, so it arrives
+    // after the Welcome.
+    let (tx, mut rx) = mpsc::channel::<Bytes>(64);
+    let (takeover, mut takeover_rx) = mpsc::channel::<oneshot::Sender<Uuid>>(1);
+    let overflowed = Arc::new(Notify::new());
 
-    // Register connection
-    {
-        let handle = ConnectionHandle {
-            player_id,
-            player_name: player_name.clone(),
+    // One live connection per identity: an older one is closed, and a
+    // seat it held mid-game passes to this one
+    let mut taken_token = None;
+    if let Some(identity) = identity {
+        for old in state.connections.with_identity(identity) {
+            tracing::info!("Player '{}' connected again, closing their older connection", player_name);
+            taken_token = old.take_over().await.or(taken_token);
+        }
+    }
+
+    let mut resumed = None;
+    let parked = resume_token
+        .and_then(|token| state.resume_tokens.take(&token))
+        .or_else(|| taken_token.and_then(|token| state.resume_tokens.take(&token)));
+    if let Some(parked) = parked {
+        let conn = ConnectionHandle {
+            player_id: parked.player_id,
+            player_name: parked.player_name,
+            identity: parked.identity,
             tx: tx.clone(),
-            room_id: None,
-            is_spectator: false,
+            takeover: takeover.clone(),
+            overflowed: overflowed.clone(),
+        };
+        let (reply, admitted) = oneshot::channel();
+        let cmd = RoomCommand::Resume {
+            conn: conn.clone(),
+            reply,
         };
-        state.connections.write().await.insert(player_id, handle);
+        if parked.room.send(cmd).await && admitted.await.unwrap_or(false) {
+            resumed = Some((conn, parked.room));
+        }
+    }
+    // A token whose seat is gone still gets a fresh session
+    let (conn, room) = match resumed {
+        Some((conn, room)) => (conn, Some(room)),
+        None => {
+            let conn = ConnectionHandle {
+                player_id: state.entropy.uuid(),
+                player_name,
+                identity,
+                tx,
+                takeover,
+                overflowed: overflowed.clone(),
+            };
+            (conn, None)
+        }
+    };
+    let player_id = conn.player_id;
+    let player_name = conn.player_name.clone();
+
+    let mut session = Session {
+        conn,
+        room,
+        spectating: false,
+        limiter: RateLimiter::new(&state.rate_limits),
+        resume_token: state.entropy.uuid(),
+    };
+    state.connections.register(session.conn.clone());
+
+    let welcome = ServerMessage::Welcome {
+        player_id,
+        server_version: env!("CARGO_PKG_VERSION").to_string(),
+        resume_token: Some(session.resume_token),
+        resumed: session.room.is_some(),
+        protocol: session_protocol,
+    };
+    if let Err(e) = protocol::send_message(&mut transport, &welcome).await {
+        handler::handle_connection_lost(&mut session, &state).await;
+        state.connections.unregister(&player_id);
+        return Err(e);
     }
 
     // Step 3: Split transport for independent read/write
     let (mut sink, mut stream) = transport.split();
 
-    // Writer task: drains rx and writes to sink
-    let write_task = tokio::spawn(async move {
-        while let Some(msg) = rx.recv().await {
-            match serialize_message(&msg) {
-                Ok(bytes) => {
-                    if sink.send(bytes.into()).await.is_err() {
-                        break;
-                    }
+    // Writer task: drains rx and writes to sink. Other handles to this
+    // connection keep the channel open, so it stops on `shutdown` after
+    // sending whatever was already queued.
+    let (shutdown, mut shutdown_rx) = oneshot::channel::<()>();
+    let max_frame_length = state.max_frame_length;
+    let writer_name = player_name.clone();
+    let mut write_task = tokio::spawn(async move {
+        let mut closing = false;
+        loop {
+            let bytes = if closing {
+                match rx.try_recv() {
+                    Ok(bytes) => bytes,
+                    Err(_) => return,
                 }
-                Err(e) => {
-                    tracing::error!("Failed to serialize message: {}", e);
+            } else {
+                tokio::select! {
+                    biased;
+                    Some(bytes) = rx.recv() => bytes,
+                    _ = &mut shutdown_rx => {
+                        closing = true;
+                        continue;
+                    }
                 }
+            };
+            // The codec would fail the whole connection on an oversized frame
+            if let Err(e) = protocol::check_frame_length(&bytes, max_frame_length) {
+                tracing::error!("Dropping message to {}: {}", writer_name, e);
+                continue;
+            }
+            if sink.send(bytes).await.is_err() {
+                return;
             }
         }
     });
 
     // Step 4: Reader loop
+    let mut malformed = 0;
+    let mut taken_over = None;
     loop {
-        match stream.next().await {
+        let next = tokio::select! {
+            next = stream.next() => next,
+            Some(reply) = takeover_rx.recv() => {
+                session
+                    .conn
+                    .send(ServerMessage::Error {
+                        code: ErrorCode::SessionTaken,
+                        message: "You connected again from somewhere else; closing this connection".into(),
+                    })
+                    .await;
+                taken_over = Some(reply);
+                break;
+            }
+            _ = overflowed.notified() => break,
+        };
+        match next {
             Some(Ok(frame)) => {
                 match protocol::deserialize_message::<ClientMessage>(&frame) {
                     Ok(msg) => {
-                        if let Err(e) = handler::handle_message(player_id, msg, &state).await {
+                        if let Err(e) = handler::handle_message(&mut session, msg, &state).await {
                             tracing::error!("Handler error for {}: {}", player_name, e);
                         }
                     }
                     Err(e) => {
                         tracing::warn!("Failed to parse message from {}: {}", player_name, e);
+                        malformed += 1;
+                        let message = if malformed >= MAX_MALFORMED_FRAMES {
+                            "Too many malformed messages, disconnecting".to_string()
+                        } else {
+                            format!("Malformed message: {}", e)
+                        };
+                        session
+                            .conn
+                            .send(ServerMessage::Error {
+                                code: ErrorCode::MalformedMessage,
+                                message,
+                            })
+                            .await;
+                        if malformed >= MAX_MALFORMED_FRAMES {
+                            tracing::warn!(
+                                "Disconnecting {} after {} malformed messages",
+                                player_name,
+                                malformed
+                            );
+                            break;
+                        }
                     }
                 }
             }
+            Some(Err(e)) if protocol::is_frame_too_large(&e) => {
+                tracing::warn!("Oversized frame from {}, disconnecting", player_name);
+                session
+                    .conn
+                    .send(ServerMessage::Error {
+                        code: ErrorCode::FrameTooLarge,
+                        message: format!(
+                            "Message over the {} byte limit, disconnecting",
+                            state.max_frame_length
+                        ),
+                    })
+                    .await;
+                break;
+            }
             Some(Err(e)) => {
                 tracing::warn!("Read error from {}: {}", player_name, e);
                 break;
@@ -121,7 +354,15 @@ pub async fn handle_connection(stream: TcpStream, state: SharedState) -> anyhow:
     }
 
     // Cleanup
-    handler::handle_disconnect(player_id, &state).await;
-    write_task.abort();
+    // Unregister first: a resumed session registers the same player ID
+    state.connections.unregister(&player_id);
+    handler::handle_connection_lost(&mut session, &state).await;
+    if let Some(reply) = taken_over {
+        let _ = reply.send(session.resume_token);
+    }
+    let _ = shutdown.send(());
+    if tokio::time::timeout(FLUSH_TIMEOUT, &mut write_task).await.is_err() {
+        write_task.abort();
+    }
     Ok(())
 }
@@ -0,0 +1,309 @@
+//! Anonymized game events and dice statistics for external tools.
+//!
+//! Rooms publish events to an [`EventBus`]; [`serve`] streams them to any
+//! number of HTTP clients as server-sent events, one JSON object per
+//! `data:` line. Rooms also keep their dice face counts on the bus, served
+//! as JSON at `/dice`. Rooms are known by id and players by seat number;
+//! player names, player ids and room names are never sent.
+//!
+//! The exception is `/rooms/{id}`, a scoreboard of one room that can be
+//! watched: names and scores, as any spectator would see them, still
+//! without player ids.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::bail;
+use dashmap::DashMap;
+use serde::Serialize;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use yaht_common::dice::FaceCounts;
+use yaht_common::player::Scorecard;
+use yaht_common::protocol::RoomState;
+use yaht_common::scoring::Category;
+
+/// Events buffered per observer before a slow one starts missing them.
+const EVENT_BUFFER: usize = 256;
+/// Longest request header accepted from an observer.
+const MAX_REQUEST_HEADER: usize = 8 * 1024;
+/// How long an observer has to send its request header.
+const REQUEST_HEADER_TIMEOUT: Duration = Duration::from_secs(10);
+/// How often idle streams get a comment, so dead observers are noticed.
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum GameEvent {
+    RoomCreated {
+        room_id: Uuid,
+        max_players: u8,
+    },
+    GameStarted {
+        room_id: Uuid,
+        players: u8,
+    },
+    /// `seat` is the player's index in turn order.
+    TurnScored {
+        room_id: Uuid,
+        round: u8,
+        seat: u8,
+        category: Category,
+        score: u16,
+    },
+    /// Final totals in seat order.
+    GameOver {
+        room_id: Uuid,
+        scores: Vec<u16>,
+        winning_seats: Vec<u8>,
+    },
+    RoomClosed {
+        room_id: Uuid,
+    },
+}
+
+#[derive(Serialize)]
+struct Envelope<'a> {
+    /// Unix timestamp in seconds.
+    at: i64,
+    #[serde(flatten)]
+    event: &'a GameEvent,
+}
+
+#[derive(Serialize)]
+struct RoomDice {
+    room_id: Uuid,
+    rolled: u32,
+    faces: FaceCounts,
+    chi_squared: f64,
+}
+
+/// A room as served at `/rooms/{id}`.
+#[derive(Debug, Clone, Serialize)]
+pub struct RoomView {
+    pub room_id: Uuid,
+    pub room_name: String,
+    pub state: RoomState,
+    pub max_players: u8,
+    pub spectators: u8,
+    /// The current or last game's round, once a game started.
+    pub round: Option<u8>,
+    pub total_rounds: Option<u8>,
+    /// Index into `players` of whoever's turn it is.
+    pub current_seat: Option<u8>,
+    /// In seat order: the game's players once one started, else whoever
+    /// is seated.
+    pub players: Vec<SeatView>,
+    /// Wins and points over the games finished in the room.
+    pub standings: Vec<StandingView>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SeatView {
+    pub name: String,
+    pub connected: bool,
+    pub forfeited: bool,
+    pub total: u16,
+    pub scorecard: Scorecard,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StandingView {
+    pub name: String,
+    pub games: u16,
+    pub wins: u16,
+    pub total: u32,
+}
+
+/// Fans events out to every observer and holds each open room's dice
+/// counts and scoreboard. Cloning shares the bus.
+#[derive(Debug, Clone)]
+pub struct EventBus {
+    tx: broadcast::Sender<Arc<str>>,
+    dice: Arc<DashMap<Uuid, FaceCounts>>,
+    rooms: Arc<DashMap<Uuid, RoomView>>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self {
+            tx: broadcast::channel(EVENT_BUFFER).0,
+            dice: Arc::default(),
+            rooms: Arc::default(),
+        }
+    }
+}
+
+impl EventBus {
+    /// Send an event to current observers. Without observers this does
+    /// nothing, not even serialize the event.
+    pub fn publish(&self, at: i64, event: GameEvent) {
+        if self.tx.receiver_count() == 0 {
+            return;
+        }
+        match serde_json::to_string(&Envelope { at, event: &event }) {
+            Ok(json) => {
+                let _ = self.tx.send(json.into());
+            }
+            Err(e) => tracing::error!("Failed to serialize game event: {}", e),
+        }
+    }
+
+    /// Receive events published from now on, as JSON.
+    pub fn subscribe(&self) -> broadcast::Receiver<Arc<str>> {
+        self.tx.subscribe()
+    }
+
+    /// Replace a room's dice counts.
+    pub fn set_room_dice(&self, room_id: Uuid, counts: FaceCounts) {
+        self.dice.insert(room_id, counts);
+    }
+
+    pub fn remove_room_dice(&self, room_id: Uuid) {
+        self.dice.remove(&room_id);
+    }
+
+    /// Replace a room's scoreboard, or take it down for a room that can't
+    /// be watched.
+    pub fn set_room_view(&self, room_id: Uuid, view: Option<RoomView>) {
+        match view {
+            Some(view) => self.rooms.insert(room_id, view),
+            None => self.rooms.remove(&room_id).map(|(_, view)| view),
+        };
+    }
+
+    /// A room's scoreboard as JSON, if it is open and can be watched.
+    fn room_json(&self, room_id: Uuid) -> Option<String> {
+        let view = self.rooms.get(&room_id)?;
+        match serde_json::to_string(view.value()) {
+            Ok(json) => Some(json),
+            Err(e) => {
+                tracing::error!("Failed to serialize room view: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Every open room's dice counts, as JSON.
+    fn dice_json(&self) -> String {
+        let mut rooms: Vec<RoomDice> = self
+            .dice
+            .iter()
+            .map(|entry| RoomDice {
+                room_id: *entry.key(),
+                rolled: entry.value().total(),
+                faces: *entry.value(),
+                chi_squared: entry.value().chi_squared(),
+            })
+            .collect();
+        rooms.sort_by_key(|r| r.room_id);
+        serde_json::json!({ "rooms": rooms }).to_string()
+    }
+}
+
+/// Accept observers on `listener`. `GET /events` streams events until the
+/// observer disconnects; `GET /dice` returns each open room's dice counts
+/// and `GET /rooms/{id}` the scoreboard of a room that can be watched.
+pub async fn serve(listener: TcpListener, bus: EventBus) -> anyhow::Result<()> {
+    loop {
+        let (stream, peer_addr) = listener.accept().await?;
+        let bus = bus.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_request(stream, &bus).await {
+                tracing::debug!("Observer {} disconnected: {}", peer_addr, e);
+            }
+        });
+    }
+}
+
+async fn handle_request(mut stream: TcpStream, bus: &EventBus) -> anyhow::Result<()> {
+    let Ok(request) = tokio::time::timeout(REQUEST_HEADER_TIMEOUT, read_request_header(&mut stream)).await else {
+        bail!("timed out waiting for the request header");
+    };
+    let request = request?;
+    let mut request_line = request.lines().next().unwrap_or_default().split_whitespace();
+    let method = request_line.next();
+    let path = request_line.next().and_then(|p| p.split('?').next());
+    match (method, path) {
+        // Subscribed before the response goes out, so the observer misses
+        // nothing published after it sees the headers
+        (Some("GET"), Some("/events")) => stream_events(stream, bus.subscribe()).await,
+        (Some("GET"), Some("/dice")) => send_json(stream, &bus.dice_json()).await,
+        (Some("GET"), Some(path)) if path.starts_with("/rooms/") => {
+            let room = path["/rooms/".len()..].parse().ok().and_then(|id| bus.room_json(id));
+            match room {
+                Some(body) => send_json(stream, &body).await,
+                None => send_not_found(stream).await,
+            }
+        }
+        _ => send_not_found(stream).await,
+    }
+}
+
+async fn send_json(mut stream: TcpStream, body: &str) -> anyhow::Result<()> {
+    let response = format!(
+        "HTTP/1.1 200 OK\r\n\
+         Content-Type: application/json\r\n\
+         Access-Control-Allow-Origin: *\r\n\
+         Cache-Control: no-cache\r\n\
+         Content-Length: {}\r\n\
+         Connection: close\r\n\r\n{}",
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    Ok(())
+}
+
+async fn send_not_found(mut stream: TcpStream) -> anyhow::Result<()> {
+    stream
+        .write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+        .await?;
+    Ok(())
+}
+
+async fn stream_events(mut stream: TcpStream, mut events: broadcast::Receiver<Arc<str>>) -> anyhow::Result<()> {
+    tracing::info!("Event observer connected from {}", stream.peer_addr()?);
+    stream
+        .write_all(
+            b"HTTP/1.1 200 OK\r\n\
+              Content-Type: text/event-stream\r\n\
+              Cache-Control: no-cache\r\n\
+              Access-Control-Allow-Origin: *\r\n\
+              Connection: keep-alive\r\n\r\n",
+        )
+        .await?;
+
+    let mut keepalive = tokio::time::interval(KEEPALIVE_INTERVAL);
+    keepalive.reset();
+    loop {
+        let chunk = tokio::select! {
+            event = events.recv() => match event {
+                Ok(json) => format!("data: {}\n\n", json),
+                Err(broadcast::error::RecvError::Lagged(missed)) => format!(": missed {} events\n\n", missed),
+                Err(broadcast::error::RecvError::Closed) => return Ok(()),
+            },
+            _ = keepalive.tick() => ": keepalive\n\n".to_string(),
+        };
+        stream.write_all(chunk.as_bytes()).await?;
+    }
+}
+
+async fn read_request_header(stream: &mut TcpStream) -> anyhow::Result<String> {
+    let mut header = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !header.windows(4).any(|w| w == b"\r\n\r\n") {
+        if header.len() >= MAX_REQUEST_HEADER {
+            bail!("request header too long");
+        }
+        let n = stream.read(&mut buf).await?;
+        if n == 0 {
+            bail!("connection closed before the request was complete");
+        }
+        header.extend_from_slice(&buf[..n]);
+    }
+    Ok(String::from_utf8_lossy(&header).into_owned())
+}
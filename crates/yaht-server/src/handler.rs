@@ -1,527 +1,257 @@
-use rand::SeedableRng;
+use std::time::Instant;
+
+use tokio::sync::oneshot;
+use tracing::{field, Instrument};
 use uuid::Uuid;
 
-use yaht_common::game::GamePhase;
-use yaht_common::player::Player;
+use yaht_common::lobby::validate_room_name;
 use yaht_common::protocol::{ClientMessage, ErrorCode, ServerMessage};
+
+use crate::connection::Session;
+use crate::password::RoomPassword;
+use crate::registry::ParkedSession;
+use crate::room::{self, Admission, Room, RoomCommand, RoomHandle};
 use crate::server::SharedState;
 
+/// Handle one client message inside a span naming the player, room and
+/// message type, warning if it takes longer than the configured threshold.
 pub async fn handle_message(
-    player_id: Uuid,
+    session: &mut Session,
+    msg: ClientMessage,
+    state: &SharedState,
+) -> anyhow::Result<()> {
+    let span = tracing::info_span!(
+        "message",
+        player = %session.conn.player_name,
+        player_id = %session.conn.player_id,
+        room = field::Empty,
+        kind = msg.name(),
+    );
+    if let Some(room) = &session.room {
+        span.record("room", field::display(room.id));
+    }
+
+    if let Err(class) = session.limiter.check(&msg) {
+        span.in_scope(|| tracing::debug!("Rate limited"));
+        session
+            .conn
+            .send(ServerMessage::Error {
+                code: ErrorCode::RateLimited,
+                message: format!("Too many {}, slow down", class.label()),
+            })
+            .await;
+        return Ok(());
+    }
+
+    let started = Instant::now();
+    let result = dispatch(session, msg, state).instrument(span.clone()).await;
+    let elapsed = started.elapsed();
+
+    span.in_scope(|| {
+        if elapsed >= state.slow_handler {
+            tracing::warn!(elapsed_ms = elapsed.as_millis() as u64, "Slow message handler");
+        } else {
+            tracing::trace!(elapsed_us = elapsed.as_micros() as u64, "Handled message");
+        }
+    });
+    result
+}
+
+async fn dispatch(
+    session: &mut Session,
     msg: ClientMessage,
     state: &SharedState,
 ) -> anyhow::Result<()> {
     match msg {
         ClientMessage::ListRooms => {
-            let lobby = state.lobby.read().await;
-            let rooms = lobby.list_rooms();
-            send_to_player(player_id, ServerMessage::RoomList { rooms }, state).await;
+            let rooms = state.lobby.read().await.list_rooms();
+            session.conn.send(ServerMessage::RoomList { rooms }).await;
         }
 
         ClientMessage::CreateRoom {
             room_name,
             max_players,
             password,
+            rules,
         } => {
-            let mut lobby = state.lobby.write().await;
-            let room_id = lobby.create_room(room_name, max_players, player_id, password);
-
-            // Update connection's room_id
-            {
-                let mut conns = state.connections.write().await;
-                if let Some(conn) = conns.get_mut(&player_id) {
-                    conn.room_id = Some(room_id);
-                    conn.is_spectator = false;
-                }
-            }
-
-            let conns = state.connections.read().await;
-            if let Some(room) = lobby.get_room(&room_id) {
-                let snapshot = room.snapshot(&conns);
-                send_to_player(
-                    player_id,
-                    ServerMessage::RoomJoined {
-                        room_id,
-                        room_state: snapshot,
-                    },
-                    state,
-                )
-                .await;
-            }
-        }
-
-        ClientMessage::JoinRoom { room_id, password } => {
-            let mut lobby = state.lobby.write().await;
-            let room = match lobby.get_room_mut(&room_id) {
-                Some(r) => r,
-                None => {
-                    send_to_player(
-                        player_id,
-                        ServerMessage::Error {
-                            code: ErrorCode::RoomNotFound,
-                            message: "Room not found".into(),
-                        },
-                        state,
-                    )
-                    .await;
+            let room_name = match validate_room_name(&room_name) {
+                Ok(name) => name.to_string(),
+                Err(e) => {
+                    session
+                        .conn
+                        .send(ServerMessage::Error {
+                            code: ErrorCode::InvalidRoomName,
+                            message: e.to_string(),
+                        })
+                        .await;
                     return Ok(());
                 }
             };
+            leave_room(session).await;
+            let password = match password {
+                Some(password) => RoomPassword::hash(password).await,
+                None => None,
+            };
 
-            // Check password
-            if !room.check_password(&password) {
-                send_to_player(
-                    player_id,
-                    ServerMessage::Error {
-                        code: ErrorCode::WrongPassword,
-                        message: "Wrong room password".into(),
-                    },
-                    state,
-                )
-                .await;
-                return Ok(());
-            }
-
-            if let Err(_) = room.add_player(player_id) {
-                send_to_player(
-                    player_id,
-                    ServerMessage::Error {
-                        code: ErrorCode::RoomFull,
-                        message: "Room is full or game already started".into(),
-                    },
-                    state,
-                )
-                .await;
-                return Ok(());
-            }
-
-            // Update connection
+            // Held from the name check until the room is listed, so two
+            // rooms can't slip in under the same name
+            let mut lobby = state.lobby.write().await;
+            if state.unique_room_names
+                && lobby.list_rooms().iter().any(|r| r.room_name.to_lowercase() == room_name.to_lowercase())
             {
-                let mut conns = state.connections.write().await;
-                if let Some(conn) = conns.get_mut(&player_id) {
-                    conn.room_id = Some(room_id);
-                    conn.is_spectator = false;
-                }
+                drop(lobby);
+                session
+                    .conn
+                    .send(ServerMessage::Error {
+                        code: ErrorCode::RoomNameTaken,
+                        message: format!("There is already a room called {}", room_name),
+                    })
+                    .await;
+                return Ok(());
             }
-
-            let conns = state.connections.read().await;
-            let player_name = conns
-                .get(&player_id)
-                .map(|c| c.player_name.clone())
-                .unwrap_or_default();
-            let snapshot = room.snapshot(&conns);
-            let members = room.all_member_ids();
-            drop(conns);
-            drop(lobby);
-
-            send_to_player(
-                player_id,
-                ServerMessage::RoomJoined {
-                    room_id,
-                    room_state: snapshot,
-                },
-                state,
+            let room = Room::new(
+                state.entropy.uuid(),
+                room_name,
+                max_players,
+                session.conn.clone(),
+                password,
+                state.replay_history,
+                state.entropy.fork(),
             )
-            .await;
-
-            broadcast_to_list(
-                &members,
-                &ServerMessage::PlayerJoined {
-                    player_id,
-                    player_name,
-                },
-                state,
-                Some(player_id),
-            )
-            .await;
+            .with_rules(rules);
+            let handle = room::spawn(room, state.clone());
+            lobby.insert_room(handle.clone());
+            drop(lobby);
+            session.room = Some(handle);
+            session.spectating = false;
         }
 
-        ClientMessage::SpectateRoom { room_id } => {
-            let mut lobby = state.lobby.write().await;
-            let room = match lobby.get_room_mut(&room_id) {
-                Some(r) => r,
-                None => {
-                    send_to_player(
-                        player_id,
-                        ServerMessage::Error {
-                            code: ErrorCode::RoomNotFound,
-                            message: "Room not found".into(),
-                        },
-                        state,
-                    )
-                    .await;
-                    return Ok(());
-                }
+        ClientMessage::JoinRoom {
+            room_id,
+            password,
+            spectate_if_started,
+        } => {
+            let Some(room) = find_room(session, room_id, state).await else {
+                return Ok(());
             };
-
-            room.add_spectator(player_id);
-
-            // Update connection
-            {
-                let mut conns = state.connections.write().await;
-                if let Some(conn) = conns.get_mut(&player_id) {
-                    conn.room_id = Some(room_id);
-                    conn.is_spectator = true;
-                }
-            }
-
-            let conns = state.connections.read().await;
-            let player_name = conns
-                .get(&player_id)
-                .map(|c| c.player_name.clone())
-                .unwrap_or_default();
-            let snapshot = room.snapshot(&conns);
-            let members = room.all_member_ids();
-
-            // Also send current game state if in progress
-            let game_snapshot = room.game.as_ref().map(|g| g.snapshot());
-            drop(conns);
-            drop(lobby);
-
-            send_to_player(
-                player_id,
-                ServerMessage::RoomJoined {
-                    room_id,
-                    room_state: snapshot,
-                },
-                state,
-            )
+            // Checked here, as hashing is too slow for the room's task
+            let password = match (room.password(), password) {
+                (Some(hash), Some(provided)) => hash.matches(provided).await.then_some(hash),
+                _ => None,
+            };
+            let conn = session.conn.clone();
+            enter_room(session, room, |reply| RoomCommand::Join {
+                conn,
+                password,
+                spectate_if_started,
+                reply,
+            })
             .await;
+        }
 
-            if let Some(gs) = game_snapshot {
-                send_to_player(
-                    player_id,
-                    ServerMessage::GameState { game_state: gs },
-                    state,
-                )
-                .await;
-            }
-
-            broadcast_to_list(
-                &members,
-                &ServerMessage::SpectatorJoined { player_name },
-                state,
-                Some(player_id),
-            )
+        ClientMessage::SpectateRoom { room_id, catch_up } => {
+            let Some(room) = find_room(session, room_id, state).await else {
+                return Ok(());
+            };
+            let conn = session.conn.clone();
+            enter_room(session, room, |reply| RoomCommand::Spectate {
+                conn,
+                catch_up,
+                reply,
+            })
             .await;
         }
 
         ClientMessage::LeaveRoom => {
-            handle_leave_room(player_id, state).await;
+            leave_room(session).await;
+            session.conn.send(ServerMessage::RoomLeft).await;
         }
 
-        ClientMessage::StartGame => {
-            let mut lobby = state.lobby.write().await;
-            let conns = state.connections.read().await;
-
-            let room_id = match conns.get(&player_id).and_then(|c| c.room_id) {
-                Some(id) => id,
-                None => return Ok(()),
-            };
-
-            let room = match lobby.get_room_mut(&room_id) {
-                Some(r) => r,
-                None => return Ok(()),
-            };
-
-            // Only host can start
-            if room.host_id != player_id {
-                send_to_player(
-                    player_id,
-                    ServerMessage::Error {
+        ClientMessage::Tracked { request_id, action } => {
+            if is_room_action(&action) {
+                room_action(session, *action, Some(request_id)).await;
+            } else {
+                session
+                    .conn
+                    .send(ServerMessage::Error {
                         code: ErrorCode::InvalidAction,
-                        message: "Only the host can start the game".into(),
-                    },
-                    state,
-                )
-                .await;
-                return Ok(());
-            }
-
-            if room.player_ids.len() < 2 {
-                send_to_player(
-                    player_id,
-                    ServerMessage::Error {
-                        code: ErrorCode::NotEnoughPlayers,
-                        message: "Need at least 2 players".into(),
-                    },
-                    state,
-                )
-                .await;
-                return Ok(());
-            }
-
-            // Build Player objects from connections
-            let players: Vec<Player> = room
-                .player_ids
-                .iter()
-                .filter_map(|id| {
-                    conns
-                        .get(id)
-                        .map(|c| Player::new(c.player_id, c.player_name.clone()))
-                })
-                .collect();
-
-            let members = room.all_member_ids();
-
-            if let Err(e) = room.start_game(players) {
-                send_to_player(
-                    player_id,
-                    ServerMessage::Error {
-                        code: ErrorCode::InternalError,
-                        message: format!("Failed to start game: {}", e),
-                    },
-                    state,
-                )
-                .await;
-                return Ok(());
+                        message: format!("{} cannot be tracked", action.name()),
+                    })
+                    .await;
             }
-
-            let game_state = room.game.as_ref().unwrap().snapshot();
-            drop(conns);
-            drop(lobby);
-
-            broadcast_to_list(
-                &members,
-                &ServerMessage::GameStarted { game_state },
-                state,
-                None,
-            )
-            .await;
         }
 
-        ClientMessage::RollDice => {
-            let mut lobby = state.lobby.write().await;
-            let conns = state.connections.read().await;
-
-            let room_id = match conns.get(&player_id).and_then(|c| c.room_id) {
-                Some(id) => id,
-                None => return Ok(()),
-            };
+        msg if is_room_action(&msg) => room_action(session, msg, None).await,
 
-            // Check spectator
-            if conns.get(&player_id).map(|c| c.is_spectator).unwrap_or(false) {
-                send_to_player(
-                    player_id,
-                    ServerMessage::Error {
-                        code: ErrorCode::InvalidAction,
-                        message: "Spectators cannot play".into(),
+        ClientMessage::GetLeaderboard { season } => {
+            let msg = match state.persistence() {
+                Some(persistence) => match persistence.standings(season) {
+                    Some(s) => ServerMessage::Leaderboard {
+                        season: s.season,
+                        current_season: persistence.current_season(),
+                        ended_at: s.ended_at,
+                        standings: s.standings,
+                    },
+                    None => ServerMessage::Error {
+                        code: ErrorCode::SeasonNotFound,
+                        message: format!("No standings for season {}", season.unwrap_or(0)),
                     },
-                    state,
-                )
-                .await;
-                return Ok(());
-            }
-
-            let room = match lobby.get_room_mut(&room_id) {
-                Some(r) => r,
-                None => return Ok(()),
-            };
-
-            let game = match room.game.as_mut() {
-                Some(g) => g,
-                None => return Ok(()),
-            };
-
-            let mut rng = rand::rngs::StdRng::from_entropy();
-            if let Err(e) = game.roll_dice(player_id, &mut rng) {
-                let (code, message) = game_error_to_protocol(&e);
-                drop(conns);
-                drop(lobby);
-                send_to_player(player_id, ServerMessage::Error { code, message }, state).await;
-                return Ok(());
-            }
-
-            let turn = game.turn.as_ref().unwrap();
-            let dice = turn.dice;
-            let rolls_remaining = yaht_common::dice::MAX_ROLLS - turn.rolls_used;
-            let members = room.all_member_ids();
-            drop(conns);
-            drop(lobby);
-
-            broadcast_to_list(
-                &members,
-                &ServerMessage::DiceRolled {
-                    dice,
-                    rolls_remaining,
                 },
-                state,
-                None,
-            )
-            .await;
-        }
-
-        ClientMessage::HoldDice { held } => {
-            let mut lobby = state.lobby.write().await;
-            let conns = state.connections.read().await;
-
-            let room_id = match conns.get(&player_id).and_then(|c| c.room_id) {
-                Some(id) => id,
-                None => return Ok(()),
-            };
-
-            let room = match lobby.get_room_mut(&room_id) {
-                Some(r) => r,
-                None => return Ok(()),
-            };
-
-            let game = match room.game.as_mut() {
-                Some(g) => g,
-                None => return Ok(()),
+                None => ServerMessage::Error {
+                    code: ErrorCode::InvalidAction,
+                    message: "Ratings are not enabled on this server".into(),
+                },
             };
-
-            if let Err(e) = game.hold_dice(player_id, held) {
-                let (code, message) = game_error_to_protocol(&e);
-                drop(conns);
-                drop(lobby);
-                send_to_player(player_id, ServerMessage::Error { code, message }, state).await;
-                return Ok(());
-            }
-
-            let dice = game.turn.as_ref().unwrap().dice;
-            let members = room.all_member_ids();
-            drop(conns);
-            drop(lobby);
-
-            broadcast_to_list(
-                &members,
-                &ServerMessage::DiceHeld { dice },
-                state,
-                None,
-            )
-            .await;
+            session.conn.send(msg).await;
         }
 
-        ClientMessage::ScoreCategory { category } => {
-            let mut lobby = state.lobby.write().await;
-            let conns = state.connections.read().await;
-
-            let room_id = match conns.get(&player_id).and_then(|c| c.room_id) {
-                Some(id) => id,
-                None => return Ok(()),
-            };
-
-            let room = match lobby.get_room_mut(&room_id) {
-                Some(r) => r,
-                None => return Ok(()),
-            };
-
-            {
-                let game = match room.game.as_ref() {
-                    Some(g) => g,
-                    None => return Ok(()),
-                };
-                // Validate before mutation
-                if !game.is_current_player(player_id) {
-                    let (code, message) = game_error_to_protocol(&yaht_common::game::GameError::NotYourTurn);
-                    drop(conns);
-                    drop(lobby);
-                    send_to_player(player_id, ServerMessage::Error { code, message }, state).await;
-                    return Ok(());
-                }
-            }
-
-            let game = room.game.as_mut().unwrap();
-            let prev_player_id = game.current_player().id;
-
-            let score = match game.score_category(player_id, category) {
-                Ok(s) => s,
-                Err(e) => {
-                    let (code, message) = game_error_to_protocol(&e);
-                    drop(conns);
-                    drop(lobby);
-                    send_to_player(player_id, ServerMessage::Error { code, message }, state).await;
-                    return Ok(());
-                }
-            };
-
-            let is_finished = game.phase == GamePhase::Finished;
-
-            let mut messages = vec![
-                ServerMessage::CategoryScored {
-                    player_id: prev_player_id,
-                    category,
-                    score,
+        ClientMessage::GetPlayerStats { player } => {
+            let msg = match state.persistence() {
+                Some(persistence) => ServerMessage::PlayerStats {
+                    categories: persistence.category_stats(&player),
+                    turn_times: persistence.turn_times(&player),
+                    player,
                 },
-                ServerMessage::TurnEnded {
-                    player_id: prev_player_id,
+                None => ServerMessage::Error {
+                    code: ErrorCode::InvalidAction,
+                    message: "Player stats are not enabled on this server".into(),
                 },
-            ];
-
-            if is_finished {
-                let final_scores: Vec<(Uuid, String, u16)> = game
-                    .players
-                    .iter()
-                    .map(|p| (p.id, p.name.clone(), p.scorecard.grand_total()))
-                    .collect();
-                let winner_id = game.winner().map(|w| w.id).unwrap_or(prev_player_id);
-                messages.push(ServerMessage::GameOver {
-                    final_scores,
-                    winner_id,
-                });
-            } else {
-                let next = game.current_player();
-                messages.push(ServerMessage::TurnStarted {
-                    player_id: next.id,
-                    player_name: next.name.clone(),
-                    turn_number: game.round,
-                });
-            }
-
-            let members = room.all_member_ids();
-
-            drop(conns);
-            drop(lobby);
-
-            for msg in &messages {
-                broadcast_to_list(&members, msg, state, None).await;
-            }
-        }
-
-        ClientMessage::Chat { message } => {
-            let lobby = state.lobby.read().await;
-            let conns = state.connections.read().await;
-
-            let (room_id, player_name) = match conns.get(&player_id) {
-                Some(c) => (c.room_id, c.player_name.clone()),
-                None => return Ok(()),
-            };
-
-            let room_id = match room_id {
-                Some(id) => id,
-                None => return Ok(()),
             };
+            session.conn.send(msg).await;
+        }
 
-            let room = match lobby.get_room(&room_id) {
-                Some(r) => r,
-                None => return Ok(()),
+        ClientMessage::GetRecentGames { player } => {
+            let msg = match state.persistence() {
+                Some(persistence) => ServerMessage::RecentGames {
+                    games: persistence.recent_games(player.as_deref()),
+                },
+                None => no_archive(),
             };
+            session.conn.send(msg).await;
+        }
 
-            let members = room.all_member_ids();
-            let timestamp = chrono::Utc::now().timestamp();
-            drop(conns);
-            drop(lobby);
-
-            broadcast_to_list(
-                &members,
-                &ServerMessage::ChatMessage {
-                    sender_id: player_id,
-                    sender_name: player_name,
-                    message,
-                    timestamp,
+        ClientMessage::GetGame { game_id } => {
+            let msg = match state.persistence() {
+                Some(persistence) => match persistence.game(game_id) {
+                    Some(game) => ServerMessage::GameDetails {
+                        game: Box::new(game.clone()),
+                    },
+                    None => ServerMessage::Error {
+                        code: ErrorCode::GameNotFound,
+                        message: "No such game in the archive".into(),
+                    },
                 },
-                state,
-                None,
-            )
-            .await;
+                None => no_archive(),
+            };
+            session.conn.send(msg).await;
         }
 
         ClientMessage::Ping => {
-            send_to_player(player_id, ServerMessage::Pong, state).await;
+            session.conn.send(ServerMessage::Pong).await;
         }
 
         ClientMessage::Disconnect => {
-            handle_disconnect(player_id, state).await;
+            handle_disconnect(session).await;
         }
 
         _ => {}
@@ -530,110 +260,157 @@ pub async fn handle_message(
     Ok(())
 }
 
-async fn handle_leave_room(player_id: Uuid, state: &SharedState) {
-    let mut lobby = state.lobby.write().await;
-    let conns = state.connections.read().await;
+/// Game and chat messages, handled by the player's room.
+fn is_room_action(msg: &ClientMessage) -> bool {
+    matches!(
+        msg,
+        ClientMessage::StartGame
+            | ClientMessage::UpdateRoomSettings { .. }
+            | ClientMessage::SetReady { .. }
+            | ClientMessage::MoveSeat { .. }
+            | ClientMessage::Kick { .. }
+            | ClientMessage::RollDice
+            | ClientMessage::HoldDice { .. }
+            | ClientMessage::ScoreCategory { .. }
+            | ClientMessage::GetDiceStats
+            | ClientMessage::AckTurn { .. }
+            | ClientMessage::Chat { .. }
+            | ClientMessage::Whisper { .. }
+    )
+}
 
-    let room_id = match conns.get(&player_id).and_then(|c| c.room_id) {
-        Some(id) => id,
-        None => return,
+async fn room_action(session: &mut Session, mut msg: ClientMessage, request_id: Option<u64>) {
+    let Some(room) = &session.room else {
+        return;
     };
-
-    let player_name = conns
-        .get(&player_id)
-        .map(|c| c.player_name.clone())
-        .unwrap_or_default();
-    let is_spectator = conns
-        .get(&player_id)
-        .map(|c| c.is_spectator)
-        .unwrap_or(false);
-
-    if let Some(room) = lobby.get_room_mut(&room_id) {
-        room.remove_player(&player_id);
-        let members = room.all_member_ids();
-        let is_empty = room.is_empty();
-        drop(conns);
-
-        if is_spectator {
-            broadcast_to_list(
-                &members,
-                &ServerMessage::SpectatorLeft { player_name },
-                state,
-                None,
-            )
+    // Spectators may chat, ask for dice stats and check their view of
+    // the game, but never act on the game or the room
+    if session.spectating
+        && !matches!(
+            msg,
+            ClientMessage::Chat { .. }
+                | ClientMessage::Whisper { .. }
+                | ClientMessage::GetDiceStats
+                | ClientMessage::AckTurn { .. }
+        )
+    {
+        session
+            .conn
+            .send(ServerMessage::Error {
+                code: ErrorCode::InvalidAction,
+                message: "Spectators cannot play".into(),
+            })
             .await;
-        } else {
-            broadcast_to_list(
-                &members,
-                &ServerMessage::PlayerLeft {
-                    player_id,
-                    player_name,
-                },
-                state,
-                None,
-            )
-            .await;
-        }
-
-        if is_empty {
-            lobby.remove_room(&room_id);
-        }
-    } else {
-        drop(conns);
+        return;
     }
-
-    // Clear room_id on connection
-    let mut conns = state.connections.write().await;
-    if let Some(conn) = conns.get_mut(&player_id) {
-        conn.room_id = None;
-        conn.is_spectator = false;
+    // A new room password is hashed here, off the room's task
+    let password = match &mut msg {
+        ClientMessage::UpdateRoomSettings { password, .. } => match password.take() {
+            Some(password) => Some(RoomPassword::hash(password).await),
+            None => None,
+        },
+        _ => None,
+    };
+    let cmd = RoomCommand::Action {
+        player_id: session.conn.player_id,
+        msg,
+        request_id,
+        password,
+    };
+    if !room.send(cmd).await {
+        session.room = None;
     }
+}
 
-    send_to_player(player_id, ServerMessage::RoomLeft, state).await;
+/// Look up a room in the lobby, telling the player if it does not exist.
+async fn find_room(session: &Session, room_id: Uuid, state: &SharedState) -> Option<RoomHandle> {
+    let room = state.lobby.read().await.get_room(&room_id);
+    if room.is_none() {
+        send_room_not_found(session).await;
+    }
+    room
 }
 
-pub async fn handle_disconnect(player_id: Uuid, state: &SharedState) {
-    // Leave room first
-    handle_leave_room(player_id, state).await;
+/// Leave the current room, then ask `room` to admit this connection as a
+/// player or spectator.
+async fn enter_room(
+    session: &mut Session,
+    room: RoomHandle,
+    command: impl FnOnce(oneshot::Sender<Option<Admission>>) -> RoomCommand,
+) {
+    leave_room(session).await;
+
+    let (reply, admitted) = oneshot::channel();
+    room.send(command(reply)).await;
+    match admitted.await {
+        Ok(Some(admission)) => {
+            session.room = Some(room);
+            session.spectating = admission == Admission::Spectator;
+        }
+        // The room already told the player why
+        Ok(None) => {}
+        // The room closed before handling the request
+        Err(_) => send_room_not_found(session).await,
+    }
+}
 
-    // Remove connection
-    state.connections.write().await.remove(&player_id);
+async fn leave_room(session: &mut Session) {
+    session.spectating = false;
+    if let Some(room) = session.room.take() {
+        room.send(RoomCommand::Leave {
+            player_id: session.conn.player_id,
+        })
+        .await;
+    }
+}
 
-    // Prune empty rooms
-    state.lobby.write().await.prune_empty_rooms();
+async fn send_room_not_found(session: &Session) {
+    session
+        .conn
+        .send(ServerMessage::Error {
+            code: ErrorCode::RoomNotFound,
+            message: "Room not found".into(),
+        })
+        .await;
 }
 
-async fn send_to_player(player_id: Uuid, msg: ServerMessage, state: &SharedState) {
-    let conns = state.connections.read().await;
-    if let Some(conn) = conns.get(&player_id) {
-        let _ = conn.tx.send(msg).await;
+/// The answer to archive queries on a server without a data directory.
+fn no_archive() -> ServerMessage {
+    ServerMessage::Error {
+        code: ErrorCode::InvalidAction,
+        message: "Game history is not enabled on this server".into(),
     }
 }
 
-/// Broadcast a message to a list of player IDs. Optionally exclude one player.
-async fn broadcast_to_list(
-    member_ids: &[Uuid],
-    msg: &ServerMessage,
-    state: &SharedState,
-    exclude: Option<Uuid>,
-) {
-    let conns = state.connections.read().await;
-    for &id in member_ids {
-        if Some(id) == exclude {
-            continue;
-        }
-        if let Some(conn) = conns.get(&id) {
-            let _ = conn.tx.send(msg.clone()).await;
-        }
-    }
+pub async fn handle_disconnect(session: &mut Session) {
+    leave_room(session).await;
 }
 
-fn game_error_to_protocol(e: &yaht_common::game::GameError) -> (ErrorCode, String) {
-    use yaht_common::game::GameError;
-    match e {
-        GameError::NotYourTurn => (ErrorCode::NotYourTurn, e.to_string()),
-        GameError::CategoryAlreadyScored => (ErrorCode::CategoryAlreadyScored, e.to_string()),
-        GameError::GameNotInProgress => (ErrorCode::InvalidAction, e.to_string()),
-        _ => (ErrorCode::InvalidAction, e.to_string()),
+/// The connection closed without the player leaving. A player in a game
+/// in progress keeps their seat for a while, under the session's resume
+/// token; anyone else leaves.
+pub async fn handle_connection_lost(session: &mut Session, state: &SharedState) {
+    if session.spectating {
+        leave_room(session).await;
+        return;
+    }
+    let Some(room) = session.room.take() else {
+        return;
+    };
+    let (reply, held) = oneshot::channel();
+    let cmd = RoomCommand::Disconnect {
+        player_id: session.conn.player_id,
+        reply,
+    };
+    if room.send(cmd).await && held.await.unwrap_or(false) {
+        state.resume_tokens.park(
+            session.resume_token,
+            ParkedSession {
+                player_id: session.conn.player_id,
+                player_name: session.conn.player_name.clone(),
+                identity: session.conn.identity,
+                room,
+            },
+        );
     }
 }
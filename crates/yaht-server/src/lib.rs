@@ -0,0 +1,13 @@
+mod connection;
+pub mod events;
+mod handler;
+mod lobby;
+pub mod monitor;
+mod outbox;
+mod password;
+pub mod persistence;
+pub mod ratelimit;
+mod registry;
+mod room;
+pub mod server;
+pub mod sim;
@@ -3,10 +3,12 @@ use uuid::Uuid;
 
 use yaht_common::lobby::RoomInfo;
 
-use crate::room::Room;
+use crate::room::RoomHandle;
 
+/// Directory of running rooms. Each room owns its own state in a separate
+/// task; this only maps IDs to their handles.
 pub struct LobbyManager {
-    pub rooms: HashMap<Uuid, Room>,
+    pub rooms: HashMap<Uuid, RoomHandle>,
 }
 
 impl LobbyManager {
@@ -16,41 +18,19 @@ impl LobbyManager {
         }
     }
 
-    pub fn create_room(&mut self, name: String, max_players: u8, host_id: Uuid, password: Option<String>) -> Uuid {
-        let id = Uuid::new_v4();
-        self.rooms
-            .insert(id, Room::new(id, name, max_players, host_id, password));
-        id
+    pub fn insert_room(&mut self, room: RoomHandle) {
+        self.rooms.insert(room.id, room);
     }
 
     pub fn list_rooms(&self) -> Vec<RoomInfo> {
         self.rooms.values().map(|r| r.info()).collect()
     }
 
-    pub fn get_room(&self, id: &Uuid) -> Option<&Room> {
-        self.rooms.get(id)
-    }
-
-    pub fn get_room_mut(&mut self, id: &Uuid) -> Option<&mut Room> {
-        self.rooms.get_mut(id)
+    pub fn get_room(&self, id: &Uuid) -> Option<RoomHandle> {
+        self.rooms.get(id).cloned()
     }
 
     pub fn remove_room(&mut self, id: &Uuid) {
         self.rooms.remove(id);
     }
-
-    pub fn prune_empty_rooms(&mut self) {
-        self.rooms.retain(|_, r| !r.is_empty());
-    }
-
-    /// Find which room a player is in.
-    #[allow(dead_code)]
-    pub fn find_player_room(&self, player_id: Uuid) -> Option<Uuid> {
-        for (room_id, room) in &self.rooms {
-            if room.player_ids.contains(&player_id) || room.spectator_ids.contains(&player_id) {
-                return Some(*room_id);
-            }
-        }
-        None
-    }
 }
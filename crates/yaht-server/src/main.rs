@@ -1,12 +1,20 @@
-mod connection;
-mod handler;
-mod lobby;
-mod room;
-mod server;
-
 use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::{Parser, ValueEnum};
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+
+use yaht_common::protocol::{self, DEFAULT_MAX_FRAME_LENGTH};
+use yaht_server::events::{self, EventBus};
+use yaht_server::monitor::PlayMonitor;
+use yaht_server::persistence;
+use yaht_server::ratelimit::{RateLimit, RateLimits};
+use yaht_server::server::{self, DirectoryConfig, ServerConfig};
 
-use clap::Parser;
+/// Time between heartbeats to the public server directory.
+const DIRECTORY_HEARTBEAT: Duration = Duration::from_secs(60);
 
 /// YAHT Server - Multiplayer Yahtzee game server
 #[derive(Parser, Debug)]
@@ -19,21 +27,183 @@ struct Args {
     /// Maximum simultaneous connections allowed
     #[arg(short, long, default_value_t = 100)]
     max_connections: usize,
+
+    /// Game events each room keeps for spectators catching up
+    #[arg(long, default_value_t = 2000)]
+    replay_history: usize,
+
+    /// Seed for player ids, room ids and dice, to replay a reported game exactly
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Directory for persistent ratings and season archives (ratings are off without it)
+    #[arg(long)]
+    data_dir: Option<PathBuf>,
+
+    /// Season length in days; 0 disables season resets
+    #[arg(long, default_value_t = 30)]
+    season_days: u32,
+
+    /// Warn about message handlers and room commands slower than this many milliseconds
+    #[arg(long, default_value_t = 100)]
+    slow_handler_ms: u64,
+
+    /// Largest message accepted from or sent to a client, in bytes
+    #[arg(long, default_value_t = DEFAULT_MAX_FRAME_LENGTH)]
+    max_frame_length: usize,
+
+    /// Game actions per connection, as BURST/PER_SECOND
+    #[arg(long, default_value = "20/10")]
+    gameplay_limit: RateLimit,
+
+    /// Chat messages per connection, as BURST/PER_SECOND
+    #[arg(long, default_value = "5/1")]
+    chat_limit: RateLimit,
+
+    /// Lobby requests (create, join, list rooms, leaderboard) per connection, as BURST/PER_SECOND
+    #[arg(long, default_value = "10/2")]
+    lobby_limit: RateLimit,
+
+    /// Seconds to hold a seat for a player whose connection drops mid-game
+    #[arg(long, default_value_t = 60)]
+    resume_grace_secs: u64,
+
+    /// Seconds a room stays listed as finished after its game, open to
+    /// rejoin even once everyone has left
+    #[arg(long, default_value_t = 300)]
+    finished_room_cooldown_secs: u64,
+
+    /// Log output format
+    #[arg(long, value_enum, default_value_t = LogFormat::Text)]
+    log_format: LogFormat,
+
+    /// Write logs to daily rotated files in this directory instead of stdout
+    #[arg(long)]
+    log_dir: Option<PathBuf>,
+
+    /// Stream anonymized game events as server-sent events at
+    /// http://ADDR/events, with dice statistics at /dice and room
+    /// scoreboards at /rooms/{id} (off without it)
+    #[arg(long)]
+    events_bind: Option<SocketAddr>,
+
+    /// List this server in the public directory at this URL, with a
+    /// heartbeat every minute (unlisted without it)
+    #[arg(long, requires = "public_address")]
+    directory_url: Option<String>,
+
+    /// Name shown in the directory
+    #[arg(long, default_value = "yaht server")]
+    public_name: String,
+
+    /// Address players should connect to, as HOST:PORT
+    #[arg(long)]
+    public_address: Option<String>,
+
+    /// Region shown in the directory, e.g. "eu-west"
+    #[arg(long)]
+    region: Option<String>,
+
+    /// Log a warning for players whose play looks automated: near-instant
+    /// actions, or category choices that almost always match the hard AI
+    #[arg(long)]
+    monitor_play: bool,
+
+    /// Refuse to open a room with the same name as one already open (ignoring case)
+    #[arg(long)]
+    unique_room_names: bool,
+
+    /// Print the JSON Schema of every protocol message and exit
+    #[arg(long)]
+    dump_schema: bool,
 }
 
-#[tokio::main]
-async fn main() -> anyhow::Result<()> {
-    tracing_subscriber::fmt()
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum LogFormat {
+    Text,
+    /// One JSON object per line, including the current span's fields
+    Json,
+}
+
+/// Set up logging. The returned guard flushes file output when dropped, so
+/// it must live until the server exits.
+fn init_logging(format: LogFormat, log_dir: Option<&PathBuf>) -> Option<WorkerGuard> {
+    let (writer, guard) = match log_dir {
+        Some(dir) => {
+            let appender = tracing_appender::rolling::daily(dir, "yaht-server.log");
+            let (writer, guard) = tracing_appender::non_blocking(appender);
+            (BoxMakeWriter::new(writer), Some(guard))
+        }
+        None => (BoxMakeWriter::new(std::io::stdout), None),
+    };
+
+    let builder = tracing_subscriber::fmt()
         .with_env_filter(
             tracing_subscriber::EnvFilter::try_from_default_env()
                 .unwrap_or_else(|_| "yaht_server=debug,yaht_common=debug".into()),
         )
-        .init();
+        .with_ansi(log_dir.is_none())
+        .with_writer(writer);
+    match format {
+        LogFormat::Text => builder.init(),
+        LogFormat::Json => builder.json().with_current_span(true).with_span_list(false).init(),
+    }
+    guard
+}
 
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
     let args = Args::parse();
+    if args.dump_schema {
+        serde_json::to_writer_pretty(std::io::stdout().lock(), &protocol::json_schema())?;
+        return Ok(());
+    }
+    let _log_guard = init_logging(args.log_format, args.log_dir.as_ref());
 
     let addr: SocketAddr = args.bind.parse()?;
 
+    let persistence = match args.data_dir {
+        Some(dir) => Some(persistence::Persistence::open(dir, args.season_days)?),
+        None => None,
+    };
+
+    let event_bus = EventBus::default();
+    if let Some(events_addr) = args.events_bind {
+        let listener = tokio::net::TcpListener::bind(events_addr).await?;
+        tracing::info!("Streaming game events at http://{}/events", events_addr);
+        let bus = event_bus.clone();
+        tokio::spawn(async move {
+            if let Err(e) = events::serve(listener, bus).await {
+                tracing::error!("Event stream stopped: {}", e);
+            }
+        });
+    }
+
     tracing::info!("Starting yaht server on {} (max {} connections)", addr, args.max_connections);
-    server::run(addr, args.max_connections).await
+    let config = ServerConfig {
+        max_connections: args.max_connections,
+        replay_history: args.replay_history,
+        seed: args.seed,
+        slow_handler: Duration::from_millis(args.slow_handler_ms),
+        max_frame_length: args.max_frame_length,
+        rate_limits: RateLimits {
+            gameplay: args.gameplay_limit,
+            chat: args.chat_limit,
+            lobby: args.lobby_limit,
+        },
+        events: event_bus,
+        resume_grace: Duration::from_secs(args.resume_grace_secs),
+        finished_room_cooldown: Duration::from_secs(args.finished_room_cooldown_secs),
+        directory: args.directory_url.map(|url| DirectoryConfig {
+            url,
+            name: args.public_name,
+            address: args.public_address.unwrap_or_default(),
+            region: args.region,
+            interval: DIRECTORY_HEARTBEAT,
+        }),
+        monitor: args.monitor_play.then(PlayMonitor::default),
+        unique_room_names: args.unique_room_names,
+        ..ServerConfig::default()
+    };
+    server::run(addr, config, persistence).await
 }
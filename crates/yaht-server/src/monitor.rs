@@ -0,0 +1,130 @@
+//! Plausibility monitoring for ranked servers.
+//!
+//! Rolls are server-side, but nothing stops a bot from playing a seat. The
+//! monitor keeps lifetime tallies per player name of how fast they act and
+//! how often their category choice matches the hard AI's, and logs a
+//! warning the first time a player crosses a threshold. It flags players
+//! for an admin to review; it never acts on its own.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+/// When play counts as implausible.
+#[derive(Debug, Clone, Copy)]
+pub struct MonitorThresholds {
+    /// Actions taken faster than this after the previous one are instant.
+    pub instant: Duration,
+    /// Actions seen before judging a player's speed.
+    pub min_actions: u32,
+    /// Share of instant actions that gets a player flagged.
+    pub instant_share: f64,
+    /// Category choices seen before judging a player's agreement with the AI.
+    pub min_choices: u32,
+    /// Share of choices agreeing with the hard AI that gets a player flagged.
+    pub agreement_share: f64,
+}
+
+impl Default for MonitorThresholds {
+    fn default() -> Self {
+        Self {
+            instant: Duration::from_millis(300),
+            min_actions: 200,
+            instant_share: 0.8,
+            min_choices: 65,
+            agreement_share: 0.98,
+        }
+    }
+}
+
+/// Why a player was flagged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Suspicion {
+    InstantDecisions,
+    OptimalAgreement,
+}
+
+/// One player's tallies.
+#[derive(Debug, Clone, Default)]
+pub struct PlayRecord {
+    pub actions: u32,
+    pub instant: u32,
+    pub choices: u32,
+    pub agreed: u32,
+    pub flags: Vec<Suspicion>,
+}
+
+/// Shared by every room; cloning shares the tallies.
+#[derive(Debug, Clone, Default)]
+pub struct PlayMonitor {
+    thresholds: MonitorThresholds,
+    players: Arc<Mutex<HashMap<String, PlayRecord>>>,
+}
+
+impl PlayMonitor {
+    pub fn new(thresholds: MonitorThresholds) -> Self {
+        Self {
+            thresholds,
+            players: Arc::default(),
+        }
+    }
+
+    /// A roll, hold or score by `player`, `think` after their previous
+    /// action or the start of their turn.
+    pub fn record_action(&self, player: &str, think: Duration) {
+        self.update(player, |record, t| {
+            record.actions += 1;
+            if think < t.instant {
+                record.instant += 1;
+            }
+            (record.actions >= t.min_actions && share(record.instant, record.actions) >= t.instant_share)
+                .then_some(Suspicion::InstantDecisions)
+        });
+    }
+
+    /// A category choice by `player`, and whether the hard AI would have made it.
+    pub fn record_choice(&self, player: &str, agreed: bool) {
+        self.update(player, |record, t| {
+            record.choices += 1;
+            if agreed {
+                record.agreed += 1;
+            }
+            (record.choices >= t.min_choices && share(record.agreed, record.choices) >= t.agreement_share)
+                .then_some(Suspicion::OptimalAgreement)
+        });
+    }
+
+    /// `player`'s tallies so far.
+    pub fn record(&self, player: &str) -> Option<PlayRecord> {
+        self.lock().get(player).cloned()
+    }
+
+    fn update(&self, player: &str, check: impl FnOnce(&mut PlayRecord, &MonitorThresholds) -> Option<Suspicion>) {
+        let mut players = self.lock();
+        let record = players.entry(player.to_string()).or_default();
+        let Some(suspicion) = check(record, &self.thresholds) else {
+            return;
+        };
+        if record.flags.contains(&suspicion) {
+            return;
+        }
+        record.flags.push(suspicion);
+        tracing::warn!(
+            player,
+            ?suspicion,
+            actions = record.actions,
+            instant = record.instant,
+            choices = record.choices,
+            agreed = record.agreed,
+            "Implausible play, flagged for review"
+        );
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, PlayRecord>> {
+        self.players.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+fn share(n: u32, of: u32) -> f64 {
+    n as f64 / of as f64
+}
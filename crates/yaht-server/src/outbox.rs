@@ -0,0 +1,125 @@
+use std::time::Duration;
+
+use bytes::Bytes;
+use uuid::Uuid;
+
+use yaht_common::protocol::{ErrorCode, FrameEncoder, ServerMessage};
+
+use crate::connection::ConnectionHandle;
+use crate::room::Room;
+
+/// How long a streamed message may wait for room in its recipient's queue
+/// before they are disconnected.
+const STREAM_TIMEOUT: Duration = Duration::from_secs(5);
+
+enum Recipient {
+    Member(Uuid),
+    /// A member sent more at once than their queue holds, e.g. a replay.
+    /// Delivery waits for the queue to drain.
+    Streamed(Uuid),
+    Members { except: Option<Uuid> },
+    /// A connection that is not (or no longer) a member, e.g. a rejected joiner.
+    Conn(ConnectionHandle),
+}
+
+enum Payload {
+    Message(ServerMessage),
+    /// Already serialized, e.g. straight from a borrowed view.
+    Frame(Bytes),
+}
+
+/// Messages produced while handling a room command.
+///
+/// Room handlers are synchronous and never send directly; they queue
+/// messages here and the room task delivers them once the handler has
+/// returned. Nothing is sent while room state is borrowed or a lock is held.
+#[derive(Default)]
+pub struct Outbox {
+    messages: Vec<(Recipient, Payload)>,
+}
+
+impl Outbox {
+    pub fn send(&mut self, player_id: Uuid, msg: ServerMessage) {
+        self.messages.push((Recipient::Member(player_id), Payload::Message(msg)));
+    }
+
+    pub fn send_frame(&mut self, player_id: Uuid, frame: Bytes) {
+        self.messages.push((Recipient::Member(player_id), Payload::Frame(frame)));
+    }
+
+    /// Like `send`, for one of many messages sent together, which may
+    /// fill the member's queue.
+    pub fn stream(&mut self, player_id: Uuid, msg: ServerMessage) {
+        self.messages.push((Recipient::Streamed(player_id), Payload::Message(msg)));
+    }
+
+    pub fn stream_frame(&mut self, player_id: Uuid, frame: Bytes) {
+        self.messages.push((Recipient::Streamed(player_id), Payload::Frame(frame)));
+    }
+
+    pub fn send_conn(&mut self, conn: &ConnectionHandle, msg: ServerMessage) {
+        self.messages.push((Recipient::Conn(conn.clone()), Payload::Message(msg)));
+    }
+
+    pub fn error(&mut self, player_id: Uuid, code: ErrorCode, message: impl Into<String>) {
+        self.send(
+            player_id,
+            ServerMessage::Error {
+                code,
+                message: message.into(),
+            },
+        );
+    }
+
+    pub fn broadcast(&mut self, msg: ServerMessage) {
+        self.messages.push((Recipient::Members { except: None }, Payload::Message(msg)));
+    }
+
+    pub fn broadcast_except(&mut self, msg: ServerMessage, except: Uuid) {
+        self.messages.push((
+            Recipient::Members {
+                except: Some(except),
+            },
+            Payload::Message(msg),
+        ));
+    }
+
+    /// Send everything in order. Each message is serialized once, so a
+    /// broadcast frame is shared by all recipients. Only streamed messages
+    /// wait on a slow client; otherwise one whose queue is full is
+    /// disconnected.
+    pub async fn deliver(self, room: &Room, encoder: &mut FrameEncoder) {
+        for (recipient, payload) in self.messages {
+            let bytes = match payload {
+                Payload::Frame(bytes) => bytes,
+                Payload::Message(msg) => match encoder.encode(&msg) {
+                    Ok(bytes) => bytes,
+                    Err(e) => {
+                        tracing::error!("Failed to serialize message: {}", e);
+                        continue;
+                    }
+                },
+            };
+            match recipient {
+                Recipient::Member(id) => {
+                    if let Some(conn) = room.member(id) {
+                        conn.try_send_bytes(bytes);
+                    }
+                }
+                Recipient::Streamed(id) => {
+                    if let Some(conn) = room.member(id) {
+                        conn.send_bytes_within(bytes, STREAM_TIMEOUT).await;
+                    }
+                }
+                Recipient::Conn(conn) => conn.try_send_bytes(bytes),
+                Recipient::Members { except } => {
+                    for conn in room.members() {
+                        if Some(conn.player_id) != except {
+                            conn.try_send_bytes(bytes.clone());
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
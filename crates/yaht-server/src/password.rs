@@ -0,0 +1,66 @@
+//! Room passwords, kept only as Argon2 hashes.
+
+use std::fmt;
+
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use rand::rngs::OsRng;
+
+/// A room's password as a salted hash in PHC string form, ready to be
+/// stored. Its `Debug` output leaves the hash out.
+///
+/// Hashing is slow on purpose, so async code should go through `hash` and
+/// `matches`, which run it on the blocking pool.
+#[derive(Clone, PartialEq, Eq)]
+pub struct RoomPassword(String);
+
+impl RoomPassword {
+    /// Hash `password`, or `None` for an empty one, which means no password.
+    pub fn new(password: &str) -> Option<Self> {
+        if password.is_empty() {
+            return None;
+        }
+        let salt = SaltString::generate(&mut OsRng);
+        match Argon2::default().hash_password(password.as_bytes(), &salt) {
+            Ok(hash) => Some(Self(hash.to_string())),
+            Err(e) => {
+                // Better a room that refuses everyone than an open one
+                tracing::error!("Failed to hash a room password: {}", e);
+                Some(Self(String::new()))
+            }
+        }
+    }
+
+    /// Whether `provided` is the password this was made from.
+    pub fn verify(&self, provided: &str) -> bool {
+        PasswordHash::new(&self.0)
+            .is_ok_and(|hash| Argon2::default().verify_password(provided.as_bytes(), &hash).is_ok())
+    }
+
+    /// `new`, off the async workers.
+    pub async fn hash(password: String) -> Option<Self> {
+        if password.is_empty() {
+            return None;
+        }
+        tokio::task::spawn_blocking(move || Self::new(&password))
+            .await
+            .unwrap_or_else(|e| {
+                tracing::error!("Failed to hash a room password: {}", e);
+                Some(Self(String::new()))
+            })
+    }
+
+    /// `verify`, off the async workers.
+    pub async fn matches(&self, provided: String) -> bool {
+        let hash = self.clone();
+        tokio::task::spawn_blocking(move || hash.verify(&provided))
+            .await
+            .unwrap_or(false)
+    }
+}
+
+impl fmt::Debug for RoomPassword {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RoomPassword(..)")
+    }
+}
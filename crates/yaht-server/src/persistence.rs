@@ -0,0 +1,457 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{mpsc, Arc};
+use std::thread::JoinHandle;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use yaht_common::protocol::{ArchivedGame, CategoryStats, GameSummary, HeadToHead, LeaderboardEntry, TurnTimes};
+use yaht_common::rating::{self, INITIAL_RATING};
+use yaht_common::records::{Milestone, PersonalBests};
+use yaht_common::scoring::Category;
+
+const RATINGS_FILE: &str = "ratings.json";
+const GAMES_FILE: &str = "games.json";
+/// Share of a rating's distance from the initial rating kept across a season reset.
+const SEASON_CARRY_OVER: f64 = 0.5;
+/// Standings are truncated to this many entries (keeps frames small).
+const MAX_STANDINGS: usize = 50;
+/// Finished games kept in the archive; the oldest are dropped first.
+const MAX_ARCHIVED_GAMES: usize = 500;
+/// Games listed by `recent_games`.
+const RECENT_GAMES: usize = 20;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RatingRecord {
+    pub rating: f64,
+    pub games_played: u32,
+    pub wins: u32,
+}
+
+impl Default for RatingRecord {
+    fn default() -> Self {
+        Self {
+            rating: INITIAL_RATING,
+            games_played: 0,
+            wins: 0,
+        }
+    }
+}
+
+/// Final standings of a past season.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeasonArchive {
+    pub season: u32,
+    pub started_at: i64,
+    pub ended_at: i64,
+    pub standings: Vec<LeaderboardEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PersistedData {
+    season: u32,
+    season_started_at: i64,
+    ratings: HashMap<String, RatingRecord>,
+    archives: Vec<SeasonArchive>,
+    /// Lifetime records keyed by client identity, then opponent identity.
+    #[serde(default)]
+    head_to_head: HashMap<Uuid, HashMap<Uuid, HeadToHead>>,
+    /// Lifetime per-category tallies keyed by player name, in `Category::ALL` order.
+    #[serde(default)]
+    category_stats: HashMap<String, Vec<CategoryStats>>,
+    #[serde(default)]
+    personal_bests: HashMap<String, PersonalBests>,
+    /// Lifetime turn times keyed by player name.
+    #[serde(default)]
+    turn_times: HashMap<String, TurnTimes>,
+}
+
+/// Standings for one season, as returned by `Persistence::standings`.
+pub struct SeasonStandings {
+    pub season: u32,
+    pub ended_at: Option<i64>,
+    pub standings: Vec<LeaderboardEntry>,
+}
+
+/// Ratings, season history and finished games, stored as JSON in the
+/// server's data directory.
+pub struct Persistence {
+    /// Season length in seconds; 0 disables automatic season rollover.
+    season_length: i64,
+    data: PersistedData,
+    /// Finished games, oldest first. Kept in their own file, as they
+    /// outgrow the ratings by far.
+    games: Vec<Arc<ArchivedGame>>,
+    writer: Writer,
+}
+
+impl Persistence {
+    pub fn open(dir: PathBuf, season_days: u32) -> anyhow::Result<Self> {
+        std::fs::create_dir_all(&dir)?;
+        let path = dir.join(RATINGS_FILE);
+        let data = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => PersistedData {
+                season: 1,
+                season_started_at: chrono::Utc::now().timestamp(),
+                ratings: HashMap::new(),
+                archives: Vec::new(),
+                head_to_head: HashMap::new(),
+                category_stats: HashMap::new(),
+                personal_bests: HashMap::new(),
+                turn_times: HashMap::new(),
+            },
+            Err(e) => return Err(e.into()),
+        };
+        tracing::info!(
+            "Loaded {} ratings from {} (season {})",
+            data.ratings.len(),
+            path.display(),
+            data.season
+        );
+        let games: Vec<ArchivedGame> = match std::fs::read(dir.join(GAMES_FILE)) {
+            Ok(bytes) => serde_json::from_slice(&bytes)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self {
+            writer: Writer::spawn(dir)?,
+            season_length: season_days as i64 * 24 * 60 * 60,
+            data,
+            games: games.into_iter().map(Arc::new).collect(),
+        })
+    }
+
+    pub fn current_season(&self) -> u32 {
+        self.data.season
+    }
+
+    /// Apply rating changes for a finished game. Returns each player's new
+    /// rating and change, in the order given. Only an outright `winner` is
+    /// credited with a win; a draw credits no one. Players are told apart
+    /// by name, so no two in `results` may share one.
+    pub fn record_game(&mut self, results: &[(String, u16)], winner: Option<&str>) -> Vec<(String, i32, i32)> {
+        let summary = self.rate(results, winner);
+        self.save_ratings();
+        summary
+    }
+
+    /// Record everything a finished game changes — head-to-head records,
+    /// ratings, category stats, turn times and personal bests — then
+    /// archive it, saving each file once. `identities` are the players'
+    /// client identities with their final scores.
+    pub fn record_finished_game(
+        &mut self,
+        game: ArchivedGame,
+        winner: Option<&str>,
+        identities: &[(Uuid, u16)],
+    ) -> RecordedGame {
+        self.record_head_to_head(identities);
+        let ratings = self.rate(&game.summary.players, winner);
+        self.record_category_stats(&game);
+        self.record_turn_times(&game);
+        let records = self.record_personal_bests(&game);
+        self.save_ratings();
+        self.archive_game(game);
+        RecordedGame { ratings, records }
+    }
+
+    fn rate(&mut self, results: &[(String, u16)], winner: Option<&str>) -> Vec<(String, i32, i32)> {
+        let before: Vec<(f64, u16)> = results
+            .iter()
+            .map(|(name, score)| {
+                let rating = self.data.ratings.get(name).map(|r| r.rating).unwrap_or(INITIAL_RATING);
+                (rating, *score)
+            })
+            .collect();
+        let changes = rating::rating_changes(&before);
+
+        let mut summary = Vec::new();
+        for ((name, _), delta) in results.iter().zip(changes) {
+            let record = self.data.ratings.entry(name.clone()).or_default();
+            record.rating += delta;
+            record.games_played += 1;
+            if Some(name.as_str()) == winner {
+                record.wins += 1;
+            }
+            summary.push((name.clone(), record.rating.round() as i32, delta.round() as i32));
+        }
+        summary
+    }
+
+    /// Update head-to-head records from a finished game's final scores,
+    /// keyed by client identity. Tied pairs are not counted.
+    fn record_head_to_head(&mut self, results: &[(Uuid, u16)]) {
+        for (i, &(a, a_score)) in results.iter().enumerate() {
+            for &(b, b_score) in &results[i + 1..] {
+                let (winner, loser) = match a_score.cmp(&b_score) {
+                    std::cmp::Ordering::Greater => (a, b),
+                    std::cmp::Ordering::Less => (b, a),
+                    std::cmp::Ordering::Equal => continue,
+                };
+                self.data.head_to_head.entry(winner).or_default().entry(loser).or_default().wins += 1;
+                self.data.head_to_head.entry(loser).or_default().entry(winner).or_default().losses += 1;
+            }
+        }
+    }
+
+    /// `player`'s record against `opponent`, if they have ever finished a game together.
+    pub fn head_to_head(&self, player: Uuid, opponent: Uuid) -> Option<HeadToHead> {
+        self.data.head_to_head.get(&player)?.get(&opponent).copied()
+    }
+
+    /// Add each player's final scorecard of a finished game to their
+    /// per-category tallies.
+    fn record_category_stats(&mut self, game: &ArchivedGame) {
+        for ((name, _), card) in game.summary.players.iter().zip(&game.scorecards) {
+            let tallies = self
+                .data
+                .category_stats
+                .entry(name.clone())
+                .or_insert_with(no_category_stats);
+            for tally in tallies.iter_mut() {
+                if let Some(score) = card.score(tally.category) {
+                    tally.record(score);
+                }
+            }
+        }
+    }
+
+    /// Add the timed turns of a finished game to each player's turn times.
+    fn record_turn_times(&mut self, game: &ArchivedGame) {
+        for turn in &game.turns {
+            let (Some(duration_ms), Some(seat)) = (turn.duration_ms, game.seat(turn)) else {
+                continue;
+            };
+            if let Some((name, _)) = game.summary.players.get(seat) {
+                self.data.turn_times.entry(name.clone()).or_default().record(duration_ms);
+            }
+        }
+    }
+
+    pub fn turn_times(&self, player: &str) -> TurnTimes {
+        self.data.turn_times.get(player).copied().unwrap_or_default()
+    }
+
+    /// `player`'s per-category tallies, all zero if they have none.
+    pub fn category_stats(&self, player: &str) -> Vec<CategoryStats> {
+        match self.data.category_stats.get(player) {
+            Some(tallies) => tallies.clone(),
+            None => no_category_stats(),
+        }
+    }
+
+    /// Update each player's personal bests from a finished game. Returns
+    /// the players who broke a record, with the records broken.
+    fn record_personal_bests(&mut self, game: &ArchivedGame) -> Vec<(String, Vec<Milestone>)> {
+        let mut broken = Vec::new();
+        for ((name, total), card) in game.summary.players.iter().zip(&game.scorecards) {
+            let milestones = self.data.personal_bests.entry(name.clone()).or_default().record(card, *total);
+            if !milestones.is_empty() {
+                broken.push((name.clone(), milestones));
+            }
+        }
+        broken
+    }
+
+    /// Keep a finished game, dropping the oldest past the archive's size.
+    pub fn archive_game(&mut self, game: ArchivedGame) {
+        self.games.push(Arc::new(game));
+        if self.games.len() > MAX_ARCHIVED_GAMES {
+            self.games.remove(0);
+        }
+        self.writer.write(Snapshot::Games(self.games.clone()));
+    }
+
+    /// The latest finished games, newest first, optionally only those
+    /// `player` took part in.
+    pub fn recent_games(&self, player: Option<&str>) -> Vec<GameSummary> {
+        self.games
+            .iter()
+            .rev()
+            .map(|g| &g.summary)
+            .filter(|s| player.is_none_or(|name| s.players.iter().any(|(p, _)| p == name)))
+            .take(RECENT_GAMES)
+            .cloned()
+            .collect()
+    }
+
+    pub fn game(&self, game_id: Uuid) -> Option<&ArchivedGame> {
+        self.games.iter().find(|g| g.summary.game_id == game_id).map(|g| &**g)
+    }
+
+    /// Archive the current standings and soft-reset ratings if the season has run its length.
+    pub fn roll_season_if_due(&mut self, now: i64) -> bool {
+        if self.season_length == 0 || now < self.data.season_started_at + self.season_length {
+            return false;
+        }
+
+        let archive = SeasonArchive {
+            season: self.data.season,
+            started_at: self.data.season_started_at,
+            ended_at: now,
+            standings: self.current_standings(),
+        };
+        self.data.archives.push(archive);
+
+        for record in self.data.ratings.values_mut() {
+            record.rating = rating::soft_reset(record.rating, SEASON_CARRY_OVER);
+            record.games_played = 0;
+            record.wins = 0;
+        }
+        self.data.season += 1;
+        self.data.season_started_at = now;
+
+        tracing::info!("Season {} started", self.data.season);
+        self.save_ratings();
+        true
+    }
+
+    /// Standings for a season (`None` for the current one), or `None` if unknown.
+    pub fn standings(&self, season: Option<u32>) -> Option<SeasonStandings> {
+        match season {
+            None => Some(SeasonStandings {
+                season: self.data.season,
+                ended_at: None,
+                standings: self.current_standings(),
+            }),
+            Some(s) if s == self.data.season => self.standings(None),
+            Some(s) => self
+                .data
+                .archives
+                .iter()
+                .find(|a| a.season == s)
+                .map(|a| SeasonStandings {
+                    season: a.season,
+                    ended_at: Some(a.ended_at),
+                    standings: a.standings.clone(),
+                }),
+        }
+    }
+
+    fn current_standings(&self) -> Vec<LeaderboardEntry> {
+        let mut players: Vec<(&String, &RatingRecord)> = self
+            .data
+            .ratings
+            .iter()
+            .filter(|(_, r)| r.games_played > 0)
+            .collect();
+        players.sort_by(|a, b| b.1.rating.total_cmp(&a.1.rating).then_with(|| a.0.cmp(b.0)));
+
+        players
+            .into_iter()
+            .take(MAX_STANDINGS)
+            .enumerate()
+            .map(|(i, (name, record))| LeaderboardEntry {
+                rank: i as u32 + 1,
+                player_name: name.clone(),
+                rating: record.rating.round() as i32,
+                games_played: record.games_played,
+                wins: record.wins,
+            })
+            .collect()
+    }
+
+    /// Queue the ratings file to be replaced with a copy of the current data.
+    fn save_ratings(&self) {
+        self.writer.write(Snapshot::Ratings(Box::new(self.data.clone())));
+    }
+}
+
+/// What a finished game changed, as returned by `Persistence::record_finished_game`.
+pub struct RecordedGame {
+    /// Each player's new rating and change, in seat order.
+    pub ratings: Vec<(String, i32, i32)>,
+    /// The players who broke a personal record, with the records broken.
+    pub records: Vec<(String, Vec<Milestone>)>,
+}
+
+/// A copy of one data file's contents, queued for the writer thread.
+enum Snapshot {
+    Ratings(Box<PersistedData>),
+    /// The archived games are shared rather than copied.
+    Games(Vec<Arc<ArchivedGame>>),
+}
+
+impl Snapshot {
+    fn file(&self) -> &'static str {
+        match self {
+            Snapshot::Ratings(_) => RATINGS_FILE,
+            Snapshot::Games(_) => GAMES_FILE,
+        }
+    }
+
+    fn to_bytes(&self) -> serde_json::Result<Vec<u8>> {
+        match self {
+            Snapshot::Ratings(data) => serde_json::to_vec(data),
+            Snapshot::Games(games) => serde_json::to_vec(&games.iter().map(|g| &**g).collect::<Vec<_>>()),
+        }
+    }
+}
+
+/// Serializes and writes the data files on a thread of its own, so that
+/// callers holding the persistence lock on an async worker wait on
+/// neither. Of several queued writes to one file only the latest is made.
+struct Writer {
+    tx: Option<mpsc::Sender<Snapshot>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Writer {
+    fn spawn(dir: PathBuf) -> anyhow::Result<Self> {
+        let (tx, rx) = mpsc::channel::<Snapshot>();
+        let thread = std::thread::Builder::new()
+            .name("persistence".into())
+            .spawn(move || {
+                while let Ok(first) = rx.recv() {
+                    let mut pending = vec![first];
+                    for snapshot in rx.try_iter() {
+                        pending.retain(|s: &Snapshot| s.file() != snapshot.file());
+                        pending.push(snapshot);
+                    }
+                    for snapshot in pending {
+                        let file = snapshot.file();
+                        let saved = snapshot
+                            .to_bytes()
+                            .map_err(std::io::Error::from)
+                            .and_then(|bytes| replace_file(&dir, file, &bytes));
+                        if let Err(e) = saved {
+                            tracing::error!("Failed to save {}: {}", file, e);
+                        }
+                    }
+                }
+            })?;
+        Ok(Self {
+            tx: Some(tx),
+            thread: Some(thread),
+        })
+    }
+
+    fn write(&self, snapshot: Snapshot) {
+        if let Some(tx) = &self.tx {
+            let _ = tx.send(snapshot);
+        }
+    }
+}
+
+impl Drop for Writer {
+    /// Finish the queued writes before the data is let go.
+    fn drop(&mut self) {
+        drop(self.tx.take());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Replace `file` in `dir`, by way of a temporary file.
+fn replace_file(dir: &std::path::Path, file: &str, bytes: &[u8]) -> std::io::Result<()> {
+    let tmp = dir.join(format!("{}.tmp", file));
+    std::fs::write(&tmp, bytes)?;
+    std::fs::rename(tmp, dir.join(file))
+}
+
+fn no_category_stats() -> Vec<CategoryStats> {
+    Category::ALL.iter().map(|&c| CategoryStats::new(c)).collect()
+}
@@ -0,0 +1,167 @@
+use std::str::FromStr;
+
+use tokio::time::Instant;
+
+use yaht_common::protocol::ClientMessage;
+
+/// A token bucket's size and refill rate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    /// Messages allowed in a burst.
+    pub burst: u32,
+    /// Tokens regained per second.
+    pub per_second: f64,
+}
+
+impl RateLimit {
+    pub const fn new(burst: u32, per_second: f64) -> Self {
+        Self { burst, per_second }
+    }
+}
+
+/// Parses `BURST/PER_SECOND`, e.g. `20/10`.
+impl FromStr for RateLimit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (burst, rate) = s
+            .split_once('/')
+            .ok_or_else(|| format!("expected BURST/PER_SECOND, got '{}'", s))?;
+        let burst = burst.trim().parse().map_err(|e| format!("bad burst: {}", e))?;
+        let per_second: f64 = rate.trim().parse().map_err(|e| format!("bad rate: {}", e))?;
+        if !(per_second >= 0.0 && per_second.is_finite()) {
+            return Err(format!("bad rate: {}", per_second));
+        }
+        Ok(Self { burst, per_second })
+    }
+}
+
+/// Per-connection limits for each class of message.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimits {
+    /// Starting the game, rolling, holding and scoring.
+    pub gameplay: RateLimit,
+    pub chat: RateLimit,
+    /// Creating, joining and listing rooms, and leaderboard requests.
+    pub lobby: RateLimit,
+}
+
+impl Default for RateLimits {
+    fn default() -> Self {
+        Self {
+            gameplay: RateLimit::new(20, 10.0),
+            chat: RateLimit::new(5, 1.0),
+            lobby: RateLimit::new(10, 2.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageClass {
+    Gameplay,
+    Chat,
+    Lobby,
+}
+
+impl MessageClass {
+    /// The class a message is limited under, or `None` if it is not limited.
+    pub fn of(msg: &ClientMessage) -> Option<Self> {
+        match msg {
+            ClientMessage::StartGame
+            | ClientMessage::RollDice
+            | ClientMessage::HoldDice { .. }
+            | ClientMessage::ScoreCategory { .. }
+            | ClientMessage::AckTurn { .. } => Some(MessageClass::Gameplay),
+            ClientMessage::Chat { .. } | ClientMessage::Whisper { .. } => Some(MessageClass::Chat),
+            ClientMessage::Tracked { action, .. } => Self::of(action),
+            ClientMessage::CreateRoom { .. }
+            | ClientMessage::JoinRoom { .. }
+            | ClientMessage::SpectateRoom { .. }
+            | ClientMessage::LeaveRoom
+            | ClientMessage::UpdateRoomSettings { .. }
+            | ClientMessage::SetReady { .. }
+            | ClientMessage::MoveSeat { .. }
+            | ClientMessage::Kick { .. }
+            | ClientMessage::ListRooms
+            | ClientMessage::GetLeaderboard { .. }
+            | ClientMessage::GetPlayerStats { .. }
+            | ClientMessage::GetRecentGames { .. }
+            | ClientMessage::GetGame { .. }
+            | ClientMessage::GetDiceStats => Some(MessageClass::Lobby),
+            ClientMessage::Hello { .. } | ClientMessage::Ping | ClientMessage::Disconnect => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MessageClass::Gameplay => "game actions",
+            MessageClass::Chat => "chat messages",
+            MessageClass::Lobby => "lobby requests",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    limit: RateLimit,
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            tokens: limit.burst as f64,
+            updated: Instant::now(),
+        }
+    }
+
+    fn try_take(&mut self) -> bool {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.updated).as_secs_f64();
+        self.updated = now;
+        self.tokens = (self.tokens + elapsed * self.limit.per_second).min(self.limit.burst as f64);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// A connection's token buckets, one per message class.
+#[derive(Debug)]
+pub struct RateLimiter {
+    gameplay: TokenBucket,
+    chat: TokenBucket,
+    lobby: TokenBucket,
+}
+
+impl RateLimiter {
+    pub fn new(limits: &RateLimits) -> Self {
+        Self {
+            gameplay: TokenBucket::new(limits.gameplay),
+            chat: TokenBucket::new(limits.chat),
+            lobby: TokenBucket::new(limits.lobby),
+        }
+    }
+
+    /// Take a token for `msg`. Returns the class that ran out, if any.
+    pub fn check(&mut self, msg: &ClientMessage) -> Result<(), MessageClass> {
+        let Some(class) = MessageClass::of(msg) else {
+            return Ok(());
+        };
+        let bucket = match class {
+            MessageClass::Gameplay => &mut self.gameplay,
+            MessageClass::Chat => &mut self.chat,
+            MessageClass::Lobby => &mut self.lobby,
+        };
+        if bucket.try_take() {
+            Ok(())
+        } else {
+            Err(class)
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use dashmap::DashMap;
+use uuid::Uuid;
+
+use crate::connection::ConnectionHandle;
+use crate::room::RoomHandle;
+
+/// Every connected player, keyed by player ID.
+///
+/// Entries only hold immutable per-connection metadata; which room a
+/// connection is in lives in its `Session`. The map is sharded, so
+/// registering or looking up one connection never blocks the others.
+#[derive(Default)]
+pub struct ConnectionRegistry {
+    connections: DashMap<Uuid, ConnectionHandle>,
+}
+
+impl ConnectionRegistry {
+    pub fn register(&self, conn: ConnectionHandle) {
+        self.connections.insert(conn.player_id, conn);
+    }
+
+    pub fn unregister(&self, player_id: &Uuid) {
+        self.connections.remove(player_id);
+    }
+
+    pub fn len(&self) -> usize {
+        self.connections.len()
+    }
+
+    /// Connections that sent `identity` in their `Hello`.
+    pub fn with_identity(&self, identity: Uuid) -> Vec<ConnectionHandle> {
+        self.connections
+            .iter()
+            .filter(|entry| entry.identity == Some(identity))
+            .map(|entry| entry.value().clone())
+            .collect()
+    }
+}
+
+/// A player whose connection dropped mid-game, holding on to their seat
+/// until they resume or the room gives up on them.
+pub struct ParkedSession {
+    pub player_id: Uuid,
+    pub player_name: String,
+    pub identity: Option<Uuid>,
+    pub room: RoomHandle,
+}
+
+/// Parked sessions, keyed by the resume token sent in `Welcome`.
+#[derive(Default)]
+pub struct ResumeTokens {
+    sessions: DashMap<Uuid, ParkedSession>,
+}
+
+impl ResumeTokens {
+    pub fn park(&self, token: Uuid, session: ParkedSession) {
+        self.sessions.insert(token, session);
+    }
+
+    /// Claim a parked session. Each token resumes at most once.
+    pub fn take(&self, token: &Uuid) -> Option<ParkedSession> {
+        self.sessions.remove(token).map(|(_, session)| session)
+    }
+
+    /// Drop a player's parked session once their seat is gone.
+    pub fn forget(&self, player_id: Uuid) {
+        self.sessions.retain(|_, session| session.player_id != player_id);
+    }
+}
@@ -1,145 +1,2099 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use tokio::sync::{mpsc, oneshot, watch};
+use tracing::Instrument;
 use uuid::Uuid;
 
-use yaht_common::game::{GameError, GameState};
+use yaht_common::ai::{self, AiDifficulty};
+use yaht_common::dice::{DiceSet, FaceCounts, MAX_ROLLS};
+use yaht_common::game::{
+    GameConfig, GameError, GamePhase, GameState, OutOfTime, Pace, SuddenDeath, STANDARD_ROUNDS,
+};
+use yaht_common::history::History;
 use yaht_common::lobby::{RoomInfo, RoomInfoState};
-use yaht_common::player::Player;
-use yaht_common::protocol::{PlayerInfo, RoomSnapshot, RoomState};
+use yaht_common::player::{Player, Scorecard};
+use yaht_common::scoring::Category;
+use yaht_common::protocol::{
+    serialize_game_state, Announcements, ArchivedGame, ClientMessage, ErrorCode, FrameEncoder, GameSummary,
+    Milestone, PlayerInfo, RoomSnapshot, RoomState, SeriesStanding, ServerMessage,
+};
 
 use crate::connection::ConnectionHandle;
+use crate::events::{GameEvent, RoomView, SeatView, StandingView};
+use crate::outbox::Outbox;
+use crate::password::RoomPassword;
+use crate::persistence::Persistence;
+use crate::server::SharedState;
+
+/// Queued commands per room before senders wait.
+const ROOM_CHANNEL_SIZE: usize = 64;
+/// How often a timed turn's clock is resent, so clients stay in step.
+const TURN_TIMER_SYNC: Duration = Duration::from_secs(5);
+/// How often held seats are checked for players who never came back.
+const AWAY_CHECK: Duration = Duration::from_secs(1);
+/// How often a timed turn is checked for running past its deadline.
+const TURN_DEADLINE_CHECK: Duration = Duration::from_secs(1);
+/// How often a running time bank is checked for running out.
+const TIME_BANK_CHECK: Duration = Duration::from_secs(1);
+/// How often an idle player is checked for being due a nudge.
+const NUDGE_CHECK: Duration = Duration::from_secs(1);
+/// How often a finished room is checked for the end of its cooldown.
+const COOLDOWN_CHECK: Duration = Duration::from_secs(1);
+/// Seconds between a room filling up and its game starting on its own.
+const AUTO_START_SECS: u32 = 5;
+/// Spectators a room lets in until its host says otherwise.
+const DEFAULT_MAX_SPECTATORS: u8 = 16;
+
+/// Commands handled by a room's task.
+pub enum RoomCommand {
+    /// Join as a player, or as a spectator if the game has started and
+    /// `spectate_if_started` is set. Replies how the player was admitted,
+    /// if at all; errors are sent to the player directly.
+    Join {
+        conn: ConnectionHandle,
+        /// The room password the player gave, once checked against the
+        /// room's hash.
+        password: Option<RoomPassword>,
+        spectate_if_started: bool,
+        reply: oneshot::Sender<Option<Admission>>,
+    },
+    Spectate {
+        conn: ConnectionHandle,
+        catch_up: bool,
+        reply: oneshot::Sender<Option<Admission>>,
+    },
+    Leave {
+        player_id: Uuid,
+    },
+    /// The player's connection dropped. Replies whether their seat is held
+    /// for them to resume; otherwise they have left.
+    Disconnect {
+        player_id: Uuid,
+        reply: oneshot::Sender<bool>,
+    },
+    /// A player whose seat is held is back on a new connection. Replies
+    /// whether the seat was still theirs.
+    Resume {
+        conn: ConnectionHandle,
+        reply: oneshot::Sender<bool>,
+    },
+    /// A game or chat message from a member, acknowledged if it carries a
+    /// request id.
+    Action {
+        player_id: Uuid,
+        msg: ClientMessage,
+        request_id: Option<u64>,
+        /// The new password an `UpdateRoomSettings` sets, already hashed;
+        /// the message's own copy is cleared.
+        password: Option<Option<RoomPassword>>,
+    },
+}
+
+/// How a connection was let into a room.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Admission {
+    Player,
+    Spectator,
+}
+
+impl RoomCommand {
+    /// Short name for logging.
+    pub fn name(&self) -> &'static str {
+        match self {
+            RoomCommand::Join { .. } => "Join",
+            RoomCommand::Spectate { .. } => "Spectate",
+            RoomCommand::Leave { .. } => "Leave",
+            RoomCommand::Disconnect { .. } => "Disconnect",
+            RoomCommand::Resume { .. } => "Resume",
+            RoomCommand::Action { msg, .. } => msg.name(),
+        }
+    }
+}
+
+/// Handle to a running room task, kept in the lobby directory and by
+/// each member's connection.
+#[derive(Clone)]
+pub struct RoomHandle {
+    pub id: Uuid,
+    tx: mpsc::Sender<RoomCommand>,
+    info: watch::Receiver<RoomInfo>,
+    password: watch::Receiver<Option<RoomPassword>>,
+}
+
+impl RoomHandle {
+    /// Latest room summary published by the room task.
+    pub fn info(&self) -> RoomInfo {
+        self.info.borrow().clone()
+    }
+
+    /// The room's current password hash, to check a joining player's
+    /// password against before they ask to join.
+    pub fn password(&self) -> Option<RoomPassword> {
+        self.password.borrow().clone()
+    }
+
+    /// Queue a command. Returns false if the room has closed.
+    pub async fn send(&self, cmd: RoomCommand) -> bool {
+        self.tx.send(cmd).await.is_ok()
+    }
+}
 
 pub struct Room {
     pub id: Uuid,
     pub name: String,
     pub max_players: u8,
     pub host_id: Uuid,
-    pub player_ids: Vec<Uuid>,
-    pub spectator_ids: Vec<Uuid>,
+    pub players: Vec<ConnectionHandle>,
+    pub spectators: Vec<ConnectionHandle>,
     pub game: Option<GameState>,
-    pub password: Option<String>,
+    pub password: Option<RoomPassword>,
+    pub rules: GameConfig,
+    /// Whether the host has closed the room to new players.
+    pub locked: bool,
+    /// Whether the game starts on its own once every seat is taken.
+    pub auto_start: bool,
+    /// Spectators let in at once; 0 closes the room to them.
+    pub max_spectators: u8,
+    /// Unix time a full room's game starts, and the seconds left last
+    /// announced.
+    countdown: Option<(i64, u32)>,
+    /// Recent game events, replayed to catching-up spectators. The game
+    /// state sent after the replay is authoritative, so evicting old events
+    /// only shortens the replay.
+    pub event_log: History<ServerMessage>,
+    /// Dice for this room's games, forked from the server's seeded entropy.
+    rng: StdRng,
+    /// The seed the host chose for the dice, if any. Games on a known seed
+    /// are not rated.
+    seed: Option<u64>,
+    /// Seated players ready to start, while the room waits.
+    ready: HashSet<Uuid>,
+    /// Which milestones are announced to the room during a game.
+    announcements: Announcements,
+    /// Draw the turn order at random when a game starts.
+    shuffle_order: bool,
+    /// Seconds the current player may go without rolling or scoring
+    /// before they are nudged; 0 never nudges.
+    nudge_secs: u32,
+    /// Unix time the current player is nudged unless they act first.
+    nudge_at: Option<i64>,
+    /// The round in play when the last turn ended, to tell when one is over.
+    round: u8,
+    /// Unix time the room stops being listed as finished after a game.
+    /// Until then it stays open even with no one in it.
+    finished_until: Option<i64>,
+    /// Wins and points over the games finished here, shown between games.
+    standings: Vec<SeriesStanding>,
+    /// Faces rolled in the current or last game, and since the room opened.
+    pub game_dice: FaceCounts,
+    pub room_dice: FaceCounts,
+    /// Unix time the current or last game started.
+    pub game_started_at: i64,
+    /// Unix time in milliseconds the current turn started.
+    turn_started_ms: i64,
+    /// Unix time in milliseconds of the current player's last action, or
+    /// of the start of their turn.
+    last_action_ms: i64,
+    /// Players whose connection dropped mid-game, and the Unix time their
+    /// held seat is given up.
+    pub away: HashMap<Uuid, i64>,
+    /// A host whose seat is held while they are away, and whether they get
+    /// the room back on return. The stand-in host kicking someone, or the
+    /// game ending, means they don't.
+    returning_host: Option<(Uuid, bool)>,
+    /// Highest request id applied for each member, to skip resent actions.
+    last_request: HashMap<Uuid, u64>,
+}
+
+/// An action as the play monitor sees it.
+struct Observation {
+    name: String,
+    /// Time since the player's previous action or the start of their turn.
+    think: Duration,
+    /// For a score, whether the hard AI would have chosen the same category.
+    agreed: Option<bool>,
+}
+
+/// The settings an `UpdateRoomSettings` changes; `None` keeps the current one.
+struct SettingsChange {
+    max_players: Option<u8>,
+    password: Option<Option<RoomPassword>>,
+    rules: Option<GameConfig>,
+    locked: Option<bool>,
+    auto_start: Option<bool>,
+    max_spectators: Option<u8>,
+    seed: Option<u64>,
+    announcements: Option<Announcements>,
+    shuffle_order: Option<bool>,
+    nudge_secs: Option<u32>,
+}
+
+fn clamp_rules(rules: GameConfig) -> GameConfig {
+    GameConfig {
+        rounds: rules.rounds.clamp(1, STANDARD_ROUNDS),
+        rolls_per_turn: rules.rolls_per_turn.clamp(1, MAX_ROLLS),
+        scoring: rules.scoring.clamped(),
+        time_bank_secs: rules.time_bank_secs.map(|secs| secs.clamp(30, 3600)),
+        pace: Pace::of(&rules),
+        ..rules
+    }
+}
+
+/// Start a room task. The host is sent `RoomJoined` once it is running.
+pub fn spawn(room: Room, state: SharedState) -> RoomHandle {
+    let (tx, rx) = mpsc::channel(ROOM_CHANNEL_SIZE);
+    let (info_tx, info_rx) = watch::channel(room.info());
+    let (password_tx, password_rx) = watch::channel(room.password.clone());
+    let id = room.id;
+    tokio::spawn(room.run(rx, info_tx, password_tx, state));
+    RoomHandle {
+        id,
+        tx,
+        info: info_rx,
+        password: password_rx,
+    }
 }
 
 impl Room {
-    pub fn new(id: Uuid, name: String, max_players: u8, host_id: Uuid, password: Option<String>) -> Self {
+    pub fn new(
+        id: Uuid,
+        name: String,
+        max_players: u8,
+        host: ConnectionHandle,
+        password: Option<RoomPassword>,
+        replay_history: usize,
+        rng: StdRng,
+    ) -> Self {
         Self {
             id,
             name,
             max_players: max_players.clamp(2, 6),
-            host_id,
-            player_ids: vec![host_id],
-            spectator_ids: Vec::new(),
+            host_id: host.player_id,
+            players: vec![host],
+            spectators: Vec::new(),
             game: None,
             password,
+            rules: GameConfig::default(),
+            locked: false,
+            auto_start: false,
+            max_spectators: DEFAULT_MAX_SPECTATORS,
+            countdown: None,
+            event_log: History::new(replay_history),
+            rng,
+            seed: None,
+            ready: HashSet::new(),
+            announcements: Announcements::default(),
+            shuffle_order: false,
+            nudge_secs: 0,
+            nudge_at: None,
+            round: 0,
+            finished_until: None,
+            game_dice: FaceCounts::default(),
+            room_dice: FaceCounts::default(),
+            game_started_at: 0,
+            turn_started_ms: 0,
+            last_action_ms: 0,
+            away: HashMap::new(),
+            returning_host: None,
+            standings: Vec::new(),
+            last_request: HashMap::new(),
+        }
+    }
+
+    /// Play this room's games under `rules`. Rolls per turn may be lowered
+    /// but not raised.
+    pub fn with_rules(mut self, rules: GameConfig) -> Self {
+        self.rules = clamp_rules(rules);
+        self
+    }
+
+    async fn run(
+        mut self,
+        mut rx: mpsc::Receiver<RoomCommand>,
+        info_tx: watch::Sender<RoomInfo>,
+        password_tx: watch::Sender<Option<RoomPassword>>,
+        state: SharedState,
+    ) {
+        let mut encoder = FrameEncoder::with_max_frame_length(state.max_frame_length);
+        state.events.publish(
+            state.clock.now(),
+            GameEvent::RoomCreated {
+                room_id: self.id,
+                max_players: self.max_players,
+            },
+        );
+        state.events.set_room_dice(self.id, self.room_dice);
+        state.events.set_room_view(self.id, self.view());
+        let mut outbox = Outbox::default();
+        outbox.send(
+            self.host_id,
+            ServerMessage::RoomJoined {
+                room_id: self.id,
+                room_state: self.snapshot(self.host_id, None),
+            },
+        );
+        outbox.deliver(&self, &mut encoder).await;
+
+        let span = tracing::info_span!("room", room = %self.id, room_name = %self.name);
+        let mut timer_sync = tokio::time::interval(TURN_TIMER_SYNC);
+        timer_sync.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let mut away_check = tokio::time::interval(AWAY_CHECK);
+        away_check.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let mut countdown_tick = tokio::time::interval(Duration::from_secs(1));
+        countdown_tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let mut deadline_check = tokio::time::interval(TURN_DEADLINE_CHECK);
+        deadline_check.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let mut time_bank_check = tokio::time::interval(TIME_BANK_CHECK);
+        time_bank_check.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let mut nudge_check = tokio::time::interval(NUDGE_CHECK);
+        nudge_check.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let mut cooldown_check = tokio::time::interval(COOLDOWN_CHECK);
+        cooldown_check.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            let cmd = tokio::select! {
+                cmd = rx.recv() => match cmd {
+                    Some(cmd) => cmd,
+                    None => break,
+                },
+                _ = timer_sync.tick(), if !self.clocks(state.clock.now()).is_empty() => {
+                    let mut outbox = Outbox::default();
+                    for msg in self.clocks(state.clock.now()) {
+                        outbox.broadcast(msg);
+                    }
+                    outbox.deliver(&self, &mut encoder).instrument(span.clone()).await;
+                    continue;
+                }
+                _ = deadline_check.tick(), if self.game.as_ref().is_some_and(|g| g.turn_deadline.is_some()) => {
+                    let mut outbox = Outbox::default();
+                    span.in_scope(|| {
+                        self.check_turn_deadline(&state, &mut outbox);
+                        self.expire_away(&state, &mut outbox);
+                    });
+                    info_tx.send_replace(self.info());
+                    outbox.deliver(&self, &mut encoder).instrument(span.clone()).await;
+                    continue;
+                }
+                _ = time_bank_check.tick(), if self.time_bank(state.clock.now()).is_some() => {
+                    let mut outbox = Outbox::default();
+                    span.in_scope(|| {
+                        self.check_time_bank(&state, &mut outbox);
+                        self.expire_away(&state, &mut outbox);
+                    });
+                    info_tx.send_replace(self.info());
+                    outbox.deliver(&self, &mut encoder).instrument(span.clone()).await;
+                    continue;
+                }
+                _ = away_check.tick(), if !self.away.is_empty() => {
+                    let mut outbox = Outbox::default();
+                    span.in_scope(|| self.expire_away(&state, &mut outbox));
+                    info_tx.send_replace(self.info());
+                    outbox.deliver(&self, &mut encoder).instrument(span.clone()).await;
+                    if self.can_close(state.clock.now()) {
+                        break;
+                    }
+                    continue;
+                }
+                _ = cooldown_check.tick(), if self.finished_until.is_some() => {
+                    let now = state.clock.now();
+                    if self.finished_until.is_some_and(|until| now >= until) {
+                        self.finished_until = None;
+                        info_tx.send_replace(self.info());
+                        if self.is_empty() {
+                            tracing::debug!("Finished room cooled down");
+                            break;
+                        }
+                    }
+                    continue;
+                }
+                _ = nudge_check.tick(), if self.nudge_at.is_some() => {
+                    let mut outbox = Outbox::default();
+                    span.in_scope(|| self.check_nudge(&state, &mut outbox));
+                    outbox.deliver(&self, &mut encoder).instrument(span.clone()).await;
+                    continue;
+                }
+                _ = countdown_tick.tick(), if self.countdown.is_some() => {
+                    let mut outbox = Outbox::default();
+                    span.in_scope(|| self.tick_countdown(&state, &mut outbox));
+                    info_tx.send_replace(self.info());
+                    outbox.deliver(&self, &mut encoder).instrument(span.clone()).await;
+                    continue;
+                }
+            };
+            let started = Instant::now();
+            let kind = cmd.name();
+            let mut outbox = Outbox::default();
+            span.in_scope(|| match cmd {
+                RoomCommand::Join {
+                    conn,
+                    password,
+                    spectate_if_started,
+                    reply,
+                } => {
+                    let admission = if self.in_game() && spectate_if_started {
+                        self.spectate(conn, false, &mut outbox)
+                            .then_some(Admission::Spectator)
+                    } else {
+                        self.join(conn, password, &state, &mut outbox)
+                            .then_some(Admission::Player)
+                    };
+                    let _ = reply.send(admission);
+                }
+                RoomCommand::Spectate {
+                    conn,
+                    catch_up,
+                    reply,
+                } => {
+                    let admitted = self.spectate(conn, catch_up, &mut outbox);
+                    let _ = reply.send(admitted.then_some(Admission::Spectator));
+                }
+                RoomCommand::Leave { player_id } => self.leave(player_id, &state, &mut outbox),
+                RoomCommand::Disconnect { player_id, reply } => {
+                    let held = self.disconnect(player_id, &state, &mut outbox);
+                    let _ = reply.send(held);
+                }
+                RoomCommand::Resume { conn, reply } => {
+                    let resumed = self.resume(conn, &state, &mut outbox);
+                    let _ = reply.send(resumed);
+                }
+                RoomCommand::Action {
+                    player_id,
+                    msg,
+                    request_id,
+                    password,
+                } => {
+                    if request_id.is_none_or(|id| self.note_request(player_id, id)) {
+                        self.handle_action(player_id, msg, password, &state, &mut outbox);
+                    } else {
+                        tracing::debug!(player_id = %player_id, request_id, "Skipped resent action");
+                    }
+                    if let Some(request_id) = request_id {
+                        outbox.send(player_id, ServerMessage::ActionAck { request_id });
+                    }
+                }
+            });
+            // A game that just ended no longer needs the seats it was holding
+            span.in_scope(|| self.expire_away(&state, &mut outbox));
+            span.in_scope(|| self.check_auto_start(&state, &mut outbox));
+
+            info_tx.send_replace(self.info());
+            password_tx.send_if_modified(|password| {
+                let changed = *password != self.password;
+                if changed {
+                    password.clone_from(&self.password);
+                }
+                changed
+            });
+            state.events.set_room_view(self.id, self.view());
+            outbox.deliver(&self, &mut encoder).instrument(span.clone()).await;
+            // Commands that start a turn send its timer themselves
+            timer_sync.reset();
+
+            let elapsed = started.elapsed();
+            if elapsed >= state.slow_handler {
+                span.in_scope(|| {
+                    tracing::warn!(
+                        kind,
+                        elapsed_ms = elapsed.as_millis() as u64,
+                        "Slow room command"
+                    )
+                });
+            }
+            if self.can_close(state.clock.now()) {
+                break;
+            }
         }
+
+        // Commands still queued are dropped; their senders see the room as gone.
+        state.lobby.write().await.remove_room(&self.id);
+        state.events.remove_room_dice(self.id);
+        state.events.set_room_view(self.id, None);
+        state
+            .events
+            .publish(state.clock.now(), GameEvent::RoomClosed { room_id: self.id });
+        tracing::debug!("Room '{}' closed", self.name);
     }
 
-    pub fn check_password(&self, provided: &Option<String>) -> bool {
+    /// Whether `provided`, a password already checked against this room's
+    /// hash, is still the room's password.
+    pub fn check_password(&self, provided: &Option<RoomPassword>) -> bool {
         match &self.password {
             None => true, // No password set, anyone can join
-            Some(pass) => provided.as_ref().map(|p| p == pass).unwrap_or(false),
+            Some(pass) => provided.as_ref() == Some(pass),
         }
     }
 
-    pub fn add_player(&mut self, player_id: Uuid) -> Result<(), GameError> {
-        if self.player_ids.len() as u8 >= self.max_players {
+    pub fn add_player(&mut self, conn: ConnectionHandle) -> Result<(), GameError> {
+        if self.players.len() as u8 >= self.max_players {
             return Err(GameError::TooManyPlayers);
         }
-        if self.game.is_some() {
+        if self.in_game() {
             return Err(GameError::GameNotInProgress);
         }
-        if !self.player_ids.contains(&player_id) {
-            self.player_ids.push(player_id);
+        if !self.players.iter().any(|p| p.player_id == conn.player_id) {
+            self.players.push(conn);
         }
         Ok(())
     }
 
-    pub fn add_spectator(&mut self, spectator_id: Uuid) {
-        if !self.spectator_ids.contains(&spectator_id) {
-            self.spectator_ids.push(spectator_id);
+    pub fn add_spectator(&mut self, conn: ConnectionHandle) {
+        if !self.spectators.iter().any(|s| s.player_id == conn.player_id) {
+            self.spectators.push(conn);
         }
     }
 
-    pub fn remove_player(&mut self, player_id: &Uuid) {
-        self.player_ids.retain(|id| id != player_id);
-        self.spectator_ids.retain(|id| id != player_id);
+    /// Remove a player or spectator, returning their handle and whether
+    /// they were spectating.
+    pub fn remove_member(&mut self, player_id: Uuid) -> Option<(ConnectionHandle, bool)> {
+        if let Some(idx) = self.spectators.iter().position(|s| s.player_id == player_id) {
+            return Some((self.spectators.remove(idx), true));
+        }
+        let idx = self.players.iter().position(|p| p.player_id == player_id)?;
+        let conn = self.players.remove(idx);
+        self.ready.remove(&player_id);
 
-        // If the host left, assign a new host
-        if &self.host_id == player_id {
-            if let Some(&new_host) = self.player_ids.first() {
+        // If the host left, assign a new host, preferring one who is here
+        if self.host_id == player_id {
+            if let Some(new_host) = self.stand_in_host() {
                 self.host_id = new_host;
             }
         }
+        if self.returning_host.is_some_and(|(id, _)| id == player_id) {
+            self.returning_host = None;
+        }
+        Some((conn, false))
+    }
+
+    /// The first seated player who is connected, or failing that the first
+    /// seated player.
+    fn stand_in_host(&self) -> Option<Uuid> {
+        self.players
+            .iter()
+            .find(|c| c.player_id != self.host_id && !self.away.contains_key(&c.player_id))
+            .or_else(|| self.players.iter().find(|c| c.player_id != self.host_id))
+            .map(|c| c.player_id)
     }
 
     pub fn is_empty(&self) -> bool {
-        self.player_ids.is_empty() && self.spectator_ids.is_empty()
+        self.players.is_empty() && self.spectators.is_empty()
+    }
+
+    /// Whether a game is being played; a finished one doesn't count.
+    pub fn in_game(&self) -> bool {
+        self.game.as_ref().is_some_and(|g| g.phase != GamePhase::Finished)
+    }
+
+    /// Whether the room has no one left and nothing left to wait for.
+    fn can_close(&self, now: i64) -> bool {
+        self.is_empty() && self.finished_until.is_none_or(|until| now >= until)
     }
 
     pub fn info(&self) -> RoomInfo {
         RoomInfo {
             room_id: self.id,
             room_name: self.name.clone(),
-            player_count: self.player_ids.len() as u8,
+            player_count: self.players.len() as u8,
             max_players: self.max_players,
-            spectator_count: self.spectator_ids.len() as u8,
-            state: if self.game.is_some() {
+            spectator_count: self.spectators.len() as u8,
+            state: if self.in_game() {
                 RoomInfoState::InProgress
+            } else if self.finished_until.is_some() {
+                RoomInfoState::Finished
             } else {
                 RoomInfoState::Waiting
             },
             has_password: self.password.is_some(),
+            locked: self.locked,
+            max_spectators: Some(self.max_spectators),
+            pace: self.rules.pace,
+        }
+    }
+
+    /// The room's scoreboard for the web, if it can be watched.
+    pub fn view(&self) -> Option<RoomView> {
+        if self.max_spectators == 0 {
+            return None;
+        }
+        let seat = |name: &str, id: Uuid, card: Option<&Scorecard>, forfeited| SeatView {
+            name: name.to_string(),
+            connected: !self.away.contains_key(&id),
+            forfeited,
+            total: card.map_or(0, Scorecard::grand_total),
+            scorecard: card.cloned().unwrap_or_default(),
+        };
+        let players = match &self.game {
+            Some(game) => game
+                .players
+                .iter()
+                .map(|p| seat(&p.name, p.id, Some(&p.scorecard), p.forfeited))
+                .collect(),
+            None => self
+                .players
+                .iter()
+                .map(|c| seat(&c.player_name, c.player_id, None, false))
+                .collect(),
+        };
+        let standings = self
+            .standings
+            .iter()
+            .map(|s| StandingView {
+                name: s.name.clone(),
+                games: s.games,
+                wins: s.wins,
+                total: s.total,
+            })
+            .collect();
+        Some(RoomView {
+            room_id: self.id,
+            room_name: self.name.clone(),
+            state: self.state(),
+            max_players: self.max_players,
+            spectators: self.spectators.len() as u8,
+            round: self.game.as_ref().map(|g| g.round.min(g.total_rounds)),
+            total_rounds: self.game.as_ref().map(|g| g.total_rounds),
+            current_seat: self
+                .game
+                .as_ref()
+                .filter(|g| g.phase == GamePhase::Playing)
+                .map(|g| g.current_player_index as u8),
+            players,
+            standings,
+        })
+    }
+
+    fn state(&self) -> RoomState {
+        match &self.game {
+            Some(game) if game.phase == GamePhase::Finished => RoomState::Finished,
+            Some(_) => RoomState::InGame,
+            None => RoomState::WaitingForPlayers,
         }
     }
 
-    pub fn snapshot(&self, connections: &HashMap<Uuid, ConnectionHandle>) -> RoomSnapshot {
+    /// Room snapshot as seen by `viewer_id`, including their head-to-head
+    /// records when persistence is available.
+    pub fn snapshot(&self, viewer_id: Uuid, persistence: Option<&Persistence>) -> RoomSnapshot {
+        let viewer_identity = self
+            .member(viewer_id)
+            .and_then(|c| c.identity);
+
         let players = self
-            .player_ids
+            .players
             .iter()
-            .filter_map(|id| {
-                connections.get(id).map(|c| PlayerInfo {
-                    id: c.player_id,
-                    name: c.player_name.clone(),
-                    connected: true,
-                })
+            .map(|c| PlayerInfo {
+                id: c.player_id,
+                name: c.player_name.clone(),
+                connected: !self.away.contains_key(&c.player_id),
+                ready: self.ready.contains(&c.player_id),
+                head_to_head: match (persistence, viewer_identity, c.identity) {
+                    (Some(p), Some(viewer), Some(opponent)) if c.player_id != viewer_id => {
+                        p.head_to_head(viewer, opponent)
+                    }
+                    _ => None,
+                },
             })
             .collect();
 
         let spectators = self
-            .spectator_ids
+            .spectators
             .iter()
-            .filter_map(|id| connections.get(id).map(|c| c.player_name.clone()))
+            .map(|c| c.player_name.clone())
             .collect();
 
-        let state = if self.game.is_some() {
-            RoomState::InGame
-        } else {
-            RoomState::WaitingForPlayers
-        };
-
         RoomSnapshot {
             room_id: self.id,
             room_name: self.name.clone(),
             host_id: self.host_id,
             players,
             spectators,
-            state,
+            state: self.state(),
             max_players: self.max_players,
+            rules: self.rules,
+            has_password: self.password.is_some(),
+            locked: self.locked,
+            auto_start: self.auto_start,
+            shuffle_order: self.shuffle_order,
+            nudge_secs: self.nudge_secs,
+            max_spectators: Some(self.max_spectators),
+            seed: self.seed,
+            announcements: self.announcements,
+            standings: self.standings.clone(),
         }
     }
 
     pub fn start_game(&mut self, players: Vec<Player>) -> Result<(), GameError> {
-        let mut game = GameState::new(players);
+        let mut game = GameState::new(players, self.rules);
         game.start()?;
+        self.round = game.round;
         self.game = Some(game);
+        self.event_log.clear();
+        self.game_dice = FaceCounts::default();
         Ok(())
     }
 
-    /// Get all player + spectator IDs for broadcasting.
-    pub fn all_member_ids(&self) -> Vec<Uuid> {
-        self.player_ids
+    /// Count the dice just rolled and publish the room's new totals.
+    fn record_roll(&mut self, dice: &DiceSet, state: &SharedState) {
+        self.game_dice.record_roll(dice);
+        self.room_dice.record_roll(dice);
+        state.events.set_room_dice(self.id, self.room_dice);
+    }
+
+    /// Record a game event for spectators who join later.
+    pub fn log_event(&mut self, msg: &ServerMessage) {
+        self.event_log.push(msg.clone());
+    }
+
+    /// Time left in the current turn, while a turn timer is running.
+    fn turn_timer(&self, now: i64) -> Option<ServerMessage> {
+        let game = self.game.as_ref()?;
+        let deadline = game.turn_deadline.filter(|&d| d > now)?;
+        Some(ServerMessage::TurnTimer {
+            player_id: game.current_player().id,
+            seconds_left: (deadline - now) as u32,
+        })
+    }
+
+    /// Time left on the current player's time bank, while it is running.
+    fn time_bank(&self, now: i64) -> Option<ServerMessage> {
+        let game = self.game.as_ref().filter(|g| g.phase == GamePhase::Playing)?;
+        Some(ServerMessage::TimeBank {
+            player_id: game.current_player().id,
+            seconds_left: game.time_bank_left(now)?,
+        })
+    }
+
+    /// Every clock running for the current turn.
+    fn clocks(&self, now: i64) -> Vec<ServerMessage> {
+        self.turn_timer(now).into_iter().chain(self.time_bank(now)).collect()
+    }
+
+    pub fn member(&self, player_id: Uuid) -> Option<&ConnectionHandle> {
+        self.members().find(|c| c.player_id == player_id)
+    }
+
+    /// All players and spectators, for broadcasting.
+    pub fn members(&self) -> impl Iterator<Item = &ConnectionHandle> {
+        self.players.iter().chain(self.spectators.iter())
+    }
+
+    fn join(
+        &mut self,
+        conn: ConnectionHandle,
+        password: Option<RoomPassword>,
+        state: &SharedState,
+        out: &mut Outbox,
+    ) -> bool {
+        if self.in_game() {
+            out.send_conn(
+                &conn,
+                ServerMessage::Error {
+                    code: ErrorCode::GameAlreadyStarted,
+                    message: "Game already started; you can watch it instead".into(),
+                },
+            );
+            return false;
+        }
+        if self.locked {
+            out.send_conn(
+                &conn,
+                ServerMessage::Error {
+                    code: ErrorCode::RoomLocked,
+                    message: "Room is locked".into(),
+                },
+            );
+            return false;
+        }
+        if !self.check_password(&password) {
+            out.send_conn(
+                &conn,
+                ServerMessage::Error {
+                    code: ErrorCode::WrongPassword,
+                    message: "Wrong room password".into(),
+                },
+            );
+            return false;
+        }
+
+        let player_id = conn.player_id;
+        let player_name = conn.player_name.clone();
+        if self.add_player(conn.clone()).is_err() {
+            out.send_conn(
+                &conn,
+                ServerMessage::Error {
+                    code: ErrorCode::RoomFull,
+                    message: "Room is full".into(),
+                },
+            );
+            return false;
+        }
+        // Someone back in a finished room everyone had left takes it over
+        if self.member(self.host_id).is_none() {
+            self.host_id = player_id;
+        }
+
+        let persistence = state.persistence();
+        out.send(
+            player_id,
+            ServerMessage::RoomJoined {
+                room_id: self.id,
+                room_state: self.snapshot(player_id, persistence.as_deref()),
+            },
+        );
+        out.broadcast_except(
+            ServerMessage::PlayerJoined {
+                player_id,
+                player_name,
+            },
+            player_id,
+        );
+
+        // Everyone else gets their own record against the newcomer
+        if let Some(persistence) = persistence.as_deref() {
+            for other in self.players.iter().filter(|p| p.player_id != player_id) {
+                let room_state = self.snapshot(other.player_id, Some(persistence));
+                out.send(other.player_id, ServerMessage::RoomUpdate { room_state });
+            }
+        }
+        true
+    }
+
+    fn spectate(&mut self, conn: ConnectionHandle, catch_up: bool, out: &mut Outbox) -> bool {
+        if self.spectators.len() >= self.max_spectators as usize {
+            let message = if self.max_spectators == 0 {
+                "This room can't be watched"
+            } else {
+                "No more spectators allowed in this room"
+            };
+            out.send_conn(
+                &conn,
+                ServerMessage::Error {
+                    code: ErrorCode::SpectatorsFull,
+                    message: message.into(),
+                },
+            );
+            return false;
+        }
+
+        let player_id = conn.player_id;
+        let player_name = conn.player_name.clone();
+        self.add_spectator(conn);
+
+        out.send(
+            player_id,
+            ServerMessage::RoomJoined {
+                room_id: self.id,
+                room_state: self.snapshot(player_id, None),
+            },
+        );
+
+        // Also send current game state if in progress
+        if let Some(game) = &self.game {
+            // A replay can outrun the spectator's queue, so it and the
+            // state after it are streamed
+            if catch_up {
+                out.stream(
+                    player_id,
+                    ServerMessage::ReplayStarted {
+                        event_count: self.event_log.len() as u32,
+                    },
+                );
+                for event in &self.event_log {
+                    out.stream(
+                        player_id,
+                        ServerMessage::ReplayEvent {
+                            event: Box::new(event.clone()),
+                        },
+                    );
+                }
+                out.stream(player_id, ServerMessage::ReplayFinished);
+            }
+            match serialize_game_state(game) {
+                Ok(frame) => out.stream_frame(player_id, frame),
+                Err(e) => tracing::error!("Failed to serialize game state: {}", e),
+            }
+        }
+
+        out.broadcast_except(ServerMessage::SpectatorJoined { player_name }, player_id);
+        true
+    }
+
+    /// Hold a dropped player's seat while their game is in progress;
+    /// anyone else has simply left. Returns whether the seat is held.
+    fn disconnect(&mut self, player_id: Uuid, state: &SharedState, out: &mut Outbox) -> bool {
+        let seated = self.players.iter().any(|c| c.player_id == player_id)
+            && self.game.as_ref().is_some_and(|g| {
+                g.phase == GamePhase::Playing && g.players.iter().any(|p| p.id == player_id && !p.forfeited)
+            });
+        if !seated {
+            self.leave(player_id, state, out);
+            return false;
+        }
+
+        let grace = state.resume_grace.as_secs();
+        self.away.insert(player_id, state.clock.now() + grace as i64);
+        let name = self.set_connected(player_id, false);
+        tracing::info!(player_id = %player_id, "Holding seat for disconnected player");
+        let mut message = format!("{} lost connection; holding their seat for {}s", name, grace);
+
+        // Someone who is here looks after the room until the host is back
+        if self.host_id == player_id {
+            if let Some(stand_in) = self.stand_in_host().filter(|id| !self.away.contains_key(id)) {
+                self.host_id = stand_in;
+                // A stand-in who drops in turn hands over again, but the
+                // room still goes back to the host who left first
+                self.returning_host.get_or_insert((player_id, true));
+                let stand_in_name = self.member(stand_in).map(|c| c.player_name.clone()).unwrap_or_default();
+                tracing::info!(host = %stand_in, "Host is away; handing over the room for now");
+                message.push_str(&format!(". {} is the host until they return", stand_in_name));
+            }
+        }
+        out.broadcast_except(
+            ServerMessage::SystemMessage {
+                message,
+                milestone: None,
+            },
+            player_id,
+        );
+        true
+    }
+
+    /// Give a held seat back to its player on their new connection and
+    /// bring them up to date.
+    fn resume(&mut self, conn: ConnectionHandle, state: &SharedState, out: &mut Outbox) -> bool {
+        let player_id = conn.player_id;
+        if self.away.remove(&player_id).is_none() {
+            return false;
+        }
+        let Some(seat) = self.players.iter_mut().find(|c| c.player_id == player_id) else {
+            return false;
+        };
+        *seat = conn;
+        let name = self.set_connected(player_id, true);
+        tracing::info!(player_id = %player_id, "Player resumed their seat");
+
+        if let Some(game) = &self.game {
+            match serialize_game_state(game) {
+                Ok(frame) => out.send_frame(player_id, frame),
+                Err(e) => tracing::error!("Failed to serialize game state: {}", e),
+            }
+        }
+        for clock in self.clocks(state.clock.now()) {
+            out.send(player_id, clock);
+        }
+
+        let mut message = format!("{} reconnected", name);
+        match self.returning_host.take() {
+            Some((host, true)) if host == player_id => {
+                tracing::info!(player_id = %player_id, "Host is back; returning the room");
+                self.host_id = player_id;
+                message.push_str(" and is the host again");
+            }
+            Some((host, false)) if host == player_id => {
+                // The stand-in has acted as host since, so they stay on
+                let stand_in = self.host_id;
+                let stand_in_name = self.member(stand_in).map(|c| c.player_name.clone()).unwrap_or_default();
+                out.send(
+                    player_id,
+                    ServerMessage::SystemMessage {
+                        message: format!("{} has taken over as host", stand_in_name),
+                        milestone: None,
+                    },
+                );
+                out.send(
+                    stand_in,
+                    ServerMessage::SystemMessage {
+                        message: format!("{} is back; you remain the host", name),
+                        milestone: None,
+                    },
+                );
+            }
+            other => self.returning_host = other,
+        }
+        out.broadcast_except(
+            ServerMessage::SystemMessage {
+                message,
+                milestone: None,
+            },
+            player_id,
+        );
+        true
+    }
+
+    /// Mark a seated player's connection in the game, returning their name.
+    fn set_connected(&mut self, player_id: Uuid, connected: bool) -> String {
+        if let Some(player) = self
+            .game
+            .as_mut()
+            .and_then(|g| g.players.iter_mut().find(|p| p.id == player_id))
+        {
+            player.connected = connected;
+        }
+        self.member(player_id)
+            .map(|c| c.player_name.clone())
+            .unwrap_or_default()
+    }
+
+    /// Give up the seats of players who did not come back in time, or
+    /// whose game is over.
+    fn expire_away(&mut self, state: &SharedState, out: &mut Outbox) {
+        if self.away.is_empty() {
+            return;
+        }
+        let now = state.clock.now();
+        let playing = self.game.as_ref().is_some_and(|g| g.phase == GamePhase::Playing);
+        let expired: Vec<Uuid> = self
+            .away
             .iter()
-            .chain(self.spectator_ids.iter())
-            .copied()
-            .collect()
+            .filter(|&(_, &deadline)| !playing || deadline <= now)
+            .map(|(&id, _)| id)
+            .collect();
+        for player_id in expired {
+            self.away.remove(&player_id);
+            state.resume_tokens.forget(player_id);
+            tracing::info!(player_id = %player_id, "Gave up held seat");
+            self.leave(player_id, state, out);
+        }
+    }
+
+    fn leave(&mut self, player_id: Uuid, state: &SharedState, out: &mut Outbox) {
+        let host = self.host_id;
+        let Some((conn, is_spectator)) = self.remove_member(player_id) else {
+            return;
+        };
+
+        if is_spectator {
+            out.broadcast(ServerMessage::SpectatorLeft {
+                player_name: conn.player_name,
+            });
+            return;
+        }
+        out.broadcast(ServerMessage::PlayerLeft {
+            player_id,
+            player_name: conn.player_name.clone(),
+        });
+        if self.host_id != host {
+            self.announce_host(state, out);
+        }
+        self.forfeit(player_id, conn.player_name, state, out);
+    }
+
+    /// Tell the room who took over as host. Between games the snapshots
+    /// carry it to the waiting room screen too.
+    fn announce_host(&mut self, state: &SharedState, out: &mut Outbox) {
+        let Some(name) = self.member(self.host_id).map(|c| c.player_name.clone()) else {
+            return;
+        };
+        tracing::info!(host = %self.host_id, "New host");
+        out.broadcast(ServerMessage::SystemMessage {
+            message: format!("{} is now the host", name),
+            milestone: None,
+        });
+        if self.game.as_ref().is_none_or(|g| g.phase == GamePhase::Finished) {
+            self.send_snapshots(state, out);
+        }
+    }
+
+    /// Take a departed player out of the game in progress, passing on their
+    /// turn or ending the game if too few players remain.
+    fn forfeit(&mut self, player_id: Uuid, player_name: String, state: &SharedState, out: &mut Outbox) {
+        let Some(game) = self.game.as_mut() else {
+            return;
+        };
+        let was_current = game.phase == GamePhase::Playing && game.is_current_player(player_id);
+        if game.forfeit(player_id).is_err() {
+            return;
+        }
+        tracing::info!(player_id = %player_id, "Player forfeited");
+
+        let mut messages = vec![ServerMessage::PlayerForfeited {
+            player_id,
+            player_name,
+        }];
+        if was_current {
+            messages.push(ServerMessage::TurnEnded { player_id });
+        }
+        if was_current || game.phase == GamePhase::Finished {
+            self.end_turn(messages, state, out);
+        } else {
+            for msg in messages {
+                self.log_event(&msg);
+                out.broadcast(msg);
+            }
+        }
+    }
+
+    /// Start the auto-start countdown once the waiting room fills up, and
+    /// stop it if a seat comes free or the host turns auto-start off.
+    fn check_auto_start(&mut self, state: &SharedState, out: &mut Outbox) {
+        let ready = self.auto_start && !self.in_game() && self.players.len() >= self.max_players as usize;
+        match (self.countdown, ready) {
+            (None, true) => {
+                self.countdown = Some((state.clock.now() + AUTO_START_SECS as i64, AUTO_START_SECS));
+                tracing::info!("Room full; auto-start countdown begun");
+                out.broadcast(ServerMessage::AutoStartCountdown {
+                    seconds_left: AUTO_START_SECS,
+                });
+            }
+            (Some(_), false) => {
+                self.countdown = None;
+                out.broadcast(ServerMessage::AutoStartCancelled);
+            }
+            _ => {}
+        }
+    }
+
+    /// Announce each second of the countdown, then start the game.
+    fn tick_countdown(&mut self, state: &SharedState, out: &mut Outbox) {
+        let Some((deadline, announced)) = self.countdown else {
+            return;
+        };
+        let now = state.clock.now();
+        if now >= deadline {
+            self.countdown = None;
+            self.handle_start_game(self.host_id, state, out);
+            return;
+        }
+        let seconds_left = (deadline - now) as u32;
+        if seconds_left != announced {
+            self.countdown = Some((deadline, seconds_left));
+            out.broadcast(ServerMessage::AutoStartCountdown { seconds_left });
+        }
+    }
+
+    /// Play the current turn for its player once the turn timer runs out.
+    fn check_turn_deadline(&mut self, state: &SharedState, out: &mut Outbox) {
+        let Some(game) = self.game.as_ref() else {
+            return;
+        };
+        if game.phase != GamePhase::Playing || game.turn_deadline.is_none_or(|d| state.clock.now() < d) {
+            return;
+        }
+        let player = game.current_player();
+        let (player_id, player_name) = (player.id, player.name.clone());
+        tracing::info!(player_id = %player_id, "Turn timer ran out");
+
+        let msg = ServerMessage::SystemMessage {
+            message: format!("{}'s turn timed out", player_name),
+            milestone: None,
+        };
+        self.log_event(&msg);
+        out.broadcast(msg);
+        self.auto_score(player_id, state, out);
+    }
+
+    /// Apply the rules' penalty once the current player's time bank is
+    /// empty: play the turn for them, or take them out of the game.
+    fn check_time_bank(&mut self, state: &SharedState, out: &mut Outbox) {
+        let Some(game) = self.game.as_ref() else {
+            return;
+        };
+        if game.phase != GamePhase::Playing || game.time_bank_left(state.clock.now()) != Some(0) {
+            return;
+        }
+        let player = game.current_player();
+        let (player_id, player_name) = (player.id, player.name.clone());
+        let penalty = game.rules.out_of_time;
+        tracing::info!(player_id = %player_id, ?penalty, "Time bank ran out");
+
+        let msg = ServerMessage::SystemMessage {
+            message: format!("{} ran out of time", player_name),
+            milestone: None,
+        };
+        self.log_event(&msg);
+        out.broadcast(msg);
+        match penalty {
+            OutOfTime::Forfeit => self.forfeit(player_id, player_name, state, out),
+            OutOfTime::AutoScore => self.auto_score(player_id, state, out),
+        }
+    }
+
+    /// Start the current player's idle clock over, if the room nudges. A
+    /// nudge due when the turn timer has already run out is not set.
+    fn schedule_nudge(&mut self, state: &SharedState) {
+        let now = state.clock.now();
+        self.nudge_at = self
+            .game
+            .as_ref()
+            .filter(|g| g.phase == GamePhase::Playing && self.nudge_secs > 0)
+            .map(|g| (now + self.nudge_secs as i64, g.turn_deadline))
+            .filter(|&(at, deadline)| deadline.is_none_or(|d| at < d))
+            .map(|(at, _)| at);
+    }
+
+    /// Remind a current player who has sat idle too long that the table
+    /// is waiting, and tell everyone else who they are waiting on.
+    fn check_nudge(&mut self, state: &SharedState, out: &mut Outbox) {
+        if self.nudge_at.is_none_or(|at| state.clock.now() < at) {
+            return;
+        }
+        self.nudge_at = None;
+        let Some(game) = self.game.as_ref().filter(|g| g.phase == GamePhase::Playing) else {
+            return;
+        };
+        let player = game.current_player();
+        // Players who dropped have their own notice and a grace period
+        if self.away.contains_key(&player.id) {
+            return;
+        }
+        tracing::debug!(player_id = %player.id, "Nudging an idle player");
+        out.send(
+            player.id,
+            ServerMessage::SystemMessage {
+                message: "It's your turn – the table is waiting on you".into(),
+                milestone: None,
+            },
+        );
+        out.broadcast_except(
+            ServerMessage::SystemMessage {
+                message: format!("Waiting on {}…", player.name),
+                milestone: None,
+            },
+            player.id,
+        );
+    }
+
+    /// Play out the current player's turn: roll if they haven't, then take
+    /// the best score on offer.
+    fn auto_score(&mut self, player_id: Uuid, state: &SharedState, out: &mut Outbox) {
+        let rolled = self
+            .game
+            .as_ref()
+            .and_then(|g| g.turn.as_ref())
+            .is_some_and(|t| t.can_score());
+        if !rolled {
+            // A playoff roll ends the turn by itself
+            self.handle_roll(player_id, state, out);
+        }
+        let Some(game) = self.game.as_ref().filter(|g| g.is_current_player(player_id)) else {
+            return;
+        };
+        let Some(turn) = game.turn.as_ref().filter(|t| t.can_score()) else {
+            return;
+        };
+        let category = ai::choose_category(
+            &turn.dice,
+            &game.current_player().scorecard,
+            AiDifficulty::Medium,
+            &mut self.rng,
+        );
+        self.handle_score(player_id, category, state, out);
+    }
+
+    /// What the play monitor needs to know about an action by the current
+    /// player, taken before the action is applied. `None` when monitoring
+    /// is off or it is not their turn.
+    fn observe(&self, player_id: Uuid, category: Option<Category>, state: &SharedState) -> Option<Observation> {
+        state.monitor.as_ref()?;
+        let game = self.game.as_ref().filter(|g| g.is_current_player(player_id))?;
+        let think = state.clock.now_millis().saturating_sub(self.last_action_ms).max(0);
+        // The hard AI never draws on its rng
+        let agreed = category.zip(game.turn.as_ref()).map(|(category, turn)| {
+            let scorecard = &game.current_player().scorecard;
+            ai::choose_category(&turn.dice, scorecard, AiDifficulty::Hard, &mut StdRng::seed_from_u64(0)) == category
+        });
+        Some(Observation {
+            name: game.current_player().name.clone(),
+            think: Duration::from_millis(think as u64),
+            agreed,
+        })
+    }
+
+    /// Pass an accepted action on to the play monitor.
+    fn report(&mut self, seen: Option<Observation>, state: &SharedState) {
+        let (Some(seen), Some(monitor)) = (seen, state.monitor.as_ref()) else {
+            return;
+        };
+        monitor.record_action(&seen.name, seen.think);
+        if let Some(agreed) = seen.agreed {
+            monitor.record_choice(&seen.name, agreed);
+        } else {
+            // A score starts the next turn, which resets the time itself
+            self.last_action_ms = state.clock.now_millis();
+        }
+    }
+
+    /// Record a tracked action's id, returning false if it was seen before.
+    fn note_request(&mut self, player_id: Uuid, request_id: u64) -> bool {
+        if self.last_request.get(&player_id).is_some_and(|&last| request_id <= last) {
+            return false;
+        }
+        self.last_request.insert(player_id, request_id);
+        true
+    }
+
+    fn handle_action(
+        &mut self,
+        player_id: Uuid,
+        msg: ClientMessage,
+        password: Option<Option<RoomPassword>>,
+        state: &SharedState,
+        out: &mut Outbox,
+    ) {
+        match msg {
+            ClientMessage::StartGame => self.handle_start_game(player_id, state, out),
+            ClientMessage::UpdateRoomSettings {
+                max_players,
+                password: _,
+                rules,
+                locked,
+                auto_start,
+                max_spectators,
+                seed,
+                announcements,
+                shuffle_order,
+                nudge_secs,
+            } => {
+                let change = SettingsChange {
+                    max_players,
+                    password,
+                    rules,
+                    locked,
+                    auto_start,
+                    max_spectators,
+                    seed,
+                    announcements,
+                    shuffle_order,
+                    nudge_secs,
+                };
+                self.handle_update_settings(player_id, change, state, out)
+            }
+            ClientMessage::SetReady { ready } => self.handle_ready(player_id, ready, state, out),
+            ClientMessage::MoveSeat { seat } => self.handle_move_seat(player_id, seat, state, out),
+            ClientMessage::Kick { player_id: target } => self.handle_kick(player_id, target, state, out),
+            ClientMessage::RollDice => {
+                let seen = self.observe(player_id, None, state);
+                if self.handle_roll(player_id, state, out) {
+                    self.report(seen, state);
+                }
+            }
+            ClientMessage::HoldDice { held } => {
+                let seen = self.observe(player_id, None, state);
+                if self.handle_hold(player_id, held, out) {
+                    self.report(seen, state);
+                }
+            }
+            ClientMessage::ScoreCategory { category } => {
+                let seen = self.observe(player_id, Some(category), state);
+                if self.handle_score(player_id, category, state, out) {
+                    self.report(seen, state);
+                }
+            }
+            ClientMessage::Chat { message } => {
+                let Some(sender_name) = self.member(player_id).map(|c| c.player_name.clone())
+                else {
+                    return;
+                };
+                out.broadcast(ServerMessage::ChatMessage {
+                    sender_id: player_id,
+                    sender_name,
+                    message,
+                    timestamp: state.clock.now(),
+                    whisper_to: None,
+                });
+            }
+            ClientMessage::Whisper { to, message } => {
+                let Some(sender_name) = self.member(player_id).map(|c| c.player_name.clone())
+                else {
+                    return;
+                };
+                let Some(recipient) = self.members().find(|c| c.player_name == to).map(|c| c.player_id)
+                else {
+                    out.error(
+                        player_id,
+                        ErrorCode::InvalidAction,
+                        format!("No one named {} is in this room", to),
+                    );
+                    return;
+                };
+                let msg = ServerMessage::ChatMessage {
+                    sender_id: player_id,
+                    sender_name,
+                    message,
+                    timestamp: state.clock.now(),
+                    whisper_to: Some(to),
+                };
+                if recipient != player_id {
+                    out.send(player_id, msg.clone());
+                }
+                out.send(recipient, msg);
+            }
+            ClientMessage::GetDiceStats => out.send(
+                player_id,
+                ServerMessage::DiceStats {
+                    game: self.game_dice,
+                    room: self.room_dice,
+                },
+            ),
+            ClientMessage::AckTurn {
+                round,
+                player_id: turn_player,
+                checksum,
+            } => self.handle_ack_turn(player_id, round, turn_player, checksum, out),
+            _ => {}
+        }
+    }
+
+    /// Compare a member's view of the turn that just started with ours,
+    /// sending them the full state if it has drifted. An ack for a turn
+    /// that has since ended says nothing either way.
+    fn handle_ack_turn(&self, player_id: Uuid, round: u8, turn_player: Uuid, checksum: u64, out: &mut Outbox) {
+        let Some(game) = self.game.as_ref().filter(|g| g.phase == GamePhase::Playing) else {
+            return;
+        };
+        if game.round != round || game.current_player().id != turn_player {
+            return;
+        }
+        let expected = game.checksum();
+        if checksum == expected {
+            return;
+        }
+        tracing::warn!(
+            player_id = %player_id,
+            round,
+            client = checksum,
+            server = expected,
+            "Client out of sync; resending the game state"
+        );
+        match serialize_game_state(game) {
+            Ok(frame) => out.send_frame(player_id, frame),
+            Err(e) => tracing::error!("Failed to serialize game state: {}", e),
+        }
+    }
+
+    fn handle_start_game(&mut self, player_id: Uuid, state: &SharedState, out: &mut Outbox) {
+        // Only host can start
+        if self.host_id != player_id {
+            out.error(player_id, ErrorCode::NotHost, "Only the host can start the game");
+            return;
+        }
+
+        if self.players.len() < 2 {
+            out.error(player_id, ErrorCode::NotEnoughPlayers, "Need at least 2 players");
+            return;
+        }
+
+        let mut players: Vec<Player> = self
+            .players
+            .iter()
+            .map(|c| Player::new(c.player_id, c.player_name.clone()))
+            .collect();
+        if self.shuffle_order {
+            players.shuffle(&mut self.rng);
+        }
+
+        if let Err(e) = self.start_game(players) {
+            out.error(
+                player_id,
+                ErrorCode::InternalError,
+                format!("Failed to start game: {}", e),
+            );
+            return;
+        }
+
+        self.game_started_at = state.clock.now();
+        self.finished_until = None;
+        self.turn_started_ms = state.clock.now_millis();
+        self.last_action_ms = self.turn_started_ms;
+        self.countdown = None;
+        self.ready.clear();
+        let game = self.game.as_mut().unwrap();
+        game.start_turn_timer(state.clock.now());
+        state.events.publish(
+            state.clock.now(),
+            GameEvent::GameStarted {
+                room_id: self.id,
+                players: game.players.len() as u8,
+            },
+        );
+        let msg = ServerMessage::GameStarted {
+            game_state: game.snapshot(),
+        };
+        self.log_event(&msg);
+        out.broadcast(msg);
+        for clock in self.clocks(state.clock.now()) {
+            out.broadcast(clock);
+        }
+        self.schedule_nudge(state);
+    }
+
+    fn handle_update_settings(
+        &mut self,
+        player_id: Uuid,
+        change: SettingsChange,
+        state: &SharedState,
+        out: &mut Outbox,
+    ) {
+        if self.host_id != player_id {
+            out.error(player_id, ErrorCode::NotHost, "Only the host can change room settings");
+            return;
+        }
+        if self.game.as_ref().is_some_and(|g| g.phase != GamePhase::Finished) {
+            out.error(
+                player_id,
+                ErrorCode::GameAlreadyStarted,
+                "Settings can't change while a game is in progress",
+            );
+            return;
+        }
+        let max_players = change.max_players.map_or(self.max_players, |n| n.clamp(2, 6));
+        if (max_players as usize) < self.players.len() {
+            out.error(
+                player_id,
+                ErrorCode::InvalidAction,
+                format!("{} players are already in the room", self.players.len()),
+            );
+            return;
+        }
+
+        self.max_players = max_players;
+        if let Some(password) = change.password {
+            self.password = password;
+        }
+        if let Some(rules) = change.rules {
+            self.rules = clamp_rules(rules);
+        }
+        if let Some(locked) = change.locked {
+            self.locked = locked;
+        }
+        if let Some(auto_start) = change.auto_start {
+            self.auto_start = auto_start;
+        }
+        if let Some(max_spectators) = change.max_spectators {
+            self.max_spectators = max_spectators;
+        }
+        if let Some(seed) = change.seed {
+            self.rng = StdRng::seed_from_u64(seed);
+            self.seed = Some(seed);
+        }
+        if let Some(announcements) = change.announcements {
+            self.announcements = announcements;
+        }
+        if let Some(shuffle_order) = change.shuffle_order {
+            self.shuffle_order = shuffle_order;
+        }
+        if let Some(nudge_secs) = change.nudge_secs {
+            self.nudge_secs = nudge_secs;
+        }
+        tracing::info!(
+            max_players,
+            rules = ?self.rules,
+            locked = self.locked,
+            auto_start = self.auto_start,
+            max_spectators = self.max_spectators,
+            seed = self.seed,
+            announcements = ?self.announcements,
+            shuffle_order = self.shuffle_order,
+            nudge_secs = self.nudge_secs,
+            "Room settings changed"
+        );
+        self.send_snapshots(state, out);
+    }
+
+    /// Mark a seated player ready or not, starting the game once everyone is.
+    fn handle_ready(&mut self, player_id: Uuid, ready: bool, state: &SharedState, out: &mut Outbox) {
+        if !self.players.iter().any(|c| c.player_id == player_id) {
+            return;
+        }
+        if self.game.as_ref().is_some_and(|g| g.phase != GamePhase::Finished) {
+            out.error(player_id, ErrorCode::GameAlreadyStarted, "The game has already started");
+            return;
+        }
+        let changed = if ready {
+            self.ready.insert(player_id)
+        } else {
+            self.ready.remove(&player_id)
+        };
+        if !changed {
+            return;
+        }
+        self.send_snapshots(state, out);
+
+        if self.players.len() >= 2 && self.players.iter().all(|c| self.ready.contains(&c.player_id)) {
+            tracing::info!("Every player is ready; starting the game");
+            self.handle_start_game(self.host_id, state, out);
+        }
+    }
+
+    /// Move a seated player to another seat, which is their place in the
+    /// turn order when the game starts.
+    fn handle_move_seat(&mut self, player_id: Uuid, seat: u8, state: &SharedState, out: &mut Outbox) {
+        let Some(from) = self.players.iter().position(|c| c.player_id == player_id) else {
+            return;
+        };
+        if self.game.as_ref().is_some_and(|g| g.phase != GamePhase::Finished) {
+            out.error(player_id, ErrorCode::GameAlreadyStarted, "The game has already started");
+            return;
+        }
+        let to = seat as usize;
+        if to >= self.players.len() {
+            out.error(player_id, ErrorCode::InvalidAction, format!("There is no seat {}", seat));
+            return;
+        }
+        if to == from {
+            return;
+        }
+        let conn = self.players.remove(from);
+        self.players.insert(to, conn);
+        tracing::debug!(player_id = %player_id, from, to, "Player changed seats");
+        self.send_snapshots(state, out);
+    }
+
+    /// Remove a member on the host's say-so. A seated player in a game in
+    /// progress forfeits, as if they had left.
+    fn handle_kick(&mut self, player_id: Uuid, target: Uuid, state: &SharedState, out: &mut Outbox) {
+        if self.host_id != player_id {
+            out.error(player_id, ErrorCode::NotHost, "Only the host can remove players");
+            return;
+        }
+        if target == player_id {
+            out.error(player_id, ErrorCode::InvalidAction, "Leave the room instead");
+            return;
+        }
+        let Some(conn) = self.member(target).cloned() else {
+            out.error(player_id, ErrorCode::InvalidAction, "No such player in this room");
+            return;
+        };
+        tracing::info!(player_id = %target, "Host removed player");
+        if let Some((_, restorable)) = &mut self.returning_host {
+            *restorable = false;
+        }
+        self.away.remove(&target);
+        self.leave(target, state, out);
+        out.send_conn(
+            &conn,
+            ServerMessage::Error {
+                code: ErrorCode::Kicked,
+                message: "The host removed you from the room".into(),
+            },
+        );
+    }
+
+    /// Send every member the room as they see it.
+    fn send_snapshots(&self, state: &SharedState, out: &mut Outbox) {
+        let persistence = state.persistence();
+        for member in self.members() {
+            let room_state = self.snapshot(member.player_id, persistence.as_deref());
+            out.send(member.player_id, ServerMessage::RoomUpdate { room_state });
+        }
+    }
+
+    /// Returns whether the roll was accepted.
+    fn handle_roll(&mut self, player_id: Uuid, state: &SharedState, out: &mut Outbox) -> bool {
+        let game = match self.game.as_mut() {
+            Some(g) => g,
+            None => return false,
+        };
+
+        let playoff = game.sudden_death.is_some();
+        if let Err(e) = game.roll_dice(player_id, &mut self.rng) {
+            let (code, message) = game_error_to_protocol(&e);
+            out.error(player_id, code, message);
+            return false;
+        }
+
+        // A playoff roll ends the turn on its own
+        if playoff {
+            let Some(roll) = game.sudden_death.as_ref().and_then(|sd| sd.rolls.last()) else {
+                return false;
+            };
+            let mut dice = DiceSet::new();
+            dice.set_unheld(roll.dice);
+            self.record_roll(&dice, state);
+            let messages = vec![
+                ServerMessage::DiceRolled {
+                    dice,
+                    rolls_remaining: 0,
+                },
+                ServerMessage::TurnEnded { player_id },
+            ];
+            self.end_turn(messages, state, out);
+            return true;
+        }
+
+        let turn = game.turn.as_ref().unwrap();
+        let dice = turn.dice;
+        let msg = ServerMessage::DiceRolled {
+            dice,
+            rolls_remaining: turn.rolls_remaining(),
+        };
+        self.record_roll(&dice, state);
+        self.log_event(&msg);
+        out.broadcast(msg);
+        self.schedule_nudge(state);
+        true
+    }
+
+    /// Returns whether the hold was accepted.
+    fn handle_hold(&mut self, player_id: Uuid, held: [bool; 5], out: &mut Outbox) -> bool {
+        let game = match self.game.as_mut() {
+            Some(g) => g,
+            None => return false,
+        };
+
+        // Holds only make sense between rolls; an unchanged hold would just
+        // echo the current dice back to everyone
+        if game.is_current_player(player_id) {
+            match game.turn.as_ref() {
+                Some(turn) if !turn.can_hold() => {
+                    out.error(player_id, ErrorCode::InvalidHold, "Roll before holding dice");
+                    return false;
+                }
+                Some(turn) if turn.dice.dice.map(|d| d.held) == held => {
+                    out.error(player_id, ErrorCode::InvalidHold, "Those dice are already held");
+                    return false;
+                }
+                _ => {}
+            }
+        }
+
+        if let Err(e) = game.hold_dice(player_id, held) {
+            let (code, message) = game_error_to_protocol(&e);
+            out.error(player_id, code, message);
+            return false;
+        }
+
+        let msg = ServerMessage::DiceHeld {
+            dice: game.turn.as_ref().unwrap().dice,
+        };
+        self.log_event(&msg);
+        out.broadcast(msg);
+        true
+    }
+
+    /// Returns whether the category was scored.
+    fn handle_score(
+        &mut self,
+        player_id: Uuid,
+        category: Category,
+        state: &SharedState,
+        out: &mut Outbox,
+    ) -> bool {
+        let game = match self.game.as_mut() {
+            Some(g) => g,
+            None => return false,
+        };
+
+        // Validate before mutation
+        if !game.is_current_player(player_id) {
+            let (code, message) = game_error_to_protocol(&GameError::NotYourTurn);
+            out.error(player_id, code, message);
+            return false;
+        }
+
+        let prev_player_id = game.current_player().id;
+        let (seat, round) = (game.current_player_index as u8, game.round);
+        let leader_before = sole_leader(game);
+        let had_bonus = game.current_player().scorecard.upper_bonus() > 0;
+
+        let score = match game.score_category(player_id, category) {
+            Ok(s) => s,
+            Err(e) => {
+                let (code, message) = game_error_to_protocol(&e);
+                out.error(player_id, code, message);
+                return false;
+            }
+        };
+
+        if let Some(turn) = game.turn_history.last_mut() {
+            let elapsed = state.clock.now_millis().saturating_sub(self.turn_started_ms);
+            turn.duration_ms = Some(elapsed.max(0) as u64);
+            let rolls: Vec<[u8; 5]> = turn.rolls.iter().map(|r| r.dice).collect();
+            tracing::debug!(
+                player_id = %turn.player_id,
+                round = turn.round,
+                category = ?turn.category,
+                score = turn.score,
+                duration_ms = turn.duration_ms,
+                rolls = ?rolls,
+                "Turn scored"
+            );
+        }
+
+        state.events.publish(
+            state.clock.now(),
+            GameEvent::TurnScored {
+                room_id: self.id,
+                round,
+                seat,
+                category,
+                score,
+            },
+        );
+
+        let name = game.players[seat as usize].name.clone();
+        let mut milestones = Vec::new();
+        if game.turn_history.last().is_some_and(|t| t.yahtzee_bonus) {
+            milestones.push((
+                Milestone::Yahtzee,
+                format!("{} rolled another Yahtzee! +{}", name, game.rules.scoring.yahtzee_bonus),
+            ));
+        } else if category == Category::Yahtzee && score > 0 {
+            milestones.push((Milestone::Yahtzee, format!("{} rolled a Yahtzee!", name)));
+        }
+        if !had_bonus && game.players[seat as usize].scorecard.upper_bonus() > 0 {
+            milestones.push((Milestone::UpperBonus, format!("{} secured the upper bonus", name)));
+        }
+        if leader_before.is_some_and(|id| id != prev_player_id) && sole_leader(game) == Some(prev_player_id) {
+            milestones.push((Milestone::TookLead, format!("{} takes the lead", name)));
+        }
+        let final_round = game.phase == GamePhase::Playing
+            && game.sudden_death.is_none()
+            && round < game.total_rounds
+            && game.round == game.total_rounds;
+
+        let mut messages = vec![ServerMessage::CategoryScored {
+            player_id: prev_player_id,
+            category,
+            score,
+        }];
+        messages.extend(self.announce(milestones));
+        messages.push(ServerMessage::TurnEnded {
+            player_id: prev_player_id,
+        });
+        if final_round {
+            messages.extend(self.announce(vec![(Milestone::FinalRound, "Final round!".to_string())]));
+        }
+        self.end_turn(messages, state, out);
+        true
+    }
+
+    /// The milestones the room's announcement setting lets through, as
+    /// system messages.
+    fn announce(&self, milestones: Vec<(Milestone, String)>) -> impl Iterator<Item = ServerMessage> + '_ {
+        milestones
+            .into_iter()
+            .filter(|(milestone, _)| self.announcements.includes(*milestone))
+            .map(|(milestone, message)| ServerMessage::SystemMessage {
+                message,
+                milestone: Some(milestone),
+            })
+    }
+
+    /// Broadcast the end of a turn, followed by the next turn or the result.
+    fn end_turn(&mut self, mut messages: Vec<ServerMessage>, state: &SharedState, out: &mut Outbox) {
+        let Some(game) = self.game.as_mut() else {
+            return;
+        };
+        let mut recorded = None;
+        let finished = game.phase == GamePhase::Finished;
+
+        if finished {
+            self.finished_until = Some(state.clock.now() + state.finished_room_cooldown.as_secs() as i64);
+            let final_scores: Vec<(Uuid, String, u16)> = game
+                .players
+                .iter()
+                .map(|p| (p.id, p.name.clone(), p.scorecard.grand_total()))
+                .collect();
+            let winner_ids: Vec<Uuid> = game.winners().iter().map(|w| w.id).collect();
+            SeriesStanding::record(&mut self.standings, &final_scores, &winner_ids);
+            state.events.publish(
+                state.clock.now(),
+                GameEvent::GameOver {
+                    room_id: self.id,
+                    scores: final_scores.iter().map(|&(_, _, score)| score).collect(),
+                    winning_seats: game
+                        .players
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, p)| winner_ids.contains(&p.id))
+                        .map(|(seat, _)| seat as u8)
+                        .collect(),
+                },
+            );
+            tracing::info!(turns = game.turn_history.len(), winners = ?winner_ids, "Game finished");
+            let winner_name = game.winner().map(|w| w.name.clone());
+            let results: Vec<(String, u16)> = final_scores
+                .iter()
+                .map(|(_, name, score)| (name.clone(), *score))
+                .collect();
+            let identities: Vec<(Uuid, u16)> = final_scores
+                .iter()
+                .filter_map(|(id, _, score)| {
+                    let identity = self.players.iter().find(|c| c.player_id == *id)?.identity?;
+                    Some((identity, *score))
+                })
+                .collect();
+            let shared_name = results
+                .iter()
+                .enumerate()
+                .any(|(i, (name, _))| results[..i].iter().any(|(other, _)| other == name));
+            // Never let a corrupt scorecard into the ratings
+            let invalid = game
+                .players
+                .iter()
+                .find_map(|p| p.scorecard.validate().err().map(|e| (&p.name, e)));
+            let now = state.clock.now();
+            let duration_secs = now.saturating_sub(self.game_started_at).max(0) as u64;
+            match invalid {
+                Some((name, e)) => {
+                    tracing::error!(player = %name, "Not recording ratings, invalid scorecard: {}", e)
+                }
+                None if self.seed.is_some() => {
+                    tracing::info!(seed = self.seed, "Not recording a game played on a chosen seed")
+                }
+                // Ratings and stats are kept by name, so they can't tell these players apart
+                None if shared_name => {
+                    tracing::info!("Not recording a game where two players share a name")
+                }
+                None => {
+                    let archived = ArchivedGame {
+                        summary: GameSummary {
+                            game_id: state.entropy.uuid(),
+                            room_name: self.name.clone(),
+                            finished_at: now,
+                            duration_secs,
+                            players: results,
+                            winners: game.winners().iter().map(|w| w.name.clone()).collect(),
+                        },
+                        rules: game.rules,
+                        player_ids: game.players.iter().map(|p| p.id).collect(),
+                        scorecards: game.players.iter().map(|p| p.scorecard.clone()).collect(),
+                        turns: game.turn_history.clone(),
+                    };
+                    recorded = Some((archived, winner_name, identities));
+                }
+            }
+            messages.push(ServerMessage::GameOver {
+                final_scores,
+                winner_ids,
+                scorecards: game.players.iter().map(|p| p.scorecard.clone()).collect(),
+                duration_secs,
+                round_totals: game.round_totals(),
+                average_turn_ms: game.average_turn_ms(),
+            });
+        } else {
+            // The standings close the round, ahead of what is announced for the next
+            if game.round > self.round && self.round <= game.total_rounds {
+                let at = messages
+                    .iter()
+                    .position(|m| matches!(m, ServerMessage::TurnEnded { .. }))
+                    .map_or(messages.len(), |i| i + 1);
+                messages.insert(at, round_standings(game, self.round));
+            }
+            self.round = game.round;
+            if let Some(sd) = &game.sudden_death {
+                if sd.rolls.last().is_none_or(|r| r.round < sd.round) {
+                    messages.push(ServerMessage::SystemMessage {
+                        message: playoff_announcement(game, sd),
+                        milestone: None,
+                    });
+                }
+            }
+            game.start_turn_timer(state.clock.now());
+            self.turn_started_ms = state.clock.now_millis();
+            self.last_action_ms = self.turn_started_ms;
+            let next = game.current_player();
+            messages.push(ServerMessage::TurnStarted {
+                player_id: next.id,
+                player_name: next.name.clone(),
+                turn_number: game.round,
+            });
+        }
+
+        self.schedule_nudge(state);
+        for msg in &messages {
+            self.log_event(msg);
+        }
+        // The clocks are live state, so they are not replayed
+        messages.extend(self.clocks(state.clock.now()));
+
+        // Rating changes and records go out before GameOver moves clients
+        // to the results screen
+        if let (Some((archived, winner, identities)), Some(mut persistence)) =
+            (recorded, state.persistence())
+        {
+            let recorded = persistence.record_finished_game(archived, winner.as_deref(), &identities);
+            drop(persistence);
+            let summary: Vec<String> = recorded
+                .ratings
+                .iter()
+                .map(|(name, rating, delta)| format!("{} {} ({:+})", name, rating, delta))
+                .collect();
+            messages.insert(
+                messages.len() - 1,
+                ServerMessage::SystemMessage {
+                    message: format!("Ratings: {}", summary.join(", ")),
+                    milestone: None,
+                },
+            );
+            for (name, milestones) in recorded.records {
+                let list: Vec<String> = milestones.iter().map(|m| m.to_string()).collect();
+                messages.insert(
+                    messages.len() - 1,
+                    ServerMessage::SystemMessage {
+                        message: format!("{} set a personal record: {}", name, list.join(", ")),
+                        milestone: None,
+                    },
+                );
+            }
+        }
+
+        for msg in messages {
+            out.broadcast(msg);
+        }
+        // The room stays open for another game, with the standings so far
+        if finished {
+            self.send_snapshots(state, out);
+        }
+    }
+}
+
+/// The one active player ahead of everyone else, if there is one.
+fn sole_leader(game: &GameState) -> Option<Uuid> {
+    let mut players: Vec<_> = game.active_players().collect();
+    players.sort_by_key(|p| std::cmp::Reverse(p.scorecard.grand_total()));
+    match players.as_slice() {
+        [first, second, ..] if first.scorecard.grand_total() > second.scorecard.grand_total() => Some(first.id),
+        _ => None,
+    }
+}
+
+/// Everyone's total after `round`, and who leads.
+fn round_standings(game: &GameState, round: u8) -> ServerMessage {
+    let best = game.active_players().map(|p| p.scorecard.grand_total()).max();
+    ServerMessage::RoundStandings {
+        round,
+        totals: game
+            .players
+            .iter()
+            .map(|p| (p.id, p.name.clone(), p.scorecard.grand_total()))
+            .collect(),
+        leader_ids: game
+            .active_players()
+            .filter(|p| Some(p.scorecard.grand_total()) == best)
+            .map(|p| p.id)
+            .collect(),
+    }
+}
+
+/// Announce a playoff round to the room.
+fn playoff_announcement(game: &GameState, sd: &SuddenDeath) -> String {
+    let names: Vec<&str> = sd
+        .contenders
+        .iter()
+        .filter_map(|id| game.players.iter().find(|p| p.id == *id))
+        .map(|p| p.name.as_str())
+        .collect();
+    if sd.round == 1 {
+        format!(
+            "Sudden death! {} are tied for first. One roll each, highest total wins.",
+            names.join(" and ")
+        )
+    } else {
+        format!("Still tied: {} roll again.", names.join(" and "))
+    }
+}
+
+fn game_error_to_protocol(e: &GameError) -> (ErrorCode, String) {
+    match e {
+        GameError::NotYourTurn => (ErrorCode::NotYourTurn, e.to_string()),
+        GameError::CategoryAlreadyScored => (ErrorCode::CategoryAlreadyScored, e.to_string()),
+        GameError::InvalidCategoryForJoker => (ErrorCode::InvalidCategoryForJoker, e.to_string()),
+        GameError::CannotHold => (ErrorCode::InvalidHold, e.to_string()),
+        GameError::GameNotInProgress => (ErrorCode::InvalidAction, e.to_string()),
+        GameError::ImpossibleScore => (ErrorCode::InternalError, e.to_string()),
+        _ => (ErrorCode::InvalidAction, e.to_string()),
     }
 }
@@ -1,37 +1,203 @@
-use std::collections::HashMap;
 use std::net::SocketAddr;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
 
 use tokio::net::TcpListener;
 use tokio::sync::RwLock;
-use uuid::Uuid;
 
-use crate::connection::{self, ConnectionHandle};
+use yaht_common::directory::{self, ServerListing};
+use yaht_common::protocol::{DEFAULT_MAX_FRAME_LENGTH, PROTOCOL_VERSION};
+
+use crate::connection;
+use crate::events::EventBus;
 use crate::lobby::LobbyManager;
+use crate::monitor::PlayMonitor;
+use crate::persistence::Persistence;
+use crate::ratelimit::RateLimits;
+use crate::registry::{ConnectionRegistry, ResumeTokens};
+use crate::sim::{Clock, Entropy};
+
+/// How often to check whether the current season has ended.
+const SEASON_CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Tunable server limits.
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    pub max_connections: usize,
+    /// Game events each room keeps for spectator catch-up.
+    pub replay_history: usize,
+    /// Seed for ids and dice; a random one is chosen (and logged) if unset.
+    pub seed: Option<u64>,
+    pub clock: Clock,
+    /// Message handlers and room commands slower than this are logged as warnings.
+    pub slow_handler: Duration,
+    /// Largest frame accepted from or sent to a client, in bytes.
+    pub max_frame_length: usize,
+    pub rate_limits: RateLimits,
+    /// Where rooms publish events for observers; see [`crate::events`].
+    pub events: EventBus,
+    /// How long a seat is held for a player whose connection dropped mid-game.
+    pub resume_grace: Duration,
+    /// How long a room whose game ended is listed as finished, staying
+    /// open even once everyone has left.
+    pub finished_room_cooldown: Duration,
+    /// The public directory to announce this server to, if any.
+    pub directory: Option<DirectoryConfig>,
+    /// Flags implausibly fast or perfect play in the logs; off if unset.
+    pub monitor: Option<PlayMonitor>,
+    /// Refuse to create a room with the name of one already open, ignoring case.
+    pub unique_room_names: bool,
+}
+
+/// How this server announces itself to a public server directory.
+#[derive(Debug, Clone)]
+pub struct DirectoryConfig {
+    /// Base URL of the directory, e.g. `http://directory.example.org`.
+    pub url: String,
+    pub name: String,
+    /// Where players should connect, as HOST:PORT.
+    pub address: String,
+    pub region: Option<String>,
+    /// Time between heartbeats.
+    pub interval: Duration,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            max_connections: 100,
+            replay_history: 2000,
+            seed: None,
+            clock: Clock::System,
+            slow_handler: Duration::from_millis(100),
+            max_frame_length: DEFAULT_MAX_FRAME_LENGTH,
+            rate_limits: RateLimits::default(),
+            events: EventBus::default(),
+            resume_grace: Duration::from_secs(60),
+            finished_room_cooldown: Duration::from_secs(300),
+            directory: None,
+            monitor: None,
+            unique_room_names: false,
+        }
+    }
+}
 
 pub struct ServerState {
+    /// Directory of room tasks; only locked to create, find or close rooms.
     pub lobby: RwLock<LobbyManager>,
-    pub connections: RwLock<HashMap<Uuid, ConnectionHandle>>,
+    pub connections: ConnectionRegistry,
+    /// Sessions of players who dropped mid-game, waiting to resume.
+    pub resume_tokens: ResumeTokens,
     pub max_connections: usize,
+    /// Game events each room keeps for spectator catch-up.
+    pub replay_history: usize,
+    /// Persistent ratings, if the server was started with a data directory.
+    pub persistence: Option<Mutex<Persistence>>,
+    pub entropy: Entropy,
+    pub clock: Clock,
+    pub slow_handler: Duration,
+    pub max_frame_length: usize,
+    pub rate_limits: RateLimits,
+    pub events: EventBus,
+    pub resume_grace: Duration,
+    pub finished_room_cooldown: Duration,
+    pub monitor: Option<PlayMonitor>,
+    pub unique_room_names: bool,
+}
+
+impl ServerState {
+    /// Lock persistent storage, if enabled. The guard is not `Send`, so it
+    /// cannot be held across an `.await` in a spawned task.
+    pub fn persistence(&self) -> Option<MutexGuard<'_, Persistence>> {
+        self.persistence
+            .as_ref()
+            .map(|p| p.lock().unwrap_or_else(PoisonError::into_inner))
+    }
 }
 
 pub type SharedState = Arc<ServerState>;
 
-pub async fn run(addr: SocketAddr, max_connections: usize) -> anyhow::Result<()> {
+pub async fn run(
+    addr: SocketAddr,
+    config: ServerConfig,
+    persistence: Option<Persistence>,
+) -> anyhow::Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    tracing::info!("Listening on {}", addr);
+    serve(listener, config, persistence).await
+}
+
+/// Accept connections on an already bound listener until it fails.
+///
+/// Binding to port 0 first and serving from a spawned task gives an
+/// in-process server on an ephemeral port, as the integration tests do.
+pub async fn serve(
+    listener: TcpListener,
+    config: ServerConfig,
+    persistence: Option<Persistence>,
+) -> anyhow::Result<()> {
+    let seed = config.seed.unwrap_or_else(rand::random);
+    tracing::info!("Random seed: {}", seed);
+
     let state: SharedState = Arc::new(ServerState {
         lobby: RwLock::new(LobbyManager::new()),
-        connections: RwLock::new(HashMap::new()),
-        max_connections,
+        connections: ConnectionRegistry::default(),
+        resume_tokens: ResumeTokens::default(),
+        max_connections: config.max_connections,
+        replay_history: config.replay_history,
+        persistence: persistence.map(Mutex::new),
+        entropy: Entropy::new(seed),
+        clock: config.clock,
+        slow_handler: config.slow_handler,
+        max_frame_length: config.max_frame_length,
+        rate_limits: config.rate_limits,
+        events: config.events,
+        resume_grace: config.resume_grace,
+        finished_room_cooldown: config.finished_room_cooldown,
+        monitor: config.monitor,
+        unique_room_names: config.unique_room_names,
     });
 
-    let listener = TcpListener::bind(addr).await?;
-    tracing::info!("Listening on {}", addr);
+    if state.persistence.is_some() {
+        let state = state.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(SEASON_CHECK_INTERVAL);
+            loop {
+                interval.tick().await;
+                if let Some(mut persistence) = state.persistence() {
+                    persistence.roll_season_if_due(state.clock.now());
+                }
+            }
+        });
+    }
+
+    if let Some(directory) = config.directory {
+        let state = state.clone();
+        tokio::spawn(async move {
+            tracing::info!("Announcing to the server directory at {}", directory.url);
+            let mut interval = tokio::time::interval(directory.interval);
+            loop {
+                interval.tick().await;
+                let listing = ServerListing {
+                    name: directory.name.clone(),
+                    address: directory.address.clone(),
+                    region: directory.region.clone(),
+                    players: state.connections.len() as u32,
+                    max_players: state.max_connections as u32,
+                    protocol: PROTOCOL_VERSION,
+                };
+                if let Err(e) = directory::announce(&directory.url, &listing).await {
+                    tracing::warn!("Directory heartbeat failed: {}", e);
+                }
+            }
+        });
+    }
 
     loop {
         let (stream, peer_addr) = listener.accept().await?;
 
         // Enforce max connections
-        let conn_count = state.connections.read().await.len();
+        let conn_count = state.connections.len();
         if conn_count >= state.max_connections {
             tracing::warn!(
                 "Rejecting connection from {} (max {} reached)",
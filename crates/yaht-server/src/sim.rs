@@ -0,0 +1,85 @@
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use uuid::Uuid;
+
+/// Where the server gets the time for chat timestamps and season rollover.
+///
+/// A virtual clock only moves when told to, so simulated games see the same
+/// timestamps on every run.
+#[derive(Debug, Clone, Default)]
+pub enum Clock {
+    #[default]
+    System,
+    Virtual(Arc<AtomicI64>),
+}
+
+impl Clock {
+    /// A virtual clock starting at `start` (Unix seconds).
+    pub fn virtual_at(start: i64) -> Self {
+        Clock::Virtual(Arc::new(AtomicI64::new(start)))
+    }
+
+    /// Current Unix timestamp in seconds.
+    pub fn now(&self) -> i64 {
+        match self {
+            Clock::System => chrono::Utc::now().timestamp(),
+            Clock::Virtual(now) => now.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Current Unix time in milliseconds. A virtual clock only has whole seconds.
+    pub fn now_millis(&self) -> i64 {
+        match self {
+            Clock::System => chrono::Utc::now().timestamp_millis(),
+            Clock::Virtual(now) => now.load(Ordering::Relaxed) * 1000,
+        }
+    }
+
+    /// Move a virtual clock forward. The system clock ignores this.
+    pub fn advance(&self, secs: i64) {
+        if let Clock::Virtual(now) = self {
+            now.fetch_add(secs, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Seeded randomness for everything the server generates: player and room
+/// ids, and each room's dice.
+///
+/// Given the same seed and the same sequence of client messages, a server
+/// makes the same ids and rolls, so a game reported with its seed can be
+/// replayed exactly.
+pub struct Entropy {
+    seed: u64,
+    rng: Mutex<StdRng>,
+}
+
+impl Entropy {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: Mutex::new(StdRng::seed_from_u64(seed)),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn uuid(&self) -> Uuid {
+        let bytes = self.lock().gen();
+        uuid::Builder::from_random_bytes(bytes).into_uuid()
+    }
+
+    /// An independent generator, e.g. for a new room's dice.
+    pub fn fork(&self) -> StdRng {
+        StdRng::seed_from_u64(self.lock().gen())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, StdRng> {
+        self.rng.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
@@ -0,0 +1,2312 @@
+//! End-to-end tests: a real server on an ephemeral port, driven by scripted
+//! protocol clients.
+
+#[macro_use]
+mod support;
+
+use std::time::Duration;
+
+use tokio::net::TcpListener;
+use uuid::Uuid;
+
+use yaht_common::game::{GameConfig, OutOfTime, Pace};
+use yaht_common::lobby::RoomInfoState;
+use yaht_common::player::Scorecard;
+use yaht_common::protocol::{
+    self, ArchivedGame, ClientMessage, ErrorCode, GameSummary, Milestone, RoomState, ServerMessage,
+};
+use yaht_common::rating::{self, INITIAL_RATING};
+use yaht_common::scoring::{Category, ScoringRules};
+use yaht_server::monitor::{MonitorThresholds, PlayMonitor, Suspicion};
+use yaht_server::persistence::Persistence;
+use yaht_server::ratelimit::RateLimit;
+use yaht_server::server::{DirectoryConfig, ServerConfig};
+use yaht_server::sim::Clock;
+
+use support::{http_get, http_request, recv_heartbeat, test_config, DataDir, EventObserver, TestGame, TestServer};
+
+#[tokio::test]
+async fn test_join_broadcasts_in_order() {
+    let server = TestServer::start().await;
+    let mut alice = server.connect("alice").await;
+    let mut bob = server.connect("bob").await;
+
+    let room_id = alice.create_room("table", 2).await;
+    bob.send(ClientMessage::JoinRoom {
+        room_id,
+        password: None,
+        spectate_if_started: false,
+    })
+    .await;
+
+    let room_state = expect!(bob, ServerMessage::RoomJoined { room_state, .. } => room_state);
+    assert_eq!(room_state.host_id, alice.player_id);
+    let names: Vec<_> = room_state.players.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, ["alice", "bob"]);
+
+    expect!(alice, ServerMessage::PlayerJoined { player_id, .. } if player_id == bob.player_id);
+    alice.expect_quiet().await;
+    bob.expect_quiet().await;
+}
+
+#[tokio::test]
+async fn test_host_updates_room_settings() {
+    let server = TestServer::start().await;
+    let mut alice = server.connect("alice").await;
+    let mut bob = server.connect("bob").await;
+    let mut carol = server.connect("carol").await;
+    let room_id = alice.create_room("table", 4).await;
+    bob.join_room(room_id).await;
+    carol.join_room(room_id).await;
+    expect!(alice, ServerMessage::PlayerJoined { .. });
+    expect!(alice, ServerMessage::PlayerJoined { .. });
+    expect!(bob, ServerMessage::PlayerJoined { .. });
+
+    let update = |max_players, password: Option<&str>, rules| ClientMessage::UpdateRoomSettings {
+        max_players,
+        password: password.map(String::from),
+        rules,
+        locked: None,
+        auto_start: None,
+        max_spectators: None,
+        seed: None,
+        announcements: None,
+        shuffle_order: None,
+        nudge_secs: None,
+    };
+
+    // Only the host may, and not below the players already seated
+    bob.send(update(Some(6), None, None)).await;
+    expect!(bob, ServerMessage::Error { code: ErrorCode::NotHost, .. });
+    bob.send(ClientMessage::StartGame).await;
+    expect!(bob, ServerMessage::Error { code: ErrorCode::NotHost, .. });
+    alice.send(update(Some(2), None, None)).await;
+    expect!(alice, ServerMessage::Error { code: ErrorCode::InvalidAction, .. });
+
+    let timed = GameConfig {
+        turn_seconds: Some(30),
+        ..GameConfig::default()
+    };
+    alice.send(update(Some(3), Some("pw"), Some(timed))).await;
+    for player in [&mut alice, &mut bob, &mut carol] {
+        let room_state = expect!(player, ServerMessage::RoomUpdate { room_state } => room_state);
+        assert_eq!(room_state.max_players, 3);
+        assert_eq!(room_state.rules.turn_seconds, Some(30));
+        assert!(room_state.has_password);
+    }
+
+    // Newcomers now need the password
+    let mut dave = server.connect("dave").await;
+    dave.send(ClientMessage::JoinRoom {
+        room_id,
+        password: None,
+        spectate_if_started: false,
+    })
+    .await;
+    expect!(dave, ServerMessage::Error { code: ErrorCode::WrongPassword, .. });
+
+    // Settings left out are kept; an empty password removes it
+    alice.send(update(None, Some(""), None)).await;
+    let room_state = expect!(alice, ServerMessage::RoomUpdate { room_state } => room_state);
+    assert_eq!(room_state.max_players, 3);
+    assert_eq!(room_state.rules.turn_seconds, Some(30));
+    assert!(!room_state.has_password);
+}
+
+#[tokio::test]
+async fn test_room_password_is_checked_not_shown() {
+    let server = TestServer::start().await;
+    let mut alice = server.connect("alice").await;
+    alice
+        .send(ClientMessage::CreateRoom {
+            room_name: "private".into(),
+            max_players: 2,
+            password: Some("hunter2".into()),
+            rules: GameConfig::default(),
+        })
+        .await;
+    let (room_id, room_state) =
+        expect!(alice, ServerMessage::RoomJoined { room_id, room_state } => (room_id, room_state));
+    assert!(room_state.has_password);
+
+    let mut bob = server.connect("bob").await;
+    bob.send(ClientMessage::ListRooms).await;
+    let rooms = expect!(bob, ServerMessage::RoomList { rooms } => rooms);
+    assert!(rooms[0].has_password);
+    assert!(!serde_json::to_string(&rooms).unwrap().contains("hunter2"));
+
+    for wrong in [None, Some("hunter"), Some("Hunter2")] {
+        bob.send(ClientMessage::JoinRoom {
+            room_id,
+            password: wrong.map(String::from),
+            spectate_if_started: false,
+        })
+        .await;
+        expect!(bob, ServerMessage::Error { code: ErrorCode::WrongPassword, .. });
+    }
+    bob.send(ClientMessage::JoinRoom {
+        room_id,
+        password: Some("hunter2".into()),
+        spectate_if_started: false,
+    })
+    .await;
+    let room_state = expect!(bob, ServerMessage::RoomJoined { room_state, .. } => room_state);
+    assert!(!serde_json::to_string(&room_state).unwrap().contains("hunter2"));
+}
+
+#[tokio::test]
+async fn test_locked_room_turns_away_new_players() {
+    let server = TestServer::start().await;
+    let mut alice = server.connect("alice").await;
+    let mut bob = server.connect("bob").await;
+    let room_id = alice.create_room("table", 4).await;
+    let lock = |locked| ClientMessage::UpdateRoomSettings {
+        max_players: None,
+        password: None,
+        rules: None,
+        locked: Some(locked),
+        auto_start: None,
+        max_spectators: None,
+        seed: None,
+        announcements: None,
+        shuffle_order: None,
+        nudge_secs: None,
+    };
+
+    alice.send(lock(true)).await;
+    let room_state = expect!(alice, ServerMessage::RoomUpdate { room_state } => room_state);
+    assert!(room_state.locked);
+
+    // The lobby shows the lock, and joining is refused
+    bob.send(ClientMessage::ListRooms).await;
+    let rooms = expect!(bob, ServerMessage::RoomList { rooms } => rooms);
+    assert!(rooms.iter().any(|r| r.room_id == room_id && r.locked && !r.has_password));
+    bob.send(ClientMessage::JoinRoom {
+        room_id,
+        password: None,
+        spectate_if_started: false,
+    })
+    .await;
+    expect!(bob, ServerMessage::Error { code: ErrorCode::RoomLocked, .. });
+
+    alice.send(lock(false)).await;
+    expect!(alice, ServerMessage::RoomUpdate { room_state } if !room_state.locked);
+    bob.join_room(room_id).await;
+}
+
+#[tokio::test]
+async fn test_room_pace_shows_in_the_lobby() {
+    let server = TestServer::start().await;
+    let mut alice = server.connect("alice").await;
+    let mut bob = server.connect("bob").await;
+    let room_id = alice
+        .create_room_with("table", 4, Pace::Blitz.apply(GameConfig::default()))
+        .await;
+
+    bob.send(ClientMessage::ListRooms).await;
+    let rooms = expect!(bob, ServerMessage::RoomList { rooms } => rooms);
+    assert!(rooms.iter().any(|r| r.room_id == room_id && r.pace == Some(Pace::Blitz)));
+
+    // A timer set by hand is no longer the preset
+    let custom = GameConfig {
+        turn_seconds: Some(60),
+        ..Pace::Blitz.apply(GameConfig::default())
+    };
+    alice
+        .send(ClientMessage::UpdateRoomSettings {
+            max_players: None,
+            password: None,
+            rules: Some(custom),
+            locked: None,
+            auto_start: None,
+            max_spectators: None,
+            seed: None,
+            announcements: None,
+            shuffle_order: None,
+            nudge_secs: None,
+        })
+        .await;
+    let room_state = expect!(alice, ServerMessage::RoomUpdate { room_state } => room_state);
+    assert_eq!(room_state.rules.pace, None);
+    assert_eq!(room_state.rules.turn_seconds, Some(60));
+}
+
+#[tokio::test]
+async fn test_room_names_are_checked() {
+    let server = TestServer::start_with(ServerConfig {
+        unique_room_names: true,
+        ..test_config()
+    })
+    .await;
+    let mut alice = server.connect("alice").await;
+    let mut bob = server.connect("bob").await;
+
+    for bad in ["   ", "<blink>", "shithouse"] {
+        bob.send(ClientMessage::CreateRoom {
+            room_name: bad.into(),
+            max_players: 4,
+            password: None,
+            rules: GameConfig::default(),
+        })
+        .await;
+        expect!(bob, ServerMessage::Error { code: ErrorCode::InvalidRoomName, .. });
+    }
+
+    // Names are stored trimmed, and compared ignoring case
+    alice.create_room("  Friday Night ", 4).await;
+    bob.send(ClientMessage::CreateRoom {
+        room_name: "friday night".into(),
+        max_players: 4,
+        password: None,
+        rules: GameConfig::default(),
+    })
+    .await;
+    expect!(bob, ServerMessage::Error { code: ErrorCode::RoomNameTaken, .. });
+
+    bob.send(ClientMessage::ListRooms).await;
+    let rooms = expect!(bob, ServerMessage::RoomList { rooms } => rooms);
+    assert_eq!(rooms.len(), 1);
+    assert_eq!(rooms[0].room_name, "Friday Night");
+}
+
+#[tokio::test]
+async fn test_spectator_limit_turns_away_extra_watchers() {
+    let server = TestServer::start().await;
+    let mut alice = server.connect("alice").await;
+    let mut bob = server.connect("bob").await;
+    let mut carol = server.connect("carol").await;
+    let room_id = alice.create_room("table", 4).await;
+    let limit = |max| ClientMessage::UpdateRoomSettings {
+        max_players: None,
+        password: None,
+        rules: None,
+        locked: None,
+        auto_start: None,
+        max_spectators: Some(max),
+        seed: None,
+        announcements: None,
+        shuffle_order: None,
+        nudge_secs: None,
+    };
+
+    alice.send(limit(1)).await;
+    expect!(alice, ServerMessage::RoomUpdate { room_state } if room_state.max_spectators == Some(1));
+
+    bob.send(ClientMessage::SpectateRoom { room_id, catch_up: false }).await;
+    expect!(bob, ServerMessage::RoomJoined { .. });
+    expect!(alice, ServerMessage::SpectatorJoined { .. });
+    carol.send(ClientMessage::SpectateRoom { room_id, catch_up: false }).await;
+    expect!(carol, ServerMessage::Error { code: ErrorCode::SpectatorsFull, .. });
+
+    // The lobby shows the limit next to the count
+    carol.send(ClientMessage::ListRooms).await;
+    let rooms = expect!(carol, ServerMessage::RoomList { rooms } => rooms);
+    let room = rooms.iter().find(|r| r.room_id == room_id).unwrap();
+    assert_eq!((room.spectator_count, room.max_spectators), (1, Some(1)));
+    assert!(!room.can_spectate());
+
+    // Closing the room to spectators keeps the ones already watching
+    alice.send(limit(0)).await;
+    expect!(alice, ServerMessage::RoomUpdate { room_state } if room_state.spectators.len() == 1);
+}
+
+#[tokio::test]
+async fn test_late_arrival_can_watch_instead() {
+    let server = TestServer::start().await;
+    let mut game = TestGame::start(&server, &["alice", "bob"]).await;
+    let mut carol = server.connect("carol").await;
+    let join = |spectate_if_started| ClientMessage::JoinRoom {
+        room_id: game.room_id,
+        password: None,
+        spectate_if_started,
+    };
+
+    carol.send(join(false)).await;
+    expect!(carol, ServerMessage::Error { code: ErrorCode::GameAlreadyStarted, .. });
+
+    // Asking to watch if it has started seats carol as a spectator
+    carol.send(join(true)).await;
+    let room_state = expect!(carol, ServerMessage::RoomJoined { room_state, .. } => room_state);
+    assert!(room_state.spectators.iter().any(|s| s == "carol"));
+    expect!(carol, ServerMessage::GameState { .. });
+    for player in &mut game.players {
+        expect!(player, ServerMessage::SpectatorJoined { player_name } if player_name == "carol");
+    }
+
+    // Spectators can't play
+    carol.send(ClientMessage::RollDice).await;
+    expect!(carol, ServerMessage::Error { .. });
+}
+
+#[tokio::test]
+async fn test_time_bank_running_out_plays_the_turn() {
+    let clock = Clock::virtual_at(1_700_000_000);
+    let server = TestServer::start_with(ServerConfig {
+        clock: clock.clone(),
+        ..test_config()
+    })
+    .await;
+    let rules = GameConfig {
+        time_bank_secs: Some(60),
+        ..GameConfig::default()
+    };
+    let mut game = TestGame::start_with(&server, &["alice", "bob"], rules).await;
+    for player in &mut game.players {
+        expect!(player, ServerMessage::TimeBank { seconds_left: 60, .. });
+    }
+
+    // A quick turn only uses a little of the bank
+    clock.advance(15);
+    game.play_turn().await;
+    let idle = game.players[game.current].player_id;
+    let out_of_time = format!("{} ran out of time", game.players[game.current].name);
+    for player in &mut game.players {
+        expect!(player, ServerMessage::TimeBank { player_id, seconds_left: 60 } if player_id == idle);
+    }
+
+    // Running out has the server play the turn
+    clock.advance(61);
+    for player in &mut game.players {
+        expect!(player, ServerMessage::SystemMessage { message, .. } if message == out_of_time);
+        expect!(player, ServerMessage::DiceRolled { .. });
+        expect!(player, ServerMessage::CategoryScored { player_id, .. } if player_id == idle);
+        expect!(player, ServerMessage::TurnEnded { player_id } if player_id == idle);
+        expect!(player, ServerMessage::RoundStandings { round: 1, .. });
+        expect!(player, ServerMessage::TurnStarted { player_id, .. } if player_id != idle);
+        expect!(player, ServerMessage::TimeBank { seconds_left: 45, .. });
+    }
+}
+
+#[tokio::test]
+async fn test_time_bank_running_out_can_forfeit() {
+    let clock = Clock::virtual_at(1_700_000_000);
+    let server = TestServer::start_with(ServerConfig {
+        clock: clock.clone(),
+        ..test_config()
+    })
+    .await;
+    let rules = GameConfig {
+        time_bank_secs: Some(60),
+        out_of_time: OutOfTime::Forfeit,
+        ..GameConfig::default()
+    };
+    let mut game = TestGame::start_with(&server, &["alice", "bob"], rules).await;
+    let idle = game.players[game.current].player_id;
+
+    clock.advance(60);
+    for player in &mut game.players {
+        let forfeited = player.recv_until(|m| matches!(m, ServerMessage::PlayerForfeited { .. })).await;
+        assert!(matches!(forfeited, ServerMessage::PlayerForfeited { player_id, .. } if player_id == idle));
+        let game_over = player.recv_until(|m| matches!(m, ServerMessage::GameOver { .. })).await;
+        assert!(matches!(game_over, ServerMessage::GameOver { winner_ids, .. } if !winner_ids.contains(&idle)));
+    }
+}
+
+#[tokio::test]
+async fn test_full_room_starts_after_a_countdown() {
+    let clock = Clock::virtual_at(1_700_000_000);
+    let server = TestServer::start_with(ServerConfig {
+        clock: clock.clone(),
+        ..test_config()
+    })
+    .await;
+    let mut alice = server.connect("alice").await;
+    let mut bob = server.connect("bob").await;
+    let mut carol = server.connect("carol").await;
+    let room_id = alice.create_room("table", 3).await;
+    let auto_start = |on| ClientMessage::UpdateRoomSettings {
+        max_players: None,
+        password: None,
+        rules: None,
+        locked: None,
+        auto_start: Some(on),
+        max_spectators: None,
+        seed: None,
+        announcements: None,
+        shuffle_order: None,
+        nudge_secs: None,
+    };
+    alice.send(auto_start(true)).await;
+    expect!(alice, ServerMessage::RoomUpdate { room_state } if room_state.auto_start);
+
+    bob.join_room(room_id).await;
+    expect!(alice, ServerMessage::PlayerJoined { .. });
+    carol.join_room(room_id).await;
+    expect!(alice, ServerMessage::PlayerJoined { .. });
+    expect!(bob, ServerMessage::PlayerJoined { .. });
+    for player in [&mut alice, &mut bob, &mut carol] {
+        expect!(player, ServerMessage::AutoStartCountdown { seconds_left: 5 });
+    }
+
+    // The host can call it off
+    alice.send(auto_start(false)).await;
+    for player in [&mut alice, &mut bob, &mut carol] {
+        expect!(player, ServerMessage::RoomUpdate { .. });
+        expect!(player, ServerMessage::AutoStartCancelled);
+    }
+
+    alice.send(auto_start(true)).await;
+    for player in [&mut alice, &mut bob, &mut carol] {
+        expect!(player, ServerMessage::RoomUpdate { .. });
+        expect!(player, ServerMessage::AutoStartCountdown { seconds_left: 5 });
+    }
+    clock.advance(2);
+    for player in [&mut alice, &mut bob, &mut carol] {
+        expect!(player, ServerMessage::AutoStartCountdown { seconds_left: 3 });
+    }
+    clock.advance(3);
+    for player in [&mut alice, &mut bob, &mut carol] {
+        expect!(player, ServerMessage::GameStarted { .. });
+    }
+}
+
+#[tokio::test]
+async fn test_full_room_starts_a_rematch_after_a_countdown() {
+    let clock = Clock::virtual_at(1_700_000_000);
+    let server = TestServer::start_with(ServerConfig {
+        clock: clock.clone(),
+        ..test_config()
+    })
+    .await;
+    let rules = GameConfig {
+        rounds: 1,
+        ..GameConfig::default()
+    };
+    let mut game = TestGame::start_with(&server, &["alice", "bob"], rules).await;
+    game.play_turn().await;
+    assert!(matches!(game.play_turn().await, ServerMessage::GameOver { .. }));
+
+    // The room is still full once the game is over
+    game.players[0]
+        .send(ClientMessage::UpdateRoomSettings {
+            max_players: None,
+            password: None,
+            rules: None,
+            locked: None,
+            auto_start: Some(true),
+            max_spectators: None,
+            seed: None,
+            announcements: None,
+            shuffle_order: None,
+            nudge_secs: None,
+        })
+        .await;
+    for player in &mut game.players {
+        player
+            .recv_until(|m| matches!(m, ServerMessage::RoomUpdate { room_state } if room_state.auto_start))
+            .await;
+        expect!(player, ServerMessage::AutoStartCountdown { seconds_left: 5 });
+    }
+    clock.advance(5);
+    for player in &mut game.players {
+        expect!(player, ServerMessage::GameStarted { .. });
+    }
+}
+
+#[tokio::test]
+async fn test_game_starts_once_everyone_is_ready() {
+    let server = TestServer::start().await;
+    let mut alice = server.connect("alice").await;
+    let mut bob = server.connect("bob").await;
+    let room_id = alice.create_room("table", 4).await;
+    bob.join_room(room_id).await;
+    expect!(alice, ServerMessage::PlayerJoined { .. });
+
+    alice.send(ClientMessage::SetReady { ready: true }).await;
+    for player in [&mut alice, &mut bob] {
+        let room_state = expect!(player, ServerMessage::RoomUpdate { room_state } => room_state);
+        let ready: Vec<bool> = room_state.players.iter().map(|p| p.ready).collect();
+        assert_eq!(ready, [true, false]);
+    }
+
+    bob.send(ClientMessage::SetReady { ready: true }).await;
+    for player in [&mut alice, &mut bob] {
+        expect!(player, ServerMessage::RoomUpdate { .. });
+        expect!(player, ServerMessage::GameStarted { .. });
+    }
+    bob.send(ClientMessage::SetReady { ready: false }).await;
+    expect!(bob, ServerMessage::Error { code: ErrorCode::GameAlreadyStarted, .. });
+}
+
+#[tokio::test]
+async fn test_host_can_kick_players() {
+    let server = TestServer::start().await;
+    let mut alice = server.connect("alice").await;
+    let mut bob = server.connect("bob").await;
+    let mut carol = server.connect("carol").await;
+    let room_id = alice.create_room("table", 4).await;
+    bob.join_room(room_id).await;
+    expect!(alice, ServerMessage::PlayerJoined { .. });
+    carol.join_room(room_id).await;
+    expect!(alice, ServerMessage::PlayerJoined { .. });
+    expect!(bob, ServerMessage::PlayerJoined { .. });
+
+    bob.send(ClientMessage::Kick { player_id: carol.player_id }).await;
+    expect!(bob, ServerMessage::Error { code: ErrorCode::NotHost, .. });
+
+    alice.send(ClientMessage::Kick { player_id: bob.player_id }).await;
+    for player in [&mut alice, &mut carol] {
+        expect!(player, ServerMessage::PlayerLeft { player_id, .. } if player_id == bob.player_id);
+    }
+    expect!(bob, ServerMessage::Error { code: ErrorCode::Kicked, .. });
+
+    // Bob is free to come back, or go anywhere else
+    bob.join_room(room_id).await;
+    expect!(alice, ServerMessage::PlayerJoined { player_id, .. } if player_id == bob.player_id);
+}
+
+#[tokio::test]
+async fn test_whispers_reach_only_their_recipient() {
+    let server = TestServer::start().await;
+    let mut alice = server.connect("alice").await;
+    let mut bob = server.connect("bob").await;
+    let mut carol = server.connect("carol").await;
+    let room_id = alice.create_room("table", 4).await;
+    bob.join_room(room_id).await;
+    expect!(alice, ServerMessage::PlayerJoined { .. });
+    carol.join_room(room_id).await;
+    expect!(alice, ServerMessage::PlayerJoined { .. });
+    expect!(bob, ServerMessage::PlayerJoined { .. });
+
+    alice
+        .send(ClientMessage::Whisper {
+            to: "bob".into(),
+            message: "go easy on carol".into(),
+        })
+        .await;
+    for player in [&mut alice, &mut bob] {
+        let (sender, whisper_to) = expect!(
+            player,
+            ServerMessage::ChatMessage { sender_name, whisper_to, .. } => (sender_name, whisper_to)
+        );
+        assert_eq!(sender, "alice");
+        assert_eq!(whisper_to.as_deref(), Some("bob"));
+    }
+    carol.expect_quiet().await;
+
+    alice
+        .send(ClientMessage::Whisper {
+            to: "dave".into(),
+            message: "anyone there?".into(),
+        })
+        .await;
+    expect!(alice, ServerMessage::Error { code: ErrorCode::InvalidAction, .. });
+}
+
+#[tokio::test]
+async fn test_chosen_seed_deals_the_same_dice() {
+    let server = TestServer::start().await;
+    let mut first_rolls = Vec::new();
+    for table in ["first", "second"] {
+        let mut host = server.connect(&format!("{}-host", table)).await;
+        let mut guest = server.connect(&format!("{}-guest", table)).await;
+        let room_id = host.create_room(table, 2).await;
+        guest.join_room(room_id).await;
+        expect!(host, ServerMessage::PlayerJoined { .. });
+
+        host.send(ClientMessage::UpdateRoomSettings {
+            max_players: None,
+            password: None,
+            rules: None,
+            locked: None,
+            auto_start: None,
+            max_spectators: None,
+            seed: Some(7),
+            announcements: None,
+            shuffle_order: None,
+            nudge_secs: None,
+        })
+        .await;
+        for player in [&mut host, &mut guest] {
+            expect!(player, ServerMessage::RoomUpdate { room_state } if room_state.seed == Some(7));
+        }
+
+        host.send(ClientMessage::StartGame).await;
+        expect!(guest, ServerMessage::GameStarted { .. });
+        let state = expect!(host, ServerMessage::GameStarted { game_state } => game_state);
+        let roller = if state.players[state.current_player_index].id == host.player_id {
+            &mut host
+        } else {
+            &mut guest
+        };
+        roller.send(ClientMessage::RollDice).await;
+        first_rolls.push(expect!(roller, ServerMessage::DiceRolled { dice, .. } => dice.values()));
+    }
+    assert_eq!(first_rolls[0], first_rolls[1]);
+}
+
+#[tokio::test]
+async fn test_shuffled_turn_order() {
+    let server = TestServer::start().await;
+    let names = ["p1", "p2", "p3", "p4", "p5", "p6"];
+    let mut players = Vec::new();
+    for name in names {
+        players.push(server.connect(name).await);
+    }
+    let room_id = players[0].create_room("shuffled", 6).await;
+    for i in 1..players.len() {
+        players[i].join_room(room_id).await;
+        for earlier in &mut players[..i] {
+            expect!(earlier, ServerMessage::PlayerJoined { .. });
+        }
+    }
+
+    // A chosen seed makes the draw repeatable
+    players[0]
+        .send(ClientMessage::UpdateRoomSettings {
+            max_players: None,
+            password: None,
+            rules: None,
+            locked: None,
+            auto_start: None,
+            max_spectators: None,
+            seed: Some(7),
+            announcements: None,
+            shuffle_order: Some(true),
+            nudge_secs: None,
+        })
+        .await;
+    for player in &mut players {
+        expect!(player, ServerMessage::RoomUpdate { room_state } if room_state.shuffle_order);
+    }
+
+    players[0].send(ClientMessage::StartGame).await;
+    let mut orders = Vec::new();
+    for player in &mut players {
+        let state = expect!(player, ServerMessage::GameStarted { game_state } => game_state);
+        assert_eq!(state.current_player_index, 0);
+        orders.push(state.players.iter().map(|p| p.name.clone()).collect::<Vec<_>>());
+    }
+    assert!(orders.windows(2).all(|w| w[0] == w[1]));
+    let mut sorted = orders[0].clone();
+    sorted.sort();
+    assert_eq!(sorted, names);
+    assert_ne!(orders[0], names);
+}
+
+#[tokio::test]
+async fn test_players_choose_their_seats() {
+    let server = TestServer::start().await;
+    let mut alice = server.connect("alice").await;
+    let mut bob = server.connect("bob").await;
+    let mut carol = server.connect("carol").await;
+    let room_id = alice.create_room("seats", 3).await;
+    bob.join_room(room_id).await;
+    expect!(alice, ServerMessage::PlayerJoined { .. });
+    carol.join_room(room_id).await;
+    expect!(alice, ServerMessage::PlayerJoined { .. });
+    expect!(bob, ServerMessage::PlayerJoined { .. });
+
+    carol.send(ClientMessage::MoveSeat { seat: 3 }).await;
+    expect!(carol, ServerMessage::Error { code: ErrorCode::InvalidAction, .. });
+
+    carol.send(ClientMessage::MoveSeat { seat: 0 }).await;
+    for player in [&mut alice, &mut bob, &mut carol] {
+        let names = expect!(player, ServerMessage::RoomUpdate { room_state } => room_state.players);
+        let names: Vec<&str> = names.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["carol", "alice", "bob"]);
+    }
+
+    alice.send(ClientMessage::StartGame).await;
+    for player in [&mut alice, &mut bob, &mut carol] {
+        let state = expect!(player, ServerMessage::GameStarted { game_state } => game_state);
+        let names: Vec<&str> = state.players.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["carol", "alice", "bob"]);
+        assert_eq!(state.current_player_index, 0);
+    }
+
+    bob.send(ClientMessage::MoveSeat { seat: 0 }).await;
+    expect!(bob, ServerMessage::Error { code: ErrorCode::GameAlreadyStarted, .. });
+}
+
+#[tokio::test]
+async fn test_full_game() {
+    let clock = Clock::virtual_at(1_700_000_000);
+    let server = TestServer::start_with(ServerConfig {
+        clock: clock.clone(),
+        ..test_config()
+    })
+    .await;
+    let mut game = TestGame::start(&server, &["alice", "bob", "carol"]).await;
+
+    let turns = 13 * game.players.len();
+    for turn in 1..=turns {
+        let before = game.current;
+        clock.advance(10);
+        match game.play_turn().await {
+            ServerMessage::TurnStarted { .. } if turn < turns => {
+                assert_eq!(game.current, (before + 1) % game.players.len());
+            }
+            ServerMessage::GameOver {
+                final_scores,
+                winner_ids,
+                scorecards,
+                duration_secs,
+                round_totals,
+                ..
+            } if turn == turns => {
+                assert_eq!(final_scores.len(), 3);
+                let best = final_scores.iter().map(|(_, _, s)| *s).max().unwrap();
+                let leaders: Vec<Uuid> = final_scores
+                    .iter()
+                    .filter(|(_, _, s)| *s == best)
+                    .map(|(id, _, _)| *id)
+                    .collect();
+                assert_eq!(winner_ids, leaders);
+
+                assert_eq!(scorecards.len(), 3);
+                for ((id, _, total), card) in final_scores.iter().zip(&scorecards) {
+                    assert!(card.is_complete());
+                    assert_eq!(card.grand_total(), *total);
+                    let seat = game.players.iter().position(|p| p.player_id == *id).unwrap();
+                    assert_eq!(card.grand_total(), game.scorecards[seat].grand_total());
+                }
+                assert_eq!(duration_secs, 10 * turns as u64);
+
+                assert_eq!(round_totals.len(), 3);
+                for ((_, _, total), totals) in final_scores.iter().zip(&round_totals) {
+                    assert_eq!(totals.len(), 13);
+                    assert!(totals.windows(2).all(|w| w[0] <= w[1]));
+                    assert_eq!(totals.last(), Some(total));
+                }
+            }
+            other => panic!("turn {}: unexpected {:?}", turn, other),
+        }
+    }
+    assert!(game.scorecards.iter().all(|s| s.is_complete()));
+}
+
+#[tokio::test]
+async fn test_rematches_keep_running_standings() {
+    let server = TestServer::start().await;
+    let mut game = TestGame::start(&server, &["alice", "bob"]).await;
+
+    let mut wins = 0;
+    let mut points = 0;
+    for played in 1..=2 {
+        let turns = 13 * game.players.len();
+        for _ in 1..turns {
+            game.play_turn().await;
+        }
+        let (final_scores, winner_ids) = match game.play_turn().await {
+            ServerMessage::GameOver { final_scores, winner_ids, .. } => (final_scores, winner_ids),
+            other => panic!("expected GameOver, got {:?}", other),
+        };
+        wins += winner_ids.len() as u16;
+        points += final_scores.iter().map(|&(_, _, score)| u32::from(score)).sum::<u32>();
+
+        // Everyone is back in the waiting room with the standings so far
+        for player in &mut game.players {
+            let room_state = expect!(player, ServerMessage::RoomUpdate { room_state } => room_state);
+            assert_eq!(room_state.state, RoomState::Finished);
+            let standings = room_state.standings;
+            assert_eq!(standings.len(), 2);
+            assert!(standings.iter().all(|s| s.games == played));
+            assert_eq!(standings.iter().map(|s| s.wins).sum::<u16>(), wins);
+            assert_eq!(standings.iter().map(|s| s.total).sum::<u32>(), points);
+            assert!(standings.windows(2).all(|w| (w[0].wins, w[0].total) >= (w[1].wins, w[1].total)));
+        }
+        if played == 1 {
+            game.rematch().await;
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_finished_games_are_archived() {
+    let data = DataDir::new();
+    let server = TestServer::start_persistent(test_config(), &data).await;
+    let mut game = TestGame::start(&server, &["alice", "bob"]).await;
+    for _ in 0..13 * game.players.len() {
+        game.play_turn().await;
+    }
+    for player in &mut game.players {
+        player.recv_until(|m| matches!(m, ServerMessage::GameOver { .. })).await;
+        expect!(player, ServerMessage::RoomUpdate { .. });
+    }
+
+    let alice = &mut game.players[0];
+    alice
+        .send(ClientMessage::GetRecentGames {
+            player: Some("alice".into()),
+        })
+        .await;
+    let games = expect!(alice, ServerMessage::RecentGames { games } => games);
+    assert_eq!(games.len(), 1);
+    let summary = &games[0];
+    let names: Vec<&str> = summary.players.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, ["alice", "bob"]);
+
+    alice
+        .send(ClientMessage::GetRecentGames {
+            player: Some("carol".into()),
+        })
+        .await;
+    expect!(alice, ServerMessage::RecentGames { games } if games.is_empty());
+
+    alice.send(ClientMessage::GetGame { game_id: summary.game_id }).await;
+    let archived = expect!(alice, ServerMessage::GameDetails { game } => game);
+    assert_eq!(archived.summary, *summary);
+    assert_eq!(archived.turns.len(), 26);
+    for (card, (_, total)) in archived.scorecards.iter().zip(&summary.players) {
+        assert!(card.is_complete());
+        assert_eq!(card.grand_total(), *total);
+    }
+
+    alice.send(ClientMessage::GetGame { game_id: Uuid::new_v4() }).await;
+    expect!(alice, ServerMessage::Error { code: ErrorCode::GameNotFound, .. });
+}
+
+#[tokio::test]
+async fn test_player_stats_tally_categories() {
+    let data = DataDir::new();
+    let server = TestServer::start_persistent(test_config(), &data).await;
+    let mut game = TestGame::start(&server, &["alice", "bob"]).await;
+    for _ in 0..13 * game.players.len() {
+        game.play_turn().await;
+    }
+    let (final_scores, scorecards) = match game.players[0]
+        .recv_until(|m| matches!(m, ServerMessage::GameOver { .. }))
+        .await
+    {
+        ServerMessage::GameOver {
+            final_scores,
+            scorecards,
+            ..
+        } => (final_scores, scorecards),
+        _ => unreachable!(),
+    };
+    let seat = final_scores.iter().position(|(_, name, _)| name == "alice").unwrap();
+    let card = &scorecards[seat];
+
+    let alice = &mut game.players[0];
+    expect!(alice, ServerMessage::RoomUpdate { .. });
+    alice
+        .send(ClientMessage::GetPlayerStats {
+            player: "alice".into(),
+        })
+        .await;
+    let categories = expect!(alice, ServerMessage::PlayerStats { player, categories, .. } if player == "alice" => categories);
+    assert_eq!(categories.len(), Category::ALL.len());
+    for (tally, category) in categories.iter().zip(Category::ALL) {
+        let score = card.score(category).unwrap();
+        assert_eq!(tally.category, category);
+        assert_eq!(tally.games(), 1);
+        assert_eq!(tally.zeroed, (score == 0) as u32);
+        assert_eq!(tally.points, score as u32);
+    }
+
+    alice
+        .send(ClientMessage::GetPlayerStats {
+            player: "carol".into(),
+        })
+        .await;
+    expect!(alice, ServerMessage::PlayerStats { categories, .. } if categories.iter().all(|t| t.games() == 0));
+}
+
+#[tokio::test]
+async fn test_personal_records_are_announced() {
+    let data = DataDir::new();
+    // alice has finished a game before, so any score beats her best
+    std::fs::create_dir_all(&data.0).unwrap();
+    std::fs::write(
+        data.0.join("ratings.json"),
+        r#"{"season":1,"season_started_at":0,"ratings":{},"archives":[],
+            "personal_bests":{"alice":{"games":1,"best_game":0,"best_category":null,"most_yahtzees":0}}}"#,
+    )
+    .unwrap();
+    let server = TestServer::start_persistent(test_config(), &data).await;
+    let mut game = TestGame::start(&server, &["alice", "bob"]).await;
+    for _ in 0..13 * game.players.len() {
+        game.play_turn().await;
+    }
+
+    let mut notices = Vec::new();
+    let final_scores = loop {
+        match game.players[0].recv().await {
+            ServerMessage::SystemMessage { message, .. } => notices.push(message),
+            ServerMessage::GameOver { final_scores, .. } => break final_scores,
+            other => panic!("unexpected message before GameOver: {:?}", other),
+        }
+    };
+    let (_, _, total) = final_scores.iter().find(|(_, name, _)| name == "alice").unwrap();
+    let record = notices
+        .iter()
+        .find(|m| m.starts_with("alice set a personal record: "))
+        .expect("alice's record is announced");
+    assert!(record.contains(&format!("best game ({})", total)), "{}", record);
+    // bob's first game only sets the bar, unless he rolled a Yahtzee
+    assert!(notices
+        .iter()
+        .filter(|m| m.starts_with("bob "))
+        .all(|m| m.starts_with("bob set a personal record: ") && m.contains("Yahtzee")));
+}
+
+#[tokio::test]
+async fn test_players_sharing_a_name_are_not_rated() {
+    let data = DataDir::new();
+    let server = TestServer::start_persistent(test_config(), &data).await;
+    let rules = GameConfig {
+        rounds: 1,
+        ..GameConfig::default()
+    };
+    let mut game = TestGame::start_with(&server, &["alice", "alice"], rules).await;
+    game.play_turn().await;
+    assert!(matches!(game.play_turn().await, ServerMessage::GameOver { .. }));
+
+    let player = &mut game.players[0];
+    player.send(ClientMessage::GetLeaderboard { season: None }).await;
+    let leaderboard = player.recv_until(|m| matches!(m, ServerMessage::Leaderboard { .. })).await;
+    assert!(matches!(leaderboard, ServerMessage::Leaderboard { standings, .. } if standings.is_empty()));
+    player.send(ClientMessage::GetRecentGames { player: None }).await;
+    let recent = player.recv_until(|m| matches!(m, ServerMessage::RecentGames { .. })).await;
+    assert!(matches!(recent, ServerMessage::RecentGames { games } if games.is_empty()));
+}
+
+/// A finished game for the archive, with `players`' names and totals.
+fn archived_game(players: &[(&str, u16)]) -> ArchivedGame {
+    ArchivedGame {
+        summary: GameSummary {
+            game_id: Uuid::new_v4(),
+            room_name: "archived".into(),
+            finished_at: 0,
+            duration_secs: 60,
+            players: players.iter().map(|&(name, total)| (name.to_string(), total)).collect(),
+            winners: vec![players[0].0.to_string()],
+        },
+        rules: GameConfig::default(),
+        player_ids: players.iter().map(|_| Uuid::new_v4()).collect(),
+        scorecards: players.iter().map(|_| Scorecard::new()).collect(),
+        turns: Vec::new(),
+    }
+}
+
+#[test]
+fn test_seasons_roll_over_and_carry_ratings_over() {
+    let data = DataDir::new();
+    let start = chrono::Utc::now().timestamp();
+    let mut persistence = Persistence::open(data.0.clone(), 1).unwrap();
+    let first = rating::rating_changes(&[(INITIAL_RATING, 250), (INITIAL_RATING, 150)]);
+    persistence.record_game(&[("alice".into(), 250), ("bob".into(), 150)], Some("alice"));
+
+    // Not due until the season has run its day
+    assert!(!persistence.roll_season_if_due(start + 60));
+    assert!(persistence.roll_season_if_due(start + 2 * 24 * 60 * 60));
+    assert_eq!(persistence.current_season(), 2);
+    assert!(persistence.standings(None).unwrap().standings.is_empty());
+
+    // The old season's standings are archived as they ended
+    let past = persistence.standings(Some(1)).unwrap();
+    assert_eq!(past.ended_at, Some(start + 2 * 24 * 60 * 60));
+    let names: Vec<&str> = past.standings.iter().map(|e| e.player_name.as_str()).collect();
+    assert_eq!(names, ["alice", "bob"]);
+    assert_eq!((past.standings[0].games_played, past.standings[0].wins), (1, 1));
+    assert_eq!(past.standings[0].rating, (INITIAL_RATING + first[0]).round() as i32);
+
+    // Half of each rating's distance from the start carries over
+    let carried = [
+        rating::soft_reset(INITIAL_RATING + first[0], 0.5),
+        rating::soft_reset(INITIAL_RATING + first[1], 0.5),
+    ];
+    let second = rating::rating_changes(&[(carried[0], 100), (carried[1], 200)]);
+    let changes = persistence.record_game(&[("alice".into(), 100), ("bob".into(), 200)], Some("bob"));
+    assert_eq!(changes[0].1, (carried[0] + second[0]).round() as i32);
+    assert_eq!(changes[1].1, (carried[1] + second[1]).round() as i32);
+    let current = persistence.standings(None).unwrap().standings;
+    assert!(current.iter().all(|e| e.games_played == 1));
+    assert_eq!(current.iter().find(|e| e.player_name == "bob").unwrap().wins, 1);
+    assert!(persistence.standings(Some(3)).is_none());
+}
+
+#[test]
+fn test_archive_keeps_the_latest_games() {
+    let data = DataDir::new();
+    let mut persistence = Persistence::open(data.0.clone(), 0).unwrap();
+    let games: Vec<ArchivedGame> = (0..501).map(|i| archived_game(&[("alice", i), ("bob", 0)])).collect();
+    let ids: Vec<Uuid> = games.iter().map(|g| g.summary.game_id).collect();
+    for game in games {
+        persistence.archive_game(game);
+    }
+
+    // The oldest game makes way for the newest
+    assert!(persistence.game(ids[0]).is_none());
+    assert!(persistence.game(ids[1]).is_some());
+    assert!(persistence.game(ids[500]).is_some());
+    let recent = persistence.recent_games(Some("alice"));
+    assert_eq!(recent.len(), 20);
+    assert_eq!(recent[0].game_id, ids[500]);
+    assert!(persistence.recent_games(Some("carol")).is_empty());
+
+    // Both files are written out by the time the store is let go
+    persistence.record_game(&[("alice".into(), 500), ("bob".into(), 0)], Some("alice"));
+    drop(persistence);
+    let reopened = Persistence::open(data.0.clone(), 0).unwrap();
+    assert!(reopened.game(ids[0]).is_none());
+    assert_eq!(reopened.recent_games(None)[0].game_id, ids[500]);
+    assert_eq!(reopened.standings(None).unwrap().standings[0].player_name, "alice");
+}
+
+#[tokio::test]
+async fn test_server_announces_itself_to_the_directory() {
+    let directory = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let config = ServerConfig {
+        directory: Some(DirectoryConfig {
+            url: format!("http://{}", directory.local_addr().unwrap()),
+            name: "Kitchen table".into(),
+            address: "yaht.example.org:9876".into(),
+            region: Some("eu-west".into()),
+            interval: Duration::from_millis(50),
+        }),
+        ..test_config()
+    };
+    let server = TestServer::start_with(config).await;
+
+    let listing = recv_heartbeat(&directory).await;
+    assert_eq!(listing.name, "Kitchen table");
+    assert_eq!(listing.address, "yaht.example.org:9876");
+    assert_eq!(listing.region.as_deref(), Some("eu-west"));
+    assert_eq!(listing.players, 0);
+    assert_eq!(listing.protocol, protocol::PROTOCOL_VERSION);
+
+    let _alice = server.connect("alice").await;
+    let mut players = 0;
+    for _ in 0..10 {
+        players = recv_heartbeat(&directory).await.players;
+        if players == 1 {
+            break;
+        }
+    }
+    assert_eq!(players, 1, "later heartbeats count connected players");
+}
+
+#[tokio::test]
+async fn test_monitor_flags_instant_play() {
+    let monitor = PlayMonitor::new(MonitorThresholds {
+        min_actions: 8,
+        min_choices: 4,
+        agreement_share: 0.0,
+        ..MonitorThresholds::default()
+    });
+    // A virtual clock that never moves makes every action instant
+    let config = ServerConfig {
+        clock: Clock::virtual_at(1_760_000_000),
+        monitor: Some(monitor.clone()),
+        ..test_config()
+    };
+    let server = TestServer::start_with(config).await;
+    let mut game = TestGame::start(&server, &["alice", "bob"]).await;
+
+    // Out of turn actions are refused, and not counted
+    let idle = 1 - game.current;
+    game.players[idle].send(ClientMessage::RollDice).await;
+    expect!(game.players[idle], ServerMessage::Error { code: ErrorCode::NotYourTurn, .. });
+
+    for _ in 0..4 * game.players.len() {
+        game.play_turn().await;
+    }
+    for name in ["alice", "bob"] {
+        let record = monitor.record(name).unwrap();
+        // Each turn is one roll and one score
+        assert_eq!(record.actions, 8, "{}", name);
+        assert_eq!(record.instant, 8);
+        assert_eq!(record.choices, 4);
+        assert_eq!(record.flags, [Suspicion::InstantDecisions, Suspicion::OptimalAgreement]);
+    }
+}
+
+#[tokio::test]
+async fn test_turns_are_timed() {
+    let data = DataDir::new();
+    let clock = Clock::virtual_at(1_760_000_000);
+    let config = ServerConfig {
+        clock: clock.clone(),
+        ..test_config()
+    };
+    let server = TestServer::start_persistent(config, &data).await;
+    let mut game = TestGame::start(&server, &["alice", "bob"]).await;
+    let think = |name: &str| if name == "alice" { 3 } else { 7 };
+    for _ in 0..13 * game.players.len() {
+        clock.advance(think(&game.players[game.current].name));
+        game.play_turn().await;
+    }
+
+    let (final_scores, average_turn_ms) = match game.players[0]
+        .recv_until(|m| matches!(m, ServerMessage::GameOver { .. }))
+        .await
+    {
+        ServerMessage::GameOver {
+            final_scores,
+            average_turn_ms,
+            ..
+        } => (final_scores, average_turn_ms),
+        _ => unreachable!(),
+    };
+    for ((_, name, _), average) in final_scores.iter().zip(&average_turn_ms) {
+        assert_eq!(*average, Some(think(name) as u64 * 1000), "{}", name);
+    }
+
+    let bob = &mut game.players[1];
+    bob.send(ClientMessage::GetPlayerStats { player: "bob".into() }).await;
+    let times = match bob.recv_until(|m| matches!(m, ServerMessage::PlayerStats { .. })).await {
+        ServerMessage::PlayerStats { turn_times, .. } => turn_times,
+        _ => unreachable!(),
+    };
+    assert_eq!(times.turns, 13);
+    assert_eq!(times.average_ms(), Some(7_000));
+    assert_eq!(times.slowest_ms, 7_000);
+}
+
+#[tokio::test]
+async fn test_final_round_is_announced() {
+    let server = TestServer::start().await;
+    let mut game = TestGame::start(&server, &["alice", "bob"]).await;
+    for _ in 0..12 * game.players.len() - 1 {
+        game.play_turn().await;
+    }
+
+    let last = game.current;
+    game.players[last].send(ClientMessage::RollDice).await;
+    let dice = expect!(game.players[last], ServerMessage::DiceRolled { dice, .. } => dice);
+    let category = game.scorecards[last].scorable_categories(&dice.values())[0];
+    game.players[last].send(ClientMessage::ScoreCategory { category }).await;
+    for player in &mut game.players {
+        player.recv_until(|m| matches!(m, ServerMessage::TurnEnded { .. })).await;
+        expect!(player, ServerMessage::RoundStandings { round: 12, .. });
+        expect!(
+            player,
+            ServerMessage::SystemMessage { milestone: Some(Milestone::FinalRound), .. }
+        );
+        expect!(player, ServerMessage::TurnStarted { turn_number: 13, .. });
+    }
+}
+
+#[tokio::test]
+async fn test_rounds_end_with_standings() {
+    let server = TestServer::start().await;
+    let mut game = TestGame::start(&server, &["alice", "bob"]).await;
+    let first = game.current;
+    game.play_turn().await;
+
+    let last = game.current;
+    game.players[last].send(ClientMessage::RollDice).await;
+    let dice = expect!(game.players[last], ServerMessage::DiceRolled { dice, .. } => dice);
+    game.players[last]
+        .send(ClientMessage::ScoreCategory { category: Category::Chance })
+        .await;
+    let mut expected = [0; 2];
+    expected[first] = game.scorecards[first].grand_total();
+    expected[last] = dice.values().iter().map(|&v| v as u16).sum();
+    let best = expected.into_iter().max();
+    for player in &mut game.players {
+        player.recv_until(|m| matches!(m, ServerMessage::TurnEnded { .. })).await;
+        let (totals, leader_ids) = expect!(
+            player,
+            ServerMessage::RoundStandings { round: 1, totals, leader_ids } => (totals, leader_ids)
+        );
+        let names: Vec<&str> = totals.iter().map(|(_, name, _)| name.as_str()).collect();
+        assert_eq!(names, ["alice", "bob"]);
+        let scores: Vec<u16> = totals.iter().map(|&(_, _, total)| total).collect();
+        assert_eq!(scores, expected);
+        let leaders: Vec<Uuid> = totals
+            .iter()
+            .filter(|&&(_, _, total)| Some(total) == best)
+            .map(|&(id, _, _)| id)
+            .collect();
+        assert_eq!(leader_ids, leaders);
+        expect!(player, ServerMessage::TurnStarted { turn_number: 2, .. });
+    }
+}
+
+#[tokio::test]
+async fn test_out_of_turn_action_is_rejected_privately() {
+    let server = TestServer::start().await;
+    let mut game = TestGame::start(&server, &["alice", "bob"]).await;
+    let waiting = 1 - game.current;
+
+    game.players[waiting].send(ClientMessage::RollDice).await;
+    expect!(
+        game.players[waiting],
+        ServerMessage::Error {
+            code: ErrorCode::NotYourTurn,
+            ..
+        }
+    );
+    game.players[game.current].expect_quiet().await;
+}
+
+#[tokio::test]
+async fn test_used_category_is_rejected_with_its_own_code() {
+    let server = TestServer::start().await;
+    let mut game = TestGame::start(&server, &["alice", "bob"]).await;
+    game.play_turn().await;
+    game.play_turn().await;
+
+    let current = game.current;
+    let (category, _) = game.scorecards[current].scores().next().unwrap();
+    game.players[current].send(ClientMessage::RollDice).await;
+    for player in &mut game.players {
+        expect!(player, ServerMessage::DiceRolled { .. });
+    }
+    game.players[current]
+        .send(ClientMessage::ScoreCategory { category })
+        .await;
+    expect!(
+        game.players[current],
+        ServerMessage::Error {
+            code: ErrorCode::CategoryAlreadyScored,
+            ..
+        }
+    );
+    game.players[1 - current].expect_quiet().await;
+}
+
+#[tokio::test]
+async fn test_hold_validation() {
+    let server = TestServer::start().await;
+    let mut game = TestGame::start(&server, &["alice", "bob"]).await;
+    let current = game.current;
+
+    let hold = ClientMessage::HoldDice {
+        held: [true, false, false, false, false],
+    };
+    game.players[current].send(hold.clone()).await;
+    expect!(
+        game.players[current],
+        ServerMessage::Error {
+            code: ErrorCode::InvalidHold,
+            ..
+        }
+    );
+
+    game.players[current].send(ClientMessage::RollDice).await;
+    game.players[current].send(hold.clone()).await;
+    for player in &mut game.players {
+        expect!(player, ServerMessage::DiceRolled { .. });
+        expect!(player, ServerMessage::DiceHeld { dice } if dice.dice[0].held);
+    }
+
+    // Repeating the same hold is rejected instead of broadcast again
+    game.players[current].send(hold).await;
+    expect!(
+        game.players[current],
+        ServerMessage::Error {
+            code: ErrorCode::InvalidHold,
+            ..
+        }
+    );
+    game.players[1 - current].expect_quiet().await;
+}
+
+#[tokio::test]
+async fn test_disconnect_and_reconnect() {
+    let clock = Clock::virtual_at(1_700_000_000);
+    let server = TestServer::start_with(ServerConfig {
+        clock: clock.clone(),
+        ..test_config()
+    })
+    .await;
+    let mut game = TestGame::start(&server, &["alice", "bob", "carol"]).await;
+    game.play_turn().await;
+
+    let bob = game.players.remove(1);
+    let old_id = bob.player_id;
+    drop(bob);
+    for player in &mut game.players {
+        expect!(player, ServerMessage::SystemMessage { message, .. } if message.contains("lost connection"));
+    }
+
+    // The seat is held for a while, then given up
+    clock.advance(61);
+    for player in &mut game.players {
+        expect!(player, ServerMessage::PlayerLeft { player_id, .. } if player_id == old_id);
+    }
+
+    // Reconnecting without a token gets a fresh session; the game in progress is closed to new players
+    let mut bob = server.connect("bob").await;
+    assert_ne!(bob.player_id, old_id);
+
+    bob.send(ClientMessage::ListRooms).await;
+    let rooms = expect!(bob, ServerMessage::RoomList { rooms } => rooms);
+    assert_eq!(rooms.len(), 1);
+    assert_eq!(rooms[0].player_count, 2);
+    assert_eq!(rooms[0].state, RoomInfoState::InProgress);
+
+    bob.send(ClientMessage::JoinRoom {
+        room_id: game.room_id,
+        password: None,
+        spectate_if_started: false,
+    })
+    .await;
+    expect!(
+        bob,
+        ServerMessage::Error {
+            code: ErrorCode::GameAlreadyStarted,
+            ..
+        }
+    );
+
+    // ...but can still watch, catching up on what was missed
+    bob.send(ClientMessage::SpectateRoom {
+        room_id: game.room_id,
+        catch_up: true,
+    })
+    .await;
+    expect!(bob, ServerMessage::RoomJoined { .. });
+    let count = expect!(bob, ServerMessage::ReplayStarted { event_count } => event_count);
+    for _ in 0..count {
+        expect!(bob, ServerMessage::ReplayEvent { .. });
+    }
+    expect!(bob, ServerMessage::ReplayFinished);
+    expect!(bob, ServerMessage::GameState { .. });
+
+    for player in &mut game.players {
+        player
+            .recv_until(|msg| matches!(msg, ServerMessage::SpectatorJoined { .. }))
+            .await;
+    }
+}
+
+#[tokio::test]
+async fn test_catching_up_late_in_a_game() {
+    let server = TestServer::start().await;
+    let mut game = TestGame::start(&server, &["alice", "bob"]).await;
+    for _ in 0..20 {
+        game.play_turn().await;
+    }
+
+    // The replay holds more than a connection's queue
+    let mut carol = server.connect("carol").await;
+    carol
+        .send(ClientMessage::SpectateRoom {
+            room_id: game.room_id,
+            catch_up: true,
+        })
+        .await;
+    expect!(carol, ServerMessage::RoomJoined { .. });
+    let count = expect!(carol, ServerMessage::ReplayStarted { event_count } => event_count);
+    assert!(count > 64, "only {} events", count);
+    for _ in 0..count {
+        expect!(carol, ServerMessage::ReplayEvent { .. });
+    }
+    expect!(carol, ServerMessage::ReplayFinished);
+    expect!(carol, ServerMessage::GameState { .. });
+
+    // Still connected, and following the game
+    for player in &mut game.players {
+        expect!(player, ServerMessage::SpectatorJoined { .. });
+    }
+    game.play_turn().await;
+    carol.recv_until(|m| matches!(m, ServerMessage::TurnStarted { .. })).await;
+}
+
+#[tokio::test]
+async fn test_dropped_player_resumes_their_seat() {
+    let server = TestServer::start().await;
+    let mut game = TestGame::start(&server, &["alice", "bob"]).await;
+
+    // The current player's connection drops mid-turn
+    let index = game.current;
+    let dropped = game.players.remove(index);
+    let (name, old_id, token) = (dropped.name.clone(), dropped.player_id, dropped.resume_token.unwrap());
+    drop(dropped);
+    let other = &mut game.players[0];
+    expect!(other, ServerMessage::SystemMessage { message, .. } if message.contains("lost connection"));
+
+    // Their token brings them back in the same seat, with the game as it stands
+    let (mut back, resumed) = support::TestClient::resume(server.addr, &name, token).await;
+    assert!(resumed);
+    assert_eq!(back.player_id, old_id);
+    assert_ne!(back.resume_token, Some(token));
+    let state = expect!(back, ServerMessage::GameState { game_state } => game_state);
+    assert_eq!(state.players[state.current_player_index].id, old_id);
+    assert!(state.players.iter().all(|p| p.connected));
+    let other = &mut game.players[0];
+    expect!(other, ServerMessage::SystemMessage { message, .. } if message.contains("reconnected"));
+
+    // ...and carry on with their turn
+    game.players.insert(index, back);
+    assert!(matches!(game.play_turn().await, ServerMessage::TurnStarted { .. }));
+
+    // A token only resumes once
+    let (again, resumed) = support::TestClient::resume(server.addr, &name, token).await;
+    assert!(!resumed);
+    assert_ne!(again.player_id, old_id);
+}
+
+#[tokio::test]
+async fn test_second_connection_takes_over_the_identity() {
+    let server = TestServer::start().await;
+    let identity = Uuid::new_v4();
+    let (mut alice, _) = support::TestClient::connect_as(server.addr, "alice", identity).await;
+    let mut bob = server.connect("bob").await;
+    let room_id = alice.create_room("table", 2).await;
+    bob.join_room(room_id).await;
+    expect!(alice, ServerMessage::PlayerJoined { .. });
+    alice.send(ClientMessage::StartGame).await;
+    expect!(alice, ServerMessage::GameStarted { .. });
+    expect!(bob, ServerMessage::GameStarted { .. });
+
+    // The same identity connects again, e.g. from another terminal: the
+    // new connection gets the seat and the old one is told and closed
+    let (mut again, resumed) = support::TestClient::connect_as(server.addr, "alice", identity).await;
+    assert!(resumed);
+    assert_eq!(again.player_id, alice.player_id);
+    let state = expect!(again, ServerMessage::GameState { game_state } => game_state);
+    assert!(state.players.iter().any(|p| p.id == alice.player_id));
+
+    alice
+        .recv_until(|m| matches!(m, ServerMessage::Error { code: ErrorCode::SessionTaken, .. }))
+        .await;
+    alice.expect_closed().await;
+    bob.recv_until(|m| matches!(m, ServerMessage::SystemMessage { message, .. } if message.contains("reconnected")))
+        .await;
+
+    // The newest connection always wins
+    let (mut third, resumed) = support::TestClient::connect_as(server.addr, "alice", identity).await;
+    assert!(resumed);
+    assert_eq!(third.player_id, alice.player_id);
+    expect!(third, ServerMessage::GameState { .. });
+    again
+        .recv_until(|m| matches!(m, ServerMessage::Error { code: ErrorCode::SessionTaken, .. }))
+        .await;
+    again.expect_closed().await;
+
+    // A different identity takes nothing over
+    let (_, resumed) = support::TestClient::connect_as(server.addr, "alice", Uuid::new_v4()).await;
+    assert!(!resumed);
+    third.send(ClientMessage::Ping).await;
+    third.recv_until(|m| matches!(m, ServerMessage::Pong)).await;
+}
+
+#[tokio::test]
+async fn test_returning_host_gets_the_room_back() {
+    let server = TestServer::start().await;
+    let mut game = TestGame::start(&server, &["alice", "bob"]).await;
+    let alice = game.players.remove(0);
+    let token = alice.resume_token.unwrap();
+    drop(alice);
+
+    let bob = &mut game.players[0];
+    expect!(
+        bob,
+        ServerMessage::SystemMessage { message, .. } if message.ends_with("bob is the host until they return")
+    );
+
+    let (mut alice, resumed) = support::TestClient::resume(server.addr, "alice", token).await;
+    assert!(resumed);
+    expect!(alice, ServerMessage::GameState { .. });
+    let bob = &mut game.players[0];
+    expect!(
+        bob,
+        ServerMessage::SystemMessage { message, .. } if message == "alice reconnected and is the host again"
+    );
+    bob.send(ClientMessage::Kick { player_id: alice.player_id }).await;
+    expect!(bob, ServerMessage::Error { code: ErrorCode::NotHost, .. });
+}
+
+#[tokio::test]
+async fn test_stand_in_host_keeps_the_room_after_acting() {
+    let server = TestServer::start().await;
+    let mut game = TestGame::start(&server, &["alice", "bob", "carol"]).await;
+    let alice = game.players.remove(0);
+    let token = alice.resume_token.unwrap();
+    drop(alice);
+    for player in &mut game.players {
+        expect!(player, ServerMessage::SystemMessage { message, .. } if message.contains("lost connection"));
+    }
+
+    // The stand-in uses the host's powers, so the room stays theirs
+    let carol = game.players.pop().unwrap();
+    let bob = &mut game.players[0];
+    bob.send(ClientMessage::Kick { player_id: carol.player_id }).await;
+    bob.recv_until(|m| matches!(m, ServerMessage::PlayerForfeited { .. })).await;
+
+    let (mut alice, resumed) = support::TestClient::resume(server.addr, "alice", token).await;
+    assert!(resumed);
+    expect!(alice, ServerMessage::GameState { .. });
+    expect!(alice, ServerMessage::SystemMessage { message, .. } if message == "bob has taken over as host");
+    let welcome_back = |m: &ServerMessage| {
+        matches!(m, ServerMessage::SystemMessage { message, .. } if message == "alice is back; you remain the host")
+    };
+    bob.recv_until(welcome_back).await;
+    expect!(bob, ServerMessage::SystemMessage { message, .. } if message == "alice reconnected");
+
+    alice.send(ClientMessage::Kick { player_id: bob.player_id }).await;
+    expect!(alice, ServerMessage::Error { code: ErrorCode::NotHost, .. });
+}
+
+#[tokio::test]
+async fn test_tracked_actions_are_acknowledged_once_applied() {
+    let server = TestServer::start().await;
+    let mut game = TestGame::start(&server, &["alice", "bob"]).await;
+    let current = game.current;
+    let roll = |request_id| ClientMessage::Tracked {
+        request_id,
+        action: Box::new(ClientMessage::RollDice),
+    };
+
+    game.players[current].send(roll(1)).await;
+    for player in &mut game.players {
+        expect!(player, ServerMessage::DiceRolled { rolls_remaining: 2, .. });
+    }
+    expect!(game.players[current], ServerMessage::ActionAck { request_id: 1 });
+
+    // A resend after a reconnect is acknowledged again but not rolled again
+    game.players[current].send(roll(1)).await;
+    expect!(game.players[current], ServerMessage::ActionAck { request_id: 1 });
+    for player in &mut game.players {
+        player.expect_quiet().await;
+    }
+
+    game.players[current].send(roll(2)).await;
+    for player in &mut game.players {
+        expect!(player, ServerMessage::DiceRolled { rolls_remaining: 1, .. });
+    }
+    expect!(game.players[current], ServerMessage::ActionAck { request_id: 2 });
+
+    // Only game and chat messages can be tracked
+    game.players[current]
+        .send(ClientMessage::Tracked {
+            request_id: 3,
+            action: Box::new(ClientMessage::ListRooms),
+        })
+        .await;
+    expect!(
+        game.players[current],
+        ServerMessage::Error {
+            code: ErrorCode::InvalidAction,
+            ..
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_handshake_negotiates_the_protocol() {
+    let server = TestServer::start().await;
+    let hello = |protocol| ClientMessage::Hello {
+        player_name: "alice".into(),
+        version: "9.9.9".into(),
+        identity: None,
+        resume_token: None,
+        protocol,
+    };
+
+    // A newer client is talked to in the server's revision
+    let mut newer = support::TestClient::open(server.addr, "newer").await;
+    newer.send(hello(protocol::PROTOCOL_VERSION + 1)).await;
+    let agreed = expect!(newer, ServerMessage::Welcome { protocol, .. } => protocol);
+    assert_eq!(agreed, protocol::PROTOCOL_VERSION);
+
+    // One older than the server accepts is told which versions it needs
+    let mut older = support::TestClient::open(server.addr, "older").await;
+    older.send(hello(protocol::MIN_PROTOCOL_VERSION - 1)).await;
+    let mismatch = expect!(
+        older,
+        ServerMessage::HandshakeError { version_mismatch: Some(m), .. } => m
+    );
+    assert_eq!(mismatch.server_protocol, protocol::PROTOCOL_VERSION);
+    assert_eq!(mismatch.min_protocol, protocol::MIN_PROTOCOL_VERSION);
+    assert_eq!(mismatch.server_version, env!("CARGO_PKG_VERSION"));
+    older.expect_closed().await;
+}
+
+#[tokio::test]
+async fn test_malformed_frames_are_reported_then_disconnected() {
+    let server = TestServer::start().await;
+    let mut alice = server.connect("alice").await;
+
+    alice.send_raw(&b"not json"[..]).await;
+    expect!(
+        alice,
+        ServerMessage::Error {
+            code: ErrorCode::MalformedMessage,
+            ..
+        }
+    );
+
+    // Still connected after a single strike
+    alice.send(ClientMessage::Ping).await;
+    expect!(alice, ServerMessage::Pong);
+
+    for _ in 0..4 {
+        alice.send_raw(&br#"{"RollDice":{"bogus":1}}"#[..]).await;
+        expect!(
+            alice,
+            ServerMessage::Error {
+                code: ErrorCode::MalformedMessage,
+                ..
+            }
+        );
+    }
+    alice.expect_closed().await;
+}
+
+#[tokio::test]
+async fn test_spectators_cannot_act() {
+    let server = TestServer::start().await;
+    let mut game = TestGame::start(&server, &["alice", "bob"]).await;
+    let mut eve = server.connect("eve").await;
+
+    eve.send(ClientMessage::SpectateRoom {
+        room_id: game.room_id,
+        catch_up: false,
+    })
+    .await;
+    expect!(eve, ServerMessage::RoomJoined { .. });
+    expect!(eve, ServerMessage::GameState { .. });
+    for player in &mut game.players {
+        expect!(player, ServerMessage::SpectatorJoined { .. });
+    }
+
+    let actions = [
+        ClientMessage::StartGame,
+        ClientMessage::RollDice,
+        ClientMessage::HoldDice { held: [true; 5] },
+        ClientMessage::ScoreCategory {
+            category: yaht_common::scoring::Category::Chance,
+        },
+    ];
+    for action in actions {
+        eve.send(action).await;
+        expect!(
+            eve,
+            ServerMessage::Error {
+                code: ErrorCode::InvalidAction,
+                ..
+            }
+        );
+    }
+    for player in &mut game.players {
+        player.expect_quiet().await;
+    }
+
+    // Chat is still allowed
+    eve.send(ClientMessage::Chat {
+        message: "good luck".into(),
+    })
+    .await;
+    for player in &mut game.players {
+        expect!(player, ServerMessage::ChatMessage { sender_id, .. } if sender_id == eve.player_id);
+    }
+}
+
+#[tokio::test]
+async fn test_oversized_frame_is_reported() {
+    let server = TestServer::start_with(ServerConfig {
+        max_frame_length: 256,
+        ..test_config()
+    })
+    .await;
+    let mut alice = server.connect("alice").await;
+
+    let chat = format!(r#"{{"Chat":{{"message":"{}"}}}}"#, "x".repeat(300));
+    alice.send_raw(chat).await;
+    expect!(
+        alice,
+        ServerMessage::Error {
+            code: ErrorCode::FrameTooLarge,
+            ..
+        }
+    );
+    alice.expect_closed().await;
+}
+
+#[tokio::test]
+async fn test_gameplay_flood_is_rate_limited() {
+    let mut config = test_config();
+    config.rate_limits.gameplay = RateLimit::new(3, 0.001);
+    let server = TestServer::start_with(config).await;
+    let mut game = TestGame::start(&server, &["alice", "bob"]).await;
+    let current = game.current;
+
+    // The host has spent one token starting the game
+    let player = &mut game.players[current];
+    let budget = if current == 0 { 2 } else { 3 };
+    for _ in 0..budget {
+        player.send(ClientMessage::RollDice).await;
+    }
+    player.send(ClientMessage::RollDice).await;
+
+    // The error comes straight from the connection, so it may overtake rolls
+    // still on their way through the room
+    let (mut rolled, mut limited) = (0, 0);
+    for _ in 0..=budget {
+        match player.recv().await {
+            ServerMessage::DiceRolled { .. } => rolled += 1,
+            ServerMessage::Error {
+                code: ErrorCode::RateLimited,
+                ..
+            } => limited += 1,
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+    assert_eq!((rolled, limited), (budget, 1));
+
+    // Other message classes have their own budget
+    player.send(ClientMessage::ListRooms).await;
+    expect!(player, ServerMessage::RoomList { .. });
+}
+
+/// Play a few turns and a chat message on a seeded server with a virtual
+/// clock, returning everything the host received.
+async fn seeded_transcript(seed: u64) -> Vec<String> {
+    const START: i64 = 1_700_000_000;
+    let clock = Clock::virtual_at(START);
+    let server = TestServer::start_with(ServerConfig {
+        seed: Some(seed),
+        clock: clock.clone(),
+        ..test_config()
+    })
+    .await;
+    let mut game = TestGame::start(&server, &["alice", "bob"]).await;
+    for _ in 0..6 {
+        game.play_turn().await;
+    }
+
+    clock.advance(90);
+    game.players[0]
+        .send(ClientMessage::Chat {
+            message: "gg".into(),
+        })
+        .await;
+    for player in &mut game.players {
+        let timestamp = expect!(player, ServerMessage::ChatMessage { timestamp, .. } => timestamp);
+        assert_eq!(timestamp, START + 90);
+    }
+
+    game.players.swap_remove(0).transcript
+}
+
+#[tokio::test]
+async fn test_seeded_server_is_deterministic() {
+    let first = seeded_transcript(7).await;
+    assert_eq!(first, seeded_transcript(7).await);
+    assert_ne!(first, seeded_transcript(8).await);
+}
+
+#[tokio::test]
+async fn test_room_rules_apply_to_its_games() {
+    let server = TestServer::start().await;
+    let mut alice = server.connect("alice").await;
+    let mut bob = server.connect("bob").await;
+
+    let rules = GameConfig {
+        rolls_per_turn: 1,
+        sudden_death: true,
+        ..GameConfig::default()
+    };
+    let room_id = alice.create_room_with("one roll", 2, rules).await;
+    bob.send(ClientMessage::JoinRoom {
+        room_id,
+        password: None,
+        spectate_if_started: false,
+    })
+    .await;
+    let room_state = expect!(bob, ServerMessage::RoomJoined { room_state, .. } => room_state);
+    assert_eq!(room_state.rules, rules);
+    expect!(alice, ServerMessage::PlayerJoined { .. });
+
+    alice.send(ClientMessage::StartGame).await;
+    let game_state = expect!(alice, ServerMessage::GameStarted { game_state } => game_state);
+    expect!(bob, ServerMessage::GameStarted { .. });
+    assert_eq!(game_state.rules, rules);
+
+    let first = game_state.players[game_state.current_player_index].id;
+    let (current, waiting) = if first == alice.player_id {
+        (&mut alice, &mut bob)
+    } else {
+        (&mut bob, &mut alice)
+    };
+    current.send(ClientMessage::RollDice).await;
+    expect!(current, ServerMessage::DiceRolled { rolls_remaining: 0, .. });
+    expect!(waiting, ServerMessage::DiceRolled { rolls_remaining: 0, .. });
+
+    current.send(ClientMessage::RollDice).await;
+    expect!(current, ServerMessage::Error { .. });
+}
+
+#[tokio::test]
+async fn test_short_game_under_custom_scoring() {
+    let server = TestServer::start().await;
+    let rules = GameConfig {
+        rounds: 2,
+        scoring: ScoringRules {
+            upper_bonus_threshold: 0,
+            upper_bonus: 7,
+            ..ScoringRules::default()
+        },
+        ..GameConfig::default()
+    };
+    let mut game = TestGame::start_with(&server, &["alice", "bob"], rules).await;
+
+    for turn in 1..=4 {
+        match game.play_turn().await {
+            ServerMessage::TurnStarted { .. } if turn < 4 => {}
+            ServerMessage::GameOver {
+                final_scores,
+                scorecards,
+                ..
+            } if turn == 4 => {
+                for ((id, _, total), card) in final_scores.iter().zip(&scorecards) {
+                    assert_eq!(card.rules, rules.scoring);
+                    assert_eq!(card.available_categories().len(), 11);
+                    let seat = game.players.iter().position(|p| p.player_id == *id).unwrap();
+                    assert_eq!(*total, game.scorecards[seat].grand_total());
+                    assert_eq!(*total, card.upper_subtotal() + 7 + card.lower_total());
+                }
+            }
+            other => panic!("turn {}: unexpected {:?}", turn, other),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_extreme_bonuses_are_clamped() {
+    let server = TestServer::start().await;
+    let rules = GameConfig {
+        rounds: 1,
+        scoring: ScoringRules {
+            upper_bonus_threshold: u16::MAX,
+            upper_bonus: u16::MAX,
+            yahtzee_bonus: u16::MAX,
+            ..ScoringRules::default()
+        },
+        ..GameConfig::default()
+    };
+    let mut game = TestGame::start_with(&server, &["alice", "bob"], rules).await;
+    let clamped = ScoringRules {
+        upper_bonus_threshold: 105,
+        upper_bonus: 1000,
+        yahtzee_bonus: 1000,
+        ..ScoringRules::default()
+    };
+    for card in &mut game.scorecards {
+        card.rules = clamped;
+    }
+
+    for turn in 1..=2 {
+        match game.play_turn().await {
+            ServerMessage::TurnStarted { .. } if turn < 2 => {}
+            ServerMessage::GameOver {
+                final_scores,
+                scorecards,
+                ..
+            } if turn == 2 => {
+                for ((id, _, total), card) in final_scores.iter().zip(&scorecards) {
+                    assert_eq!(card.rules, clamped);
+                    let seat = game.players.iter().position(|p| p.player_id == *id).unwrap();
+                    assert_eq!(*total, game.scorecards[seat].grand_total());
+                }
+            }
+            other => panic!("turn {}: unexpected {:?}", turn, other),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_leaving_mid_game_forfeits() {
+    let server = TestServer::start().await;
+    let mut game = TestGame::start(&server, &["alice", "bob", "carol"]).await;
+    let mut host = game.players[0].player_id;
+
+    // The current player walks out; their turn passes on
+    let mut leaver = game.players.remove(game.current);
+    game.scorecards.remove(game.current);
+    let leaver_id = leaver.player_id;
+    leaver.send(ClientMessage::LeaveRoom).await;
+
+    let mut next = None;
+    for player in &mut game.players {
+        expect!(player, ServerMessage::PlayerLeft { player_id, .. } if player_id == leaver_id);
+        if leaver_id == host {
+            expect!(player, ServerMessage::SystemMessage { message, .. } if message.ends_with("is now the host"));
+        }
+        expect!(player, ServerMessage::PlayerForfeited { player_id, .. } if player_id == leaver_id);
+        expect!(player, ServerMessage::TurnEnded { player_id } if player_id == leaver_id);
+        next = Some(expect!(player, ServerMessage::TurnStarted { player_id, .. } => player_id));
+    }
+    game.current = game
+        .players
+        .iter()
+        .position(|p| Some(p.player_id) == next)
+        .unwrap();
+    if leaver_id == host {
+        host = game.players[0].player_id;
+    }
+    assert!(matches!(game.play_turn().await, ServerMessage::TurnStarted { .. }));
+
+    // Someone leaves while waiting for their turn, leaving one player
+    let waiting = 1 - game.current;
+    let mut quitter = game.players.remove(waiting);
+    let quitter_id = quitter.player_id;
+    quitter.send(ClientMessage::LeaveRoom).await;
+
+    let last = &mut game.players[0];
+    expect!(last, ServerMessage::PlayerLeft { player_id, .. } if player_id == quitter_id);
+    if quitter_id == host {
+        expect!(last, ServerMessage::SystemMessage { message, .. } if message.ends_with("is now the host"));
+    }
+    expect!(last, ServerMessage::PlayerForfeited { player_id, .. } if player_id == quitter_id);
+    let winner_ids = expect!(last, ServerMessage::GameOver { winner_ids, .. } => winner_ids);
+    assert_eq!(winner_ids, vec![last.player_id]);
+}
+
+#[tokio::test]
+async fn test_finished_rooms_stay_open_for_a_cooldown() {
+    let clock = Clock::virtual_at(1_700_000_000);
+    let server = TestServer::start_with(ServerConfig {
+        clock: clock.clone(),
+        finished_room_cooldown: Duration::from_secs(120),
+        ..test_config()
+    })
+    .await;
+    let mut game = TestGame::start(&server, &["alice", "bob"]).await;
+    let room_id = game.room_id;
+    let mut bob = game.players.pop().unwrap();
+    let mut alice = game.players.pop().unwrap();
+    bob.send(ClientMessage::LeaveRoom).await;
+    expect!(bob, ServerMessage::RoomLeft);
+    alice.recv_until(|m| matches!(m, ServerMessage::GameOver { .. })).await;
+    alice.send(ClientMessage::LeaveRoom).await;
+    alice.recv_until(|m| matches!(m, ServerMessage::RoomLeft)).await;
+
+    // Empty, but still listed, and anyone can come back in
+    let mut carol = server.connect("carol").await;
+    carol.send(ClientMessage::ListRooms).await;
+    let rooms = expect!(carol, ServerMessage::RoomList { rooms } => rooms);
+    assert_eq!(rooms.len(), 1);
+    assert_eq!(rooms[0].state, RoomInfoState::Finished);
+    assert_eq!(rooms[0].player_count, 0);
+    carol
+        .send(ClientMessage::JoinRoom {
+            room_id,
+            password: None,
+            spectate_if_started: false,
+        })
+        .await;
+    let room_state = expect!(carol, ServerMessage::RoomJoined { room_state, .. } => room_state);
+    assert_eq!(room_state.host_id, carol.player_id);
+    carol.send(ClientMessage::LeaveRoom).await;
+    expect!(carol, ServerMessage::RoomLeft);
+
+    // Closed once the cooldown is over
+    clock.advance(121);
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+    carol.send(ClientMessage::ListRooms).await;
+    expect!(carol, ServerMessage::RoomList { rooms } if rooms.is_empty());
+}
+
+#[tokio::test]
+async fn test_event_stream_reports_games_anonymously() {
+    let config = test_config();
+    let mut observer = EventObserver::connect(&config.events).await;
+    let server = TestServer::start_with(config).await;
+    let mut game = TestGame::start(&server, &["alice", "bob"]).await;
+
+    let event = |json: &str| serde_json::from_str::<serde_json::Value>(json).unwrap();
+    let created = event(&observer.next().await);
+    assert_eq!(created["event"], "room_created");
+    assert_eq!(created["room_id"], game.room_id.to_string());
+    let started = event(&observer.next().await);
+    assert_eq!(started["event"], "game_started");
+    assert_eq!(started["players"], 2);
+
+    let mut raw = Vec::new();
+    for _ in 0..26 {
+        let seat = game.current;
+        game.play_turn().await;
+        let json = observer.next().await;
+        let scored = event(&json);
+        assert_eq!(scored["event"], "turn_scored");
+        assert_eq!(scored["seat"], seat);
+        let category = serde_json::from_value(scored["category"].clone()).unwrap();
+        assert_eq!(scored["score"], game.scorecards[seat].score(category).unwrap());
+        raw.push(json);
+    }
+
+    let json = observer.next().await;
+    let over = event(&json);
+    assert_eq!(over["event"], "game_over");
+    let totals: Vec<u16> = game.scorecards.iter().map(|s| s.grand_total()).collect();
+    assert_eq!(over["scores"], serde_json::json!(totals));
+    raw.push(json);
+
+    for json in raw {
+        assert!(!json.contains("alice") && !json.contains("bob"), "{}", json);
+        for player in &game.players {
+            assert!(!json.contains(&player.player_id.to_string()), "{}", json);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_dice_stats_count_every_roll() {
+    let config = test_config();
+    let bus = config.events.clone();
+    let server = TestServer::start_with(config).await;
+    let mut game = TestGame::start(&server, &["alice", "bob"]).await;
+    // Each turn rolls all five dice once
+    for _ in 0..4 {
+        game.play_turn().await;
+    }
+
+    let alice = &mut game.players[0];
+    alice.send(ClientMessage::GetDiceStats).await;
+    let (game_dice, room_dice) = expect!(alice, ServerMessage::DiceStats { game, room } => (game, room));
+    assert_eq!(game_dice.total(), 20);
+    assert_eq!(room_dice, game_dice);
+
+    let mut eve = server.connect("eve").await;
+    eve.send(ClientMessage::SpectateRoom {
+        room_id: game.room_id,
+        catch_up: false,
+    })
+    .await;
+    expect!(eve, ServerMessage::RoomJoined { .. });
+    expect!(eve, ServerMessage::GameState { .. });
+    eve.send(ClientMessage::GetDiceStats).await;
+    expect!(eve, ServerMessage::DiceStats { game, .. } if game == game_dice);
+
+    let body: serde_json::Value = serde_json::from_str(&http_get(&bus, "/dice").await).unwrap();
+    let rooms = body["rooms"].as_array().unwrap();
+    assert_eq!(rooms.len(), 1);
+    assert_eq!(rooms[0]["room_id"], game.room_id.to_string());
+    assert_eq!(rooms[0]["rolled"], 20);
+    assert_eq!(rooms[0]["faces"], serde_json::json!(game_dice.0));
+}
+
+#[tokio::test]
+async fn test_out_of_sync_client_is_sent_the_game_state() {
+    let server = TestServer::start().await;
+    let mut game = TestGame::start(&server, &["alice", "bob"]).await;
+    let (round, turn_player) = match game.play_turn().await {
+        ServerMessage::TurnStarted { player_id, turn_number, .. } => (turn_number, player_id),
+        other => panic!("expected TurnStarted, got {:?}", other),
+    };
+
+    // A view that missed the score is set straight
+    let alice = &mut game.players[0];
+    alice
+        .send(ClientMessage::AckTurn {
+            round,
+            player_id: turn_player,
+            checksum: 0,
+        })
+        .await;
+    let state = expect!(alice, ServerMessage::GameState { game_state } => game_state);
+    assert_eq!(state.round, round);
+
+    // One that agrees, or is about another turn, gets no answer
+    alice
+        .send(ClientMessage::AckTurn {
+            round,
+            player_id: turn_player,
+            checksum: state.checksum(),
+        })
+        .await;
+    alice
+        .send(ClientMessage::AckTurn {
+            round: round + 1,
+            player_id: turn_player,
+            checksum: 0,
+        })
+        .await;
+    alice.expect_quiet().await;
+}
+
+#[tokio::test]
+async fn test_room_scoreboard_served_over_http() {
+    let config = test_config();
+    let bus = config.events.clone();
+    let server = TestServer::start_with(config).await;
+    let mut game = TestGame::start(&server, &["alice", "bob"]).await;
+    game.play_turn().await;
+
+    let path = format!("/rooms/{}", game.room_id);
+    let body: serde_json::Value = serde_json::from_str(&http_get(&bus, &path).await).unwrap();
+    assert_eq!(body["room_id"], game.room_id.to_string());
+    assert_eq!(body["state"], "InGame");
+    assert_eq!(body["round"], 1);
+    assert_eq!(body["current_seat"], game.current);
+    let players = body["players"].as_array().unwrap();
+    let names: Vec<&str> = players.iter().map(|p| p["name"].as_str().unwrap()).collect();
+    assert_eq!(names, ["alice", "bob"]);
+    for (player, scorecard) in players.iter().zip(&game.scorecards) {
+        assert_eq!(player["total"], scorecard.grand_total());
+    }
+    for player in &game.players {
+        assert!(!body.to_string().contains(&player.player_id.to_string()));
+    }
+
+    // A room closed to spectators has no scoreboard
+    let mut carol = server.connect("carol").await;
+    let room_id = carol.create_room("private", 2).await;
+    let path = format!("/rooms/{}", room_id);
+    let body: serde_json::Value = serde_json::from_str(&http_get(&bus, &path).await).unwrap();
+    assert_eq!(body["state"], "WaitingForPlayers");
+    assert_eq!(body["players"][0]["name"], "carol");
+    carol
+        .send(ClientMessage::UpdateRoomSettings {
+            max_players: None,
+            password: None,
+            rules: None,
+            locked: None,
+            auto_start: None,
+            max_spectators: Some(0),
+            seed: None,
+            announcements: None,
+            shuffle_order: None,
+            nudge_secs: None,
+        })
+        .await;
+    expect!(carol, ServerMessage::RoomUpdate { .. });
+    let (head, _) = http_request(&bus, &path).await;
+    assert!(head.starts_with("HTTP/1.1 404"), "{}", head);
+    let (head, _) = http_request(&bus, "/rooms/lobby").await;
+    assert!(head.starts_with("HTTP/1.1 404"), "{}", head);
+}
+
+#[tokio::test]
+async fn test_timed_turns_send_their_clock() {
+    let clock = Clock::virtual_at(1_700_000_000);
+    let server = TestServer::start_with(ServerConfig {
+        clock: clock.clone(),
+        ..test_config()
+    })
+    .await;
+    let mut alice = server.connect("alice").await;
+    let mut bob = server.connect("bob").await;
+    let rules = GameConfig {
+        turn_seconds: Some(30),
+        ..GameConfig::default()
+    };
+    let room_id = alice.create_room_with("timed", 2, rules).await;
+    bob.join_room(room_id).await;
+    expect!(alice, ServerMessage::PlayerJoined { .. });
+
+    alice.send(ClientMessage::StartGame).await;
+    let mut first = None;
+    for player in [&mut alice, &mut bob] {
+        let state = expect!(player, ServerMessage::GameStarted { game_state } => game_state);
+        first = Some(state.players[state.current_player_index].id);
+        expect!(
+            player,
+            ServerMessage::TurnTimer { player_id, seconds_left: 30 } if Some(player_id) == first
+        );
+    }
+
+    let (current, waiting) = if first == Some(alice.player_id) {
+        (&mut alice, &mut bob)
+    } else {
+        (&mut bob, &mut alice)
+    };
+    clock.advance(12);
+    current.send(ClientMessage::RollDice).await;
+    current
+        .send(ClientMessage::ScoreCategory {
+            category: yaht_common::scoring::Category::Chance,
+        })
+        .await;
+    let next = waiting.player_id;
+    for player in [current, waiting] {
+        expect!(player, ServerMessage::DiceRolled { .. });
+        expect!(player, ServerMessage::CategoryScored { .. });
+        expect!(player, ServerMessage::TurnEnded { .. });
+        expect!(player, ServerMessage::TurnStarted { player_id, .. } if player_id == next);
+        // The new turn gets the full time, whatever the last one used
+        expect!(player, ServerMessage::TurnTimer { player_id, seconds_left: 30 } if player_id == next);
+    }
+}
+
+#[tokio::test]
+async fn test_turn_timer_running_out_plays_the_turn() {
+    let clock = Clock::virtual_at(1_700_000_000);
+    let server = TestServer::start_with(ServerConfig {
+        clock: clock.clone(),
+        ..test_config()
+    })
+    .await;
+    let rules = GameConfig {
+        turn_seconds: Some(30),
+        ..GameConfig::default()
+    };
+    let mut game = TestGame::start_with(&server, &["alice", "bob"], rules).await;
+    let idle = game.players[game.current].player_id;
+    let timed_out = format!("{}'s turn timed out", game.players[game.current].name);
+
+    clock.advance(31);
+    for player in &mut game.players {
+        let notice = player.recv_until(|m| matches!(m, ServerMessage::SystemMessage { .. })).await;
+        assert!(matches!(notice, ServerMessage::SystemMessage { message, .. } if message == timed_out));
+        expect!(player, ServerMessage::DiceRolled { .. });
+        expect!(player, ServerMessage::CategoryScored { player_id, .. } if player_id == idle);
+        expect!(player, ServerMessage::TurnEnded { player_id } if player_id == idle);
+        expect!(player, ServerMessage::TurnStarted { player_id, .. } if player_id != idle);
+        expect!(player, ServerMessage::TurnTimer { seconds_left: 30, .. });
+    }
+}
+
+#[tokio::test]
+async fn test_idle_players_are_nudged() {
+    let clock = Clock::virtual_at(1_700_000_000);
+    let server = TestServer::start_with(ServerConfig {
+        clock: clock.clone(),
+        ..test_config()
+    })
+    .await;
+    let mut alice = server.connect("alice").await;
+    let mut bob = server.connect("bob").await;
+    let room_id = alice.create_room("nudging", 2).await;
+    bob.join_room(room_id).await;
+    expect!(alice, ServerMessage::PlayerJoined { .. });
+    alice
+        .send(ClientMessage::UpdateRoomSettings {
+            max_players: None,
+            password: None,
+            rules: None,
+            locked: None,
+            auto_start: None,
+            max_spectators: None,
+            seed: None,
+            announcements: None,
+            shuffle_order: None,
+            nudge_secs: Some(20),
+        })
+        .await;
+    for player in [&mut alice, &mut bob] {
+        expect!(player, ServerMessage::RoomUpdate { room_state } if room_state.nudge_secs == 20);
+    }
+
+    alice.send(ClientMessage::StartGame).await;
+    let mut first = None;
+    for player in [&mut alice, &mut bob] {
+        let state = expect!(player, ServerMessage::GameStarted { game_state } => game_state);
+        first = Some(state.players[state.current_player_index].id);
+    }
+    let (current, waiting) = if first == Some(alice.player_id) {
+        (&mut alice, &mut bob)
+    } else {
+        (&mut bob, &mut alice)
+    };
+
+    // Nudges are checked every second
+    let check = Duration::from_millis(1100);
+    clock.advance(19);
+    tokio::time::sleep(check).await;
+    current.expect_quiet().await;
+    clock.advance(2);
+    expect!(current, ServerMessage::SystemMessage { message, milestone: None } if message.contains("your turn"));
+    let name = current.name.clone();
+    expect!(waiting, ServerMessage::SystemMessage { message, .. } if message == format!("Waiting on {}…", name));
+    // Once per stretch of idling
+    clock.advance(30);
+    tokio::time::sleep(check).await;
+    current.expect_quiet().await;
+
+    // A roll starts the idle clock over
+    current.send(ClientMessage::RollDice).await;
+    expect!(current, ServerMessage::DiceRolled { .. });
+    expect!(waiting, ServerMessage::DiceRolled { .. });
+    clock.advance(21);
+    expect!(current, ServerMessage::SystemMessage { message, .. } if message.contains("your turn"));
+}
@@ -0,0 +1,494 @@
+//! In-process server and scripted protocol clients for end-to-end tests.
+
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use bytes::Bytes;
+use futures::SinkExt;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use yaht_common::directory::ServerListing;
+use yaht_common::game::GameConfig;
+use yaht_common::player::Scorecard;
+use yaht_common::protocol::{self, ClientMessage, ServerMessage, Transport};
+use yaht_server::events::{self, EventBus};
+use yaht_server::persistence::Persistence;
+use yaht_server::ratelimit::{RateLimit, RateLimits};
+use yaht_server::server::{self, ServerConfig};
+
+/// Receive the next message on a client and match it against a pattern,
+/// failing the test with the actual message otherwise.
+macro_rules! expect {
+    ($client:expr, $pat:pat $(if $guard:expr)? => $out:expr) => {
+        match $client.recv().await {
+            $pat $(if $guard)? => $out,
+            other => panic!("{}: expected {}, got {:?}", $client.name, stringify!($pat), other),
+        }
+    };
+    ($client:expr, $pat:pat $(if $guard:expr)?) => {
+        expect!($client, $pat $(if $guard)? => ())
+    };
+}
+
+/// How long a client waits for a message before the test fails.
+const RECV_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A server running on an ephemeral port. Stopped when dropped.
+pub struct TestServer {
+    pub addr: SocketAddr,
+    task: JoinHandle<anyhow::Result<()>>,
+}
+
+impl TestServer {
+    /// A server with default settings, except that rate limits are high
+    /// enough never to trip in a fast scripted game.
+    pub async fn start() -> Self {
+        Self::start_with(test_config()).await
+    }
+
+    pub async fn start_with(config: ServerConfig) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let task = tokio::spawn(server::serve(listener, config, None));
+        Self { addr, task }
+    }
+
+    /// A server keeping ratings and finished games in `data`.
+    pub async fn start_persistent(config: ServerConfig, data: &DataDir) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let persistence = Persistence::open(data.0.clone(), 0).unwrap();
+        let task = tokio::spawn(server::serve(listener, config, Some(persistence)));
+        Self { addr, task }
+    }
+
+    /// Connect and complete the handshake.
+    pub async fn connect(&self, name: &str) -> TestClient {
+        TestClient::connect(self.addr, name).await
+    }
+}
+
+/// Default settings with rate limits out of the way. Tests that change
+/// other settings start from this.
+pub fn test_config() -> ServerConfig {
+    let unlimited = RateLimit::new(10_000, 10_000.0);
+    ServerConfig {
+        rate_limits: RateLimits {
+            gameplay: unlimited,
+            chat: unlimited,
+            lobby: unlimited,
+        },
+        ..ServerConfig::default()
+    }
+}
+
+/// A scratch data directory, removed when dropped.
+pub struct DataDir(pub PathBuf);
+
+impl DataDir {
+    pub fn new() -> Self {
+        Self(std::env::temp_dir().join(format!("yaht-test-{}", Uuid::new_v4())))
+    }
+}
+
+impl Drop for DataDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+impl Drop for TestServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// A client that speaks the raw protocol, one message at a time.
+pub struct TestClient {
+    pub name: String,
+    pub player_id: Uuid,
+    /// Token from `Welcome` for resuming this session.
+    pub resume_token: Option<Uuid>,
+    /// Every message received so far, debug-formatted, for comparing runs.
+    pub transcript: Vec<String>,
+    transport: Transport,
+}
+
+impl TestClient {
+    pub async fn connect(addr: SocketAddr, name: &str) -> Self {
+        Self::handshake(addr, name, None, None).await.0
+    }
+
+    /// Connect with a resume token, returning the client and whether the
+    /// server resumed the earlier session.
+    pub async fn resume(addr: SocketAddr, name: &str, token: Uuid) -> (Self, bool) {
+        Self::handshake(addr, name, None, Some(token)).await
+    }
+
+    /// Connect with a persistent identity, returning the client and whether
+    /// the server resumed a session.
+    pub async fn connect_as(addr: SocketAddr, name: &str, identity: Uuid) -> (Self, bool) {
+        Self::handshake(addr, name, Some(identity), None).await
+    }
+
+    /// Open a connection without sending `Hello`, for tests of the
+    /// handshake itself.
+    pub async fn open(addr: SocketAddr, name: &str) -> Self {
+        let stream = TcpStream::connect(addr).await.unwrap();
+        Self {
+            name: name.to_string(),
+            player_id: Uuid::nil(),
+            resume_token: None,
+            transcript: Vec::new(),
+            transport: protocol::framed_transport(stream),
+        }
+    }
+
+    async fn handshake(
+        addr: SocketAddr,
+        name: &str,
+        identity: Option<Uuid>,
+        resume_token: Option<Uuid>,
+    ) -> (Self, bool) {
+        let mut client = Self::open(addr, name).await;
+        client
+            .send(ClientMessage::Hello {
+                player_name: name.to_string(),
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                identity,
+                resume_token,
+                protocol: protocol::PROTOCOL_VERSION,
+            })
+            .await;
+        let (player_id, token, resumed) = expect!(
+            client,
+            ServerMessage::Welcome { player_id, resume_token, resumed, .. } => (player_id, resume_token, resumed)
+        );
+        client.player_id = player_id;
+        client.resume_token = token;
+        (client, resumed)
+    }
+
+    pub async fn send(&mut self, msg: ClientMessage) {
+        protocol::send_message(&mut self.transport, &msg)
+            .await
+            .unwrap_or_else(|e| panic!("{}: {}", self.name, e));
+    }
+
+    /// Send a frame as-is, e.g. one that is not a valid message.
+    pub async fn send_raw(&mut self, frame: impl Into<Bytes>) {
+        self.transport
+            .send(frame.into())
+            .await
+            .unwrap_or_else(|e| panic!("{}: {}", self.name, e));
+    }
+
+    /// The next message, failing the test if none arrives in time.
+    pub async fn recv(&mut self) -> ServerMessage {
+        match tokio::time::timeout(RECV_TIMEOUT, protocol::recv_message(&mut self.transport)).await {
+            Ok(Ok(Some(msg))) => {
+                self.transcript.push(format!("{:?}", msg));
+                msg
+            }
+            Ok(Ok(None)) => panic!("{}: server closed the connection", self.name),
+            Ok(Err(e)) => panic!("{}: {}", self.name, e),
+            Err(_) => panic!("{}: no message within {:?}", self.name, RECV_TIMEOUT),
+        }
+    }
+
+    /// Skip messages until one matches, returning it.
+    pub async fn recv_until(&mut self, mut matches: impl FnMut(&ServerMessage) -> bool) -> ServerMessage {
+        loop {
+            let msg = self.recv().await;
+            if matches(&msg) {
+                return msg;
+            }
+        }
+    }
+
+    /// The next message that is not a milestone announcement. Which
+    /// milestones come up depends on the dice.
+    pub async fn recv_past_milestones(&mut self) -> ServerMessage {
+        self.recv_until(|m| !matches!(m, ServerMessage::SystemMessage { milestone: Some(_), .. }))
+            .await
+    }
+
+    /// Assert nothing arrives for a short while.
+    pub async fn expect_quiet(&mut self) {
+        let wait = Duration::from_millis(100);
+        if let Ok(msg) = tokio::time::timeout(wait, protocol::recv_message::<ServerMessage>(&mut self.transport)).await {
+            panic!("{}: expected no message, got {:?}", self.name, msg);
+        }
+    }
+
+    /// Assert the server closes the connection without sending anything else.
+    pub async fn expect_closed(&mut self) {
+        match tokio::time::timeout(RECV_TIMEOUT, protocol::recv_message::<ServerMessage>(&mut self.transport)).await {
+            Ok(Ok(None)) | Ok(Err(_)) => {}
+            Ok(Ok(Some(msg))) => panic!("{}: expected the connection to close, got {:?}", self.name, msg),
+            Err(_) => panic!("{}: connection still open after {:?}", self.name, RECV_TIMEOUT),
+        }
+    }
+
+    pub async fn create_room(&mut self, room_name: &str, max_players: u8) -> Uuid {
+        self.create_room_with(room_name, max_players, GameConfig::default()).await
+    }
+
+    pub async fn create_room_with(&mut self, room_name: &str, max_players: u8, rules: GameConfig) -> Uuid {
+        self.send(ClientMessage::CreateRoom {
+            room_name: room_name.to_string(),
+            max_players,
+            password: None,
+            rules,
+        })
+        .await;
+        expect!(self, ServerMessage::RoomJoined { room_id, .. } => room_id)
+    }
+
+    pub async fn join_room(&mut self, room_id: Uuid) {
+        self.send(ClientMessage::JoinRoom {
+            room_id,
+            password: None,
+            spectate_if_started: false,
+        })
+        .await;
+        expect!(self, ServerMessage::RoomJoined { room_id: joined, .. } if joined == room_id => ());
+    }
+}
+
+/// A room of connected players, host first, whose game has been started.
+pub struct TestGame {
+    pub room_id: Uuid,
+    pub players: Vec<TestClient>,
+    pub scorecards: Vec<Scorecard>,
+    /// Index into `players` of whoever's turn it is.
+    pub current: usize,
+}
+
+impl TestGame {
+    /// Create a room for `names`, join everyone and start the game. Every
+    /// player has seen `GameStarted` when this returns.
+    pub async fn start(server: &TestServer, names: &[&str]) -> Self {
+        Self::start_with(server, names, GameConfig::default()).await
+    }
+
+    /// Like `start`, playing under `rules`.
+    pub async fn start_with(server: &TestServer, names: &[&str], rules: GameConfig) -> Self {
+        let mut players = Vec::new();
+        for name in names {
+            players.push(server.connect(name).await);
+        }
+
+        let room_id = players[0]
+            .create_room_with("test room", names.len() as u8, rules)
+            .await;
+        for i in 1..players.len() {
+            players[i].join_room(room_id).await;
+            for earlier in &mut players[..i] {
+                expect!(earlier, ServerMessage::PlayerJoined { .. });
+            }
+        }
+
+        players[0].send(ClientMessage::StartGame).await;
+        let current = first_to_play(&mut players).await;
+
+        let mut scorecard = Scorecard::new();
+        scorecard.rules = rules.scoring;
+        Self {
+            room_id,
+            scorecards: vec![scorecard; names.len()],
+            players,
+            current,
+        }
+    }
+
+    /// Have the host start another game in the same room, once this one
+    /// is over and everyone has had the room's update.
+    pub async fn rematch(&mut self) {
+        self.players[0].send(ClientMessage::StartGame).await;
+        self.current = first_to_play(&mut self.players).await;
+        for card in &mut self.scorecards {
+            let rules = card.rules;
+            *card = Scorecard::new();
+            card.rules = rules;
+        }
+    }
+
+    /// Play the current player's turn: roll once and score the first open
+    /// category. Checks every player sees the same events in order, and
+    /// returns the message that followed `TurnEnded` and any round
+    /// standings.
+    pub async fn play_turn(&mut self) -> ServerMessage {
+        let index = self.current;
+        let player_id = self.players[index].player_id;
+        self.players[index].send(ClientMessage::RollDice).await;
+        let mut rolled = None;
+        for player in &mut self.players {
+            rolled = Some(expect!(player, ServerMessage::DiceRolled { dice, rolls_remaining: 2 } => dice));
+        }
+
+        let dice = rolled.expect("no players").values();
+        let category = self.scorecards[index].scorable_categories(&dice)[0];
+        self.players[index]
+            .send(ClientMessage::ScoreCategory { category })
+            .await;
+
+        let mut next: Option<ServerMessage> = None;
+        for player in &mut self.players {
+            let score = expect!(
+                player,
+                ServerMessage::CategoryScored { player_id: p, category: c, score }
+                    if p == player_id && c == category => score
+            );
+            if player.player_id == player_id {
+                self.scorecards[index].record(category, score).unwrap();
+            }
+            match player.recv_past_milestones().await {
+                ServerMessage::TurnEnded { player_id: p } if p == player_id => {}
+                other => panic!("{}: expected TurnEnded, got {:?}", player.name, other),
+            }
+            let mut msg = player.recv_past_milestones().await;
+            // Every round but the last ends with everyone's totals
+            if matches!(msg, ServerMessage::RoundStandings { .. }) {
+                msg = player.recv_past_milestones().await;
+            }
+            if let Some(seen) = &next {
+                assert_eq!(format!("{:?}", seen), format!("{:?}", msg));
+            }
+            next = Some(msg);
+        }
+
+        let next = next.unwrap();
+        if let ServerMessage::TurnStarted { player_id, .. } = &next {
+            self.current = self
+                .players
+                .iter()
+                .position(|p| p.player_id == *player_id)
+                .unwrap();
+        }
+        next
+    }
+}
+
+/// Wait for every player to see `GameStarted`, returning the index of
+/// whoever goes first.
+async fn first_to_play(players: &mut [TestClient]) -> usize {
+    let mut first = None;
+    for player in players.iter_mut() {
+        // Servers with persistence send head-to-head records first
+        let state = match player
+            .recv_until(|m| matches!(m, ServerMessage::GameStarted { .. }))
+            .await
+        {
+            ServerMessage::GameStarted { game_state } => game_state,
+            _ => unreachable!(),
+        };
+        first = Some(state.players[state.current_player_index].id);
+    }
+    players.iter().position(|p| Some(p.player_id) == first).unwrap()
+}
+
+/// An HTTP client reading a server's event stream.
+pub struct EventObserver {
+    stream: BufReader<TcpStream>,
+}
+
+impl EventObserver {
+    /// Serve `bus` on an ephemeral port and subscribe to it. Events
+    /// published after this returns are all received.
+    pub async fn connect(bus: &EventBus) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(events::serve(listener, bus.clone()));
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"GET /events HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        let mut stream = BufReader::new(stream);
+        let status = Self::read_line(&mut stream).await;
+        assert!(status.starts_with("HTTP/1.1 200"), "unexpected response: {}", status);
+        while !Self::read_line(&mut stream).await.trim_end().is_empty() {}
+        Self { stream }
+    }
+
+    /// The JSON of the next event.
+    pub async fn next(&mut self) -> String {
+        loop {
+            let line = Self::read_line(&mut self.stream).await;
+            if let Some(data) = line.strip_prefix("data: ") {
+                return data.trim_end().to_string();
+            }
+        }
+    }
+
+    async fn read_line(stream: &mut BufReader<TcpStream>) -> String {
+        let mut line = String::new();
+        tokio::time::timeout(RECV_TIMEOUT, stream.read_line(&mut line))
+            .await
+            .expect("timed out waiting for an event")
+            .unwrap();
+        line
+    }
+}
+
+/// Serve `bus` on an ephemeral port and return the body of a GET for `path`.
+pub async fn http_get(bus: &EventBus, path: &str) -> String {
+    let (head, body) = http_request(bus, path).await;
+    assert!(head.starts_with("HTTP/1.1 200"), "unexpected response: {}", head);
+    body
+}
+
+/// The status line and headers, and the body, of a `GET` to the bus's
+/// HTTP listener.
+pub async fn http_request(bus: &EventBus, path: &str) -> (String, String) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(events::serve(listener, bus.clone()));
+
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    let request = format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path);
+    stream.write_all(request.as_bytes()).await.unwrap();
+    let mut response = String::new();
+    tokio::time::timeout(RECV_TIMEOUT, stream.read_to_string(&mut response))
+        .await
+        .expect("timed out waiting for a response")
+        .unwrap();
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    (head.to_string(), body.to_string())
+}
+
+/// Answer one request made to a fake server directory, returning the
+/// listing it carried.
+pub async fn recv_heartbeat(directory: &TcpListener) -> ServerListing {
+    let exchange = async {
+        let (stream, _) = directory.accept().await.unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).await.unwrap();
+        assert!(request_line.starts_with("POST /servers "), "unexpected request: {}", request_line);
+        let mut length = 0;
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header).await.unwrap();
+            if header == "\r\n" {
+                break;
+            }
+            if let Some(value) = header.to_ascii_lowercase().strip_prefix("content-length:") {
+                length = value.trim().parse().unwrap();
+            }
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).await.unwrap();
+        reader.write_all(b"HTTP/1.0 204 No Content\r\n\r\n").await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    };
+    tokio::time::timeout(RECV_TIMEOUT, exchange)
+        .await
+        .expect("timed out waiting for a heartbeat")
+}